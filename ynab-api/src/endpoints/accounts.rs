@@ -1,5 +1,6 @@
 use super::{BudgetId, LastKnowledgeOfServer, LastKnowledgeQuery, Milliunits};
 use crate::macros::setter;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use tower_api_client::{Request, RequestData};
@@ -30,6 +31,9 @@ pub struct Account {
     pub direct_import_linked: bool,
     /// If an account linked to a financial institution (direct_import_linked=true) and the linked connection is not in a healthy state, this will be true.
     pub direct_import_in_error: bool,
+    /// When the account was last reconciled, if ever
+    #[serde(default)]
+    pub last_reconciled_at: Option<DateTime<Utc>>,
     /// Whether or not the account has been deleted. Deleted accounts will only be included in delta requests.
     pub deleted: bool,
 }
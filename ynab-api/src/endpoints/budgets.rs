@@ -48,6 +48,29 @@ impl Request for ListBudgets {
     }
 }
 
+/// The budget's date and currency formats
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct GetBudgetSettings {
+    budget_id: BudgetId,
+}
+
+impl GetBudgetSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setter!(budget_id: BudgetId);
+}
+
+impl Request for GetBudgetSettings {
+    type Data = ();
+    type Response = BudgetSettingsResponse;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!("/budgets/{}/settings", self.budget_id).into()
+    }
+}
+
 // Responses
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,3 +83,19 @@ pub struct BudgetsData {
     pub budgets: Vec<BudgetSummary>,
     pub default_budget: Option<BudgetSummary>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetSettingsResponse {
+    pub data: BudgetSettingsData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetSettingsData {
+    pub settings: BudgetSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetSettings {
+    pub date_format: Option<DateFormat>,
+    pub currency_format: Option<CurrencyFormat>,
+}
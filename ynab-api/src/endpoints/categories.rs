@@ -1,6 +1,6 @@
 use super::{BudgetId, LastKnowledgeOfServer, LastKnowledgeQuery};
 use crate::{endpoints::Milliunits, macros::setter};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use tower_api_client::{Method, Request, RequestData};
@@ -81,6 +81,43 @@ impl Request for ListCategories {
     }
 }
 
+/// A category as it stood in one plan month: what was assigned, spent and left
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct GetMonthCategory {
+    budget_id: BudgetId,
+    month: String,
+    category_id: Uuid,
+}
+
+impl GetMonthCategory {
+    pub fn new(category_id: Uuid) -> Self {
+        Self {
+            category_id,
+            ..Self::default()
+        }
+    }
+
+    setter!(budget_id: BudgetId);
+
+    pub fn month(mut self, month: impl Into<String>) -> Self {
+        self.month = month.into();
+        self
+    }
+}
+
+impl Request for GetMonthCategory {
+    type Data = ();
+    type Response = GetCategoryResponse;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!(
+            "/budgets/{}/months/{}/categories/{}",
+            self.budget_id, self.month, self.category_id
+        )
+        .into()
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct UpdateMonthCategory {
     #[serde(skip)]
@@ -128,6 +165,45 @@ impl Request for UpdateMonthCategory {
     }
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCategory {
+    #[serde(skip)]
+    budget_id: BudgetId,
+    #[serde(skip)]
+    category_id: Uuid,
+    category: SaveCategory,
+}
+
+impl UpdateCategory {
+    pub fn new(category_id: Uuid, category: SaveCategory) -> Self {
+        Self {
+            budget_id: BudgetId::default(),
+            category_id,
+            category,
+        }
+    }
+
+    setter!(budget_id: BudgetId);
+}
+
+impl Request for UpdateCategory {
+    type Data = Self;
+    type Response = SaveCategoryResponse;
+    const METHOD: Method = Method::PATCH;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!(
+            "/budgets/{}/categories/{}",
+            self.budget_id, self.category_id
+        )
+        .into()
+    }
+
+    fn data(&self) -> RequestData<&Self::Data> {
+        RequestData::Json(self)
+    }
+}
+
 // Responses
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,6 +217,16 @@ pub struct CategoriesData {
     pub server_knowledge: Option<LastKnowledgeOfServer>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetCategoryResponse {
+    pub data: CategoryData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryData {
+    pub category: Category,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveMonthCategoryRequest {
     pub category: SaveMonthCategory,
@@ -151,6 +237,24 @@ pub struct SaveMonthCategory {
     pub budgeted: Milliunits,
 }
 
+/// Fields to change on a category; unset fields are left as they are
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SaveCategory {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Move the category into this group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_group_id: Option<Uuid>,
+    /// Only takes effect on a category that already has a goal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_target: Option<Milliunits>,
+    /// `Some(None)` removes the goal's target date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal_target_date: Option<Option<NaiveDate>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveCategoryResponse {
     pub data: SaveCategoryData,
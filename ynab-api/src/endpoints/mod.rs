@@ -3,7 +3,9 @@ pub mod budgets;
 pub mod categories;
 pub mod months;
 pub mod payees;
+pub mod scheduled_transactions;
 pub mod transactions;
+pub mod user;
 
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
@@ -184,6 +186,190 @@ impl std::fmt::Display for Milliunits {
     }
 }
 
+/// Why text couldn't be read as an amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMilliunitsError {
+    Empty,
+    Invalid,
+    TooLarge,
+}
+
+impl std::fmt::Display for ParseMilliunitsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("amount is empty"),
+            Self::Invalid => f.write_str("not a valid amount"),
+            Self::TooLarge => f.write_str("amount is too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseMilliunitsError {}
+
+/// A plain decimal amount in currency units, e.g. `-12.5` or `1234.567`
+impl FromStr for Milliunits {
+    type Err = ParseMilliunitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, number) = split_sign(s.trim());
+        parse_units(number, ".", negative)
+    }
+}
+
+impl Milliunits {
+    /// Parse an amount written in `format`, e.g. `-$1,234.50` or
+    /// `1.234,50€`. The symbol and group separators are optional, and the
+    /// minus sign can go before or after the symbol. Digits past a milliunit
+    /// are rounded half away from zero.
+    pub fn from_str_with_format(
+        s: &str,
+        format: &CurrencyFormat,
+    ) -> Result<Self, ParseMilliunitsError> {
+        let (mut negative, mut rest) = split_sign(s.trim());
+        let symbol = format.currency_symbol.as_str();
+        if !symbol.is_empty() {
+            if let Some(stripped) = rest.strip_prefix(symbol) {
+                rest = stripped.trim_start();
+            } else if let Some(stripped) = rest.strip_suffix(symbol) {
+                rest = stripped.trim_end();
+            }
+            if !negative {
+                (negative, rest) = split_sign(rest);
+            }
+        }
+
+        let number = if format.group_separator.is_empty()
+            || format.group_separator == format.decimal_separator
+        {
+            rest.to_string()
+        } else {
+            rest.replace(format.group_separator.as_str(), "")
+        };
+        parse_units(&number, &format.decimal_separator, negative)
+    }
+
+    /// The amount written in `format`, rounded half away from zero to its
+    /// decimal digits: `-$1,234.50`, `1.234,50€`, `¥1,500`. A minus sign is
+    /// left off amounts that round to zero.
+    pub fn format(&self, format: &CurrencyFormat) -> String {
+        let number = self.format_number(
+            format.decimal_digits,
+            &format.decimal_separator,
+            &format.group_separator,
+        );
+        let sign = self.sign(&number);
+        match (format.display_symbol, format.symbol_first) {
+            (false, _) => format!("{}{}", sign, number),
+            (true, true) => format!("{}{}{}", sign, format.currency_symbol, number),
+            (true, false) => format!("{}{}{}", sign, number, format.currency_symbol),
+        }
+    }
+
+    /// The amount as a plain decimal with `decimal_digits` places and no
+    /// grouping, e.g. `-1234.50`; reads back with [`FromStr`]
+    pub fn format_plain(&self, decimal_digits: i32) -> String {
+        let number = self.format_number(decimal_digits, ".", "");
+        format!("{}{}", self.sign(&number), number)
+    }
+
+    fn sign(&self, number: &str) -> &'static str {
+        if self.0 < 0 && number.chars().any(|c| matches!(c, '1'..='9')) {
+            "-"
+        } else {
+            ""
+        }
+    }
+
+    /// The absolute amount, rounded in whole milliunits so there's no float error
+    fn format_number(
+        &self,
+        decimal_digits: i32,
+        decimal_separator: &str,
+        group_separator: &str,
+    ) -> String {
+        let decimal_digits = decimal_digits.clamp(0, 3) as u32;
+        let step = 10_u64.pow(3 - decimal_digits);
+        let units = (self.0.unsigned_abs() + step / 2) / step;
+        let scale = 10_u64.pow(decimal_digits);
+
+        let integer = (units / scale).to_string();
+        let mut grouped = String::new();
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i).is_multiple_of(3) {
+                grouped.push_str(group_separator);
+            }
+            grouped.push(c);
+        }
+
+        if decimal_digits == 0 {
+            grouped
+        } else {
+            format!(
+                "{}{}{:0width$}",
+                grouped,
+                decimal_separator,
+                units % scale,
+                width = decimal_digits as usize
+            )
+        }
+    }
+}
+
+/// A leading `-` or `+`, or a trailing `-`
+fn split_sign(s: &str) -> (bool, &str) {
+    if let Some(rest) = s.strip_prefix('-') {
+        (true, rest.trim_start())
+    } else if let Some(rest) = s.strip_prefix('+') {
+        (false, rest.trim_start())
+    } else if let Some(rest) = s.strip_suffix('-') {
+        (true, rest.trim_end())
+    } else {
+        (false, s)
+    }
+}
+
+/// Unsigned digits with an optional fraction after `decimal_separator`
+fn parse_units(
+    number: &str,
+    decimal_separator: &str,
+    negative: bool,
+) -> Result<Milliunits, ParseMilliunitsError> {
+    if number.is_empty() {
+        return Err(ParseMilliunitsError::Empty);
+    }
+    let (integer, fraction) = match number.split_once(decimal_separator) {
+        Some((integer, fraction)) if !decimal_separator.is_empty() => (integer, fraction),
+        _ => (number, ""),
+    };
+    let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !all_digits(integer) || !all_digits(fraction)
+    {
+        return Err(ParseMilliunitsError::Invalid);
+    }
+
+    let mut milliunits: i64 = 0;
+    for digit in integer.bytes() {
+        milliunits = milliunits
+            .checked_mul(10)
+            .and_then(|m| m.checked_add(i64::from(digit - b'0')))
+            .ok_or(ParseMilliunitsError::TooLarge)?;
+    }
+    let mut fraction_digits = fraction.bytes().map(|d| i64::from(d - b'0'));
+    for _ in 0..3 {
+        milliunits = milliunits
+            .checked_mul(10)
+            .and_then(|m| m.checked_add(fraction_digits.next().unwrap_or(0)))
+            .ok_or(ParseMilliunitsError::TooLarge)?;
+    }
+    if fraction_digits.next().is_some_and(|d| d >= 5) {
+        milliunits = milliunits
+            .checked_add(1)
+            .ok_or(ParseMilliunitsError::TooLarge)?;
+    }
+
+    Ok(Milliunits(if negative { -milliunits } else { milliunits }))
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DateFormat {
     pub format: String,
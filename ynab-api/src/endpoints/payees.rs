@@ -2,7 +2,7 @@ use super::{BudgetId, LastKnowledgeOfServer, LastKnowledgeQuery};
 use crate::macros::setter;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use tower_api_client::{Request, RequestData};
+use tower_api_client::{Method, Request, RequestData};
 use uuid::Uuid;
 
 // Common
@@ -15,6 +15,17 @@ pub struct Payee {
     pub deleted: bool,
 }
 
+/// Where a transaction with a payee was entered, as recorded by YNAB's
+/// mobile apps. Coordinates are decimal degrees, sent as strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayeeLocation {
+    pub id: Uuid,
+    pub payee_id: Uuid,
+    pub latitude: String,
+    pub longitude: String,
+    pub deleted: bool,
+}
+
 // Requests
 
 #[derive(Default, Debug, Clone, Serialize)]
@@ -54,6 +65,71 @@ impl Request for ListPayees {
     }
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdatePayee {
+    #[serde(skip)]
+    budget_id: BudgetId,
+    #[serde(skip)]
+    payee_id: Uuid,
+    payee: SavePayee,
+}
+
+impl UpdatePayee {
+    pub fn new(payee_id: Uuid, name: impl Into<String>) -> Self {
+        Self {
+            budget_id: BudgetId::default(),
+            payee_id,
+            payee: SavePayee { name: name.into() },
+        }
+    }
+
+    setter!(budget_id: BudgetId);
+}
+
+impl Request for UpdatePayee {
+    type Data = Self;
+    type Response = SavePayeeResponse;
+    const METHOD: Method = Method::PATCH;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!("/budgets/{}/payees/{}", self.budget_id, self.payee_id).into()
+    }
+
+    fn data(&self) -> RequestData<&Self::Data> {
+        RequestData::Json(self)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ListPayeeLocations {
+    budget_id: BudgetId,
+    payee_id: Uuid,
+}
+
+impl ListPayeeLocations {
+    pub fn new(payee_id: Uuid) -> Self {
+        Self {
+            budget_id: BudgetId::default(),
+            payee_id,
+        }
+    }
+
+    setter!(budget_id: BudgetId);
+}
+
+impl Request for ListPayeeLocations {
+    type Data = ();
+    type Response = PayeeLocationsResponse;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!(
+            "/budgets/{}/payees/{}/payee_locations",
+            self.budget_id, self.payee_id
+        )
+        .into()
+    }
+}
+
 // Responses
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,3 +142,29 @@ pub struct PayeesData {
     pub payees: Vec<Payee>,
     pub server_knowledge: Option<LastKnowledgeOfServer>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavePayee {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavePayeeResponse {
+    pub data: SavePayeeData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavePayeeData {
+    pub payee: Payee,
+    pub server_knowledge: Option<LastKnowledgeOfServer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayeeLocationsResponse {
+    pub data: PayeeLocationsData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayeeLocationsData {
+    pub payee_locations: Vec<PayeeLocation>,
+}
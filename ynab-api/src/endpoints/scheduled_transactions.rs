@@ -0,0 +1,116 @@
+use super::{
+    BudgetId, LastKnowledgeOfServer, LastKnowledgeQuery, Milliunits, transactions::FlagColor,
+};
+use crate::macros::setter;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use tower_api_client::{Request, RequestData};
+use uuid::Uuid;
+
+// Common
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScheduledTransaction {
+    pub id: Uuid,
+    pub date_first: NaiveDate,
+    pub date_next: NaiveDate,
+    pub frequency: Frequency,
+    pub amount: Milliunits,
+    pub memo: Option<String>,
+    pub flag_color: Option<FlagColor>,
+    pub account_id: Uuid,
+    pub payee_id: Option<Uuid>,
+    pub category_id: Option<Uuid>,
+    pub transfer_account_id: Option<Uuid>,
+    pub deleted: bool,
+    pub account_name: String,
+    pub payee_name: Option<String>,
+    pub category_name: Option<String>,
+    pub subtransactions: Vec<ScheduledSubTransaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScheduledSubTransaction {
+    pub id: Uuid,
+    pub scheduled_transaction_id: Uuid,
+    pub amount: Milliunits,
+    pub memo: Option<String>,
+    pub payee_id: Option<Uuid>,
+    pub category_id: Option<Uuid>,
+    pub transfer_account_id: Option<Uuid>,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Frequency {
+    Never,
+    Daily,
+    Weekly,
+    EveryOtherWeek,
+    TwiceAMonth,
+    #[serde(rename = "every4Weeks")]
+    Every4Weeks,
+    Monthly,
+    EveryOtherMonth,
+    #[serde(rename = "every3Months")]
+    Every3Months,
+    #[serde(rename = "every4Months")]
+    Every4Months,
+    TwiceAYear,
+    Yearly,
+    EveryOtherYear,
+}
+
+// Requests
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct ListScheduledTransactions {
+    budget_id: BudgetId,
+    #[serde(skip)]
+    last_knowledge_query: Option<LastKnowledgeQuery>,
+}
+
+impl ListScheduledTransactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setter!(budget_id: BudgetId);
+
+    pub fn last_knowledge_of_server(mut self, value: LastKnowledgeOfServer) -> Self {
+        self.last_knowledge_query = Some(LastKnowledgeQuery::from(&value));
+        self
+    }
+}
+
+impl Request for ListScheduledTransactions {
+    type Data = LastKnowledgeQuery;
+    type Response = ScheduledTransactionsResponse;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!("/budgets/{}/scheduled_transactions", self.budget_id).into()
+    }
+
+    fn data(&self) -> RequestData<&Self::Data> {
+        if let Some(ref query) = self.last_knowledge_query {
+            RequestData::Query(query)
+        } else {
+            RequestData::Empty
+        }
+    }
+}
+
+// Responses
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTransactionsResponse {
+    pub data: ScheduledTransactionsData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTransactionsData {
+    pub scheduled_transactions: Vec<ScheduledTransaction>,
+    pub server_knowledge: Option<LastKnowledgeOfServer>,
+}
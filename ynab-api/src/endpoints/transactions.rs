@@ -1,4 +1,4 @@
-use super::{BudgetId, LastKnowledgeOfServer, LastKnowledgeQuery, Milliunits, TransactionId};
+use super::{BudgetId, LastKnowledgeOfServer, Milliunits, TransactionId};
 use crate::macros::setter;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
@@ -59,7 +59,26 @@ pub struct ListTransactions {
     budget_id: BudgetId,
     account_id: Uuid,
     #[serde(skip)]
-    last_knowledge_query: Option<LastKnowledgeQuery>,
+    query: ListTransactionsQuery,
+}
+
+/// Query parameters for [`ListTransactions`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListTransactionsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_knowledge_of_server: Option<i64>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<TransactionType>,
+}
+
+/// Server-side filter for transaction lists
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionType {
+    Unapproved,
+    Uncategorized,
 }
 
 impl ListTransactions {
@@ -67,20 +86,26 @@ impl ListTransactions {
         Self {
             account_id,
             budget_id: BudgetId::default(),
-            last_knowledge_query: None,
+            query: ListTransactionsQuery::default(),
         }
     }
 
     setter!(budget_id: BudgetId);
 
     pub fn last_knowledge_of_server(mut self, value: LastKnowledgeOfServer) -> Self {
-        self.last_knowledge_query = Some(LastKnowledgeQuery::from(&value));
+        self.query.last_knowledge_of_server = Some(value.into());
+        self
+    }
+
+    /// Only return transactions on or after this date
+    pub fn since_date(mut self, value: NaiveDate) -> Self {
+        self.query.since_date = Some(value);
         self
     }
 }
 
 impl Request for ListTransactions {
-    type Data = LastKnowledgeQuery;
+    type Data = ListTransactionsQuery;
     type Response = TransactionsResponse;
 
     fn endpoint(&self) -> Cow<'_, str> {
@@ -92,8 +117,53 @@ impl Request for ListTransactions {
     }
 
     fn data(&self) -> RequestData<&Self::Data> {
-        if let Some(ref query) = self.last_knowledge_query {
-            RequestData::Query(query)
+        if self.query.since_date.is_some() || self.query.last_knowledge_of_server.is_some() {
+            RequestData::Query(&self.query)
+        } else {
+            RequestData::Empty
+        }
+    }
+}
+
+/// Transactions across every account in a budget
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct ListBudgetTransactions {
+    budget_id: BudgetId,
+    #[serde(skip)]
+    query: ListTransactionsQuery,
+}
+
+impl ListBudgetTransactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setter!(budget_id: BudgetId);
+
+    /// Only return transactions on or after this date
+    pub fn since_date(mut self, value: NaiveDate) -> Self {
+        self.query.since_date = Some(value);
+        self
+    }
+
+    /// Only return unapproved or uncategorized transactions
+    pub fn transaction_type(mut self, value: TransactionType) -> Self {
+        self.query.transaction_type = Some(value);
+        self
+    }
+}
+
+impl Request for ListBudgetTransactions {
+    type Data = ListTransactionsQuery;
+    type Response = TransactionsResponse;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!("/budgets/{}/transactions", self.budget_id).into()
+    }
+
+    fn data(&self) -> RequestData<&Self::Data> {
+        if self.query.since_date.is_some() || self.query.transaction_type.is_some() {
+            RequestData::Query(&self.query)
         } else {
             RequestData::Empty
         }
@@ -128,6 +198,7 @@ impl CreateTransaction {
     setter!(opt transaction.approved: bool);
     setter!(opt transaction.flag_color: FlagColor);
     setter!(opt transaction.subtransactions: Vec<NewSubTransaction>);
+    setter!(opt transaction.import_id: String);
 }
 
 impl Request for CreateTransaction {
@@ -165,6 +236,9 @@ pub struct NewTransaction {
     pub flag_color: Option<FlagColor>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtransactions: Option<Vec<NewSubTransaction>>,
+    /// Lets YNAB reject duplicates when the same data is imported twice
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import_id: Option<String>,
 }
 
 impl NewTransaction {
@@ -184,6 +258,7 @@ impl NewTransaction {
             approved: None,
             flag_color: None,
             subtransactions: None,
+            import_id: None,
         }
     }
 }
@@ -271,7 +346,60 @@ pub struct CreateTransactionResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateTransactionData {
-    pub transaction: Transaction,
+    /// `None` when the import id was a duplicate and nothing was created
+    #[serde(default)]
+    pub transaction: Option<Transaction>,
+    /// Import ids that already existed in the account
+    #[serde(default)]
+    pub duplicate_import_ids: Vec<String>,
+    pub server_knowledge: Option<LastKnowledgeOfServer>,
+}
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct CreateTransactions {
+    #[serde(skip)]
+    budget_id: BudgetId,
+    transactions: Vec<NewTransaction>,
+}
+
+impl CreateTransactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setter!(budget_id: BudgetId);
+
+    pub fn transactions(mut self, transactions: Vec<NewTransaction>) -> Self {
+        self.transactions = transactions;
+        self
+    }
+}
+
+impl Request for CreateTransactions {
+    type Data = Self;
+    type Response = CreateTransactionsResponse;
+    const METHOD: Method = Method::POST;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!("/budgets/{}/transactions", self.budget_id).into()
+    }
+
+    fn data(&self) -> RequestData<&Self::Data> {
+        RequestData::Json(self)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTransactionsResponse {
+    pub data: CreateTransactionsData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTransactionsData {
+    pub transaction_ids: Vec<String>,
+    /// Import ids that already existed in the budget and were not created again
+    #[serde(default)]
+    pub duplicate_import_ids: Vec<String>,
     pub server_knowledge: Option<LastKnowledgeOfServer>,
 }
 
@@ -391,6 +519,43 @@ impl Request for DeleteTransaction {
     }
 }
 
+/// Ask YNAB to import new transactions from the budget's linked accounts,
+/// as the web app does when it syncs
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct ImportTransactions {
+    #[serde(skip)]
+    budget_id: BudgetId,
+}
+
+impl ImportTransactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    setter!(budget_id: BudgetId);
+}
+
+impl Request for ImportTransactions {
+    type Data = ();
+    type Response = ImportTransactionsResponse;
+    const METHOD: Method = Method::POST;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        format!("/budgets/{}/transactions/import", self.budget_id).into()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportTransactionsResponse {
+    pub data: ImportTransactionsData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportTransactionsData {
+    /// Ids of the transactions that were imported; empty if there were none
+    pub transaction_ids: Vec<TransactionId>,
+}
+
 #[derive(Default, Debug, Clone, Serialize)]
 pub struct BulkUpdateTransactions {
     #[serde(skip)]
@@ -430,6 +595,15 @@ pub struct BulkTransactionUpdate {
     pub id: TransactionId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cleared: Option<ReconciliationStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payee_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approved: Option<bool>,
+    /// `Some(None)` clears the flag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flag_color: Option<Option<FlagColor>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use tower_api_client::Request;
+use uuid::Uuid;
+
+// Common
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: Uuid,
+}
+
+// Requests
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct GetUser;
+
+impl GetUser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Request for GetUser {
+    type Data = ();
+    type Response = UserResponse;
+
+    fn endpoint(&self) -> Cow<'_, str> {
+        "/user".into()
+    }
+}
+
+// Responses
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserResponse {
+    pub data: UserData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserData {
+    pub user: User,
+}
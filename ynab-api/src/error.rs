@@ -1,28 +1,83 @@
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use tower_api_client::{Error as ApiError, StatusCode};
 
 #[derive(Debug)]
 pub enum YnabApiError {
     Ynab(StatusCode, ErrorDetail),
     Internal(ApiError),
+    /// YNAB answered with this status but the body couldn't be read or
+    /// decoded, so the request may well have been applied
+    Unreadable(StatusCode, String),
+    /// No response arrived within the allowed time
+    TimedOut(Duration),
+    /// The request was abandoned before a response arrived
+    Cancelled,
 }
 
 impl From<ApiError> for YnabApiError {
     fn from(value: ApiError) -> Self {
         match value {
             ApiError::ClientError(status, detail) | ApiError::ServerError(status, detail) => {
-                let response: ErrorResponse = serde_json::from_str(&detail).unwrap();
-                YnabApiError::Ynab(status, response.error)
+                YnabApiError::from_response(status, detail.as_bytes())
             }
             e => YnabApiError::Internal(e),
         }
     }
 }
 
+impl YnabApiError {
+    /// The error for a failed response with `status` and `body`. A body that
+    /// isn't one of YNAB's error responses, such as a proxy's HTML error page,
+    /// is kept as it came.
+    pub(crate) fn from_response(status: StatusCode, body: &[u8]) -> Self {
+        match serde_json::from_slice::<ErrorResponse>(body) {
+            Ok(response) => YnabApiError::Ynab(status, response.error),
+            Err(_) => YnabApiError::Unreadable(status, String::from_utf8_lossy(body).into_owned()),
+        }
+    }
+
+    /// An error response with `status`, as YNAB sends them, for simulating failures
+    pub fn from_status(status: u16, name: &str, detail: &str) -> Self {
+        YnabApiError::Ynab(
+            StatusCode::from_u16(status).expect("invalid status code"),
+            ErrorDetail {
+                id: status.to_string(),
+                name: name.to_string(),
+                detail: detail.to_string(),
+            },
+        )
+    }
+
+    /// An answer with `status` whose body couldn't be decoded, for simulating
+    /// a response lost after YNAB applied the request
+    pub fn unreadable(status: u16, detail: &str) -> Self {
+        YnabApiError::Unreadable(
+            StatusCode::from_u16(status).expect("invalid status code"),
+            detail.to_string(),
+        )
+    }
+
+    /// A request that never reached YNAB, for simulating an unreachable server
+    pub fn connection_failed(detail: &str) -> Self {
+        YnabApiError::Internal(ApiError::Io(std::io::Error::new(
+            std::io::ErrorKind::ConnectionRefused,
+            detail,
+        )))
+    }
+}
+
 impl std::fmt::Display for YnabApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             YnabApiError::Internal(e) => write!(f, "Internal error: {}", e),
+            YnabApiError::Unreadable(status, e) => {
+                write!(f, "({}) Unreadable response: {}", status, e)
+            }
+            YnabApiError::TimedOut(after) => {
+                write!(f, "No response after {}s", after.as_secs_f64())
+            }
+            YnabApiError::Cancelled => write!(f, "Request cancelled"),
             YnabApiError::Ynab(status, detail) => {
                 write!(f, "({}) {}: {}", status, detail.name, detail.detail)
             }
@@ -1,34 +1,166 @@
 pub mod endpoints;
 mod error;
 mod macros;
+mod rate_limit;
 pub mod repositories;
 
 pub use crate::error::YnabApiError;
+pub use crate::rate_limit::RateLimit;
 use repositories::*;
-use tower_api_client::{Client as ApiClient, Request as ApiRequest};
+use secrecy::{ExposeSecret, SecretString};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+pub use tower_api_client::Method;
+pub use tower_api_client::Request as ApiRequest;
+use tower_api_client::{Error as ApiError, RequestData, StatusCode};
 
 const BASE_URL: &str = "https://api.ynab.com/v1";
 //const APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
 
+/// A request as it went out and YNAB's answer as it came back, before the
+/// answer is parsed
+#[derive(Debug)]
+pub struct Exchange<'a> {
+    pub method: &'a str,
+    /// Path and query, e.g. `/v1/budgets/<id>/accounts`
+    pub path: &'a str,
+    pub request_body: Option<&'a [u8]>,
+    pub status: u16,
+    pub response_body: &'a [u8],
+}
+
+/// Called with every exchange that got an answer, e.g. to keep it for debugging
+pub type Observer = Arc<dyn Fn(&Exchange<'_>) + Send + Sync>;
+
+/// Client for the YNAB API.
+///
+/// Requests are described with [`ApiRequest`] as before; they're sent with
+/// `reqwest` so the response headers, including YNAB's rate limit, can be read.
 pub struct Client {
-    inner: ApiClient,
+    http: reqwest::Client,
+    base_url: String,
+    access_token: SecretString,
+    /// Quota reported by the most recent response
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    observer: Option<Observer>,
 }
 
 impl Client {
     pub fn new(access_token: &str) -> Self {
+        Self::with_base_url(BASE_URL, access_token)
+    }
+
+    /// A client for another server speaking the same API, such as a mock
+    pub fn with_base_url(base_url: &str, access_token: &str) -> Self {
         Self {
-            inner: ApiClient::new(BASE_URL).bearer_auth(access_token),
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            access_token: access_token.into(),
+            rate_limit: Arc::new(Mutex::new(None)),
+            observer: None,
         }
     }
 
+    /// Show `observer` the raw bodies of every request and response
+    pub fn with_observer(mut self, observer: Observer) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Requests used and allowed in the current hour, as of the last response.
+    /// `None` until a response has carried an `X-Rate-Limit` header.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     pub async fn send<R>(&self, request: R) -> Result<R::Response, YnabApiError>
     where
         R: ApiRequest,
     {
-        self.inner.send(request).await.map_err(From::from)
+        let url = format!("{}/{}", self.base_url, request.endpoint().trim_matches('/'));
+        let method = reqwest::Method::from_bytes(R::METHOD.as_str().as_bytes())
+            .expect("HTTP methods are valid");
+        let mut req = self
+            .http
+            .request(method, url)
+            .bearer_auth(self.access_token.expose_secret());
+        for (name, value) in request.headers().iter() {
+            req = req.header(name.as_str(), value.as_bytes());
+        }
+        req = match request.data() {
+            RequestData::Empty => req,
+            RequestData::Form(data) => req.form(data),
+            RequestData::Json(data) => req.json(data),
+            RequestData::Query(data) => req.query(data),
+        };
+
+        // Built first so the observer sees the body exactly as it's sent
+        let req = req.build().map_err(unreachable)?;
+        let sent = self.observer.is_some().then(|| {
+            let path = match req.url().query() {
+                Some(query) => format!("{}?{}", req.url().path(), query),
+                None => req.url().path().to_string(),
+            };
+            let body = req.body().and_then(|body| body.as_bytes()).map(Vec::from);
+            (path, body)
+        });
+
+        let response = self.http.execute(req).await.map_err(unreachable)?;
+        if let Some(rate_limit) = response
+            .headers()
+            .get(rate_limit::HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(RateLimit::parse)
+        {
+            *self.rate_limit.lock().unwrap_or_else(|e| e.into_inner()) = Some(rate_limit);
+        }
+
+        let status = StatusCode::from_u16(response.status().as_u16())
+            .expect("reqwest only returns valid status codes");
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| YnabApiError::Unreadable(status, e.to_string()))?;
+        if let (Some(observer), Some((path, request_body))) = (&self.observer, &sent) {
+            observer(&Exchange {
+                method: R::METHOD.as_str(),
+                path,
+                request_body: request_body.as_deref(),
+                status: status.as_u16(),
+                response_body: &body,
+            });
+        }
+        if status.is_success() {
+            serde_json::from_slice(&body)
+                .map_err(|e| YnabApiError::Unreadable(status, e.to_string()))
+        } else {
+            Err(YnabApiError::from_response(status, &body))
+        }
+    }
+
+    /// Like [`Client::send`], but gives up as soon as `cancel` is cancelled.
+    /// The in-flight HTTP request is dropped, closing its connection.
+    pub async fn send_with_cancel<R>(
+        &self,
+        request: R,
+        cancel: &CancellationToken,
+    ) -> Result<R::Response, YnabApiError>
+    where
+        R: ApiRequest,
+    {
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => Err(YnabApiError::Cancelled),
+            response = self.send(request) => response,
+        }
     }
 }
 
+/// A request that failed before YNAB answered
+fn unreachable(error: reqwest::Error) -> YnabApiError {
+    YnabApiError::Internal(ApiError::Io(std::io::Error::other(error)))
+}
+
 pub struct Request;
 
 impl Request {
@@ -56,7 +188,15 @@ impl Request {
         PayeeRepository::new()
     }
 
+    pub fn scheduled_transactions() -> ScheduledTransactionRepository {
+        ScheduledTransactionRepository::new()
+    }
+
     pub fn transactions() -> TransactionRepository {
         TransactionRepository::new()
     }
+
+    pub fn user() -> UserRepository {
+        UserRepository::new()
+    }
 }
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+/// Header YNAB reports the rate limit in, e.g. `X-Rate-Limit: 36/200`
+pub(crate) const HEADER: &str = "x-rate-limit";
+
+/// Requests made in YNAB's rolling one-hour window and how many are allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub used: u32,
+    pub limit: u32,
+}
+
+impl RateLimit {
+    /// Parse a `used/limit` header value
+    pub fn parse(value: &str) -> Option<Self> {
+        let (used, limit) = value.trim().split_once('/')?;
+        Some(Self {
+            used: used.trim().parse().ok()?,
+            limit: limit.trim().parse().ok()?,
+        })
+    }
+
+    /// Requests left before YNAB starts answering 429
+    pub fn remaining(&self) -> u32 {
+        self.limit.saturating_sub(self.used)
+    }
+}
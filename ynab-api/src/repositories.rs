@@ -1,15 +1,20 @@
 use crate::endpoints::{
     BudgetId, Milliunits, TransactionId,
     accounts::ListAccounts,
-    budgets::ListBudgets,
-    categories::{ListCategories, UpdateMonthCategory},
+    budgets::{GetBudgetSettings, ListBudgets},
+    categories::{
+        GetMonthCategory, ListCategories, SaveCategory, UpdateCategory, UpdateMonthCategory,
+    },
     months::GetMonth,
-    payees::ListPayees,
+    payees::{ListPayeeLocations, ListPayees, UpdatePayee},
+    scheduled_transactions::ListScheduledTransactions,
     transactions::{
-        BulkUpdateTransactions, CreateTransaction, DeleteTransaction, ListTransactions,
-        UpdateTransaction,
+        BulkUpdateTransactions, CreateTransaction, CreateTransactions, DeleteTransaction,
+        ImportTransactions, ListBudgetTransactions, ListTransactions, UpdateTransaction,
     },
+    user::GetUser,
 };
+use chrono::{Months, NaiveDate};
 use uuid::Uuid;
 
 #[derive(Default)]
@@ -42,6 +47,10 @@ impl BudgetRepository {
     pub fn list(&self) -> ListBudgets {
         ListBudgets::default()
     }
+
+    pub fn settings(&self) -> GetBudgetSettings {
+        GetBudgetSettings::default()
+    }
 }
 
 #[derive(Default)]
@@ -63,6 +72,27 @@ impl CategoryRepository {
         ListCategories::new().budget_id(self.budget_id.clone())
     }
 
+    pub fn get_month(&self, category_id: Uuid, month: impl Into<String>) -> GetMonthCategory {
+        GetMonthCategory::new(category_id)
+            .budget_id(self.budget_id.clone())
+            .month(month)
+    }
+
+    /// Requests for the category in each of the `count` months before
+    /// `month`, oldest first
+    pub fn months_before(
+        &self,
+        category_id: Uuid,
+        month: NaiveDate,
+        count: u32,
+    ) -> Vec<GetMonthCategory> {
+        (1..=count)
+            .rev()
+            .filter_map(|back| month.checked_sub_months(Months::new(back)))
+            .map(|month| self.get_month(category_id, month.format("%Y-%m-%d").to_string()))
+            .collect()
+    }
+
     pub fn update_month(
         &self,
         category_id: Uuid,
@@ -73,6 +103,10 @@ impl CategoryRepository {
             .budget_id(self.budget_id.clone())
             .month(month)
     }
+
+    pub fn update(&self, category_id: Uuid, category: SaveCategory) -> UpdateCategory {
+        UpdateCategory::new(category_id, category).budget_id(self.budget_id.clone())
+    }
 }
 
 pub struct MonthRepository;
@@ -97,6 +131,34 @@ impl PayeeRepository {
     pub fn list(&self) -> ListPayees {
         ListPayees::default()
     }
+
+    pub fn update(&self, payee_id: Uuid, name: impl Into<String>) -> UpdatePayee {
+        UpdatePayee::new(payee_id, name)
+    }
+
+    pub fn locations(&self, payee_id: Uuid) -> ListPayeeLocations {
+        ListPayeeLocations::new(payee_id)
+    }
+}
+
+#[derive(Default)]
+pub struct ScheduledTransactionRepository {
+    budget_id: BudgetId,
+}
+
+impl ScheduledTransactionRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_budget(mut self, budget_id: BudgetId) -> Self {
+        self.budget_id = budget_id;
+        self
+    }
+
+    pub fn list(&self) -> ListScheduledTransactions {
+        ListScheduledTransactions::new().budget_id(self.budget_id.clone())
+    }
 }
 
 #[derive(Default)]
@@ -122,6 +184,10 @@ impl TransactionRepository {
         ListTransactions::new(account_id).budget_id(self.budget_id.clone())
     }
 
+    pub fn list_all(&self) -> ListBudgetTransactions {
+        ListBudgetTransactions::new().budget_id(self.budget_id.clone())
+    }
+
     pub fn create(&self, account_id: Uuid, date: String, amount: i64) -> CreateTransaction {
         CreateTransaction::new(account_id, date, amount).budget_id(self.budget_id.clone())
     }
@@ -133,6 +199,10 @@ impl TransactionRepository {
     pub fn delete(&self, transaction_id: TransactionId) -> DeleteTransaction {
         DeleteTransaction::new(transaction_id).budget_id(self.budget_id.clone())
     }
+
+    pub fn import(&self) -> ImportTransactions {
+        ImportTransactions::new().budget_id(self.budget_id.clone())
+    }
 }
 
 pub struct BulkTransactionRepository;
@@ -142,7 +212,24 @@ impl BulkTransactionRepository {
         Self {}
     }
 
+    pub fn create(&self) -> CreateTransactions {
+        CreateTransactions::new()
+    }
+
     pub fn update(&self) -> BulkUpdateTransactions {
         BulkUpdateTransactions::new()
     }
 }
+
+#[derive(Default)]
+pub struct UserRepository;
+
+impl UserRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn get(&self) -> GetUser {
+        GetUser::new()
+    }
+}
@@ -1,34 +1,82 @@
 mod models;
+mod pinning;
 
 use crate::common::StoredToken;
 pub use models::AuthClientError;
 use models::*;
+use pinning::CertificatePin;
+pub use pinning::CertificateSummary;
 use reqwest::Client;
+use std::sync::Arc;
 use std::time::Duration;
 
 const POLL_INTERVAL_MS: u64 = 2000; // Poll every 2 seconds
 const POLL_TIMEOUT_SECS: u64 = 300; // 5 minute timeout
 
 pub struct ServerAuthClient {
+    /// Sends everything; its connections must present the pinned key
     http_client: Client,
+    /// Only asks for the certificate, so a changed one can be shown to the user
+    probe_client: Client,
+    pin: Arc<CertificatePin>,
     server_url: String,
     device_id: String,
 }
 
 impl ServerAuthClient {
     pub fn new(server_url: String, device_id: String) -> Self {
-        let http_client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .expect("Failed to create HTTP client");
+        let pin = Arc::new(CertificatePin::default());
+        let client = |pin| {
+            Client::builder()
+                .timeout(Duration::from_secs(10))
+                .tls_backend_preconfigured(pinning::tls_config(pin))
+                .tls_info(true)
+                .build()
+                .expect("Failed to create HTTP client")
+        };
 
         Self {
-            http_client,
+            http_client: client(Some(pin.clone())),
+            probe_client: client(None),
+            pin,
             server_url,
             device_id,
         }
     }
 
+    /// Only talk to a server whose certificate has the public key with this
+    /// SHA-256 fingerprint. `None` leaves it to the usual certificate checks.
+    pub fn pin_certificate(&self, public_key: Option<String>) {
+        self.pin.set(public_key);
+    }
+
+    /// Also accept these keys, published by the server's operator ahead of a
+    /// key change
+    pub fn accept_backup_keys(&self, public_keys: Vec<String>) {
+        self.pin.set_backups(public_keys);
+    }
+
+    /// The server's TLS certificate, or `None` over plain HTTP. Nothing but the
+    /// health check is sent, so the certificate can be shown even when it no
+    /// longer matches the pin.
+    pub async fn server_certificate(&self) -> Result<Option<CertificateSummary>, AuthClientError> {
+        let url = format!("{}/health", self.server_url);
+        let resp = self.probe_client.get(&url).send().await?;
+        Ok(resp
+            .extensions()
+            .get::<reqwest::tls::TlsInfo>()
+            .and_then(|info| info.peer_certificate())
+            .map(CertificateSummary::from_der))
+    }
+
+    /// A failed send, told apart when the certificate didn't match the pin
+    fn send_error(&self, error: reqwest::Error) -> AuthClientError {
+        match self.pin.take_rejected() {
+            Some(certificate) => AuthClientError::CertificateChanged { certificate },
+            None => AuthClientError::Http(error),
+        }
+    }
+
     pub async fn initiate_auth(&self) -> Result<(String, String), AuthClientError> {
         let url = format!("{}/auth/initiate", self.server_url);
         let req = InitiateRequest {
@@ -40,10 +88,9 @@ impl ServerAuthClient {
             .post(&url)
             .json(&req)
             .send()
-            .await?
-            .error_for_status()?
-            .json::<InitiateResponse>()
-            .await?;
+            .await
+            .map_err(|e| self.send_error(e))?;
+        let resp = check_status(resp).await?.json::<InitiateResponse>().await?;
 
         Ok((resp.session_id, resp.authorization_url))
     }
@@ -63,10 +110,9 @@ impl ServerAuthClient {
                 .get(&url)
                 .query(&[("device_id", &self.device_id)])
                 .send()
-                .await?
-                .error_for_status()?
-                .json::<PollResponse>()
-                .await?;
+                .await
+                .map_err(|e| self.send_error(e))?;
+            let resp = check_status(resp).await?.json::<PollResponse>().await?;
 
             match resp.status {
                 SessionStatus::Completed => {
@@ -77,6 +123,7 @@ impl ServerAuthClient {
                         access_token: tokens.access_token,
                         refresh_token: tokens.refresh_token,
                         expires_at: tokens.expires_at,
+                        scope: tokens.scope,
                     });
                 }
                 SessionStatus::Error(msg) => {
@@ -96,6 +143,7 @@ impl ServerAuthClient {
         let url = format!("{}/auth/refresh", self.server_url);
         let req = RefreshRequest {
             refresh_token: refresh_token.to_string(),
+            device_id: self.device_id.clone(),
         };
 
         let resp = self
@@ -103,15 +151,49 @@ impl ServerAuthClient {
             .post(&url)
             .json(&req)
             .send()
-            .await?
-            .error_for_status()?
-            .json::<RefreshResponse>()
-            .await?;
+            .await
+            .map_err(|e| self.send_error(e))?;
+        // Refresh tokens are single use; the server revokes them on reuse and
+        // refuses ones it has no record of
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+            let message = resp
+                .json::<ErrorResponse>()
+                .await
+                .map(|e| e.error)
+                .unwrap_or_else(|_| "please authorize again".to_string());
+            return Err(AuthClientError::RefreshTokenRevoked(message));
+        }
+        let resp = check_status(resp).await?.json::<RefreshResponse>().await?;
 
         Ok(StoredToken {
             access_token: resp.access_token,
             refresh_token: resp.refresh_token,
             expires_at: resp.expires_at,
+            scope: resp.scope,
         })
     }
 }
+
+/// Turn an error response into [`AuthClientError::Rejected`], keeping the
+/// server's message and request ID so it can be quoted in a bug report
+async fn check_status(resp: reqwest::Response) -> Result<reqwest::Response, AuthClientError> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+    let request_id = resp
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let message = resp
+        .json::<ErrorResponse>()
+        .await
+        .map(|e| e.error)
+        .unwrap_or_else(|_| status.to_string());
+    Err(AuthClientError::Rejected {
+        status: status.as_u16(),
+        message,
+        request_id,
+    })
+}
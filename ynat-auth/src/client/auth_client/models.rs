@@ -1,6 +1,8 @@
 use chrono::{serde::ts_seconds, DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::CertificateSummary;
+
 // Mirror server models
 #[derive(Debug, Serialize)]
 pub struct InitiateRequest {
@@ -34,11 +36,14 @@ pub struct TokenPair {
     pub refresh_token: String,
     #[serde(with = "ts_seconds")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub scope: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct RefreshRequest {
     pub refresh_token: String,
+    pub device_id: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,6 +52,13 @@ pub struct RefreshResponse {
     pub refresh_token: String,
     #[serde(with = "ts_seconds")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
 }
 
 #[derive(Debug)]
@@ -54,8 +66,21 @@ pub enum AuthClientError {
     Http(reqwest::Error),
     Timeout,
     SessionExpired,
+    /// The server won't refresh this token any more, because it was used
+    /// twice or the server has no record of it; the message says which
+    RefreshTokenRevoked(String),
     OAuthError(String),
     ServerError(String),
+    /// The server presented a key other than the pinned one
+    CertificateChanged {
+        certificate: CertificateSummary,
+    },
+    /// The server answered with an error status
+    Rejected {
+        status: u16,
+        message: String,
+        request_id: Option<String>,
+    },
 }
 
 impl std::fmt::Display for AuthClientError {
@@ -64,8 +89,25 @@ impl std::fmt::Display for AuthClientError {
             Self::Http(e) => write!(f, "HTTP error: {}", e),
             Self::Timeout => write!(f, "Authentication timed out after 5 minutes"),
             Self::SessionExpired => write!(f, "Session expired"),
+            Self::RefreshTokenRevoked(msg) => write!(f, "Refresh token refused: {}", msg),
             Self::OAuthError(msg) => write!(f, "OAuth error: {}", msg),
             Self::ServerError(msg) => write!(f, "Server error: {}", msg),
+            Self::CertificateChanged { certificate } => write!(
+                f,
+                "Auth server key changed to {} and wasn't trusted",
+                certificate.public_key
+            ),
+            Self::Rejected {
+                status,
+                message,
+                request_id,
+            } => {
+                write!(f, "Server error ({}): {}", status, message)?;
+                if let Some(id) = request_id {
+                    write!(f, " [request id: {}]", id)?;
+                }
+                Ok(())
+            }
         }
     }
 }
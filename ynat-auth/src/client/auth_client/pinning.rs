@@ -0,0 +1,369 @@
+//! Public key pinning for the auth server.
+//!
+//! Every connection the auth client opens goes through [`PinningVerifier`]:
+//! the certificate must pass the platform's usual checks and, once a key has
+//! been confirmed, carry that public key or one of the server's published
+//! backup keys. Pinning the key rather than the whole certificate means a
+//! renewal that keeps the key goes unnoticed. Checking in the handshake means
+//! the key the user confirmed is the one the device ID and tokens are sent to.
+
+use chrono::{DateTime, NaiveDate};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use x509_parser::prelude::{FromDer, X509Certificate, X509Name};
+
+/// The keys connections have to match, shared with the verifier
+#[derive(Debug, Default)]
+pub struct CertificatePin {
+    state: Mutex<PinState>,
+}
+
+#[derive(Debug, Default)]
+struct PinState {
+    /// `None` until the user has confirmed a key
+    expected: Option<String>,
+    /// Keys the server operator published for its next certificate
+    backups: Vec<String>,
+    /// The last certificate turned away for not matching
+    rejected: Option<CertificateSummary>,
+}
+
+impl CertificatePin {
+    fn state(&self) -> std::sync::MutexGuard<'_, PinState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(&self, public_key: Option<String>) {
+        let mut state = self.state();
+        state.expected = public_key;
+        state.rejected = None;
+    }
+
+    /// Also accept these keys once one has been confirmed
+    pub fn set_backups(&self, backups: Vec<String>) {
+        self.state().backups = backups;
+    }
+
+    /// The certificate that failed the pin since the last call, if any
+    pub fn take_rejected(&self) -> Option<CertificateSummary> {
+        self.state().rejected.take()
+    }
+
+    fn check(&self, certificate: CertificateSummary) -> Result<(), rustls::Error> {
+        let mut state = self.state();
+        let Some(expected) = &state.expected else {
+            return Ok(());
+        };
+        if *expected == certificate.public_key || state.backups.contains(&certificate.public_key) {
+            return Ok(());
+        }
+        state.rejected = Some(certificate);
+        Err(rustls::Error::General(
+            "auth server key doesn't match the confirmed one".to_string(),
+        ))
+    }
+}
+
+/// What the user is shown about a server's certificate, and what is pinned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateSummary {
+    /// SHA-256 of the certificate's public key, colon-separated uppercase hex
+    pub public_key: String,
+    /// Common name, or organization, of the certificate's issuer
+    pub issuer: Option<String>,
+    pub expires: Option<NaiveDate>,
+}
+
+impl CertificateSummary {
+    /// Read a DER certificate. One that can't be read, or has anything after
+    /// it, is summarized by a hash of all its bytes, which no pinned key matches.
+    pub fn from_der(der: &[u8]) -> Self {
+        match X509Certificate::from_der(der) {
+            Ok(([], certificate)) => Self {
+                public_key: fingerprint(certificate.public_key().raw),
+                issuer: issuer_name(certificate.issuer()),
+                expires: DateTime::from_timestamp(certificate.validity().not_after.timestamp(), 0)
+                    .map(|expires| expires.date_naive()),
+            },
+            _ => Self {
+                public_key: fingerprint(der),
+                issuer: None,
+                expires: None,
+            },
+        }
+    }
+}
+
+/// Runs the normal certificate checks, then compares the server's key with
+/// the pin. A verifier without a pin only observes.
+#[derive(Debug)]
+pub struct PinningVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    pin: Option<Arc<CertificatePin>>,
+}
+
+impl PinningVerifier {
+    pub fn new(inner: Arc<dyn ServerCertVerifier>, pin: Option<Arc<CertificatePin>>) -> Self {
+        Self { inner, pin }
+    }
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        if let Some(pin) = &self.pin {
+            pin.check(CertificateSummary::from_der(end_entity))?;
+        }
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// TLS settings using the platform's trust store, checked against `pin`
+pub fn tls_config(pin: Option<Arc<CertificatePin>>) -> rustls::ClientConfig {
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let platform = rustls_platform_verifier::Verifier::new(provider.clone())
+        .expect("Failed to load the platform certificate verifier");
+    let verifier = PinningVerifier::new(Arc::new(platform), pin);
+
+    rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .expect("Default TLS versions are supported")
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth()
+}
+
+/// Colon-separated uppercase hex SHA-256, as shown by browsers and `openssl`
+fn fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// The issuer's common name, or its organization when it has none
+fn issuer_name(issuer: &X509Name<'_>) -> Option<String> {
+    let attribute = issuer
+        .iter_common_name()
+        .next()
+        .or_else(|| issuer.iter_organization().next())?;
+    attribute.as_str().ok().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{CertificateParams, DistinguishedName, DnType, KeyPair, PublicKeyData};
+
+    /// Accepts every certificate, standing in for the platform checks
+    #[derive(Debug)]
+    struct AcceptAll;
+
+    impl ServerCertVerifier for AcceptAll {
+        fn verify_server_cert(
+            &self,
+            _: &CertificateDer<'_>,
+            _: &[CertificateDer<'_>],
+            _: &ServerName<'_>,
+            _: &[u8],
+            _: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            _: &[u8],
+            _: &CertificateDer<'_>,
+            _: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            Ok(HandshakeSignatureValid::assertion())
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            _: &[u8],
+            _: &CertificateDer<'_>,
+            _: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            Ok(HandshakeSignatureValid::assertion())
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            Vec::new()
+        }
+    }
+
+    fn verify(verifier: &PinningVerifier, certificate: &[u8]) -> bool {
+        let certificate = CertificateDer::from(certificate.to_vec());
+        let server_name = ServerName::try_from("auth.example.com").unwrap();
+        verifier
+            .verify_server_cert(&certificate, &[], &server_name, &[], UnixTime::now())
+            .is_ok()
+    }
+
+    /// A self-signed certificate for `key`, so its issuer is its own subject
+    fn certificate(key: &KeyPair, issuer: &[(DnType, &str)], not_after: (i32, u8, u8)) -> Vec<u8> {
+        let mut params = CertificateParams::new(vec!["auth.example.com".to_string()]).unwrap();
+        params.distinguished_name = DistinguishedName::new();
+        for (kind, value) in issuer {
+            params.distinguished_name.push(kind.clone(), *value);
+        }
+        params.not_before = rcgen::date_time_ymd(2025, 1, 1);
+        params.not_after = rcgen::date_time_ymd(not_after.0, not_after.1, not_after.2);
+        params.self_signed(key).unwrap().der().to_vec()
+    }
+
+    fn issued_by(key: &KeyPair, issuer: &str) -> Vec<u8> {
+        certificate(key, &[(DnType::CommonName, issuer)], (2026, 3, 15))
+    }
+
+    #[test]
+    fn summaries_show_the_key_issuer_and_expiry() {
+        let key = KeyPair::generate().unwrap();
+        let summary = CertificateSummary::from_der(&issued_by(&key, "R11"));
+        assert_eq!(
+            summary,
+            CertificateSummary {
+                public_key: fingerprint(&key.subject_public_key_info()),
+                issuer: Some("R11".to_string()),
+                expires: NaiveDate::from_ymd_opt(2026, 3, 15),
+            }
+        );
+
+        // Without a common name the organization stands in
+        let organization = certificate(
+            &key,
+            &[
+                (DnType::CountryName, "US"),
+                (DnType::OrganizationName, "Let's Encrypt"),
+            ],
+            (2049, 12, 31),
+        );
+        let summary = CertificateSummary::from_der(&organization);
+        assert_eq!(summary.issuer.as_deref(), Some("Let's Encrypt"));
+        assert_eq!(summary.expires, NaiveDate::from_ymd_opt(2049, 12, 31));
+
+        let anonymous = CertificateSummary::from_der(&certificate(&key, &[], (2026, 3, 15)));
+        assert_eq!(anonymous.issuer, None);
+        assert_eq!(anonymous.public_key, summary.public_key);
+    }
+
+    #[test]
+    fn unreadable_certificates_match_no_key() {
+        let key = KeyPair::generate().unwrap();
+        let whole = issued_by(&key, "R11");
+        let public_key = CertificateSummary::from_der(&whole).public_key;
+
+        for broken in [
+            b"garbage".to_vec(),
+            Vec::new(),
+            whole[..whole.len() / 2].to_vec(),
+            whole[..whole.len() - 1].to_vec(),
+            [whole.as_slice(), &[0]].concat(),
+        ] {
+            let summary = CertificateSummary::from_der(&broken);
+            assert_eq!(summary.public_key, fingerprint(&broken));
+            assert_ne!(summary.public_key, public_key);
+            assert_eq!(summary.issuer, None);
+            assert_eq!(summary.expires, None);
+        }
+    }
+
+    #[test]
+    fn only_the_pinned_key_is_accepted() {
+        let pin = Arc::new(CertificatePin::default());
+        let verifier = PinningVerifier::new(Arc::new(AcceptAll), Some(pin.clone()));
+        let key = KeyPair::generate().unwrap();
+        let first = issued_by(&key, "R10");
+
+        // Nothing confirmed yet: the platform checks decide
+        assert!(verify(&verifier, &first));
+
+        pin.set(Some(CertificateSummary::from_der(&first).public_key));
+        assert!(verify(&verifier, &first));
+        // A renewal keeping the key passes
+        let renewed = certificate(&key, &[(DnType::CommonName, "R11")], (2026, 6, 15));
+        assert!(verify(&verifier, &renewed));
+
+        // Even with the same issuer and expiry, another key is refused
+        let impostor = issued_by(&KeyPair::generate().unwrap(), "R10");
+        assert!(!verify(&verifier, &impostor));
+        assert_eq!(
+            pin.take_rejected(),
+            Some(CertificateSummary::from_der(&impostor))
+        );
+        assert_eq!(pin.take_rejected(), None);
+    }
+
+    #[test]
+    fn published_backup_keys_are_accepted() {
+        let pin = Arc::new(CertificatePin::default());
+        let verifier = PinningVerifier::new(Arc::new(AcceptAll), Some(pin.clone()));
+        let current = issued_by(&KeyPair::generate().unwrap(), "R10");
+        let next = issued_by(&KeyPair::generate().unwrap(), "R11");
+
+        pin.set(Some(CertificateSummary::from_der(&current).public_key));
+        pin.set_backups(vec![CertificateSummary::from_der(&next).public_key]);
+        assert!(verify(&verifier, &next));
+        assert!(!verify(
+            &verifier,
+            &issued_by(&KeyPair::generate().unwrap(), "R11")
+        ));
+    }
+
+    #[test]
+    fn reqwest_accepts_the_config() {
+        let pin = Arc::new(CertificatePin::default());
+        let client = reqwest::Client::builder()
+            .tls_backend_preconfigured(tls_config(Some(pin)))
+            .build();
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn a_verifier_without_a_pin_only_observes() {
+        let verifier = PinningVerifier::new(Arc::new(AcceptAll), None);
+        assert!(verify(&verifier, b"anything"));
+    }
+}
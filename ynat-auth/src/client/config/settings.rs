@@ -5,6 +5,10 @@ use serde::Deserialize;
 pub struct Settings {
     #[serde(default = "default_server_url")]
     pub server_url: String,
+    /// SHA-256 fingerprints of public keys the server's operator published
+    /// for its next certificate, accepted without asking
+    #[serde(default)]
+    pub backup_pins: Vec<String>,
 }
 
 fn default_server_url() -> String {
@@ -13,11 +17,10 @@ fn default_server_url() -> String {
 
 impl Settings {
     pub fn new() -> Result<Self, ConfigError> {
-        let config_path =
-            std::env::var("YNAB_TUI_CONFIG").unwrap_or_else(|_| "config.toml".to_string());
+        let config_path = crate::client::paths::config_file();
 
         let settings = Config::builder()
-            .add_source(File::with_name(&config_path).required(false))
+            .add_source(File::from(config_path).required(false))
             .add_source(config::Environment::with_prefix("YNAB_TUI").separator("__"))
             .build()?;
 
@@ -2,6 +2,7 @@ use std::fs;
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use super::paths;
 use crate::error::AuthError;
 
 pub struct DeviceIdStore {
@@ -10,16 +11,8 @@ pub struct DeviceIdStore {
 
 impl DeviceIdStore {
     pub fn new() -> Result<Self, AuthError> {
-        let cache_dir = dirs::cache_dir()
-            .ok_or_else(|| AuthError::Configuration("Could not find cache directory".to_string()))?
-            .join("ynat");
-
-        if !cache_dir.exists() {
-            fs::create_dir_all(&cache_dir)?;
-        }
-
         Ok(Self {
-            device_id_path: cache_dir.join("device_id"),
+            device_id_path: paths::cache_dir()?.join("device_id"),
         })
     }
 
@@ -0,0 +1,96 @@
+//! Access tokens supplied from outside, for people who keep secrets in a
+//! password manager. Either source skips the stored token, the device id and
+//! the auth server entirely.
+
+use crate::common::StoredToken;
+use crate::error::AuthError;
+use std::io::{BufRead, IsTerminal};
+use std::process::{Command, Stdio};
+
+/// Command whose output is the access token, e.g. `pass show ynab`
+pub const TOKEN_CMD_VAR: &str = "YNAT_TOKEN_CMD";
+
+/// A token from [`TOKEN_CMD_VAR`] if it's set, else from the first line of
+/// stdin if something is piped in. `None` means neither was given.
+pub fn load() -> Result<Option<StoredToken>, AuthError> {
+    if let Some(command) = std::env::var(TOKEN_CMD_VAR)
+        .ok()
+        .filter(|c| !c.trim().is_empty())
+    {
+        return run_command(&command).map(Some);
+    }
+
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Ok(None);
+    }
+    let mut line = String::new();
+    stdin.lock().read_line(&mut line)?;
+    // An empty pipe (e.g. /dev/null) falls back to signing in as usual
+    Ok(token_from_output(line.as_bytes()).ok())
+}
+
+/// Run `command` through the shell. Stdin and stderr stay attached to the
+/// terminal so the password manager can prompt for a passphrase.
+fn run_command(command: &str) -> Result<StoredToken, AuthError> {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    let output = shell
+        .arg(command)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| AuthError::ExternalToken(format!("{TOKEN_CMD_VAR} failed to start: {e}")))?;
+    if !output.status.success() {
+        return Err(AuthError::ExternalToken(format!(
+            "{TOKEN_CMD_VAR} exited with {}",
+            output.status
+        )));
+    }
+    token_from_output(&output.stdout)
+}
+
+/// The first line of the output, like `pass` prints the password first
+fn token_from_output(output: &[u8]) -> Result<StoredToken, AuthError> {
+    let output = String::from_utf8_lossy(output);
+    match output.lines().next().map(str::trim) {
+        Some(token) if !token.is_empty() => Ok(StoredToken::external(token)),
+        _ => Err(AuthError::ExternalToken(format!(
+            "{TOKEN_CMD_VAR} printed no token"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_token_is_the_first_line_of_output() {
+        let token = token_from_output(b"  abc123  \nlogin: me@example.com\n").unwrap();
+        assert_eq!(token.access_token, "abc123");
+        assert!(token.is_external());
+        assert!(token_from_output(b"\n").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn commands_run_through_the_shell() {
+        assert_eq!(
+            run_command("printf 'abc123\\n'").unwrap().access_token,
+            "abc123"
+        );
+        assert!(run_command("exit 3").is_err());
+    }
+}
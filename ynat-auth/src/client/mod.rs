@@ -1,19 +1,31 @@
 pub mod auth_client;
 mod config;
 mod device_id;
+mod external_token;
+pub mod paths;
+pub mod qr;
+mod server_pin;
 mod token_storage;
 
 pub use auth_client::ServerAuthClient;
 pub use config::Settings;
 pub use device_id::DeviceIdStore;
+pub use external_token::TOKEN_CMD_VAR;
+pub use server_pin::ServerPinStore;
 pub use token_storage::TokenStore;
 
 use crate::common::StoredToken;
 use crate::error::AuthError;
+use auth_client::{AuthClientError, CertificateSummary};
 
 /// Authenticate user before starting TUI
 /// Returns a valid token or exits with error
 pub async fn authenticate() -> Result<StoredToken, AuthError> {
+    // A token from a password manager or a pipe is used as is
+    if let Some(token) = external_token::load()? {
+        return Ok(token);
+    }
+
     // Load configuration
     let settings = Settings::new().map_err(|e| {
         eprintln!("Failed to load configuration: {}", e);
@@ -37,6 +49,11 @@ pub async fn authenticate() -> Result<StoredToken, AuthError> {
     // Initialize clients
     let auth_client = ServerAuthClient::new(settings.server_url.clone(), device_id);
     let token_store = TokenStore::new()?;
+    let pin_store = ServerPinStore::new()?;
+    // Refreshes only go to the key confirmed before
+    auth_client.accept_backup_keys(settings.backup_pins.clone());
+    let pinned = pin_store.load(&settings.server_url)?;
+    auth_client.pin_certificate(pinned.map(|pinned| pinned.public_key));
 
     // Check for existing token
     if let Some(token) = token_store.load_token()? {
@@ -47,12 +64,25 @@ pub async fn authenticate() -> Result<StoredToken, AuthError> {
 
         // Token expired, try to refresh
         println!("Token expired, attempting to refresh...");
-        match auth_client.refresh_token(&token.refresh_token).await {
-            Ok(new_token) => {
+        let refreshed = match auth_client.refresh_token(&token.refresh_token).await {
+            Err(AuthClientError::CertificateChanged { .. }) => {
+                trust_server(&auth_client, &settings, &pin_store).await?;
+                auth_client.refresh_token(&token.refresh_token).await
+            }
+            result => result,
+        };
+        match refreshed {
+            Ok(mut new_token) => {
+                // A refresh keeps the scope the user granted
+                new_token.scope = new_token.scope.or(token.scope);
                 token_store.save_token(&new_token)?;
                 println!("✓ Token refreshed successfully");
                 return Ok(new_token);
             }
+            Err(AuthClientError::RefreshTokenRevoked(message)) => {
+                eprintln!("Your saved session can't be refreshed: {}", message);
+                token_store.delete_token()?;
+            }
             Err(e) => {
                 eprintln!("Failed to refresh token: {}", e);
                 token_store.delete_token()?;
@@ -64,10 +94,9 @@ pub async fn authenticate() -> Result<StoredToken, AuthError> {
     println!("\n=== YNAB Authentication Required ===\n");
     println!("This will open your browser to authorize the application.");
     println!("After authorization, please wait while we complete the process.\n");
-    println!("Press Enter to start authentication, or Ctrl+C to cancel...");
 
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input)?;
+    // Show who will receive the device ID before sending it
+    trust_server(&auth_client, &settings, &pin_store).await?;
 
     // Initiate auth on server
     let (session_id, auth_url) = auth_client.initiate_auth().await?;
@@ -82,6 +111,7 @@ pub async fn authenticate() -> Result<StoredToken, AuthError> {
         println!("\nYou can also open this URL directly in your browser:");
         println!("{}\n", auth_url);
     }
+    print_qr_code(&auth_url);
 
     // Poll for completion
     println!("Waiting for authorization...");
@@ -93,3 +123,95 @@ pub async fn authenticate() -> Result<StoredToken, AuthError> {
 
     Ok(token)
 }
+
+/// Show the URL as a QR code so a headless machine can be authorized from a phone
+fn print_qr_code(auth_url: &str) {
+    if let Some(qr) = qr::render(auth_url) {
+        println!("Or scan this QR code with your phone:\n");
+        println!("{}", qr);
+    }
+}
+
+/// Have the user confirm the server's certificate and pin its key, so every
+/// later request is refused unless the handshake presents that same key
+async fn trust_server(
+    auth_client: &ServerAuthClient,
+    settings: &Settings,
+    pin_store: &ServerPinStore,
+) -> Result<(), AuthError> {
+    let certificate = auth_client.server_certificate().await?;
+    confirm_server(settings, certificate.as_ref(), pin_store)?;
+    auth_client.pin_certificate(certificate.map(|certificate| certificate.public_key));
+    Ok(())
+}
+
+/// Show the server URL and certificate and wait for the user to accept them.
+/// The key is pinned, so a later change gets a warning.
+fn confirm_server(
+    settings: &Settings,
+    certificate: Option<&CertificateSummary>,
+    pin_store: &ServerPinStore,
+) -> Result<(), AuthError> {
+    let server_url = &settings.server_url;
+    println!("Auth server: {}", server_url);
+    let Some(certificate) = certificate else {
+        println!("This server doesn't use HTTPS, so its identity can't be verified.\n");
+        println!("Press Enter to start authentication, or Ctrl+C to cancel...");
+        read_line()?;
+        return Ok(());
+    };
+    println!("{}\n", describe_certificate(certificate));
+
+    match pin_store.load(server_url)? {
+        Some(pinned) if pinned.public_key == certificate.public_key => {
+            println!("This matches the key you confirmed before.\n");
+            println!("Press Enter to start authentication, or Ctrl+C to cancel...");
+            read_line()?;
+        }
+        Some(_) if settings.backup_pins.contains(&certificate.public_key) => {
+            println!("This is a backup key configured for this server.\n");
+            println!("Press Enter to start authentication, or Ctrl+C to cancel...");
+            read_line()?;
+        }
+        Some(pinned) => {
+            eprintln!("WARNING: the server's key differs from the one you confirmed before.");
+            eprintln!("Before:\n{}\n", describe_certificate(&pinned));
+            eprintln!("Renewing a certificate usually keeps its key, so this is unusual.");
+            eprintln!(
+                "Check with the server's operator; it may be misconfigured or impersonated.\n"
+            );
+            println!("Type 'yes' to trust the new key, or anything else to cancel:");
+            if read_line()?.trim() != "yes" {
+                return Err(AuthError::Configuration(
+                    "Auth server certificate was not trusted".to_string(),
+                ));
+            }
+        }
+        None => {
+            println!("Check that this is the server you expect. Its key will be");
+            println!("remembered and you'll be warned if it changes.\n");
+            println!("Press Enter to trust it and start authentication, or Ctrl+C to cancel...");
+            read_line()?;
+        }
+    }
+
+    pin_store.save(server_url, certificate)
+}
+
+/// The key fingerprint, then who issued the certificate and until when
+fn describe_certificate(certificate: &CertificateSummary) -> String {
+    format!(
+        "Public key SHA-256: {}\nIssued by {}, valid until {}",
+        certificate.public_key,
+        certificate.issuer.as_deref().unwrap_or("an unknown issuer"),
+        certificate
+            .expires
+            .map_or("an unknown date".to_string(), |date| date.to_string())
+    )
+}
+
+fn read_line() -> Result<String, AuthError> {
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input)
+}
@@ -0,0 +1,50 @@
+//! Where ynat keeps its files.
+//!
+//! Directories come from each platform's conventions: `~/.config` and
+//! `~/.cache` on Linux, `~/Library` on macOS, and `%APPDATA%` and
+//! `%LOCALAPPDATA%` on Windows.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::AuthError;
+
+/// Environment variable naming the config file
+pub const CONFIG_VAR: &str = "YNAB_TUI_CONFIG";
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// The config file: the one named by `YNAB_TUI_CONFIG`, else a `config.toml`
+/// in the working directory, else `config.toml` in the user's config
+/// directory. The last is what a program started from a desktop shortcut or
+/// the Start menu finds, since its working directory is arbitrary.
+pub fn config_file() -> PathBuf {
+    if let Some(path) = std::env::var_os(CONFIG_VAR) {
+        return PathBuf::from(path);
+    }
+    let local = PathBuf::from(CONFIG_FILE);
+    if local.exists() {
+        return local;
+    }
+    match dirs::config_dir() {
+        Some(dir) => {
+            let dir = dir.join("ynat");
+            // So the file can be written there when settings are saved
+            let _ = fs::create_dir_all(&dir);
+            dir.join(CONFIG_FILE)
+        }
+        None => local,
+    }
+}
+
+/// The directory holding the token, device ID and server pins, created if
+/// it doesn't exist yet
+pub fn cache_dir() -> Result<PathBuf, AuthError> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| AuthError::Configuration("Could not find cache directory".to_string()))?
+        .join("ynat");
+    if !cache_dir.exists() {
+        fs::create_dir_all(&cache_dir)?;
+    }
+    Ok(cache_dir)
+}
@@ -0,0 +1,42 @@
+//! QR code of the authorization URL for the terminal, so a headless machine
+//! can be authorized by scanning it with a phone.
+
+use qrcode::render::unicode::Dense1x2;
+use qrcode::{EcLevel, QrCode};
+
+/// Render `text` as a QR code for the terminal, or `None` if it doesn't fit.
+///
+/// Half-block characters put two rows of modules on each line. Light modules
+/// are drawn as filled blocks, so the code reads correctly on the usual
+/// light-on-dark terminal. Includes the required quiet zone.
+pub fn render(text: &str) -> Option<String> {
+    let code = QrCode::with_error_correction_level(text, EcLevel::L).ok()?;
+    Some(
+        code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .quiet_zone(true)
+            .build(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_the_code_with_its_quiet_zone() {
+        // Version 2 is 25 modules, plus 4 of quiet zone on each side
+        let qr = render("https://example.com/auth").unwrap();
+        let lines: Vec<&str> = qr.lines().collect();
+        assert_eq!(lines.len(), 33_usize.div_ceil(2));
+        assert!(lines.iter().all(|line| line.chars().count() == 33));
+        // The quiet zone is light, drawn as full blocks
+        assert!(lines[0].chars().all(|c| c == '█'));
+    }
+
+    #[test]
+    fn text_too_long_for_any_version_is_refused() {
+        assert!(render(&"x".repeat(3000)).is_none());
+    }
+}
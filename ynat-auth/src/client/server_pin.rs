@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::auth_client::CertificateSummary;
+use super::paths;
+use crate::error::AuthError;
+
+/// Certificates of auth servers the user has confirmed, keyed by URL. Their
+/// public keys are pinned.
+pub struct ServerPinStore {
+    pins_path: PathBuf,
+}
+
+impl ServerPinStore {
+    pub fn new() -> Result<Self, AuthError> {
+        Ok(Self {
+            pins_path: paths::cache_dir()?.join("server_key_pins.json"),
+        })
+    }
+
+    fn load_all(&self) -> Result<BTreeMap<String, CertificateSummary>, AuthError> {
+        if !self.pins_path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let json = fs::read_to_string(&self.pins_path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Certificate pinned for `server_url`, if it was confirmed before
+    pub fn load(&self, server_url: &str) -> Result<Option<CertificateSummary>, AuthError> {
+        Ok(self.load_all()?.remove(server_url))
+    }
+
+    /// Remember `certificate` as the trusted one for `server_url`
+    pub fn save(
+        &self,
+        server_url: &str,
+        certificate: &CertificateSummary,
+    ) -> Result<(), AuthError> {
+        let mut pins = self.load_all()?;
+        pins.insert(server_url.to_string(), certificate.clone());
+        fs::write(&self.pins_path, serde_json::to_string_pretty(&pins)?)?;
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use super::paths;
 use crate::common::StoredToken;
 use crate::error::AuthError;
 use chrono::{Duration, Utc};
@@ -12,24 +13,10 @@ pub struct TokenStore {
 
 impl TokenStore {
     pub fn new() -> Result<Self, AuthError> {
-        let cache_dir = Self::get_cache_dir()?;
-        let token_path = cache_dir.join("token.json");
-
-        // Create cache directory if it doesn't exist
-        if !cache_dir.exists() {
-            fs::create_dir_all(&cache_dir).map_err(|e| {
-                AuthError::TokenStorage(format!("Failed to create cache directory: {}", e))
-            })?;
-        }
-
+        let token_path = paths::cache_dir()?.join("token.json");
         Ok(Self { token_path })
     }
 
-    fn get_cache_dir() -> Result<PathBuf, AuthError> {
-        let cache_dir = dirs::cache_dir().expect("Always returns").join("ynat");
-        Ok(cache_dir)
-    }
-
     pub fn save_token(&self, token: &StoredToken) -> Result<(), AuthError> {
         let json = serde_json::to_string_pretty(token)?;
 
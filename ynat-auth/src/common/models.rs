@@ -1,13 +1,19 @@
 use chrono::{serde::ts_seconds, DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// The OAuth scope YNAB grants when the user only allows reading
+pub const READ_ONLY_SCOPE: &str = "read-only";
+
 /// Token pair returned from OAuth flow
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TokenPair {
     pub access_token: String,
     pub refresh_token: String,
     #[serde(with = "ts_seconds")]
     pub expires_at: DateTime<Utc>,
+    /// Scopes YNAB granted, space separated, if it said
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 /// Stored token with expiration
@@ -17,6 +23,35 @@ pub struct StoredToken {
     pub refresh_token: String,
     #[serde(with = "ts_seconds")]
     pub expires_at: DateTime<Utc>,
+    /// Scopes YNAB granted, space separated. Missing for tokens saved before
+    /// it was recorded and for external tokens, which are treated as full
+    /// access.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl StoredToken {
+    /// A token supplied by the user rather than the OAuth flow. It can't be
+    /// refreshed, so it's treated as never expiring.
+    pub fn external(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            refresh_token: String::new(),
+            expires_at: DateTime::<Utc>::MAX_UTC,
+            scope: None,
+        }
+    }
+
+    pub fn is_external(&self) -> bool {
+        self.refresh_token.is_empty()
+    }
+
+    /// Whether the user only allowed reading their budgets
+    pub fn is_read_only(&self) -> bool {
+        self.scope
+            .as_deref()
+            .is_some_and(|scope| scope.split_whitespace().any(|s| s == READ_ONLY_SCOPE))
+    }
 }
 
 impl From<TokenPair> for StoredToken {
@@ -25,6 +60,30 @@ impl From<TokenPair> for StoredToken {
             access_token: tokens.access_token,
             refresh_token: tokens.refresh_token,
             expires_at: tokens.expires_at,
+            scope: tokens.scope,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_when_the_scope_says_so() {
+        let mut token = StoredToken::external("token");
+        assert!(!token.is_read_only());
+
+        token.scope = Some("read-only".to_string());
+        assert!(token.is_read_only());
+        token.scope = Some("".to_string());
+        assert!(!token.is_read_only());
+
+        // Tokens saved before the scope was recorded have full access
+        let saved: StoredToken = serde_json::from_str(
+            r#"{"access_token":"a","refresh_token":"r","expires_at":1700000000}"#,
+        )
+        .unwrap();
+        assert_eq!(saved.scope, None);
+    }
+}
@@ -8,6 +8,9 @@ pub enum AuthError {
     #[error("Token storage error: {0}")]
     TokenStorage(String),
 
+    #[error("External token error: {0}")]
+    ExternalToken(String),
+
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
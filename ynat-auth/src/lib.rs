@@ -5,7 +5,10 @@ pub mod common;
 mod client;
 mod error;
 
-pub use client::{authenticate, DeviceIdStore, ServerAuthClient, Settings, TokenStore};
+pub use client::{
+    authenticate, paths, DeviceIdStore, ServerAuthClient, ServerPinStore, Settings, TokenStore,
+    TOKEN_CMD_VAR,
+};
 pub use common::{StoredToken, TokenPair};
 pub use error::AuthError;
 
@@ -1,16 +1,18 @@
 use anyhow::Result;
 use axum::{
+    middleware::from_fn_with_state,
     routing::{get, post},
     Router,
 };
+use std::path::PathBuf;
 use std::sync::Arc;
-use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 use ynat_auth::server::{
     config::Configuration,
     handlers,
-    services::{OAuthClient, SessionStore},
+    middleware::log_requests,
+    services::{OAuthClient, RefreshTokenStore, SessionStore},
     AppState,
 };
 
@@ -38,10 +40,19 @@ async fn main() -> Result<()> {
     // Initialize services
     let session_store = Arc::new(SessionStore::new(configuration.server.session_ttl_seconds));
     let oauth_client = Arc::new(OAuthClient::new(&configuration.oauth)?);
+    let refresh_token_path = Some(&configuration.server.refresh_token_path)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+    let refresh_token_store = Arc::new(RefreshTokenStore::new(
+        refresh_token_path,
+        configuration.server.refresh_token_ttl_seconds,
+        configuration.server.refresh_grace_seconds,
+    )?);
 
     let app_state = AppState {
         session_store,
         oauth_client,
+        refresh_token_store: refresh_token_store.clone(),
     };
 
     // Build router
@@ -51,7 +62,10 @@ async fn main() -> Result<()> {
         .route("/auth/callback", get(handlers::oauth_callback))
         .route("/auth/poll/{session_id}", get(handlers::poll_session))
         .route("/auth/refresh", post(handlers::refresh_token))
-        .layer(TraceLayer::new_for_http())
+        .layer(from_fn_with_state(
+            configuration.server.log_verbosity,
+            log_requests,
+        ))
         .with_state(app_state);
 
     // Start server
@@ -62,7 +76,36 @@ async fn main() -> Result<()> {
     tracing::info!("Starting server on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    // Rotations queued by the last requests must reach the disk before exit
+    refresh_token_store.flush().await;
 
     Ok(())
 }
+
+/// Resolves on Ctrl-C or SIGTERM, which is how Fly stops an idle machine
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutting down");
+}
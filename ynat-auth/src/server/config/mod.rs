@@ -16,6 +16,35 @@ pub struct ServerConfiguration {
 
     #[serde(default = "default_session_ttl")]
     pub session_ttl_seconds: u64,
+
+    /// How long rotated refresh tokens are remembered for reuse detection
+    #[serde(default = "default_refresh_token_ttl")]
+    pub refresh_token_ttl_seconds: u64,
+
+    /// Where refresh token rotation is saved; put it on a volume so it
+    /// survives restarts. Empty keeps it in memory only.
+    #[serde(default = "default_refresh_token_path")]
+    pub refresh_token_path: String,
+
+    /// How long a retried refresh gets the pair it was already rotated into
+    #[serde(default = "default_refresh_grace")]
+    pub refresh_grace_seconds: u64,
+
+    #[serde(default)]
+    pub log_verbosity: LogVerbosity,
+}
+
+/// How much of each request is logged. Secrets are redacted at every level.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LogVerbosity {
+    /// Only the handlers' own logs
+    Off,
+    /// Method, path, status and latency
+    #[default]
+    Basic,
+    /// Also request and response bodies
+    Body,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -37,6 +66,18 @@ fn default_session_ttl() -> u64 {
     600
 }
 
+fn default_refresh_token_ttl() -> u64 {
+    90 * 24 * 60 * 60
+}
+
+fn default_refresh_token_path() -> String {
+    "refresh_tokens.json".to_string()
+}
+
+fn default_refresh_grace() -> u64 {
+    60
+}
+
 impl Configuration {
     pub fn new() -> Result<Self, config::ConfigError> {
         let mut builder = config::Config::builder();
@@ -17,6 +17,9 @@ pub enum ServerError {
     #[error("Forbidden: {0}")]
     Forbidden(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
     #[error("OAuth error: {0}")]
     OAuthError(String),
 
@@ -36,6 +39,7 @@ impl IntoResponse for ServerError {
             ServerError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             ServerError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             ServerError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            ServerError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
             ServerError::OAuthError(msg) => (StatusCode::BAD_GATEWAY, msg),
             ServerError::Configuration(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             ServerError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
@@ -182,6 +182,11 @@ pub async fn oauth_callback(
     // Exchange code for tokens
     let tokens = state.oauth_client.exchange_code_for_token(&code).await?;
 
+    // Start a rotation family so reuse of this refresh token can be detected
+    state
+        .refresh_token_store
+        .issue(&tokens.refresh_token, &session.device_id);
+
     // Store tokens in session
     state.session_store.complete_session(session_id, tokens);
 
@@ -1,8 +1,10 @@
 use axum::{extract::State, Json};
+use std::time::Duration;
 
 use crate::server::{
     error::ServerError,
     models::{RefreshRequest, RefreshResponse},
+    services::RefreshClaim,
     AppState,
 };
 
@@ -12,16 +14,51 @@ pub async fn refresh_token(
 ) -> Result<Json<RefreshResponse>, ServerError> {
     tracing::debug!("Token refresh requested");
 
-    let tokens = state
+    let family_id = loop {
+        match state
+            .refresh_token_store
+            .claim(&req.refresh_token, req.device_id.as_deref())
+        {
+            RefreshClaim::Granted { family_id } => break family_id,
+            // Another ynat process is refreshing the same token; wait for its
+            // result rather than treating this as reuse
+            RefreshClaim::Pending => tokio::time::sleep(Duration::from_millis(250)).await,
+            RefreshClaim::Replayed { tokens } => return Ok(Json((*tokens).into())),
+            RefreshClaim::Reused { .. } | RefreshClaim::Revoked => {
+                return Err(ServerError::Unauthorized(
+                    "it was used more than once, so the session was revoked. If you didn't \
+                     copy token.json to another machine, someone else may have it. Please \
+                     authorize again"
+                        .to_string(),
+                ));
+            }
+            // Also every token from before rotation was tracked, or after the
+            // state file was lost: 401 so the client signs in again
+            RefreshClaim::Unknown => {
+                return Err(ServerError::Unauthorized(
+                    "this auth server has no record of it. Please authorize again".to_string(),
+                ));
+            }
+        }
+    };
+
+    let tokens = match state
         .oauth_client
         .refresh_access_token(&req.refresh_token)
-        .await?;
+        .await
+    {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            state.refresh_token_store.release(&req.refresh_token);
+            return Err(e);
+        }
+    };
+
+    state
+        .refresh_token_store
+        .complete(&req.refresh_token, &family_id, &tokens);
 
-    tracing::info!("Token refresh successful");
+    tracing::info!(family_id = %family_id, "Token refresh successful");
 
-    Ok(Json(RefreshResponse {
-        access_token: tokens.access_token,
-        refresh_token: tokens.refresh_token,
-        expires_at: tokens.expires_at,
-    }))
+    Ok(Json(tokens.into()))
 }
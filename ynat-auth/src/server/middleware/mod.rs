@@ -0,0 +1,3 @@
+mod request_logging;
+
+pub use request_logging::{log_requests, REQUEST_ID_HEADER};
@@ -0,0 +1,282 @@
+use axum::{
+    body::{to_bytes, Body, Bytes, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderValue, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use std::time::Instant;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::server::config::LogVerbosity;
+use crate::server::error::ServerError;
+
+/// Response header carrying the correlation ID of a request
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Placeholder written over secret values before they're logged
+const REDACTED: &str = "[redacted]";
+
+/// Largest body that will be buffered for logging; larger ones are passed on
+/// untouched and only their size is logged
+const MAX_LOGGED_BODY_BYTES: usize = 64 * 1024;
+
+/// Log each request with a correlation ID, redacting tokens and codes.
+///
+/// The ID is returned in the `x-request-id` header and added to JSON error
+/// bodies, so an error a user reports can be found in the server logs.
+pub async fn log_requests(
+    State(verbosity): State<LogVerbosity>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let request_id = Uuid::new_v4().to_string();
+    let method = req.method().clone();
+    let uri = redact_uri(req.uri());
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let start = Instant::now();
+
+    let req = if verbosity >= LogVerbosity::Body {
+        let (parts, body) = req.into_parts();
+        let body = match capture(body).await {
+            Ok(Captured::Buffered(bytes)) => {
+                let body = describe_body(&bytes);
+                span.in_scope(|| tracing::info!(%method, %uri, %body, "Request"));
+                Body::from(bytes)
+            }
+            Ok(Captured::Passed(body, placeholder)) => {
+                span.in_scope(|| tracing::info!(%method, %uri, body = %placeholder, "Request"));
+                body
+            }
+            Err(e) => {
+                span.in_scope(
+                    || tracing::warn!(%method, %uri, "Couldn't read request body: {}", e),
+                );
+                let error = ServerError::BadRequest("Couldn't read the request body".to_string());
+                return with_request_id_header(error.into_response(), &request_id);
+            }
+        };
+        Request::from_parts(parts, body)
+    } else {
+        req
+    };
+
+    let response = next.run(req).instrument(span.clone()).await;
+    let status = response.status();
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    let response = if status.is_client_error()
+        || status.is_server_error()
+        || verbosity >= LogVerbosity::Body
+    {
+        let (mut parts, body) = response.into_parts();
+        let body = match capture(body).await {
+            Ok(Captured::Buffered(mut bytes)) => {
+                if is_json && !status.is_success() {
+                    bytes = with_request_id(&bytes, &request_id);
+                    parts.headers.remove(header::CONTENT_LENGTH);
+                }
+                if verbosity >= LogVerbosity::Body {
+                    let body = describe_body(&bytes);
+                    span.in_scope(|| tracing::info!(%body, "Response body"));
+                }
+                Body::from(bytes)
+            }
+            Ok(Captured::Passed(body, placeholder)) => {
+                if verbosity >= LogVerbosity::Body {
+                    span.in_scope(|| tracing::info!(body = %placeholder, "Response body"));
+                }
+                body
+            }
+            Err(e) => {
+                span.in_scope(
+                    || tracing::error!(%method, %uri, "Couldn't read response body: {}", e),
+                );
+                let error = ServerError::Internal("Couldn't send the response".to_string());
+                return with_request_id_header(error.into_response(), &request_id);
+            }
+        };
+        Response::from_parts(parts, body)
+    } else {
+        response
+    };
+
+    if verbosity >= LogVerbosity::Basic {
+        let latency_ms = start.elapsed().as_millis() as u64;
+        let status = status.as_u16();
+        span.in_scope(|| match status {
+            500.. => tracing::error!(%method, %uri, status, latency_ms, "Request failed"),
+            400.. => tracing::warn!(%method, %uri, status, latency_ms, "Request rejected"),
+            _ => tracing::info!(%method, %uri, status, latency_ms, "Request completed"),
+        });
+    }
+
+    with_request_id_header(response, &request_id)
+}
+
+fn with_request_id_header(mut response: Response, request_id: &str) -> Response {
+    if let Ok(value) = HeaderValue::from_str(request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// A body read for logging, or passed on as it came
+enum Captured {
+    Buffered(Bytes),
+    /// Too large, or of unknown length, to buffer; the placeholder is logged
+    Passed(Body, String),
+}
+
+/// Buffer `body` if its length is known and within [`MAX_LOGGED_BODY_BYTES`]
+async fn capture(body: Body) -> Result<Captured, axum::Error> {
+    match body.size_hint().exact() {
+        Some(len) if len <= MAX_LOGGED_BODY_BYTES as u64 => to_bytes(body, MAX_LOGGED_BODY_BYTES)
+            .await
+            .map(Captured::Buffered),
+        Some(len) => Ok(Captured::Passed(body, format!("<{} bytes elided>", len))),
+        None => Ok(Captured::Passed(
+            body,
+            "<body of unknown length elided>".to_string(),
+        )),
+    }
+}
+
+/// Whether a query parameter or JSON key holds a credential that must never be
+/// logged. The device ID counts: with a refresh token it gets a retried
+/// rotation answered.
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key == "code" || key == "device_id" || key.contains("token") || key.contains("secret")
+}
+
+/// Path and query with the values of secret parameters replaced
+fn redact_uri(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.path().to_string();
+    };
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if is_secret_key(key) => format!("{}={}", key, REDACTED),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{}?{}", uri.path(), query)
+}
+
+/// Replace the values of any secret-looking keys, at any depth
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    redact(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Redacted JSON, or just the size for anything else (e.g. the callback page)
+fn describe_body(bytes: &Bytes) -> String {
+    if bytes.is_empty() {
+        return "<empty>".to_string();
+    }
+    match serde_json::from_slice::<Value>(bytes) {
+        Ok(mut value) => {
+            redact(&mut value);
+            value.to_string()
+        }
+        Err(_) => format!("<{} bytes>", bytes.len()),
+    }
+}
+
+/// Add `request_id` to a JSON error object
+fn with_request_id(bytes: &Bytes, request_id: &str) -> Bytes {
+    match serde_json::from_slice::<Value>(bytes) {
+        Ok(Value::Object(mut map)) => {
+            map.insert("request_id".to_string(), Value::String(request_id.into()));
+            Bytes::from(Value::Object(map).to_string())
+        }
+        _ => bytes.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn codes_and_tokens_are_redacted() {
+        let uri: Uri = "/auth/callback?code=abc123&state=session-1"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            redact_uri(&uri),
+            "/auth/callback?code=[redacted]&state=session-1"
+        );
+
+        let body = Bytes::from(
+            json!({
+                "status": { "type": "Completed" },
+                "tokens": { "access_token": "a", "refresh_token": "r" }
+            })
+            .to_string(),
+        );
+        let logged: Value = serde_json::from_str(&describe_body(&body)).unwrap();
+        assert_eq!(logged["tokens"], REDACTED);
+    }
+
+    #[test]
+    fn device_ids_are_redacted() {
+        let uri: Uri = "/auth/poll/session-1?device_id=abc".parse().unwrap();
+        assert_eq!(
+            redact_uri(&uri),
+            "/auth/poll/session-1?device_id=[redacted]"
+        );
+
+        let body = Bytes::from(json!({ "refresh_token": "r", "device_id": "d" }).to_string());
+        let logged: Value = serde_json::from_str(&describe_body(&body)).unwrap();
+        assert_eq!(logged["device_id"], REDACTED);
+    }
+
+    #[tokio::test]
+    async fn large_bodies_pass_through_untouched() {
+        let large = Bytes::from(vec![b'x'; MAX_LOGGED_BODY_BYTES + 1]);
+        match capture(Body::from(large.clone())).await.unwrap() {
+            Captured::Passed(body, placeholder) => {
+                assert_eq!(placeholder, "<65537 bytes elided>");
+                assert_eq!(to_bytes(body, usize::MAX).await.unwrap(), large);
+            }
+            Captured::Buffered(_) => panic!("a body over the limit was buffered"),
+        }
+
+        let small = Bytes::from_static(b"{}");
+        assert!(matches!(
+            capture(Body::from(small.clone())).await.unwrap(),
+            Captured::Buffered(bytes) if bytes == small
+        ));
+    }
+
+    #[test]
+    fn error_bodies_carry_the_request_id() {
+        let body = Bytes::from(json!({ "error": "Invalid session" }).to_string());
+        let value: Value = serde_json::from_slice(&with_request_id(&body, "req-1")).unwrap();
+        assert_eq!(
+            value,
+            json!({ "error": "Invalid session", "request_id": "req-1" })
+        );
+    }
+}
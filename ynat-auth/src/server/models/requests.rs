@@ -41,6 +41,10 @@ pub struct PollResponse {
 #[derive(Debug, Deserialize)]
 pub struct RefreshRequest {
     pub refresh_token: String,
+    /// Sent by clients since refresh token rotation, so a retry can only be
+    /// answered for the device the token was issued to
+    #[serde(default)]
+    pub device_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -49,6 +53,19 @@ pub struct RefreshResponse {
     pub refresh_token: String,
     #[serde(with = "ts_seconds")]
     pub expires_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl From<TokenPair> for RefreshResponse {
+    fn from(tokens: TokenPair) -> Self {
+        Self {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            expires_at: tokens.expires_at,
+            scope: tokens.scope,
+        }
+    }
 }
 
 // Health check
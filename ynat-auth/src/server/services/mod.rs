@@ -1,5 +1,7 @@
 pub mod oauth_client;
+pub mod refresh_token_store;
 pub mod session_store;
 
 pub use oauth_client::OAuthClient;
+pub use refresh_token_store::{RefreshClaim, RefreshTokenStore};
 pub use session_store::SessionStore;
@@ -30,6 +30,17 @@ async fn http_client(request: HttpRequest) -> Result<HttpResponse, reqwest::Erro
     Ok(http_response)
 }
 
+/// Scopes in a token response, space separated like the OAuth `scope` field
+fn granted_scope(token_result: &impl TokenResponse) -> Option<String> {
+    token_result.scopes().map(|scopes| {
+        scopes
+            .iter()
+            .map(|scope| scope.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
 const YNAB_AUTH_URL: &str = "https://app.ynab.com/oauth/authorize";
 const YNAB_TOKEN_URL: &str = "https://app.ynab.com/oauth/token";
 
@@ -109,6 +120,7 @@ impl OAuthClient {
             access_token,
             refresh_token,
             expires_at,
+            scope: granted_scope(&token_result),
         })
     }
 
@@ -147,6 +159,7 @@ impl OAuthClient {
             access_token,
             refresh_token,
             expires_at,
+            scope: granted_scope(&token_result),
         })
     }
 
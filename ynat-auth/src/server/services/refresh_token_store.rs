@@ -0,0 +1,506 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::common::TokenPair;
+use crate::server::error::ServerError;
+
+/// Tracks refresh token rotation so a token that was already exchanged can't
+/// be used again.
+///
+/// Every token issued to a device belongs to a family. Refreshing rotates the
+/// family onto a new token; presenting a rotated token again means two parties
+/// hold the same family, so the whole family is revoked and the device has to
+/// authorize again. A retry within the grace window (two ynat processes
+/// refreshing at once, or a response lost on the way back) gets the same new
+/// pair instead, but only when it comes from the device the family was issued
+/// to; from anywhere else it counts as reuse.
+///
+/// Only SHA-256 hashes of tokens are written to the state file, so rotation
+/// survives restarts; the pair kept for retries stays in memory.
+pub struct RefreshTokenStore {
+    state: Arc<Mutex<StoreState>>,
+    writer: Option<Arc<StateWriter>>,
+    ttl: Duration,
+    grace: Duration,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreState {
+    tokens: HashMap<String, RefreshTokenRecord>,
+    revoked_families: HashMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RefreshTokenRecord {
+    family_id: String,
+    state: RefreshTokenState,
+    issued_at: DateTime<Utc>,
+    /// When `state` last changed
+    changed_at: DateTime<Utc>,
+    /// The device that authorized the family
+    device_id: String,
+    /// The pair this token was rotated into, for retries within the grace window
+    #[serde(skip)]
+    replacement: Option<TokenPair>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RefreshTokenState {
+    /// The family's current token
+    Active,
+    /// Claimed by a refresh that hasn't finished yet
+    Rotating,
+    /// Exchanged for a newer token
+    Rotated,
+}
+
+/// Outcome of presenting a refresh token
+#[derive(Debug, PartialEq, Eq)]
+pub enum RefreshClaim {
+    /// The token may be exchanged; finish with [`RefreshTokenStore::complete`]
+    /// or give it back with [`RefreshTokenStore::release`]
+    Granted { family_id: String },
+    /// Another refresh of this token is in flight; claim again shortly
+    Pending,
+    /// The same device presented the token again moments after it was
+    /// rotated; answer with the same pair
+    Replayed { tokens: Box<TokenPair> },
+    /// The token was already rotated; its family is now revoked
+    Reused { family_id: String },
+    /// The token's family was revoked earlier
+    Revoked,
+    /// This server never issued the token
+    Unknown,
+}
+
+impl RefreshTokenStore {
+    /// Open the store, loading earlier state from `path` if there is any.
+    /// Without a path the state is kept in memory only.
+    ///
+    /// Fails if `path` can't be written, rather than serving refreshes whose
+    /// rotation would be forgotten at the next restart.
+    pub fn new(
+        path: Option<PathBuf>,
+        ttl_seconds: u64,
+        grace_seconds: u64,
+    ) -> Result<Self, ServerError> {
+        let state = match &path {
+            Some(path) if path.exists() => {
+                let json = fs::read_to_string(path).map_err(|e| {
+                    ServerError::Configuration(format!("Can't read {}: {}", path.display(), e))
+                })?;
+                serde_json::from_str(&json).map_err(|e| {
+                    ServerError::Configuration(format!("Can't parse {}: {}", path.display(), e))
+                })?
+            }
+            _ => StoreState::default(),
+        };
+        match &path {
+            Some(path) => tracing::info!(
+                "Refresh token store loaded {} tokens from {}",
+                state.tokens.len(),
+                path.display()
+            ),
+            None => tracing::warn!("Refresh token store is in memory only"),
+        }
+
+        let writer = match path {
+            Some(path) => {
+                let json = serde_json::to_vec(&state).map_err(|e| {
+                    ServerError::Configuration(format!("Can't serialize refresh tokens: {}", e))
+                })?;
+                write_state(&path, &json).map_err(|e| {
+                    ServerError::Configuration(format!("Can't write {}: {}", path.display(), e))
+                })?;
+                Some(Arc::new(StateWriter::spawn(path)))
+            }
+            None => None,
+        };
+
+        let store = Self {
+            state: Arc::new(Mutex::new(state)),
+            writer,
+            ttl: Duration::from_secs(ttl_seconds),
+            grace: Duration::from_secs(grace_seconds),
+        };
+
+        let state = store.state.clone();
+        let writer = store.writer.clone();
+        let (ttl, grace) = (store.ttl, store.grace);
+        tokio::spawn(async move {
+            cleanup_expired_tokens(state, writer, ttl, grace).await;
+        });
+
+        Ok(store)
+    }
+
+    /// Start a new family for a token issued to `device_id` by the
+    /// authorization code flow
+    pub fn issue(&self, refresh_token: &str, device_id: &str) -> String {
+        let family_id = Uuid::new_v4().to_string();
+        let mut state = self.state();
+        insert_active(&mut state, refresh_token, &family_id, device_id);
+        self.save(&state);
+        tracing::debug!(family_id = %family_id, "Issued refresh token");
+        family_id
+    }
+
+    /// Claim `refresh_token` for a refresh on behalf of `device_id`
+    pub fn claim(&self, refresh_token: &str, device_id: Option<&str>) -> RefreshClaim {
+        let now = Utc::now();
+        let mut guard = self.state();
+        let state = &mut *guard;
+        let Some(record) = state.tokens.get_mut(&hash_token(refresh_token)) else {
+            tracing::warn!("Unknown refresh token presented");
+            return RefreshClaim::Unknown;
+        };
+        let family_id = record.family_id.clone();
+        if state.revoked_families.contains_key(&family_id) {
+            return RefreshClaim::Revoked;
+        }
+        let within_grace = elapsed(now, record.changed_at) < self.grace;
+        let same_device = device_id == Some(record.device_id.as_str());
+
+        match (record.state, &record.replacement) {
+            (RefreshTokenState::Rotating, _) if within_grace => RefreshClaim::Pending,
+            // A claim older than the grace window belongs to a refresh that
+            // died with the process
+            (RefreshTokenState::Active | RefreshTokenState::Rotating, _) => {
+                record.state = RefreshTokenState::Rotating;
+                record.changed_at = now;
+                self.save(state);
+                RefreshClaim::Granted { family_id }
+            }
+            (RefreshTokenState::Rotated, Some(tokens)) if within_grace && same_device => {
+                tracing::debug!(family_id = %family_id, "Replaying refresh within grace window");
+                RefreshClaim::Replayed {
+                    tokens: Box::new(tokens.clone()),
+                }
+            }
+            (RefreshTokenState::Rotated, _) => {
+                state.revoked_families.insert(family_id.clone(), now);
+                self.save(state);
+                tracing::warn!(
+                    family_id = %family_id,
+                    "Rotated refresh token reused, revoking token family"
+                );
+                RefreshClaim::Reused { family_id }
+            }
+        }
+    }
+
+    /// Finish a granted claim: retire the old token and make the new pair current
+    pub fn complete(&self, old_token: &str, family_id: &str, tokens: &TokenPair) {
+        let mut state = self.state();
+        let Some(record) = state.tokens.get_mut(&hash_token(old_token)) else {
+            // Only when the claim outlived the token's TTL
+            tracing::warn!(family_id = %family_id, "Rotated refresh token expired mid-refresh");
+            return;
+        };
+        record.state = RefreshTokenState::Rotated;
+        record.changed_at = Utc::now();
+        record.replacement = Some(tokens.clone());
+        let device_id = record.device_id.clone();
+        insert_active(&mut state, &tokens.refresh_token, family_id, &device_id);
+        self.save(&state);
+        tracing::debug!(family_id = %family_id, "Rotated refresh token");
+    }
+
+    /// Give back a granted claim after the refresh failed upstream
+    pub fn release(&self, refresh_token: &str) {
+        let mut state = self.state();
+        if let Some(record) = state.tokens.get_mut(&hash_token(refresh_token)) {
+            if record.state == RefreshTokenState::Rotating {
+                record.state = RefreshTokenState::Active;
+                record.changed_at = Utc::now();
+                self.save(&state);
+            }
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, StoreState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait until every change so far has been written to the state file
+    pub async fn flush(&self) {
+        if let Some(writer) = &self.writer {
+            writer.flush().await;
+        }
+    }
+
+    fn save(&self, state: &StoreState) {
+        if let Some(writer) = &self.writer {
+            writer.queue(state);
+        }
+    }
+}
+
+fn insert_active(state: &mut StoreState, refresh_token: &str, family_id: &str, device_id: &str) {
+    let now = Utc::now();
+    state.tokens.insert(
+        hash_token(refresh_token),
+        RefreshTokenRecord {
+            family_id: family_id.to_string(),
+            state: RefreshTokenState::Active,
+            issued_at: now,
+            changed_at: now,
+            device_id: device_id.to_string(),
+            replacement: None,
+        },
+    );
+}
+
+/// Writes the state file on the blocking pool, so handlers never wait on the
+/// disk while holding the state lock. Only the newest snapshot is kept
+/// queued, so writes can't land out of order.
+struct StateWriter {
+    queued: watch::Sender<Snapshot>,
+    written: watch::Receiver<u64>,
+}
+
+#[derive(Clone, Default)]
+struct Snapshot {
+    generation: u64,
+    json: Arc<Vec<u8>>,
+}
+
+impl StateWriter {
+    fn spawn(path: PathBuf) -> Self {
+        let (queued, mut pending) = watch::channel(Snapshot::default());
+        let (done, written) = watch::channel(0);
+        tokio::spawn(async move {
+            // Still sees the last snapshot after the store is dropped
+            while pending.changed().await.is_ok() {
+                let snapshot = pending.borrow_and_update().clone();
+                let target = path.clone();
+                let result =
+                    tokio::task::spawn_blocking(move || write_state(&target, &snapshot.json))
+                        .await
+                        .map_err(std::io::Error::other)
+                        .and_then(|result| result);
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Failed to save refresh token store to {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+                let _ = done.send(snapshot.generation);
+            }
+        });
+        Self { queued, written }
+    }
+
+    fn queue(&self, state: &StoreState) {
+        match serde_json::to_vec(state) {
+            Ok(json) => self.queued.send_modify(|snapshot| {
+                snapshot.generation += 1;
+                snapshot.json = Arc::new(json);
+            }),
+            Err(e) => tracing::error!("Failed to serialize refresh token store: {}", e),
+        }
+    }
+
+    async fn flush(&self) {
+        let target = self.queued.borrow().generation;
+        let mut written = self.written.clone();
+        let _ = written.wait_for(|generation| *generation >= target).await;
+    }
+}
+
+/// Write the state next to `path` and move it into place, so a crash never
+/// leaves half a file
+fn write_state(path: &Path, json: &[u8]) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, json)?;
+    fs::rename(&tmp, path)
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn elapsed(now: DateTime<Utc>, since: DateTime<Utc>) -> Duration {
+    now.signed_duration_since(since)
+        .to_std()
+        .unwrap_or(Duration::ZERO)
+}
+
+/// Background task that forgets tokens and revocations older than the TTL,
+/// and pairs kept for retries once the grace window has passed
+async fn cleanup_expired_tokens(
+    state: Arc<Mutex<StoreState>>,
+    writer: Option<Arc<StateWriter>>,
+    ttl: Duration,
+    grace: Duration,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(3600));
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let initial_count = state.tokens.len();
+        state
+            .tokens
+            .retain(|_, record| elapsed(now, record.issued_at) < ttl);
+        state
+            .revoked_families
+            .retain(|_, revoked_at| elapsed(now, *revoked_at) < ttl);
+        for record in state.tokens.values_mut() {
+            if elapsed(now, record.changed_at) >= grace {
+                record.replacement = None;
+            }
+        }
+
+        let cleaned = initial_count.saturating_sub(state.tokens.len());
+        if cleaned > 0 {
+            tracing::info!("Forgot {} expired refresh tokens", cleaned);
+            if let Some(writer) = &writer {
+                writer.queue(&state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE: &str = "device";
+
+    fn pair(refresh_token: &str) -> TokenPair {
+        TokenPair {
+            access_token: format!("access-{}", refresh_token),
+            refresh_token: refresh_token.to_string(),
+            expires_at: Utc::now(),
+            scope: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn reusing_a_rotated_token_revokes_the_family() {
+        let store = RefreshTokenStore::new(None, 3600, 0).unwrap();
+        let family_id = store.issue("first", DEVICE);
+
+        assert_eq!(
+            store.claim("first", Some(DEVICE)),
+            RefreshClaim::Granted {
+                family_id: family_id.clone()
+            }
+        );
+        store.complete("first", &family_id, &pair("second"));
+
+        assert_eq!(
+            store.claim("first", Some(DEVICE)),
+            RefreshClaim::Reused {
+                family_id: family_id.clone()
+            }
+        );
+        // The legitimate holder of the newest token is locked out too
+        assert_eq!(store.claim("second", Some(DEVICE)), RefreshClaim::Revoked);
+    }
+
+    #[tokio::test]
+    async fn failed_refresh_can_be_retried() {
+        let store = RefreshTokenStore::new(None, 3600, 30).unwrap();
+        let family_id = store.issue("token", DEVICE);
+
+        assert!(matches!(
+            store.claim("token", Some(DEVICE)),
+            RefreshClaim::Granted { .. }
+        ));
+        store.release("token");
+        assert_eq!(
+            store.claim("token", Some(DEVICE)),
+            RefreshClaim::Granted { family_id }
+        );
+    }
+
+    #[tokio::test]
+    async fn a_retry_within_the_grace_window_gets_the_same_pair() {
+        let store = RefreshTokenStore::new(None, 3600, 30).unwrap();
+        let family_id = store.issue("first", DEVICE);
+
+        assert!(matches!(
+            store.claim("first", Some(DEVICE)),
+            RefreshClaim::Granted { .. }
+        ));
+        // A second process refreshing at the same moment waits for the first
+        assert_eq!(store.claim("first", Some(DEVICE)), RefreshClaim::Pending);
+
+        let second = pair("second");
+        store.complete("first", &family_id, &second);
+        assert_eq!(
+            store.claim("first", Some(DEVICE)),
+            RefreshClaim::Replayed {
+                tokens: Box::new(second)
+            }
+        );
+        assert!(matches!(
+            store.claim("second", Some(DEVICE)),
+            RefreshClaim::Granted { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_retry_from_another_device_revokes_the_family() {
+        let store = RefreshTokenStore::new(None, 3600, 30).unwrap();
+        let family_id = store.issue("first", DEVICE);
+        assert!(matches!(
+            store.claim("first", Some(DEVICE)),
+            RefreshClaim::Granted { .. }
+        ));
+        store.complete("first", &family_id, &pair("second"));
+
+        // Whoever copied the old token doesn't get the new pair
+        assert_eq!(
+            store.claim("first", Some("elsewhere")),
+            RefreshClaim::Reused {
+                family_id: family_id.clone()
+            }
+        );
+        assert_eq!(store.claim("first", None), RefreshClaim::Revoked);
+        assert_eq!(store.claim("second", Some(DEVICE)), RefreshClaim::Revoked);
+    }
+
+    #[tokio::test]
+    async fn tokens_the_server_never_issued_are_rejected() {
+        let store = RefreshTokenStore::new(None, 3600, 30).unwrap();
+        assert_eq!(store.claim("forged", Some(DEVICE)), RefreshClaim::Unknown);
+    }
+
+    #[tokio::test]
+    async fn rotation_survives_a_restart() {
+        let path = std::env::temp_dir().join(format!("ynat-refresh-{}.json", Uuid::new_v4()));
+        let store = RefreshTokenStore::new(Some(path.clone()), 3600, 30).unwrap();
+        let family_id = store.issue("first", DEVICE);
+        assert!(matches!(
+            store.claim("first", Some(DEVICE)),
+            RefreshClaim::Granted { .. }
+        ));
+        store.complete("first", &family_id, &pair("second"));
+        store.flush().await;
+        drop(store);
+
+        let store = RefreshTokenStore::new(Some(path.clone()), 3600, 30).unwrap();
+        // The pair kept for retries isn't written to disk, so this is reuse
+        assert_eq!(
+            store.claim("first", Some(DEVICE)),
+            RefreshClaim::Reused { family_id }
+        );
+        assert_eq!(store.claim("second", Some(DEVICE)), RefreshClaim::Revoked);
+
+        let _ = fs::remove_file(path);
+    }
+}
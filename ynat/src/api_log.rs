@@ -0,0 +1,223 @@
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+use ynab_api::{Exchange, Observer};
+
+/// Placeholder written over secret values before a payload is stored
+const REDACTED: &str = "[redacted]";
+
+/// A single API exchange captured for the debug inspector
+#[derive(Debug, Clone)]
+pub struct ApiRecord {
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    /// Method and path, e.g. `GET /v1/budgets/<id>/accounts`
+    pub endpoint: String,
+    pub status: u16,
+    /// Request body as sent, with secrets redacted
+    pub request: Option<Value>,
+    /// Response body as received, with secrets redacted
+    pub payload: Value,
+}
+
+impl ApiRecord {
+    /// Find the first JSON object in the payload whose `id` equals `id`
+    pub fn find_object(&self, id: &str) -> Option<&Value> {
+        find_by_id(&self.payload, id)
+    }
+}
+
+/// Thread-safe circular buffer of recent API payloads
+#[derive(Debug, Clone)]
+pub struct ApiLog {
+    records: Arc<RwLock<VecDeque<ApiRecord>>>,
+    max_records: usize,
+}
+
+impl ApiLog {
+    pub fn new(max_records: usize) -> Self {
+        Self {
+            records: Arc::new(RwLock::new(VecDeque::with_capacity(max_records))),
+            max_records,
+        }
+    }
+
+    /// Store the raw bodies of an exchange, redacting tokens
+    pub fn record(&self, exchange: &Exchange<'_>) {
+        self.push(ApiRecord {
+            timestamp: chrono::Local::now(),
+            endpoint: format!("{} {}", exchange.method, exchange.path),
+            status: exchange.status,
+            request: exchange.request_body.map(body_value),
+            payload: body_value(exchange.response_body),
+        });
+    }
+
+    /// Hook for the API client, so every exchange is recorded as it happens
+    pub fn observer(&self) -> Observer {
+        let log = self.clone();
+        Arc::new(move |exchange: &Exchange<'_>| log.record(exchange))
+    }
+
+    pub fn push(&self, record: ApiRecord) {
+        if self.max_records == 0 {
+            return;
+        }
+        let mut records = self.records.write().unwrap();
+        if records.len() >= self.max_records {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    pub fn get_records(&self) -> Vec<ApiRecord> {
+        self.records.read().unwrap().iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.read().unwrap().is_empty()
+    }
+}
+
+/// A body as JSON when it parses, otherwise as text
+fn body_value(body: &[u8]) -> Value {
+    let mut value = serde_json::from_slice(body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()));
+    redact(&mut value);
+    value
+}
+
+/// Whether a JSON key holds a credential that must never be displayed
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.contains("token") || key.contains("authorization") || key.contains("secret")
+}
+
+/// Replace the values of any secret-looking keys, at any depth
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    redact(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn find_by_id<'a>(value: &'a Value, id: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => {
+            if map.get("id").and_then(Value::as_str) == Some(id) {
+                return Some(value);
+            }
+            map.values().find_map(|v| find_by_id(v, id))
+        }
+        Value::Array(items) => items.iter().find_map(|v| find_by_id(v, id)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(log: &ApiLog, method: &str, path: &str, request: Option<&str>, response: &str) {
+        log.record(&Exchange {
+            method,
+            path,
+            request_body: request.map(str::as_bytes),
+            status: 200,
+            response_body: response.as_bytes(),
+        });
+    }
+
+    #[test]
+    fn record_redacts_nested_tokens() {
+        let log = ApiLog::new(10);
+        record(
+            &log,
+            "POST",
+            "/oauth/token",
+            Some(r#"{"refresh_token": "old", "grant_type": "refresh_token"}"#),
+            r#"{
+                "access_token": "abc",
+                "data": { "items": [{ "refresh_token": "def", "name": "ok" }] },
+                "Authorization": "Bearer abc"
+            }"#,
+        );
+
+        let record = &log.get_records()[0];
+        let payload = &record.payload;
+        assert_eq!(payload["access_token"], REDACTED);
+        assert_eq!(payload["Authorization"], REDACTED);
+        assert_eq!(payload["data"]["items"][0]["refresh_token"], REDACTED);
+        assert_eq!(payload["data"]["items"][0]["name"], "ok");
+        let request = record.request.as_ref().unwrap();
+        assert_eq!(request["refresh_token"], REDACTED);
+        assert_eq!(request["grant_type"], "refresh_token");
+    }
+
+    #[test]
+    fn record_keeps_the_bodies_as_sent_and_received() {
+        let log = ApiLog::new(10);
+        record(
+            &log,
+            "PATCH",
+            "/v1/budgets/b/transactions",
+            Some(r#"{"transactions":[{"id":"a","flag_color":null}]}"#),
+            // Fields ynat doesn't model are still shown
+            r#"{"data":{"transactions":[{"id":"a","new_field":true}]}}"#,
+        );
+        record(&log, "GET", "/v1/user", None, "<html>Bad Gateway</html>");
+
+        let records = log.get_records();
+        assert_eq!(records[0].endpoint, "PATCH /v1/budgets/b/transactions");
+        assert_eq!(
+            records[0].request,
+            Some(json!({ "transactions": [{ "id": "a", "flag_color": null }] }))
+        );
+        assert_eq!(
+            records[0].payload["data"]["transactions"][0]["new_field"],
+            true
+        );
+        assert_eq!(records[1].request, None);
+        assert_eq!(records[1].payload, json!("<html>Bad Gateway</html>"));
+    }
+
+    #[test]
+    fn push_evicts_oldest_when_full() {
+        let log = ApiLog::new(2);
+        for i in 0..3 {
+            record(&log, "GET", &format!("/{}", i), None, "{}");
+        }
+
+        let endpoints: Vec<_> = log.get_records().into_iter().map(|r| r.endpoint).collect();
+        assert_eq!(endpoints, vec!["GET /1", "GET /2"]);
+    }
+
+    #[test]
+    fn find_object_searches_nested_arrays() {
+        let log = ApiLog::new(1);
+        record(
+            &log,
+            "GET",
+            "/budgets/x/transactions",
+            None,
+            r#"{ "data": { "transactions": [{ "id": "a", "amount": 1 }, { "id": "b", "amount": 2 }] } }"#,
+        );
+
+        let record = &log.get_records()[0];
+        assert_eq!(record.find_object("b").unwrap()["amount"], 2);
+        assert!(record.find_object("missing").is_none());
+    }
+}
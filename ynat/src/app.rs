@@ -9,23 +9,71 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::sync::Arc;
 use ynat_auth::StoredToken;
 
+use crate::api_log::ApiLog;
 use crate::background::{data_loader::DataLoader, BackgroundTaskManager};
 use crate::cache::Cache;
 use crate::commands::{executor, handlers};
+use crate::config::AppConfig;
+use crate::error::{AppError, ErrorKind};
 use crate::input::KeyEvent;
 use crate::log_buffer::LogBuffer;
 use crate::logging::init_logging_with_buffer;
+use crate::session_log::SessionRecorder;
+use crate::settings::{LastScreen, Settings};
 use crate::state::AppState;
 use crate::ui::screens::Screen;
-use ynab_api::Client;
+use ynab_api::{Client, Request};
 
 pub struct App {
     token: StoredToken,
+    user_id: Option<String>,
+    resume: bool,
 }
 
 impl App {
     pub fn new(token: StoredToken) -> Self {
-        Self { token }
+        Self {
+            token,
+            user_id: None,
+            resume: false,
+        }
+    }
+
+    /// Reopen the screen open at the last quit, even without
+    /// `[startup] restore_screen`
+    pub fn resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// An app for `token`, checked against YNAB first. A token YNAB rejects is
+    /// dropped and the user signs in again, instead of every load failing. If
+    /// YNAB can't be reached the token is kept, since the cache works offline.
+    pub async fn verified(token: StoredToken) -> Result<Self> {
+        match fetch_user_id(&token).await {
+            Ok(user_id) => Ok(Self {
+                user_id: Some(user_id),
+                ..Self::new(token)
+            }),
+            // Signing in again wouldn't change a token the user supplied
+            Err(e) if e.kind == ErrorKind::Unauthorized && token.is_external() => {
+                anyhow::bail!(
+                    "YNAB rejected the supplied access token; check {} or the token piped in",
+                    ynat_auth::TOKEN_CMD_VAR
+                )
+            }
+            Err(e) if e.kind == ErrorKind::Unauthorized => {
+                eprintln!("YNAB no longer accepts the saved session, signing in again.");
+                ynat_auth::TokenStore::new()?.delete_token()?;
+                let token = ynat_auth::authenticate().await?;
+                let user_id = fetch_user_id(&token).await.ok();
+                Ok(Self {
+                    user_id,
+                    ..Self::new(token)
+                })
+            }
+            Err(_) => Ok(Self::new(token)),
+        }
     }
 
     pub async fn run(&self) -> Result<()> {
@@ -34,6 +82,9 @@ impl App {
         let _log_path = init_logging_with_buffer(log_buffer.clone())?;
 
         tracing::info!("ynat starting");
+        if let Some(user_id) = &self.user_id {
+            tracing::info!("Signed in as YNAB user {}", user_id);
+        }
 
         let mut terminal = self.init()?;
         let cache = Arc::new(Cache::new().await?);
@@ -41,35 +92,110 @@ impl App {
         let (data_tx, mut data_rx) = tokio::sync::mpsc::unbounded_channel();
 
         let mut ui_state = AppState::new();
+        ui_state.user_id = self.user_id.clone();
+        ui_state.config = AppConfig::load();
+        ui_state.settings = crate::settings::load().await;
+        ui_state.read_only = self.token.is_read_only() || ui_state.config.api.read_only;
+        if ui_state.read_only {
+            tracing::info!("Read-only: changes to the budget are turned off");
+        }
+        crate::i18n::init(ui_state.config.display.locale.as_deref());
         let mut task_manager = BackgroundTaskManager::new();
 
-        let api_client = Arc::new(Client::new(&self.token.access_token));
-        let data_loader = DataLoader::new(api_client.clone(), cache.clone(), data_tx.clone());
+        // Only record API payloads when the debug inspector is enabled
+        let api_log = ApiLog::new(ui_state.config.debug.inspector_records);
+        let mut api_client = Client::new(&self.token.access_token);
+        if ui_state.config.debug.inspector {
+            tracing::info!("API inspector enabled");
+            api_client = api_client.with_observer(api_log.observer());
+        }
+        let api_client = Arc::new(api_client);
+        let data_loader = DataLoader::new(api_client.clone(), cache.clone(), data_tx.clone())
+            .with_timeouts(
+                std::time::Duration::from_secs(ui_state.config.api.list_timeout_secs),
+                std::time::Duration::from_secs(ui_state.config.api.mutation_timeout_secs),
+            );
+
+        let mut recorder = match &ui_state.config.debug.record_session {
+            Some(path) => {
+                tracing::info!("Recording session to {}", path.display());
+                Some(SessionRecorder::create(path)?)
+            }
+            None => None,
+        };
 
         let mut event_stream = EventStream::new();
 
-        self.init_data(&mut ui_state, &mut task_manager, &data_loader);
+        load_initial_data(&mut ui_state, &mut task_manager, &data_loader);
+        if self.resume || ui_state.config.startup.restore_screen {
+            restore_last_screen(&mut ui_state, &mut task_manager, &data_loader);
+        }
+        let mut saved_settings = ui_state.settings.clone();
+        // Send anything changed while offline last time
+        if !ui_state.read_only {
+            data_loader.sync_pending_writes();
+        }
+
+        // Only repaint in response to input, data, new log lines, or a spinner frame.
+        // With animations off (explicitly or via low-bandwidth mode over SSH) the
+        // tick only checks for new log lines.
+        let animate = ui_state.config.animations_enabled();
+        if !animate {
+            tracing::info!("Spinner animation disabled");
+        }
+        crate::ui::components::loading_indicator::set_animated(animate);
+        let mut needs_redraw = true;
 
         tracing::info!("Entering main event loop");
 
-        let mut interval = tokio::time::interval(std::time::Duration::from_millis(100));
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(
+            ui_state.config.animation.interval_ms.max(16),
+        ));
+        let mut auto_refresh_secs = ui_state.config.auto_refresh.interval_secs;
+        let mut auto_refresh = auto_refresh_interval(auto_refresh_secs);
+        if auto_refresh_secs == 0 {
+            tracing::info!("Auto-refresh disabled");
+        }
         loop {
             // Update total_entries for logs screen if active
             if let Screen::Logs(logs_state) = ui_state.current_screen_mut() {
-                logs_state.total_entries = log_buffer.len();
+                if logs_state.total_entries != log_buffer.len() {
+                    logs_state.total_entries = log_buffer.len();
+                    needs_redraw = true;
+                }
             }
 
-            terminal.draw(|f| {
-                crate::ui::render_app(f, &ui_state, &log_buffer);
-            })?;
+            // Keep the inspector's record count in sync with the API log
+            if let Screen::Inspector(inspector_state) = ui_state.current_screen_mut() {
+                let total = inspector_state
+                    .visible_records(&api_log.get_records())
+                    .len();
+                if inspector_state.total_records != total {
+                    inspector_state.total_records = total;
+                    needs_redraw = true;
+                }
+            }
+
+            if needs_redraw {
+                let frame = terminal.draw(|f| {
+                    crate::ui::render_app(f, &ui_state, &log_buffer, &api_log);
+                })?;
+                ui_state.table_rows = crate::ui::layouts::table_rows(frame.area.height);
+                needs_redraw = false;
+            }
 
             tokio::select! {
                 _ = interval.tick() => {
-                    if let Some(throbber_state) = ui_state.loading_state() {
-                        throbber_state.calc_next();
+                    if animate {
+                        if let Some(throbber_state) = ui_state.loading_state() {
+                            throbber_state.calc_next();
+                            needs_redraw = true;
+                        }
                     }
                 }
                 Some(Ok(event)) = event_stream.next() => {
+                    // Any terminal event (including resize) may change what's on screen
+                    needs_redraw = true;
                     match event {
                         Event::Key(key) if matches!(key.kind, KeyEventKind::Press) => {
                             // Don't log when on logs screen to avoid feedback loop
@@ -81,12 +207,22 @@ impl App {
                                 if !on_logs_screen {
                                     tracing::info!("Executing command: {:?}", command);
                                 }
+                                if let Some(recorder) = recorder.as_mut() {
+                                    recorder.command(&command);
+                                }
                                 executor::execute_command(
                                     command,
                                     &mut ui_state,
                                     &mut task_manager,
                                     &data_loader,
                                 );
+                                save_settings_if_changed(&ui_state, &mut saved_settings);
+                                write_config_edits(&mut ui_state).await;
+                                // The settings screen can change the interval
+                                if ui_state.config.auto_refresh.interval_secs != auto_refresh_secs {
+                                    auto_refresh_secs = ui_state.config.auto_refresh.interval_secs;
+                                    auto_refresh = auto_refresh_interval(auto_refresh_secs);
+                                }
                             }
                         }
                         _ => {
@@ -94,9 +230,16 @@ impl App {
                         }
                     }
                 }
+                _ = auto_refresh.tick(), if auto_refresh_secs > 0 => {
+                    executor::check_for_changes(&ui_state, &mut task_manager, &data_loader);
+                }
                 Some(data_event) = data_rx.recv() => {
+                    needs_redraw = true;
                     tracing::debug!("Received data event: {:?}", data_event);
-                    crate::state::reducer::reduce_data_event(&mut ui_state, data_event);
+                    if let Some(recorder) = recorder.as_mut() {
+                        recorder.event(&data_event);
+                    }
+                    crate::app_core::reduce_data_event(&mut ui_state, data_event);
                 }
             }
 
@@ -112,6 +255,12 @@ impl App {
         // Cancel all background data loading tasks
         task_manager.cancel_all();
 
+        // Remember where we were for `--resume`
+        ui_state.settings.last_screen = LastScreen::of(&ui_state);
+        if let Err(e) = crate::settings::save(&ui_state.settings).await {
+            tracing::error!("Failed to save settings: {}", e);
+        }
+
         self.exit(terminal)?;
 
         Ok(())
@@ -125,50 +274,6 @@ impl App {
         Terminal::new(backend)
     }
 
-    fn init_data(
-        &self,
-        ui_state: &mut AppState,
-        task_manager: &mut BackgroundTaskManager,
-        data_loader: &DataLoader,
-    ) {
-        tracing::info!("Loading default budget accounts");
-        executor::execute_command(
-            crate::commands::AppCommand::LoadBudgets {
-                force_refresh: false,
-                load_accounts: false,
-            },
-            ui_state,
-            task_manager,
-            data_loader,
-        );
-        executor::execute_command(
-            crate::commands::AppCommand::LoadAccounts {
-                budget_id: "default".to_string(),
-                budget: Box::new(None),
-                force_refresh: false,
-            },
-            ui_state,
-            task_manager,
-            data_loader,
-        );
-        executor::execute_command(
-            crate::commands::AppCommand::LoadPayees {
-                budget_id: "default".to_string(),
-            },
-            ui_state,
-            task_manager,
-            data_loader,
-        );
-        executor::execute_command(
-            crate::commands::AppCommand::LoadCategories {
-                budget_id: "default".to_string(),
-            },
-            ui_state,
-            task_manager,
-            data_loader,
-        );
-    }
-
     fn exit(
         &self,
         mut terminal: Terminal<CrosstermBackend<std::io::Stdout>>,
@@ -183,3 +288,113 @@ impl App {
         Ok(())
     }
 }
+
+async fn fetch_user_id(token: &StoredToken) -> Result<String, AppError> {
+    let response = Client::new(&token.access_token)
+        .send(Request::user().get())
+        .await?;
+    Ok(response.data.user.id.to_string())
+}
+
+/// Write the app-wide settings once a command changes them. Done here rather
+/// than in the executor so replays and tests never touch the user's file.
+fn save_settings_if_changed(ui_state: &AppState, saved: &mut Settings) {
+    if ui_state.settings == *saved {
+        return;
+    }
+    *saved = ui_state.settings.clone();
+    let settings = saved.clone();
+    tokio::spawn(async move {
+        if let Err(e) = crate::settings::save(&settings).await {
+            tracing::error!("Failed to save settings: {}", e);
+        }
+    });
+}
+
+/// Write what the settings screen changed to `config.toml`. Awaited rather
+/// than spawned so quick changes can't overwrite each other's edits.
+async fn write_config_edits(ui_state: &mut AppState) {
+    if ui_state.config_edits.is_empty() {
+        return;
+    }
+    let edits = std::mem::take(&mut ui_state.config_edits);
+    if let Err(e) = crate::config_edit::write(&AppConfig::path(), &edits).await {
+        tracing::error!("Failed to save config: {}", e);
+    }
+}
+
+/// Ticks every `secs` seconds, starting one period from now: a first tick
+/// straight away would come while the screen is still loading
+fn auto_refresh_interval(secs: u64) -> tokio::time::Interval {
+    let period = std::time::Duration::from_secs(secs.max(1));
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval
+}
+
+/// Open the screen saved at the last quit on top of the accounts list that
+/// [`load_initial_data`] started
+fn restore_last_screen(
+    ui_state: &mut AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+) {
+    let (Some(last_screen), Some(budget_id)) = (
+        ui_state.settings.last_screen.clone(),
+        ui_state.current_budget_id.clone(),
+    ) else {
+        return;
+    };
+    if let Some(command) = last_screen.command(budget_id) {
+        tracing::info!("Restoring last screen: {:?}", last_screen);
+        executor::execute_command(command, ui_state, task_manager, data_loader);
+    }
+}
+
+/// Start loading the last budget opened, or YNAB's default one, as the TUI
+/// does on startup
+pub(crate) fn load_initial_data(
+    ui_state: &mut AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+) {
+    let budget_id = ui_state
+        .settings
+        .last_budget_id
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    tracing::info!("Loading accounts for budget {}", budget_id);
+    executor::execute_command(
+        crate::commands::AppCommand::LoadBudgets {
+            force_refresh: false,
+            load_accounts: false,
+        },
+        ui_state,
+        task_manager,
+        data_loader,
+    );
+    executor::execute_command(
+        crate::commands::AppCommand::LoadAccounts {
+            budget_id: budget_id.clone(),
+            budget: Box::new(None),
+            force_refresh: false,
+        },
+        ui_state,
+        task_manager,
+        data_loader,
+    );
+    executor::execute_command(
+        crate::commands::AppCommand::LoadPayees {
+            budget_id: budget_id.clone(),
+        },
+        ui_state,
+        task_manager,
+        data_loader,
+    );
+    executor::execute_command(
+        crate::commands::AppCommand::LoadCategories { budget_id },
+        ui_state,
+        task_manager,
+        data_loader,
+    );
+}
@@ -13,6 +13,10 @@ pub trait DataEventHandler {
     /// In production, this spawns background tasks and manages the data loader.
     /// In tests, this can update state synchronously without side effects.
     fn execute_with_context(&mut self, command: AppCommand, state: &mut AppState);
+
+    /// Run an auto-refresh check for the current screen. Handlers without
+    /// background loading have nothing to check.
+    fn check_for_changes(&mut self, _state: &AppState) {}
 }
 
 /// Testable application core without terminal dependencies
@@ -51,7 +55,13 @@ impl<H: DataEventHandler> AppCore<H> {
     /// Data events come from background tasks in production (API responses, cache loads).
     /// In tests, you can inject events directly to simulate async operations.
     pub fn handle_data_event(&mut self, event: DataEvent) {
-        reducer::reduce_data_event(&mut self.ui_state, event);
+        reduce_data_event(&mut self.ui_state, event);
+    }
+
+    /// Check the current screen for changes made elsewhere, as the app does
+    /// on each auto-refresh tick
+    pub fn check_for_changes(&mut self) {
+        self.handler.check_for_changes(&self.ui_state);
     }
 
     /// Get read-only access to the current UI state (for rendering or assertions)
@@ -59,8 +69,50 @@ impl<H: DataEventHandler> AppCore<H> {
         &self.ui_state
     }
 
+    /// Get mutable access to the handler (e.g. to collect its data events)
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
     /// Check if the application should quit
     pub fn should_quit(&self) -> bool {
         self.ui_state.should_quit
     }
 }
+
+/// Pure state transition for a data event: route it to its domain's reducer
+pub fn reduce_data_event(state: &mut AppState, event: DataEvent) {
+    match event {
+        DataEvent::Budget(event) => reducer::reduce_budget_event(state, event),
+        DataEvent::Transaction(event) => reducer::reduce_transaction_event(state, event),
+        DataEvent::Scheduled(event) => reducer::reduce_scheduled_event(state, event),
+        DataEvent::Plan(event) => reducer::reduce_plan_event(state, event),
+        DataEvent::Payee(event) => reducer::reduce_payee_event(state, event),
+        DataEvent::Report(event) => reducer::reduce_report_event(state, event),
+        DataEvent::LoadError { error } => reducer::reduce_load_error(state, error),
+        DataEvent::CacheRecovered => {
+            state.notice = Some(crate::i18n::t("notice-cache-recovered").to_string());
+        }
+        DataEvent::RateLimitWarning { rate_limit } => state.rate_limit_warning = rate_limit,
+        DataEvent::WriteQueued { pending } => reducer::reduce_write_queued(state, pending),
+        DataEvent::PendingWritesSynced { synced, pending } => {
+            reducer::reduce_pending_writes_synced(state, synced, pending)
+        }
+        DataEvent::PendingWriteRejected {
+            change,
+            unconfirmed,
+            error,
+        } => {
+            let key = if unconfirmed {
+                "notice-pending-write-unconfirmed"
+            } else {
+                "notice-pending-write-rejected"
+            };
+            state.notice = Some(
+                crate::i18n::t(key)
+                    .replace("{change}", &change)
+                    .replace("{error}", &error.message),
+            );
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Suggestions for categories that have gone unused long enough to archive.
+//!
+//! YNAB's API can't hide a category, so archiving moves it into a group the
+//! user keeps for that, named one of [`ARCHIVE_GROUPS`].
+
+use crate::reports::variance::INTERNAL_GROUP;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ynab_api::endpoints::{
+    categories::{Category, CategoryGroup},
+    months::MonthDetail,
+};
+
+/// Months a category must sit with nothing budgeted and no activity before it is suggested
+pub const IDLE_MONTHS: usize = 6;
+
+/// Names of the group archived categories are moved into, matched ignoring case
+pub const ARCHIVE_GROUPS: &[&str] = &["Archive", "Archived", "Hidden"];
+
+pub fn is_archive_group(name: &str) -> bool {
+    ARCHIVE_GROUPS
+        .iter()
+        .any(|archive| archive.eq_ignore_ascii_case(name.trim()))
+}
+
+/// The group to move archived categories into, if the budget has one
+pub fn archive_group(groups: &[CategoryGroup]) -> Option<&CategoryGroup> {
+    groups
+        .iter()
+        .find(|group| !group.deleted && is_archive_group(&group.name))
+}
+
+/// A category that looks safe to hide
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveSuggestion {
+    pub category_id: String,
+    pub category_name: String,
+    pub group_name: Option<String>,
+}
+
+/// Categories that are visible, hold no money, and had nothing budgeted and
+/// no activity in any of `months`. Returns nothing unless at least
+/// [`IDLE_MONTHS`] months were given, so a short history never suggests anything.
+pub fn suggestions(months: &[MonthDetail]) -> Vec<ArchiveSuggestion> {
+    if months.len() < IDLE_MONTHS {
+        return Vec::new();
+    }
+
+    // How many months each category was idle, and its latest state
+    let mut idle: HashMap<_, (usize, &Category)> = HashMap::new();
+    let mut order = Vec::new();
+    for category in months.iter().flat_map(|m| &m.categories) {
+        let untouched = i64::from(category.budgeted) == 0 && i64::from(category.activity) == 0;
+        let entry = idle.entry(category.id).or_insert_with(|| {
+            order.push(category.id);
+            (0, category)
+        });
+        if untouched {
+            entry.0 += 1;
+        }
+        entry.1 = category;
+    }
+
+    order
+        .into_iter()
+        .filter_map(|id| idle.get(&id))
+        .filter(|(count, category)| {
+            *count == months.len()
+                && !category.hidden
+                && !category.deleted
+                && i64::from(category.balance) == 0
+                && category.category_group_name.as_deref() != Some(INTERNAL_GROUP)
+                && !category
+                    .category_group_name
+                    .as_deref()
+                    .is_some_and(is_archive_group)
+        })
+        .map(|(_, category)| ArchiveSuggestion {
+            category_id: category.id.to_string(),
+            category_name: category.name.clone(),
+            group_name: category.category_group_name.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{category, month};
+
+    #[test]
+    fn suggests_categories_idle_in_every_month() {
+        let mut months: Vec<MonthDetail> = (0..IDLE_MONTHS)
+            .map(|_| {
+                month(vec![
+                    category("Old hobby").build(),
+                    category("Groceries").amounts(400_000, -380_000).build(),
+                ])
+            })
+            .collect();
+
+        let names: Vec<_> = suggestions(&months)
+            .into_iter()
+            .map(|s| s.category_name)
+            .collect();
+        assert_eq!(names, vec!["Old hobby"]);
+
+        // A single month of activity is enough to keep it
+        months[2] = month(vec![category("Old hobby").amounts(0, -5_000).build()]);
+        assert!(suggestions(&months).is_empty());
+    }
+
+    #[test]
+    fn skips_categories_already_archived() {
+        let archived = category("Old hobby").group("archive").build();
+        let months: Vec<MonthDetail> = (0..IDLE_MONTHS)
+            .map(|_| month(vec![archived.clone()]))
+            .collect();
+        assert!(suggestions(&months).is_empty());
+    }
+
+    #[test]
+    fn finds_the_archive_group_by_name() {
+        let group = |name: &str, deleted: bool| CategoryGroup {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            hidden: false,
+            deleted,
+            categories: Vec::new(),
+        };
+        let groups = vec![
+            group("Everyday", false),
+            group("Archive", true),
+            group(" hidden ", false),
+        ];
+        assert_eq!(archive_group(&groups).unwrap().name, " hidden ");
+        assert!(archive_group(&groups[..2]).is_none());
+    }
+
+    #[test]
+    fn short_history_suggests_nothing() {
+        let months = vec![month(vec![category("Old hobby").build()])];
+        assert!(suggestions(&months).is_empty());
+    }
+}
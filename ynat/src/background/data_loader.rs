@@ -1,22 +1,122 @@
-use crate::cache::Cache;
-use crate::events::DataEvent;
+use super::faults::{FaultInjector, Faults};
+use super::write_queue::{self, PendingWrite, WriteQueue};
+use crate::archive;
+use crate::background;
+use crate::budget_settings::{self, BudgetSettings};
+use crate::cache::{Cache, CacheError};
+use crate::category_suggestions;
+use crate::config::{ApiConfig, ExportConfig};
+use crate::error::{AppError, ErrorKind};
+use crate::events::{
+    BudgetEvent, DataEvent, PayeeEvent, PlanEvent, ReportEvent, ScheduledEvent, TransactionEvent,
+};
+use crate::export;
+use crate::health::{self, HealthItem};
+use crate::maps;
+use crate::payee_cleanup::{self, PayeeMerge};
+use crate::reimbursements::{self, ReimbursementEntry};
+use crate::reports::cash_flow::{self, CashFlowProjection, ProjectionHorizon};
+use crate::reports::net_worth::{self, NetWorth};
+use crate::reports::obligations::{self, ObligationsForecast};
+use crate::reports::spending::{self, SpendingGrouping};
+use crate::reports::{self, heatmap, income_expense, variance, ReportPeriod};
+use crate::rules::RuleMatch;
+use crate::state::validators;
+use crate::utils::balance_history::{self, RecentSpending};
+use crate::utils::ynab_export;
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 use ynab_api::{
     endpoints::{
-        transactions::{NewTransaction, TransactionUpdate},
+        accounts::Account,
+        categories::{Category, SaveCategory},
+        months::MonthDetail,
+        payees::Payee,
+        transactions::{
+            BulkTransactionUpdate, NewTransaction, Transaction, TransactionType, TransactionUpdate,
+        },
         BudgetId, TransactionId,
     },
-    Client, Request,
+    ApiRequest, Client, Method, Request, YnabApiError,
 };
 
+/// Requests left in the hour at which background checks for changes stop
+const RATE_LIMIT_RESERVE: u32 = 20;
+
+/// How long to wait between attempts to send changes made offline
+const SYNC_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Days of transaction history fetched per page
+const HISTORY_WINDOW_DAYS: i64 = 90;
+
+/// The server knowledge to ask for changes since: the cache's, unless
+/// another process has moved the cache on past what's on screen here, in
+/// which case those changes are fetched again for this one
+fn catch_up_from(known: Option<i64>, cached: i64) -> i64 {
+    match known {
+        Some(known) if known < cached => {
+            tracing::info!(
+                "Cache updated by another ynat (knowledge {} -> {}), catching up",
+                known,
+                cached
+            );
+            known
+        }
+        _ => cached,
+    }
+}
+
+/// Start of the history window ending at `end`
+fn history_window_start(end: NaiveDate) -> NaiveDate {
+    end - chrono::Duration::days(HISTORY_WINDOW_DAYS)
+}
+
+/// Town or city at `place`, from the geocoder URL configured under `[maps]`.
+/// Failures only cost the name, so they're logged rather than shown.
+async fn reverse_geocode(geocoder: &str, place: &maps::Place) -> Option<String> {
+    let url = maps::render_args(&[geocoder.to_string()], place).remove(0);
+    let response = reqwest::Client::builder()
+        .user_agent(concat!("ynat/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(10))
+        .build()
+        .ok()?
+        .get(&url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    match response {
+        Ok(response) => maps::city(&response.json().await.ok()?),
+        Err(e) => {
+            tracing::warn!("Reverse geocoding failed: {}", e);
+            None
+        }
+    }
+}
+
 /// Data loader that implements cache-first loading with delta updates
 #[derive(Clone)]
 pub struct DataLoader {
     pub api_client: Arc<Client>,
     pub cache: Arc<Cache>,
     pub data_tx: mpsc::UnboundedSender<DataEvent>,
+    /// Set once the user has been told about an unreadable cache file
+    cache_recovery_notified: Arc<AtomicBool>,
+    /// Latency and failures added to API requests; only set in tests
+    faults: Option<Arc<FaultInjector>>,
+    /// How long a GET may take before it's abandoned
+    list_timeout: Duration,
+    /// How long a create, update or delete may take before it's abandoned
+    mutation_timeout: Duration,
+    /// Changes waiting for YNAB to be reachable
+    write_queue: Arc<WriteQueue>,
+    /// Set while the hourly quota is down to [`RATE_LIMIT_RESERVE`]
+    rate_limit_low: Arc<AtomicBool>,
 }
 
 impl DataLoader {
@@ -25,10 +125,171 @@ impl DataLoader {
         cache: Arc<Cache>,
         data_tx: mpsc::UnboundedSender<DataEvent>,
     ) -> Self {
+        let write_queue = Arc::new(WriteQueue::new(cache.clone()));
         Self {
             api_client,
             cache,
             data_tx,
+            cache_recovery_notified: Arc::new(AtomicBool::new(false)),
+            faults: None,
+            list_timeout: Duration::from_secs(ApiConfig::default().list_timeout_secs),
+            mutation_timeout: Duration::from_secs(ApiConfig::default().mutation_timeout_secs),
+            write_queue,
+            rate_limit_low: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Give up on reads after `list` and on changes after `mutation`
+    pub fn with_timeouts(mut self, list: Duration, mutation: Duration) -> Self {
+        self.list_timeout = list;
+        self.mutation_timeout = mutation;
+        self
+    }
+
+    /// Delay or fail API requests as `faults` describes
+    pub fn with_faults(mut self, faults: Faults) -> Self {
+        self.faults = Some(Arc::new(FaultInjector::new(faults)));
+        self
+    }
+
+    /// Send an API request, through the fault injector if there is one.
+    ///
+    /// A request that outlasts its timeout fails like an unreachable server,
+    /// so the screen waiting on it shows an error instead of loading forever.
+    /// When called from a background task, replacing or stopping the task
+    /// drops the request.
+    pub async fn send<R: ApiRequest>(&self, request: R) -> Result<R::Response, YnabApiError> {
+        let timeout = if R::METHOD == Method::GET {
+            self.list_timeout
+        } else {
+            self.mutation_timeout
+        };
+        let cancel = background::current_cancellation().unwrap_or_default();
+        let attempt = async {
+            if let Some(faults) = &self.faults {
+                faults.before_request().await?;
+            }
+            self.api_client.send_with_cancel(request, &cancel).await
+        };
+        let result = tokio::time::timeout(timeout, attempt)
+            .await
+            .unwrap_or(Err(YnabApiError::TimedOut(timeout)));
+        self.check_rate_limit();
+        result
+    }
+
+    /// Warn the UI while YNAB's hourly quota is nearly used up, and clear the
+    /// warning once it has room again
+    fn check_rate_limit(&self) {
+        let Some(rate_limit) = self.api_client.rate_limit() else {
+            return;
+        };
+        let low = rate_limit.remaining() <= RATE_LIMIT_RESERVE;
+        let was_low = self.rate_limit_low.swap(low, Ordering::Relaxed);
+        if low || was_low {
+            let _ = self.data_tx.send(DataEvent::RateLimitWarning {
+                rate_limit: low.then_some(rate_limit),
+            });
+        }
+    }
+
+    /// Whether to skip checking cached data for changes, keeping the last of
+    /// the hourly quota for things the user asks for. Refreshing with `r`
+    /// still fetches.
+    fn saving_quota(&self) -> bool {
+        let saving = self.rate_limit_low.load(Ordering::Relaxed);
+        if saving {
+            tracing::info!("Few API requests left this hour, showing cached data as is");
+        }
+        saving
+    }
+
+    /// Unwrap a cache read, treating an unreadable file like a miss so the
+    /// caller falls through to a full fetch, which rewrites it
+    fn cached<T>(&self, what: &str, result: Result<Option<T>, CacheError>) -> Option<T> {
+        match result {
+            Ok(cached) => cached,
+            Err(e) => {
+                self.cache_unreadable(what, &e);
+                None
+            }
+        }
+    }
+
+    /// Log an unreadable cache file and tell the user the first time it happens
+    fn cache_unreadable(&self, what: &str, error: &CacheError) {
+        tracing::warn!("Cached {} unreadable, refetching from API: {}", what, error);
+        if !self.cache_recovery_notified.swap(true, Ordering::Relaxed) {
+            let _ = self.data_tx.send(DataEvent::CacheRecovered);
+        }
+    }
+
+    /// Fetch an account's transactions and rewrite its cache, without updating any screen
+    async fn refetch_transactions(
+        &self,
+        budget_id: &str,
+        account_id: &str,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let account_uuid = Uuid::parse_str(account_id)
+            .map_err(|e| AppError::new(ErrorKind::Other, e.to_string()))?;
+        let req = Request::transactions()
+            .with_budget(budget_id.to_string().into())
+            .list(account_uuid);
+        let response = self.send(req).await?;
+
+        let transactions: Vec<_> = response
+            .data
+            .transactions
+            .into_iter()
+            .filter(|t| !t.deleted)
+            .collect();
+        let server_knowledge = response.data.server_knowledge.map(|k| k.inner());
+        let _ = self
+            .cache
+            .set_transactions(budget_id, account_id, &transactions, server_knowledge, None)
+            .await;
+        Ok(transactions)
+    }
+
+    /// Cached transactions for an account, refetching them if the cache file is unreadable.
+    /// `None` if the account was never loaded.
+    async fn cached_or_refetched_transactions(
+        &self,
+        budget_id: &str,
+        account_id: &str,
+    ) -> Option<Vec<Transaction>> {
+        match self.cache.get_transactions(budget_id, account_id).await {
+            Ok(cached) => cached.map(|c| c.transactions),
+            Err(e) => {
+                self.cache_unreadable("transactions", &e);
+                self.refetch_transactions(budget_id, account_id)
+                    .await
+                    .map_err(|e| tracing::error!("Failed to refetch transactions: {}", e))
+                    .ok()
+            }
+        }
+    }
+
+    /// Fetch the budget's date and currency formats, for when it was opened
+    /// without a budget summary that has them
+    pub async fn load_budget_formats(&self, budget_id: String) {
+        let req = Request::budgets()
+            .settings()
+            .budget_id(BudgetId::from(budget_id.clone()));
+        match self.send(req).await {
+            Ok(response) => {
+                let settings = response.data.settings;
+                let _ = self.data_tx.send(
+                    BudgetEvent::BudgetFormatsLoaded {
+                        budget_id,
+                        date_format: settings.date_format,
+                        currency_format: settings.currency_format,
+                    }
+                    .into(),
+                );
+            }
+            // Amounts keep the fallback format
+            Err(e) => tracing::warn!("Failed to load budget settings: {}", e),
         }
     }
 
@@ -38,13 +299,16 @@ impl DataLoader {
 
         // Step 1: Try cache first (fast path)
         if !force_refresh {
-            if let Ok(Some(cached)) = self.cache.get_budgets().await {
+            if let Some(cached) = self.cached("budgets", self.cache.get_budgets().await) {
                 tracing::debug!("Loaded {} budgets from cache", cached.budgets.len());
                 // Send cached data immediately
-                let _ = self.data_tx.send(DataEvent::BudgetsCacheLoaded {
-                    budgets: cached.budgets.clone(),
-                    default_budget: cached.default_budget.clone(),
-                });
+                let _ = self.data_tx.send(
+                    BudgetEvent::BudgetsCacheLoaded {
+                        budgets: cached.budgets.clone(),
+                        default_budget: cached.default_budget.clone(),
+                    }
+                    .into(),
+                );
 
                 // TODO: Implement delta for budgets if YNAB API supports it
                 // For now, we skip delta check for budgets
@@ -57,14 +321,17 @@ impl DataLoader {
         // Step 2: Load from API (slower path or forced refresh)
         tracing::debug!("Fetching budgets from API");
         let req = Request::budgets().list().include_accounts(include_accounts);
-        match self.api_client.send(req).await {
+        match self.send(req).await {
             Ok(response) => {
                 tracing::info!("Loaded {} budgets from API", response.data.budgets.len());
                 // Send fresh data
-                let _ = self.data_tx.send(DataEvent::BudgetsLoaded {
-                    budgets: response.data.budgets.clone(),
-                    default_budget: response.data.default_budget.clone(),
-                });
+                let _ = self.data_tx.send(
+                    BudgetEvent::BudgetsLoaded {
+                        budgets: response.data.budgets.clone(),
+                        default_budget: response.data.default_budget.clone(),
+                    }
+                    .into(),
+                );
 
                 // Update cache in background
                 let cache = self.cache.clone();
@@ -77,9 +344,7 @@ impl DataLoader {
             }
             Err(e) => {
                 tracing::error!("Failed to load budgets from API: {}", e);
-                let _ = self.data_tx.send(DataEvent::LoadError {
-                    error: e.to_string(),
-                });
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
             }
         }
     }
@@ -92,16 +357,37 @@ impl DataLoader {
             force_refresh
         );
 
-        // Step 1: Try cache first (fast path)
         if !force_refresh {
-            if let Ok(Some(cached)) = self.cache.get_accounts(&budget_id).await {
+            // Opening the budget: its preferences go first so they apply to the cached list
+            let settings = budget_settings::load(&budget_id).await;
+            let _ = self.data_tx.send(
+                BudgetEvent::BudgetSettingsLoaded {
+                    budget_id: budget_id.clone(),
+                    settings,
+                }
+                .into(),
+            );
+
+            // Step 1: Try cache first (fast path)
+            if let Some(cached) = self.cached("accounts", self.cache.get_accounts(&budget_id).await)
+            {
                 tracing::debug!("Loaded {} accounts from cache", cached.accounts.len());
                 // Send cached data immediately
-                let _ = self.data_tx.send(DataEvent::AccountsCacheLoaded {
-                    accounts: cached.accounts.clone(),
-                });
+                let _ = self.data_tx.send(
+                    BudgetEvent::AccountsCacheLoaded {
+                        accounts: cached.accounts.clone(),
+                    }
+                    .into(),
+                );
+                self.cache
+                    .know_accounts(&budget_id, cached.server_knowledge);
+                self.load_balance_histories(&budget_id, &cached.accounts)
+                    .await;
 
                 // Step 2: Check for delta updates in background
+                if self.saving_quota() {
+                    return;
+                }
                 if let Some(server_knowledge) = cached.server_knowledge {
                     tracing::debug!(
                         "Checking for account deltas (server_knowledge={})",
@@ -131,7 +417,7 @@ impl DataLoader {
             .with_budget(budget_id_api)
             .list()
             .last_knowledge_of_server(last_knowledge.into());
-        match self.api_client.send(req).await {
+        match self.send(req).await {
             Ok(delta_response) => {
                 // Check if there are actual changes
                 if let Some(new_knowledge) = delta_response.data.server_knowledge {
@@ -141,9 +427,12 @@ impl DataLoader {
                             delta_response.data.accounts.len()
                         );
                         // Send delta update
-                        let _ = self.data_tx.send(DataEvent::AccountsDeltaLoaded {
-                            delta: delta_response.data.accounts.clone(),
-                        });
+                        let _ = self.data_tx.send(
+                            BudgetEvent::AccountsDeltaLoaded {
+                                delta: delta_response.data.accounts.clone(),
+                            }
+                            .into(),
+                        );
 
                         // Update cache in background
                         let cache = self.cache.clone();
@@ -174,13 +463,18 @@ impl DataLoader {
         tracing::debug!("Fetching full accounts from API");
         let budget_id_api: BudgetId = budget_id.clone().into();
         let req = Request::accounts().with_budget(budget_id_api).list();
-        match self.api_client.send(req).await {
+        match self.send(req).await {
             Ok(response) => {
                 tracing::info!("Loaded {} accounts from API", response.data.accounts.len());
                 // Send fresh data
-                let _ = self.data_tx.send(DataEvent::AccountsLoaded {
-                    accounts: response.data.accounts.clone(),
-                });
+                let _ = self.data_tx.send(
+                    BudgetEvent::AccountsLoaded {
+                        accounts: response.data.accounts.clone(),
+                    }
+                    .into(),
+                );
+                self.load_balance_histories(&budget_id, &response.data.accounts)
+                    .await;
 
                 // Update cache in background
                 let cache = self.cache.clone();
@@ -194,265 +488,2061 @@ impl DataLoader {
                 });
             }
             Err(e) => {
-                let _ = self.data_tx.send(DataEvent::LoadError {
-                    error: e.to_string(),
-                });
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
             }
         }
     }
 
-    /// Load transactions with cache-first strategy and delta updates
-    pub async fn load_transactions(
-        &self,
-        budget_id: String,
-        account_id: String,
-        force_refresh: bool,
-    ) {
-        tracing::info!(
-            "Loading transactions for budget {} account {} (force_refresh={})",
-            budget_id,
-            account_id,
-            force_refresh
-        );
-
-        // Step 1: Try cache first (fast path)
-        if !force_refresh {
-            if let Ok(Some(cached)) = self.cache.get_transactions(&budget_id, &account_id).await {
-                // Filter out deleted transactions
-                let transactions: Vec<_> = cached
-                    .transactions
-                    .into_iter()
-                    .filter(|t| !t.deleted)
-                    .collect();
+    /// Compute sparkline balance history and this month's net change for each
+    /// account from cached transactions, then what the budget's accounts
+    /// spent today and this week.
+    /// Accounts whose transactions have never been loaded are skipped.
+    async fn load_balance_histories(&self, budget_id: &str, accounts: &[Account]) {
+        let today = chrono::Local::now().date_naive();
+        let mut spending = RecentSpending::default();
+        for account in accounts.iter().filter(|a| !a.deleted) {
+            let account_id = account.id.to_string();
+            if let Some(transactions) = self
+                .cached_or_refetched_transactions(budget_id, &account_id)
+                .await
+            {
+                if account.on_budget {
+                    spending.add(&transactions, today);
+                }
+                let balances = balance_history::daily_balances(
+                    account.balance.into(),
+                    &transactions,
+                    today,
+                    balance_history::BALANCE_HISTORY_DAYS,
+                );
+                let month_change = balance_history::month_net_change(&transactions, today);
+                let _ = self.data_tx.send(
+                    BudgetEvent::AccountBalanceHistoryLoaded {
+                        account_id,
+                        balances,
+                        month_change,
+                    }
+                    .into(),
+                );
+            }
+        }
+        let _ = self
+            .data_tx
+            .send(BudgetEvent::RecentSpendingLoaded { spending }.into());
+    }
 
-                tracing::debug!("Loaded {} transactions from cache", transactions.len());
-                // Send cached data immediately
-                let _ = self.data_tx.send(DataEvent::TransactionsCacheLoaded {
-                    transactions: transactions.clone(),
-                });
+    /// Run the follow-up command built from a transaction and report how it went
+    pub async fn run_follow_up(&self, args: Vec<String>) {
+        let Some((program, rest)) = args.split_first() else {
+            return;
+        };
+        tracing::info!("Running follow-up command {}", program);
 
-                // Step 2: Check for delta updates in background
-                if let Some(server_knowledge) = cached.server_knowledge {
-                    tracing::debug!(
-                        "Checking for transaction deltas (server_knowledge={})",
-                        server_knowledge
-                    );
-                    self.check_transactions_delta(
-                        budget_id.clone(),
-                        account_id.clone(),
-                        server_knowledge,
-                    )
-                    .await;
-                } else {
-                    tracing::debug!("No server knowledge, fetching full transactions");
-                    // No server_knowledge, need full refresh
-                    self.fetch_transactions_full(budget_id.clone(), account_id.clone())
-                        .await;
+        let event = match tokio::process::Command::new(program)
+            .args(rest)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await
+        {
+            Ok(output) if output.status.success() => TransactionEvent::FollowUpCreated.into(),
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let error = match stderr.trim() {
+                    "" => output.status.to_string(),
+                    stderr => stderr.lines().last().unwrap_or_default().to_string(),
+                };
+                tracing::error!("Follow-up command failed: {}", stderr.trim());
+                TransactionEvent::FollowUpFailed {
+                    error: AppError::new(ErrorKind::Other, error),
+                }
+                .into()
+            }
+            Err(e) => {
+                tracing::error!("Failed to run follow-up command {}: {}", program, e);
+                TransactionEvent::FollowUpFailed {
+                    error: AppError::new(ErrorKind::Other, e.to_string()),
                 }
+                .into()
+            }
+        };
+        let _ = self.data_tx.send(event);
+    }
+
+    /// Look up where a payee's transactions were entered, naming the town
+    /// with `geocoder` if one is configured, for the transaction detail popup
+    pub async fn load_payee_location(&self, budget_id: String, payee_id: String, geocoder: String) {
+        let Ok(payee_uuid) = payee_id.parse::<Uuid>() else {
+            tracing::error!("Invalid payee id {}", payee_id);
+            return;
+        };
+        let req = Request::payees()
+            .locations(payee_uuid)
+            .budget_id(BudgetId::from(budget_id));
+        let locations = match self.send(req).await {
+            Ok(response) => response.data.payee_locations,
+            Err(e) => {
+                tracing::error!("Failed to load locations for payee {}: {}", payee_id, e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::PayeeLocationLoadFailed {
+                        payee_id,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
                 return;
-            } else {
-                tracing::debug!("No cached transactions found");
             }
+        };
+
+        let mut place = maps::latest(&locations).map(maps::Place::new);
+        if let Some(place) = place.as_mut().filter(|_| !geocoder.is_empty()) {
+            place.city = reverse_geocode(&geocoder, place).await;
         }
+        let _ = self
+            .data_tx
+            .send(TransactionEvent::PayeeLocationLoaded { payee_id, place }.into());
+    }
 
-        // Cache miss or forced refresh - load from API
-        self.fetch_transactions_full(budget_id, account_id).await;
+    /// Open a payee's location with the configured maps command
+    pub async fn open_payee_location(&self, place: maps::Place, command: Vec<String>) {
+        let args = maps::render_args(&command, &place);
+        let Some((program, rest)) = args.split_first() else {
+            return;
+        };
+        tracing::info!("Opening payee location with {}", program);
+
+        let event = match tokio::process::Command::new(program)
+            .args(rest)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .await
+        {
+            Ok(status) if status.success() => TransactionEvent::PayeeLocationOpened {
+                latitude: place.latitude,
+                longitude: place.longitude,
+            },
+            Ok(status) => TransactionEvent::PayeeLocationFailed {
+                error: AppError::new(
+                    ErrorKind::Other,
+                    format!("{} exited with {}", program, status),
+                ),
+            },
+            Err(e) => {
+                tracing::error!("Failed to run maps command {}: {}", program, e);
+                TransactionEvent::PayeeLocationFailed {
+                    error: AppError::new(ErrorKind::Other, e.to_string()),
+                }
+            }
+        };
+        let _ = self.data_tx.send(event.into());
     }
 
-    /// Check for delta updates to transactions
-    async fn check_transactions_delta(
+    /// Write an exported CSV (encrypting it if configured) and report where it went
+    pub async fn write_transactions_export(
         &self,
-        budget_id: String,
-        account_id: String,
-        last_knowledge: i64,
+        path: PathBuf,
+        contents: String,
+        config: ExportConfig,
     ) {
-        let budget_id_api: BudgetId = budget_id.clone().into();
-        let account_id_uuid = Uuid::parse_str(&account_id).expect("invalid account_id uuid");
-        let req = Request::transactions()
-            .with_budget(budget_id_api)
-            .list(account_id_uuid)
-            .last_knowledge_of_server(last_knowledge.into());
-        match self.api_client.send(req).await {
-            Ok(delta_response) => {
-                // Check if there are actual changes
-                if let Some(new_knowledge) = delta_response.data.server_knowledge {
-                    if new_knowledge.inner() > last_knowledge {
-                        // Filter out deleted transactions
-                        let delta: Vec<_> = delta_response
-                            .data
-                            .transactions
-                            .into_iter()
-                            .filter(|t| !t.deleted)
-                            .collect();
-
-                        // Send delta update
-                        let _ = self.data_tx.send(DataEvent::TransactionsDeltaLoaded {
-                            delta: delta.clone(),
-                        });
+        // Encryption waits on an external program, so keep it off the runtime
+        let result =
+            tokio::task::spawn_blocking(move || export::write(&path, contents.as_bytes(), &config))
+                .await;
 
-                        // Update cache in background
-                        let cache = self.cache.clone();
-                        let budget_id_clone = budget_id.clone();
-                        let account_id_clone = account_id.clone();
-                        let new_knowledge_i64 = new_knowledge.inner();
-                        tokio::spawn(async move {
-                            let _ = cache
-                                .merge_transactions_delta(
-                                    &budget_id_clone,
-                                    &account_id_clone,
-                                    &delta,
-                                    new_knowledge_i64,
-                                )
-                                .await;
-                        });
-                    }
+        let event = match result {
+            Ok(Ok(path)) => {
+                tracing::info!("Exported transactions to {}", path.display());
+                TransactionEvent::TransactionsExported { path }.into()
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Failed to export transactions: {:#}", e);
+                TransactionEvent::TransactionsExportFailed {
+                    error: AppError::new(ErrorKind::Other, e.to_string()),
                 }
+                .into()
             }
-            Err(e) => {
-                // Delta check failed, not critical (we have cached data)
-                tracing::error!("Delta check failed for transactions: {}", e);
+            Err(e) => TransactionEvent::TransactionsExportFailed {
+                error: AppError::new(ErrorKind::Other, e.to_string()),
+            }
+            .into(),
+        };
+        let _ = self.data_tx.send(event);
+    }
+
+    /// Load tracked reimbursements and suggest matching deposits from cached transactions
+    pub async fn load_reimbursements(&self, budget_id: String) {
+        match self.stored_reimbursements(&budget_id).await {
+            Ok(entries) => self.send_reimbursements(&budget_id, entries).await,
+            Err(error) => {
+                tracing::error!("Failed to read reimbursements: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
             }
         }
     }
 
-    /// Fetch full transactions data from API
-    async fn fetch_transactions_full(&self, budget_id: String, account_id: String) {
-        let budget_id_api: BudgetId = budget_id.clone().into();
-        let account_id_uuid = Uuid::parse_str(&account_id).expect("invalid account_id uuid");
-        let req = Request::transactions()
-            .with_budget(budget_id_api)
-            .list(account_id_uuid);
-        match self.api_client.send(req).await {
-            Ok(response) => {
-                // Filter out deleted transactions
-                let transactions: Vec<_> = response
-                    .data
-                    .transactions
-                    .into_iter()
-                    .filter(|t| !t.deleted)
-                    .collect();
+    /// Tracked reimbursements, none if nothing was tracked yet
+    async fn stored_reimbursements(
+        &self,
+        budget_id: &str,
+    ) -> Result<Vec<ReimbursementEntry>, AppError> {
+        reimbursements::load(budget_id)
+            .await
+            .map(Option::unwrap_or_default)
+            .map_err(|e| AppError::new(ErrorKind::Cache, e.to_string()))
+    }
 
-                // Send fresh data
-                let _ = self.data_tx.send(DataEvent::TransactionsLoaded {
-                    transactions: transactions.clone(),
-                });
+    /// Start or stop tracking a transaction as awaiting reimbursement
+    pub async fn toggle_reimbursement(&self, budget_id: String, entry: ReimbursementEntry) {
+        self.update_reimbursements(budget_id, |entries| {
+            let before = entries.len();
+            entries.retain(|e| e.transaction_id != entry.transaction_id);
+            if entries.len() == before {
+                tracing::info!(
+                    "Tracking {} as awaiting reimbursement",
+                    entry.transaction_id
+                );
+                entries.push(entry);
+            } else {
+                tracing::info!(
+                    "Stopped tracking {} for reimbursement",
+                    entry.transaction_id
+                );
+            }
+        })
+        .await;
+    }
 
-                // Update cache in background
-                let cache = self.cache.clone();
-                let budget_id_clone = budget_id.clone();
-                let account_id_clone = account_id.clone();
-                let server_knowledge = response.data.server_knowledge.map(|k| k.inner());
-                tokio::spawn(async move {
-                    let _ = cache
-                        .set_transactions(
-                            &budget_id_clone,
-                            &account_id_clone,
-                            &transactions,
-                            server_knowledge,
-                        )
-                        .await;
-                });
+    /// Record that an entry has been reimbursed
+    pub async fn mark_reimbursed(
+        &self,
+        budget_id: String,
+        transaction_id: String,
+        deposit_id: Option<String>,
+    ) {
+        self.update_reimbursements(budget_id, |entries| {
+            if let Some(entry) = entries
+                .iter_mut()
+                .find(|e| e.transaction_id == transaction_id)
+            {
+                tracing::info!("Marked {} as reimbursed", transaction_id);
+                entry.reimbursed_by = Some(deposit_id.unwrap_or_else(|| "manual".to_string()));
             }
-            Err(e) => {
-                tracing::error!("Failed to load transactions from API: {}", e);
-                let _ = self.data_tx.send(DataEvent::LoadError {
-                    error: e.to_string(),
-                });
+        })
+        .await;
+    }
+
+    pub async fn save_budget_settings(&self, budget_id: String, settings: BudgetSettings) {
+        if let Err(e) = budget_settings::save(&budget_id, &settings).await {
+            tracing::error!("Failed to save settings for budget {}: {}", budget_id, e);
+        }
+    }
+
+    async fn update_reimbursements(
+        &self,
+        budget_id: String,
+        update: impl FnOnce(&mut Vec<ReimbursementEntry>),
+    ) {
+        match self.save_reimbursements(&budget_id, update).await {
+            Ok(entries) => self.send_reimbursements(&budget_id, entries).await,
+            Err(error) => {
+                tracing::error!("Failed to save reimbursements: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
             }
         }
     }
 
-    /// Load plan with cache-first strategy
-    pub async fn load_plan(&self, budget_id: String, force_refresh: bool) {
+    /// Read, change and write the tracked reimbursements under the cache lock,
+    /// so another ynat's change isn't lost. Nothing is written if they can't
+    /// be read.
+    async fn save_reimbursements(
+        &self,
+        budget_id: &str,
+        update: impl FnOnce(&mut Vec<ReimbursementEntry>),
+    ) -> Result<Vec<ReimbursementEntry>, AppError> {
+        let _lock = self.cache.lock().await?;
+        let mut entries = self.stored_reimbursements(budget_id).await?;
+        update(&mut entries);
+        reimbursements::save(budget_id, &entries)
+            .await
+            .map_err(|e| AppError::new(ErrorKind::Cache, e.to_string()))?;
+        Ok(entries)
+    }
+
+    async fn send_reimbursements(&self, budget_id: &str, entries: Vec<ReimbursementEntry>) {
+        // Deposits can land in any account, so look through everything cached
+        let transactions = self.cached_budget_transactions(budget_id).await;
+        let matches = reimbursements::suggest_matches(&entries, &transactions);
+        let _ = self
+            .data_tx
+            .send(TransactionEvent::ReimbursementsLoaded { entries, matches }.into());
+    }
+
+    /// Work out each payee's most used category from the cached history of
+    /// every account
+    pub async fn load_category_suggestions(&self, budget_id: String) {
+        let transactions = self.cached_budget_transactions(&budget_id).await;
+        let suggestions = category_suggestions::by_payee(&transactions);
+        tracing::debug!("Suggesting categories for {} payees", suggestions.len());
+        let _ = self
+            .data_tx
+            .send(TransactionEvent::CategorySuggestionsLoaded { suggestions }.into());
+    }
+
+    /// Every cached transaction across the budget's accounts
+    async fn cached_budget_transactions(&self, budget_id: &str) -> Vec<Transaction> {
+        let mut transactions = Vec::new();
+        if let Some(accounts) = self.cached("accounts", self.cache.get_accounts(budget_id).await) {
+            for account in accounts.accounts {
+                if let Some(cached) = self
+                    .cached_or_refetched_transactions(budget_id, &account.id.to_string())
+                    .await
+                {
+                    transactions.extend(cached);
+                }
+            }
+        }
+        transactions
+    }
+
+    /// Every transaction in the budget, or those dated `since` onwards,
+    /// straight from YNAB. The per-account caches only hold accounts that
+    /// have been opened, and only their recent history.
+    async fn fetch_budget_transactions(
+        &self,
+        budget_id: &str,
+        since: Option<NaiveDate>,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let mut req = Request::transactions()
+            .with_budget(budget_id.to_string().into())
+            .list_all();
+        if let Some(since) = since {
+            req = req.since_date(since);
+        }
+        let response = self.send(req).await?;
+        Ok(response
+            .data
+            .transactions
+            .into_iter()
+            .filter(|t| !t.deleted)
+            .collect())
+    }
+
+    /// Group near-duplicate payees from the cached payee list
+    pub async fn load_payee_clusters(&self, budget_id: String) {
+        let payees = match self.cached("payees", self.cache.get_payees(&budget_id).await) {
+            Some(cached) => cached,
+            None => {
+                let req = Request::payees()
+                    .list()
+                    .budget_id(BudgetId::from(budget_id.clone()));
+                match self.send(req).await {
+                    Ok(response) => response.data.payees,
+                    Err(e) => {
+                        tracing::error!("Failed to load payees for cleanup: {}", e);
+                        let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+                        return;
+                    }
+                }
+            }
+        };
+
+        // Payees merged away earlier keep existing in YNAB; don't offer them again
+        let hidden = budget_settings::load(&budget_id).await.hidden_payee_ids;
+        let payees: Vec<Payee> = payees
+            .into_iter()
+            .filter(|p| !hidden.contains(&p.id.to_string()))
+            .collect();
+        let clusters = payee_cleanup::clusters(&payees);
+        tracing::info!(
+            "Found {} groups of similar payees among {}",
+            clusters.len(),
+            payees.len()
+        );
+        let _ = self
+            .data_tx
+            .send(PayeeEvent::PayeeClustersLoaded { clusters }.into());
+    }
+
+    /// Rename payees one request at a time, then refresh the payee cache
+    pub async fn rename_payees(&self, budget_id: String, renames: Vec<(String, String)>) {
+        tracing::info!("Renaming {} payees in budget {}", renames.len(), budget_id);
+
+        let mut payees: Vec<Payee> = Vec::new();
+        let mut failed = 0;
+        for (payee_id, name) in renames {
+            let Ok(payee_uuid) = payee_id.parse::<Uuid>() else {
+                failed += 1;
+                continue;
+            };
+            let req = Request::payees()
+                .update(payee_uuid, name)
+                .budget_id(BudgetId::from(budget_id.clone()));
+            match self.send(req).await {
+                Ok(response) => {
+                    payees.push(response.data.payee);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to rename payee {}: {}", payee_id, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        let _ = self
+            .data_tx
+            .send(PayeeEvent::PayeesRenamed { payees, failed }.into());
+        self.load_payees(budget_id, true).await;
+    }
+
+    /// Approve transactions in one bulk update, then drop the cached
+    /// histories of the accounts they belong to
+    pub async fn approve_transactions(
+        &self,
+        budget_id: String,
+        transaction_ids: Vec<String>,
+        account_ids: Vec<String>,
+    ) {
+        tracing::info!("Approving {} transactions", transaction_ids.len());
+        let updates = transaction_ids
+            .iter()
+            .filter_map(|id| id.parse().ok())
+            .map(|id| BulkTransactionUpdate {
+                id,
+                cleared: None,
+                payee_id: None,
+                category_id: None,
+                approved: Some(true),
+                flag_color: None,
+            })
+            .collect();
+        let req = Request::transactions()
+            .bulk()
+            .update()
+            .budget_id(BudgetId::from(budget_id.clone()))
+            .transactions(updates);
+
+        match self.send(req).await {
+            Ok(_) => {
+                for account_id in &account_ids {
+                    let _ = self
+                        .cache
+                        .invalidate_transactions(&budget_id, account_id)
+                        .await;
+                }
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionsApproved { transaction_ids }.into());
+            }
+            Err(e) => {
+                tracing::error!("Failed to approve transactions: {}", e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionsApproveFailed {
+                        transaction_ids,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// Set each transaction's category in one bulk update, then drop the
+    /// cached histories of the accounts they belong to. `originals` are sent
+    /// back on failure so the screen can be put back as it was.
+    pub async fn categorize_transactions(
+        &self,
+        budget_id: String,
+        matches: Vec<RuleMatch>,
+        originals: Vec<Transaction>,
+    ) {
+        tracing::info!("Categorizing {} transactions by payee rule", matches.len());
+        let updates = matches
+            .iter()
+            .filter_map(|m| Some((m.transaction_id.parse().ok()?, m.category_id)))
+            .map(|(id, category_id)| BulkTransactionUpdate {
+                id,
+                cleared: None,
+                payee_id: None,
+                category_id: Some(category_id),
+                approved: None,
+                flag_color: None,
+            })
+            .collect();
+        let req = Request::transactions()
+            .bulk()
+            .update()
+            .budget_id(BudgetId::from(budget_id.clone()))
+            .transactions(updates);
+
+        match self.send(req).await {
+            Ok(_) => {
+                let mut account_ids: Vec<String> =
+                    originals.iter().map(|t| t.account_id.to_string()).collect();
+                account_ids.sort();
+                account_ids.dedup();
+                for account_id in account_ids {
+                    let _ = self
+                        .cache
+                        .invalidate_transactions(&budget_id, &account_id)
+                        .await;
+                }
+                let transaction_ids = matches.into_iter().map(|m| m.transaction_id).collect();
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionsCategorized { transaction_ids }.into());
+            }
+            Err(e) => {
+                tracing::error!("Failed to categorize transactions: {}", e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionsCategorizeFailed {
+                        originals,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// Set a transaction's suggested category, approving it too if
+    /// `approve`, then drop the cached history of its account. `original`
+    /// is sent back on failure so the screen can be put back as it was.
+    pub async fn accept_category_suggestion(
+        &self,
+        budget_id: String,
+        original: Transaction,
+        category_id: Uuid,
+        approve: bool,
+    ) {
+        tracing::info!("Accepting the suggested category for {}", original.id);
+        let update = BulkTransactionUpdate {
+            id: original.id.clone(),
+            cleared: None,
+            payee_id: None,
+            category_id: Some(category_id),
+            approved: approve.then_some(true),
+            flag_color: None,
+        };
+        let req = Request::transactions()
+            .bulk()
+            .update()
+            .budget_id(BudgetId::from(budget_id.clone()))
+            .transactions(vec![update]);
+
+        match self.send(req).await {
+            Ok(_) => {
+                let _ = self
+                    .cache
+                    .invalidate_transactions(&budget_id, &original.account_id.to_string())
+                    .await;
+                let _ = self.data_tx.send(
+                    TransactionEvent::CategorySuggestionAccepted {
+                        transaction_id: original.id.to_string(),
+                    }
+                    .into(),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to accept the suggested category: {}", e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionsCategorizeFailed {
+                        originals: vec![original],
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// Clear the flags of `originals` in one bulk update, then drop the cached
+    /// histories of their accounts. `originals` are sent back on failure so
+    /// the screen can be put back as it was.
+    pub async fn clear_transaction_flags(&self, budget_id: String, originals: Vec<Transaction>) {
+        tracing::info!("Clearing the flags of {} transactions", originals.len());
+        let updates = originals
+            .iter()
+            .map(|t| BulkTransactionUpdate {
+                id: t.id.clone(),
+                cleared: None,
+                payee_id: None,
+                category_id: None,
+                approved: None,
+                flag_color: Some(None),
+            })
+            .collect();
+        let req = Request::transactions()
+            .bulk()
+            .update()
+            .budget_id(BudgetId::from(budget_id.clone()))
+            .transactions(updates);
+
+        match self.send(req).await {
+            Ok(_) => {
+                let mut account_ids: Vec<String> =
+                    originals.iter().map(|t| t.account_id.to_string()).collect();
+                account_ids.sort();
+                account_ids.dedup();
+                for account_id in account_ids {
+                    let _ = self
+                        .cache
+                        .invalidate_transactions(&budget_id, &account_id)
+                        .await;
+                }
+                let transaction_ids = originals.iter().map(|t| t.id.to_string()).collect();
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionFlagsCleared { transaction_ids }.into());
+            }
+            Err(e) => {
+                tracing::error!("Failed to clear transaction flags: {}", e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionFlagsClearFailed {
+                        originals,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// Ask YNAB to import from the budget's linked accounts, then reload the
+    /// transactions shown if anything new came in
+    pub async fn import_linked_transactions(&self, budget_id: String, account_id: Option<String>) {
+        tracing::info!("Importing linked transactions for budget {}", budget_id);
+        let req = Request::transactions()
+            .import()
+            .budget_id(BudgetId::from(budget_id.clone()));
+
+        match self.send(req).await {
+            Ok(response) => {
+                let count = response.data.transaction_ids.len();
+                tracing::info!("Imported {} transactions", count);
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionsImported { count }.into());
+                if count > 0 {
+                    match account_id {
+                        Some(account_id) => {
+                            self.load_transactions(budget_id, account_id, false).await
+                        }
+                        None => self.load_budget_transactions(budget_id).await,
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to import linked transactions: {}", e);
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionsImportFailed { error: e.into() }.into());
+            }
+        }
+    }
+
+    /// Move each merge's transactions onto its kept payee with one bulk
+    /// update, rename the kept payee, then hide the emptied duplicates.
+    ///
+    /// YNAB has no way to delete a payee, so "hidden" is a local setting.
+    pub async fn merge_payees(&self, budget_id: String, merges: Vec<PayeeMerge>) {
+        tracing::info!(
+            "Merging {} payee groups in budget {}",
+            merges.len(),
+            budget_id
+        );
+
+        let budget_id_api = BudgetId::from(budget_id.clone());
+        let req = Request::transactions()
+            .with_budget(budget_id_api.clone())
+            .list_all();
+        let transactions = match self.send(req).await {
+            Ok(response) => response.data.transactions,
+            Err(e) => {
+                tracing::error!("Failed to load transactions to merge payees: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+                return;
+            }
+        };
+
+        let mut merged = Vec::new();
+        let mut transactions_moved = 0;
+        let mut failed = 0;
+        for merge in merges {
+            let Ok(target_id) = merge.target.payee_id.parse::<Uuid>() else {
+                failed += 1;
+                continue;
+            };
+            let updates: Vec<BulkTransactionUpdate> = transactions
+                .iter()
+                .filter(|t| !t.deleted)
+                .filter(|t| {
+                    t.payee_id.is_some_and(|id| {
+                        merge
+                            .duplicates
+                            .iter()
+                            .any(|d| d.payee_id == id.to_string())
+                    })
+                })
+                .map(|t| BulkTransactionUpdate {
+                    id: t.id.clone(),
+                    cleared: None,
+                    payee_id: Some(target_id),
+                    category_id: None,
+                    approved: None,
+                    flag_color: None,
+                })
+                .collect();
+            let moved = updates.len();
+
+            if !updates.is_empty() {
+                let req = Request::transactions()
+                    .bulk()
+                    .update()
+                    .budget_id(budget_id_api.clone())
+                    .transactions(updates);
+                if let Err(e) = self.send(req).await {
+                    tracing::error!("Failed to move transactions to {}: {}", merge.name, e);
+                    failed += 1;
+                    continue;
+                }
+            }
+            if merge.target.name != merge.name {
+                let req = Request::payees()
+                    .update(target_id, merge.name.clone())
+                    .budget_id(budget_id_api.clone());
+                if let Err(e) = self.send(req).await {
+                    // The transactions moved, so the merge still counts
+                    tracing::warn!("Failed to rename payee {}: {}", merge.target.payee_id, e);
+                }
+            }
+            transactions_moved += moved;
+            merged.push(merge);
+        }
+
+        let mut settings = budget_settings::load(&budget_id).await;
+        for duplicate in merged.iter().flat_map(|m| &m.duplicates) {
+            if !settings.hidden_payee_ids.contains(&duplicate.payee_id) {
+                settings.hidden_payee_ids.push(duplicate.payee_id.clone());
+            }
+        }
+        self.save_budget_settings(budget_id.clone(), settings).await;
+
+        let _ = self.data_tx.send(
+            PayeeEvent::PayeesMerged {
+                merged,
+                transactions_moved,
+                failed,
+            }
+            .into(),
+        );
+        self.load_payees(budget_id, true).await;
+    }
+
+    /// Build the budget vs actual report from each month in the period
+    pub async fn load_variance_report(
+        &self,
+        budget_id: String,
+        period: ReportPeriod,
+        force_refresh: bool,
+    ) {
+        let today = chrono::Local::now().date_naive();
+        tracing::info!(
+            "Loading variance report for budget {} ({:?})",
+            budget_id,
+            period
+        );
+
+        let mut months = Vec::new();
+        for month in period.months(today) {
+            let month = month.format("%Y-%m-%d").to_string();
+            match self.month_detail(&budget_id, &month, force_refresh).await {
+                Ok(detail) => months.push(detail),
+                Err(error) => {
+                    tracing::error!("Failed to load month {} for report: {}", month, error);
+                    let _ = self.data_tx.send(DataEvent::LoadError { error });
+                    return;
+                }
+            }
+        }
+
+        let rows = variance::build_rows(&months);
+        let _ = self
+            .data_tx
+            .send(ReportEvent::VarianceReportLoaded { period, rows }.into());
+    }
+
+    /// Load the transactions behind one variance row, fetched across the budget
+    pub async fn load_variance_transactions(
+        &self,
+        budget_id: String,
+        category_id: String,
+        period: ReportPeriod,
+    ) {
+        let today = chrono::Local::now().date_naive();
+        let since = period.start(today);
+        let all = match self
+            .fetch_budget_transactions(&budget_id, Some(since))
+            .await
+        {
+            Ok(all) => all,
+            Err(error) => {
+                tracing::error!("Failed to load transactions for report: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+                return;
+            }
+        };
+        let transactions = variance::category_transactions(&all, &category_id, |date| {
+            period.contains(today, date)
+        });
+        let _ = self.data_tx.send(
+            ReportEvent::VarianceTransactionsLoaded {
+                category_id,
+                transactions,
+            }
+            .into(),
+        );
+    }
+
+    /// Total each day's spending in `month` across the budget
+    pub async fn load_spending_heatmap(&self, budget_id: String, month: NaiveDate) {
+        let since = reports::first_of_month(month);
+        match self
+            .fetch_budget_transactions(&budget_id, Some(since))
+            .await
+        {
+            Ok(transactions) => {
+                let heatmap = heatmap::build(&transactions, month);
+                let _ = self
+                    .data_tx
+                    .send(ReportEvent::SpendingHeatmapLoaded { heatmap }.into());
+            }
+            Err(error) => {
+                tracing::error!("Failed to load transactions for heatmap: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    /// Add up the period's spending per category or payee across the budget
+    pub async fn load_spending_breakdown(
+        &self,
+        budget_id: String,
+        period: ReportPeriod,
+        grouping: SpendingGrouping,
+    ) {
+        let today = chrono::Local::now().date_naive();
+        let since = period.start(today);
+        match self
+            .fetch_budget_transactions(&budget_id, Some(since))
+            .await
+        {
+            Ok(transactions) => {
+                let breakdown = spending::build(&transactions, period, grouping, |date| {
+                    period.contains(today, date)
+                });
+                let _ = self
+                    .data_tx
+                    .send(ReportEvent::SpendingBreakdownLoaded { breakdown }.into());
+            }
+            Err(error) => {
+                tracing::error!("Failed to load transactions for report: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    /// Total each of the last twelve months' inflows and outflows across the budget
+    pub async fn load_income_expense(&self, budget_id: String) {
+        let today = chrono::Local::now().date_naive();
+        let since = income_expense::first_month(today);
+        match self
+            .fetch_budget_transactions(&budget_id, Some(since))
+            .await
+        {
+            Ok(transactions) => {
+                let report = income_expense::build(&transactions, today);
+                let _ = self
+                    .data_tx
+                    .send(ReportEvent::IncomeExpenseLoaded { report }.into());
+            }
+            Err(error) => {
+                tracing::error!("Failed to load transactions for report: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    /// Add up the budget's accounts into a net worth, from the cached accounts
+    /// unless `force_refresh`
+    pub async fn load_net_worth(&self, budget_id: String, force_refresh: bool) {
+        tracing::info!("Working out net worth for budget {}", budget_id);
+        match self.net_worth(&budget_id, force_refresh).await {
+            Ok(net_worth) => {
+                let _ = self
+                    .data_tx
+                    .send(ReportEvent::NetWorthLoaded { net_worth }.into());
+            }
+            Err(error) => {
+                tracing::error!("Failed to work out net worth: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    async fn net_worth(&self, budget_id: &str, force_refresh: bool) -> Result<NetWorth, AppError> {
+        if !force_refresh {
+            if let Some(cached) = self.cached("accounts", self.cache.get_accounts(budget_id).await)
+            {
+                return Ok(net_worth::build(&cached.accounts));
+            }
+        }
+
+        let req = Request::accounts()
+            .with_budget(BudgetId::from(budget_id.to_string()))
+            .list();
+        let response = self.send(req).await?;
+        let accounts = response.data.accounts;
+        let server_knowledge = response.data.server_knowledge.map(|k| k.inner());
+        let _ = self
+            .cache
+            .set_accounts(budget_id, &accounts, server_knowledge)
+            .await;
+        Ok(net_worth::build(&accounts))
+    }
+
+    /// Forecast next month's goal contributions against the income scheduled for it
+    pub async fn load_goal_obligations(&self, budget_id: String, force_refresh: bool) {
+        tracing::info!(
+            "Forecasting next month's goal obligations for budget {}",
+            budget_id
+        );
+        match self.goal_obligations(&budget_id, force_refresh).await {
+            Ok(forecast) => {
+                let _ = self
+                    .data_tx
+                    .send(ReportEvent::GoalObligationsForecast { forecast }.into());
+            }
+            Err(error) => {
+                tracing::error!("Failed to forecast goal obligations: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    async fn goal_obligations(
+        &self,
+        budget_id: &str,
+        force_refresh: bool,
+    ) -> Result<ObligationsForecast, AppError> {
+        let next =
+            reports::first_of_month(chrono::Local::now().date_naive()) + chrono::Months::new(1);
+        let plan = self
+            .month_detail(
+                budget_id,
+                &next.format("%Y-%m-%d").to_string(),
+                force_refresh,
+            )
+            .await?;
+
+        let req = Request::scheduled_transactions()
+            .with_budget(BudgetId::from(budget_id.to_string()))
+            .list();
+        let response = self.send(req).await?;
+
+        Ok(obligations::build(
+            next,
+            &plan,
+            &response.data.scheduled_transactions,
+        ))
+    }
+
+    /// Project an account's balance forward from scheduled transactions and
+    /// the recent run-rate of its unscheduled categories
+    pub async fn load_cash_flow(
+        &self,
+        budget_id: String,
+        account_id: String,
+        horizon: ProjectionHorizon,
+    ) {
+        tracing::info!(
+            "Projecting cash flow for budget {} account {} ({} days)",
+            budget_id,
+            account_id,
+            horizon.days()
+        );
+
+        match self.cash_flow(&budget_id, &account_id, horizon).await {
+            Ok(projection) => {
+                let _ = self
+                    .data_tx
+                    .send(ReportEvent::CashFlowProjected { projection }.into());
+            }
+            Err(error) => {
+                tracing::error!("Failed to project cash flow: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    async fn cash_flow(
+        &self,
+        budget_id: &str,
+        account_id: &str,
+        horizon: ProjectionHorizon,
+    ) -> Result<CashFlowProjection, AppError> {
+        let account_uuid = Uuid::parse_str(account_id)
+            .map_err(|e| AppError::new(ErrorKind::Other, e.to_string()))?;
+        let budget_id_api: BudgetId = budget_id.to_string().into();
+
+        let account = self
+            .cached("accounts", self.cache.get_accounts(budget_id).await)
+            .and_then(|cached| cached.accounts.into_iter().find(|a| a.id == account_uuid));
+        let account = match account {
+            Some(account) => account,
+            None => {
+                let req = Request::accounts()
+                    .with_budget(budget_id_api.clone())
+                    .list();
+                let response = self.send(req).await?;
+                response
+                    .data
+                    .accounts
+                    .into_iter()
+                    .find(|a| a.id == account_uuid)
+                    .ok_or_else(|| {
+                        AppError::new(
+                            ErrorKind::NotFound,
+                            format!("Account {} not found", account_id),
+                        )
+                    })?
+            }
+        };
+
+        let transactions = match self.cached(
+            "transactions",
+            self.cache.get_transactions(budget_id, account_id).await,
+        ) {
+            Some(cached) => cached.transactions,
+            None => self.refetch_transactions(budget_id, account_id).await?,
+        };
+
+        // Schedules change rarely but aren't cached, so always ask the API
+        let req = Request::scheduled_transactions()
+            .with_budget(budget_id_api)
+            .list();
+        let response = self.send(req).await?;
+
+        Ok(cash_flow::project(
+            account_uuid,
+            &account.name,
+            account.balance.into(),
+            chrono::Local::now().date_naive(),
+            horizon,
+            &response.data.scheduled_transactions,
+            &transactions,
+        ))
+    }
+
+    /// Find categories unused for the last few months, from cached plan months where possible
+    pub async fn load_archive_suggestions(&self, budget_id: String) {
+        let current = reports::first_of_month(chrono::Local::now().date_naive());
+        tracing::info!("Looking for unused categories in budget {}", budget_id);
+
+        // The current month counts too: budgeting anything this month means it's in use
+        let mut months = Vec::new();
+        for back in (0..=archive::IDLE_MONTHS as u32).rev() {
+            let month = (current - chrono::Months::new(back))
+                .format("%Y-%m-%d")
+                .to_string();
+            match self.month_detail(&budget_id, &month, false).await {
+                Ok(detail) => months.push(detail),
+                Err(error) => {
+                    tracing::error!(
+                        "Failed to load month {} for archive review: {}",
+                        month,
+                        error
+                    );
+                    let _ = self.data_tx.send(DataEvent::LoadError { error });
+                    return;
+                }
+            }
+        }
+
+        let suggestions = archive::suggestions(&months);
+        tracing::info!("Found {} unused categories", suggestions.len());
+        let _ = self
+            .data_tx
+            .send(PlanEvent::ArchiveSuggestionsLoaded { suggestions }.into());
+    }
+
+    /// The categories in the `count` plan months before `month`, oldest first,
+    /// for a quick-budget command. Months that can't be loaded, like those
+    /// before the budget started, are left out.
+    ///
+    /// A single category is fetched on its own from each month rather than
+    /// loading every month's whole plan.
+    pub async fn load_quick_budget_history(
+        &self,
+        budget_id: String,
+        month: String,
+        count: u32,
+        category_ids: Vec<String>,
+    ) {
+        let Ok(start) = NaiveDate::parse_from_str(&month, "%Y-%m-%d") else {
+            tracing::error!("Can't read plan month {}", month);
+            return;
+        };
+
+        let mut months = Vec::new();
+        let mut last_error = None;
+        if let [category_id] = category_ids.as_slice() {
+            let Ok(category_uuid) = category_id.parse::<Uuid>() else {
+                tracing::error!("Can't read category id {}", category_id);
+                return;
+            };
+            for req in Request::categories()
+                .with_budget(BudgetId::from(budget_id.clone()))
+                .months_before(category_uuid, start, count)
+            {
+                match self.send(req).await {
+                    Ok(response) => {
+                        months.push(vec![response.data.category]);
+                    }
+                    Err(error) => {
+                        tracing::warn!("Skipping a month for quick budget: {}", error);
+                        last_error = Some(error.into());
+                    }
+                }
+            }
+        } else {
+            for back in (1..=count).rev() {
+                let month = (start - chrono::Months::new(back))
+                    .format("%Y-%m-%d")
+                    .to_string();
+                match self.month_detail(&budget_id, &month, false).await {
+                    Ok(detail) => months.push(detail.categories),
+                    Err(error) => {
+                        tracing::warn!("Skipping month {} for quick budget: {}", month, error);
+                        last_error = Some(error);
+                    }
+                }
+            }
+        }
+
+        match last_error {
+            Some(error) if months.is_empty() => {
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+            _ => {
+                let _ = self
+                    .data_tx
+                    .send(PlanEvent::QuickBudgetHistoryLoaded { months }.into());
+            }
+        }
+    }
+
+    /// Work out the health checklist from the cached accounts, transactions and this month's plan
+    pub async fn load_health_checklist(&self, budget_id: String) {
+        tracing::info!("Checking the health of budget {}", budget_id);
+        match self.health_checklist(&budget_id).await {
+            Ok(items) => {
+                let _ = self
+                    .data_tx
+                    .send(PlanEvent::HealthChecklistLoaded { items }.into());
+            }
+            Err(error) => {
+                tracing::error!("Failed to check budget health: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    async fn health_checklist(&self, budget_id: &str) -> Result<Vec<HealthItem>, AppError> {
+        let today = chrono::Local::now().date_naive();
+        let month = reports::first_of_month(today)
+            .format("%Y-%m-%d")
+            .to_string();
+        let month = self.month_detail(budget_id, &month, false).await?;
+
+        let accounts = match self.cached("accounts", self.cache.get_accounts(budget_id).await) {
+            Some(cached) => cached.accounts,
+            None => {
+                let req = Request::accounts()
+                    .with_budget(BudgetId::from(budget_id.to_string()))
+                    .list();
+                let response = self.send(req).await?;
+                response.data.accounts
+            }
+        };
+
+        // Only accounts opened before have a cached history; the rest aren't fetched
+        let mut transactions = Vec::new();
+        for account in &accounts {
+            let account_id = account.id.to_string();
+            if let Some(cached) = self.cached(
+                "transactions",
+                self.cache.get_transactions(budget_id, &account_id).await,
+            ) {
+                transactions.push((account_id, cached.transactions));
+            }
+        }
+
+        Ok(health::checklist(&accounts, &transactions, &month, today))
+    }
+
+    /// Move each category into the budget's archive group, one request at a
+    /// time. YNAB's API can't hide a category, so the group has to exist.
+    pub async fn archive_categories(&self, budget_id: String, category_ids: Vec<String>) {
+        tracing::info!(
+            "Archiving {} categories in budget {}",
+            category_ids.len(),
+            budget_id
+        );
+
+        let groups = match self
+            .send(
+                Request::categories()
+                    .list()
+                    .budget_id(BudgetId::from(budget_id.clone())),
+            )
+            .await
+        {
+            Ok(response) => response.data.category_groups,
+            Err(e) => {
+                tracing::error!("Failed to load category groups: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+                return;
+            }
+        };
+        let Some(group) = archive::archive_group(&groups) else {
+            let _ = self.data_tx.send(PlanEvent::ArchiveGroupMissing.into());
+            return;
+        };
+        let (group_id, group_name) = (group.id, group.name.clone());
+
+        let repository = Request::categories().with_budget(budget_id.clone().into());
+        let mut categories = Vec::new();
+        let mut failed = 0;
+        for category_id in category_ids {
+            let Ok(category_uuid) = category_id.parse::<Uuid>() else {
+                failed += 1;
+                continue;
+            };
+            let req = repository.update(
+                category_uuid,
+                SaveCategory {
+                    category_group_id: Some(group_id),
+                    ..Default::default()
+                },
+            );
+            match self.send(req).await {
+                Ok(response) => {
+                    let mut category = response.data.category;
+                    if category.category_group_id == group_id {
+                        category.category_group_name = Some(group_name.clone());
+                        categories.push(category);
+                    } else {
+                        tracing::warn!("Server did not move category {}", category_id);
+                        failed += 1;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to archive category {}: {}", category_id, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        let _ = self.data_tx.send(
+            PlanEvent::CategoriesArchived {
+                categories,
+                group: group_name,
+                failed,
+            }
+            .into(),
+        );
+        // Refresh the category list so pickers list them under the archive group
+        self.load_categories(budget_id, true).await;
+    }
+
+    /// Move a category into another group
+    pub async fn move_category(
+        &self,
+        budget_id: String,
+        category_id: String,
+        group_id: Uuid,
+        forward: bool,
+    ) {
+        let Ok(category_uuid) = category_id.parse::<Uuid>() else {
+            tracing::error!("Invalid category id {}", category_id);
+            return;
+        };
+        let req = Request::categories().with_budget(budget_id.into()).update(
+            category_uuid,
+            SaveCategory {
+                category_group_id: Some(group_id),
+                ..Default::default()
+            },
+        );
+        match self.send(req).await {
+            Ok(response) => {
+                let category = response.data.category;
+                let _ = self
+                    .data_tx
+                    .send(PlanEvent::CategoryMoved { category, forward }.into());
+            }
+            Err(e) => {
+                tracing::error!("Failed to move category {}: {}", category_id, e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+            }
+        }
+    }
+
+    /// Change a category's goal target and date. `original` is the category
+    /// before the optimistic edit, put back if the server refuses it.
+    pub async fn update_category_goal(
+        &self,
+        budget_id: String,
+        original: Category,
+        target: i64,
+        target_month: Option<NaiveDate>,
+    ) {
+        let req = Request::categories().with_budget(budget_id.into()).update(
+            original.id,
+            SaveCategory {
+                goal_target: Some(target.into()),
+                goal_target_date: Some(target_month),
+                ..Default::default()
+            },
+        );
+        match self.send(req).await {
+            Ok(response) => {
+                let category = response.data.category;
+                let _ = self
+                    .data_tx
+                    .send(PlanEvent::CategoryGoalUpdated { category }.into());
+            }
+            Err(e) => {
+                tracing::error!("Failed to update goal of category {}: {}", original.id, e);
+                let _ = self.data_tx.send(
+                    PlanEvent::CategoryGoalUpdateFailed {
+                        original,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// A single plan month, from the cache unless stale data isn't acceptable
+    async fn month_detail(
+        &self,
+        budget_id: &str,
+        month: &str,
+        force_refresh: bool,
+    ) -> Result<MonthDetail, AppError> {
+        use ynab_api::endpoints::months::Month;
+
+        if !force_refresh {
+            if let Some(cached) = self.cached(
+                "plan month",
+                self.cache.get_plan_month(budget_id, month).await,
+            ) {
+                return Ok(cached.month);
+            }
+        }
+
+        let req = Request::months()
+            .get()
+            .budget_id(BudgetId::from(budget_id.to_string()))
+            .month(Month::Month(month.to_string()));
+        let response = self.send(req).await?;
+
+        let detail = response.data.month;
+        let _ = self
+            .cache
+            .set_plan_month(budget_id, month, &detail, &detail.categories)
+            .await;
+        Ok(detail)
+    }
+
+    /// Force-refresh everything for a budget concurrently, then signal completion.
+    /// If `plan_month` is set, that month is refreshed instead of the current one.
+    pub async fn refresh_all(&self, budget_id: String, plan_month: Option<String>) {
+        tracing::info!("Refreshing all data for budget {}", budget_id);
+
+        let plan = async {
+            match plan_month {
+                Some(month) => self.load_plan_month(budget_id.clone(), month).await,
+                None => self.load_plan(budget_id.clone(), true).await,
+            }
+        };
+        tokio::join!(
+            self.load_budgets(true, false),
+            self.load_accounts(budget_id.clone(), true),
+            plan,
+            self.load_payees(budget_id.clone(), true),
+            self.load_categories(budget_id.clone(), true),
+        );
+
+        tracing::info!("Refresh of budget {} complete", budget_id);
+        let _ = self.data_tx.send(BudgetEvent::RefreshAllCompleted.into());
+    }
+
+    /// Auto-refresh: apply account changes made elsewhere since the cache
+    /// was last updated. Failures are only logged, since nothing was asked for.
+    pub async fn poll_accounts(&self, budget_id: String) {
+        if self.saving_quota() {
+            return;
+        }
+        let cached = self.cached("accounts", self.cache.get_accounts(&budget_id).await);
+        if let Some(server_knowledge) = cached.and_then(|c| c.server_knowledge) {
+            let since = catch_up_from(self.cache.known_accounts(&budget_id), server_knowledge);
+            self.check_accounts_delta(budget_id, since).await;
+        }
+    }
+
+    /// Auto-refresh: apply transaction changes made elsewhere to an account
+    pub async fn poll_transactions(&self, budget_id: String, account_id: String) {
+        if self.saving_quota() {
+            return;
+        }
+        let Some(cached) = self.cached(
+            "transactions",
+            self.cache.get_transactions(&budget_id, &account_id).await,
+        ) else {
+            return;
+        };
+        if let Some(server_knowledge) = cached.server_knowledge {
+            let since = catch_up_from(
+                self.cache.known_transactions(&budget_id, &account_id),
+                server_knowledge,
+            );
+            self.check_transactions_delta(budget_id, account_id, since, cached.loaded_since)
+                .await;
+        }
+    }
+
+    /// Auto-refresh: fetch the plan month on screen again, since months
+    /// have no delta requests. `None` is the current month.
+    pub async fn poll_plan(&self, budget_id: String, month: Option<String>) {
+        if self.saving_quota() {
+            return;
+        }
+        let result = match month {
+            Some(month) => self.try_fetch_plan_month(budget_id, month).await,
+            None => self.try_fetch_plan_full(budget_id).await,
+        };
+        if let Err(e) = result {
+            tracing::error!("Plan refresh failed: {}", e);
+        }
+    }
+
+    /// Load transactions with cache-first strategy and delta updates
+    pub async fn load_transactions(
+        &self,
+        budget_id: String,
+        account_id: String,
+        force_refresh: bool,
+    ) {
+        tracing::info!(
+            "Loading transactions for budget {} account {} (force_refresh={})",
+            budget_id,
+            account_id,
+            force_refresh
+        );
+
+        let cached = self.cached(
+            "transactions",
+            self.cache.get_transactions(&budget_id, &account_id).await,
+        );
+
+        // Step 1: Try cache first (fast path)
+        if !force_refresh {
+            if let Some(cached) = cached {
+                // Filter out deleted transactions
+                let transactions: Vec<_> = cached
+                    .transactions
+                    .into_iter()
+                    .filter(|t| !t.deleted)
+                    .collect();
+
+                tracing::debug!("Loaded {} transactions from cache", transactions.len());
+                // Send cached data immediately
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionsCacheLoaded {
+                        transactions: transactions.clone(),
+                        loaded_since: cached.loaded_since,
+                    }
+                    .into(),
+                );
+                self.cache
+                    .know_transactions(&budget_id, &account_id, cached.server_knowledge);
+
+                // Step 2: Check for delta updates in background
+                if self.saving_quota() {
+                    return;
+                }
+                if let Some(server_knowledge) = cached.server_knowledge {
+                    tracing::debug!(
+                        "Checking for transaction deltas (server_knowledge={})",
+                        server_knowledge
+                    );
+                    self.check_transactions_delta(
+                        budget_id.clone(),
+                        account_id.clone(),
+                        server_knowledge,
+                        cached.loaded_since,
+                    )
+                    .await;
+                } else {
+                    tracing::debug!("No server knowledge, fetching full transactions");
+                    // No server_knowledge, need full refresh
+                    self.fetch_transactions_full(
+                        budget_id.clone(),
+                        account_id.clone(),
+                        cached.loaded_since,
+                    )
+                    .await;
+                }
+                return;
+            } else {
+                tracing::debug!("No cached transactions found");
+            }
+        }
+
+        // Cache miss or forced refresh - load from API. A refresh keeps
+        // whatever history was already paged in; a first load only fetches
+        // the most recent window.
+        let since = match cached {
+            Some(cached) => cached.loaded_since,
+            None => Some(history_window_start(chrono::Local::now().date_naive())),
+        };
+        self.fetch_transactions_full(budget_id, account_id, since)
+            .await;
+    }
+
+    /// Check for delta updates to transactions
+    async fn check_transactions_delta(
+        &self,
+        budget_id: String,
+        account_id: String,
+        last_knowledge: i64,
+        loaded_since: Option<NaiveDate>,
+    ) {
+        let budget_id_api: BudgetId = budget_id.clone().into();
+        let account_id_uuid = Uuid::parse_str(&account_id).expect("invalid account_id uuid");
+        let mut req = Request::transactions()
+            .with_budget(budget_id_api)
+            .list(account_id_uuid)
+            .last_knowledge_of_server(last_knowledge.into());
+        // Changes to history that hasn't been paged in yet aren't wanted
+        if let Some(since) = loaded_since {
+            req = req.since_date(since);
+        }
+        match self.send(req).await {
+            Ok(delta_response) => {
+                // Check if there are actual changes
+                if let Some(new_knowledge) = delta_response.data.server_knowledge {
+                    if new_knowledge.inner() > last_knowledge {
+                        // Filter out deleted transactions
+                        let delta: Vec<_> = delta_response
+                            .data
+                            .transactions
+                            .into_iter()
+                            .filter(|t| !t.deleted)
+                            .collect();
+
+                        // Send delta update
+                        let _ = self.data_tx.send(
+                            TransactionEvent::TransactionsDeltaLoaded {
+                                delta: delta.clone(),
+                            }
+                            .into(),
+                        );
+
+                        // Update cache in background
+                        let cache = self.cache.clone();
+                        let budget_id_clone = budget_id.clone();
+                        let account_id_clone = account_id.clone();
+                        let new_knowledge_i64 = new_knowledge.inner();
+                        tokio::spawn(async move {
+                            let _ = cache
+                                .merge_transactions_delta(
+                                    &budget_id_clone,
+                                    &account_id_clone,
+                                    &delta,
+                                    new_knowledge_i64,
+                                )
+                                .await;
+                        });
+                    }
+                }
+            }
+            Err(e) => {
+                // Delta check failed, not critical (we have cached data)
+                tracing::error!("Delta check failed for transactions: {}", e);
+            }
+        }
+    }
+
+    /// Fetch full transactions data from API, back to `since` if given
+    async fn fetch_transactions_full(
+        &self,
+        budget_id: String,
+        account_id: String,
+        since: Option<NaiveDate>,
+    ) {
+        let budget_id_api: BudgetId = budget_id.clone().into();
+        let account_id_uuid = Uuid::parse_str(&account_id).expect("invalid account_id uuid");
+        let mut req = Request::transactions()
+            .with_budget(budget_id_api)
+            .list(account_id_uuid);
+        if let Some(since) = since {
+            req = req.since_date(since);
+        }
+        match self.send(req).await {
+            Ok(response) => {
+                // Filter out deleted transactions
+                let transactions: Vec<_> = response
+                    .data
+                    .transactions
+                    .into_iter()
+                    .filter(|t| !t.deleted)
+                    .collect();
+
+                // Send fresh data
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionsLoaded {
+                        transactions: transactions.clone(),
+                        loaded_since: since,
+                    }
+                    .into(),
+                );
+
+                // Update cache in background
+                let cache = self.cache.clone();
+                let budget_id_clone = budget_id.clone();
+                let account_id_clone = account_id.clone();
+                let server_knowledge = response.data.server_knowledge.map(|k| k.inner());
+                tokio::spawn(async move {
+                    let _ = cache
+                        .set_transactions(
+                            &budget_id_clone,
+                            &account_id_clone,
+                            &transactions,
+                            server_knowledge,
+                            since,
+                        )
+                        .await;
+                });
+            }
+            Err(e) => {
+                tracing::error!("Failed to load transactions from API: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+            }
+        }
+    }
+
+    /// Page in the window of history before `before`.
+    ///
+    /// The API can only bound a listing from below, so the page repeats the
+    /// newer transactions we already have; only the older ones are kept. An
+    /// empty window (e.g. a quiet savings account) fetches all remaining
+    /// history at once rather than stepping back one empty page at a time.
+    pub async fn load_older_transactions(
+        &self,
+        budget_id: String,
+        account_id: String,
+        before: NaiveDate,
+    ) {
+        let since = history_window_start(before);
         tracing::info!(
-            "Loading plan for budget {} (force_refresh={})",
+            "Loading transactions for account {} from {} to {}",
+            account_id,
+            since,
+            before
+        );
+
+        let result = match self
+            .fetch_transactions_before(&budget_id, &account_id, before, Some(since))
+            .await
+        {
+            Ok(older) if older.is_empty() => self
+                .fetch_transactions_before(&budget_id, &account_id, before, None)
+                .await
+                .map(|older| (older, None)),
+            Ok(older) => Ok((older, Some(since))),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok((transactions, loaded_since)) => {
+                tracing::debug!(
+                    "Loaded {} older transactions (history complete: {})",
+                    transactions.len(),
+                    loaded_since.is_none()
+                );
+                let _ = self.data_tx.send(
+                    TransactionEvent::OlderTransactionsLoaded {
+                        transactions: transactions.clone(),
+                        loaded_since,
+                    }
+                    .into(),
+                );
+
+                let cache = self.cache.clone();
+                tokio::spawn(async move {
+                    let _ = cache
+                        .merge_older_transactions(
+                            &budget_id,
+                            &account_id,
+                            &transactions,
+                            loaded_since,
+                        )
+                        .await;
+                });
+            }
+            Err(e) => {
+                tracing::error!("Failed to load older transactions: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e });
+            }
+        }
+    }
+
+    /// Transactions dated before `before`, fetched from `since` (or the start)
+    async fn fetch_transactions_before(
+        &self,
+        budget_id: &str,
+        account_id: &str,
+        before: NaiveDate,
+        since: Option<NaiveDate>,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let account_uuid = Uuid::parse_str(account_id)
+            .map_err(|e| AppError::new(ErrorKind::Other, e.to_string()))?;
+        let mut req = Request::transactions()
+            .with_budget(budget_id.to_string().into())
+            .list(account_uuid);
+        if let Some(since) = since {
+            req = req.since_date(since);
+        }
+        let response = self.send(req).await?;
+        Ok(response
+            .data
+            .transactions
+            .into_iter()
+            .filter(|t| !t.deleted && t.date < before)
+            .collect())
+    }
+
+    /// Load every account's transactions for a budget-wide search.
+    ///
+    /// Not cached: the per-account caches stay the source for account screens.
+    pub async fn load_budget_transactions(&self, budget_id: String) {
+        tracing::info!("Loading all transactions for budget {}", budget_id);
+
+        match self.fetch_budget_transactions(&budget_id, None).await {
+            Ok(transactions) => {
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionsLoaded {
+                        transactions,
+                        loaded_since: None,
+                    }
+                    .into(),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to load budget transactions: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e });
+            }
+        }
+    }
+
+    /// Load every transaction in the budget still waiting for review: the
+    /// unapproved ones and the uncategorized ones
+    pub async fn load_inbox(&self, budget_id: String) {
+        tracing::info!("Loading inbox for budget {}", budget_id);
+
+        let list = |transaction_type| {
+            Request::transactions()
+                .with_budget(budget_id.clone().into())
+                .list_all()
+                .transaction_type(transaction_type)
+        };
+        let (unapproved, uncategorized) = tokio::join!(
+            self.send(list(TransactionType::Unapproved)),
+            self.send(list(TransactionType::Uncategorized)),
+        );
+        match unapproved.and_then(|u| Ok((u, uncategorized?))) {
+            Ok((unapproved, uncategorized)) => {
+                let mut transactions: Vec<Transaction> = Vec::new();
+                for transaction in unapproved
+                    .data
+                    .transactions
+                    .into_iter()
+                    .chain(uncategorized.data.transactions)
+                {
+                    if !transaction.deleted && !transactions.iter().any(|t| t.id == transaction.id)
+                    {
+                        transactions.push(transaction);
+                    }
+                }
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionsLoaded {
+                        transactions,
+                        loaded_since: None,
+                    }
+                    .into(),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to load inbox: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+            }
+        }
+    }
+
+    /// Load scheduled transactions with cache-first strategy and delta updates
+    pub async fn load_scheduled_transactions(&self, budget_id: String, force_refresh: bool) {
+        tracing::info!(
+            "Loading scheduled transactions for budget {} (force_refresh={})",
             budget_id,
             force_refresh
         );
 
-        // Step 1: Try cache first (fast path)
         if !force_refresh {
-            if let Ok(Some(cached)) = self.cache.get_plan(&budget_id).await {
-                tracing::debug!("Loaded {} categories from cache", cached.categories.len());
-                // Send cached data immediately
-                let _ = self.data_tx.send(DataEvent::PlanCacheLoaded {
-                    month: cached.month.clone(),
-                    categories: cached.categories.clone(),
-                });
+            if let Some(cached) = self.cached(
+                "scheduled transactions",
+                self.cache.get_scheduled_transactions(&budget_id).await,
+            ) {
+                tracing::debug!(
+                    "Loaded {} scheduled transactions from cache",
+                    cached.scheduled_transactions.len()
+                );
+                let _ = self.data_tx.send(
+                    ScheduledEvent::ScheduledTransactionsLoaded {
+                        scheduled_transactions: cached.scheduled_transactions,
+                    }
+                    .into(),
+                );
 
-                // For now, we always fetch fresh data after showing cache
-                // TODO: Implement delta updates if YNAB API supports server_knowledge for months
-                tracing::debug!("Fetching fresh plan data from API");
-                self.fetch_plan_full(budget_id).await;
+                if self.saving_quota() {
+                    return;
+                }
+                match cached.server_knowledge {
+                    Some(server_knowledge) => {
+                        self.check_scheduled_transactions_delta(budget_id, server_knowledge)
+                            .await
+                    }
+                    None => self.fetch_scheduled_transactions_full(budget_id).await,
+                }
                 return;
-            } else {
-                tracing::debug!("No cached plan found");
             }
         }
 
-        // Cache miss or forced refresh - load from API
-        self.fetch_plan_full(budget_id).await;
+        self.fetch_scheduled_transactions_full(budget_id).await;
     }
 
-    /// Fetch full plan data from API
-    async fn fetch_plan_full(&self, budget_id: String) {
-        tracing::debug!("Fetching full plan from API");
-        let budget_id_api: BudgetId = budget_id.clone().into();
-        let req = Request::months().get().budget_id(budget_id_api);
-        match self.api_client.send(req).await {
-            Ok(response) => {
-                tracing::info!(
-                    "Loaded {} categories from API",
-                    response.data.month.categories.len()
+    /// Check for delta updates to scheduled transactions
+    async fn check_scheduled_transactions_delta(&self, budget_id: String, last_knowledge: i64) {
+        let req = Request::scheduled_transactions()
+            .with_budget(budget_id.clone().into())
+            .list()
+            .last_knowledge_of_server(last_knowledge.into());
+        match self.send(req).await {
+            Ok(delta_response) => {
+                let Some(new_knowledge) = delta_response.data.server_knowledge else {
+                    return;
+                };
+                if new_knowledge.inner() <= last_knowledge {
+                    return;
+                }
+
+                // Deleted schedules stay in the delta so the screen can drop them
+                let delta = delta_response.data.scheduled_transactions;
+                let _ = self.data_tx.send(
+                    ScheduledEvent::ScheduledTransactionsDeltaLoaded {
+                        delta: delta.clone(),
+                    }
+                    .into(),
                 );
-                // Send fresh data
-                let _ = self.data_tx.send(DataEvent::PlanLoaded {
-                    month: response.data.month.clone(),
-                    categories: response.data.month.categories.clone(),
+
+                let cache = self.cache.clone();
+                tokio::spawn(async move {
+                    let _ = cache
+                        .merge_scheduled_transactions_delta(
+                            &budget_id,
+                            &delta,
+                            new_knowledge.inner(),
+                        )
+                        .await;
                 });
+            }
+            Err(e) => {
+                // Delta check failed, not critical (we have cached data)
+                tracing::error!("Delta check failed for scheduled transactions: {}", e);
+            }
+        }
+    }
+
+    /// Fetch all scheduled transactions from the API
+    async fn fetch_scheduled_transactions_full(&self, budget_id: String) {
+        let req = Request::scheduled_transactions()
+            .with_budget(budget_id.clone().into())
+            .list();
+        match self.send(req).await {
+            Ok(response) => {
+                let scheduled_transactions: Vec<_> = response
+                    .data
+                    .scheduled_transactions
+                    .into_iter()
+                    .filter(|s| !s.deleted)
+                    .collect();
+
+                let _ = self.data_tx.send(
+                    ScheduledEvent::ScheduledTransactionsLoaded {
+                        scheduled_transactions: scheduled_transactions.clone(),
+                    }
+                    .into(),
+                );
 
-                // Update cache in background
                 let cache = self.cache.clone();
-                let budget_id_clone = budget_id.clone();
-                let month = response.data.month;
                 let server_knowledge = response.data.server_knowledge.map(|k| k.inner());
                 tokio::spawn(async move {
                     let _ = cache
-                        .set_plan(
-                            &budget_id_clone,
-                            &month,
-                            &month.categories,
+                        .set_scheduled_transactions(
+                            &budget_id,
+                            &scheduled_transactions,
                             server_knowledge,
                         )
                         .await;
-                    tracing::debug!("Cached plan updated");
                 });
             }
-            Err(e) => {
-                tracing::error!("Failed to load plan from API: {}", e);
-                let _ = self.data_tx.send(DataEvent::LoadError {
-                    error: e.to_string(),
-                });
+            Err(e) => {
+                tracing::error!("Failed to load scheduled transactions from API: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+            }
+        }
+    }
+
+    /// Load plan with cache-first strategy
+    pub async fn load_plan(&self, budget_id: String, force_refresh: bool) {
+        tracing::info!(
+            "Loading plan for budget {} (force_refresh={})",
+            budget_id,
+            force_refresh
+        );
+
+        // Step 1: Try cache first (fast path)
+        if !force_refresh {
+            if let Some(cached) = self.cached("plan", self.cache.get_plan(&budget_id).await) {
+                tracing::debug!("Loaded {} categories from cache", cached.categories.len());
+                // Send cached data immediately
+                let _ = self.data_tx.send(
+                    PlanEvent::PlanCacheLoaded {
+                        month: cached.month.clone(),
+                        categories: cached.categories.clone(),
+                    }
+                    .into(),
+                );
+
+                // For now, we always fetch fresh data after showing cache
+                // TODO: Implement delta updates if YNAB API supports server_knowledge for months
+                if self.saving_quota() {
+                    return;
+                }
+                tracing::debug!("Fetching fresh plan data from API");
+                self.fetch_plan_full(budget_id).await;
+                return;
+            } else {
+                tracing::debug!("No cached plan found");
+            }
+        }
+
+        // Cache miss or forced refresh - load from API
+        self.fetch_plan_full(budget_id).await;
+    }
+
+    /// Load this month's category balances for the transactions screen,
+    /// fetching the plan only if it isn't cached yet
+    pub async fn load_category_balances(&self, budget_id: String) {
+        match self.cached("plan", self.cache.get_plan(&budget_id).await) {
+            Some(cached) => {
+                let _ = self.data_tx.send(
+                    PlanEvent::PlanCacheLoaded {
+                        month: cached.month,
+                        categories: cached.categories,
+                    }
+                    .into(),
+                );
             }
+            None => self.fetch_plan_full(budget_id).await,
+        }
+    }
+
+    /// Fetch full plan data from API
+    async fn fetch_plan_full(&self, budget_id: String) {
+        tracing::debug!("Fetching full plan from API");
+        if let Err(e) = self.try_fetch_plan_full(budget_id).await {
+            tracing::error!("Failed to load plan from API: {}", e);
+            let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
         }
     }
 
+    async fn try_fetch_plan_full(&self, budget_id: String) -> Result<(), YnabApiError> {
+        let budget_id_api: BudgetId = budget_id.clone().into();
+        let req = Request::months().get().budget_id(budget_id_api);
+        let response = self.send(req).await?;
+        tracing::info!(
+            "Loaded {} categories from API",
+            response.data.month.categories.len()
+        );
+        // Send fresh data
+        let _ = self.data_tx.send(
+            PlanEvent::PlanLoaded {
+                month: response.data.month.clone(),
+                categories: response.data.month.categories.clone(),
+            }
+            .into(),
+        );
+
+        // Update cache in background
+        let cache = self.cache.clone();
+        let month = response.data.month;
+        let server_knowledge = response.data.server_knowledge.map(|k| k.inner());
+        tokio::spawn(async move {
+            let _ = cache
+                .set_plan(&budget_id, &month, &month.categories, server_knowledge)
+                .await;
+            tracing::debug!("Cached plan updated");
+        });
+        Ok(())
+    }
+
     /// Load plan for a specific month with cache-first strategy
     pub async fn load_plan_month(&self, budget_id: String, month: String) {
         tracing::info!("Loading plan for budget {} month {}", budget_id, month);
 
         // Try cache first
-        if let Ok(Some(cached)) = self.cache.get_plan_month(&budget_id, &month).await {
+        if let Some(cached) = self.cached(
+            "plan month",
+            self.cache.get_plan_month(&budget_id, &month).await,
+        ) {
             tracing::debug!(
                 "Loaded {} categories from cache for month {}",
                 cached.categories.len(),
                 month
             );
             // Send cached data immediately
-            let _ = self.data_tx.send(DataEvent::PlanCacheLoaded {
-                month: cached.month.clone(),
-                categories: cached.categories.clone(),
-            });
+            let _ = self.data_tx.send(
+                PlanEvent::PlanCacheLoaded {
+                    month: cached.month.clone(),
+                    categories: cached.categories.clone(),
+                }
+                .into(),
+            );
 
             // Still fetch fresh data in background to keep cache updated
             self.fetch_plan_month(budget_id, month).await;
@@ -465,49 +2555,95 @@ impl DataLoader {
 
     /// Fetch plan data for a specific month from API
     async fn fetch_plan_month(&self, budget_id: String, month: String) {
+        if let Err(e) = self.try_fetch_plan_month(budget_id, month.clone()).await {
+            tracing::error!("Failed to load plan for month {}: {}", month, e);
+            let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+        }
+    }
+
+    async fn try_fetch_plan_month(
+        &self,
+        budget_id: String,
+        month: String,
+    ) -> Result<(), YnabApiError> {
+        let month = self.fetch_month_detail(budget_id, month).await?;
+        let _ = self.data_tx.send(
+            PlanEvent::PlanLoaded {
+                categories: month.categories.clone(),
+                month,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    /// Load a month to compare the plan against, from the cache first and
+    /// then fresh from the API
+    pub async fn load_comparison_month(&self, budget_id: String, month: String) {
+        tracing::info!(
+            "Loading comparison month {} for budget {}",
+            month,
+            budget_id
+        );
+        if let Some(cached) = self.cached(
+            "plan month",
+            self.cache.get_plan_month(&budget_id, &month).await,
+        ) {
+            let _ = self.data_tx.send(
+                PlanEvent::ComparisonMonthLoaded {
+                    month: cached.month,
+                    categories: cached.categories,
+                }
+                .into(),
+            );
+        }
+
+        match self.fetch_month_detail(budget_id, month.clone()).await {
+            Ok(month) => {
+                let _ = self.data_tx.send(
+                    PlanEvent::ComparisonMonthLoaded {
+                        categories: month.categories.clone(),
+                        month,
+                    }
+                    .into(),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to load comparison month {}: {}", month, e);
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
+            }
+        }
+    }
+
+    /// Fetch one month of the plan from the API and cache it
+    async fn fetch_month_detail(
+        &self,
+        budget_id: String,
+        month: String,
+    ) -> Result<MonthDetail, YnabApiError> {
         use ynab_api::endpoints::months::Month;
         let budget_id_api: BudgetId = budget_id.clone().into();
         let req = Request::months()
             .get()
             .budget_id(budget_id_api)
             .month(Month::Month(month.clone()));
-        match self.api_client.send(req).await {
-            Ok(response) => {
-                tracing::info!(
-                    "Loaded {} categories from API for month {}",
-                    response.data.month.categories.len(),
-                    month
-                );
-                // Send fresh data
-                let _ = self.data_tx.send(DataEvent::PlanLoaded {
-                    month: response.data.month.clone(),
-                    categories: response.data.month.categories.clone(),
-                });
+        let response = self.send(req).await?;
+        tracing::info!(
+            "Loaded {} categories from API for month {}",
+            response.data.month.categories.len(),
+            month
+        );
 
-                // Update cache in background
-                let cache = self.cache.clone();
-                let budget_id_clone = budget_id.clone();
-                let month_clone = month.clone();
-                let month_detail = response.data.month;
-                tokio::spawn(async move {
-                    let _ = cache
-                        .set_plan_month(
-                            &budget_id_clone,
-                            &month_clone,
-                            &month_detail,
-                            &month_detail.categories,
-                        )
-                        .await;
-                    tracing::debug!("Cached plan for month {} updated", month_clone);
-                });
-            }
-            Err(e) => {
-                tracing::error!("Failed to load plan for month {}: {}", month, e);
-                let _ = self.data_tx.send(DataEvent::LoadError {
-                    error: e.to_string(),
-                });
-            }
-        }
+        // Update cache in background
+        let cache = self.cache.clone();
+        let month_detail = response.data.month.clone();
+        tokio::spawn(async move {
+            let _ = cache
+                .set_plan_month(&budget_id, &month, &month_detail, &month_detail.categories)
+                .await;
+            tracing::debug!("Cached plan for month {} updated", month);
+        });
+        Ok(response.data.month)
     }
 
     /// Load payees for a budget (for transaction creation autocomplete)
@@ -520,11 +2656,11 @@ impl DataLoader {
 
         // Try cache first unless force refresh
         if !force_refresh {
-            if let Ok(Some(cached)) = self.cache.get_payees(&budget_id).await {
+            if let Some(cached) = self.cached("payees", self.cache.get_payees(&budget_id).await) {
                 tracing::debug!("Loaded {} payees from cache", cached.len());
                 let _ = self
                     .data_tx
-                    .send(DataEvent::PayeesLoaded { payees: cached });
+                    .send(PayeeEvent::PayeesLoaded { payees: cached }.into());
                 return;
             }
         }
@@ -533,7 +2669,7 @@ impl DataLoader {
         tracing::debug!("Fetching payees from API");
         let budget_id_api: BudgetId = budget_id.clone().into();
         let req = Request::payees().list().budget_id(budget_id_api);
-        match self.api_client.send(req).await {
+        match self.send(req).await {
             Ok(response) => {
                 // Filter out deleted payees
                 let payees: Vec<_> = response
@@ -544,9 +2680,12 @@ impl DataLoader {
                     .collect();
 
                 tracing::info!("Loaded {} payees from API", payees.len());
-                let _ = self.data_tx.send(DataEvent::PayeesLoaded {
-                    payees: payees.clone(),
-                });
+                let _ = self.data_tx.send(
+                    PayeeEvent::PayeesLoaded {
+                        payees: payees.clone(),
+                    }
+                    .into(),
+                );
 
                 // Update cache in background
                 let cache = self.cache.clone();
@@ -558,9 +2697,7 @@ impl DataLoader {
             }
             Err(e) => {
                 tracing::error!("Failed to load payees from API: {}", e);
-                let _ = self.data_tx.send(DataEvent::LoadError {
-                    error: e.to_string(),
-                });
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
             }
         }
     }
@@ -575,11 +2712,13 @@ impl DataLoader {
 
         // Try cache first unless force refresh
         if !force_refresh {
-            if let Ok(Some(cached)) = self.cache.get_categories(&budget_id).await {
+            if let Some(cached) =
+                self.cached("categories", self.cache.get_categories(&budget_id).await)
+            {
                 tracing::debug!("Loaded {} categories from cache", cached.len());
                 let _ = self
                     .data_tx
-                    .send(DataEvent::CategoriesLoaded { categories: cached });
+                    .send(PlanEvent::CategoriesLoaded { categories: cached }.into());
                 return;
             }
         }
@@ -588,7 +2727,7 @@ impl DataLoader {
         tracing::debug!("Fetching categories from API");
         let budget_id_api: BudgetId = budget_id.clone().into();
         let req = Request::categories().list().budget_id(budget_id_api);
-        match self.api_client.send(req).await {
+        match self.send(req).await {
             Ok(response) => {
                 // Flatten category groups into single list with group name prefix
                 let mut categories = Vec::new();
@@ -605,9 +2744,12 @@ impl DataLoader {
                 }
 
                 tracing::info!("Loaded {} categories from API", categories.len());
-                let _ = self.data_tx.send(DataEvent::CategoriesLoaded {
-                    categories: categories.clone(),
-                });
+                let _ = self.data_tx.send(
+                    PlanEvent::CategoriesLoaded {
+                        categories: categories.clone(),
+                    }
+                    .into(),
+                );
 
                 // Update cache in background
                 let cache = self.cache.clone();
@@ -619,15 +2761,84 @@ impl DataLoader {
             }
             Err(e) => {
                 tracing::error!("Failed to load categories from API: {}", e);
-                let _ = self.data_tx.send(DataEvent::LoadError {
-                    error: e.to_string(),
-                });
+                let _ = self.data_tx.send(DataEvent::LoadError { error: e.into() });
             }
         }
     }
 
     /// Create a new transaction
     pub async fn create_transaction(&self, budget_id: String, new_transaction: NewTransaction) {
+        match self
+            .send_new_transaction(&budget_id, new_transaction.clone())
+            .await
+        {
+            Ok(Some(transaction)) => {
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionCreated { transaction }.into());
+            }
+            Ok(None) => tracing::warn!("YNAB already had this transaction, nothing created"),
+            Err(e)
+                if write_queue::is_offline(&e)
+                    && self
+                        .queue_write(PendingWrite::CreateTransaction {
+                            budget_id,
+                            transaction: write_queue::with_import_id(new_transaction),
+                        })
+                        .await => {}
+            Err(e) => {
+                tracing::error!("Failed to create transaction: {}", e);
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionCreateFailed { error: e.into() }.into());
+            }
+        }
+    }
+
+    /// Recreate a deleted transaction (undo), keeping its date and status
+    pub async fn restore_transaction(&self, budget_id: String, transaction: Transaction) {
+        let new_transaction = NewTransaction {
+            cleared: Some(transaction.cleared),
+            approved: Some(transaction.approved),
+            ..validators::build_duplicate_transaction(
+                &transaction,
+                transaction.account_id,
+                transaction.date,
+            )
+        };
+        let original_id = transaction.id.to_string();
+
+        match self.send_new_transaction(&budget_id, new_transaction).await {
+            Ok(None) => tracing::warn!("YNAB already had transaction {}", original_id),
+            Ok(Some(transaction)) => {
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionRestored {
+                        original_id,
+                        transaction,
+                    }
+                    .into(),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to restore transaction {}: {}", original_id, e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionRestoreFailed {
+                        original_id,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// POST a new transaction and invalidate its account's cache. `None` when
+    /// YNAB already has a transaction with its import id.
+    async fn send_new_transaction(
+        &self,
+        budget_id: &str,
+        new_transaction: NewTransaction,
+    ) -> Result<Option<Transaction>, YnabApiError> {
         tracing::info!(
             "Creating transaction for account {} in budget {}",
             new_transaction.account_id,
@@ -635,7 +2846,7 @@ impl DataLoader {
         );
 
         let account_id = new_transaction.account_id;
-        let budget_id_api: BudgetId = budget_id.clone().into();
+        let budget_id_api: BudgetId = budget_id.to_string().into();
         let mut req = Request::transactions().with_budget(budget_id_api).create(
             new_transaction.account_id,
             new_transaction.date,
@@ -667,35 +2878,33 @@ impl DataLoader {
         if let Some(subtransactions) = new_transaction.subtransactions {
             req = req.subtransactions(subtransactions);
         }
+        if let Some(import_id) = new_transaction.import_id {
+            req = req.import_id(import_id);
+        }
 
-        match self.api_client.send(req).await {
-            Ok(response) => {
-                tracing::info!(
-                    "Transaction created successfully: {}",
-                    response.data.transaction.id
-                );
-                let _ = self.data_tx.send(DataEvent::TransactionCreated {
-                    transaction: response.data.transaction,
-                });
-
-                // Invalidate transaction cache to force refresh
-                let cache = self.cache.clone();
-                let budget_id_clone = budget_id.clone();
-                let account_id_str = account_id.to_string();
-                tokio::spawn(async move {
-                    let _ = cache
-                        .invalidate_transactions(&budget_id_clone, &account_id_str)
-                        .await;
-                    tracing::debug!("Transaction cache invalidated");
-                });
-            }
-            Err(e) => {
-                tracing::error!("Failed to create transaction: {}", e);
-                let _ = self.data_tx.send(DataEvent::TransactionCreateFailed {
-                    error: e.to_string(),
-                });
+        let response = self.send(req).await?;
+        match &response.data.transaction {
+            Some(transaction) => {
+                tracing::info!("Transaction created successfully: {}", transaction.id)
             }
+            None => tracing::info!(
+                "Transaction already created, skipped import ids {:?}",
+                response.data.duplicate_import_ids
+            ),
         }
+
+        // Invalidate transaction cache to force refresh
+        let cache = self.cache.clone();
+        let budget_id = budget_id.to_string();
+        let account_id_str = account_id.to_string();
+        tokio::spawn(async move {
+            let _ = cache
+                .invalidate_transactions(&budget_id, &account_id_str)
+                .await;
+            tracing::debug!("Transaction cache invalidated");
+        });
+
+        Ok(response.data.transaction)
     }
 
     /// Update a transaction (full update with all fields)
@@ -705,6 +2914,44 @@ impl DataLoader {
         transaction_id: String,
         update: TransactionUpdate,
     ) {
+        match self
+            .send_transaction_update(&budget_id, &transaction_id, update.clone())
+            .await
+        {
+            Ok(transaction) => {
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionUpdatedFull { transaction }.into());
+            }
+            Err(e)
+                if write_queue::is_offline(&e)
+                    && self
+                        .queue_write(PendingWrite::UpdateTransaction {
+                            budget_id,
+                            transaction_id: transaction_id.clone(),
+                            update,
+                        })
+                        .await => {}
+            Err(e) => {
+                tracing::error!("Failed to update transaction {}: {}", transaction_id, e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionUpdateFullFailed {
+                        transaction_id,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// PUT a transaction's new fields and invalidate the cache of the account it moved to
+    async fn send_transaction_update(
+        &self,
+        budget_id: &str,
+        transaction_id: &str,
+        update: TransactionUpdate,
+    ) -> Result<Transaction, YnabApiError> {
         tracing::info!(
             "Updating transaction {} in budget {}",
             transaction_id,
@@ -712,7 +2959,7 @@ impl DataLoader {
         );
 
         let account_id = update.account_id;
-        let budget_id_api: BudgetId = budget_id.clone().into();
+        let budget_id_api: BudgetId = budget_id.to_string().into();
         let txn_id: TransactionId = transaction_id.parse().expect("invalid transaction id");
 
         let mut req = Request::transactions()
@@ -754,36 +3001,71 @@ impl DataLoader {
             req = req.subtransactions(subtransactions);
         }
 
-        match self.api_client.send(req).await {
-            Ok(response) => {
-                tracing::info!("Transaction {} updated successfully", transaction_id);
-                let _ = self.data_tx.send(DataEvent::TransactionUpdatedFull {
-                    transaction: response.data.transaction,
-                });
+        let response = self.send(req).await?;
+        tracing::info!("Transaction {} updated successfully", transaction_id);
 
-                // Invalidate transaction cache if account changed
-                if let Some(new_account_id) = account_id {
-                    let cache = self.cache.clone();
-                    let budget_id_clone = budget_id.clone();
-                    let account_id_str = new_account_id.to_string();
-                    tokio::spawn(async move {
-                        let _ = cache
-                            .invalidate_transactions(&budget_id_clone, &account_id_str)
-                            .await;
-                        tracing::debug!("Transaction cache invalidated");
-                    });
-                }
+        // Invalidate transaction cache if account changed
+        if let Some(new_account_id) = account_id {
+            let cache = self.cache.clone();
+            let budget_id = budget_id.to_string();
+            let account_id_str = new_account_id.to_string();
+            tokio::spawn(async move {
+                let _ = cache
+                    .invalidate_transactions(&budget_id, &account_id_str)
+                    .await;
+                tracing::debug!("Transaction cache invalidated");
+            });
+        }
+
+        Ok(response.data.transaction)
+    }
+
+    /// Delete a transaction that's already been removed from the screen
+    pub async fn delete_transaction(&self, budget_id: String, transaction_id: String) {
+        match self
+            .send_delete_transaction(&budget_id, &transaction_id)
+            .await
+        {
+            Ok(()) => {
+                tracing::info!("Successfully deleted transaction {}", transaction_id);
+                let _ = self
+                    .data_tx
+                    .send(TransactionEvent::TransactionDeleted { transaction_id }.into());
             }
+            Err(e)
+                if write_queue::is_offline(&e)
+                    && self
+                        .queue_write(PendingWrite::DeleteTransaction {
+                            budget_id,
+                            transaction_id: transaction_id.clone(),
+                        })
+                        .await => {}
             Err(e) => {
-                tracing::error!("Failed to update transaction {}: {}", transaction_id, e);
-                let _ = self.data_tx.send(DataEvent::TransactionUpdateFullFailed {
-                    transaction_id,
-                    error: e.to_string(),
-                });
+                tracing::error!("Failed to delete transaction {}: {}", transaction_id, e);
+                let _ = self.data_tx.send(
+                    TransactionEvent::TransactionDeleteFailed {
+                        transaction_id,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
             }
         }
     }
 
+    async fn send_delete_transaction(
+        &self,
+        budget_id: &str,
+        transaction_id: &str,
+    ) -> Result<(), YnabApiError> {
+        let budget_id_api: BudgetId = budget_id.to_string().into();
+        let transaction_id: TransactionId = transaction_id.parse().expect("invalid transaction id");
+        let req = Request::transactions()
+            .with_budget(budget_id_api)
+            .delete(transaction_id);
+        self.send(req).await.map(|_| ())
+    }
+
     /// Update a category's budgeted amount for a specific month
     pub async fn update_category_budget(
         &self,
@@ -793,6 +3075,48 @@ impl DataLoader {
         budgeted: i64,
         original_budgeted: i64,
     ) {
+        match self
+            .send_category_budget(&budget_id, &month, &category_id, budgeted)
+            .await
+        {
+            Ok(category) => {
+                let _ = self
+                    .data_tx
+                    .send(PlanEvent::CategoryBudgetUpdated { category }.into());
+            }
+            Err(e)
+                if write_queue::is_offline(&e)
+                    && self
+                        .queue_write(PendingWrite::SetCategoryBudgeted {
+                            budget_id,
+                            month,
+                            category_id: category_id.clone(),
+                            budgeted,
+                        })
+                        .await => {}
+            Err(e) => {
+                tracing::error!("Failed to update category budget: {}", e);
+                let _ = self.data_tx.send(
+                    PlanEvent::CategoryBudgetUpdateFailed {
+                        category_id,
+                        original_budgeted,
+                        new_budgeted: budgeted,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// PATCH a category's assigned amount for `month`
+    async fn send_category_budget(
+        &self,
+        budget_id: &str,
+        month: &str,
+        category_id: &str,
+        budgeted: i64,
+    ) -> Result<Category, YnabApiError> {
         tracing::info!(
             "Updating budget for category {} in month {} to {}",
             category_id,
@@ -800,29 +3124,349 @@ impl DataLoader {
             budgeted
         );
 
-        let budget_id_api: BudgetId = budget_id.into();
+        let budget_id_api: BudgetId = budget_id.to_string().into();
         let category_uuid: Uuid = category_id.parse().expect("invalid category id");
 
         let req = Request::categories()
             .with_budget(budget_id_api)
-            .update_month(category_uuid, month, budgeted.into());
+            .update_month(category_uuid, month.to_string(), budgeted.into());
 
-        match self.api_client.send(req).await {
-            Ok(response) => {
-                tracing::info!("Category budget updated successfully");
-                let _ = self.data_tx.send(DataEvent::CategoryBudgetUpdated {
-                    category: response.data.category,
-                });
+        let response = self.send(req).await?;
+        tracing::info!("Category budget updated successfully");
+        Ok(response.data.category)
+    }
+
+    /// Keep a write that couldn't reach YNAB to send later, and start trying.
+    /// `false` if it couldn't be saved either, in which case the caller
+    /// reports the failure as usual.
+    async fn queue_write(&self, write: PendingWrite) -> bool {
+        match self.write_queue.push(write).await {
+            Ok(pending) => {
+                tracing::warn!("YNAB unreachable, {} change(s) waiting to sync", pending);
+                let _ = self.data_tx.send(DataEvent::WriteQueued { pending });
+                self.sync_pending_writes();
+                true
             }
             Err(e) => {
-                tracing::error!("Failed to update category budget: {}", e);
-                let _ = self.data_tx.send(DataEvent::CategoryBudgetUpdateFailed {
-                    category_id: category_uuid.to_string(),
-                    original_budgeted,
-                    new_budgeted: budgeted,
-                    error: e.to_string(),
+                tracing::error!("Couldn't save change to send later: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Send queued offline changes in the background, oldest first, retrying
+    /// every [`SYNC_RETRY_INTERVAL`] while YNAB is unreachable. Does nothing
+    /// if a sync is already running.
+    pub fn sync_pending_writes(&self) {
+        if !self.write_queue.start_sync() {
+            return;
+        }
+        let loader = self.clone();
+        tokio::spawn(async move {
+            // Another process using the same cache is sending the journal
+            let _claim = match loader.cache.try_lock_sync() {
+                Ok(Some(claim)) => claim,
+                Ok(None) => {
+                    tracing::info!("Pending changes are being sent by another ynat");
+                    loader.write_queue.finish_sync();
+                    return;
+                }
+                Err(e) => {
+                    tracing::error!("Couldn't claim sending pending changes: {}", e);
+                    loader.write_queue.finish_sync();
+                    return;
+                }
+            };
+            while !loader.send_pending_writes().await {
+                tokio::time::sleep(SYNC_RETRY_INTERVAL).await;
+            }
+            loader.write_queue.finish_sync();
+        });
+    }
+
+    /// Send queued writes until the queue is empty (`true`) or YNAB stops
+    /// answering (`false`). Writes YNAB refuses are dropped and reported.
+    async fn send_pending_writes(&self) -> bool {
+        let mut synced = 0;
+        let (done, pending) = loop {
+            let (write, pending) = match self.write_queue.first().await {
+                Ok(first) => first,
+                Err(e) => {
+                    // Nothing can be sent until the journal is readable again
+                    tracing::error!("Pending changes unreadable: {}", e);
+                    let _ = self.data_tx.send(DataEvent::LoadError {
+                        error: AppError::new(ErrorKind::Cache, e.to_string()),
+                    });
+                    break (true, 0);
+                }
+            };
+            let Some(write) = write else {
+                break (true, pending);
+            };
+
+            let result = match write.clone() {
+                PendingWrite::CreateTransaction {
+                    budget_id,
+                    transaction,
+                } => self
+                    .send_new_transaction(&budget_id, transaction)
+                    .await
+                    .map(|_| ()),
+                PendingWrite::UpdateTransaction {
+                    budget_id,
+                    transaction_id,
+                    update,
+                } => self
+                    .send_transaction_update(&budget_id, &transaction_id, update)
+                    .await
+                    .map(|_| ()),
+                PendingWrite::DeleteTransaction {
+                    budget_id,
+                    transaction_id,
+                } => {
+                    self.send_delete_transaction(&budget_id, &transaction_id)
+                        .await
+                }
+                PendingWrite::SetCategoryBudgeted {
+                    budget_id,
+                    month,
+                    category_id,
+                    budgeted,
+                } => self
+                    .send_category_budget(&budget_id, &month, &category_id, budgeted)
+                    .await
+                    .map(|_| ()),
+            };
+
+            match result {
+                Ok(()) => synced += 1,
+                // An earlier attempt went through without its answer arriving
+                Err(e) if write_queue::is_already_applied(&write, &e) => synced += 1,
+                Err(e) if write_queue::is_retryable(&e) => {
+                    tracing::info!("Still can't sync {} pending change(s): {}", pending, e);
+                    break (false, pending);
+                }
+                Err(e) => {
+                    tracing::error!("Dropped pending change {:?}: {}", write, e);
+                    let _ = self.data_tx.send(DataEvent::PendingWriteRejected {
+                        change: write.describe(),
+                        unconfirmed: matches!(e, YnabApiError::Unreadable(..)),
+                        error: e.into(),
+                    });
+                }
+            }
+            if let Err(e) = self.write_queue.remove_first().await {
+                tracing::error!("Couldn't update pending changes: {}", e);
+                let _ = self.data_tx.send(DataEvent::LoadError {
+                    error: AppError::new(ErrorKind::Cache, e.to_string()),
                 });
+                break (true, 0);
+            }
+        };
+
+        if synced > 0 || pending > 0 {
+            let _ = self
+                .data_tx
+                .send(DataEvent::PendingWritesSynced { synced, pending });
+        }
+        done
+    }
+
+    /// Import a YNAB "Export budget data" register and plan from `dir` into a budget.
+    ///
+    /// Accounts and categories are matched by name. Transactions are created in one
+    /// bulk request with stable import ids, so running the import twice is harmless.
+    /// Find the export to import and count what importing it would change,
+    /// without writing anything
+    pub async fn preview_ynab_export(
+        &self,
+        budget_id: String,
+        dir: &Path,
+        budget_name: Option<String>,
+    ) {
+        tracing::info!("Previewing YNAB export from {:?}", dir);
+
+        match self
+            .try_preview_ynab_export(&budget_id, dir, budget_name.as_deref())
+            .await
+        {
+            Ok(preview) => {
+                let _ = self
+                    .data_tx
+                    .send(BudgetEvent::YnabExportPreviewed { preview }.into());
+            }
+            Err(error) => {
+                tracing::error!("YNAB export preview failed: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
             }
         }
     }
+
+    async fn try_preview_ynab_export(
+        &self,
+        budget_id: &str,
+        dir: &Path,
+        budget_name: Option<&str>,
+    ) -> Result<ynab_export::ImportPreview, AppError> {
+        let exports = ynab_export::find_exports(dir).map_err(|e| {
+            AppError::new(
+                ErrorKind::Other,
+                format!("Cannot read {}: {}", dir.display(), e),
+            )
+        })?;
+        let export = ynab_export::pick_export(exports, budget_name).ok_or_else(|| {
+            AppError::new(
+                ErrorKind::NotFound,
+                format!("No YNAB export found in {}", dir.display()),
+            )
+        })?;
+
+        let (transactions, allocations, skipped) =
+            self.match_ynab_export(budget_id, &export).await?;
+        let months: HashSet<&str> = allocations
+            .iter()
+            .map(|(month, _, _)| month.as_str())
+            .collect();
+
+        Ok(ynab_export::ImportPreview {
+            same_budget: budget_name
+                .is_some_and(|name| export.budget_name.eq_ignore_ascii_case(name.trim())),
+            transactions: transactions.len(),
+            allocations: allocations.len(),
+            months: months.len(),
+            skipped,
+            export,
+        })
+    }
+
+    /// Import a previewed export, writing its transactions and plan amounts
+    pub async fn import_ynab_export(&self, budget_id: String, export: ynab_export::ExportFiles) {
+        tracing::info!(
+            "Importing YNAB export {:?} / {:?} into budget {}",
+            export.register,
+            export.plan,
+            budget_id
+        );
+
+        match self.try_import_ynab_export(&budget_id, &export).await {
+            Ok(event) => {
+                let _ = self.data_tx.send(event);
+                self.load_accounts(budget_id, true).await;
+            }
+            Err(error) => {
+                tracing::error!("YNAB export import failed: {}", error);
+                let _ = self.data_tx.send(DataEvent::LoadError { error });
+            }
+        }
+    }
+
+    /// Parse an export's files and match them to the live budget, returning the
+    /// transactions to create, the allocations to assign and the rows skipped
+    async fn match_ynab_export(
+        &self,
+        budget_id: &str,
+        export: &ynab_export::ExportFiles,
+    ) -> Result<(Vec<NewTransaction>, Vec<(String, Uuid, i64)>, usize), AppError> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path).map_err(|e| {
+                AppError::new(
+                    ErrorKind::Other,
+                    format!("Cannot read {}: {}", path.display(), e),
+                )
+            })
+        };
+        let (register, mut skipped) = match &export.register {
+            Some(path) => ynab_export::parse_register(&read(path)?),
+            None => (Vec::new(), 0),
+        };
+        let (plan, plan_skipped) = match &export.plan {
+            Some(path) => ynab_export::parse_plan(&read(path)?),
+            None => (Vec::new(), 0),
+        };
+        skipped += plan_skipped;
+
+        // Match against the live budget rather than the cache
+        let budget_id_api: BudgetId = budget_id.to_string().into();
+        let accounts = self
+            .send(
+                Request::accounts()
+                    .with_budget(budget_id_api.clone())
+                    .list(),
+            )
+            .await?
+            .data
+            .accounts;
+        let categories: Vec<_> = self
+            .send(Request::categories().list().budget_id(budget_id_api))
+            .await?
+            .data
+            .category_groups
+            .into_iter()
+            .filter(|g| !g.deleted)
+            .flat_map(|group| {
+                group.categories.into_iter().map(move |mut category| {
+                    category.category_group_name = Some(group.name.clone());
+                    category
+                })
+            })
+            .filter(|c| !c.deleted)
+            .collect();
+        let matcher = ynab_export::BudgetMatcher::new(&accounts, &categories);
+
+        let (transactions, unmatched) = matcher.transactions(&register);
+        skipped += unmatched;
+        let (allocations, unmatched) = matcher.allocations(&plan);
+        skipped += unmatched;
+
+        Ok((transactions, allocations, skipped))
+    }
+
+    async fn try_import_ynab_export(
+        &self,
+        budget_id: &str,
+        export: &ynab_export::ExportFiles,
+    ) -> Result<DataEvent, AppError> {
+        let (transactions, allocations, mut skipped) =
+            self.match_ynab_export(budget_id, export).await?;
+        let budget_id_api: BudgetId = budget_id.to_string().into();
+
+        let mut created = 0;
+        if !transactions.is_empty() {
+            let req = Request::transactions()
+                .bulk()
+                .create()
+                .budget_id(budget_id_api.clone())
+                .transactions(transactions);
+            let response = self.send(req).await?;
+            created = response.data.transaction_ids.len();
+            skipped += response.data.duplicate_import_ids.len();
+        }
+
+        let mut allocated = 0;
+        for (month, category_id, budgeted) in allocations {
+            let req = Request::categories()
+                .with_budget(budget_id_api.clone())
+                .update_month(category_id, month.clone(), budgeted.into());
+            match self.send(req).await {
+                Ok(_) => allocated += 1,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to assign {} to {} in {}: {}",
+                        budgeted,
+                        category_id,
+                        month,
+                        e
+                    );
+                    skipped += 1;
+                }
+            }
+        }
+
+        Ok(BudgetEvent::YnabExportImported {
+            transactions: created,
+            allocations: allocated,
+            skipped,
+        }
+        .into())
+    }
 }
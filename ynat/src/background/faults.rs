@@ -0,0 +1,129 @@
+//! Latency and failures injected into API requests, for tests.
+//!
+//! Which requests fail is decided from a seed and the request's position, so
+//! the same sequence of requests always fails the same way.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use ynab_api::YnabApiError;
+
+/// What to do to each API request
+#[derive(Debug, Clone, Default)]
+pub struct Faults {
+    /// Wait this long before each request
+    pub delay: Duration,
+    /// Share of requests (0.0 to 1.0) that fail as if YNAB couldn't be reached
+    pub error_rate: f64,
+    /// Answer every nth request with 429 Too Many Requests
+    pub rate_limit_every: Option<u64>,
+    /// Seed for picking which requests fail
+    pub seed: u64,
+}
+
+impl Faults {
+    /// Every request fails as if YNAB couldn't be reached
+    pub fn offline() -> Self {
+        Self {
+            error_rate: 1.0,
+            ..Default::default()
+        }
+    }
+
+    /// Every request is rate limited
+    pub fn rate_limited() -> Self {
+        Self {
+            rate_limit_every: Some(1),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FaultInjector {
+    faults: Faults,
+    requests: AtomicU64,
+}
+
+impl FaultInjector {
+    pub fn new(faults: Faults) -> Self {
+        Self {
+            faults,
+            requests: AtomicU64::new(0),
+        }
+    }
+
+    /// Delay the next request, then fail it if its turn has come
+    pub async fn before_request(&self) -> Result<(), YnabApiError> {
+        let n = self.requests.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.faults.delay.is_zero() {
+            tokio::time::sleep(self.faults.delay).await;
+        }
+        if self
+            .faults
+            .rate_limit_every
+            .is_some_and(|every| every > 0 && n.is_multiple_of(every))
+        {
+            return Err(YnabApiError::from_status(
+                429,
+                "too_many_requests",
+                "Too many requests (injected)",
+            ));
+        }
+        if unit_interval(self.faults.seed ^ n) < self.faults.error_rate {
+            return Err(YnabApiError::connection_failed(
+                "Connection refused (injected)",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A well-mixed number in [0, 1) from `x` (splitmix64)
+fn unit_interval(x: u64) -> f64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn outcomes(faults: Faults, requests: usize) -> Vec<bool> {
+        let injector = FaultInjector::new(faults);
+        let mut outcomes = Vec::new();
+        for _ in 0..requests {
+            outcomes.push(injector.before_request().await.is_ok());
+        }
+        outcomes
+    }
+
+    #[tokio::test]
+    async fn failures_repeat_for_the_same_seed() {
+        let faults = Faults {
+            error_rate: 0.5,
+            seed: 7,
+            ..Default::default()
+        };
+
+        let first = outcomes(faults.clone(), 40).await;
+
+        assert_eq!(first, outcomes(faults, 40).await);
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[tokio::test]
+    async fn every_nth_request_is_rate_limited() {
+        let faults = Faults {
+            rate_limit_every: Some(3),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            outcomes(faults, 6).await,
+            vec![true, true, false, true, true, false]
+        );
+    }
+}
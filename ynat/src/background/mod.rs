@@ -1,13 +1,40 @@
 pub mod data_loader;
+pub mod faults;
+pub mod write_queue;
 
 use std::collections::HashMap;
 use std::future::Future;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+tokio::task_local! {
+    /// Cancelled when the task running the current load is replaced or stopped
+    static CANCEL: CancellationToken;
+}
+
+/// The cancellation token of the background task this is called from, if any
+pub fn current_cancellation() -> Option<CancellationToken> {
+    CANCEL.try_with(CancellationToken::clone).ok()
+}
+
+/// A spawned task and the token that stops its API requests
+struct BackgroundTask {
+    handle: JoinHandle<()>,
+    cancel: CancellationToken,
+}
+
+impl BackgroundTask {
+    /// Cancel in-flight requests, then stop the task itself
+    fn stop(self) {
+        self.cancel.cancel();
+        self.handle.abort();
+    }
+}
 
 /// Manages background tasks for data loading
 /// Tracks running tasks and provides cancellation support
 pub struct BackgroundTaskManager {
-    tasks: HashMap<String, JoinHandle<()>>,
+    tasks: HashMap<String, BackgroundTask>,
 }
 
 impl BackgroundTaskManager {
@@ -24,19 +51,26 @@ impl BackgroundTaskManager {
         F: Future<Output = ()> + Send + 'static,
     {
         // Cancel existing task with same ID (prevents stale data)
-        if let Some(handle) = self.tasks.remove(&task_id) {
-            handle.abort();
+        if let Some(task) = self.tasks.remove(&task_id) {
+            task.stop();
         }
 
-        // Spawn new task
-        let handle = tokio::spawn(future);
-        self.tasks.insert(task_id, handle);
+        // Spawn new task; requests it sends see the token through `current_cancellation`
+        let cancel = CancellationToken::new();
+        let handle = tokio::spawn(CANCEL.scope(cancel.clone(), future));
+        self.tasks
+            .insert(task_id, BackgroundTask { handle, cancel });
+    }
+
+    /// Whether every spawned task has finished
+    pub fn is_idle(&self) -> bool {
+        self.tasks.values().all(|task| task.handle.is_finished())
     }
 
     /// Cancel all running tasks (used on shutdown)
     pub fn cancel_all(&mut self) {
-        for (_, handle) in self.tasks.drain() {
-            handle.abort();
+        for (_, task) in self.tasks.drain() {
+            task.stop();
         }
     }
 }
@@ -52,3 +86,25 @@ impl Drop for BackgroundTaskManager {
         self.cancel_all();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn replacing_a_task_cancels_its_requests() {
+        let mut tasks = BackgroundTaskManager::new();
+        let (tx, rx) = oneshot::channel();
+        tasks.spawn_load_task("load".to_string(), async move {
+            let _ = tx.send(current_cancellation());
+            std::future::pending::<()>().await;
+        });
+        let first = rx.await.unwrap().expect("tasks run with a token");
+        assert!(!first.is_cancelled());
+
+        tasks.spawn_load_task("load".to_string(), async {});
+        assert!(first.is_cancelled());
+        assert!(current_cancellation().is_none());
+    }
+}
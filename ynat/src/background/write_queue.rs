@@ -0,0 +1,236 @@
+//! Changes made while YNAB can't be reached.
+//!
+//! A create, edit, delete or assigned amount whose request never reaches the
+//! server is written to a journal in the cache directory instead of being
+//! lost. [`DataLoader::sync_pending_writes`](super::data_loader::DataLoader::sync_pending_writes)
+//! sends the journal in order once requests go through again. It survives
+//! restarts, so quitting while offline keeps the changes. Other ynat
+//! processes sharing the cache directory share the journal too, and only one
+//! of them sends it at a time.
+
+use crate::cache::{Cache, CacheError};
+use crate::i18n::t;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+use ynab_api::endpoints::transactions::{NewTransaction, TransactionUpdate};
+use ynab_api::YnabApiError;
+
+/// A change to send to YNAB once it's reachable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingWrite {
+    CreateTransaction {
+        budget_id: String,
+        transaction: NewTransaction,
+    },
+    UpdateTransaction {
+        budget_id: String,
+        transaction_id: String,
+        update: TransactionUpdate,
+    },
+    DeleteTransaction {
+        budget_id: String,
+        transaction_id: String,
+    },
+    SetCategoryBudgeted {
+        budget_id: String,
+        month: String,
+        category_id: String,
+        budgeted: i64,
+    },
+}
+
+impl PendingWrite {
+    /// Which change this is, for telling the user about one that was dropped
+    pub fn describe(&self) -> String {
+        match self {
+            PendingWrite::CreateTransaction { transaction, .. } => {
+                t("pending-write-create").replace("{date}", &transaction.date)
+            }
+            PendingWrite::UpdateTransaction { .. } => t("pending-write-update").to_string(),
+            PendingWrite::DeleteTransaction { .. } => t("pending-write-delete").to_string(),
+            PendingWrite::SetCategoryBudgeted { month, .. } => {
+                t("pending-write-budget").replace("{month}", month)
+            }
+        }
+    }
+}
+
+/// Whether a failed write should wait in the journal rather than be reported.
+/// Only requests that got no answer qualify; anything YNAB answered, even
+/// with an error or a body that couldn't be read, wouldn't go differently the
+/// second time. An unanswered request may still have been applied, for
+/// example when the connection dropped while waiting for the reply, so
+/// replays must be safe to repeat (see [`with_import_id`] and
+/// [`is_already_applied`]).
+pub fn is_offline(error: &YnabApiError) -> bool {
+    matches!(error, YnabApiError::Internal(_))
+}
+
+/// Whether a replayed write failed only because an earlier attempt already
+/// went through: a delete of a transaction that's no longer there
+pub fn is_already_applied(write: &PendingWrite, error: &YnabApiError) -> bool {
+    matches!(
+        (write, error),
+        (PendingWrite::DeleteTransaction { .. }, YnabApiError::Ynab(status, _))
+            if status.as_u16() == 404
+    )
+}
+
+/// Whether a queued write that failed again should be retried later rather than dropped
+pub fn is_retryable(error: &YnabApiError) -> bool {
+    match error {
+        YnabApiError::Internal(_) | YnabApiError::TimedOut(_) | YnabApiError::Cancelled => true,
+        YnabApiError::Ynab(status, _) => status.as_u16() == 429 || status.is_server_error(),
+        YnabApiError::Unreadable(..) => false,
+    }
+}
+
+/// Give a create an import id before it's queued, so YNAB skips it as a
+/// duplicate if a replay it already applied is sent again
+pub fn with_import_id(mut transaction: NewTransaction) -> NewTransaction {
+    if transaction.import_id.is_none() {
+        // Import ids are at most 36 characters, the length of a UUID
+        transaction.import_id = Some(Uuid::new_v4().to_string());
+    }
+    transaction
+}
+
+/// The journal of pending writes, shared by every clone of the data loader
+pub struct WriteQueue {
+    cache: Arc<Cache>,
+    /// Held while the journal is read and rewritten, so writes aren't lost;
+    /// the cache lock does the same for other processes
+    journal: Mutex<()>,
+    /// Set while a sync is running, so only one sends the journal
+    syncing: AtomicBool,
+}
+
+impl WriteQueue {
+    pub fn new(cache: Arc<Cache>) -> Self {
+        Self {
+            cache,
+            journal: Mutex::new(()),
+            syncing: AtomicBool::new(false),
+        }
+    }
+
+    /// Append a write; returns how many are pending
+    pub async fn push(&self, write: PendingWrite) -> Result<usize, CacheError> {
+        let _journal = self.journal.lock().await;
+        let _lock = self.cache.lock().await?;
+        let mut writes = self.cache.get_pending_writes().await?.unwrap_or_default();
+        writes.push(write);
+        self.cache.set_pending_writes(&writes).await?;
+        Ok(writes.len())
+    }
+
+    /// The oldest pending write and how many there are
+    pub async fn first(&self) -> Result<(Option<PendingWrite>, usize), CacheError> {
+        let _journal = self.journal.lock().await;
+        let _lock = self.cache.lock().await?;
+        let writes = self.cache.get_pending_writes().await?.unwrap_or_default();
+        Ok((writes.first().cloned(), writes.len()))
+    }
+
+    /// Drop the oldest write once it's been sent or refused; returns how many are left
+    pub async fn remove_first(&self) -> Result<usize, CacheError> {
+        let _journal = self.journal.lock().await;
+        let _lock = self.cache.lock().await?;
+        let mut writes = self.cache.get_pending_writes().await?.unwrap_or_default();
+        if !writes.is_empty() {
+            writes.remove(0);
+        }
+        self.cache.set_pending_writes(&writes).await?;
+        Ok(writes.len())
+    }
+
+    /// Claim the sync; `false` if one is already running
+    pub fn start_sync(&self) -> bool {
+        !self.syncing.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn finish_sync(&self) {
+        self.syncing.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delete(transaction_id: &str) -> PendingWrite {
+        PendingWrite::DeleteTransaction {
+            budget_id: "budget".to_string(),
+            transaction_id: transaction_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_are_kept_in_order_across_restarts() {
+        let dir = crate::replay::scratch_cache_dir();
+        let cache = Arc::new(Cache::in_dir(dir.clone()).await.unwrap());
+        let queue = WriteQueue::new(cache.clone());
+        assert_eq!(queue.push(delete("t1")).await.unwrap(), 1);
+        assert_eq!(queue.push(delete("t2")).await.unwrap(), 2);
+
+        let reopened = WriteQueue::new(cache);
+        let (first, pending) = reopened.first().await.unwrap();
+        assert!(matches!(
+            first,
+            Some(PendingWrite::DeleteTransaction { transaction_id, .. }) if transaction_id == "t1"
+        ));
+        assert_eq!(pending, 2);
+        assert_eq!(reopened.remove_first().await.unwrap(), 1);
+        assert_eq!(reopened.remove_first().await.unwrap(), 0);
+        assert!(reopened.first().await.unwrap().0.is_none());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn only_unanswered_requests_are_queued() {
+        assert!(is_offline(&YnabApiError::connection_failed("refused")));
+        assert!(!is_offline(&YnabApiError::from_status(
+            400,
+            "bad_request",
+            ""
+        )));
+        assert!(is_retryable(&YnabApiError::from_status(
+            429,
+            "too_many_requests",
+            ""
+        )));
+        assert!(!is_retryable(&YnabApiError::from_status(
+            404,
+            "not_found",
+            ""
+        )));
+    }
+
+    #[test]
+    fn replayed_deletes_that_already_happened_count_as_sent() {
+        let not_found = YnabApiError::from_status(404, "not_found", "");
+        assert!(is_already_applied(&delete("t1"), &not_found));
+        assert!(!is_already_applied(
+            &delete("t1"),
+            &YnabApiError::from_status(400, "bad_request", "")
+        ));
+        let update = PendingWrite::UpdateTransaction {
+            budget_id: "budget".to_string(),
+            transaction_id: "t1".to_string(),
+            update: TransactionUpdate::default(),
+        };
+        assert!(!is_already_applied(&update, &not_found));
+    }
+
+    #[test]
+    fn answers_that_could_not_be_read_are_never_queued_or_retried() {
+        let unreadable = YnabApiError::unreadable(201, "expected value");
+        assert!(!is_offline(&unreadable));
+        assert!(!is_retryable(&unreadable));
+    }
+}
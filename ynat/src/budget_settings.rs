@@ -0,0 +1,138 @@
+//! Local preferences kept per budget.
+//!
+//! Stored as `<state dir>/ynat/budgets/<budget id>.json` rather than in the
+//! cache, so clearing the cache doesn't lose them, and rather than in
+//! `config.toml`, so two budgets can differ. Toggling a view remembers the
+//! choice for the current budget; the other fields can be edited by hand.
+
+use crate::reconcile_reminders::ReconcileCadence;
+use crate::state::{PlanFocusedView, TransactionSort};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BudgetSettings {
+    /// Account the cursor starts on in the accounts list (the last one opened)
+    pub default_account_id: Option<String>,
+    pub show_closed_accounts: bool,
+    /// Show the 90-day balance sparkline column on the accounts screen
+    pub balance_history_column: bool,
+    /// Show each account's net change this month on the accounts screen
+    pub month_change_column: bool,
+    pub show_reconciled_transactions: bool,
+    /// Order of the transactions table
+    pub transaction_sort: TransactionSort,
+    /// Ask before deleting a transaction
+    pub confirm_delete: bool,
+    /// View the Plan screen opens in
+    pub plan_view: PlanFocusedView,
+    /// Show the selected category's note and goal under the plan table
+    pub plan_category_details: bool,
+    /// Payees merged into another; left out of suggestions and cleanup
+    pub hidden_payee_ids: Vec<String>,
+    /// How often each account (by id) should be reconciled
+    pub reconcile_reminders: BTreeMap<String, ReconcileCadence>,
+    /// Starred accounts, listed first on the accounts screen
+    pub favorite_account_ids: Vec<String>,
+    /// Starred categories, listed first on the plan
+    pub favorite_category_ids: Vec<String>,
+}
+
+impl Default for BudgetSettings {
+    fn default() -> Self {
+        Self {
+            default_account_id: None,
+            show_closed_accounts: false,
+            balance_history_column: true,
+            month_change_column: true,
+            show_reconciled_transactions: true,
+            transaction_sort: TransactionSort::default(),
+            confirm_delete: true,
+            plan_view: PlanFocusedView::default(),
+            plan_category_details: true,
+            hidden_payee_ids: Vec::new(),
+            reconcile_reminders: BTreeMap::new(),
+            favorite_account_ids: Vec::new(),
+            favorite_category_ids: Vec::new(),
+        }
+    }
+}
+
+fn settings_dir() -> Option<PathBuf> {
+    let dir = dirs::state_dir().or_else(dirs::data_local_dir)?;
+    Some(dir.join("ynat").join("budgets"))
+}
+
+fn settings_path(budget_id: &str) -> Option<PathBuf> {
+    Some(settings_dir()?.join(format!("{}.json", budget_id)))
+}
+
+/// Settings saved for every budget, by budget id
+pub async fn load_all() -> std::io::Result<BTreeMap<String, BudgetSettings>> {
+    let mut all = BTreeMap::new();
+    let Some(dir) = settings_dir() else {
+        return Ok(all);
+    };
+    let mut entries = match tokio::fs::read_dir(&dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(all),
+        Err(e) => return Err(e),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            if let Some(budget_id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                all.insert(budget_id.to_string(), load(budget_id).await);
+            }
+        }
+    }
+    Ok(all)
+}
+
+/// Settings for `budget_id`, or the defaults if none were saved or the file is unreadable
+pub async fn load(budget_id: &str) -> BudgetSettings {
+    let Some(path) = settings_path(budget_id) else {
+        return BudgetSettings::default();
+    };
+    match tokio::fs::read_to_string(&path).await {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable budget settings {:?}: {}", path, e);
+            BudgetSettings::default()
+        }),
+        Err(_) => BudgetSettings::default(),
+    }
+}
+
+pub async fn save(budget_id: &str, settings: &BudgetSettings) -> std::io::Result<()> {
+    let Some(path) = settings_path(budget_id) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(std::io::Error::other)?;
+    // Replace the file in one step so an interrupted save leaves the old one intact
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, json).await?;
+    tokio::fs::rename(tmp_path, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_keep_their_defaults() {
+        let settings: BudgetSettings =
+            serde_json::from_str(r#"{ "confirm_delete": false, "plan_view": "underfunded" }"#)
+                .unwrap();
+
+        assert!(!settings.confirm_delete);
+        assert_eq!(settings.plan_view, PlanFocusedView::Underfunded);
+        assert!(settings.show_reconciled_transactions);
+        assert!(settings.balance_history_column);
+        assert!(settings.month_change_column);
+    }
+}
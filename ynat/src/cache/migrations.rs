@@ -0,0 +1,170 @@
+//! Versioning and migration of cache files.
+//!
+//! Every cache file is written as `{ "version": N, "data": ... }`. When a
+//! cached model changes shape, bump [`CURRENT_VERSION`] and add a
+//! [`Migration`] that rewrites the raw JSON of the previous version, so
+//! existing caches are upgraded in place instead of failing to deserialize.
+//! Files written before versioning existed have no envelope and are treated
+//! as version 0.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::CacheError;
+
+/// Version written to new cache files
+pub const CURRENT_VERSION: u32 = 1;
+
+/// The kind of data stored in a cache file, so migrations can target one model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Budgets,
+    Accounts,
+    Transactions,
+    Plan,
+    Payees,
+    Categories,
+    PendingWrites,
+    ScheduledTransactions,
+}
+
+/// A single upgrade step from `from` to `from + 1`
+pub struct Migration {
+    pub from: u32,
+    /// Kind of cache file this applies to; `None` applies to every kind
+    pub kind: Option<CacheKind>,
+    pub description: &'static str,
+    pub apply: fn(&mut Value),
+}
+
+/// Registered migrations, in version order
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    kind: None,
+    description: "wrap unversioned cache files in a version envelope",
+    apply: |_| {},
+}];
+
+/// On-disk envelope for a cache file
+#[derive(Debug, Serialize)]
+pub struct Versioned<T> {
+    pub version: u32,
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    pub fn current(data: T) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data,
+        }
+    }
+}
+
+/// Split raw file contents into (version, payload), treating envelope-less files as version 0
+pub fn unwrap_envelope(raw: Value) -> (u32, Value) {
+    match raw {
+        Value::Object(mut map) if map.contains_key("version") && map.contains_key("data") => {
+            let version = map
+                .get("version")
+                .and_then(Value::as_u64)
+                .unwrap_or_default() as u32;
+            let data = map.remove("data").unwrap_or(Value::Null);
+            (version, data)
+        }
+        other => (0, other),
+    }
+}
+
+/// Run every migration needed to bring `data` from `version` up to [`CURRENT_VERSION`]
+pub fn migrate(kind: CacheKind, version: u32, mut data: Value) -> Result<Value, CacheError> {
+    if version > CURRENT_VERSION {
+        return Err(CacheError::UnsupportedVersion {
+            found: version,
+            supported: CURRENT_VERSION,
+        });
+    }
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|m| m.from >= version && m.kind.is_none_or(|k| k == kind))
+    {
+        tracing::debug!(
+            "Migrating {:?} cache from v{}: {}",
+            kind,
+            migration.from,
+            migration.description
+        );
+        (migration.apply)(&mut data);
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CachedAccounts, CachedBudgets};
+    use serde::de::DeserializeOwned;
+    use serde_json::json;
+
+    /// Migration test harness: run a fixture written at `version` through every
+    /// migration and check it deserializes into the current model.
+    fn assert_migrates<T: DeserializeOwned>(kind: CacheKind, raw: Value) -> T {
+        let (version, data) = unwrap_envelope(raw);
+        let migrated = migrate(kind, version, data).expect("migration should succeed");
+        serde_json::from_value(migrated).expect("migrated data should match the current model")
+    }
+
+    #[test]
+    fn migrations_are_contiguous_and_end_at_current_version() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.from, i as u32, "{}", migration.description);
+        }
+        assert_eq!(MIGRATIONS.len() as u32, CURRENT_VERSION);
+    }
+
+    #[test]
+    fn unversioned_file_is_version_zero() {
+        let (version, data) = unwrap_envelope(json!({ "budgets": [] }));
+        assert_eq!(version, 0);
+        assert_eq!(data, json!({ "budgets": [] }));
+    }
+
+    #[test]
+    fn envelope_is_unwrapped() {
+        let (version, data) = unwrap_envelope(json!({ "version": 1, "data": [1, 2] }));
+        assert_eq!(version, 1);
+        assert_eq!(data, json!([1, 2]));
+    }
+
+    #[test]
+    fn legacy_budgets_cache_migrates() {
+        let cached: CachedBudgets = assert_migrates(
+            CacheKind::Budgets,
+            json!({ "budgets": [], "default_budget": null, "cached_at": 1700000000 }),
+        );
+        assert_eq!(cached.cached_at, 1700000000);
+    }
+
+    #[test]
+    fn current_accounts_cache_loads_unchanged() {
+        let cached: CachedAccounts = assert_migrates(
+            CacheKind::Accounts,
+            json!({
+                "version": CURRENT_VERSION,
+                "data": { "accounts": [], "server_knowledge": 42, "cached_at": 1 }
+            }),
+        );
+        assert_eq!(cached.server_knowledge, Some(42));
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let result = migrate(CacheKind::Payees, CURRENT_VERSION + 1, json!([]));
+        assert!(matches!(
+            result,
+            Err(CacheError::UnsupportedVersion { found, .. }) if found == CURRENT_VERSION + 1
+        ));
+    }
+}
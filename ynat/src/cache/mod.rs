@@ -1,15 +1,28 @@
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+pub mod migrations;
+
+use crate::background::write_queue::PendingWrite;
+use chrono::NaiveDate;
+use migrations::{CacheKind, Versioned};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::fs;
 use ynab_api::endpoints::{
     accounts::Account, budgets::BudgetSummary, categories::Category, months::MonthDetail,
-    payees::Payee, transactions::Transaction,
+    payees::Payee, scheduled_transactions::ScheduledTransaction, transactions::Transaction,
 };
 
 #[derive(Debug)]
 pub enum CacheError {
     Io(std::io::Error),
     Serialization(serde_json::Error),
+    /// The file was written by a newer version of ynat
+    UnsupportedVersion {
+        found: u32,
+        supported: u32,
+    },
 }
 
 impl std::fmt::Display for CacheError {
@@ -17,6 +30,11 @@ impl std::fmt::Display for CacheError {
         match self {
             CacheError::Io(e) => write!(f, "IO error: {}", e),
             CacheError::Serialization(e) => write!(f, "Serialization error: {}", e),
+            CacheError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "Cache version {} is newer than supported version {}",
+                found, supported
+            ),
         }
     }
 }
@@ -54,6 +72,16 @@ pub struct CachedTransactions {
     pub transactions: Vec<Transaction>,
     pub server_knowledge: Option<i64>,
     pub cached_at: i64,
+    /// Earliest date the cached history covers; `None` means all of it
+    #[serde(default)]
+    pub loaded_since: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedScheduledTransactions {
+    pub scheduled_transactions: Vec<ScheduledTransaction>,
+    pub server_knowledge: Option<i64>,
+    pub cached_at: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,18 +92,49 @@ pub struct CachedPlan {
     pub cached_at: i64,
 }
 
-/// Async cache layer using tokio::fs for non-blocking file I/O
+/// Taken while a cache file is read and rewritten, across every ynat
+/// process using the cache directory
+const LOCK_FILE: &str = ".lock";
+/// Held by the one process sending changes made offline
+const SYNC_LOCK_FILE: &str = ".sync.lock";
+
+/// Tells apart temporary files written at the same time by one process
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// An advisory lock on the cache directory, released when dropped
+#[derive(Debug)]
+pub struct CacheLock {
+    // Closing the file releases the lock
+    _file: std::fs::File,
+}
+
+/// Async cache layer using tokio::fs for non-blocking file I/O.
+///
+/// Several ynat processes (say the TUI and a CLI command) can share one cache
+/// directory. Files are replaced whole, so a reader never sees half a write,
+/// and read-modify-write updates hold [`Cache::lock`] so none are lost.
 #[derive(Clone)]
 pub struct Cache {
     cache_dir: PathBuf,
+    /// Server knowledge of each file as this process last showed or wrote
+    /// it; a cache further along was updated by another process
+    known: Arc<Mutex<HashMap<PathBuf, i64>>>,
 }
 
 impl Cache {
     pub async fn new() -> Result<Self, CacheError> {
         let cache_dir = Self::get_cache_dir()?;
+        Self::in_dir(cache_dir).await
+    }
+
+    /// A cache kept in `cache_dir` instead of the user's cache directory
+    pub async fn in_dir(cache_dir: PathBuf) -> Result<Self, CacheError> {
         fs::create_dir_all(&cache_dir).await?;
 
-        Ok(Self { cache_dir })
+        Ok(Self {
+            cache_dir,
+            known: Arc::default(),
+        })
     }
 
     fn get_cache_dir() -> Result<PathBuf, CacheError> {
@@ -87,18 +146,109 @@ impl Cache {
         Ok(cache_dir)
     }
 
-    // Budgets cache
-    pub async fn get_budgets(&self) -> Result<Option<CachedBudgets>, CacheError> {
-        let path = self.cache_dir.join("budgets.json");
+    /// Wait for exclusive use of the cache directory. Other processes only
+    /// wait on it for read-modify-write updates; plain reads go ahead.
+    pub async fn lock(&self) -> Result<CacheLock, CacheError> {
+        let path = self.cache_dir.join(LOCK_FILE);
+        let file = tokio::task::spawn_blocking(move || {
+            let file = open_lock_file(&path)?;
+            file.lock()?;
+            Ok::<_, std::io::Error>(file)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        Ok(CacheLock { _file: file })
+    }
+
+    /// Claim sending changes made offline, or `None` if another process
+    /// already has
+    pub fn try_lock_sync(&self) -> Result<Option<CacheLock>, CacheError> {
+        let file = open_lock_file(&self.cache_dir.join(SYNC_LOCK_FILE))?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(CacheLock { _file: file })),
+            Err(std::fs::TryLockError::WouldBlock) => Ok(None),
+            Err(std::fs::TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    fn know(&self, path: &Path, server_knowledge: Option<i64>) {
+        if let Some(knowledge) = server_knowledge {
+            let mut known = self.known.lock().unwrap_or_else(|e| e.into_inner());
+            known.insert(path.to_path_buf(), knowledge);
+        }
+    }
+
+    fn known(&self, path: &Path) -> Option<i64> {
+        let known = self.known.lock().unwrap_or_else(|e| e.into_inner());
+        known.get(path).copied()
+    }
+
+    fn accounts_path(&self, budget_id: &str) -> PathBuf {
+        self.cache_dir.join(format!("accounts_{}.json", budget_id))
+    }
+
+    fn transactions_path(&self, budget_id: &str, account_id: &str) -> PathBuf {
+        self.cache_dir
+            .join(format!("transactions_{}_{}.json", budget_id, account_id))
+    }
+
+    /// Read a versioned cache file, migrating (and rewriting) it if it is from an older version
+    async fn read_file<T: DeserializeOwned + Serialize>(
+        &self,
+        path: &Path,
+        kind: CacheKind,
+    ) -> Result<Option<T>, CacheError> {
         if !path.exists() {
             return Ok(None);
         }
 
-        let data = fs::read_to_string(&path).await?;
-        let cached: CachedBudgets = serde_json::from_str(&data)?;
+        let raw: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).await?)?;
+        let (version, data) = migrations::unwrap_envelope(raw);
+        let data = migrations::migrate(kind, version, data)?;
+        let cached: T = serde_json::from_value(data)?;
+
+        if version < migrations::CURRENT_VERSION {
+            tracing::info!(
+                "Upgraded {:?} cache {:?} from v{} to v{}",
+                kind,
+                path,
+                version,
+                migrations::CURRENT_VERSION
+            );
+            self.write_file(path, &cached).await?;
+        }
+
         Ok(Some(cached))
     }
 
+    /// Write a cache file wrapped in the current version envelope
+    async fn write_file<T: Serialize + ?Sized>(
+        &self,
+        path: &Path,
+        data: &T,
+    ) -> Result<(), CacheError> {
+        let json = serde_json::to_string_pretty(&Versioned::current(data))?;
+        // Write beside the file and swap it in, so another process reading
+        // it never sees half a write
+        let temp = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp, json).await?;
+        if let Err(e) = fs::rename(&temp, path).await {
+            let _ = fs::remove_file(&temp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    // Budgets cache
+    pub async fn get_budgets(&self) -> Result<Option<CachedBudgets>, CacheError> {
+        let path = self.cache_dir.join("budgets.json");
+        self.read_file(&path, CacheKind::Budgets).await
+    }
+
     pub async fn set_budgets(
         &self,
         budgets: &[BudgetSummary],
@@ -111,9 +261,7 @@ impl Cache {
         };
 
         let path = self.cache_dir.join("budgets.json");
-        let json = serde_json::to_string_pretty(&cached)?;
-        fs::write(&path, json).await?;
-        Ok(())
+        self.write_file(&path, &cached).await
     }
 
     // Accounts cache
@@ -121,14 +269,18 @@ impl Cache {
         &self,
         budget_id: &str,
     ) -> Result<Option<CachedAccounts>, CacheError> {
-        let path = self.cache_dir.join(format!("accounts_{}.json", budget_id));
-        if !path.exists() {
-            return Ok(None);
-        }
+        let path = self.accounts_path(budget_id);
+        self.read_file(&path, CacheKind::Accounts).await
+    }
 
-        let data = fs::read_to_string(&path).await?;
-        let cached: CachedAccounts = serde_json::from_str(&data)?;
-        Ok(Some(cached))
+    /// Record that accounts as of `server_knowledge` are on screen
+    pub fn know_accounts(&self, budget_id: &str, server_knowledge: Option<i64>) {
+        self.know(&self.accounts_path(budget_id), server_knowledge);
+    }
+
+    /// Server knowledge of the accounts last shown or written by this process
+    pub fn known_accounts(&self, budget_id: &str) -> Option<i64> {
+        self.known(&self.accounts_path(budget_id))
     }
 
     pub async fn set_accounts(
@@ -143,9 +295,9 @@ impl Cache {
             cached_at: chrono::Utc::now().timestamp(),
         };
 
-        let path = self.cache_dir.join(format!("accounts_{}.json", budget_id));
-        let json = serde_json::to_string_pretty(&cached)?;
-        fs::write(&path, json).await?;
+        let path = self.accounts_path(budget_id);
+        self.write_file(&path, &cached).await?;
+        self.know(&path, server_knowledge);
         Ok(())
     }
 
@@ -156,6 +308,7 @@ impl Cache {
         delta: &[Account],
         new_server_knowledge: i64,
     ) -> Result<(), CacheError> {
+        let _lock = self.lock().await?;
         // Read existing cache
         let mut cached = self.get_accounts(budget_id).await?.ok_or_else(|| {
             CacheError::Io(std::io::Error::new(
@@ -163,6 +316,14 @@ impl Cache {
                 "Cache not found for merge",
             ))
         })?;
+        // Another process already stored these changes, or later ones
+        if cached
+            .server_knowledge
+            .is_some_and(|knowledge| knowledge >= new_server_knowledge)
+        {
+            self.know_accounts(budget_id, Some(new_server_knowledge));
+            return Ok(());
+        }
 
         // Merge delta
         for delta_account in delta {
@@ -193,16 +354,27 @@ impl Cache {
         budget_id: &str,
         account_id: &str,
     ) -> Result<Option<CachedTransactions>, CacheError> {
-        let path = self
-            .cache_dir
-            .join(format!("transactions_{}_{}.json", budget_id, account_id));
-        if !path.exists() {
-            return Ok(None);
-        }
+        let path = self.transactions_path(budget_id, account_id);
+        self.read_file(&path, CacheKind::Transactions).await
+    }
 
-        let data = fs::read_to_string(&path).await?;
-        let cached: CachedTransactions = serde_json::from_str(&data)?;
-        Ok(Some(cached))
+    /// Record that an account's transactions as of `server_knowledge` are on screen
+    pub fn know_transactions(
+        &self,
+        budget_id: &str,
+        account_id: &str,
+        server_knowledge: Option<i64>,
+    ) {
+        self.know(
+            &self.transactions_path(budget_id, account_id),
+            server_knowledge,
+        );
+    }
+
+    /// Server knowledge of an account's transactions last shown or written
+    /// by this process
+    pub fn known_transactions(&self, budget_id: &str, account_id: &str) -> Option<i64> {
+        self.known(&self.transactions_path(budget_id, account_id))
     }
 
     pub async fn set_transactions(
@@ -211,18 +383,18 @@ impl Cache {
         account_id: &str,
         transactions: &[Transaction],
         server_knowledge: Option<i64>,
+        loaded_since: Option<NaiveDate>,
     ) -> Result<(), CacheError> {
         let cached = CachedTransactions {
             transactions: transactions.to_vec(),
             server_knowledge,
             cached_at: chrono::Utc::now().timestamp(),
+            loaded_since,
         };
 
-        let path = self
-            .cache_dir
-            .join(format!("transactions_{}_{}.json", budget_id, account_id));
-        let json = serde_json::to_string_pretty(&cached)?;
-        fs::write(&path, json).await?;
+        let path = self.transactions_path(budget_id, account_id);
+        self.write_file(&path, &cached).await?;
+        self.know(&path, server_knowledge);
         Ok(())
     }
 
@@ -234,6 +406,7 @@ impl Cache {
         delta: &[Transaction],
         new_server_knowledge: i64,
     ) -> Result<(), CacheError> {
+        let _lock = self.lock().await?;
         // Read existing cache
         let mut cached = self
             .get_transactions(budget_id, account_id)
@@ -244,6 +417,14 @@ impl Cache {
                     "Cache not found for merge",
                 ))
             })?;
+        // Another process already stored these changes, or later ones
+        if cached
+            .server_knowledge
+            .is_some_and(|knowledge| knowledge >= new_server_knowledge)
+        {
+            self.know_transactions(budget_id, account_id, Some(new_server_knowledge));
+            return Ok(());
+        }
 
         // Merge delta
         for delta_transaction in delta {
@@ -269,6 +450,120 @@ impl Cache {
             account_id,
             &cached.transactions,
             cached.server_knowledge,
+            cached.loaded_since,
+        )
+        .await
+    }
+
+    /// Add a page of history from before the cached range
+    pub async fn merge_older_transactions(
+        &self,
+        budget_id: &str,
+        account_id: &str,
+        older: &[Transaction],
+        loaded_since: Option<NaiveDate>,
+    ) -> Result<(), CacheError> {
+        let _lock = self.lock().await?;
+        let mut cached = self
+            .get_transactions(budget_id, account_id)
+            .await?
+            .ok_or_else(|| {
+                CacheError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Cache not found for merge",
+                ))
+            })?;
+
+        for transaction in older {
+            if !cached.transactions.iter().any(|t| t.id == transaction.id) {
+                cached.transactions.push(transaction.clone());
+            }
+        }
+
+        self.set_transactions(
+            budget_id,
+            account_id,
+            &cached.transactions,
+            cached.server_knowledge,
+            loaded_since,
+        )
+        .await
+    }
+
+    // Scheduled transactions cache
+    pub async fn get_scheduled_transactions(
+        &self,
+        budget_id: &str,
+    ) -> Result<Option<CachedScheduledTransactions>, CacheError> {
+        let path = self
+            .cache_dir
+            .join(format!("scheduled_transactions_{}.json", budget_id));
+        self.read_file(&path, CacheKind::ScheduledTransactions)
+            .await
+    }
+
+    pub async fn set_scheduled_transactions(
+        &self,
+        budget_id: &str,
+        scheduled_transactions: &[ScheduledTransaction],
+        server_knowledge: Option<i64>,
+    ) -> Result<(), CacheError> {
+        let cached = CachedScheduledTransactions {
+            scheduled_transactions: scheduled_transactions.to_vec(),
+            server_knowledge,
+            cached_at: chrono::Utc::now().timestamp(),
+        };
+
+        let path = self
+            .cache_dir
+            .join(format!("scheduled_transactions_{}.json", budget_id));
+        self.write_file(&path, &cached).await
+    }
+
+    /// Merge delta updates into existing scheduled transactions cache
+    pub async fn merge_scheduled_transactions_delta(
+        &self,
+        budget_id: &str,
+        delta: &[ScheduledTransaction],
+        new_server_knowledge: i64,
+    ) -> Result<(), CacheError> {
+        let _lock = self.lock().await?;
+        let mut cached = self
+            .get_scheduled_transactions(budget_id)
+            .await?
+            .ok_or_else(|| {
+                CacheError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Cache not found for merge",
+                ))
+            })?;
+        if cached
+            .server_knowledge
+            .is_some_and(|knowledge| knowledge >= new_server_knowledge)
+        {
+            return Ok(());
+        }
+
+        for delta_scheduled in delta {
+            if delta_scheduled.deleted {
+                cached
+                    .scheduled_transactions
+                    .retain(|s| s.id != delta_scheduled.id);
+            } else if let Some(existing) = cached
+                .scheduled_transactions
+                .iter_mut()
+                .find(|s| s.id == delta_scheduled.id)
+            {
+                *existing = delta_scheduled.clone();
+            } else {
+                cached.scheduled_transactions.push(delta_scheduled.clone());
+            }
+        }
+
+        self.set_scheduled_transactions(
+            budget_id,
+            &cached.scheduled_transactions,
+            Some(new_server_knowledge),
         )
         .await
     }
@@ -276,13 +571,7 @@ impl Cache {
     // Plan cache
     pub async fn get_plan(&self, budget_id: &str) -> Result<Option<CachedPlan>, CacheError> {
         let path = self.cache_dir.join(format!("plan_{}.json", budget_id));
-        if !path.exists() {
-            return Ok(None);
-        }
-
-        let data = fs::read_to_string(&path).await?;
-        let cached: CachedPlan = serde_json::from_str(&data)?;
-        Ok(Some(cached))
+        self.read_file(&path, CacheKind::Plan).await
     }
 
     pub async fn set_plan(
@@ -300,9 +589,7 @@ impl Cache {
         };
 
         let path = self.cache_dir.join(format!("plan_{}.json", budget_id));
-        let json = serde_json::to_string_pretty(&cached)?;
-        fs::write(&path, json).await?;
-        Ok(())
+        self.write_file(&path, &cached).await
     }
 
     /// Get plan for a specific month (month format: YYYY-MM-DD)
@@ -314,13 +601,7 @@ impl Cache {
         let path = self
             .cache_dir
             .join(format!("plan_{}_{}.json", budget_id, month));
-        if !path.exists() {
-            return Ok(None);
-        }
-
-        let data = fs::read_to_string(&path).await?;
-        let cached: CachedPlan = serde_json::from_str(&data)?;
-        Ok(Some(cached))
+        self.read_file(&path, CacheKind::Plan).await
     }
 
     /// Set plan for a specific month (month format: YYYY-MM-DD)
@@ -341,28 +622,18 @@ impl Cache {
         let path = self
             .cache_dir
             .join(format!("plan_{}_{}.json", budget_id, month_str));
-        let json = serde_json::to_string_pretty(&cached)?;
-        fs::write(&path, json).await?;
-        Ok(())
+        self.write_file(&path, &cached).await
     }
 
     // Payees cache (for transaction creation autocomplete)
     pub async fn get_payees(&self, budget_id: &str) -> Result<Option<Vec<Payee>>, CacheError> {
         let path = self.cache_dir.join(format!("payees_{}.json", budget_id));
-        if !path.exists() {
-            return Ok(None);
-        }
-
-        let data = fs::read_to_string(&path).await?;
-        let payees: Vec<Payee> = serde_json::from_str(&data)?;
-        Ok(Some(payees))
+        self.read_file(&path, CacheKind::Payees).await
     }
 
     pub async fn set_payees(&self, budget_id: &str, payees: &[Payee]) -> Result<(), CacheError> {
         let path = self.cache_dir.join(format!("payees_{}.json", budget_id));
-        let json = serde_json::to_string_pretty(&payees)?;
-        fs::write(&path, json).await?;
-        Ok(())
+        self.write_file(&path, payees).await
     }
 
     // Categories cache (for transaction creation autocomplete)
@@ -373,13 +644,7 @@ impl Cache {
         let path = self
             .cache_dir
             .join(format!("categories_{}.json", budget_id));
-        if !path.exists() {
-            return Ok(None);
-        }
-
-        let data = fs::read_to_string(&path).await?;
-        let categories: Vec<Category> = serde_json::from_str(&data)?;
-        Ok(Some(categories))
+        self.read_file(&path, CacheKind::Categories).await
     }
 
     pub async fn set_categories(
@@ -390,9 +655,18 @@ impl Cache {
         let path = self
             .cache_dir
             .join(format!("categories_{}.json", budget_id));
-        let json = serde_json::to_string_pretty(&categories)?;
-        fs::write(&path, json).await?;
-        Ok(())
+        self.write_file(&path, categories).await
+    }
+
+    // Changes waiting for YNAB to be reachable, oldest first (local only)
+    pub async fn get_pending_writes(&self) -> Result<Option<Vec<PendingWrite>>, CacheError> {
+        let path = self.cache_dir.join("pending_writes.json");
+        self.read_file(&path, CacheKind::PendingWrites).await
+    }
+
+    pub async fn set_pending_writes(&self, writes: &[PendingWrite]) -> Result<(), CacheError> {
+        let path = self.cache_dir.join("pending_writes.json");
+        self.write_file(&path, writes).await
     }
 
     // Invalidate transactions cache (after creating a new transaction)
@@ -401,9 +675,7 @@ impl Cache {
         budget_id: &str,
         account_id: &str,
     ) -> Result<(), CacheError> {
-        let path = self
-            .cache_dir
-            .join(format!("transactions_{}_{}.json", budget_id, account_id));
+        let path = self.transactions_path(budget_id, account_id);
 
         if path.exists() {
             fs::remove_file(&path).await?;
@@ -429,3 +701,54 @@ impl Cache {
         Ok(())
     }
 }
+
+fn open_lock_file(path: &Path) -> std::io::Result<std::fs::File> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn changes_another_process_stored_are_not_merged_twice() {
+        let dir = crate::replay::scratch_cache_dir();
+        let here = Cache::in_dir(dir.clone()).await.unwrap();
+        let elsewhere = Cache::in_dir(dir.clone()).await.unwrap();
+
+        here.set_accounts("budget", &[], Some(10)).await.unwrap();
+        // Another process has already merged changes up to 12
+        elsewhere
+            .set_accounts("budget", &[], Some(12))
+            .await
+            .unwrap();
+        assert_eq!(here.known_accounts("budget"), Some(10));
+
+        // A delta this process fetched from 10 mustn't wind the cache back
+        here.merge_accounts_delta("budget", &[], 11).await.unwrap();
+        let cached = here.get_accounts("budget").await.unwrap().unwrap();
+        assert_eq!(cached.server_knowledge, Some(12));
+        assert_eq!(here.known_accounts("budget"), Some(11));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn only_one_process_sends_pending_writes() {
+        let dir = crate::replay::scratch_cache_dir();
+        let here = Cache::in_dir(dir.clone()).await.unwrap();
+        let elsewhere = Cache::in_dir(dir.clone()).await.unwrap();
+
+        let claim = here.try_lock_sync().unwrap();
+        assert!(claim.is_some());
+        assert!(elsewhere.try_lock_sync().unwrap().is_none());
+        drop(claim);
+        assert!(elsewhere.try_lock_sync().unwrap().is_some());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,150 @@
+//! Category suggestions for transactions waiting for review.
+//!
+//! An unapproved transaction without a category is offered the category its
+//! payee was given most often before, going by the cached history of every
+//! account. Unlike payee rules there's nothing to set up, and each
+//! suggestion is accepted (`Tab`) or ignored one transaction at a time.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use ynab_api::endpoints::transactions::Transaction;
+
+/// The category a payee's transactions most often had
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategorySuggestion {
+    pub category_id: Uuid,
+    pub category_name: String,
+}
+
+/// The most used category of each payee, by payee id. Ties go to the
+/// category used most recently.
+pub fn by_payee<'a>(
+    transactions: impl IntoIterator<Item = &'a Transaction>,
+) -> HashMap<Uuid, CategorySuggestion> {
+    // Uses and latest date of each payee's categories
+    let mut uses: HashMap<(Uuid, Uuid), (usize, chrono::NaiveDate, &str)> = HashMap::new();
+    for transaction in transactions {
+        if transaction.deleted
+            || transaction.transfer_account_id.is_some()
+            || !transaction.subtransactions.is_empty()
+        {
+            continue;
+        }
+        let (Some(payee_id), Some(category_id), Some(category_name)) = (
+            transaction.payee_id,
+            transaction.category_id,
+            transaction.category_name.as_deref(),
+        ) else {
+            continue;
+        };
+        if category_name == "Uncategorized" {
+            continue;
+        }
+        let entry =
+            uses.entry((payee_id, category_id))
+                .or_insert((0, transaction.date, category_name));
+        entry.0 += 1;
+        if transaction.date > entry.1 {
+            entry.1 = transaction.date;
+            // The newest name, should the category have been renamed
+            entry.2 = category_name;
+        }
+    }
+
+    let mut best: HashMap<Uuid, (usize, chrono::NaiveDate, CategorySuggestion)> = HashMap::new();
+    for ((payee_id, category_id), (count, latest, name)) in uses {
+        let better = best
+            .get(&payee_id)
+            .is_none_or(|(best_count, best_latest, _)| {
+                (count, latest) > (*best_count, *best_latest)
+            });
+        if better {
+            let suggestion = CategorySuggestion {
+                category_id,
+                category_name: name.to_string(),
+            };
+            best.insert(payee_id, (count, latest, suggestion));
+        }
+    }
+    best.into_iter()
+        .map(|(payee_id, (_, _, suggestion))| (payee_id, suggestion))
+        .collect()
+}
+
+/// Whether `transaction` is new and still needs a category: unapproved,
+/// uncategorized, and not a split, transfer or reconciled one
+pub fn wants_suggestion(transaction: &Transaction) -> bool {
+    !transaction.approved
+        && !transaction.deleted
+        && !transaction.is_reconciled()
+        && transaction.transfer_account_id.is_none()
+        && transaction.subtransactions.is_empty()
+        && (transaction.category_id.is_none()
+            || transaction.category_name.as_deref() == Some("Uncategorized"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    #[test]
+    fn payees_get_their_most_used_category() {
+        let history = [
+            transaction("2024-01-03", -12_000)
+                .payee("Shell")
+                .category("Fuel")
+                .build(),
+            transaction("2024-02-03", -12_000)
+                .payee("Shell")
+                .category("Fuel")
+                .build(),
+            transaction("2024-03-03", -12_000)
+                .payee("Shell")
+                .category("Snacks")
+                .build(),
+            // A tie goes to the category used last
+            transaction("2024-01-10", -12_000)
+                .payee("Cafe")
+                .category("Dining Out")
+                .build(),
+            transaction("2024-02-10", -12_000)
+                .payee("Cafe")
+                .category("Coffee")
+                .build(),
+            transaction("2024-02-10", -12_000)
+                .payee("Bank")
+                .category("Uncategorized")
+                .build(),
+        ];
+
+        let suggestions = by_payee(&history);
+
+        let name = |t: &Transaction| {
+            suggestions
+                .get(&t.payee_id.unwrap())
+                .map(|s| s.category_name.as_str())
+        };
+        assert_eq!(name(&history[0]), Some("Fuel"));
+        assert_eq!(name(&history[3]), Some("Coffee"));
+        assert_eq!(name(&history[5]), None);
+
+        assert!(wants_suggestion(
+            &transaction("2024-04-01", -12_000)
+                .payee("Shell")
+                .approved(false)
+                .build()
+        ));
+        assert!(!wants_suggestion(
+            &transaction("2024-04-01", -12_000).payee("Shell").build()
+        ));
+        assert!(!wants_suggestion(
+            &transaction("2024-04-01", -12_000)
+                .payee("Shell")
+                .category("Fuel")
+                .approved(false)
+                .build()
+        ));
+    }
+}
@@ -0,0 +1,369 @@
+//! Non-interactive subcommands, run instead of the TUI.
+//!
+//! Reports only read the local cache so they work without a token, e.g. from
+//! cron. Arguments are parsed by hand since there are only a few.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::config::AppConfig;
+use crate::config_bundle;
+use crate::export;
+use crate::replay;
+use crate::reports::weekly;
+use crate::session_log;
+
+/// A subcommand given on the command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    WeeklyReport(WeeklyReportArgs),
+    Replay(ReplayArgs),
+    SessionReplay(SessionReplayArgs),
+    /// Start the TUI on the screen open at the last quit
+    Resume,
+    /// Write config and settings to a bundle, see [`crate::config_bundle`]
+    ConfigExport(PathBuf),
+    /// Replace config and settings with a bundle's
+    ConfigImport(PathBuf),
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WeeklyReportArgs {
+    /// Write here instead of stdout
+    pub output: Option<PathBuf>,
+    /// Replace the built-in Markdown template
+    pub template: Option<PathBuf>,
+    /// Budget id; defaults to YNAB's default budget, or the only cached one
+    pub budget_id: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReplayArgs {
+    /// Key script, see [`crate::replay`]
+    pub script: PathBuf,
+    /// Fail unless the replay ends on this screen
+    pub assert_screen: Option<String>,
+    /// API to replay against, e.g. a mock server; without it the real API is
+    /// used with the stored token
+    pub api_url: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SessionReplayArgs {
+    /// Session log recorded with `[debug] record_session`
+    pub log: PathBuf,
+    /// Stop after this step and print the screen
+    pub until: Option<usize>,
+}
+
+const USAGE: &str = "Usage: ynat [--resume]
+       ynat report --weekly [--output FILE] [--template FILE] [--budget ID]
+       ynat --replay FILE [--assert-screen SCREEN] [--api-url URL]
+       ynat replay FILE [--until STEP]
+       ynat config export|import FILE";
+
+/// Parse arguments (without the program name). `None` means start the TUI.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Command>> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None => Ok(None),
+        Some("--resume") => match args.next() {
+            None => Ok(Some(Command::Resume)),
+            Some(arg) => bail!("Unknown argument {arg}\n{USAGE}"),
+        },
+        Some("report") => {
+            let mut weekly = false;
+            let mut report = WeeklyReportArgs::default();
+            while let Some(arg) = args.next() {
+                let mut value = || args.next().ok_or_else(|| anyhow!("{arg} needs a value"));
+                match arg.as_str() {
+                    "--weekly" => weekly = true,
+                    "--output" | "-o" => report.output = Some(value()?.into()),
+                    "--template" => report.template = Some(value()?.into()),
+                    "--budget" => report.budget_id = Some(value()?),
+                    _ => bail!("Unknown argument {arg}\n{USAGE}"),
+                }
+            }
+            if !weekly {
+                bail!("Only weekly reports are supported\n{USAGE}");
+            }
+            Ok(Some(Command::WeeklyReport(report)))
+        }
+        Some("--replay") => {
+            let script = args
+                .next()
+                .ok_or_else(|| anyhow!("--replay needs a value"))?;
+            let mut replay = ReplayArgs {
+                script: script.into(),
+                ..Default::default()
+            };
+            while let Some(arg) = args.next() {
+                let mut value = || args.next().ok_or_else(|| anyhow!("{arg} needs a value"));
+                match arg.as_str() {
+                    "--assert-screen" => replay.assert_screen = Some(value()?),
+                    "--api-url" => replay.api_url = Some(value()?),
+                    _ => bail!("Unknown argument {arg}\n{USAGE}"),
+                }
+            }
+            Ok(Some(Command::Replay(replay)))
+        }
+        Some("replay") => {
+            let log = args.next().ok_or_else(|| anyhow!("replay needs a file"))?;
+            let mut replay = SessionReplayArgs {
+                log: log.into(),
+                ..Default::default()
+            };
+            while let Some(arg) = args.next() {
+                let mut value = || args.next().ok_or_else(|| anyhow!("{arg} needs a value"));
+                match arg.as_str() {
+                    "--until" => {
+                        let step = value()?;
+                        replay.until = Some(
+                            step.parse()
+                                .map_err(|_| anyhow!("--until needs a step number, not {step}"))?,
+                        );
+                    }
+                    _ => bail!("Unknown argument {arg}\n{USAGE}"),
+                }
+            }
+            Ok(Some(Command::SessionReplay(replay)))
+        }
+        Some("config") => {
+            let action = args.next();
+            let file = args
+                .next()
+                .ok_or_else(|| anyhow!("config needs export or import and a file\n{USAGE}"))?;
+            if let Some(arg) = args.next() {
+                bail!("Unknown argument {arg}\n{USAGE}");
+            }
+            match action.as_deref() {
+                Some("export") => Ok(Some(Command::ConfigExport(file.into()))),
+                Some("import") => Ok(Some(Command::ConfigImport(file.into()))),
+                _ => bail!("config needs export or import\n{USAGE}"),
+            }
+        }
+        Some(other) => bail!("Unknown command {other}\n{USAGE}"),
+    }
+}
+
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::WeeklyReport(args) => weekly_report(args).await,
+        Command::Replay(args) => replay(args).await,
+        Command::SessionReplay(args) => session_replay(args).await,
+        Command::Resume => bail!("--resume starts the TUI rather than a subcommand"),
+        Command::ConfigExport(path) => config_export(&path).await,
+        Command::ConfigImport(path) => config_import(&path).await,
+    }
+}
+
+async fn config_export(path: &Path) -> Result<()> {
+    let bundle = config_bundle::export(&AppConfig::path()).await?;
+    let json = serde_json::to_string_pretty(&bundle)?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("{}", bundle_summary("Exported", bundle.counts(), path));
+    Ok(())
+}
+
+async fn config_import(path: &Path) -> Result<()> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let bundle = config_bundle::Bundle::parse(&json)?;
+    config_bundle::import(&bundle, &AppConfig::path()).await?;
+    println!("{}", bundle_summary("Imported", bundle.counts(), path));
+    Ok(())
+}
+
+fn bundle_summary(verb: &str, counts: config_bundle::Counts, path: &Path) -> String {
+    let config = if counts.config { "config.toml, " } else { "" };
+    format!(
+        "{verb} {config}settings and {} budget{} ({})",
+        counts.budgets,
+        if counts.budgets == 1 { "" } else { "s" },
+        path.display()
+    )
+}
+
+async fn session_replay(args: SessionReplayArgs) -> Result<()> {
+    let log = std::fs::read_to_string(&args.log)
+        .with_context(|| format!("Failed to read session log {}", args.log.display()))?;
+    let entries = session_log::read(&log)?;
+
+    let replayed = session_log::replay(&entries, args.until, |step, entry, state| {
+        println!(
+            "{step:>5}  {:<50} {}",
+            entry.name(),
+            replay::screen_name(state.current_screen())
+        );
+    })
+    .await?;
+
+    if args.until.is_some() {
+        println!("\n{}", replayed.screen);
+    }
+    println!(
+        "Replayed {} of {} steps, ended on {}",
+        replayed.steps,
+        entries.len(),
+        replay::screen_name(replayed.state.current_screen())
+    );
+    Ok(())
+}
+
+async fn replay(args: ReplayArgs) -> Result<()> {
+    let script = std::fs::read_to_string(&args.script)
+        .with_context(|| format!("Failed to read key script {}", args.script.display()))?;
+    let keys = replay::parse_script(&script)?;
+
+    let client = match &args.api_url {
+        // Mock servers don't check the token
+        Some(url) => ynab_api::Client::with_base_url(url, "replay"),
+        None => ynab_api::Client::new(&ynat_auth::authenticate().await?.access_token),
+    };
+    let state = replay::run(&keys, client).await?;
+
+    let screen = replay::screen_name(state.current_screen());
+    if let Some(expected) = &args.assert_screen {
+        if screen != expected {
+            bail!("Expected to end on the {expected} screen, but ended on {screen}");
+        }
+    }
+    println!("Replayed {} keys, ended on {screen}", keys.len());
+    Ok(())
+}
+
+async fn weekly_report(args: WeeklyReportArgs) -> Result<()> {
+    let cache = Cache::new().await?;
+
+    let budgets = cache
+        .get_budgets()
+        .await?
+        .ok_or_else(|| anyhow!("No cached budgets; open ynat once to sync first"))?;
+    let budget = match &args.budget_id {
+        Some(id) => budgets.budgets.iter().find(|b| b.id.to_string() == *id),
+        None => budgets
+            .default_budget
+            .as_ref()
+            .or_else(|| (budgets.budgets.len() == 1).then(|| &budgets.budgets[0])),
+    }
+    .ok_or_else(|| anyhow!("Pick a budget with --budget ID"))?;
+    let budget_id = budget.id.to_string();
+
+    let mut transactions = Vec::new();
+    if let Some(accounts) = cache.get_accounts(&budget_id).await? {
+        for account in accounts.accounts {
+            if let Some(cached) = cache
+                .get_transactions(&budget_id, &account.id.to_string())
+                .await?
+            {
+                transactions.extend(cached.transactions);
+            }
+        }
+    }
+    let plan = cache.get_plan(&budget_id).await?;
+
+    let template = match &args.template {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read template {}", path.display()))?,
+        None => weekly::DEFAULT_TEMPLATE.to_string(),
+    };
+
+    let today = chrono::Local::now().date_naive();
+    let summary = weekly::build(&transactions, plan.as_ref().map(|p| &p.month), today);
+    let report = weekly::render(&summary, Some(budget), &template);
+
+    let config = AppConfig::load().export;
+    match &args.output {
+        Some(path) => {
+            let written = export::write(path, report.as_bytes(), &config)?;
+            if written != *path {
+                eprintln!("Encrypted report written to {}", written.display());
+            }
+        }
+        None => std::io::stdout().write_all(&export::encrypt(report.as_bytes(), &config, true)?)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn no_arguments_starts_the_tui() {
+        assert_eq!(parse(args("")).unwrap(), None);
+    }
+
+    #[test]
+    fn parses_weekly_report() {
+        let command = parse(args("report --weekly --output report.md --budget abc")).unwrap();
+        assert_eq!(
+            command,
+            Some(Command::WeeklyReport(WeeklyReportArgs {
+                output: Some("report.md".into()),
+                template: None,
+                budget_id: Some("abc".into()),
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_replay() {
+        let command = parse(args("--replay keys.txt --assert-screen plan")).unwrap();
+        assert_eq!(
+            command,
+            Some(Command::Replay(ReplayArgs {
+                script: "keys.txt".into(),
+                assert_screen: Some("plan".into()),
+                api_url: None,
+            }))
+        );
+        assert!(parse(args("--replay")).is_err());
+    }
+
+    #[test]
+    fn parses_config_bundles() {
+        assert_eq!(
+            parse(args("config export setup.json")).unwrap(),
+            Some(Command::ConfigExport("setup.json".into()))
+        );
+        assert_eq!(
+            parse(args("config import setup.json")).unwrap(),
+            Some(Command::ConfigImport("setup.json".into()))
+        );
+        assert!(parse(args("config export")).is_err());
+        assert!(parse(args("config sync setup.json")).is_err());
+    }
+
+    #[test]
+    fn parses_resume() {
+        assert_eq!(parse(args("--resume")).unwrap(), Some(Command::Resume));
+        assert!(parse(args("--resume now")).is_err());
+    }
+
+    #[test]
+    fn parses_session_replay() {
+        let command = parse(args("replay session.log --until 12")).unwrap();
+        assert_eq!(
+            command,
+            Some(Command::SessionReplay(SessionReplayArgs {
+                log: "session.log".into(),
+                until: Some(12),
+            }))
+        );
+        assert!(parse(args("replay")).is_err());
+        assert!(parse(args("replay session.log --until twelve")).is_err());
+    }
+
+    #[test]
+    fn rejects_incomplete_report_arguments() {
+        assert!(parse(args("report")).is_err());
+        assert!(parse(args("report --weekly --output")).is_err());
+    }
+}
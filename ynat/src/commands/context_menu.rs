@@ -0,0 +1,115 @@
+//! Per-row action menu, so capabilities are discoverable without
+//! memorizing every key.
+//!
+//! Entries are found by asking the key handler what each candidate key
+//! would do for the current selection, so the menu only lists actions
+//! that are valid right now and never drifts from the real bindings.
+
+use crate::events::AppCommand;
+use crate::i18n::t;
+use crate::input::{Key, KeyEvent};
+use crate::state::{AppState, ContextMenuItem, ContextMenuState, InputMode};
+use crate::ui::screens::Screen;
+
+use super::handlers::handle_key_input;
+
+/// Build the menu for the selected row, or `None` if nothing is selected
+pub fn build(state: &AppState) -> Option<ContextMenuState> {
+    let (title, id, candidates): (String, String, &[(Key, &'static str, &'static str)]) =
+        match state.current_screen() {
+            Screen::Transactions(transactions_state) => {
+                if transactions_state.input_mode != InputMode::Normal {
+                    return None;
+                }
+                let selected = transactions_state.table_state.borrow().selected()?;
+                let transaction = *transactions_state.filtered_transactions().get(selected)?;
+                let title = transaction
+                    .payee_name
+                    .clone()
+                    .unwrap_or_else(|| t("transactions-title").to_string());
+                (
+                    title,
+                    transaction.id.to_string(),
+                    &[
+                        (Key::Char('e'), "e", "help-edit-transaction"),
+                        (Key::Char('C'), "C", "help-categorize-transaction"),
+                        (Key::Tab, "Tab", "help-accept-suggestion"),
+                        (Key::Char('d'), "d", "help-delete-transaction"),
+                        (Key::Char('a'), "a", "help-approve-transaction"),
+                        (Key::Char('c'), "c", "help-toggle-cleared"),
+                        (Key::Char('F'), "F", "help-cycle-flag"),
+                        (Key::Char('t'), "t", "help-jump-to-transfer"),
+                        (Key::Char('$'), "$", "help-toggle-reimbursement"),
+                        (Key::Char('T'), "T", "help-follow-up"),
+                        (Key::Char('L'), "L", "help-payee-location"),
+                        (Key::Char('i'), "i", "help-inspect"),
+                    ],
+                )
+            }
+            Screen::Accounts(accounts_state) => {
+                if accounts_state.input_mode != InputMode::Normal {
+                    return None;
+                }
+                let selected = accounts_state.table_state.borrow().selected()?;
+                let account = *accounts_state.filtered_accounts().get(selected)?;
+                (
+                    account.name.clone(),
+                    account.id.to_string(),
+                    &[
+                        (Key::Enter, "Enter", "help-view-transactions"),
+                        (Key::Char('F'), "F", "help-cash-flow"),
+                        (Key::Char('c'), "c", "help-reconcile-reminder"),
+                    ],
+                )
+            }
+            Screen::Plan(plan_state) => {
+                if plan_state.input_mode != InputMode::Normal {
+                    return None;
+                }
+                let category = plan_state.selected_category()?;
+                (
+                    category.name.clone(),
+                    category.id.to_string(),
+                    &[
+                        (Key::Char('e'), "e", "help-edit-budgeted"),
+                        (Key::Char('m'), "m", "help-move-money"),
+                        (Key::Char('w'), "w", "help-goal-calculator"),
+                        (Key::Char('i'), "i", "help-inspect"),
+                    ],
+                )
+            }
+            _ => return None,
+        };
+
+    let mut items: Vec<ContextMenuItem> = candidates
+        .iter()
+        .filter_map(|&(key, shortcut, label)| {
+            handle_key_input(KeyEvent::new(key), state).map(|command| ContextMenuItem {
+                label: t(label),
+                shortcut: Some(shortcut),
+                command,
+            })
+        })
+        .collect();
+    if let Screen::Transactions(..) = state.current_screen() {
+        // Two-key sequences can't be probed like single keys
+        items.push(ContextMenuItem {
+            label: t("help-yank-transaction"),
+            shortcut: Some("yy"),
+            command: AppCommand::YankTransaction {
+                transaction_id: id.clone(),
+            },
+        });
+    }
+    items.push(ContextMenuItem {
+        label: t("context-menu-copy-id"),
+        shortcut: None,
+        command: AppCommand::CopyToClipboard(id),
+    });
+
+    Some(ContextMenuState {
+        title,
+        items,
+        selected: 0,
+    })
+}
@@ -1,75 +1,105 @@
 use crate::background::{data_loader::DataLoader, BackgroundTaskManager};
-use crate::events::{AppCommand, DataEvent};
+use crate::error::AppError;
+use crate::events::{AppCommand, TransactionEvent};
+use crate::export;
+use crate::follow_up;
+use crate::health::HealthItem;
+use crate::i18n::t;
+use crate::lint;
+use crate::options::Saved;
+use crate::reconcile_reminders::ReconcileCadence;
+use crate::reports::variance::VarianceSort;
+use crate::reports::{ReportKind, ReportPeriod};
+use crate::rules;
+use crate::state::undo::UndoEntry;
 use crate::state::*;
 use crate::ui::screens::Screen;
 use crate::utils;
+use crate::utils::currency::{self, ForeignAmount};
+use crate::utils::money;
 use ratatui::widgets::TableState;
 use std::cell::RefCell;
 use throbber_widgets_tui::ThrobberState;
-use ynab_api::endpoints::transactions::{BulkTransactionUpdate, FlagColor, ReconciliationStatus};
+use uuid::Uuid;
+use ynab_api::endpoints::transactions::{
+    BulkTransactionUpdate, FlagColor, ReconciliationStatus, Transaction,
+};
 use ynab_api::endpoints::{BudgetId, TransactionId};
 use ynab_api::Request;
 
 /// Execute a command by spawning background tasks or sending app events
+/// The accounts screen's type filter and jump-to-account commands
+fn account_jump_or_filter(state: &mut AppState, command: AppCommand) {
+    let Screen::Accounts(accounts_state) = state.current_screen_mut() else {
+        return;
+    };
+    match command {
+        AppCommand::CycleAccountTypeFilter => {
+            accounts_state.type_filter = AccountGroup::cycle(accounts_state.type_filter);
+            accounts_state.table_state = RefCell::new(TableState::default().with_selected(0));
+        }
+        AppCommand::EnterAccountJump => {
+            accounts_state.input_mode = InputMode::AccountJump;
+            accounts_state.jump_query.clear();
+        }
+        AppCommand::AppendAccountJumpChar(c) => {
+            accounts_state.jump_query.push(c);
+            accounts_state.jump();
+        }
+        AppCommand::DeleteAccountJumpChar => {
+            accounts_state.jump_query.pop();
+            accounts_state.jump();
+        }
+        AppCommand::ExitAccountJump => {
+            accounts_state.input_mode = InputMode::Normal;
+            accounts_state.jump_query.clear();
+        }
+        _ => {}
+    }
+}
+
+/// Turn away a command that would change the budget when only reading is
+/// allowed, saying why instead of letting YNAB reject it
+fn refused_read_only(command: &AppCommand, state: &mut AppState) -> bool {
+    if !state.read_only || !command.changes_budget() {
+        return false;
+    }
+    tracing::info!("Read-only, ignoring {:?}", command);
+    state.notice = Some(t("notice-read-only").to_string());
+    state.pending_key = None;
+    state.pending_count = None;
+    true
+}
+
 pub fn execute_command(
     command: AppCommand,
     state: &mut AppState,
     task_manager: &mut BackgroundTaskManager,
     data_loader: &DataLoader,
 ) {
-    // Save whether we're setting a pending key (we don't want to clear it in that case)
+    // Save whether we're setting a pending key or count (we don't want to clear it in that case)
     let is_setting_pending_key = matches!(command, AppCommand::SetPendingKey(_));
+    let is_setting_pending_count = matches!(command, AppCommand::SetPendingCount(_));
+    // Notices only last until the next command, which may set its own
+    state.notice = None;
+    if refused_read_only(&command, state) {
+        return;
+    }
 
     match command {
         AppCommand::SelectNext => {
-            // Update selection based on current screen
-            match state.current_screen_mut() {
-                Screen::Budgets(budgets_state) => {
-                    if !budgets_state.budgets.is_empty() {
-                        budgets_state.selected_budget_index =
-                            (budgets_state.selected_budget_index + 1) % budgets_state.budgets.len();
-                    }
-                }
-                Screen::Accounts(accounts_state) => {
-                    accounts_state.select_next();
-                }
-                Screen::Transactions(transactions_state) => {
-                    transactions_state.select_next();
-                }
-                Screen::Plan(plan_state) => {
-                    plan_state.select_next();
-                }
-                Screen::Logs(_) => {
-                    // Logs screen uses its own scroll commands, not SelectNext
-                }
-            }
+            state.current_screen_mut().controller_mut().select_next();
         }
 
         AppCommand::SelectPrevious => {
-            // Update selection based on current screen
-            match state.current_screen_mut() {
-                Screen::Budgets(budgets_state) => {
-                    if !budgets_state.budgets.is_empty() {
-                        if budgets_state.selected_budget_index == 0 {
-                            budgets_state.selected_budget_index = budgets_state.budgets.len() - 1;
-                        } else {
-                            budgets_state.selected_budget_index -= 1;
-                        }
-                    }
-                }
-                Screen::Accounts(accounts_state) => {
-                    accounts_state.select_prev();
-                }
-                Screen::Transactions(transactions_state) => {
-                    transactions_state.select_prev();
-                }
-                Screen::Plan(plan_state) => {
-                    plan_state.select_prev();
-                }
-                Screen::Logs(_) => {
-                    // Logs screen uses its own scroll commands, not SelectPrevious
-                }
-            }
+            state
+                .current_screen_mut()
+                .controller_mut()
+                .select_previous();
+        }
+
+        AppCommand::MoveSelection { rows } => {
+            state.current_screen_mut().controller_mut().select_by(rows);
         }
 
         AppCommand::LoadBudgets {
@@ -108,10 +138,26 @@ pub fn execute_command(
             force_refresh,
         } => {
             // Update current budget ID and budget details
+            if state.current_budget_id.as_deref() != Some(budget_id.as_str()) {
+                // Another budget's spending until this one's is worked out
+                state.recent_spending = None;
+            }
             state.current_budget_id = Some(budget_id.clone());
+            // "default" is YNAB's choice, not one the user made
+            if budget_id != "default" {
+                state.settings.last_budget_id = Some(budget_id.clone());
+            }
             if let Some(budget) = *budget {
                 state.current_budget = Some(budget);
             }
+            if !force_refresh && state.currency_format().is_none() {
+                let data_loader = data_loader.clone();
+                let budget_id = budget_id.clone();
+                task_manager
+                    .spawn_load_task(format!("load_budget_formats_{}", budget_id), async move {
+                        data_loader.load_budget_formats(budget_id).await
+                    });
+            }
 
             // Check if we're already on Accounts screen (refresh) or navigating to it (new)
             match state.current_screen_mut() {
@@ -126,6 +172,9 @@ pub fn execute_command(
                     tracing::debug!("Navigating to accounts screen");
                     state.navigate_to(Screen::Accounts(AccountsState {
                         accounts_loading: LoadingState::Loading(ThrobberState::default()),
+                        show_closed_accounts: state.budget_settings.show_closed_accounts,
+                        currency_format: state.currency_format(),
+                        favorite_ids: state.budget_settings.favorite_account_ids.clone(),
                         ..Default::default()
                     }));
                 }
@@ -150,6 +199,21 @@ pub fn execute_command(
         } => {
             // Update current account ID
             state.current_account_id = Some(account_id.clone());
+            if state.budget_settings.default_account_id.as_ref() != Some(&account_id) {
+                state.budget_settings.default_account_id = Some(account_id.clone());
+                save_budget_settings(state, task_manager, data_loader);
+            }
+
+            // For running balances when only recent history is loaded
+            let account = state.history.iter().rev().find_map(|screen| match screen {
+                Screen::Accounts(accounts_state) => accounts_state
+                    .accounts
+                    .iter()
+                    .find(|a| a.id.to_string() == account_id),
+                _ => None,
+            });
+            let account_balance = account.map(|a| i64::from(a.balance));
+            let account_cleared_balance = account.map(|a| i64::from(a.cleared_balance));
 
             // Check if we're already on Transactions screen (refresh) or navigating to it (new)
             match state.current_screen_mut() {
@@ -158,17 +222,40 @@ pub fn execute_command(
                     tracing::debug!("Refreshing transactions screen");
                     transactions_state.transactions_loading =
                         LoadingState::Loading(ThrobberState::default());
+                    if account_balance.is_some() {
+                        transactions_state.account_balance = account_balance;
+                        transactions_state.account_cleared_balance = account_cleared_balance;
+                    }
                 }
                 _ => {
                     // Navigate to transactions screen
                     tracing::debug!("Navigating to transactions screen");
                     state.navigate_to(Screen::Transactions(Box::new(TransactionsState {
                         transactions_loading: LoadingState::Loading(ThrobberState::default()),
+                        show_reconciled_transactions: state
+                            .budget_settings
+                            .show_reconciled_transactions,
+                        sort: state.budget_settings.transaction_sort,
+                        account_balance,
+                        account_cleared_balance,
+                        currency_format: state.currency_format(),
                         ..Default::default()
                     })));
                 }
             }
 
+            // Needed to mark transactions awaiting reimbursement
+            {
+                let data_loader = data_loader.clone();
+                let budget_id_clone = budget_id.clone();
+                task_manager
+                    .spawn_load_task(format!("load_reimbursements_{}", budget_id), async move {
+                        data_loader.load_reimbursements(budget_id_clone).await
+                    });
+            }
+            load_category_balances(&budget_id, task_manager, data_loader);
+            load_category_suggestions(&budget_id, task_manager, data_loader);
+
             // Spawn background task to load transactions
             let data_loader = data_loader.clone();
             let budget_id_clone = budget_id.clone();
@@ -185,6 +272,111 @@ pub fn execute_command(
             );
         }
 
+        AppCommand::NavigateToTransactionSearch { budget_id } => {
+            // Results span every account, so there's no current account
+            state.current_account_id = None;
+            state.navigate_to(Screen::Transactions(Box::new(TransactionsState {
+                all_accounts: true,
+                input_mode: InputMode::Filter,
+                show_reconciled_transactions: state.budget_settings.show_reconciled_transactions,
+                sort: state.budget_settings.transaction_sort,
+                currency_format: state.currency_format(),
+                ..Default::default()
+            })));
+            execute_command(
+                AppCommand::LoadBudgetTransactions { budget_id },
+                state,
+                task_manager,
+                data_loader,
+            );
+        }
+
+        AppCommand::LoadBudgetTransactions { budget_id } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.transactions_loading =
+                    LoadingState::Loading(ThrobberState::default());
+            }
+
+            // Needed to mark transactions awaiting reimbursement
+            {
+                let data_loader = data_loader.clone();
+                let budget_id_clone = budget_id.clone();
+                task_manager
+                    .spawn_load_task(format!("load_reimbursements_{}", budget_id), async move {
+                        data_loader.load_reimbursements(budget_id_clone).await
+                    });
+            }
+            load_category_balances(&budget_id, task_manager, data_loader);
+            load_category_suggestions(&budget_id, task_manager, data_loader);
+
+            let data_loader = data_loader.clone();
+            let budget_id_clone = budget_id.clone();
+            task_manager.spawn_load_task(
+                format!("load_budget_transactions_{}", budget_id),
+                async move { data_loader.load_budget_transactions(budget_id_clone).await },
+            );
+        }
+
+        AppCommand::NavigateToInbox { budget_id } => {
+            // The inbox spans every account, so there's no current account
+            state.current_account_id = None;
+            state.navigate_to(Screen::Transactions(Box::new(TransactionsState {
+                all_accounts: true,
+                inbox: true,
+                show_reconciled_transactions: true,
+                sort: state.budget_settings.transaction_sort,
+                currency_format: state.currency_format(),
+                ..Default::default()
+            })));
+            execute_command(
+                AppCommand::LoadInbox { budget_id },
+                state,
+                task_manager,
+                data_loader,
+            );
+        }
+
+        AppCommand::LoadInbox { budget_id } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.transactions_loading =
+                    LoadingState::Loading(ThrobberState::default());
+            }
+            load_category_balances(&budget_id, task_manager, data_loader);
+            load_category_suggestions(&budget_id, task_manager, data_loader);
+
+            let data_loader = data_loader.clone();
+            let budget_id_clone = budget_id.clone();
+            task_manager.spawn_load_task(format!("load_inbox_{}", budget_id), async move {
+                data_loader.load_inbox(budget_id_clone).await
+            });
+        }
+
+        AppCommand::LoadOlderTransactions {
+            budget_id,
+            account_id,
+        } => {
+            let Screen::Transactions(transactions_state) = state.current_screen_mut() else {
+                return;
+            };
+            let Some(before) = transactions_state.loaded_since else {
+                return;
+            };
+            transactions_state.transactions_loading =
+                LoadingState::Loading(ThrobberState::default());
+
+            let data_loader = data_loader.clone();
+            let budget_id_clone = budget_id.clone();
+            let account_id_clone = account_id.clone();
+            task_manager.spawn_load_task(
+                format!("load_older_transactions_{}_{}", budget_id, account_id),
+                async move {
+                    data_loader
+                        .load_older_transactions(budget_id_clone, account_id_clone, before)
+                        .await
+                },
+            );
+        }
+
         AppCommand::LoadPlan {
             budget_id,
             force_refresh,
@@ -199,10 +391,12 @@ pub fn execute_command(
                 _ => {
                     // Navigate to plan screen
                     tracing::debug!("Navigating to plan screen");
-                    state.navigate_to(Screen::Plan(PlanState {
+                    state.navigate_to(Screen::Plan(Box::new(PlanState {
                         plan_loading: LoadingState::Loading(ThrobberState::default()),
+                        focused_view: state.budget_settings.plan_view,
+                        favorite_ids: state.budget_settings.favorite_category_ids.clone(),
                         ..Default::default()
-                    }));
+                    })));
                 }
             }
 
@@ -216,6 +410,65 @@ pub fn execute_command(
             task_manager.spawn_load_task(format!("load_plan_{}", budget_id), future);
         }
 
+        AppCommand::RefreshAll { budget_id } => {
+            // The current screen's indicator tracks the whole batch
+            let mut plan_month = None;
+            match state.current_screen_mut() {
+                Screen::Accounts(accounts_state) => {
+                    accounts_state.accounts_loading =
+                        LoadingState::Loading(ThrobberState::default());
+                }
+                Screen::Plan(plan_state) => {
+                    plan_state.plan_loading = LoadingState::Loading(ThrobberState::default());
+                    plan_month = plan_state.month.as_ref().map(|m| m.month.clone());
+                }
+                _ => {}
+            }
+
+            let data_loader = data_loader.clone();
+            let budget_id_clone = budget_id.clone();
+            let future = async move {
+                data_loader.refresh_all(budget_id_clone, plan_month).await;
+            };
+
+            task_manager.spawn_load_task(format!("refresh_all_{}", budget_id), future);
+        }
+
+        AppCommand::PreviewYnabExport { budget_id } => {
+            let dir = state.config.import.directory();
+            let budget_name = state.current_budget.as_ref().map(|b| b.name.clone());
+            let data_loader = data_loader.clone();
+            let budget_id_clone = budget_id.clone();
+            let future = async move {
+                data_loader
+                    .preview_ynab_export(budget_id_clone, &dir, budget_name)
+                    .await;
+            };
+
+            task_manager.spawn_load_task(format!("preview_ynab_export_{}", budget_id), future);
+        }
+
+        AppCommand::ImportYnabExport { budget_id, export } => {
+            state.import_preview = None;
+            if let Screen::Accounts(accounts_state) = state.current_screen_mut() {
+                accounts_state.accounts_loading = LoadingState::Loading(ThrobberState::default());
+            }
+
+            let data_loader = data_loader.clone();
+            let budget_id_clone = budget_id.clone();
+            let future = async move {
+                data_loader
+                    .import_ynab_export(budget_id_clone, export)
+                    .await;
+            };
+
+            task_manager.spawn_load_task(format!("import_ynab_export_{}", budget_id), future);
+        }
+
+        AppCommand::CancelYnabExportImport => {
+            state.import_preview = None;
+        }
+
         AppCommand::LoadPlanMonth { budget_id, month } => {
             // Set loading state on existing Plan screen
             if let Screen::Plan(plan_state) = state.current_screen_mut() {
@@ -258,88 +511,70 @@ pub fn execute_command(
             }
         }
 
+        AppCommand::TogglePlanComparison => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if plan_state.comparison.take().is_none() {
+                    let month = plan_state
+                        .month
+                        .as_ref()
+                        .and_then(|month| compute_adjacent_month(&month.month, false));
+                    if let Some(month) = month {
+                        compare_plan_month(state, task_manager, data_loader, month);
+                    }
+                }
+            }
+        }
+
+        AppCommand::ShiftPlanComparison { forward } => {
+            if let Screen::Plan(plan_state) = state.current_screen() {
+                // Only earlier months are compared against
+                let shown = plan_state.month.as_ref().map(|month| month.month.as_str());
+                let month = plan_state
+                    .comparison
+                    .as_ref()
+                    .and_then(|comparison| compute_adjacent_month(&comparison.month, forward))
+                    .filter(|month| shown.is_some_and(|shown| month.as_str() < shown));
+                if let Some(month) = month {
+                    compare_plan_month(state, task_manager, data_loader, month);
+                }
+            }
+        }
+
         AppCommand::ToggleTransactionCleared {
             transaction_id,
             budget_id,
         } => {
-            // Optimistic update: toggle cleared status locally
-            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
-                if let Some(transaction) = transactions_state
+            let current_status = match state.current_screen() {
+                Screen::Transactions(transactions_state) => transactions_state
                     .transactions
-                    .iter_mut()
+                    .iter()
                     .find(|t| t.id.to_string() == transaction_id)
-                {
-                    // Save original status for rollback if needed
-                    let original_status = transaction.cleared;
-                    let original_approved = transaction.approved;
-
-                    // Toggle: cleared <-> uncleared (never touch reconciled)
-                    let new_status = match transaction.cleared {
-                        ReconciliationStatus::Cleared => ReconciliationStatus::Uncleared,
-                        ReconciliationStatus::Uncleared => ReconciliationStatus::Cleared,
-                        ReconciliationStatus::Reconciled => {
-                            unreachable!("Should never be able to toggle reconciled transactions")
-                        }
-                    };
-
-                    // Apply optimistic update
-                    transaction.cleared = new_status;
-                    transaction.approved = true;
-
-                    tracing::info!(
-                        "Optimistically toggled transaction {} from {} to {}",
-                        transaction_id,
-                        original_status,
-                        new_status.clone()
-                    );
-
-                    // Spawn background task to update via API
-                    let api_client = data_loader.api_client.clone();
-                    let data_tx = data_loader.data_tx.clone();
-                    let transaction_id_clone = transaction_id.clone();
-                    let budget_id_clone = budget_id.clone();
-                    let new_status_clone = new_status;
-
-                    let future = async move {
-                        let budget_id_api: BudgetId = budget_id_clone.clone().into();
-                        let transaction_id: TransactionId = transaction_id_clone
-                            .parse()
-                            .expect("invalid transaction id");
-                        let req = Request::transactions()
-                            .with_budget(budget_id_api)
-                            .update(transaction_id)
-                            .cleared(new_status_clone)
-                            .approved(true);
-
-                        match api_client.send(req).await {
-                            Ok(_) => {
-                                tracing::info!(
-                                    "Transaction {} updated successfully on server",
-                                    transaction_id_clone
-                                );
-                                let _ = data_tx.send(DataEvent::TransactionUpdated {
-                                    transaction_id: transaction_id_clone,
-                                });
-                            }
-                            Err(e) => {
-                                tracing::error!(
-                                    "Failed to update transaction {}: {}",
-                                    transaction_id_clone,
-                                    e
-                                );
-                                let _ = data_tx.send(DataEvent::TransactionUpdateFailed {
-                                    transaction_id: transaction_id_clone,
-                                    original_status,
-                                    original_approved,
-                                    error: e.to_string(),
-                                });
-                            }
-                        }
-                    };
-
-                    task_manager
-                        .spawn_load_task(format!("update_transaction_{}", transaction_id), future);
-                }
+                    .map(|t| t.cleared),
+                _ => None,
+            };
+            if let Some(from) = current_status {
+                // Toggle: cleared <-> uncleared (never touch reconciled)
+                let to = match from {
+                    ReconciliationStatus::Cleared => ReconciliationStatus::Uncleared,
+                    ReconciliationStatus::Uncleared => ReconciliationStatus::Cleared,
+                    ReconciliationStatus::Reconciled => {
+                        unreachable!("Should never be able to toggle reconciled transactions")
+                    }
+                };
+                set_transaction_cleared(
+                    state,
+                    task_manager,
+                    data_loader,
+                    &budget_id,
+                    &transaction_id,
+                    to,
+                );
+                state.undo_history.record(UndoEntry::ClearedToggled {
+                    budget_id,
+                    transaction_id,
+                    from,
+                    to,
+                });
             }
         }
 
@@ -425,9 +660,28 @@ pub fn execute_command(
                 accounts_state.show_closed_accounts = !accounts_state.show_closed_accounts;
                 // Reset table selection when toggling view
                 accounts_state.table_state = RefCell::new(TableState::default().with_selected(0));
+                let show_closed_accounts = accounts_state.show_closed_accounts;
+                state.budget_settings.show_closed_accounts = show_closed_accounts;
+                save_budget_settings(state, task_manager, data_loader);
             }
         }
 
+        AppCommand::CycleAccountTypeFilter
+        | AppCommand::EnterAccountJump
+        | AppCommand::AppendAccountJumpChar(_)
+        | AppCommand::DeleteAccountJumpChar
+        | AppCommand::ExitAccountJump => account_jump_or_filter(state, command),
+
+        AppCommand::CycleReconcileReminder { account_id } => {
+            cycle_reconcile_reminder(state, &account_id);
+            save_budget_settings(state, task_manager, data_loader);
+        }
+
+        AppCommand::ToggleFavoriteAccount { account_id } => {
+            toggle_favorite_account(state, &account_id);
+            save_budget_settings(state, task_manager, data_loader);
+        }
+
         AppCommand::ToggleShowReconciledTransactions => {
             if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
                 transactions_state.show_reconciled_transactions =
@@ -435,6 +689,52 @@ pub fn execute_command(
                 // Reset table selection when toggling view
                 transactions_state.table_state =
                     RefCell::new(TableState::default().with_selected(0));
+                let show_reconciled = transactions_state.show_reconciled_transactions;
+                state.budget_settings.show_reconciled_transactions = show_reconciled;
+                save_budget_settings(state, task_manager, data_loader);
+            }
+        }
+
+        AppCommand::CycleTransactionSort | AppCommand::ReverseTransactionSort => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.sort = match command {
+                    AppCommand::ReverseTransactionSort => transactions_state.sort.reversed(),
+                    _ => transactions_state.sort.next_column(),
+                };
+                transactions_state.table_state =
+                    RefCell::new(TableState::default().with_selected(0));
+                state.budget_settings.transaction_sort = transactions_state.sort;
+                save_budget_settings(state, task_manager, data_loader);
+            }
+        }
+
+        AppCommand::ToggleTransactionPreset(preset) => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.toggle_preset(preset);
+                transactions_state.table_state =
+                    RefCell::new(TableState::default().with_selected(0));
+            }
+        }
+
+        AppCommand::CycleFlagView => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.flag_view = next_flag_color(transactions_state.flag_view);
+                transactions_state.table_state =
+                    RefCell::new(TableState::default().with_selected(0));
+            }
+        }
+
+        AppCommand::ResolveDeltaConflict { keep_mine } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.resolve_conflict(keep_mine);
+            }
+        }
+
+        AppCommand::ToggleConflictDiff => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                if let Some(conflict) = transactions_state.conflicts.first_mut() {
+                    conflict.show_diff = !conflict.show_diff;
+                }
             }
         }
 
@@ -443,83 +743,76 @@ pub fn execute_command(
                 plan_state.focused_view = plan_state.focused_view.next();
                 // Reset table selection when toggling view
                 plan_state.table_state = RefCell::new(TableState::default().with_selected(0));
+                let plan_view = plan_state.focused_view;
+                state.budget_settings.plan_view = plan_view;
+                save_budget_settings(state, task_manager, data_loader);
+            }
+        }
+
+        AppCommand::TogglePlanCategoryDetails => {
+            state.budget_settings.plan_category_details =
+                !state.budget_settings.plan_category_details;
+            save_budget_settings(state, task_manager, data_loader);
+        }
+
+        AppCommand::TogglePlanGoalDetails => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.goal_details = !plan_state.goal_details;
+            }
+        }
+
+        AppCommand::TogglePlanGroup { group_id } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.toggle_group(&group_id);
             }
         }
 
+        AppCommand::ToggleFavoriteCategory { category_id } => {
+            toggle_favorite_category(state, &category_id);
+            save_budget_settings(state, task_manager, data_loader);
+        }
+
         AppCommand::ToggleHelp => {
             state.help_visible = !state.help_visible;
         }
 
+        AppCommand::OpenContextMenu => {
+            state.context_menu = super::context_menu::build(state);
+        }
+
+        AppCommand::CloseContextMenu => {
+            state.context_menu = None;
+        }
+
+        AppCommand::MoveContextMenuCursor { down } => {
+            if let Some(ref mut menu) = state.context_menu {
+                menu.move_cursor(down);
+            }
+        }
+
+        AppCommand::RunContextMenuItem => {
+            if let Some(command) = state
+                .context_menu
+                .take()
+                .and_then(|menu| menu.selected_command().cloned())
+            {
+                execute_command(command, state, task_manager, data_loader);
+            }
+        }
+
+        AppCommand::CopyToClipboard(text) => match utils::clipboard::copy(&text) {
+            Ok(()) => tracing::info!("Copied {} to clipboard", text),
+            Err(e) => tracing::error!("Failed to copy to clipboard: {}", e),
+        },
+
         AppCommand::NavigateToTop => {
             // Navigate to the first item in the current screen's list
-            match state.current_screen_mut() {
-                Screen::Budgets(budgets_state) => {
-                    if !budgets_state.budgets.is_empty() {
-                        budgets_state.selected_budget_index = 0;
-                    }
-                }
-                Screen::Accounts(accounts_state) => {
-                    let num_items = accounts_state.filtered_accounts().len();
-                    if num_items > 0 {
-                        accounts_state.table_state =
-                            RefCell::new(TableState::default().with_selected(0));
-                    }
-                }
-                Screen::Transactions(transactions_state) => {
-                    let num_items = transactions_state.filtered_transactions().len();
-                    if num_items > 0 {
-                        transactions_state.table_state =
-                            RefCell::new(TableState::default().with_selected(0));
-                    }
-                }
-                Screen::Plan(plan_state) => {
-                    let num_items = plan_state.filtered_categories().len();
-                    if num_items > 0 {
-                        plan_state.table_state =
-                            RefCell::new(TableState::default().with_selected(0));
-                    }
-                }
-                Screen::Logs(logs_state) => {
-                    // Scroll to oldest logs (top)
-                    logs_state.scroll_offset = logs_state.total_entries.saturating_sub(1);
-                }
-            }
+            state.current_screen_mut().controller_mut().select_first();
         }
 
         AppCommand::NavigateToBottom => {
             // Navigate to the last item in the current screen's list
-            match state.current_screen_mut() {
-                Screen::Budgets(budgets_state) => {
-                    if !budgets_state.budgets.is_empty() {
-                        budgets_state.selected_budget_index = budgets_state.budgets.len() - 1;
-                    }
-                }
-                Screen::Accounts(accounts_state) => {
-                    let num_items = accounts_state.filtered_accounts().len();
-                    if num_items > 0 {
-                        accounts_state.table_state =
-                            RefCell::new(TableState::default().with_selected(num_items - 1));
-                    }
-                }
-                Screen::Transactions(transactions_state) => {
-                    let num_items = transactions_state.filtered_transactions().len();
-                    if num_items > 0 {
-                        transactions_state.table_state =
-                            RefCell::new(TableState::default().with_selected(num_items - 1));
-                    }
-                }
-                Screen::Plan(plan_state) => {
-                    let num_items = plan_state.filtered_categories().len();
-                    if num_items > 0 {
-                        plan_state.table_state =
-                            RefCell::new(TableState::default().with_selected(num_items - 1));
-                    }
-                }
-                Screen::Logs(logs_state) => {
-                    // Scroll to newest logs (bottom)
-                    logs_state.scroll_offset = 0;
-                }
-            }
+            state.current_screen_mut().controller_mut().select_last();
         }
 
         AppCommand::SetPendingKey(c) => {
@@ -530,16 +823,24 @@ pub fn execute_command(
             state.pending_key = None;
         }
 
+        AppCommand::SetPendingCount(count) => {
+            state.pending_count = Some(count);
+        }
+
         // Transaction creation form commands
         AppCommand::EnterTransactionCreateMode => {
             // Get IDs and date format before mutable borrow
             let account_id_opt = state.current_account_id.clone();
             let budget_id_opt = state.current_budget_id.clone();
-            let date_format = state
-                .current_budget
+            let rounding = state.config.amounts.rounding;
+            let currency_format = state.currency_format();
+            let last_used = account_id_opt
                 .as_ref()
-                .and_then(|b| b.date_format.as_ref())
-                .map(|d| d.format.clone())
+                .and_then(|id| state.last_used_entries.get(id))
+                .cloned();
+            let date_format = state
+                .date_format()
+                .map(|d| d.format)
                 .unwrap_or_else(|| "YYYY-MM-DD".to_string());
 
             if let Screen::Transactions(trans_state) = state.current_screen_mut() {
@@ -547,8 +848,12 @@ pub fn execute_command(
                 if let Some(account_id) = account_id_opt {
                     trans_state.table_state.borrow_mut().select_first();
                     trans_state.input_mode = InputMode::TransactionForm;
-                    trans_state.form_state =
-                        Some(TransactionFormState::new(account_id, &date_format));
+                    trans_state.form_state = Some(TransactionFormState {
+                        rounding,
+                        currency_format,
+                        last_used,
+                        ..TransactionFormState::new(account_id, &date_format)
+                    });
 
                     // Load payees and categories if not already loaded
                     if let Some(budget_id) = budget_id_opt {
@@ -581,13 +886,25 @@ pub fn execute_command(
         }
 
         AppCommand::NavigateFormField { forward } => {
+            let foreign = pending_foreign_amount(state);
             if let Screen::Transactions(trans_state) = state.current_screen_mut() {
                 if let Some(ref mut form) = trans_state.form_state {
                     use FormField::*;
 
+                    // A foreign amount is converted on the way out, once there's a rate
+                    if form.current_field == Some(Amount) {
+                        if let Some((amount, rate)) = foreign {
+                            if !apply_foreign_amount(form, amount, rate) {
+                                return;
+                            }
+                        }
+                    }
+
                     // If leaving the Amount field, evaluate any math expression
                     if form.current_field == Some(Amount) && !form.amount.is_empty() {
-                        if let Some(result) = utils::math::evaluate_expression(&form.amount) {
+                        if let Some(result) =
+                            utils::math::evaluate_expression_rounded(&form.amount, form.rounding)
+                        {
                             form.amount = result;
                         }
                     }
@@ -600,9 +917,10 @@ pub fn execute_command(
                             if form.subtransaction_field == SubTransactionField::Amount {
                                 let sub_amount = &form.subtransactions[sub_idx].amount;
                                 if !sub_amount.is_empty() {
-                                    if let Some(result) =
-                                        utils::math::evaluate_expression(sub_amount)
-                                    {
+                                    if let Some(result) = utils::math::evaluate_expression_rounded(
+                                        sub_amount,
+                                        form.rounding,
+                                    ) {
                                         form.subtransactions[sub_idx].amount = result;
                                     }
                                 }
@@ -707,14 +1025,20 @@ pub fn execute_command(
         AppCommand::AppendFormFieldChar { c } => {
             // Get date format before mutable borrow
             let date_format = state
-                .current_budget
-                .as_ref()
-                .and_then(|b| b.date_format.as_ref())
-                .map(|d| d.format.clone())
+                .date_format()
+                .map(|d| d.format)
                 .unwrap_or_else(|| "YYYY-MM-DD".to_string());
 
             if let Screen::Transactions(trans_state) = state.current_screen_mut() {
                 if let Some(ref mut form) = trans_state.form_state {
+                    if let Some(ref mut prompt) = form.rate_prompt {
+                        if c.is_ascii_digit() || c == '.' {
+                            prompt.rate.push(c);
+                        }
+                        form.validation_error = None;
+                        return;
+                    }
+
                     // Handle subtransaction input if active
                     if let Some(sub_idx) = form.active_subtransaction_index {
                         if let Some(sub) = form.subtransactions.get_mut(sub_idx) {
@@ -761,7 +1085,9 @@ pub fn execute_command(
                             }
                         }
                         Some(FormField::Amount) => {
-                            // Allow digits, decimal point, and math operators
+                            // Allow digits, decimal point, and math operators, and
+                            // a currency code after "=" for a foreign amount
+                            let foreign = form.amount.starts_with('=');
                             if c.is_ascii_digit()
                                 || c == '.'
                                 || c == '-'
@@ -770,8 +1096,10 @@ pub fn execute_command(
                                 || c == '/'
                                 || c == '('
                                 || c == ')'
+                                || (c == '=' && form.amount.is_empty())
+                                || (foreign && (c == ' ' || c.is_ascii_alphabetic()))
                             {
-                                form.amount.push(c);
+                                form.amount.push(c.to_ascii_uppercase());
                             }
                         }
                         Some(FormField::Payee) => {
@@ -798,16 +1126,7 @@ pub fn execute_command(
                         }
                         Some(FormField::Memo) => form.memo.push(c),
                         Some(FormField::FlagColor) => {
-                            use FlagColor::*;
-                            form.flag_color = match form.flag_color {
-                                None => Some(Red),
-                                Some(Red) => Some(Orange),
-                                Some(Orange) => Some(Yellow),
-                                Some(Yellow) => Some(Green),
-                                Some(Green) => Some(Blue),
-                                Some(Blue) => Some(Purple),
-                                Some(Purple) => None,
-                            }
+                            form.flag_color = next_flag_color(form.flag_color);
                         }
                         Some(FormField::Cleared) => {
                             // Cycle through cleared options: uncleared -> cleared -> reconciled
@@ -832,6 +1151,11 @@ pub fn execute_command(
         AppCommand::DeleteFormFieldChar => {
             if let Screen::Transactions(trans_state) = state.current_screen_mut() {
                 if let Some(ref mut form) = trans_state.form_state {
+                    if let Some(ref mut prompt) = form.rate_prompt {
+                        prompt.rate.pop();
+                        return;
+                    }
+
                     // Handle subtransaction input if active
                     if let Some(sub_idx) = form.active_subtransaction_index {
                         if let Some(sub) = form.subtransactions.get_mut(sub_idx) {
@@ -943,6 +1267,81 @@ pub fn execute_command(
             }
         }
 
+        AppCommand::FillFormFromLastUsed => {
+            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = trans_state.form_state {
+                    if let Some(last) = form.last_used.clone() {
+                        form.payee = last.payee;
+                        form.filtered_payees =
+                            autocomplete::filter_payees(&trans_state.payees, &form.payee);
+                        form.payee_selection_index = 0;
+                        // Leave a split's categories alone
+                        if !form.is_split_mode {
+                            form.category = last.category;
+                            form.filtered_categories = autocomplete::filter_categories(
+                                &trans_state.categories,
+                                &form.category,
+                            );
+                            form.category_selection_index = 0;
+                        }
+                        form.cleared = last.cleared;
+                        form.validation_error = None;
+                    }
+                }
+            }
+        }
+
+        AppCommand::ConfirmExchangeRate => {
+            let mut entered = None;
+            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = trans_state.form_state {
+                    if let Some(prompt) = form.rate_prompt.take() {
+                        match currency::parse_rate(&prompt.rate) {
+                            Some(rate) => {
+                                convert_form_amount(form, &prompt.amount, rate);
+                                entered = Some((prompt.amount.currency, rate));
+                            }
+                            None => {
+                                form.validation_error =
+                                    Some(AppError::validation(t("form-rate-invalid")));
+                                form.rate_prompt = Some(prompt);
+                            }
+                        }
+                    }
+                }
+            }
+            // Later amounts in the same currency use it without asking
+            if let Some((currency, rate)) = entered {
+                state.entered_rates.insert(currency, rate);
+            }
+        }
+
+        AppCommand::CancelExchangeRate => {
+            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = trans_state.form_state {
+                    form.rate_prompt = None;
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::ToggleAmountSign => {
+            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = trans_state.form_state {
+                    let amount = match form.active_subtransaction_index {
+                        Some(sub_idx) => {
+                            form.subtransactions.get_mut(sub_idx).map(|s| &mut s.amount)
+                        }
+                        None => Some(&mut form.amount),
+                    };
+                    if let Some(amount) = amount {
+                        *amount = utils::math::negate_expression(amount);
+                    }
+                    form.validation_error = None;
+                }
+            }
+        }
+
         AppCommand::SelectAutocompleteItem { up } => {
             if let Screen::Transactions(trans_state) = state.current_screen_mut() {
                 if let Some(ref mut form) = trans_state.form_state {
@@ -1058,14 +1457,31 @@ pub fn execute_command(
             // Get budget ID and date format before mutable borrow
             let budget_id_opt = state.current_budget_id.clone();
             let date_format = state
-                .current_budget
-                .as_ref()
-                .and_then(|b| b.date_format.as_ref())
-                .map(|d| d.format.clone())
+                .date_format()
+                .map(|d| d.format)
                 .unwrap_or_else(|| "YYYY-MM-DD".to_string());
+            let lint_config = state.config.lint.clone();
+            let approve = state.config.approval.on_save;
+            let foreign = pending_foreign_amount(state);
 
             if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                if let (Some(form), Some((amount, rate))) = (&mut trans_state.form_state, foreign) {
+                    if !apply_foreign_amount(form, amount, rate) {
+                        return;
+                    }
+                }
                 if let Some(ref form) = trans_state.form_state {
+                    let warnings = lint::check(form, &trans_state.payees, &lint_config);
+                    if !warnings.is_empty() && warnings != form.acknowledged_warnings {
+                        if let Some(ref mut form_mut) = trans_state.form_state {
+                            form_mut.validation_error = Some(AppError::validation(
+                                t("lint-save-anyway").replace("{warnings}", &warnings.join("; ")),
+                            ));
+                            form_mut.acknowledged_warnings = warnings;
+                        }
+                        return;
+                    }
+
                     // Check if editing or creating
                     if let Some(ref transaction_id) = form.editing_transaction_id {
                         // EDIT MODE - Build update request
@@ -1075,7 +1491,11 @@ pub fn execute_command(
                             &trans_state.categories,
                             &date_format,
                         ) {
-                            Ok(update_request) => {
+                            Ok(mut update_request) => {
+                                if !approve {
+                                    // Leave the approval as it was
+                                    update_request.approved = None;
+                                }
                                 if let Some(budget_id) = budget_id_opt {
                                     let data_loader = data_loader.clone();
                                     let transaction_id_clone = transaction_id.clone();
@@ -1095,7 +1515,7 @@ pub fn execute_command(
                             Err(error) => {
                                 // Set validation error
                                 if let Some(ref mut form_mut) = trans_state.form_state {
-                                    form_mut.validation_error = Some(error);
+                                    form_mut.validation_error = Some(AppError::validation(error));
                                 }
                             }
                         }
@@ -1107,7 +1527,8 @@ pub fn execute_command(
                             &trans_state.categories,
                             &date_format,
                         ) {
-                            Ok(new_transaction) => {
+                            Ok(mut new_transaction) => {
+                                new_transaction.approved = Some(approve);
                                 // Spawn background task to create transaction if we have a budget ID
                                 if let Some(budget_id) = budget_id_opt {
                                     let data_loader = data_loader.clone();
@@ -1123,7 +1544,7 @@ pub fn execute_command(
                             Err(error) => {
                                 // Set validation error in form
                                 if let Some(ref mut form_mut) = trans_state.form_state {
-                                    form_mut.validation_error = Some(error);
+                                    form_mut.validation_error = Some(AppError::validation(error));
                                 }
                             }
                         }
@@ -1148,6 +1569,240 @@ pub fn execute_command(
             task_manager.spawn_load_task("load_categories".to_string(), future);
         }
 
+        AppCommand::CycleTransactionFlag {
+            budget_id,
+            transaction_id,
+        } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                if let Some(transaction) = transactions_state
+                    .transactions
+                    .iter_mut()
+                    .find(|t| t.id.to_string() == transaction_id)
+                {
+                    // Optimistic update; rolled back if the API call fails
+                    let original_flag = transaction.flag_color;
+                    let new_flag = next_flag_color(original_flag);
+                    transaction.flag_color = new_flag;
+                    transactions_state.unsaved.insert(transaction_id.clone());
+
+                    let loader = data_loader.clone();
+                    let data_tx = data_loader.data_tx.clone();
+                    let transaction_id_clone = transaction_id.clone();
+                    let future = async move {
+                        let budget_id_api: BudgetId = budget_id.into();
+                        let transaction_id: TransactionId = transaction_id_clone
+                            .parse()
+                            .expect("invalid transaction id");
+                        // An unset flag is sent as null, which clears it
+                        let mut req = Request::transactions()
+                            .with_budget(budget_id_api)
+                            .update(transaction_id);
+                        if let Some(flag) = new_flag {
+                            req = req.flag_color(flag);
+                        }
+
+                        match loader.send(req).await {
+                            Ok(_) => {
+                                let _ = data_tx.send(
+                                    TransactionEvent::TransactionUpdated {
+                                        transaction_id: transaction_id_clone,
+                                    }
+                                    .into(),
+                                );
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    "Failed to flag transaction {}: {}",
+                                    transaction_id_clone,
+                                    e
+                                );
+                                let _ = data_tx.send(
+                                    TransactionEvent::TransactionFlagUpdateFailed {
+                                        transaction_id: transaction_id_clone,
+                                        original_flag,
+                                        error: e.into(),
+                                    }
+                                    .into(),
+                                );
+                            }
+                        }
+                    };
+
+                    task_manager
+                        .spawn_load_task(format!("flag_transaction_{}", transaction_id), future);
+                }
+            }
+        }
+
+        AppCommand::YankTransaction { transaction_id } => {
+            yank_transaction(state, &transaction_id);
+        }
+
+        AppCommand::ExportTransactions => {
+            let Screen::Transactions(trans_state) = state.current_screen() else {
+                return;
+            };
+            let contents = export::csv::transactions_csv(
+                &trans_state.filtered_transactions(),
+                state.current_budget.as_ref(),
+            );
+            let file_name = chrono::Local::now()
+                .format("transactions-%Y%m%d-%H%M%S.csv")
+                .to_string();
+            let config = state.config.export.clone();
+            let path = config.directory.clone().unwrap_or_default().join(file_name);
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .write_transactions_export(path, contents, config)
+                    .await;
+            };
+            task_manager.spawn_load_task("export_transactions".to_string(), future);
+        }
+
+        AppCommand::DismissExportConfirmation => {
+            state.export_path = None;
+        }
+
+        AppCommand::CreateFollowUp { transaction_id } => {
+            let Screen::Transactions(trans_state) = state.current_screen() else {
+                return;
+            };
+            let Some(transaction) = trans_state
+                .transactions
+                .iter()
+                .find(|t| t.id.to_string() == transaction_id)
+            else {
+                return;
+            };
+            let args = follow_up::render_args(
+                &state.config.follow_up.command,
+                transaction,
+                state.current_budget.as_ref(),
+            );
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.run_follow_up(args).await;
+            };
+            task_manager.spawn_load_task(format!("follow_up_{}", transaction_id), future);
+        }
+
+        AppCommand::ShowTransactionDetail {
+            budget_id,
+            transaction_id,
+        } => {
+            let Some(payee_id) = open_transaction_detail(state, &transaction_id) else {
+                return;
+            };
+            let geocoder = state.config.maps.geocoder.clone();
+            let data_loader = data_loader.clone();
+            let task_id = format!("payee_location_{}", payee_id);
+            let future = async move {
+                data_loader
+                    .load_payee_location(budget_id, payee_id, geocoder)
+                    .await;
+            };
+            task_manager.spawn_load_task(task_id, future);
+        }
+
+        AppCommand::CloseTransactionDetail => {
+            state.transaction_detail = None;
+        }
+
+        AppCommand::OpenPayeeLocation { place } => {
+            let command = state.config.maps.command.clone();
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.open_payee_location(place, command).await;
+            };
+            task_manager.spawn_load_task("open_payee_location".to_string(), future);
+        }
+
+        AppCommand::PasteTransaction {
+            budget_id,
+            account_id,
+            open_form,
+        } => {
+            if let Some(yanked) = state.yanked_transaction.clone() {
+                if open_form {
+                    // Reuse the create form (and its payee/category loading), pre-filled
+                    execute_command(
+                        AppCommand::EnterTransactionCreateMode,
+                        state,
+                        task_manager,
+                        data_loader,
+                    );
+                    let currency = state.currency_format();
+                    let date_format = state
+                        .date_format()
+                        .map(|d| d.format)
+                        .unwrap_or_else(|| "YYYY-MM-DD".to_string());
+                    if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                        if let Some(ref mut form) = trans_state.form_state {
+                            *form = TransactionFormState {
+                                rounding: form.rounding,
+                                ..TransactionFormState::duplicate_of(
+                                    &yanked,
+                                    account_id,
+                                    &date_format,
+                                    currency.as_ref(),
+                                )
+                            };
+                        }
+                    }
+                } else {
+                    match account_id.parse() {
+                        Ok(account_uuid) => {
+                            let today = chrono::Local::now().date_naive();
+                            let mut new_transaction = validators::build_duplicate_transaction(
+                                &yanked,
+                                account_uuid,
+                                today,
+                            );
+                            new_transaction.approved = Some(state.config.approval.on_save);
+                            let data_loader = data_loader.clone();
+                            let future = async move {
+                                data_loader
+                                    .create_transaction(budget_id, new_transaction)
+                                    .await;
+                            };
+                            task_manager.spawn_load_task("create_transaction".to_string(), future);
+                        }
+                        Err(e) => tracing::error!("Invalid account id {}: {}", account_id, e),
+                    }
+                }
+            }
+        }
+
+        AppCommand::JumpToTransfer {
+            budget_id,
+            account_id,
+            transaction_id,
+        } => {
+            // Always open a new screen so back returns to this account
+            let return_account_id = state.current_account_id.clone();
+            state.navigate_to(Screen::Transactions(Box::new(TransactionsState {
+                focus_transaction_id: transaction_id,
+                return_account_id,
+                show_reconciled_transactions: state.budget_settings.show_reconciled_transactions,
+                sort: state.budget_settings.transaction_sort,
+                currency_format: state.currency_format(),
+                ..Default::default()
+            })));
+            execute_command(
+                AppCommand::LoadTransactions {
+                    budget_id,
+                    account_id,
+                    force_refresh: false,
+                },
+                state,
+                task_manager,
+                data_loader,
+            );
+        }
+
         AppCommand::ApproveTransaction {
             budget_id,
             transaction_id,
@@ -1159,9 +1814,10 @@ pub fn execute_command(
                     .find(|t| t.id.to_string() == transaction_id)
                 {
                     transaction.approved = true;
+                    transactions_state.unsaved.insert(transaction_id.clone());
 
                     let transaction_id_clone = transaction_id.clone();
-                    let api_client = data_loader.api_client.clone();
+                    let loader = data_loader.clone();
                     let data_tx = data_loader.data_tx.clone();
                     let budget_id_clone = budget_id.clone();
                     let future = async move {
@@ -1174,15 +1830,18 @@ pub fn execute_command(
                             .update(transaction_id)
                             .approved(true);
 
-                        match api_client.send(req).await {
+                        match loader.send(req).await {
                             Ok(_) => {
                                 tracing::info!(
                                     "Transaction {} approved successfully on server",
                                     transaction_id_clone
                                 );
-                                let _ = data_tx.send(DataEvent::TransactionUpdated {
-                                    transaction_id: transaction_id_clone,
-                                });
+                                let _ = data_tx.send(
+                                    TransactionEvent::TransactionUpdated {
+                                        transaction_id: transaction_id_clone,
+                                    }
+                                    .into(),
+                                );
                             }
                             Err(e) => {
                                 tracing::error!(
@@ -1190,10 +1849,13 @@ pub fn execute_command(
                                     transaction_id_clone,
                                     e
                                 );
-                                let _ = data_tx.send(DataEvent::TransactionApproveFailed {
-                                    transaction_id: transaction_id_clone,
-                                    error: e.to_string(),
-                                });
+                                let _ = data_tx.send(
+                                    TransactionEvent::TransactionApproveFailed {
+                                        transaction_id: transaction_id_clone,
+                                        error: e.into(),
+                                    }
+                                    .into(),
+                                );
                             }
                         }
                     };
@@ -1206,80 +1868,191 @@ pub fn execute_command(
             }
         }
 
-        AppCommand::InitiateTransactionDelete { transaction_id } => {
-            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
-                transactions_state.input_mode = InputMode::DeleteConfirmation;
-                transactions_state.delete_confirmation_transaction_id = Some(transaction_id);
-            }
+        AppCommand::ApproveTransactions {
+            transaction_ids, ..
+        } if transaction_ids.is_empty() => {
+            state.notice = Some(t("notice-nothing-to-approve").to_string());
         }
 
-        AppCommand::ConfirmTransactionDelete {
-            transaction_id,
+        AppCommand::ApproveTransactions {
             budget_id,
+            transaction_ids,
         } => {
+            let mut account_ids = Vec::new();
             if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
-                // 1. Optimistically remove from local state
-                transactions_state
+                for transaction in transactions_state
                     .transactions
-                    .retain(|t| t.id.to_string() != transaction_id);
-
-                // 2. Clear confirmation state and return to normal mode
-                transactions_state.input_mode = InputMode::Normal;
-                transactions_state.delete_confirmation_transaction_id = None;
-
-                // 3. Reset table selection if needed
-                let num_transactions = transactions_state.filtered_transactions().len();
-                let mut table_state = transactions_state.table_state.borrow_mut();
-                if let Some(selected) = table_state.selected() {
-                    if selected >= num_transactions && num_transactions > 0 {
-                        table_state.select(Some(num_transactions - 1));
-                    } else if num_transactions == 0 {
-                        table_state.select(None);
+                    .iter_mut()
+                    .filter(|t| transaction_ids.contains(&t.id.to_string()))
+                {
+                    transaction.approved = true;
+                    let account_id = transaction.account_id.to_string();
+                    if !account_ids.contains(&account_id) {
+                        account_ids.push(account_id);
                     }
                 }
-                drop(table_state);
-
-                // 4. Spawn background task to call DELETE API
-                let api_client = data_loader.api_client.clone();
-                let data_tx = data_loader.data_tx.clone();
-                let transaction_id_clone = transaction_id.clone();
-                let budget_id_clone = budget_id.clone();
+                transactions_state
+                    .unsaved
+                    .extend(transaction_ids.iter().cloned());
+            }
 
-                let future = async move {
-                    let budget_id_api: BudgetId = budget_id_clone.into();
-                    let transaction_id: TransactionId = transaction_id_clone
-                        .parse()
-                        .expect("invalid transaction id");
-                    let req = Request::transactions()
-                        .with_budget(budget_id_api)
-                        .delete(transaction_id);
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .approve_transactions(budget_id, transaction_ids, account_ids)
+                    .await;
+            };
+            task_manager.spawn_load_task("approve_transactions".to_string(), future);
+        }
 
-                    match api_client.send(req).await {
-                        Ok(_) => {
-                            tracing::info!(
-                                "Successfully deleted transaction {}",
-                                transaction_id_clone
-                            );
-                            let _ = data_tx.send(DataEvent::TransactionDeleted {
-                                transaction_id: transaction_id_clone,
-                            });
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "Failed to delete transaction {}: {}",
-                                transaction_id_clone,
-                                e
-                            );
-                            let _ = data_tx.send(DataEvent::TransactionDeleteFailed {
-                                transaction_id: transaction_id_clone,
-                                error: e.to_string(),
-                            });
-                        }
-                    }
-                };
+        AppCommand::ClearTransactionFlags {
+            transaction_ids, ..
+        } if transaction_ids.is_empty() => {
+            state.notice = Some(t("notice-nothing-flagged").to_string());
+        }
 
-                task_manager
-                    .spawn_load_task(format!("delete_transaction_{}", transaction_id), future);
+        AppCommand::ClearTransactionFlags {
+            budget_id,
+            transaction_ids,
+        } => {
+            let Screen::Transactions(transactions_state) = state.current_screen_mut() else {
+                return;
+            };
+            // Shown straight away and put back if the update fails
+            let mut originals = Vec::new();
+            for transaction in transactions_state
+                .transactions
+                .iter_mut()
+                .filter(|t| transaction_ids.contains(&t.id.to_string()))
+            {
+                originals.push(transaction.clone());
+                transaction.flag_color = None;
+            }
+            transactions_state
+                .unsaved
+                .extend(transaction_ids.iter().cloned());
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .clear_transaction_flags(budget_id, originals)
+                    .await;
+            };
+            task_manager.spawn_load_task("clear_transaction_flags".to_string(), future);
+        }
+
+        AppCommand::ApplyPayeeRules { budget_id } => {
+            let payee_rules = state.config.rules.clone();
+            let Screen::Transactions(transactions_state) = state.current_screen_mut() else {
+                return;
+            };
+            let matches = rules::categorize(
+                &payee_rules,
+                &transactions_state.transactions,
+                &transactions_state.categories,
+            );
+            if matches.is_empty() {
+                state.notice = Some(t("notice-no-rule-matches").to_string());
+                return;
+            }
+
+            // Shown straight away and put back if the update fails
+            let mut originals = Vec::new();
+            for m in &matches {
+                if let Some(transaction) = transactions_state
+                    .transactions
+                    .iter_mut()
+                    .find(|t| t.id.to_string() == m.transaction_id)
+                {
+                    originals.push(transaction.clone());
+                    transaction.category_id = Some(m.category_id);
+                    transaction.category_name = Some(m.category_name.clone());
+                }
+            }
+            transactions_state
+                .unsaved
+                .extend(matches.iter().map(|m| m.transaction_id.clone()));
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .categorize_transactions(budget_id, matches, originals)
+                    .await;
+            };
+            task_manager.spawn_load_task("apply_payee_rules".to_string(), future);
+        }
+
+        AppCommand::AcceptCategorySuggestion {
+            budget_id,
+            transaction_id,
+        } => {
+            let approve = state.config.approval.on_save;
+            let Some((original, category_id)) = accept_category_suggestion(state, &transaction_id)
+            else {
+                return;
+            };
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .accept_category_suggestion(budget_id, original, category_id, approve)
+                    .await;
+            };
+            task_manager.spawn_load_task(
+                format!("accept_category_suggestion_{}", transaction_id),
+                future,
+            );
+        }
+
+        AppCommand::ImportLinkedTransactions {
+            budget_id,
+            account_id,
+        } => {
+            state.notice = Some(t("notice-importing").to_string());
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .import_linked_transactions(budget_id, account_id)
+                    .await;
+            };
+            task_manager.spawn_load_task("import_linked_transactions".to_string(), future);
+        }
+
+        AppCommand::InitiateTransactionDelete { transaction_id } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.input_mode = InputMode::DeleteConfirmation;
+                transactions_state.delete_confirmation_transaction_id = Some(transaction_id);
+            }
+        }
+
+        AppCommand::ConfirmTransactionDelete {
+            transaction_id,
+            budget_id,
+        } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                // Kept so undo can recreate it
+                let deleted = transactions_state
+                    .transactions
+                    .iter()
+                    .find(|t| t.id.to_string() == transaction_id)
+                    .cloned();
+
+                // Clear confirmation state and return to normal mode
+                transactions_state.input_mode = InputMode::Normal;
+                transactions_state.delete_confirmation_transaction_id = None;
+
+                delete_transaction(
+                    state,
+                    task_manager,
+                    data_loader,
+                    &budget_id,
+                    &transaction_id,
+                );
+                if let Some(transaction) = deleted {
+                    state.undo_history.record(UndoEntry::TransactionDeleted {
+                        budget_id,
+                        transaction: Box::new(transaction),
+                    });
+                }
             }
         }
 
@@ -1315,6 +2088,20 @@ pub fn execute_command(
             }
         }
 
+        AppCommand::CategorizeTransaction { transaction_id } => {
+            execute_command(
+                AppCommand::EnterTransactionEditMode { transaction_id },
+                state,
+                task_manager,
+                data_loader,
+            );
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                if let Some(form) = transactions_state.form_state.as_mut() {
+                    form.current_field = Some(FormField::Category);
+                }
+            }
+        }
+
         AppCommand::ConfirmReconciledEdit { transaction_id } => {
             if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
                 transactions_state.input_mode = InputMode::Normal;
@@ -1349,15 +2136,7 @@ pub fn execute_command(
             account_id,
         } => {
             if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
-                // Collect cleared transaction IDs and optimistically update them
-                let transaction_ids: Vec<String> = transactions_state
-                    .transactions
-                    .iter()
-                    .filter(|t| t.cleared == ReconciliationStatus::Cleared)
-                    .map(|t| t.id.to_string())
-                    .collect();
-
-                // Optimistically update local state
+                // Optimistically update the loaded transactions
                 for transaction in transactions_state.transactions.iter_mut() {
                     if transaction.cleared == ReconciliationStatus::Cleared {
                         transaction.cleared = ReconciliationStatus::Reconciled;
@@ -1368,55 +2147,82 @@ pub fn execute_command(
                 transactions_state.input_mode = InputMode::Normal;
                 transactions_state.reconcile_cleared_balance = None;
 
-                // Spawn background task to bulk update via API
-                if !transaction_ids.is_empty() {
-                    let api_client = data_loader.api_client.clone();
-                    let data_tx = data_loader.data_tx.clone();
-                    let cache = data_loader.cache.clone();
-                    let transaction_ids_clone = transaction_ids.clone();
-                    let budget_id_clone = budget_id.clone();
-                    let account_id_clone = account_id.clone();
+                // Only the recent window is loaded, so look through the account's
+                // whole history for cleared transactions before marking them
+                let loader = data_loader.clone();
+                let data_tx = data_loader.data_tx.clone();
+                let cache = data_loader.cache.clone();
+                let future = async move {
+                    let budget_id_api: BudgetId = budget_id.clone().into();
+                    let Ok(account_uuid) = Uuid::parse_str(&account_id) else {
+                        tracing::error!("Invalid account id {}", account_id);
+                        return;
+                    };
+                    let list = Request::transactions()
+                        .with_budget(budget_id_api.clone())
+                        .list(account_uuid);
+                    let transaction_ids: Vec<String> = match loader.send(list).await {
+                        Ok(response) => response
+                            .data
+                            .transactions
+                            .into_iter()
+                            .filter(|t| !t.deleted && t.cleared == ReconciliationStatus::Cleared)
+                            .map(|t| t.id.to_string())
+                            .collect(),
+                        Err(e) => {
+                            tracing::error!("Failed to load transactions to reconcile: {}", e);
+                            let _ = data_tx.send(
+                                TransactionEvent::TransactionsReconcileFailed { error: e.into() }
+                                    .into(),
+                            );
+                            return;
+                        }
+                    };
+                    if transaction_ids.is_empty() {
+                        return;
+                    }
 
-                    let future = async move {
-                        let budget_id_api: BudgetId = budget_id_clone.clone().into();
-                        let bulk_updates: Vec<BulkTransactionUpdate> = transaction_ids_clone
-                            .iter()
-                            .map(|id| BulkTransactionUpdate {
-                                id: id.parse().expect("invalid transaction id"),
-                                cleared: Some(ReconciliationStatus::Reconciled),
-                            })
-                            .collect();
+                    let bulk_updates: Vec<BulkTransactionUpdate> = transaction_ids
+                        .iter()
+                        .map(|id| BulkTransactionUpdate {
+                            id: id.parse().expect("invalid transaction id"),
+                            cleared: Some(ReconciliationStatus::Reconciled),
+                            payee_id: None,
+                            category_id: None,
+                            approved: None,
+                            flag_color: None,
+                        })
+                        .collect();
 
-                        let req = Request::transactions()
-                            .bulk()
-                            .update()
-                            .budget_id(budget_id_api)
-                            .transactions(bulk_updates);
+                    let req = Request::transactions()
+                        .bulk()
+                        .update()
+                        .budget_id(budget_id_api)
+                        .transactions(bulk_updates);
 
-                        match api_client.send(req).await {
-                            Ok(_) => {
-                                tracing::info!(
-                                    "Successfully reconciled {} transactions",
-                                    transaction_ids.len()
-                                );
-                                let _ = data_tx
-                                    .send(DataEvent::TransactionsReconciled { transaction_ids });
-                                // Invalidate cache so next load gets fresh data
-                                let _ = cache
-                                    .invalidate_transactions(&budget_id_clone, &account_id_clone)
-                                    .await;
-                            }
-                            Err(e) => {
-                                tracing::error!("Failed to reconcile transactions: {}", e);
-                                let _ = data_tx.send(DataEvent::TransactionsReconcileFailed {
-                                    error: e.to_string(),
-                                });
-                            }
+                    match loader.send(req).await {
+                        Ok(_) => {
+                            tracing::info!(
+                                "Successfully reconciled {} transactions",
+                                transaction_ids.len()
+                            );
+                            let _ = data_tx.send(
+                                TransactionEvent::TransactionsReconciled { transaction_ids }.into(),
+                            );
+                            // Invalidate cache so next load gets fresh data
+                            let _ = cache.invalidate_transactions(&budget_id, &account_id).await;
                         }
-                    };
+                        Err(e) => {
+                            tracing::error!("Failed to reconcile transactions: {}", e);
+                            let _ = data_tx.send(
+                                TransactionEvent::TransactionsReconcileFailed { error: e.into() }
+                                    .into(),
+                            );
+                        }
+                    }
+                };
 
-                    task_manager.spawn_load_task("reconcile_transactions".to_string(), future);
-                }
+                task_manager.spawn_load_task("reconcile_transactions".to_string(), future);
             }
         }
 
@@ -1429,11 +2235,10 @@ pub fn execute_command(
 
         AppCommand::EnterTransactionEditMode { transaction_id } => {
             let budget_id_opt = state.current_budget_id.clone();
+            let currency = state.currency_format();
             let date_format = state
-                .current_budget
-                .as_ref()
-                .and_then(|b| b.date_format.as_ref())
-                .map(|d| d.format.clone())
+                .date_format()
+                .map(|d| d.format)
                 .unwrap_or_else(|| "YYYY-MM-DD".to_string());
 
             if let Screen::Transactions(trans_state) = state.current_screen_mut() {
@@ -1448,6 +2253,7 @@ pub fn execute_command(
                     trans_state.form_state = Some(TransactionFormState::from_transaction(
                         transaction,
                         &date_format,
+                        currency.as_ref(),
                     ));
 
                     // Load payees/categories if not already loaded
@@ -1473,6 +2279,16 @@ pub fn execute_command(
             }
         }
 
+        AppCommand::NavigateToSettings => {
+            state.navigate_to(Screen::Settings(SettingsState::default()));
+        }
+
+        AppCommand::ChangeSetting { forward } => {
+            if change_setting(state, forward) {
+                save_budget_settings(state, task_manager, data_loader);
+            }
+        }
+
         AppCommand::NavigateToLogs => {
             // Navigate to logs screen (no logging to avoid feedback loop)
             state.navigate_to(Screen::Logs(LogsState::default()));
@@ -1523,142 +2339,1106 @@ pub fn execute_command(
             }
         }
 
-        AppCommand::NavigateBack => {
-            // Navigate back in history (pop from navigation stack)
-            state.navigate_back();
+        AppCommand::NavigateToReimbursements { budget_id } => {
+            state.navigate_to(Screen::Reimbursements(ReimbursementsState::default()));
+            execute_command(
+                AppCommand::LoadReimbursements { budget_id },
+                state,
+                task_manager,
+                data_loader,
+            );
         }
 
-        // Budget editing commands
-        AppCommand::InitiateBudgetEdit { category_id } => {
-            if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                // Find the category
-                if let Some(category) = plan_state
-                    .categories
-                    .iter()
-                    .find(|c| c.id.to_string() == category_id)
-                {
-                    plan_state.input_mode = InputMode::BudgetEdit;
-                    plan_state.budget_form = Some(BudgetFormState::new(
-                        category.id.to_string(),
-                        category.name.clone(),
-                        category.budgeted.into(),
-                    ));
-                }
+        AppCommand::LoadReimbursements { budget_id } => {
+            if let Screen::Reimbursements(reimbursements_state) = state.current_screen_mut() {
+                reimbursements_state.loading = LoadingState::Loading(ThrobberState::default());
             }
-        }
 
-        AppCommand::ExitBudgetEditMode => {
-            if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                plan_state.input_mode = InputMode::Normal;
-                plan_state.budget_form = None;
-            }
+            let data_loader = data_loader.clone();
+            let budget_id_clone = budget_id.clone();
+            let future = async move {
+                data_loader.load_reimbursements(budget_id_clone).await;
+            };
+            task_manager.spawn_load_task(format!("load_reimbursements_{}", budget_id), future);
         }
 
-        AppCommand::AppendBudgetChar(c) => {
-            if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                if let Some(ref mut form) = plan_state.budget_form {
-                    form.budgeted_input.push(c);
-                    form.validation_error = None;
-                }
-            }
+        AppCommand::NavigateToScheduledTransactions {
+            budget_id,
+            account_id,
+        } => {
+            state.navigate_to(Screen::ScheduledTransactions(
+                ScheduledTransactionsState::new(account_id),
+            ));
+            execute_command(
+                AppCommand::LoadScheduledTransactions {
+                    budget_id,
+                    force_refresh: false,
+                },
+                state,
+                task_manager,
+                data_loader,
+            );
         }
 
-        AppCommand::DeleteBudgetChar => {
-            if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                if let Some(ref mut form) = plan_state.budget_form {
-                    form.budgeted_input.pop();
-                    form.validation_error = None;
-                }
+        AppCommand::LoadScheduledTransactions {
+            budget_id,
+            force_refresh,
+        } => {
+            if let Screen::ScheduledTransactions(scheduled_state) = state.current_screen_mut() {
+                scheduled_state.loading = LoadingState::Loading(ThrobberState::default());
             }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .load_scheduled_transactions(budget_id, force_refresh)
+                    .await;
+            };
+            task_manager.spawn_load_task("load_scheduled_transactions".to_string(), future);
         }
 
-        AppCommand::SubmitBudgetEdit { budget_id, month } => {
-            if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                if let Some(ref mut form) = plan_state.budget_form {
-                    // Evaluate math expression if present
-                    let input = if let Some(result) =
-                        utils::math::evaluate_expression(&form.budgeted_input)
-                    {
-                        result
-                    } else {
-                        form.budgeted_input.clone()
-                    };
+        AppCommand::NavigateToNetWorth { budget_id } => {
+            state.navigate_to(Screen::NetWorth(NetWorthState::default()));
+            execute_command(
+                AppCommand::LoadNetWorth {
+                    budget_id,
+                    force_refresh: false,
+                },
+                state,
+                task_manager,
+                data_loader,
+            );
+        }
 
-                    // Parse the amount
-                    match input.parse::<f64>() {
-                        Ok(amount) => {
-                            use ynab_api::endpoints::Milliunits;
-                            let budgeted_milliunits = (amount * 1000.0) as i64;
-                            let category_id = form.category_id.clone();
-                            let original_budgeted = form.original_budgeted;
-                            let delta: Milliunits =
-                                (budgeted_milliunits - original_budgeted).into();
+        AppCommand::LoadNetWorth {
+            budget_id,
+            force_refresh,
+        } => {
+            if let Screen::NetWorth(net_worth_state) = state.current_screen_mut() {
+                net_worth_state.loading = LoadingState::Loading(ThrobberState::default());
+            }
 
-                            // Optimistic update: apply locally immediately
-                            if let Some(category) = plan_state
-                                .categories
-                                .iter_mut()
-                                .find(|c| c.id.to_string() == category_id)
-                            {
-                                category.budgeted = budgeted_milliunits.into();
-                            }
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.load_net_worth(budget_id, force_refresh).await;
+            };
+            task_manager.spawn_load_task("load_net_worth".to_string(), future);
+        }
 
-                            // Update month summary (budgeted increases, to_be_budgeted decreases)
-                            if let Some(ref mut month_detail) = plan_state.month {
-                                month_detail.budgeted = month_detail.budgeted + delta;
-                                month_detail.to_be_budgeted = month_detail.to_be_budgeted - delta;
-                            }
+        AppCommand::NavigateToPayeeCleanup { budget_id } => {
+            state.navigate_to(Screen::PayeeCleanup(PayeeCleanupState::default()));
+            execute_command(
+                AppCommand::LoadPayeeClusters { budget_id },
+                state,
+                task_manager,
+                data_loader,
+            );
+        }
 
-                            // Exit edit mode
-                            plan_state.input_mode = InputMode::Normal;
-                            plan_state.budget_form = None;
+        AppCommand::LoadPayeeClusters { budget_id } => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.loading = LoadingState::Loading(ThrobberState::default());
+            }
 
-                            // Spawn background task to update via API
-                            let data_loader = data_loader.clone();
-                            let future = async move {
-                                data_loader
-                                    .update_category_budget(
-                                        budget_id,
-                                        month,
-                                        category_id,
-                                        budgeted_milliunits,
-                                        original_budgeted,
-                                    )
-                                    .await;
-                            };
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.load_payee_clusters(budget_id).await;
+            };
+            task_manager.spawn_load_task("load_payee_clusters".to_string(), future);
+        }
 
-                            task_manager.spawn_load_task("update_budget".to_string(), future);
-                        }
-                        Err(_) => {
-                            form.validation_error =
-                                Some("Invalid amount. Enter a number (e.g., 150.00)".to_string());
-                        }
-                    }
-                }
+        AppCommand::ApplyPayeeRenames { budget_id, renames } => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.loading = LoadingState::Loading(ThrobberState::default());
             }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.rename_payees(budget_id, renames).await;
+            };
+            task_manager.spawn_load_task("rename_payees".to_string(), future);
         }
 
-        AppCommand::EnterSplitMode => {
-            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
-                if let Some(ref mut form) = trans_state.form_state {
-                    if !form.is_split_mode {
-                        form.is_split_mode = true;
-                        form.subtransactions.push(SubTransactionFormState::new());
-                        form.category.clear();
-                        form.filtered_categories.clear();
-                        form.category_selection_index = 0;
-                        // Focus the first subtransaction's amount field
-                        //form.active_subtransaction_index = Some(0);
-                        //form.subtransaction_field = SubTransactionField::Amount;
-                    }
-                }
+        AppCommand::MergePayees { budget_id, merges } => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.confirm_merge = false;
+                cleanup_state.loading = LoadingState::Loading(ThrobberState::default());
             }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.merge_payees(budget_id, merges).await;
+            };
+            task_manager.spawn_load_task("merge_payees".to_string(), future);
         }
 
-        AppCommand::AddSubtransaction => {
-            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
-                if let Some(ref mut form) = trans_state.form_state {
-                    if form.is_split_mode {
+        AppCommand::ToggleAwaitingReimbursement { budget_id, entry } => {
+            let task_id = format!("update_reimbursement_{}", entry.transaction_id);
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.toggle_reimbursement(budget_id, entry).await;
+            };
+            task_manager.spawn_load_task(task_id, future);
+        }
+
+        AppCommand::MarkReimbursed {
+            budget_id,
+            transaction_id,
+            deposit_id,
+        } => {
+            if let Screen::Reimbursements(reimbursements_state) = state.current_screen_mut() {
+                reimbursements_state.loading = LoadingState::Loading(ThrobberState::default());
+            }
+
+            let task_id = format!("update_reimbursement_{}", transaction_id);
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .mark_reimbursed(budget_id, transaction_id, deposit_id)
+                    .await;
+            };
+            task_manager.spawn_load_task(task_id, future);
+        }
+
+        AppCommand::NavigateToReports { budget_id } => {
+            let period = ReportPeriod::default();
+            state.navigate_to(Screen::Reports(ReportsState {
+                period,
+                loading: LoadingState::Loading(ThrobberState::default()),
+                ..Default::default()
+            }));
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .load_variance_report(budget_id, period, false)
+                    .await;
+            };
+            task_manager.spawn_load_task("load_report".to_string(), future);
+        }
+
+        AppCommand::LoadVarianceReport {
+            budget_id,
+            period,
+            force_refresh,
+        } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.kind = ReportKind::Variance;
+                reports_state.period = period;
+                reports_state.loading = LoadingState::Loading(ThrobberState::default());
+                reports_state.drill_down = None;
+            }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .load_variance_report(budget_id, period, force_refresh)
+                    .await;
+            };
+            task_manager.spawn_load_task("load_report".to_string(), future);
+        }
+
+        AppCommand::DrillIntoVarianceCategory {
+            budget_id,
+            category_id,
+        } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                let category_name = reports_state
+                    .variance_rows
+                    .iter()
+                    .find(|r| r.category_id == category_id)
+                    .map(|r| r.category_name.clone())
+                    .or_else(|| {
+                        let rows = &reports_state.spending.as_ref()?.rows;
+                        rows.iter()
+                            .find(|r| r.category_id.as_deref() == Some(category_id.as_str()))
+                            .map(|r| r.name.clone())
+                    })
+                    .unwrap_or_default();
+                reports_state.drill_down = Some(ReportDrillDown {
+                    category_id: category_id.clone(),
+                    category_name,
+                    loading: LoadingState::Loading(ThrobberState::default()),
+                    ..Default::default()
+                });
+
+                let period = reports_state.period;
+                let data_loader = data_loader.clone();
+                let future = async move {
+                    data_loader
+                        .load_variance_transactions(budget_id, category_id, period)
+                        .await;
+                };
+                task_manager.spawn_load_task("load_report_drill_down".to_string(), future);
+            }
+        }
+
+        AppCommand::ToggleVarianceSort => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.variance_sort = match reports_state.variance_sort {
+                    VarianceSort::Category => VarianceSort::Variance,
+                    VarianceSort::Variance => VarianceSort::Category,
+                };
+                reports_state.table_state.borrow_mut().select(Some(0));
+            }
+        }
+
+        AppCommand::CloseReportDrillDown => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.drill_down = None;
+            }
+        }
+
+        AppCommand::LoadCashFlowProjection {
+            budget_id,
+            account_id,
+            horizon,
+        } => {
+            let loading = LoadingState::Loading(ThrobberState::default());
+            match state.current_screen_mut() {
+                Screen::Reports(reports_state) if reports_state.kind == ReportKind::CashFlow => {
+                    reports_state.horizon = horizon;
+                    reports_state.loading = loading;
+                }
+                _ => state.navigate_to(Screen::Reports(ReportsState {
+                    kind: ReportKind::CashFlow,
+                    account_id: Some(account_id.clone()),
+                    horizon,
+                    loading,
+                    ..Default::default()
+                })),
+            }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .load_cash_flow(budget_id, account_id, horizon)
+                    .await;
+            };
+            task_manager.spawn_load_task("load_report".to_string(), future);
+        }
+
+        AppCommand::LoadSpendingHeatmap { budget_id, month } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.kind = ReportKind::Heatmap;
+                reports_state.heatmap_month = Some(month);
+                reports_state.loading = LoadingState::Loading(ThrobberState::default());
+                reports_state.drill_down = None;
+            }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.load_spending_heatmap(budget_id, month).await;
+            };
+            task_manager.spawn_load_task("load_report".to_string(), future);
+        }
+
+        AppCommand::LoadSpendingBreakdown {
+            budget_id,
+            period,
+            grouping,
+        } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.kind = ReportKind::Spending;
+                reports_state.period = period;
+                reports_state.spending_grouping = grouping;
+                reports_state.loading = LoadingState::Loading(ThrobberState::default());
+                reports_state.drill_down = None;
+            }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .load_spending_breakdown(budget_id, period, grouping)
+                    .await;
+            };
+            task_manager.spawn_load_task("load_report".to_string(), future);
+        }
+
+        AppCommand::LoadIncomeExpense { budget_id } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.kind = ReportKind::IncomeExpense;
+                reports_state.loading = LoadingState::Loading(ThrobberState::default());
+                reports_state.drill_down = None;
+            }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader.load_income_expense(budget_id).await;
+            };
+            task_manager.spawn_load_task("load_report".to_string(), future);
+        }
+
+        AppCommand::LoadGoalObligations {
+            budget_id,
+            force_refresh,
+        } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.kind = ReportKind::Obligations;
+                reports_state.loading = LoadingState::Loading(ThrobberState::default());
+                reports_state.drill_down = None;
+            }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .load_goal_obligations(budget_id, force_refresh)
+                    .await;
+            };
+            task_manager.spawn_load_task("load_report".to_string(), future);
+        }
+
+        AppCommand::NavigateToTransactionsOnDate { budget_id, date } => {
+            // The day's transactions span every account, so there's no current account
+            state.current_account_id = None;
+            state.navigate_to(Screen::Transactions(Box::new(TransactionsState {
+                all_accounts: true,
+                on_date: Some(date),
+                show_reconciled_transactions: true,
+                sort: state.budget_settings.transaction_sort,
+                currency_format: state.currency_format(),
+                ..Default::default()
+            })));
+            execute_command(
+                AppCommand::LoadBudgetTransactions { budget_id },
+                state,
+                task_manager,
+                data_loader,
+            );
+        }
+
+        AppCommand::NavigateToInspector { focus_id } => {
+            if state.config.debug.inspector {
+                state.navigate_to(Screen::Inspector(InspectorState::new(focus_id)));
+            } else {
+                tracing::warn!("API inspector is disabled; set debug.inspector = true to enable");
+            }
+        }
+
+        AppCommand::ScrollInspectorDetail { down } => {
+            if let Screen::Inspector(inspector_state) = state.current_screen_mut() {
+                inspector_state.scroll_detail(down);
+            }
+        }
+
+        AppCommand::NavigateBack => {
+            // Navigate back in history (pop from navigation stack)
+            state.navigate_back();
+        }
+
+        // Budget editing commands
+        AppCommand::InitiateBudgetEdit { category_id } => {
+            let currency = state.currency_format();
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                // Find the category
+                if let Some(category) = plan_state
+                    .categories
+                    .iter()
+                    .find(|c| c.id.to_string() == category_id)
+                {
+                    plan_state.input_mode = InputMode::BudgetEdit;
+                    plan_state.budget_form = Some(BudgetFormState::new(
+                        category.id.to_string(),
+                        category.name.clone(),
+                        category.budgeted.into(),
+                        currency.as_ref(),
+                    ));
+                }
+            }
+        }
+
+        AppCommand::ExitBudgetEditMode => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.budget_form = None;
+            }
+        }
+
+        AppCommand::AppendBudgetChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.budget_form {
+                    form.budgeted_input.push(c);
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::DeleteBudgetChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.budget_form {
+                    form.budgeted_input.pop();
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::OpenGoalCalculator { category_id } => {
+            let currency = state.currency_format();
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                let from_month = plan_state
+                    .month
+                    .as_ref()
+                    .and_then(|m| utils::goals::parse_month(&m.month));
+                if let (Some(category), Some(from_month)) = (
+                    plan_state
+                        .categories
+                        .iter()
+                        .find(|c| c.id.to_string() == category_id),
+                    from_month,
+                ) {
+                    plan_state.input_mode = InputMode::GoalCalculator;
+                    plan_state.goal_calculator = Some(GoalCalculatorState::new(
+                        category,
+                        from_month,
+                        currency.as_ref(),
+                    ));
+                }
+            }
+        }
+
+        AppCommand::CloseGoalCalculator => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.goal_calculator = None;
+            }
+        }
+
+        AppCommand::ToggleGoalCalculatorField => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut calculator) = plan_state.goal_calculator {
+                    calculator.field = match calculator.field {
+                        GoalCalculatorField::Monthly => GoalCalculatorField::TargetMonth,
+                        GoalCalculatorField::TargetMonth => GoalCalculatorField::Monthly,
+                    };
+                }
+            }
+        }
+
+        AppCommand::AppendGoalCalculatorChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut calculator) = plan_state.goal_calculator {
+                    calculator.focused_input_mut().push(c);
+                }
+            }
+        }
+
+        AppCommand::DeleteGoalCalculatorChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut calculator) = plan_state.goal_calculator {
+                    calculator.focused_input_mut().pop();
+                }
+            }
+        }
+
+        AppCommand::OpenGoalForm { category_id } => {
+            let currency = state.currency_format();
+            let mut no_goal = false;
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(category) = plan_state
+                    .categories
+                    .iter()
+                    .find(|c| c.id.to_string() == category_id)
+                {
+                    match GoalFormState::new(category, currency.as_ref()) {
+                        Some(form) => {
+                            plan_state.goal_form = Some(form);
+                            plan_state.input_mode = InputMode::GoalForm;
+                        }
+                        // YNAB's API can change a goal but not create one
+                        None => no_goal = true,
+                    }
+                }
+            }
+            if no_goal {
+                state.notice = Some(t("notice-goal-not-set").to_string());
+            }
+        }
+
+        AppCommand::CloseGoalForm => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.goal_form = None;
+            }
+        }
+
+        AppCommand::ToggleGoalFormField => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.goal_form {
+                    form.field = match form.field {
+                        GoalFormField::Target => GoalFormField::TargetMonth,
+                        GoalFormField::TargetMonth => GoalFormField::Target,
+                    };
+                }
+            }
+        }
+
+        AppCommand::AppendGoalFormChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.goal_form {
+                    form.focused_input_mut().push(c);
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::DeleteGoalFormChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.goal_form {
+                    form.focused_input_mut().pop();
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::SubmitGoalForm { budget_id } => {
+            let mut update = None;
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.goal_form {
+                    match (
+                        form.target().filter(|target| *target > 0),
+                        form.target_month(),
+                    ) {
+                        (None, _) => {
+                            form.validation_error =
+                                Some(AppError::validation(t("goal-form-invalid-target")));
+                        }
+                        (Some(_), None) => {
+                            form.validation_error =
+                                Some(AppError::validation(t("goal-form-invalid-month")));
+                        }
+                        (Some(target), Some(target_month)) => {
+                            let category_id = form.category_id.clone();
+                            plan_state.input_mode = InputMode::Normal;
+                            plan_state.goal_form = None;
+
+                            // Show the new goal right away; the server's
+                            // figures replace it once it answers
+                            if let Some(category) = plan_state
+                                .categories
+                                .iter_mut()
+                                .find(|c| c.id.to_string() == category_id)
+                            {
+                                let original = category.clone();
+                                category.goal_target = Some(target.into());
+                                category.goal_target_month =
+                                    target_month.map(|month| month.format("%Y-%m-%d").to_string());
+                                update = Some((original, target, target_month));
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some((original, target, target_month)) = update {
+                let data_loader = data_loader.clone();
+                let task_id = format!("update_goal_{}", original.id);
+                let future = async move {
+                    data_loader
+                        .update_category_goal(budget_id, original, target, target_month)
+                        .await;
+                };
+                task_manager.spawn_load_task(task_id, future);
+            }
+        }
+
+        AppCommand::OpenMoveMoney { category_id } => {
+            let currency = state.currency_format();
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(source) = plan_state
+                    .categories
+                    .iter()
+                    .find(|c| c.id.to_string() == category_id)
+                {
+                    plan_state.move_money = Some(MoveMoneyState::new(
+                        source,
+                        &plan_state.categories,
+                        currency.as_ref(),
+                    ));
+                    plan_state.input_mode = InputMode::MoveMoney;
+                }
+            }
+        }
+
+        AppCommand::CloseMoveMoney => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.move_money = None;
+            }
+        }
+
+        AppCommand::ToggleMoveMoneyField => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    form.field = match form.field {
+                        MoveMoneyField::Amount => MoveMoneyField::Destination,
+                        MoveMoneyField::Destination => MoveMoneyField::Amount,
+                    };
+                }
+            }
+        }
+
+        AppCommand::AppendMoveMoneyChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    form.focused_input_mut().push(c);
+                    form.validation_error = None;
+                    if form.field == MoveMoneyField::Destination {
+                        form.update_destinations(&plan_state.categories);
+                    }
+                }
+            }
+        }
+
+        AppCommand::DeleteMoveMoneyChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    form.focused_input_mut().pop();
+                    form.validation_error = None;
+                    if form.field == MoveMoneyField::Destination {
+                        form.update_destinations(&plan_state.categories);
+                    }
+                }
+            }
+        }
+
+        AppCommand::MoveMoneyDestinationCursor { down } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    let last = form.destinations.len().saturating_sub(1);
+                    form.selected_destination = if down {
+                        (form.selected_destination + 1).min(last)
+                    } else {
+                        form.selected_destination.saturating_sub(1)
+                    };
+                }
+            }
+        }
+
+        AppCommand::CloseArchiveReview => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.archive_review = None;
+            }
+        }
+
+        AppCommand::CloseQuickBudget => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.quick_budget = None;
+            }
+        }
+
+        AppCommand::MoveQuickBudgetCursor { down } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut quick) = plan_state.quick_budget {
+                    if down {
+                        quick.select_next();
+                    } else {
+                        quick.select_prev();
+                    }
+                }
+            }
+        }
+
+        AppCommand::MoveArchiveCursor { down } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut review) = plan_state.archive_review {
+                    if down {
+                        review.select_next();
+                    } else {
+                        review.select_prev();
+                    }
+                }
+            }
+        }
+
+        AppCommand::ToggleArchiveSuggestion => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut review) = plan_state.archive_review {
+                    review.toggle_selected();
+                }
+            }
+        }
+
+        AppCommand::CloseHealthChecklist => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.health = None;
+            }
+        }
+
+        AppCommand::MoveHealthCursor { down } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut health) = plan_state.health {
+                    if down {
+                        health.select_next();
+                    } else {
+                        health.select_prev();
+                    }
+                }
+            }
+        }
+
+        AppCommand::TogglePayeeCluster => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.toggle_selected();
+            }
+        }
+
+        AppCommand::OpenPayeeMergeConfirmation => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.confirm_merge = true;
+            }
+        }
+
+        AppCommand::CancelPayeeMerge => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.confirm_merge = false;
+            }
+        }
+
+        AppCommand::OpenQuickBudget {
+            budget_id,
+            action,
+            all_visible,
+        } => {
+            let Screen::Plan(plan_state) = state.current_screen_mut() else {
+                return;
+            };
+            let Some((month, to_be_budgeted)) = plan_state
+                .month
+                .as_ref()
+                .map(|m| (m.month.clone(), i64::from(m.to_be_budgeted)))
+            else {
+                return;
+            };
+            let category_ids: Vec<String> = if all_visible {
+                plan_state
+                    .visible_categories()
+                    .iter()
+                    .map(|c| c.id.to_string())
+                    .collect()
+            } else {
+                plan_state
+                    .selected_category()
+                    .map(|c| vec![c.id.to_string()])
+                    .unwrap_or_default()
+            };
+            if category_ids.is_empty() {
+                return;
+            }
+
+            plan_state.input_mode = InputMode::QuickBudget;
+            plan_state.quick_budget = Some(QuickBudgetState {
+                action,
+                category_ids,
+                month: month.clone(),
+                changes: Vec::new(),
+                to_be_budgeted,
+                loading: LoadingState::Loading(ThrobberState::default()),
+                table_state: RefCell::default(),
+            });
+
+            // Funding goals only needs this month
+            let count = action.history_months();
+            if count == 0 {
+                plan_state.fill_quick_budget(&[]);
+                return;
+            }
+            let category_ids = plan_state
+                .quick_budget
+                .as_ref()
+                .map(|quick| quick.category_ids.clone())
+                .unwrap_or_default();
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .load_quick_budget_history(budget_id, month, count, category_ids)
+                    .await;
+            };
+            task_manager.spawn_load_task("load_quick_budget_history".to_string(), future);
+        }
+
+        AppCommand::ApplyQuickBudget { budget_id } => {
+            let Screen::Plan(plan_state) = state.current_screen_mut() else {
+                return;
+            };
+            plan_state.input_mode = InputMode::Normal;
+            let Some(quick) = plan_state.quick_budget.take() else {
+                return;
+            };
+            if quick.changes.is_empty() {
+                return;
+            }
+
+            let entry = UndoEntry::BudgetsEdited {
+                budget_id,
+                month: quick.month,
+                edits: quick
+                    .changes
+                    .into_iter()
+                    .map(|change| (change.category_id, change.from, change.to))
+                    .collect(),
+            };
+            apply_undo_entry(&entry, false, state, task_manager, data_loader);
+            state.undo_history.record(entry);
+        }
+
+        AppCommand::OpenArchiveReview { budget_id } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::ArchiveReview;
+                plan_state.archive_review = Some(ArchiveReviewState {
+                    loading: LoadingState::Loading(ThrobberState::default()),
+                    ..Default::default()
+                });
+
+                let data_loader = data_loader.clone();
+                let future = async move {
+                    data_loader.load_archive_suggestions(budget_id).await;
+                };
+                task_manager.spawn_load_task("load_archive_suggestions".to_string(), future);
+            }
+        }
+
+        AppCommand::OpenHealthChecklist { budget_id } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::HealthChecklist;
+                plan_state.health = Some(HealthChecklistState {
+                    loading: LoadingState::Loading(ThrobberState::default()),
+                    ..Default::default()
+                });
+
+                let data_loader = data_loader.clone();
+                let future = async move {
+                    data_loader.load_health_checklist(budget_id).await;
+                };
+                task_manager.spawn_load_task("load_health_checklist".to_string(), future);
+            }
+        }
+
+        AppCommand::FixHealthItem { budget_id, item } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.health = None;
+            }
+
+            match item {
+                HealthItem::Approval { .. } => {
+                    execute_command(
+                        AppCommand::NavigateToTransactionSearch { budget_id },
+                        state,
+                        task_manager,
+                        data_loader,
+                    );
+                    if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                        transactions_state.input_mode = InputMode::Normal;
+                        transactions_state.presets = vec![TransactionPreset::Unapproved];
+                    }
+                }
+                HealthItem::Reconciliation { stale } => {
+                    if let Some(account) = stale.into_iter().next() {
+                        execute_command(
+                            AppCommand::LoadTransactions {
+                                budget_id,
+                                account_id: account.account_id,
+                                force_refresh: false,
+                            },
+                            state,
+                            task_manager,
+                            data_loader,
+                        );
+                    }
+                }
+                HealthItem::Overspending { categories } => {
+                    if let (Screen::Plan(plan_state), Some((category_id, _))) =
+                        (state.current_screen_mut(), categories.first())
+                    {
+                        plan_state.reveal_category(category_id);
+                    }
+                }
+                HealthItem::ReadyToAssign { amount } => {
+                    // Money left goes to underfunded categories; overassigned
+                    // money comes back from overfunded ones
+                    if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                        plan_state.focused_view = if amount > 0 {
+                            PlanFocusedView::Underfunded
+                        } else {
+                            PlanFocusedView::Overfunded
+                        };
+                        plan_state.table_state =
+                            RefCell::new(TableState::default().with_selected(0));
+                    }
+                }
+            }
+        }
+
+        AppCommand::ArchiveCategories {
+            budget_id,
+            category_ids,
+        } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.archive_review = None;
+            }
+
+            let data_loader = data_loader.clone();
+            let future = async move {
+                data_loader
+                    .archive_categories(budget_id, category_ids)
+                    .await;
+            };
+            task_manager.spawn_load_task("archive_categories".to_string(), future);
+        }
+
+        AppCommand::MoveCategoryToGroup {
+            budget_id,
+            category_id,
+            forward,
+        } => {
+            let Screen::Plan(plan_state) = state.current_screen() else {
+                return;
+            };
+            let Some((group_id, group_name)) = plan_state.adjacent_group(&category_id, forward)
+            else {
+                let key = if forward {
+                    "notice-no-group-below"
+                } else {
+                    "notice-no-group-above"
+                };
+                state.notice = Some(t(key).to_string());
+                return;
+            };
+
+            tracing::info!("Moving category {} to group {}", category_id, group_name);
+            let data_loader = data_loader.clone();
+            let task_id = format!("move_category_{}", category_id);
+            let future = async move {
+                data_loader
+                    .move_category(budget_id, category_id, group_id, forward)
+                    .await;
+            };
+            task_manager.spawn_load_task(task_id, future);
+        }
+
+        AppCommand::SubmitMoveMoney { budget_id, month } => {
+            let mut moved = None;
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    let amount = form.amount().filter(|amount| *amount > 0);
+                    match (amount, form.destination()) {
+                        (None, _) => {
+                            form.validation_error =
+                                Some(AppError::validation(t("move-money-invalid-amount")));
+                        }
+                        (Some(_), None) => {
+                            form.validation_error =
+                                Some(AppError::validation(t("move-money-no-destination")));
+                        }
+                        (Some(amount), Some(destination)) => {
+                            let budgeted = |id: &str| {
+                                plan_state
+                                    .categories
+                                    .iter()
+                                    .find(|c| c.id.to_string() == id)
+                                    .map(|c| i64::from(c.budgeted))
+                            };
+                            let destination_id = destination.id.to_string();
+                            if let (Some(source_budgeted), Some(destination_budgeted)) =
+                                (budgeted(&form.source_id), budgeted(&destination_id))
+                            {
+                                moved = Some(UndoEntry::MoneyMoved {
+                                    budget_id,
+                                    month,
+                                    source_id: form.source_id.clone(),
+                                    source_budgeted,
+                                    destination_id,
+                                    destination_budgeted,
+                                    amount,
+                                });
+                            }
+                            plan_state.input_mode = InputMode::Normal;
+                            plan_state.move_money = None;
+                        }
+                    }
+                }
+            }
+
+            if let Some(entry) = moved {
+                apply_undo_entry(&entry, false, state, task_manager, data_loader);
+                state.undo_history.record(entry);
+            }
+        }
+
+        AppCommand::SubmitBudgetEdit { budget_id, month } => {
+            let mut edit = None;
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.budget_form {
+                    // A number or math expression
+                    match money::parse_input(&form.budgeted_input, form.currency_format.as_ref()) {
+                        Some(budgeted_milliunits) => {
+                            edit = Some((
+                                form.category_id.clone(),
+                                form.original_budgeted,
+                                budgeted_milliunits,
+                            ));
+
+                            // Exit edit mode
+                            plan_state.input_mode = InputMode::Normal;
+                            plan_state.budget_form = None;
+                        }
+                        None => {
+                            form.validation_error = Some(AppError::validation(
+                                "Invalid amount. Enter a number (e.g., 150.00)",
+                            ));
+                        }
+                    }
+                }
+            }
+
+            if let Some((category_id, from, to)) = edit {
+                set_category_budgeted(
+                    state,
+                    task_manager,
+                    data_loader,
+                    &budget_id,
+                    BudgetEdit {
+                        month: month.clone(),
+                        category_id: category_id.clone(),
+                        from,
+                        to,
+                    },
+                );
+                state.undo_history.record(UndoEntry::BudgetEdited {
+                    budget_id,
+                    month,
+                    category_id,
+                    from,
+                    to,
+                });
+            }
+        }
+
+        AppCommand::EnterSplitMode => {
+            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = trans_state.form_state {
+                    if !form.is_split_mode {
+                        form.is_split_mode = true;
+                        form.subtransactions.push(SubTransactionFormState::new());
+                        form.category.clear();
+                        form.filtered_categories.clear();
+                        form.category_selection_index = 0;
+                        // Focus the first subtransaction's amount field
+                        //form.active_subtransaction_index = Some(0);
+                        //form.subtransaction_field = SubTransactionField::Amount;
+                    }
+                }
+            }
+        }
+
+        AppCommand::AddSubtransaction => {
+            if let Screen::Transactions(trans_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = trans_state.form_state {
+                    if form.is_split_mode {
                         // Add a new subtransaction
                         form.subtransactions.push(SubTransactionFormState::new());
                         // Focus the new subtransaction's amount field
@@ -1696,6 +3476,26 @@ pub fn execute_command(
             }
         }
 
+        AppCommand::Undo | AppCommand::Redo if state.undo_history.is_pending() => {
+            state.notice = Some(t("notice-undo-pending").to_string());
+        }
+
+        AppCommand::Undo => match state.undo_history.undo() {
+            Some(entry) => {
+                apply_undo_entry(&entry, true, state, task_manager, data_loader);
+                state.notice = Some(t("notice-undone").replace("{action}", undo_label(&entry)));
+            }
+            None => state.notice = Some(t("notice-nothing-to-undo").to_string()),
+        },
+
+        AppCommand::Redo => match state.undo_history.redo() {
+            Some(entry) => {
+                apply_undo_entry(&entry, false, state, task_manager, data_loader);
+                state.notice = Some(t("notice-redone").replace("{action}", undo_label(&entry)));
+            }
+            None => state.notice = Some(t("notice-nothing-to-redo").to_string()),
+        },
+
         AppCommand::Quit => {
             state.should_quit = true;
         }
@@ -1706,6 +3506,59 @@ pub fn execute_command(
     if !is_setting_pending_key && state.pending_key.is_some() {
         state.pending_key = None;
     }
+    // Likewise a count only applies to the command right after it
+    if !is_setting_pending_count {
+        state.pending_count = None;
+    }
+}
+
+/// Auto-refresh tick: look for changes made elsewhere to what the current
+/// screen shows. Screens still loading or with a form or filter open are
+/// left alone, and nothing else about the state changes.
+pub fn check_for_changes(
+    state: &AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+) {
+    let Some(budget_id) = state.current_budget_id.clone() else {
+        return;
+    };
+    let data_loader = data_loader.clone();
+    match state.current_screen() {
+        Screen::Accounts(accounts_state)
+            if matches!(accounts_state.accounts_loading, LoadingState::Loaded)
+                && accounts_state.input_mode == InputMode::Normal =>
+        {
+            task_manager.spawn_load_task(format!("poll_accounts_{}", budget_id), async move {
+                data_loader.poll_accounts(budget_id).await
+            });
+        }
+        Screen::Transactions(transactions_state)
+            if matches!(
+                transactions_state.transactions_loading,
+                LoadingState::Loaded
+            ) && transactions_state.input_mode == InputMode::Normal
+                && !transactions_state.all_accounts =>
+        {
+            let Some(account_id) = state.current_account_id.clone() else {
+                return;
+            };
+            task_manager.spawn_load_task(
+                format!("poll_transactions_{}_{}", budget_id, account_id),
+                async move { data_loader.poll_transactions(budget_id, account_id).await },
+            );
+        }
+        Screen::Plan(plan_state)
+            if matches!(plan_state.plan_loading, LoadingState::Loaded)
+                && plan_state.input_mode == InputMode::Normal =>
+        {
+            let month = plan_state.month.as_ref().map(|m| m.month.clone());
+            task_manager.spawn_load_task(format!("poll_plan_{}", budget_id), async move {
+                data_loader.poll_plan(budget_id, month).await
+            });
+        }
+        _ => {}
+    }
 }
 
 /// Synchronous command execution for testing (no background tasks)
@@ -1724,77 +3577,58 @@ pub fn execute_command(
 /// NOTE: This is public for use by the testing module but should not be used in production code.
 pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
     let is_setting_pending_key = matches!(command, AppCommand::SetPendingKey(_));
+    let is_setting_pending_count = matches!(command, AppCommand::SetPendingCount(_));
+    // Notices only last until the next command, which may set its own
+    state.notice = None;
+    if refused_read_only(&command, state) {
+        return;
+    }
 
     match command {
         // Simple state updates
         AppCommand::Quit => state.should_quit = true,
         AppCommand::ToggleHelp => state.help_visible = !state.help_visible,
+        AppCommand::OpenContextMenu => state.context_menu = super::context_menu::build(state),
+        AppCommand::YankTransaction { transaction_id } => yank_transaction(state, &transaction_id),
+        AppCommand::DismissExportConfirmation => state.export_path = None,
+        AppCommand::CancelYnabExportImport => state.import_preview = None,
+        AppCommand::ShowTransactionDetail { transaction_id, .. } => {
+            open_transaction_detail(state, &transaction_id);
+        }
+        AppCommand::CloseTransactionDetail => state.transaction_detail = None,
+        AppCommand::CloseContextMenu => state.context_menu = None,
+        AppCommand::MoveContextMenuCursor { down } => {
+            if let Some(ref mut menu) = state.context_menu {
+                menu.move_cursor(down);
+            }
+        }
+        AppCommand::RunContextMenuItem => {
+            if let Some(command) = state
+                .context_menu
+                .take()
+                .and_then(|menu| menu.selected_command().cloned())
+            {
+                execute_command_sync(command, state);
+            }
+        }
         AppCommand::SetPendingKey(c) => state.pending_key = Some(c),
         AppCommand::ClearPendingKey => state.pending_key = None,
+        AppCommand::SetPendingCount(count) => state.pending_count = Some(count),
 
         // Navigation
         AppCommand::NavigateBack => {
             state.navigate_back();
         }
-        AppCommand::NavigateToTop => match state.current_screen_mut() {
-            Screen::Budgets(s) => s.selected_budget_index = 0,
-            Screen::Accounts(s) => s.table_state.borrow_mut().select(Some(0)),
-            Screen::Transactions(s) => s.table_state.borrow_mut().select(Some(0)),
-            Screen::Plan(s) => s.table_state.borrow_mut().select(Some(0)),
-            Screen::Logs(s) => s.scroll_offset = s.total_entries.saturating_sub(1),
-        },
-        AppCommand::NavigateToBottom => match state.current_screen_mut() {
-            Screen::Budgets(s) => {
-                if !s.budgets.is_empty() {
-                    s.selected_budget_index = s.budgets.len() - 1;
-                }
-            }
-            Screen::Accounts(s) => {
-                let len = s.filtered_accounts().len();
-                if len > 0 {
-                    s.table_state.borrow_mut().select(Some(len - 1));
-                }
-            }
-            Screen::Transactions(s) => {
-                let len = s.filtered_transactions().len();
-                if len > 0 {
-                    s.table_state.borrow_mut().select(Some(len - 1));
-                }
-            }
-            Screen::Plan(s) => {
-                let len = s.filtered_categories().len();
-                if len > 0 {
-                    s.table_state.borrow_mut().select(Some(len - 1));
-                }
-            }
-            Screen::Logs(s) => s.scroll_offset = 0,
-        },
-        AppCommand::SelectNext => match state.current_screen_mut() {
-            Screen::Budgets(s) => {
-                if !s.budgets.is_empty() {
-                    s.selected_budget_index = (s.selected_budget_index + 1) % s.budgets.len();
-                }
-            }
-            Screen::Accounts(s) => s.select_next(),
-            Screen::Transactions(s) => s.select_next(),
-            Screen::Plan(s) => s.select_next(),
-            Screen::Logs(_) => {} // Uses scroll commands instead
-        },
-        AppCommand::SelectPrevious => match state.current_screen_mut() {
-            Screen::Budgets(s) => {
-                if !s.budgets.is_empty() {
-                    if s.selected_budget_index == 0 {
-                        s.selected_budget_index = s.budgets.len() - 1;
-                    } else {
-                        s.selected_budget_index -= 1;
-                    }
-                }
-            }
-            Screen::Accounts(s) => s.select_prev(),
-            Screen::Transactions(s) => s.select_prev(),
-            Screen::Plan(s) => s.select_prev(),
-            Screen::Logs(_) => {} // Uses scroll commands instead
-        },
+        AppCommand::NavigateToTop => state.current_screen_mut().controller_mut().select_first(),
+        AppCommand::NavigateToBottom => state.current_screen_mut().controller_mut().select_last(),
+        AppCommand::SelectNext => state.current_screen_mut().controller_mut().select_next(),
+        AppCommand::SelectPrevious => state
+            .current_screen_mut()
+            .controller_mut()
+            .select_previous(),
+        AppCommand::MoveSelection { rows } => {
+            state.current_screen_mut().controller_mut().select_by(rows)
+        }
 
         // Filter mode
         AppCommand::EnterFilterMode => match state.current_screen_mut() {
@@ -1837,19 +3671,87 @@ pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
         AppCommand::ToggleShowClosedAccounts => {
             if let Screen::Accounts(s) = state.current_screen_mut() {
                 s.show_closed_accounts = !s.show_closed_accounts;
+                let show_closed_accounts = s.show_closed_accounts;
+                state.budget_settings.show_closed_accounts = show_closed_accounts;
             }
         }
+        AppCommand::CycleAccountTypeFilter
+        | AppCommand::EnterAccountJump
+        | AppCommand::AppendAccountJumpChar(_)
+        | AppCommand::DeleteAccountJumpChar
+        | AppCommand::ExitAccountJump => account_jump_or_filter(state, command),
+        AppCommand::CycleReconcileReminder { account_id } => {
+            cycle_reconcile_reminder(state, &account_id);
+        }
+        AppCommand::ToggleFavoriteAccount { account_id } => {
+            toggle_favorite_account(state, &account_id);
+        }
         AppCommand::ToggleShowReconciledTransactions => {
             if let Screen::Transactions(s) = state.current_screen_mut() {
                 s.show_reconciled_transactions = !s.show_reconciled_transactions;
+                let show_reconciled = s.show_reconciled_transactions;
+                state.budget_settings.show_reconciled_transactions = show_reconciled;
+            }
+        }
+        AppCommand::CycleTransactionSort | AppCommand::ReverseTransactionSort => {
+            if let Screen::Transactions(s) = state.current_screen_mut() {
+                s.sort = match command {
+                    AppCommand::ReverseTransactionSort => s.sort.reversed(),
+                    _ => s.sort.next_column(),
+                };
+                s.table_state = RefCell::new(TableState::default().with_selected(0));
+                state.budget_settings.transaction_sort = s.sort;
+            }
+        }
+        AppCommand::ToggleTransactionPreset(preset) => {
+            if let Screen::Transactions(s) = state.current_screen_mut() {
+                s.toggle_preset(preset);
+                s.table_state = RefCell::new(TableState::default().with_selected(0));
+            }
+        }
+        AppCommand::CycleFlagView => {
+            if let Screen::Transactions(s) = state.current_screen_mut() {
+                s.flag_view = next_flag_color(s.flag_view);
+                s.table_state = RefCell::new(TableState::default().with_selected(0));
+            }
+        }
+        AppCommand::ResolveDeltaConflict { keep_mine } => {
+            if let Screen::Transactions(s) = state.current_screen_mut() {
+                s.resolve_conflict(keep_mine);
+            }
+        }
+        AppCommand::ToggleConflictDiff => {
+            if let Screen::Transactions(s) = state.current_screen_mut() {
+                if let Some(conflict) = s.conflicts.first_mut() {
+                    conflict.show_diff = !conflict.show_diff;
+                }
             }
         }
         AppCommand::TogglePlanFocusedView => {
             if let Screen::Plan(s) = state.current_screen_mut() {
                 s.focused_view = s.focused_view.next();
                 s.table_state = RefCell::new(TableState::default().with_selected(0));
+                let plan_view = s.focused_view;
+                state.budget_settings.plan_view = plan_view;
+            }
+        }
+        AppCommand::TogglePlanCategoryDetails => {
+            state.budget_settings.plan_category_details =
+                !state.budget_settings.plan_category_details;
+        }
+        AppCommand::TogglePlanGoalDetails => {
+            if let Screen::Plan(s) = state.current_screen_mut() {
+                s.goal_details = !s.goal_details;
+            }
+        }
+        AppCommand::TogglePlanGroup { group_id } => {
+            if let Screen::Plan(s) = state.current_screen_mut() {
+                s.toggle_group(&group_id);
             }
         }
+        AppCommand::ToggleFavoriteCategory { category_id } => {
+            toggle_favorite_category(state, &category_id);
+        }
 
         AppCommand::ExitTransactionCreateMode => {
             if let Screen::Transactions(s) = state.current_screen_mut() {
@@ -1859,6 +3761,15 @@ pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
         }
 
         // Log screen commands - can be handled synchronously
+        AppCommand::NavigateToSettings => {
+            state.navigate_to(Screen::Settings(SettingsState::default()));
+        }
+        AppCommand::ChangeSetting { forward } => {
+            change_setting(state, forward);
+        }
+        AppCommand::AcceptCategorySuggestion { transaction_id, .. } => {
+            accept_category_suggestion(state, &transaction_id);
+        }
         AppCommand::NavigateToLogs => {
             state.navigate_to(Screen::Logs(LogsState::default()));
         }
@@ -1869,72 +3780,375 @@ pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
                 }
             }
         }
-        AppCommand::ScrollLogsDown => {
-            if let Screen::Logs(s) = state.current_screen_mut() {
-                s.scroll_offset = s.scroll_offset.saturating_sub(1);
+        AppCommand::ScrollLogsDown => {
+            if let Screen::Logs(s) = state.current_screen_mut() {
+                s.scroll_offset = s.scroll_offset.saturating_sub(1);
+            }
+        }
+        AppCommand::ScrollLogsPageUp => {
+            if let Screen::Logs(s) = state.current_screen_mut() {
+                s.scroll_offset = (s.scroll_offset + 20).min(s.total_entries.saturating_sub(1));
+            }
+        }
+        AppCommand::ScrollLogsPageDown => {
+            if let Screen::Logs(s) = state.current_screen_mut() {
+                s.scroll_offset = s.scroll_offset.saturating_sub(20);
+            }
+        }
+        AppCommand::ScrollLogsToTop => {
+            if let Screen::Logs(s) = state.current_screen_mut() {
+                s.scroll_offset = s.total_entries.saturating_sub(1);
+            }
+        }
+        AppCommand::ScrollLogsToBottom => {
+            if let Screen::Logs(s) = state.current_screen_mut() {
+                s.scroll_offset = 0;
+            }
+        }
+
+        AppCommand::ToggleVarianceSort => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.variance_sort = match reports_state.variance_sort {
+                    VarianceSort::Category => VarianceSort::Variance,
+                    VarianceSort::Variance => VarianceSort::Category,
+                };
+                reports_state.table_state.borrow_mut().select(Some(0));
+            }
+        }
+
+        AppCommand::CloseReportDrillDown => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                reports_state.drill_down = None;
+            }
+        }
+
+        AppCommand::NavigateToInspector { focus_id } => {
+            if state.config.debug.inspector {
+                state.navigate_to(Screen::Inspector(InspectorState::new(focus_id)));
+            }
+        }
+        AppCommand::ScrollInspectorDetail { down } => {
+            if let Screen::Inspector(s) = state.current_screen_mut() {
+                s.scroll_detail(down);
+            }
+        }
+
+        // Budget edit mode (sync state changes only)
+        AppCommand::InitiateBudgetEdit { category_id } => {
+            let currency = state.currency_format();
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(category) = plan_state
+                    .categories
+                    .iter()
+                    .find(|c| c.id.to_string() == category_id)
+                {
+                    plan_state.input_mode = InputMode::BudgetEdit;
+                    plan_state.budget_form = Some(BudgetFormState::new(
+                        category.id.to_string(),
+                        category.name.clone(),
+                        category.budgeted.into(),
+                        currency.as_ref(),
+                    ));
+                }
+            }
+        }
+
+        AppCommand::ExitBudgetEditMode => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.budget_form = None;
+            }
+        }
+
+        AppCommand::AppendBudgetChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.budget_form {
+                    form.budgeted_input.push(c);
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::DeleteBudgetChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.budget_form {
+                    form.budgeted_input.pop();
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::OpenGoalCalculator { category_id } => {
+            let currency = state.currency_format();
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                let from_month = plan_state
+                    .month
+                    .as_ref()
+                    .and_then(|m| utils::goals::parse_month(&m.month));
+                if let (Some(category), Some(from_month)) = (
+                    plan_state
+                        .categories
+                        .iter()
+                        .find(|c| c.id.to_string() == category_id),
+                    from_month,
+                ) {
+                    plan_state.input_mode = InputMode::GoalCalculator;
+                    plan_state.goal_calculator = Some(GoalCalculatorState::new(
+                        category,
+                        from_month,
+                        currency.as_ref(),
+                    ));
+                }
+            }
+        }
+
+        AppCommand::CloseGoalCalculator => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.goal_calculator = None;
+            }
+        }
+
+        AppCommand::ToggleGoalCalculatorField => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut calculator) = plan_state.goal_calculator {
+                    calculator.field = match calculator.field {
+                        GoalCalculatorField::Monthly => GoalCalculatorField::TargetMonth,
+                        GoalCalculatorField::TargetMonth => GoalCalculatorField::Monthly,
+                    };
+                }
+            }
+        }
+
+        AppCommand::AppendGoalCalculatorChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut calculator) = plan_state.goal_calculator {
+                    calculator.focused_input_mut().push(c);
+                }
+            }
+        }
+
+        AppCommand::DeleteGoalCalculatorChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut calculator) = plan_state.goal_calculator {
+                    calculator.focused_input_mut().pop();
+                }
+            }
+        }
+
+        AppCommand::OpenGoalForm { category_id } => {
+            let currency = state.currency_format();
+            let mut no_goal = false;
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(category) = plan_state
+                    .categories
+                    .iter()
+                    .find(|c| c.id.to_string() == category_id)
+                {
+                    match GoalFormState::new(category, currency.as_ref()) {
+                        Some(form) => {
+                            plan_state.goal_form = Some(form);
+                            plan_state.input_mode = InputMode::GoalForm;
+                        }
+                        // YNAB's API can change a goal but not create one
+                        None => no_goal = true,
+                    }
+                }
+            }
+            if no_goal {
+                state.notice = Some(t("notice-goal-not-set").to_string());
+            }
+        }
+
+        AppCommand::CloseGoalForm => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.goal_form = None;
+            }
+        }
+
+        AppCommand::ToggleGoalFormField => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.goal_form {
+                    form.field = match form.field {
+                        GoalFormField::Target => GoalFormField::TargetMonth,
+                        GoalFormField::TargetMonth => GoalFormField::Target,
+                    };
+                }
+            }
+        }
+
+        AppCommand::AppendGoalFormChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.goal_form {
+                    form.focused_input_mut().push(c);
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::DeleteGoalFormChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.goal_form {
+                    form.focused_input_mut().pop();
+                    form.validation_error = None;
+                }
+            }
+        }
+
+        AppCommand::OpenMoveMoney { category_id } => {
+            let currency = state.currency_format();
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(source) = plan_state
+                    .categories
+                    .iter()
+                    .find(|c| c.id.to_string() == category_id)
+                {
+                    plan_state.move_money = Some(MoveMoneyState::new(
+                        source,
+                        &plan_state.categories,
+                        currency.as_ref(),
+                    ));
+                    plan_state.input_mode = InputMode::MoveMoney;
+                }
+            }
+        }
+
+        AppCommand::CloseMoveMoney => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.move_money = None;
+            }
+        }
+
+        AppCommand::ToggleMoveMoneyField => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    form.field = match form.field {
+                        MoveMoneyField::Amount => MoveMoneyField::Destination,
+                        MoveMoneyField::Destination => MoveMoneyField::Amount,
+                    };
+                }
+            }
+        }
+
+        AppCommand::AppendMoveMoneyChar(c) => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    form.focused_input_mut().push(c);
+                    form.validation_error = None;
+                    if form.field == MoveMoneyField::Destination {
+                        form.update_destinations(&plan_state.categories);
+                    }
+                }
+            }
+        }
+
+        AppCommand::DeleteMoveMoneyChar => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    form.focused_input_mut().pop();
+                    form.validation_error = None;
+                    if form.field == MoveMoneyField::Destination {
+                        form.update_destinations(&plan_state.categories);
+                    }
+                }
+            }
+        }
+
+        AppCommand::MoveMoneyDestinationCursor { down } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut form) = plan_state.move_money {
+                    let last = form.destinations.len().saturating_sub(1);
+                    form.selected_destination = if down {
+                        (form.selected_destination + 1).min(last)
+                    } else {
+                        form.selected_destination.saturating_sub(1)
+                    };
+                }
             }
         }
-        AppCommand::ScrollLogsPageUp => {
-            if let Screen::Logs(s) = state.current_screen_mut() {
-                s.scroll_offset = (s.scroll_offset + 20).min(s.total_entries.saturating_sub(1));
+
+        AppCommand::CloseArchiveReview => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.archive_review = None;
             }
         }
-        AppCommand::ScrollLogsPageDown => {
-            if let Screen::Logs(s) = state.current_screen_mut() {
-                s.scroll_offset = s.scroll_offset.saturating_sub(20);
+
+        AppCommand::CloseQuickBudget => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.input_mode = InputMode::Normal;
+                plan_state.quick_budget = None;
             }
         }
-        AppCommand::ScrollLogsToTop => {
-            if let Screen::Logs(s) = state.current_screen_mut() {
-                s.scroll_offset = s.total_entries.saturating_sub(1);
+
+        AppCommand::MoveQuickBudgetCursor { down } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut quick) = plan_state.quick_budget {
+                    if down {
+                        quick.select_next();
+                    } else {
+                        quick.select_prev();
+                    }
+                }
             }
         }
-        AppCommand::ScrollLogsToBottom => {
-            if let Screen::Logs(s) = state.current_screen_mut() {
-                s.scroll_offset = 0;
+
+        AppCommand::MoveArchiveCursor { down } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut review) = plan_state.archive_review {
+                    if down {
+                        review.select_next();
+                    } else {
+                        review.select_prev();
+                    }
+                }
             }
         }
 
-        // Budget edit mode (sync state changes only)
-        AppCommand::InitiateBudgetEdit { category_id } => {
+        AppCommand::ToggleArchiveSuggestion => {
             if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                if let Some(category) = plan_state
-                    .categories
-                    .iter()
-                    .find(|c| c.id.to_string() == category_id)
-                {
-                    plan_state.input_mode = InputMode::BudgetEdit;
-                    plan_state.budget_form = Some(BudgetFormState::new(
-                        category.id.to_string(),
-                        category.name.clone(),
-                        category.budgeted.into(),
-                    ));
+                if let Some(ref mut review) = plan_state.archive_review {
+                    review.toggle_selected();
                 }
             }
         }
 
-        AppCommand::ExitBudgetEditMode => {
+        AppCommand::CloseHealthChecklist => {
             if let Screen::Plan(plan_state) = state.current_screen_mut() {
                 plan_state.input_mode = InputMode::Normal;
-                plan_state.budget_form = None;
+                plan_state.health = None;
             }
         }
 
-        AppCommand::AppendBudgetChar(c) => {
+        AppCommand::MoveHealthCursor { down } => {
             if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                if let Some(ref mut form) = plan_state.budget_form {
-                    form.budgeted_input.push(c);
-                    form.validation_error = None;
+                if let Some(ref mut health) = plan_state.health {
+                    if down {
+                        health.select_next();
+                    } else {
+                        health.select_prev();
+                    }
                 }
             }
         }
 
-        AppCommand::DeleteBudgetChar => {
-            if let Screen::Plan(plan_state) = state.current_screen_mut() {
-                if let Some(ref mut form) = plan_state.budget_form {
-                    form.budgeted_input.pop();
-                    form.validation_error = None;
-                }
+        AppCommand::TogglePayeeCluster => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.toggle_selected();
+            }
+        }
+
+        AppCommand::OpenPayeeMergeConfirmation => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.confirm_merge = true;
+            }
+        }
+
+        AppCommand::CancelPayeeMerge => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                cleanup_state.confirm_merge = false;
             }
         }
 
@@ -1943,9 +4157,50 @@ pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
         AppCommand::LoadBudgets { .. }
         | AppCommand::LoadAccounts { .. }
         | AppCommand::LoadTransactions { .. }
+        | AppCommand::LoadOlderTransactions { .. }
+        | AppCommand::NavigateToTransactionSearch { .. }
+        | AppCommand::LoadBudgetTransactions { .. }
+        | AppCommand::NavigateToInbox { .. }
+        | AppCommand::LoadInbox { .. }
         | AppCommand::LoadPlan { .. }
         | AppCommand::LoadPlanMonth { .. }
         | AppCommand::NavigatePlanMonth { .. }
+        | AppCommand::TogglePlanComparison
+        | AppCommand::ShiftPlanComparison { .. }
+        | AppCommand::RefreshAll { .. }
+        | AppCommand::CreateFollowUp { .. }
+        | AppCommand::OpenPayeeLocation { .. }
+        | AppCommand::ExportTransactions
+        | AppCommand::NavigateToReimbursements { .. }
+        | AppCommand::LoadReimbursements { .. }
+        | AppCommand::NavigateToNetWorth { .. }
+        | AppCommand::LoadNetWorth { .. }
+        | AppCommand::NavigateToScheduledTransactions { .. }
+        | AppCommand::LoadScheduledTransactions { .. }
+        | AppCommand::NavigateToPayeeCleanup { .. }
+        | AppCommand::LoadPayeeClusters { .. }
+        | AppCommand::ApplyPayeeRenames { .. }
+        | AppCommand::MergePayees { .. }
+        | AppCommand::ToggleAwaitingReimbursement { .. }
+        | AppCommand::MarkReimbursed { .. }
+        | AppCommand::NavigateToReports { .. }
+        | AppCommand::LoadVarianceReport { .. }
+        | AppCommand::DrillIntoVarianceCategory { .. }
+        | AppCommand::LoadCashFlowProjection { .. }
+        | AppCommand::LoadSpendingHeatmap { .. }
+        | AppCommand::LoadSpendingBreakdown { .. }
+        | AppCommand::LoadIncomeExpense { .. }
+        | AppCommand::LoadGoalObligations { .. }
+        | AppCommand::NavigateToTransactionsOnDate { .. }
+        | AppCommand::OpenArchiveReview { .. }
+        | AppCommand::OpenHealthChecklist { .. }
+        | AppCommand::FixHealthItem { .. }
+        | AppCommand::ArchiveCategories { .. }
+        | AppCommand::OpenQuickBudget { .. }
+        | AppCommand::ApplyQuickBudget { .. }
+        | AppCommand::MoveCategoryToGroup { .. }
+        | AppCommand::PreviewYnabExport { .. }
+        | AppCommand::ImportYnabExport { .. }
         | AppCommand::LoadPayees { .. }
         | AppCommand::LoadCategories { .. }
         | AppCommand::ToggleTransactionCleared { .. }
@@ -1954,6 +4209,10 @@ pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
         | AppCommand::AppendFormFieldChar { .. }
         | AppCommand::DeleteFormFieldChar
         | AppCommand::ClearFormField
+        | AppCommand::FillFormFromLastUsed
+        | AppCommand::ToggleAmountSign
+        | AppCommand::ConfirmExchangeRate
+        | AppCommand::CancelExchangeRate
         | AppCommand::SelectAutocompleteItem { .. }
         | AppCommand::ConfirmAutocompleteSelection
         | AppCommand::SubmitTransactionForm
@@ -1961,17 +4220,30 @@ pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
         | AppCommand::AddSubtransaction
         | AppCommand::DeleteSubtransaction
         | AppCommand::ApproveTransaction { .. }
+        | AppCommand::ApproveTransactions { .. }
+        | AppCommand::ClearTransactionFlags { .. }
+        | AppCommand::ApplyPayeeRules { .. }
+        | AppCommand::ImportLinkedTransactions { .. }
+        | AppCommand::CycleTransactionFlag { .. }
+        | AppCommand::JumpToTransfer { .. }
+        | AppCommand::PasteTransaction { .. }
+        | AppCommand::CopyToClipboard(..)
         | AppCommand::InitiateTransactionDelete { .. }
         | AppCommand::ConfirmTransactionDelete { .. }
         | AppCommand::CancelTransactionDelete
         | AppCommand::InitiateTransactionEdit { .. }
+        | AppCommand::CategorizeTransaction { .. }
         | AppCommand::ConfirmReconciledEdit { .. }
         | AppCommand::CancelReconciledEdit
         | AppCommand::EnterTransactionEditMode { .. }
         | AppCommand::InitiateReconcile { .. }
         | AppCommand::ConfirmReconcile { .. }
         | AppCommand::CancelReconcile
-        | AppCommand::SubmitBudgetEdit { .. } => {
+        | AppCommand::SubmitBudgetEdit { .. }
+        | AppCommand::SubmitMoveMoney { .. }
+        | AppCommand::SubmitGoalForm { .. }
+        | AppCommand::Undo
+        | AppCommand::Redo => {
             // Skip - tests will inject corresponding DataEvents
         }
     }
@@ -1980,11 +4252,674 @@ pub fn execute_command_sync(command: AppCommand, state: &mut AppState) {
     if !is_setting_pending_key && state.pending_key.is_some() {
         state.pending_key = None;
     }
+    if !is_setting_pending_count {
+        state.pending_count = None;
+    }
+}
+
+/// Set a transaction's cleared status optimistically and send it to YNAB,
+/// rolling back if the request fails
+fn set_transaction_cleared(
+    state: &mut AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+    budget_id: &str,
+    transaction_id: &str,
+    new_status: ReconciliationStatus,
+) {
+    let approve = state.config.approval.on_clear;
+    // Undo can target a transaction that isn't on screen; there's nothing to roll back then
+    let mut original_status = new_status;
+    let mut original_approved = true;
+    if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+        if let Some(transaction) = transactions_state
+            .transactions
+            .iter_mut()
+            .find(|t| t.id.to_string() == transaction_id)
+        {
+            original_status = transaction.cleared;
+            original_approved = transaction.approved;
+            transaction.cleared = new_status;
+            transaction.approved |= approve;
+            transactions_state
+                .unsaved
+                .insert(transaction_id.to_string());
+
+            tracing::info!(
+                "Optimistically set transaction {} from {} to {}",
+                transaction_id,
+                original_status,
+                new_status
+            );
+        }
+    }
+
+    // Spawn background task to update via API
+    let loader = data_loader.clone();
+    let data_tx = data_loader.data_tx.clone();
+    let transaction_id_clone = transaction_id.to_string();
+    let budget_id_clone = budget_id.to_string();
+
+    let future = async move {
+        let budget_id_api: BudgetId = budget_id_clone.into();
+        let transaction_id: TransactionId = transaction_id_clone
+            .parse()
+            .expect("invalid transaction id");
+        let mut req = Request::transactions()
+            .with_budget(budget_id_api)
+            .update(transaction_id)
+            .cleared(new_status);
+        if approve {
+            req = req.approved(true);
+        }
+
+        match loader.send(req).await {
+            Ok(_) => {
+                tracing::info!(
+                    "Transaction {} updated successfully on server",
+                    transaction_id_clone
+                );
+                let _ = data_tx.send(
+                    TransactionEvent::TransactionUpdated {
+                        transaction_id: transaction_id_clone,
+                    }
+                    .into(),
+                );
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to update transaction {}: {}",
+                    transaction_id_clone,
+                    e
+                );
+                let _ = data_tx.send(
+                    TransactionEvent::TransactionUpdateFailed {
+                        transaction_id: transaction_id_clone,
+                        original_status,
+                        original_approved,
+                        error: e.into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    };
+
+    task_manager.spawn_load_task(format!("update_transaction_{}", transaction_id), future);
+}
+
+/// Category balances for the header above the transaction list
+/// Each payee's most used category, to suggest for new transactions
+/// Show a transaction on the current screen in the detail popup, returning
+/// the payee whose location should be looked up
+fn open_transaction_detail(state: &mut AppState, transaction_id: &str) -> Option<String> {
+    let Screen::Transactions(transactions_state) = state.current_screen() else {
+        return None;
+    };
+    let transaction = transactions_state
+        .transactions
+        .iter()
+        .find(|t| t.id.to_string() == transaction_id)?
+        .clone();
+    let payee_id = transaction.payee_id.map(|id| id.to_string());
+    let location = match payee_id {
+        Some(_) => PayeeLocationLookup::Loading,
+        None => PayeeLocationLookup::Missing,
+    };
+    state.transaction_detail = Some(TransactionDetail {
+        transaction,
+        location,
+    });
+    payee_id
+}
+
+fn load_category_suggestions(
+    budget_id: &str,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+) {
+    let data_loader = data_loader.clone();
+    let budget_id_clone = budget_id.to_string();
+    task_manager.spawn_load_task(
+        format!("load_category_suggestions_{}", budget_id),
+        async move { data_loader.load_category_suggestions(budget_id_clone).await },
+    );
+}
+
+/// Give a transaction its suggested category on screen, approving it too
+/// when saves approve, and select the next row with a suggestion. Returns
+/// the transaction as it was and the category, to send to YNAB.
+fn accept_category_suggestion(
+    state: &mut AppState,
+    transaction_id: &str,
+) -> Option<(Transaction, Uuid)> {
+    let approve = state.config.approval.on_save;
+    let Screen::Transactions(transactions_state) = state.current_screen_mut() else {
+        return None;
+    };
+    let transaction = transactions_state
+        .transactions
+        .iter()
+        .find(|t| t.id.to_string() == transaction_id)?;
+    let suggestion = transactions_state.suggested_category(transaction)?.clone();
+    let original = transaction.clone();
+
+    let transaction = transactions_state
+        .transactions
+        .iter_mut()
+        .find(|t| t.id.to_string() == transaction_id)?;
+    transaction.category_id = Some(suggestion.category_id);
+    transaction.category_name = Some(suggestion.category_name);
+    transaction.approved |= approve;
+    transactions_state
+        .unsaved
+        .insert(transaction_id.to_string());
+
+    // The accepted row has no suggestion now, so this finds the next one
+    // whether or not it's still listed
+    let selected = transactions_state
+        .table_state
+        .borrow()
+        .selected()
+        .unwrap_or(0);
+    let next = transactions_state
+        .filtered_transactions()
+        .iter()
+        .skip(selected)
+        .position(|t| transactions_state.suggested_category(t).is_some());
+    if let Some(offset) = next {
+        transactions_state
+            .table_state
+            .borrow_mut()
+            .select(Some(selected + offset));
+    }
+    Some((original, suggestion.category_id))
+}
+
+fn load_category_balances(
+    budget_id: &str,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+) {
+    let data_loader = data_loader.clone();
+    let budget_id_clone = budget_id.to_string();
+    task_manager.spawn_load_task(
+        format!("load_category_balances_{}", budget_id),
+        async move { data_loader.load_category_balances(budget_id_clone).await },
+    );
+}
+
+/// Take back a recorded change (`undo`), or apply it again
+fn apply_undo_entry(
+    entry: &UndoEntry,
+    undo: bool,
+    state: &mut AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+) {
+    match entry.clone() {
+        UndoEntry::TransactionDeleted {
+            budget_id,
+            transaction,
+        } => {
+            if undo {
+                let data_loader = data_loader.clone();
+                let task_id = format!("restore_transaction_{}", transaction.id);
+                task_manager.spawn_load_task(task_id, async move {
+                    data_loader
+                        .restore_transaction(budget_id, *transaction)
+                        .await
+                });
+            } else {
+                let transaction_id = transaction.id.to_string();
+                delete_transaction(
+                    state,
+                    task_manager,
+                    data_loader,
+                    &budget_id,
+                    &transaction_id,
+                );
+            }
+        }
+        UndoEntry::BudgetEdited {
+            budget_id,
+            month,
+            category_id,
+            from,
+            to,
+        } => {
+            let (from, to) = if undo { (to, from) } else { (from, to) };
+            set_category_budgeted(
+                state,
+                task_manager,
+                data_loader,
+                &budget_id,
+                BudgetEdit {
+                    month,
+                    category_id,
+                    from,
+                    to,
+                },
+            );
+        }
+        UndoEntry::BudgetsEdited {
+            budget_id,
+            month,
+            edits,
+        } => {
+            for (category_id, from, to) in edits {
+                let (from, to) = if undo { (to, from) } else { (from, to) };
+                set_category_budgeted(
+                    state,
+                    task_manager,
+                    data_loader,
+                    &budget_id,
+                    BudgetEdit {
+                        month: month.clone(),
+                        category_id,
+                        from,
+                        to,
+                    },
+                );
+            }
+        }
+        UndoEntry::MoneyMoved {
+            budget_id,
+            month,
+            source_id,
+            source_budgeted,
+            destination_id,
+            destination_budgeted,
+            amount,
+        } => {
+            let moved = if undo { 0 } else { amount };
+            let was_moved = amount - moved;
+            for (category_id, budgeted, sign) in [
+                (source_id, source_budgeted, -1),
+                (destination_id, destination_budgeted, 1),
+            ] {
+                set_category_budgeted(
+                    state,
+                    task_manager,
+                    data_loader,
+                    &budget_id,
+                    BudgetEdit {
+                        month: month.clone(),
+                        category_id,
+                        from: budgeted + sign * was_moved,
+                        to: budgeted + sign * moved,
+                    },
+                );
+            }
+        }
+        UndoEntry::ClearedToggled {
+            budget_id,
+            transaction_id,
+            from,
+            to,
+        } => {
+            let status = if undo { from } else { to };
+            set_transaction_cleared(
+                state,
+                task_manager,
+                data_loader,
+                &budget_id,
+                &transaction_id,
+                status,
+            );
+        }
+    }
+}
+
+fn undo_label(entry: &UndoEntry) -> &'static str {
+    match entry {
+        UndoEntry::TransactionDeleted { .. } => t("undo-transaction-delete"),
+        UndoEntry::BudgetEdited { .. } => t("undo-budget-edit"),
+        UndoEntry::BudgetsEdited { .. } => t("undo-quick-budget"),
+        UndoEntry::MoneyMoved { .. } => t("undo-money-move"),
+        UndoEntry::ClearedToggled { .. } => t("undo-cleared-toggle"),
+    }
+}
+
+/// Remove a transaction optimistically and delete it in YNAB
+fn delete_transaction(
+    state: &mut AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+    budget_id: &str,
+    transaction_id: &str,
+) {
+    if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+        transactions_state
+            .transactions
+            .retain(|t| t.id.to_string() != transaction_id);
+
+        // Keep the selection on a row that still exists
+        let num_transactions = transactions_state.filtered_transactions().len();
+        let mut table_state = transactions_state.table_state.borrow_mut();
+        if let Some(selected) = table_state.selected() {
+            if selected >= num_transactions && num_transactions > 0 {
+                table_state.select(Some(num_transactions - 1));
+            } else if num_transactions == 0 {
+                table_state.select(None);
+            }
+        }
+    }
+
+    // Spawn background task to call DELETE API
+    let loader = data_loader.clone();
+    let budget_id = budget_id.to_string();
+    let transaction_id_clone = transaction_id.to_string();
+    let future = async move {
+        loader
+            .delete_transaction(budget_id, transaction_id_clone)
+            .await;
+    };
+
+    task_manager.spawn_load_task(format!("delete_transaction_{}", transaction_id), future);
+}
+
+/// A category's assigned amount changing from one value to another
+struct BudgetEdit {
+    month: String,
+    category_id: String,
+    from: i64,
+    to: i64,
+}
+
+/// Change a category's assigned amount on screen and send it to YNAB
+fn set_category_budgeted(
+    state: &mut AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+    budget_id: &str,
+    edit: BudgetEdit,
+) {
+    let BudgetEdit {
+        month,
+        category_id,
+        from,
+        to,
+    } = edit;
+    use ynab_api::endpoints::Milliunits;
+
+    if let Screen::Plan(plan_state) = state.current_screen_mut() {
+        let showing_month = plan_state.month.as_ref().is_some_and(|m| m.month == month);
+        if showing_month {
+            let delta: Milliunits = (to - from).into();
+            if let Some(category) = plan_state
+                .categories
+                .iter_mut()
+                .find(|c| c.id.to_string() == category_id)
+            {
+                category.budgeted = to.into();
+                category.balance += delta;
+            }
+
+            // Update month summary (budgeted increases, to_be_budgeted decreases)
+            if let Some(ref mut month_detail) = plan_state.month {
+                month_detail.budgeted += delta;
+                month_detail.to_be_budgeted -= delta;
+            }
+        }
+    }
+
+    // Spawn background task to update via API
+    let data_loader = data_loader.clone();
+    let budget_id = budget_id.to_string();
+    let task_id = format!("update_budget_{}", category_id);
+    let future = async move {
+        data_loader
+            .update_category_budget(budget_id, month, category_id, to, from)
+            .await;
+    };
+
+    task_manager.spawn_load_task(task_id, future);
+}
+
+/// Write the current budget's preferences in the background after one changed
+/// Change the setting selected on the settings screen, queueing the
+/// `config.toml` edit for the app to write. Returns whether the budget's
+/// settings need saving instead.
+fn change_setting(state: &mut AppState, forward: bool) -> bool {
+    let Screen::Settings(settings_state) = state.current_screen() else {
+        return false;
+    };
+    let Some(option) = settings_state.selected_option() else {
+        return false;
+    };
+    match option.change(state, forward) {
+        Saved::Config(edit) => {
+            state.config_edits.push(edit);
+            false
+        }
+        Saved::Budget => true,
+    }
+}
+
+fn save_budget_settings(
+    state: &AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+) {
+    let Some(budget_id) = state.current_budget_id.clone() else {
+        return;
+    };
+    let settings = state.budget_settings.clone();
+    let data_loader = data_loader.clone();
+    task_manager.spawn_load_task(format!("save_budget_settings_{}", budget_id), async move {
+        data_loader.save_budget_settings(budget_id, settings).await
+    });
+}
+
+/// Give an account the next reconcile reminder cadence and say which it got
+fn cycle_reconcile_reminder(state: &mut AppState, account_id: &str) {
+    let Screen::Accounts(accounts_state) = state.current_screen() else {
+        return;
+    };
+    let Some(name) = accounts_state
+        .accounts
+        .iter()
+        .find(|a| a.id.to_string() == account_id)
+        .map(|a| a.name.clone())
+    else {
+        return;
+    };
+
+    let reminders = &mut state.budget_settings.reconcile_reminders;
+    let notice = match ReconcileCadence::cycle(reminders.get(account_id).copied()) {
+        Some(cadence) => {
+            reminders.insert(account_id.to_string(), cadence);
+            t("notice-reconcile-reminder-set").replace("{cadence}", cadence.label())
+        }
+        None => {
+            reminders.remove(account_id);
+            t("notice-reconcile-reminder-off").to_string()
+        }
+    };
+    state.notice = Some(notice.replace("{account}", &name));
+}
+
+/// Add `id` to the starred ids, or take it out if it's there. Returns
+/// whether it's starred now.
+fn toggle_favorite(favorites: &mut Vec<String>, id: &str) -> bool {
+    match favorites.iter().position(|f| f == id) {
+        Some(index) => {
+            favorites.remove(index);
+            false
+        }
+        None => {
+            favorites.push(id.to_string());
+            true
+        }
+    }
+}
+
+/// Star or unstar an account, keeping the cursor on it as it moves
+fn toggle_favorite_account(state: &mut AppState, account_id: &str) {
+    let Screen::Accounts(accounts_state) = state.current_screen() else {
+        return;
+    };
+    let Some(name) = accounts_state
+        .accounts
+        .iter()
+        .find(|a| a.id.to_string() == account_id)
+        .map(|a| a.name.clone())
+    else {
+        return;
+    };
+
+    let favorites = &mut state.budget_settings.favorite_account_ids;
+    let starred = toggle_favorite(favorites, account_id);
+    let favorites = favorites.clone();
+    if let Screen::Accounts(accounts_state) = state.current_screen_mut() {
+        accounts_state.favorite_ids = favorites;
+        let position = accounts_state
+            .filtered_accounts()
+            .iter()
+            .position(|a| a.id.to_string() == account_id);
+        accounts_state.table_state.borrow_mut().select(position);
+    }
+    let notice = if starred {
+        t("notice-favorite-added")
+    } else {
+        t("notice-favorite-removed")
+    };
+    state.notice = Some(notice.replace("{name}", &name));
+}
+
+/// Star or unstar a category, keeping the cursor on it in its own group
+fn toggle_favorite_category(state: &mut AppState, category_id: &str) {
+    let Screen::Plan(plan_state) = state.current_screen() else {
+        return;
+    };
+    let Some(name) = plan_state
+        .categories
+        .iter()
+        .find(|c| c.id.to_string() == category_id)
+        .map(|c| c.name.clone())
+    else {
+        return;
+    };
+
+    let favorites = &mut state.budget_settings.favorite_category_ids;
+    let starred = toggle_favorite(favorites, category_id);
+    let favorites = favorites.clone();
+    if let Screen::Plan(plan_state) = state.current_screen_mut() {
+        plan_state.favorite_ids = favorites;
+        let position = plan_state.rows().iter().rposition(
+            |row| matches!(row, PlanRow::Category(c) if c.id.to_string() == category_id),
+        );
+        if position.is_some() {
+            plan_state.table_state.borrow_mut().select(position);
+        }
+    }
+    let notice = if starred {
+        t("notice-favorite-added")
+    } else {
+        t("notice-favorite-removed")
+    };
+    state.notice = Some(notice.replace("{name}", &name));
+}
+
+/// Remember a transaction on the current screen for pasting as a copy
+fn yank_transaction(state: &mut AppState, transaction_id: &str) {
+    let Screen::Transactions(trans_state) = state.current_screen() else {
+        return;
+    };
+    let yanked = trans_state
+        .transactions
+        .iter()
+        .find(|t| t.id.to_string() == transaction_id)
+        .cloned();
+    if yanked.is_some() {
+        state.yanked_transaction = yanked;
+        tracing::info!(
+            "Copied transaction {} (p to paste dated today, P to edit first)",
+            transaction_id
+        );
+    }
+}
+
+/// The foreign amount typed into the open form, with its rate if one is known
+fn pending_foreign_amount(state: &AppState) -> Option<(ForeignAmount, Option<f64>)> {
+    let Screen::Transactions(trans_state) = state.current_screen() else {
+        return None;
+    };
+    let form = trans_state.form_state.as_ref()?;
+    let amount = ForeignAmount::parse(&form.amount)?;
+    let rate = state.exchange_rate(&amount.currency);
+    Some((amount, rate))
+}
+
+/// Convert a foreign amount if there's a rate for it, or ask for one.
+/// Returns false while the form is waiting on the rate prompt.
+fn apply_foreign_amount(
+    form: &mut TransactionFormState,
+    amount: ForeignAmount,
+    rate: Option<f64>,
+) -> bool {
+    match rate {
+        Some(rate) => {
+            convert_form_amount(form, &amount, rate);
+            true
+        }
+        None => {
+            form.rate_prompt = Some(RatePrompt {
+                amount,
+                rate: String::new(),
+            });
+            false
+        }
+    }
+}
+
+/// Replace a foreign amount with the converted one, noting the original in the memo
+fn convert_form_amount(form: &mut TransactionFormState, amount: &ForeignAmount, rate: f64) {
+    let converted = amount.convert(rate, form.rounding);
+    form.amount = money::format_input(converted, form.currency_format.as_ref());
+    form.memo = currency::append_to_memo(&form.memo, &amount.memo_note(rate));
+}
+
+/// Next flag color in YNAB's order, clearing the flag after the last one
+fn next_flag_color(flag: Option<FlagColor>) -> Option<FlagColor> {
+    use FlagColor::*;
+    match flag {
+        None => Some(Red),
+        Some(Red) => Some(Orange),
+        Some(Orange) => Some(Yellow),
+        Some(Yellow) => Some(Green),
+        Some(Green) => Some(Blue),
+        Some(Blue) => Some(Purple),
+        Some(Purple) => None,
+    }
 }
 
 /// Compute the adjacent month (next or previous) from a given month string
 /// Input format: YYYY-MM-DD (first day of month)
 /// Returns the first day of the next/previous month in the same format
+/// Start comparing the plan against `month`, through the month cache
+fn compare_plan_month(
+    state: &mut AppState,
+    task_manager: &mut BackgroundTaskManager,
+    data_loader: &DataLoader,
+    month: String,
+) {
+    let Some(budget_id) = state.current_budget_id.clone() else {
+        return;
+    };
+    if let Screen::Plan(plan_state) = state.current_screen_mut() {
+        plan_state.comparison = Some(PlanComparison::new(month.clone()));
+    }
+
+    let loader = data_loader.clone();
+    let task_id = format!("load_comparison_{}_{}", budget_id, month);
+    let future = async move {
+        loader.load_comparison_month(budget_id, month).await;
+    };
+    task_manager.spawn_load_task(task_id, future);
+}
+
 fn compute_adjacent_month(current_month: &str, forward: bool) -> Option<String> {
     use chrono::{Datelike, NaiveDate};
 
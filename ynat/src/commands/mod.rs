@@ -1,3 +1,4 @@
+pub mod context_menu;
 pub mod executor;
 pub mod handlers;
 
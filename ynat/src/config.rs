@@ -0,0 +1,477 @@
+//! User configuration for the TUI.
+//!
+//! Read from the same `config.toml` as the auth settings (or the path in
+//! `YNAB_TUI_CONFIG`). Every section is optional; a missing or invalid file
+//! falls back to defaults so the app always starts.
+
+use crate::i18n::t;
+use config::{Config, File};
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use ynab_api::endpoints::transactions::FlagColor;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub display: DisplayConfig,
+    pub animation: AnimationConfig,
+    pub debug: DebugConfig,
+    pub api: ApiConfig,
+    pub import: ImportConfig,
+    pub amounts: AmountsConfig,
+    pub export: ExportConfig,
+    pub follow_up: FollowUpConfig,
+    pub maps: MapsConfig,
+    pub colors: ColorsConfig,
+    pub lint: LintConfig,
+    pub approval: ApprovalConfig,
+    pub auto_refresh: AutoRefreshConfig,
+    pub startup: StartupConfig,
+    /// Payee rules for categorizing transactions in bulk, first match wins
+    pub rules: Vec<PayeeRule>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Local display names for categories. Only affects rendering; the real
+    /// category name is still used for autocomplete and API requests.
+    pub category_aliases: Vec<CategoryAlias>,
+    /// UI language, e.g. "de" loads `<config dir>/ynat/locales/de.ftl`. Defaults to English.
+    pub locale: Option<String>,
+    /// Reduced-redraw mode for slow connections: no spinner animation and no
+    /// periodic repaints. Defaults to on when running over SSH.
+    pub low_bandwidth: Option<bool>,
+    /// What each flag color stands for, e.g. `red = "Dispute"`
+    pub flag_names: FlagNames,
+    /// Date format instead of the budget's, e.g. "DD.MM.YYYY"
+    #[serde(deserialize_with = "non_empty")]
+    pub date_format: Option<String>,
+}
+
+/// An empty string counts as unset, which is how the settings screen
+/// writes it back
+fn non_empty<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.filter(|s| !s.is_empty()))
+}
+
+/// Names for the flag colors, shown on the transactions flag view
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FlagNames {
+    pub red: Option<String>,
+    pub orange: Option<String>,
+    pub yellow: Option<String>,
+    pub green: Option<String>,
+    pub blue: Option<String>,
+    pub purple: Option<String>,
+}
+
+impl FlagNames {
+    pub fn get(&self, color: FlagColor) -> Option<&str> {
+        match color {
+            FlagColor::Red => self.red.as_deref(),
+            FlagColor::Orange => self.orange.as_deref(),
+            FlagColor::Yellow => self.yellow.as_deref(),
+            FlagColor::Green => self.green.as_deref(),
+            FlagColor::Blue => self.blue.as_deref(),
+            FlagColor::Purple => self.purple.as_deref(),
+        }
+    }
+}
+
+/// Loading spinner settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AnimationConfig {
+    /// Animate the loading spinner. When off, a static "Loading…" is shown instead.
+    pub enabled: bool,
+    /// Milliseconds between spinner frames
+    pub interval_ms: u64,
+}
+
+impl Default for AnimationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_ms: 100,
+        }
+    }
+}
+
+/// Developer settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DebugConfig {
+    /// Record recent API responses and enable the payload inspector (`gi`)
+    pub inspector: bool,
+    /// Number of responses to keep
+    pub inspector_records: usize,
+    /// Record commands and data events to this file for `ynat replay`
+    pub record_session: Option<PathBuf>,
+}
+
+impl Default for DebugConfig {
+    fn default() -> Self {
+        Self {
+            inspector: false,
+            inspector_records: 50,
+            record_session: None,
+        }
+    }
+}
+
+/// How long to wait for YNAB before giving up on a request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// Seconds to wait for a read, e.g. loading accounts or transactions
+    pub list_timeout_secs: u64,
+    /// Seconds to wait for a change to be saved. Longer than reads, since
+    /// giving up doesn't undo a change YNAB is still working on.
+    pub mutation_timeout_secs: u64,
+    /// Never change anything in YNAB, even if the login allows it
+    pub read_only: bool,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            list_timeout_secs: 30,
+            mutation_timeout_secs: 60,
+            read_only: false,
+        }
+    }
+}
+
+/// Checking YNAB for changes made elsewhere without pressing `r`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AutoRefreshConfig {
+    /// Seconds between checks of the accounts, transactions or plan on
+    /// screen; 0 turns auto-refresh off
+    pub interval_secs: u64,
+}
+
+impl Default for AutoRefreshConfig {
+    fn default() -> Self {
+        Self { interval_secs: 300 }
+    }
+}
+
+/// Where a new session opens
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct StartupConfig {
+    /// Reopen the screen open at the last quit, as `--resume` does, instead
+    /// of the accounts list
+    pub restore_screen: bool,
+}
+
+/// When transactions entered in ynat are marked approved. Turn these off to
+/// keep approving as a separate review step, as for bank imports.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ApprovalConfig {
+    /// Approve transactions saved from the form, new or edited, and pasted copies
+    pub on_save: bool,
+    /// Approve a transaction when its cleared status is toggled
+    pub on_clear: bool,
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self {
+            on_save: true,
+            on_clear: true,
+        }
+    }
+}
+
+/// Data import settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportConfig {
+    /// Where to look for YNAB "Export budget data" CSVs. Defaults to the downloads directory.
+    pub directory: Option<PathBuf>,
+}
+
+impl ImportConfig {
+    pub fn directory(&self) -> PathBuf {
+        self.directory
+            .clone()
+            .or_else(dirs::download_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+}
+
+/// How amounts typed into the transaction form are treated
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AmountsConfig {
+    /// Rounding for new transactions, e.g. "0.05" where 1 and 2 cent coins aren't used
+    pub rounding: Rounding,
+    /// What one unit of a foreign currency is worth in the budget's, by ISO
+    /// code, for amounts typed as `=45 EUR`
+    pub rates: HashMap<String, f64>,
+}
+
+impl AmountsConfig {
+    pub fn rate(&self, currency: &str) -> Option<f64> {
+        self.rates
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(currency))
+            .map(|(_, rate)| *rate)
+    }
+}
+
+/// Rounding rule for entered amounts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum Rounding {
+    #[default]
+    #[serde(rename = "none")]
+    None,
+    /// Nearest whole unit
+    #[serde(rename = "unit")]
+    Unit,
+    /// Nearest 0.05
+    #[serde(rename = "0.05")]
+    FiveHundredths,
+}
+
+impl Rounding {
+    fn step(&self) -> Option<i64> {
+        match self {
+            Rounding::None => None,
+            Rounding::Unit => Some(1000),
+            Rounding::FiveHundredths => Some(50),
+        }
+    }
+
+    /// Round milliunits to the nearest step, halves away from zero
+    pub fn apply(&self, milliunits: i64) -> i64 {
+        match self.step() {
+            None => milliunits,
+            Some(step) => (milliunits + milliunits.signum() * step / 2) / step * step,
+        }
+    }
+
+    /// As written in `config.toml`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rounding::None => "none",
+            Rounding::Unit => "unit",
+            Rounding::FiveHundredths => "0.05",
+        }
+    }
+
+    /// Short description for the form, if rounding is on
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            Rounding::None => None,
+            Rounding::Unit => Some(t("form-rounding-unit")),
+            Rounding::FiveHundredths => Some(t("form-rounding-five-hundredths")),
+        }
+    }
+}
+
+/// Settings for files written by `ynat report` and other exports
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// Encrypt exported files with this tool before they touch the disk
+    pub encrypt: Option<EncryptionTool>,
+    /// age recipient (`age1…`) or GPG key id/email to encrypt to
+    pub recipient: Option<String>,
+    /// Where CSV exports from the TUI are written. Defaults to the current directory.
+    pub directory: Option<PathBuf>,
+}
+
+/// External encryption program used for exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncryptionTool {
+    Age,
+    Gpg,
+}
+
+/// Checks on the transaction form before saving; see [`crate::lint`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Off unless switched on
+    pub enabled: bool,
+    /// Flag new payee names that look like a typo of an existing payee
+    pub payee_spelling: bool,
+    /// Flag memos containing a card number
+    pub card_numbers: bool,
+    /// Flag memos containing a long run of digits, such as an account number
+    pub account_numbers: bool,
+    /// Digits in a row that count as an account number
+    pub account_digits: usize,
+    /// Flag memos containing any of these words (ignoring case), e.g. `["password"]`
+    pub words: Vec<String>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            payee_spelling: true,
+            card_numbers: true,
+            account_numbers: true,
+            account_digits: 8,
+            words: Vec::new(),
+        }
+    }
+}
+
+/// External command that records a follow-up for a transaction (`T`)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FollowUpConfig {
+    /// Program and arguments, e.g. `["task", "add", "Check {payee} {amount}"]`.
+    /// Empty disables the action.
+    pub command: Vec<String>,
+}
+
+/// How payee locations in the transaction detail popup (`L`) are shown
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MapsConfig {
+    /// Program and arguments that open a location on a map, with `{lat}` and
+    /// `{lon}` filled in. Defaults to opening OpenStreetMap with the system's
+    /// URL opener. Empty hides the action.
+    pub command: Vec<String>,
+    /// Reverse geocoding URL returning Nominatim-style JSON, with `{lat}` and
+    /// `{lon}` filled in, used to name the town. Empty (the default) keeps
+    /// coordinates on this machine.
+    pub geocoder: String,
+}
+
+impl Default for MapsConfig {
+    fn default() -> Self {
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        Self {
+            command: vec![
+                opener.to_string(),
+                "https://www.openstreetmap.org/?mlat={lat}&mlon={lon}#map=17/{lat}/{lon}"
+                    .to_string(),
+            ],
+            geocoder: String::new(),
+        }
+    }
+}
+
+/// Amount colors that depend on size rather than only on sign.
+///
+/// Amounts that don't fall under any threshold keep the usual green/gray/red.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ColorsConfig {
+    /// Thresholds for the Plan "Available" column
+    pub available: Vec<ColorThreshold>,
+    /// Thresholds for account balances
+    pub balances: Vec<ColorThreshold>,
+}
+
+/// Amounts below `below` (in the budget's currency, not milliunits) are drawn
+/// in `color`, e.g. `{ below = 20, color = "yellow" }`. When several match, the
+/// lowest threshold wins, so the order in the file doesn't matter.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ColorThreshold {
+    pub below: f64,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+}
+
+/// Color name (`"yellow"`, `"light-red"`), index (`"208"`) or hex (`"#ffaf00"`)
+fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    name.parse()
+        .map_err(|_| serde::de::Error::custom(format!("unknown color '{}'", name)))
+}
+
+/// Transactions whose payee matches `payee` go in `category`, e.g.
+/// `{ payee = "AMZN*", category = "Shopping" }`. Case is ignored and `*`
+/// matches anything; without one the whole payee name has to match.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PayeeRule {
+    pub payee: String,
+    pub category: String,
+}
+
+/// A shorter display name for a category, e.g. `{ name = "🛒 Groceries", alias = "🛒 Groc" }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CategoryAlias {
+    pub name: String,
+    pub alias: String,
+}
+
+impl AppConfig {
+    /// Where the config is read from: `YNAB_TUI_CONFIG`, a `config.toml` in
+    /// the working directory, or `config.toml` in the user's config directory
+    pub fn path() -> String {
+        ynat_auth::paths::config_file()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Load configuration, logging and falling back to defaults on error
+    pub fn load() -> Self {
+        let config_path = Self::path();
+
+        let result = Config::builder()
+            .add_source(File::with_name(&config_path).required(false))
+            .build()
+            .and_then(|settings| settings.try_deserialize::<AppConfig>());
+
+        match result {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("Failed to load config from {}: {}", config_path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Parse `config.toml` contents, failing on anything [`Self::load`] would
+    /// fall back from
+    pub fn parse(toml: &str) -> Result<Self, config::ConfigError> {
+        Config::builder()
+            .add_source(File::from_str(toml, config::FileFormat::Toml))
+            .build()?
+            .try_deserialize()
+    }
+}
+
+impl AppConfig {
+    /// Whether the spinner should animate, taking low-bandwidth mode into account
+    pub fn animations_enabled(&self) -> bool {
+        self.animation.enabled && !self.display.low_bandwidth_enabled()
+    }
+}
+
+impl DisplayConfig {
+    /// Whether low-bandwidth rendering is active, auto-detecting SSH sessions when unset
+    pub fn low_bandwidth_enabled(&self) -> bool {
+        self.low_bandwidth
+            .unwrap_or_else(|| std::env::var_os("SSH_CONNECTION").is_some())
+    }
+
+    /// Get the name to display for a category, using the configured alias if any
+    pub fn category_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.category_aliases
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.alias.as_str())
+            .unwrap_or(name)
+    }
+}
@@ -0,0 +1,236 @@
+//! `ynat config export` / `ynat config import`: everything that sets ynat up,
+//! in one file for copying to another machine.
+//!
+//! A bundle is JSON holding `config.toml` as written (comments and all), the
+//! app-wide [`Settings`] and every budget's [`BudgetSettings`]. Tokens are
+//! kept by the token store and never included; neither are the auth
+//! server's `[oauth]` secrets, should `config.toml` hold them. Colors live in
+//! `config.toml`, so they come along with it.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::budget_settings::{self, BudgetSettings};
+use crate::config::AppConfig;
+use crate::settings::{self, Settings};
+
+/// Marks a file as a ynat bundle
+pub const FORMAT: &str = "ynat-config";
+
+/// Bumped when a change would make older versions misread a bundle
+pub const VERSION: u32 = 1;
+
+/// Config table only the auth server reads, holding its client secret
+const SECRET_TABLE: &str = "oauth";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bundle {
+    pub format: String,
+    pub version: u32,
+    /// `config.toml`, if there was one
+    pub config: Option<String>,
+    pub settings: Settings,
+    /// Per-budget settings, by budget id
+    pub budgets: BTreeMap<String, BudgetSettings>,
+}
+
+/// What was exported or imported, for the summary line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Counts {
+    pub config: bool,
+    pub budgets: usize,
+}
+
+impl Bundle {
+    pub fn new(
+        config: Option<String>,
+        settings: Settings,
+        budgets: BTreeMap<String, BudgetSettings>,
+    ) -> Self {
+        Self {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            config: config.map(|config| without_table(&config, SECRET_TABLE)),
+            settings,
+            budgets,
+        }
+    }
+
+    pub fn counts(&self) -> Counts {
+        Counts {
+            config: self.config.is_some(),
+            budgets: self.budgets.len(),
+        }
+    }
+
+    /// Read a bundle, checking it's one this version understands before
+    /// anything is written
+    pub fn parse(json: &str) -> Result<Self> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).context("Not a ynat config bundle")?;
+        if value.get("format").and_then(|f| f.as_str()) != Some(FORMAT) {
+            bail!("Not a ynat config bundle");
+        }
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow!("Bundle has no version"))?;
+        if version > u64::from(VERSION) {
+            bail!("Bundle version {version} was written by a newer ynat; this one reads up to {VERSION}");
+        }
+
+        let bundle: Bundle = serde_json::from_value(value).context("Invalid bundle")?;
+        if let Some(config) = &bundle.config {
+            AppConfig::parse(config).context("Invalid config.toml in bundle")?;
+        }
+        if let Some(budget_id) = bundle.budgets.keys().find(|id| !is_budget_id(id)) {
+            bail!("Invalid budget id {budget_id:?} in bundle");
+        }
+        Ok(bundle)
+    }
+}
+
+/// Bundle the config at `config_path` with the saved settings
+pub async fn export(config_path: &str) -> Result<Bundle> {
+    let config = match tokio::fs::read_to_string(config_path).await {
+        Ok(config) => Some(config),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {config_path}")),
+    };
+    let budgets = budget_settings::load_all()
+        .await
+        .context("Failed to read budget settings")?;
+    let bundle = Bundle::new(config, settings::load().await, budgets);
+    if bundle.config.as_deref().is_some_and(has_secrets) {
+        bail!(
+            "{config_path} sets {SECRET_TABLE} outside an [{SECRET_TABLE}] table; move it there \
+             or to YNAB_AUTH__OAUTH__* environment variables so it's left out"
+        );
+    }
+    Ok(bundle)
+}
+
+/// Write a bundle's contents in place of the current ones. A `config.toml`
+/// that would change is kept as `config.toml.bak`; budgets the bundle
+/// doesn't mention are left alone.
+pub async fn import(bundle: &Bundle, config_path: &str) -> Result<()> {
+    if let Some(config) = &bundle.config {
+        let backup = format!("{config_path}.bak");
+        match tokio::fs::read_to_string(config_path).await {
+            Ok(current) if current == *config => {}
+            Ok(_) => tokio::fs::copy(config_path, &backup)
+                .await
+                .map(|_| ())
+                .with_context(|| format!("Failed to back up {config_path}"))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {config_path}")),
+        }
+        tokio::fs::write(config_path, config)
+            .await
+            .with_context(|| format!("Failed to write {config_path}"))?;
+    }
+    settings::save(&bundle.settings)
+        .await
+        .context("Failed to save settings")?;
+    for (budget_id, budget) in &bundle.budgets {
+        budget_settings::save(budget_id, budget)
+            .await
+            .with_context(|| format!("Failed to save settings for budget {budget_id}"))?;
+    }
+    Ok(())
+}
+
+/// Budget ids become file names, so only allow what YNAB uses
+fn is_budget_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Whether auth server secrets are still in `toml`, as dotted keys or an
+/// inline table that [`without_table`] can't see
+fn has_secrets(toml: &str) -> bool {
+    config::Config::builder()
+        .add_source(config::File::from_str(toml, config::FileFormat::Toml))
+        .build()
+        .is_ok_and(|config| config.get_table(SECRET_TABLE).is_ok())
+}
+
+/// `toml` without the `[name]` table or its subtables
+fn without_table(toml: &str, name: &str) -> String {
+    let mut skipping = false;
+    let mut kept = String::new();
+    for line in toml.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('[') {
+            let header = trimmed
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or_default()
+                .trim();
+            skipping = header == name || header.starts_with(&format!("{name}."));
+        }
+        if !skipping {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "[api]\nread_only = true\n\n[oauth]\nclient_id = \"id\"\nclient_secret = \"secret\"\n\n[colors]\nbalances = []\n";
+
+    fn bundle() -> Bundle {
+        let mut budgets = BTreeMap::new();
+        budgets.insert(
+            "c65d6c18-e379-b08c-c65d-6c18e379b08c".to_string(),
+            BudgetSettings {
+                confirm_delete: false,
+                ..Default::default()
+            },
+        );
+        Bundle::new(Some(CONFIG.to_string()), Settings::default(), budgets)
+    }
+
+    #[test]
+    fn bundles_round_trip_without_auth_secrets() {
+        let json = serde_json::to_string(&bundle()).unwrap();
+
+        let read = Bundle::parse(&json).unwrap();
+
+        assert_eq!(read, bundle());
+        let config = read.config.unwrap();
+        assert!(!config.contains("secret"));
+        assert!(config.contains("read_only = true"));
+        assert!(config.contains("[colors]"));
+        assert!(!has_secrets(&config));
+        assert!(has_secrets("oauth.client_secret = \"secret\"\n"));
+        assert_eq!(
+            read.budgets.values().next().map(|b| b.confirm_delete),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn bundles_are_checked_before_import() {
+        let json = |bundle: &Bundle| serde_json::to_string(bundle).unwrap();
+        let error = |json: &str| Bundle::parse(json).unwrap_err().to_string();
+
+        assert_eq!(error("{}"), "Not a ynat config bundle");
+        let mut newer = bundle();
+        newer.version = VERSION + 1;
+        assert!(error(&json(&newer)).contains("newer ynat"));
+        let mut bad_config = bundle();
+        bad_config.config = Some("[api]\nread_only = \"sometimes\"\n".to_string());
+        assert_eq!(error(&json(&bad_config)), "Invalid config.toml in bundle");
+        let mut bad_id = bundle();
+        bad_id
+            .budgets
+            .insert("../escape".to_string(), BudgetSettings::default());
+        assert!(error(&json(&bad_id)).starts_with("Invalid budget id"));
+    }
+}
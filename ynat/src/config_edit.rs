@@ -0,0 +1,144 @@
+//! Writing values changed on the settings screen back to `config.toml`.
+//!
+//! Only the changed `key = value` lines are touched, so comments, ordering
+//! and sections ynat doesn't read (such as `[auth]`) stay as the user wrote
+//! them.
+
+use crate::config::AppConfig;
+
+/// A `key = value` line to set under `[section]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigEdit {
+    pub section: &'static str,
+    pub key: &'static str,
+    /// TOML literal, e.g. `true`, `300` or `"0.05"`
+    pub value: String,
+}
+
+impl ConfigEdit {
+    pub fn new(section: &'static str, key: &'static str, value: impl ToString) -> Self {
+        Self {
+            section,
+            key,
+            value: value.to_string(),
+        }
+    }
+
+    pub fn string(section: &'static str, key: &'static str, value: &str) -> Self {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        Self::new(section, key, format!("\"{escaped}\""))
+    }
+}
+
+/// `toml` with each edit applied. A key already in its section is replaced
+/// in place; otherwise it's added at the end of the section, which is
+/// appended to the file if missing.
+pub fn apply(toml: &str, edits: &[ConfigEdit]) -> String {
+    let mut lines: Vec<String> = toml.lines().map(str::to_string).collect();
+    for edit in edits {
+        let line = format!("{} = {}", edit.key, edit.value);
+        let Some(start) = lines
+            .iter()
+            .position(|l| header(l).as_deref() == Some(edit.section))
+        else {
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{}]", edit.section));
+            lines.push(line);
+            continue;
+        };
+        let end = lines[start + 1..]
+            .iter()
+            .position(|l| header(l).is_some())
+            .map_or(lines.len(), |i| start + 1 + i);
+        match (start + 1..end).find(|&i| sets_key(&lines[i], edit.key)) {
+            Some(i) => lines[i] = line,
+            None => {
+                // After the section's last setting, before the blank lines
+                // separating it from the next one
+                let last = (start..end)
+                    .rev()
+                    .find(|&i| !lines[i].trim().is_empty())
+                    .unwrap_or(start);
+                lines.insert(last + 1, line);
+            }
+        }
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Apply `edits` to the file at `path`, creating it if needed. Nothing is
+/// written if the result wouldn't load.
+pub async fn write(path: &str, edits: &[ConfigEdit]) -> anyhow::Result<()> {
+    let current = match tokio::fs::read_to_string(path).await {
+        Ok(current) => current,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let updated = apply(&current, edits);
+    AppConfig::parse(&updated)?;
+    // Replace the file in one step so an interrupted save leaves the old one intact
+    let tmp_path = format!("{path}.tmp");
+    tokio::fs::write(&tmp_path, updated).await?;
+    tokio::fs::rename(tmp_path, path).await?;
+    Ok(())
+}
+
+/// Table name if `line` is a `[table]` header (`[[array]]` headers count,
+/// so their keys aren't mistaken for the section before)
+fn header(line: &str) -> Option<String> {
+    let line = line.trim();
+    let name = line.strip_prefix('[')?.split(']').next()?;
+    Some(name.trim_start_matches('[').trim().to_string())
+}
+
+fn sets_key(line: &str, key: &str) -> bool {
+    line.trim_start()
+        .strip_prefix(key)
+        .is_some_and(|rest| rest.trim_start().starts_with('='))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_keep_the_rest_of_the_file() {
+        let toml = "# My setup\n[auth]\nserver_url = \"https://auth\"\n\n[approval]\n# review bank imports by hand\non_save = true\non_clear = true\n\n[[rules]]\npayee = \"Shell\"\ncategory = \"Fuel\"\n";
+
+        let updated = apply(
+            toml,
+            &[
+                ConfigEdit::new("approval", "on_clear", false),
+                ConfigEdit::new("auto_refresh", "interval_secs", 60),
+                ConfigEdit::string("amounts", "rounding", "0.05"),
+                ConfigEdit::new("approval", "on_save", false),
+            ],
+        );
+
+        assert_eq!(
+            updated,
+            "# My setup\n[auth]\nserver_url = \"https://auth\"\n\n[approval]\n# review bank imports by hand\non_save = false\non_clear = false\n\n[[rules]]\npayee = \"Shell\"\ncategory = \"Fuel\"\n\n[auto_refresh]\ninterval_secs = 60\n\n[amounts]\nrounding = \"0.05\"\n"
+        );
+        let config = AppConfig::parse(&updated).unwrap();
+        assert!(!config.approval.on_clear);
+        assert_eq!(config.auto_refresh.interval_secs, 60);
+        assert_eq!(config.rules.len(), 1);
+    }
+
+    #[test]
+    fn keys_are_added_at_the_end_of_their_section() {
+        let updated = apply(
+            "[display]\nlocale = \"de\"\n\n[api]\nread_only = true\n",
+            &[ConfigEdit::string("display", "date_format", "DD.MM.YYYY")],
+        );
+
+        assert_eq!(
+            updated,
+            "[display]\nlocale = \"de\"\ndate_format = \"DD.MM.YYYY\"\n\n[api]\nread_only = true\n"
+        );
+    }
+}
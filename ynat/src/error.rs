@@ -0,0 +1,145 @@
+//! Errors shown to the user.
+//!
+//! Loaders and validators report failures as an [`AppError`] so every screen
+//! can present them the same way: what went wrong, whether retrying can help,
+//! and what to do next.
+
+use serde::{Deserialize, Serialize};
+use ynab_api::YnabApiError;
+
+use crate::i18n::t;
+
+/// Broad cause of an error, used to pick the title and suggested action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorKind {
+    /// YNAB couldn't be reached
+    Network,
+    /// The token was rejected
+    Unauthorized,
+    /// The budget, account or transaction no longer exists
+    NotFound,
+    /// Too many requests in the last hour
+    RateLimited,
+    /// YNAB returned a server error
+    Server,
+    /// Input that YNAB (or the form) won't accept
+    Validation,
+    /// Local data couldn't be read or written
+    Cache,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Validation, message)
+    }
+
+    /// Whether trying the same thing again might work
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::Network
+                | ErrorKind::RateLimited
+                | ErrorKind::Server
+                | ErrorKind::Cache
+                | ErrorKind::Other
+        )
+    }
+
+    /// Short heading for the error panel
+    pub fn title(&self) -> &'static str {
+        match self.kind {
+            ErrorKind::Network => t("error-network"),
+            ErrorKind::Unauthorized => t("error-unauthorized"),
+            ErrorKind::NotFound => t("error-not-found"),
+            ErrorKind::RateLimited => t("error-rate-limited"),
+            ErrorKind::Server => t("error-server"),
+            ErrorKind::Validation => t("error-validation"),
+            ErrorKind::Cache => t("error-cache"),
+            ErrorKind::Other => t("error-other"),
+        }
+    }
+
+    /// What the user can do about it
+    pub fn suggestion(&self) -> &'static str {
+        match self.kind {
+            ErrorKind::Network => t("error-network-suggestion"),
+            ErrorKind::Unauthorized => t("error-unauthorized-suggestion"),
+            ErrorKind::NotFound => t("error-not-found-suggestion"),
+            ErrorKind::RateLimited => t("error-rate-limited-suggestion"),
+            ErrorKind::Server => t("error-server-suggestion"),
+            ErrorKind::Validation => t("error-validation-suggestion"),
+            ErrorKind::Cache => t("error-cache-suggestion"),
+            ErrorKind::Other => t("error-other-suggestion"),
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<YnabApiError> for AppError {
+    fn from(error: YnabApiError) -> Self {
+        let kind = match &error {
+            YnabApiError::Ynab(status, _) => match status.as_u16() {
+                401 | 403 => ErrorKind::Unauthorized,
+                404 => ErrorKind::NotFound,
+                429 => ErrorKind::RateLimited,
+                400 | 409 | 422 => ErrorKind::Validation,
+                500..=599 => ErrorKind::Server,
+                _ => ErrorKind::Other,
+            },
+            YnabApiError::Internal(_) | YnabApiError::TimedOut(_) => ErrorKind::Network,
+            YnabApiError::Unreadable(..) => ErrorKind::Server,
+            YnabApiError::Cancelled => ErrorKind::Other,
+        };
+        let message = match &error {
+            YnabApiError::Ynab(_, detail) => detail.detail.clone(),
+            e => e.to_string(),
+        };
+        Self::new(kind, message)
+    }
+}
+
+impl From<crate::cache::CacheError> for AppError {
+    fn from(error: crate::cache::CacheError) -> Self {
+        Self::new(ErrorKind::Cache, error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_transient_errors_are_retryable() {
+        assert!(AppError::new(ErrorKind::Network, "offline").is_retryable());
+        assert!(AppError::new(ErrorKind::RateLimited, "slow down").is_retryable());
+        assert!(!AppError::new(ErrorKind::Unauthorized, "expired").is_retryable());
+        assert!(!AppError::validation("Amount cannot be empty").is_retryable());
+    }
+
+    #[test]
+    fn displays_the_message() {
+        let error = AppError::validation("Amount cannot be empty");
+        assert_eq!(error.to_string(), "Amount cannot be empty");
+    }
+}
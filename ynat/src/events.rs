@@ -1,17 +1,50 @@
+use crate::archive::ArchiveSuggestion;
+use crate::budget_settings::BudgetSettings;
+use crate::category_suggestions::CategorySuggestion;
+use crate::error::AppError;
+use crate::health::HealthItem;
+use crate::maps::Place;
+use crate::payee_cleanup::{PayeeCluster, PayeeMerge};
+use crate::quick_budget::QuickBudgetAction;
+use crate::reimbursements::{ReimbursementEntry, ReimbursementMatch};
+use crate::reports::cash_flow::{CashFlowProjection, ProjectionHorizon};
+use crate::reports::heatmap::SpendingHeatmap;
+use crate::reports::income_expense::IncomeExpense;
+use crate::reports::net_worth::NetWorth;
+use crate::reports::obligations::ObligationsForecast;
+use crate::reports::spending::{SpendingBreakdown, SpendingGrouping};
+use crate::reports::variance::{VarianceRow, VarianceTransaction};
+use crate::reports::ReportPeriod;
+use crate::state::TransactionPreset;
+use crate::utils::balance_history::RecentSpending;
+use crate::utils::ynab_export::{ExportFiles, ImportPreview};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
 use ynab_api::endpoints::{
     accounts::Account,
     budgets::BudgetSummary,
     categories::Category,
     months::MonthDetail,
     payees::Payee,
-    transactions::{ReconciliationStatus, Transaction},
+    scheduled_transactions::ScheduledTransaction,
+    transactions::{FlagColor, ReconciliationStatus, Transaction},
+    CurrencyFormat, DateFormat,
 };
+use ynab_api::RateLimit;
 
 /// Commands to execute (user actions → background tasks)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AppCommand {
     SelectNext,
     SelectPrevious,
+    /// Move the selection this many rows, down when positive, without
+    /// wrapping around: `5j`, `5k`, Ctrl+D and Ctrl+U
+    MoveSelection {
+        rows: isize,
+    },
     NavigateToTop,
     NavigateToBottom,
 
@@ -33,6 +66,25 @@ pub enum AppCommand {
         account_id: String,
         force_refresh: bool,
     },
+    /// Page further back through an account's history
+    LoadOlderTransactions {
+        budget_id: String,
+        account_id: String,
+    },
+    /// Search transactions across every account in the budget
+    NavigateToTransactionSearch {
+        budget_id: String,
+    },
+    LoadBudgetTransactions {
+        budget_id: String,
+    },
+    /// Review unapproved and uncategorized transactions from every account
+    NavigateToInbox {
+        budget_id: String,
+    },
+    LoadInbox {
+        budget_id: String,
+    },
     LoadPlan {
         budget_id: String,
         force_refresh: bool,
@@ -44,6 +96,27 @@ pub enum AppCommand {
     NavigatePlanMonth {
         forward: bool,
     },
+    /// Show the plan side by side with the month before it, or stop comparing
+    TogglePlanComparison,
+    /// Compare against the month after (or before) the compared one
+    ShiftPlanComparison {
+        forward: bool,
+    },
+    /// Force-refresh budgets, accounts, plan, payees, and categories in one batch
+    RefreshAll {
+        budget_id: String,
+    },
+    /// Find the YNAB "Export budget data" CSVs in the configured directory
+    /// and ask before importing them
+    PreviewYnabExport {
+        budget_id: String,
+    },
+    /// Import the export that was confirmed
+    ImportYnabExport {
+        budget_id: String,
+        export: ExportFiles,
+    },
+    CancelYnabExportImport,
 
     // Transaction updates
     ToggleTransactionCleared {
@@ -62,6 +135,14 @@ pub enum AppCommand {
     },
     DeleteFormFieldChar,
     ClearFormField,
+    /// Fill payee, category and cleared status from the last transaction entered in the account
+    FillFormFromLastUsed,
+    /// Flip the amount (or the active split's amount) between outflow and inflow
+    ToggleAmountSign,
+    /// Convert the foreign amount at the rate typed into the rate prompt
+    ConfirmExchangeRate,
+    /// Close the rate prompt, leaving the foreign amount to be edited
+    CancelExchangeRate,
     SelectAutocompleteItem {
         up: bool,
     },
@@ -83,6 +164,74 @@ pub enum AppCommand {
         budget_id: String,
         transaction_id: String,
     },
+    /// Approve every listed transaction in one bulk update
+    ApproveTransactions {
+        budget_id: String,
+        transaction_ids: Vec<String>,
+    },
+    /// Clear the flags of these transactions in one bulk update, once
+    /// they've been dealt with
+    ClearTransactionFlags {
+        budget_id: String,
+        transaction_ids: Vec<String>,
+    },
+    /// Categorize the loaded transactions that need review with the payee
+    /// rules from the config, in one bulk update
+    ApplyPayeeRules {
+        budget_id: String,
+    },
+    /// Give a transaction the category suggested from its payee's history,
+    /// then select the next row with a suggestion
+    AcceptCategorySuggestion {
+        budget_id: String,
+        transaction_id: String,
+    },
+    /// Have YNAB import new transactions from linked accounts, then reload
+    /// the account shown (or every account when `account_id` is `None`)
+    ImportLinkedTransactions {
+        budget_id: String,
+        account_id: Option<String>,
+    },
+    /// Advance the flag to the next color (or clear it after the last one)
+    CycleTransactionFlag {
+        budget_id: String,
+        transaction_id: String,
+    },
+    /// Run the configured follow-up command for a transaction
+    CreateFollowUp {
+        transaction_id: String,
+    },
+    /// Show a transaction's details and look up where its payee is
+    ShowTransactionDetail {
+        budget_id: String,
+        transaction_id: String,
+    },
+    CloseTransactionDetail,
+    /// Open a payee's location with the configured maps command
+    OpenPayeeLocation {
+        place: Place,
+    },
+    /// Write the transactions currently listed (after filtering) to a CSV file
+    ExportTransactions,
+    /// Close the popup showing where an export was written
+    DismissExportConfirmation,
+    /// Remember a transaction so it can be pasted as a copy
+    YankTransaction {
+        transaction_id: String,
+    },
+    /// Create a copy of the yanked transaction dated today, or open the
+    /// form pre-filled with it if `open_form` is set
+    PasteTransaction {
+        budget_id: String,
+        account_id: String,
+        open_form: bool,
+    },
+    /// Open the other side of a transfer, selecting the matching transaction
+    JumpToTransfer {
+        budget_id: String,
+        account_id: String,
+        transaction_id: Option<String>,
+    },
 
     // Transaction deletion
     InitiateTransactionDelete {
@@ -98,6 +247,10 @@ pub enum AppCommand {
     InitiateTransactionEdit {
         transaction_id: String,
     },
+    /// Open the edit form on the category field
+    CategorizeTransaction {
+        transaction_id: String,
+    },
     ConfirmReconciledEdit {
         transaction_id: String,
     },
@@ -125,10 +278,65 @@ pub enum AppCommand {
 
     // View toggles
     ToggleShowClosedAccounts,
+    /// List only the next kind of account (or every account after the last)
+    CycleAccountTypeFilter,
+
+    // Jumping to an account by typing the start of its name
+    EnterAccountJump,
+    AppendAccountJumpChar(char),
+    DeleteAccountJumpChar,
+    ExitAccountJump,
+    /// Move an account to the next reconcile reminder cadence (or none)
+    CycleReconcileReminder {
+        account_id: String,
+    },
+    /// Star or unstar an account, listing it first on the accounts screen
+    ToggleFavoriteAccount {
+        account_id: String,
+    },
     ToggleShowReconciledTransactions,
+    ToggleTransactionPreset(TransactionPreset),
+    /// List only the next flag color (or every transaction after the last one)
+    CycleFlagView,
+    /// Settle the shown delta conflict in favour of the local row or the server
+    ResolveDeltaConflict {
+        keep_mine: bool,
+    },
+    ToggleConflictDiff,
+    /// Order the transactions table by the next column
+    CycleTransactionSort,
+    /// Flip the direction the transactions table is ordered in
+    ReverseTransactionSort,
     TogglePlanFocusedView,
+    /// Show or hide the selected category's note and goal on the plan screen
+    TogglePlanCategoryDetails,
+    /// Show or hide the pane with the selected category's goal progress
+    TogglePlanGoalDetails,
+    TogglePlanGroup {
+        group_id: String,
+    },
+    /// Star or unstar a category, listing it first on the plan
+    ToggleFavoriteCategory {
+        category_id: String,
+    },
     ToggleHelp,
 
+    // Per-row context menu
+    OpenContextMenu,
+    CloseContextMenu,
+    MoveContextMenuCursor {
+        down: bool,
+    },
+    RunContextMenuItem,
+    CopyToClipboard(String),
+
+    // Settings screen
+    NavigateToSettings,
+    /// Switch the selected setting to its next value, or the previous one
+    ChangeSetting {
+        forward: bool,
+    },
+
     // Log screen
     NavigateToLogs,
     ScrollLogsUp,
@@ -138,9 +346,131 @@ pub enum AppCommand {
     ScrollLogsToTop,
     ScrollLogsToBottom,
 
+    // Net worth
+    NavigateToNetWorth {
+        budget_id: String,
+    },
+    /// Reload the net worth screen in place, from YNAB when `force_refresh`
+    LoadNetWorth {
+        budget_id: String,
+        force_refresh: bool,
+    },
+
+    // Reimbursement tracking
+    NavigateToReimbursements {
+        budget_id: String,
+    },
+    /// Reload the reimbursements screen in place
+    LoadReimbursements {
+        budget_id: String,
+    },
+
+    // Scheduled transactions
+    NavigateToScheduledTransactions {
+        budget_id: String,
+        /// Only show this account's schedules; `None` shows every account
+        account_id: Option<String>,
+    },
+    LoadScheduledTransactions {
+        budget_id: String,
+        force_refresh: bool,
+    },
+
+    // Payee cleanup
+    NavigateToPayeeCleanup {
+        budget_id: String,
+    },
+    /// Reload the payee cleanup screen in place
+    LoadPayeeClusters {
+        budget_id: String,
+    },
+    TogglePayeeCluster,
+    /// Rename each payee id to its new name
+    ApplyPayeeRenames {
+        budget_id: String,
+        renames: Vec<(String, String)>,
+    },
+    /// Ask before merging the ticked payee groups
+    OpenPayeeMergeConfirmation,
+    CancelPayeeMerge,
+    /// Move each group's transactions to one payee and hide the rest
+    MergePayees {
+        budget_id: String,
+        merges: Vec<PayeeMerge>,
+    },
+    /// Flag a transaction as awaiting reimbursement, or unflag it if already tracked
+    ToggleAwaitingReimbursement {
+        budget_id: String,
+        entry: ReimbursementEntry,
+    },
+    /// Mark an entry as paid back, by the given deposit or manually if `None`
+    MarkReimbursed {
+        budget_id: String,
+        transaction_id: String,
+        deposit_id: Option<String>,
+    },
+
+    // Reports
+    NavigateToReports {
+        budget_id: String,
+    },
+    LoadVarianceReport {
+        budget_id: String,
+        period: ReportPeriod,
+        force_refresh: bool,
+    },
+    ToggleVarianceSort,
+    /// Show the transactions behind a variance row for the current period
+    DrillIntoVarianceCategory {
+        budget_id: String,
+        category_id: String,
+    },
+    CloseReportDrillDown,
+    /// Open (or reload) the cash-flow projection for an account
+    LoadCashFlowProjection {
+        budget_id: String,
+        account_id: String,
+        horizon: ProjectionHorizon,
+    },
+    /// Show spending per day of `month` as a calendar
+    LoadSpendingHeatmap {
+        budget_id: String,
+        month: NaiveDate,
+    },
+    /// Add up the period's spending by category or payee
+    LoadSpendingBreakdown {
+        budget_id: String,
+        period: ReportPeriod,
+        grouping: SpendingGrouping,
+    },
+    /// Inflow and outflow for each of the last twelve months
+    LoadIncomeExpense {
+        budget_id: String,
+    },
+    /// Forecast next month's goal contributions against its scheduled income
+    LoadGoalObligations {
+        budget_id: String,
+        force_refresh: bool,
+    },
+    /// List every account's transactions dated `date`
+    NavigateToTransactionsOnDate {
+        budget_id: String,
+        date: NaiveDate,
+    },
+
+    // Debug inspector
+    NavigateToInspector {
+        focus_id: Option<String>,
+    },
+    ScrollInspectorDetail {
+        down: bool,
+    },
+
     // Key sequence state
     SetPendingKey(char),
     ClearPendingKey,
+    /// Digits typed so far as a count for the next movement key
+    SetPendingCount(usize),
 
     // Budget editing (Plan screen)
     InitiateBudgetEdit {
@@ -154,13 +484,340 @@ pub enum AppCommand {
         month: String,
     },
 
+    // Savings goal what-if calculator (Plan screen)
+    OpenGoalCalculator {
+        category_id: String,
+    },
+    CloseGoalCalculator,
+    ToggleGoalCalculatorField,
+    AppendGoalCalculatorChar(char),
+    DeleteGoalCalculatorChar,
+
+    // Editing a category's goal target (Plan screen)
+    OpenGoalForm {
+        category_id: String,
+    },
+    CloseGoalForm,
+    ToggleGoalFormField,
+    AppendGoalFormChar(char),
+    DeleteGoalFormChar,
+    SubmitGoalForm {
+        budget_id: String,
+    },
+
+    /// Move a category into the group above (to its end) or below (to its start)
+    MoveCategoryToGroup {
+        budget_id: String,
+        category_id: String,
+        forward: bool,
+    },
+
+    // Moving money between categories (Plan screen)
+    OpenMoveMoney {
+        category_id: String,
+    },
+    CloseMoveMoney,
+    ToggleMoveMoneyField,
+    AppendMoveMoneyChar(char),
+    DeleteMoveMoneyChar,
+    MoveMoneyDestinationCursor {
+        down: bool,
+    },
+    SubmitMoveMoney {
+        budget_id: String,
+        month: String,
+    },
+
+    // Quick-budget commands (Plan screen)
+    /// Work out new assigned amounts for the selected category, or every
+    /// listed one, and ask before applying them
+    OpenQuickBudget {
+        budget_id: String,
+        action: QuickBudgetAction,
+        all_visible: bool,
+    },
+    CloseQuickBudget,
+    MoveQuickBudgetCursor {
+        down: bool,
+    },
+    ApplyQuickBudget {
+        budget_id: String,
+    },
+
+    // Hiding unused categories (Plan screen)
+    OpenArchiveReview {
+        budget_id: String,
+    },
+    CloseArchiveReview,
+    MoveArchiveCursor {
+        down: bool,
+    },
+    ToggleArchiveSuggestion,
+    ArchiveCategories {
+        budget_id: String,
+        category_ids: Vec<String>,
+    },
+
+    // Budget health checklist (Plan screen)
+    OpenHealthChecklist {
+        budget_id: String,
+    },
+    CloseHealthChecklist,
+    MoveHealthCursor {
+        down: bool,
+    },
+    /// Jump to wherever the failing item can be fixed
+    FixHealthItem {
+        budget_id: String,
+        item: HealthItem,
+    },
+
+    // Undo history
+    Undo,
+    Redo,
+
     // System
     Quit,
 }
 
-/// Events from background tasks (responses to commands)
-#[derive(Debug, Clone)]
+impl AppCommand {
+    /// Whether the command changes the budget in YNAB, or opens something
+    /// whose only purpose is to. These are refused with a read-only token.
+    pub fn changes_budget(&self) -> bool {
+        // No catch-all, so each new command has to be sorted into one side
+        match self {
+            AppCommand::PreviewYnabExport { .. }
+            | AppCommand::ImportYnabExport { .. }
+            | AppCommand::ToggleTransactionCleared { .. }
+            | AppCommand::EnterTransactionCreateMode
+            | AppCommand::SubmitTransactionForm
+            | AppCommand::ApproveTransaction { .. }
+            | AppCommand::ApproveTransactions { .. }
+            | AppCommand::ClearTransactionFlags { .. }
+            | AppCommand::ApplyPayeeRules { .. }
+            | AppCommand::AcceptCategorySuggestion { .. }
+            | AppCommand::ImportLinkedTransactions { .. }
+            | AppCommand::CycleTransactionFlag { .. }
+            | AppCommand::PasteTransaction { .. }
+            | AppCommand::InitiateTransactionDelete { .. }
+            | AppCommand::ConfirmTransactionDelete { .. }
+            | AppCommand::InitiateTransactionEdit { .. }
+            | AppCommand::CategorizeTransaction { .. }
+            | AppCommand::ConfirmReconciledEdit { .. }
+            | AppCommand::EnterTransactionEditMode { .. }
+            | AppCommand::InitiateReconcile { .. }
+            | AppCommand::ConfirmReconcile { .. }
+            | AppCommand::ApplyPayeeRenames { .. }
+            | AppCommand::OpenPayeeMergeConfirmation
+            | AppCommand::MergePayees { .. }
+            | AppCommand::InitiateBudgetEdit { .. }
+            | AppCommand::SubmitBudgetEdit { .. }
+            | AppCommand::OpenGoalForm { .. }
+            | AppCommand::SubmitGoalForm { .. }
+            | AppCommand::MoveCategoryToGroup { .. }
+            | AppCommand::OpenMoveMoney { .. }
+            | AppCommand::SubmitMoveMoney { .. }
+            | AppCommand::OpenQuickBudget { .. }
+            | AppCommand::ApplyQuickBudget { .. }
+            | AppCommand::OpenArchiveReview { .. }
+            | AppCommand::ArchiveCategories { .. }
+            | AppCommand::Undo
+            | AppCommand::Redo => true,
+
+            AppCommand::SelectNext
+            | AppCommand::SelectPrevious
+            | AppCommand::MoveSelection { .. }
+            | AppCommand::NavigateToTop
+            | AppCommand::NavigateToBottom
+            | AppCommand::NavigateBack
+            | AppCommand::LoadBudgets { .. }
+            | AppCommand::LoadAccounts { .. }
+            | AppCommand::LoadTransactions { .. }
+            | AppCommand::LoadOlderTransactions { .. }
+            | AppCommand::NavigateToTransactionSearch { .. }
+            | AppCommand::LoadBudgetTransactions { .. }
+            | AppCommand::NavigateToInbox { .. }
+            | AppCommand::LoadInbox { .. }
+            | AppCommand::LoadPlan { .. }
+            | AppCommand::LoadPlanMonth { .. }
+            | AppCommand::NavigatePlanMonth { .. }
+            | AppCommand::TogglePlanComparison
+            | AppCommand::ShiftPlanComparison { .. }
+            | AppCommand::RefreshAll { .. }
+            | AppCommand::ExitTransactionCreateMode
+            | AppCommand::NavigateFormField { .. }
+            | AppCommand::AppendFormFieldChar { .. }
+            | AppCommand::DeleteFormFieldChar
+            | AppCommand::ClearFormField
+            | AppCommand::FillFormFromLastUsed
+            | AppCommand::ToggleAmountSign
+            | AppCommand::ConfirmExchangeRate
+            | AppCommand::CancelExchangeRate
+            | AppCommand::SelectAutocompleteItem { .. }
+            | AppCommand::ConfirmAutocompleteSelection
+            | AppCommand::EnterSplitMode
+            | AppCommand::AddSubtransaction
+            | AppCommand::DeleteSubtransaction
+            | AppCommand::LoadPayees { .. }
+            | AppCommand::LoadCategories { .. }
+            | AppCommand::CreateFollowUp { .. }
+            | AppCommand::ShowTransactionDetail { .. }
+            | AppCommand::CloseTransactionDetail
+            | AppCommand::OpenPayeeLocation { .. }
+            | AppCommand::ExportTransactions
+            | AppCommand::DismissExportConfirmation
+            | AppCommand::YankTransaction { .. }
+            | AppCommand::JumpToTransfer { .. }
+            | AppCommand::CancelTransactionDelete
+            | AppCommand::CancelReconciledEdit
+            | AppCommand::CancelReconcile
+            | AppCommand::EnterFilterMode
+            | AppCommand::ExitFilterMode
+            | AppCommand::AppendFilterChar(_)
+            | AppCommand::DeleteFilterChar
+            | AppCommand::ClearFilter
+            | AppCommand::ToggleShowClosedAccounts
+            | AppCommand::CycleAccountTypeFilter
+            | AppCommand::EnterAccountJump
+            | AppCommand::AppendAccountJumpChar(_)
+            | AppCommand::DeleteAccountJumpChar
+            | AppCommand::ExitAccountJump
+            | AppCommand::CycleReconcileReminder { .. }
+            | AppCommand::ToggleFavoriteAccount { .. }
+            | AppCommand::ToggleShowReconciledTransactions
+            | AppCommand::ToggleTransactionPreset(_)
+            | AppCommand::CycleFlagView
+            | AppCommand::ResolveDeltaConflict { .. }
+            | AppCommand::ToggleConflictDiff
+            | AppCommand::CycleTransactionSort
+            | AppCommand::ReverseTransactionSort
+            | AppCommand::TogglePlanFocusedView
+            | AppCommand::TogglePlanCategoryDetails
+            | AppCommand::TogglePlanGoalDetails
+            | AppCommand::TogglePlanGroup { .. }
+            | AppCommand::ToggleFavoriteCategory { .. }
+            | AppCommand::ToggleHelp
+            | AppCommand::OpenContextMenu
+            | AppCommand::CloseContextMenu
+            | AppCommand::MoveContextMenuCursor { .. }
+            | AppCommand::RunContextMenuItem
+            | AppCommand::CopyToClipboard(_)
+            | AppCommand::NavigateToSettings
+            | AppCommand::ChangeSetting { .. }
+            | AppCommand::NavigateToLogs
+            | AppCommand::ScrollLogsUp
+            | AppCommand::ScrollLogsDown
+            | AppCommand::ScrollLogsPageUp
+            | AppCommand::ScrollLogsPageDown
+            | AppCommand::ScrollLogsToTop
+            | AppCommand::ScrollLogsToBottom
+            | AppCommand::NavigateToNetWorth { .. }
+            | AppCommand::LoadNetWorth { .. }
+            | AppCommand::NavigateToReimbursements { .. }
+            | AppCommand::LoadReimbursements { .. }
+            | AppCommand::NavigateToScheduledTransactions { .. }
+            | AppCommand::LoadScheduledTransactions { .. }
+            | AppCommand::NavigateToPayeeCleanup { .. }
+            | AppCommand::LoadPayeeClusters { .. }
+            | AppCommand::TogglePayeeCluster
+            | AppCommand::CancelPayeeMerge
+            | AppCommand::CancelYnabExportImport
+            | AppCommand::ToggleAwaitingReimbursement { .. }
+            | AppCommand::MarkReimbursed { .. }
+            | AppCommand::NavigateToReports { .. }
+            | AppCommand::LoadVarianceReport { .. }
+            | AppCommand::ToggleVarianceSort
+            | AppCommand::DrillIntoVarianceCategory { .. }
+            | AppCommand::CloseReportDrillDown
+            | AppCommand::LoadCashFlowProjection { .. }
+            | AppCommand::LoadSpendingHeatmap { .. }
+            | AppCommand::LoadSpendingBreakdown { .. }
+            | AppCommand::LoadIncomeExpense { .. }
+            | AppCommand::LoadGoalObligations { .. }
+            | AppCommand::NavigateToTransactionsOnDate { .. }
+            | AppCommand::NavigateToInspector { .. }
+            | AppCommand::ScrollInspectorDetail { .. }
+            | AppCommand::SetPendingKey(_)
+            | AppCommand::ClearPendingKey
+            | AppCommand::SetPendingCount(_)
+            | AppCommand::ExitBudgetEditMode
+            | AppCommand::AppendBudgetChar(_)
+            | AppCommand::DeleteBudgetChar
+            | AppCommand::OpenGoalCalculator { .. }
+            | AppCommand::CloseGoalCalculator
+            | AppCommand::ToggleGoalCalculatorField
+            | AppCommand::AppendGoalCalculatorChar(_)
+            | AppCommand::DeleteGoalCalculatorChar
+            | AppCommand::CloseGoalForm
+            | AppCommand::ToggleGoalFormField
+            | AppCommand::AppendGoalFormChar(_)
+            | AppCommand::DeleteGoalFormChar
+            | AppCommand::CloseMoveMoney
+            | AppCommand::ToggleMoveMoneyField
+            | AppCommand::AppendMoveMoneyChar(_)
+            | AppCommand::DeleteMoveMoneyChar
+            | AppCommand::MoveMoneyDestinationCursor { .. }
+            | AppCommand::CloseQuickBudget
+            | AppCommand::MoveQuickBudgetCursor { .. }
+            | AppCommand::CloseArchiveReview
+            | AppCommand::MoveArchiveCursor { .. }
+            | AppCommand::ToggleArchiveSuggestion
+            | AppCommand::OpenHealthChecklist { .. }
+            | AppCommand::CloseHealthChecklist
+            | AppCommand::MoveHealthCursor { .. }
+            | AppCommand::FixHealthItem { .. }
+            | AppCommand::Quit => false,
+        }
+    }
+}
+
+/// Events from background tasks (responses to commands), grouped by domain.
+///
+/// Each domain has its own event type and reducer in `state::reducer`, so a
+/// new domain adds a variant here rather than growing one shared match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DataEvent {
+    Budget(BudgetEvent),
+    Transaction(TransactionEvent),
+    Scheduled(ScheduledEvent),
+    Plan(PlanEvent),
+    Payee(PayeeEvent),
+    Report(ReportEvent),
+
+    // Errors, whichever domain they came from
+    LoadError {
+        error: AppError,
+    },
+    /// A cache file couldn't be read and is being rebuilt from the API
+    CacheRecovered,
+    /// YNAB's hourly quota is nearly used up; `None` once it has room again
+    RateLimitWarning {
+        rate_limit: Option<RateLimit>,
+    },
+
+    // Changes made while YNAB couldn't be reached
+    /// A change was saved to send later; `pending` counts it
+    WriteQueued {
+        pending: usize,
+    },
+    /// An attempt to send queued changes ended, with `pending` still waiting
+    PendingWritesSynced {
+        synced: usize,
+        pending: usize,
+    },
+    /// A queued change was dropped after YNAB refused it, or answered in a
+    /// way that leaves it `unconfirmed` whether it was applied
+    PendingWriteRejected {
+        /// Which change, as told to the user
+        change: String,
+        unconfirmed: bool,
+        error: AppError,
+    },
+}
+
+/// Budgets, accounts and budget-wide operations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BudgetEvent {
     // Cache events (instant)
     BudgetsCacheLoaded {
         budgets: Vec<BudgetSummary>,
@@ -169,9 +826,6 @@ pub enum DataEvent {
     AccountsCacheLoaded {
         accounts: Vec<Account>,
     },
-    TransactionsCacheLoaded {
-        transactions: Vec<Transaction>,
-    },
 
     // API events (slower)
     BudgetsLoaded {
@@ -181,26 +835,73 @@ pub enum DataEvent {
     AccountsLoaded {
         accounts: Vec<Account>,
     },
-    TransactionsLoaded {
-        transactions: Vec<Transaction>,
-    },
 
     // Delta updates (background refresh)
     AccountsDeltaLoaded {
         delta: Vec<Account>,
     },
-    TransactionsDeltaLoaded {
-        delta: Vec<Transaction>,
+
+    // Account balance history and this month's net change (computed from cached transactions)
+    AccountBalanceHistoryLoaded {
+        account_id: String,
+        balances: Vec<i64>,
+        month_change: i64,
     },
 
-    // Plan data
-    PlanCacheLoaded {
-        month: MonthDetail,
-        categories: Vec<Category>,
+    // Spending today and this week across the budget's accounts (computed
+    // from cached transactions)
+    RecentSpendingLoaded {
+        spending: RecentSpending,
     },
-    PlanLoaded {
-        month: MonthDetail,
-        categories: Vec<Category>,
+
+    // Local preferences for a budget, read when its accounts are opened
+    BudgetSettingsLoaded {
+        budget_id: String,
+        settings: BudgetSettings,
+    },
+
+    // Date and currency formats from YNAB, for a budget opened without them
+    BudgetFormatsLoaded {
+        budget_id: String,
+        date_format: Option<DateFormat>,
+        currency_format: Option<CurrencyFormat>,
+    },
+
+    // Batch refresh finished (all loaders have reported)
+    RefreshAllCompleted,
+
+    // YNAB export found and matched, waiting for confirmation
+    YnabExportPreviewed {
+        preview: ImportPreview,
+    },
+
+    // YNAB export import finished
+    YnabExportImported {
+        transactions: usize,
+        allocations: usize,
+        skipped: usize,
+    },
+}
+
+/// An account's transactions and everything done to them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TransactionEvent {
+    // Transactions data (cache, API or delta)
+    TransactionsCacheLoaded {
+        transactions: Vec<Transaction>,
+        loaded_since: Option<NaiveDate>,
+    },
+    TransactionsLoaded {
+        transactions: Vec<Transaction>,
+        loaded_since: Option<NaiveDate>,
+    },
+    /// A page of history from before `TransactionsState::loaded_since`
+    OlderTransactionsLoaded {
+        transactions: Vec<Transaction>,
+        loaded_since: Option<NaiveDate>,
+    },
+    TransactionsDeltaLoaded {
+        delta: Vec<Transaction>,
     },
 
     // Transaction approval
@@ -209,7 +910,42 @@ pub enum DataEvent {
     },
     TransactionApproveFailed {
         transaction_id: String,
-        error: String,
+        error: AppError,
+    },
+    TransactionsApproved {
+        transaction_ids: Vec<String>,
+    },
+    TransactionsApproveFailed {
+        transaction_ids: Vec<String>,
+        error: AppError,
+    },
+    TransactionsCategorized {
+        transaction_ids: Vec<String>,
+    },
+    CategorySuggestionAccepted {
+        transaction_id: String,
+    },
+    /// The transactions as they were before the payee rules or a suggested
+    /// category were applied
+    TransactionsCategorizeFailed {
+        originals: Vec<Transaction>,
+        error: AppError,
+    },
+    TransactionFlagsCleared {
+        transaction_ids: Vec<String>,
+    },
+    /// The transactions as they were before their flags were cleared
+    TransactionFlagsClearFailed {
+        originals: Vec<Transaction>,
+        error: AppError,
+    },
+
+    // Linked account import
+    TransactionsImported {
+        count: usize,
+    },
+    TransactionsImportFailed {
+        error: AppError,
     },
 
     // Transaction updates
@@ -220,21 +956,29 @@ pub enum DataEvent {
         transaction_id: String,
         original_status: ReconciliationStatus,
         original_approved: bool,
-        error: String,
+        error: AppError,
+    },
+    TransactionFlagUpdateFailed {
+        transaction_id: String,
+        original_flag: Option<FlagColor>,
+        error: AppError,
     },
 
     // Transaction creation
-    PayeesLoaded {
-        payees: Vec<Payee>,
-    },
-    CategoriesLoaded {
-        categories: Vec<Category>,
-    },
     TransactionCreated {
         transaction: Transaction,
     },
     TransactionCreateFailed {
-        error: String,
+        error: AppError,
+    },
+    /// A deleted transaction recreated by undo, under a new id
+    TransactionRestored {
+        original_id: String,
+        transaction: Transaction,
+    },
+    TransactionRestoreFailed {
+        original_id: String,
+        error: AppError,
     },
 
     // Transaction deletion
@@ -243,7 +987,7 @@ pub enum DataEvent {
     },
     TransactionDeleteFailed {
         transaction_id: String,
-        error: String,
+        error: AppError,
     },
 
     // Transaction editing (full update)
@@ -252,7 +996,7 @@ pub enum DataEvent {
     },
     TransactionUpdateFullFailed {
         transaction_id: String,
-        error: String,
+        error: AppError,
     },
 
     // Reconciliation
@@ -260,7 +1004,82 @@ pub enum DataEvent {
         transaction_ids: Vec<String>,
     },
     TransactionsReconcileFailed {
-        error: String,
+        error: AppError,
+    },
+
+    // Reimbursement sidecar loaded or updated
+    ReimbursementsLoaded {
+        entries: Vec<ReimbursementEntry>,
+        matches: Vec<ReimbursementMatch>,
+    },
+    /// Each payee's most used category, by payee id
+    CategorySuggestionsLoaded {
+        suggestions: HashMap<Uuid, CategorySuggestion>,
+    },
+
+    // Follow-ups
+    FollowUpCreated,
+    FollowUpFailed {
+        error: AppError,
+    },
+
+    // Payee locations
+    /// `None` when YNAB has no location recorded for the payee
+    PayeeLocationLoaded {
+        payee_id: String,
+        place: Option<Place>,
+    },
+    PayeeLocationLoadFailed {
+        payee_id: String,
+        error: AppError,
+    },
+    PayeeLocationOpened {
+        latitude: String,
+        longitude: String,
+    },
+    PayeeLocationFailed {
+        error: AppError,
+    },
+
+    // CSV export
+    TransactionsExported {
+        path: PathBuf,
+    },
+    TransactionsExportFailed {
+        error: AppError,
+    },
+}
+
+/// Scheduled (recurring) transactions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScheduledEvent {
+    // Scheduled transactions data (cache or API)
+    ScheduledTransactionsLoaded {
+        scheduled_transactions: Vec<ScheduledTransaction>,
+    },
+    /// Changes since the cached copy; includes deleted schedules so they can be removed
+    ScheduledTransactionsDeltaLoaded { delta: Vec<ScheduledTransaction> },
+}
+
+/// The monthly plan and its categories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PlanEvent {
+    // Plan data
+    PlanCacheLoaded {
+        month: MonthDetail,
+        categories: Vec<Category>,
+    },
+    PlanLoaded {
+        month: MonthDetail,
+        categories: Vec<Category>,
+    },
+    CategoriesLoaded {
+        categories: Vec<Category>,
+    },
+    /// The month the plan is being compared against
+    ComparisonMonthLoaded {
+        month: MonthDetail,
+        categories: Vec<Category>,
     },
 
     // Budget category updates
@@ -271,11 +1090,112 @@ pub enum DataEvent {
         category_id: String,
         original_budgeted: i64,
         new_budgeted: i64,
-        error: String,
+        error: AppError,
+    },
+    /// A category whose goal target or date the server changed
+    CategoryGoalUpdated {
+        category: Category,
+    },
+    /// The goal edit didn't go through; `original` has the goal as it was
+    CategoryGoalUpdateFailed {
+        original: Category,
+        error: AppError,
+    },
+    /// Categories in each month before the plan month, oldest first, for a
+    /// quick-budget command
+    QuickBudgetHistoryLoaded {
+        months: Vec<Vec<Category>>,
+    },
+    ArchiveSuggestionsLoaded {
+        suggestions: Vec<ArchiveSuggestion>,
     },
+    HealthChecklistLoaded {
+        items: Vec<HealthItem>,
+    },
+    /// Categories the server confirmed as moved into the archive `group`,
+    /// and how many it didn't
+    CategoriesArchived {
+        categories: Vec<Category>,
+        group: String,
+        failed: usize,
+    },
+    /// The budget has no group to archive categories into
+    ArchiveGroupMissing,
+    /// A category the server moved into another group, from the group above
+    /// (`forward`) or below
+    CategoryMoved {
+        category: Category,
+        forward: bool,
+    },
+}
 
-    // Errors
-    LoadError {
-        error: String,
+/// Payees and payee cleanup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PayeeEvent {
+    PayeesLoaded {
+        payees: Vec<Payee>,
+    },
+    PayeeClustersLoaded {
+        clusters: Vec<PayeeCluster>,
+    },
+    /// Payees the server renamed, and how many renames failed
+    PayeesRenamed {
+        payees: Vec<Payee>,
+        failed: usize,
+    },
+    /// Merges that went through, how many transactions moved, and how many
+    /// merges failed
+    PayeesMerged {
+        merged: Vec<PayeeMerge>,
+        transactions_moved: usize,
+        failed: usize,
     },
 }
+
+/// Reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReportEvent {
+    VarianceReportLoaded {
+        period: ReportPeriod,
+        rows: Vec<VarianceRow>,
+    },
+    VarianceTransactionsLoaded {
+        category_id: String,
+        transactions: Vec<VarianceTransaction>,
+    },
+    CashFlowProjected {
+        projection: CashFlowProjection,
+    },
+    SpendingHeatmapLoaded {
+        heatmap: SpendingHeatmap,
+    },
+    SpendingBreakdownLoaded {
+        breakdown: SpendingBreakdown,
+    },
+    IncomeExpenseLoaded {
+        report: IncomeExpense,
+    },
+    GoalObligationsForecast {
+        forecast: ObligationsForecast,
+    },
+    NetWorthLoaded {
+        net_worth: NetWorth,
+    },
+}
+
+macro_rules! domain_event {
+    ($domain:ident, $variant:ident) => {
+        impl From<$domain> for DataEvent {
+            fn from(event: $domain) -> Self {
+                DataEvent::$variant(event)
+            }
+        }
+    };
+}
+
+domain_event!(BudgetEvent, Budget);
+domain_event!(TransactionEvent, Transaction);
+domain_event!(ScheduledEvent, Scheduled);
+domain_event!(PlanEvent, Plan);
+domain_event!(PayeeEvent, Payee);
+domain_event!(ReportEvent, Report);
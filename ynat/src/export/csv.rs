@@ -0,0 +1,121 @@
+//! CSV export of a transaction list.
+//!
+//! Columns follow YNAB's own register export (Outflow and Inflow rather than a
+//! signed amount) so a file can be opened next to one, or imported back.
+//! Dates and amounts use the budget's formats.
+
+use ynab_api::endpoints::{budgets::BudgetSummary, transactions::Transaction};
+
+use crate::ui::utils::{fmt_date, format_amount};
+
+const HEADER: [&str; 8] = [
+    "Date", "Account", "Payee", "Category", "Memo", "Outflow", "Inflow", "Cleared",
+];
+
+/// CSV text for `transactions`, in the order given
+pub fn transactions_csv(transactions: &[&Transaction], budget: Option<&BudgetSummary>) -> String {
+    let mut out = String::new();
+    push_row(&mut out, HEADER.map(String::from));
+
+    for transaction in transactions {
+        let amount: i64 = transaction.amount.into();
+        let (outflow, inflow) = if amount < 0 {
+            (format_export_amount(-amount, budget), String::new())
+        } else {
+            (String::new(), format_export_amount(amount, budget))
+        };
+        push_row(
+            &mut out,
+            [
+                format_export_date(transaction, budget),
+                transaction.account_name.clone(),
+                transaction.payee_name.clone().unwrap_or_default(),
+                transaction.category_name.clone().unwrap_or_default(),
+                transaction.memo.clone().unwrap_or_default(),
+                outflow,
+                inflow,
+                transaction.cleared.to_string(),
+            ],
+        );
+    }
+    out
+}
+
+fn format_export_date(transaction: &Transaction, budget: Option<&BudgetSummary>) -> String {
+    let date_iso = transaction.date.format("%Y-%m-%d").to_string();
+    match budget.and_then(|b| b.date_format.as_ref()) {
+        Some(date_format) => fmt_date(&date_iso, date_format),
+        None => date_iso,
+    }
+}
+
+fn format_export_amount(amount: i64, budget: Option<&BudgetSummary>) -> String {
+    // The on-screen format pads positive amounts to line up with a minus sign
+    format_amount(amount, budget).trim().to_string()
+}
+
+fn push_row(out: &mut String, fields: impl IntoIterator<Item = String>) {
+    let fields: Vec<String> = fields.into_iter().map(|f| escape(&f)).collect();
+    out.push_str(&fields.join(","));
+    out.push_str("\r\n");
+}
+
+/// Quote a field if it contains a delimiter, quote or line break
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{budget, transaction};
+    use ynab_api::endpoints::DateFormat;
+
+    #[test]
+    fn rows_split_outflow_and_inflow_and_quote_awkward_fields() {
+        let grocery = transaction("2025-03-07", -42_500)
+            .payee("Corner Shop, Inc")
+            .category("Groceries")
+            .memo("said \"thanks\"")
+            .build();
+        let refund = transaction("2025-03-07", 10_000)
+            .payee("Corner Shop")
+            .category("Groceries")
+            .build();
+
+        let csv = transactions_csv(&[&grocery, &refund], None);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(
+            lines[0],
+            "Date,Account,Payee,Category,Memo,Outflow,Inflow,Cleared"
+        );
+        assert_eq!(
+            lines[1],
+            "2025-03-07,Checking,\"Corner Shop, Inc\",Groceries,\"said \"\"thanks\"\"\",$42.50,,Cleared"
+        );
+        assert_eq!(
+            lines[2],
+            "2025-03-07,Checking,Corner Shop,Groceries,,,$10.00,Cleared"
+        );
+    }
+
+    #[test]
+    fn dates_use_the_budget_format() {
+        let budget = BudgetSummary {
+            date_format: Some(DateFormat {
+                format: "DD.MM.YYYY".to_string(),
+            }),
+            ..budget("Budget")
+        };
+        let grocery = transaction("2025-03-07", -1_000).build();
+
+        let csv = transactions_csv(&[&grocery], Some(&budget));
+
+        assert!(csv.lines().nth(1).unwrap().starts_with("07.03.2025,"));
+    }
+}
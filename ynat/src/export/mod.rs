@@ -0,0 +1,152 @@
+//! Writing exported data, optionally encrypted with age or GPG.
+//!
+//! Encryption shells out to the `age`/`gpg` binary on `PATH` so keys and
+//! agents are handled exactly as on the command line. Plaintext is only ever
+//! held in memory and passed over a pipe.
+
+pub mod csv;
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config::{EncryptionTool, ExportConfig};
+
+impl EncryptionTool {
+    fn program(&self) -> &'static str {
+        match self {
+            EncryptionTool::Age => "age",
+            EncryptionTool::Gpg => "gpg",
+        }
+    }
+
+    /// Extension appended to encrypted files
+    pub fn extension(&self) -> &'static str {
+        match self {
+            EncryptionTool::Age => "age",
+            EncryptionTool::Gpg => "gpg",
+        }
+    }
+
+    /// Arguments to encrypt stdin to `recipient` on stdout
+    fn args(&self, recipient: &str, armor: bool) -> Vec<String> {
+        let mut args: Vec<String> = match self {
+            EncryptionTool::Age => vec!["--encrypt".into(), "--recipient".into()],
+            EncryptionTool::Gpg => vec![
+                "--batch".into(),
+                "--yes".into(),
+                "--encrypt".into(),
+                "--recipient".into(),
+            ],
+        };
+        args.push(recipient.to_string());
+        if armor {
+            args.push("--armor".into());
+        }
+        args
+    }
+}
+
+/// Encrypt `contents` if `config` asks for it, otherwise return them unchanged.
+/// `armor` produces ASCII output, for stdout or mail bodies.
+pub fn encrypt(contents: &[u8], config: &ExportConfig, armor: bool) -> Result<Vec<u8>> {
+    let Some(tool) = config.encrypt else {
+        return Ok(contents.to_vec());
+    };
+    let recipient = config
+        .recipient
+        .as_deref()
+        .filter(|r| !r.trim().is_empty())
+        .ok_or_else(|| anyhow!("[export] encrypt is set but no recipient is configured"))?;
+
+    let mut child = Command::new(tool.program())
+        .args(tool.args(recipient, armor))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}; is it installed?", tool.program()))?;
+
+    // Feed stdin from another thread so a large export can't deadlock on a full pipe
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = contents.to_vec();
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+
+    let output = child.wait_with_output()?;
+    writer
+        .join()
+        .map_err(|_| anyhow!("{} input thread panicked", tool.program()))??;
+
+    if !output.status.success() {
+        bail!(
+            "{} failed: {}",
+            tool.program(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Path an export to `path` actually ends up at, with `.age`/`.gpg` added when encrypting
+pub fn output_path(path: &Path, config: &ExportConfig) -> PathBuf {
+    match config.encrypt {
+        Some(tool) if path.extension().and_then(|e| e.to_str()) != Some(tool.extension()) => {
+            let mut name = path.as_os_str().to_owned();
+            name.push(".");
+            name.push(tool.extension());
+            PathBuf::from(name)
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Write an export, encrypting it first when configured. Returns the path written.
+pub fn write(path: &Path, contents: &[u8], config: &ExportConfig) -> Result<PathBuf> {
+    let data = encrypt(contents, config, false)?;
+    let path = output_path(path, config);
+    std::fs::write(&path, data).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(tool: Option<EncryptionTool>) -> ExportConfig {
+        ExportConfig {
+            encrypt: tool,
+            recipient: Some("me@example.com".into()),
+            directory: None,
+        }
+    }
+
+    #[test]
+    fn encrypted_exports_get_an_extension() {
+        let path = Path::new("report.md");
+        assert_eq!(output_path(path, &config(None)), PathBuf::from("report.md"));
+        assert_eq!(
+            output_path(path, &config(Some(EncryptionTool::Age))),
+            PathBuf::from("report.md.age")
+        );
+        assert_eq!(
+            output_path(Path::new("report.gpg"), &config(Some(EncryptionTool::Gpg))),
+            PathBuf::from("report.gpg")
+        );
+    }
+
+    #[test]
+    fn plaintext_passes_through_without_encryption() {
+        assert_eq!(encrypt(b"data", &config(None), false).unwrap(), b"data");
+    }
+
+    #[test]
+    fn encryption_requires_a_recipient() {
+        let config = ExportConfig {
+            encrypt: Some(EncryptionTool::Age),
+            recipient: None,
+            directory: None,
+        };
+        assert!(encrypt(b"data", &config, false).is_err());
+    }
+}
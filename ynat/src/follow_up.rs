@@ -0,0 +1,100 @@
+//! Follow-up items for transactions, created by an external command.
+//!
+//! The command comes from `[follow_up] command` as a list of arguments, each
+//! of which may contain `{placeholders}` for the transaction's fields. It is
+//! run directly, not through a shell, so payee names and memos can't inject
+//! anything.
+
+use ynab_api::endpoints::{budgets::BudgetSummary, transactions::Transaction};
+
+use crate::ui::utils::format_amount;
+
+/// Fill each argument's placeholders from `transaction`
+///
+/// Supported: `{date}`, `{payee}`, `{amount}`, `{memo}`, `{account}`,
+/// `{category}` and `{id}`. Missing fields become empty strings. Each
+/// argument is read once, so a value that itself contains a placeholder
+/// stays as it is.
+pub fn render_args(
+    template: &[String],
+    transaction: &Transaction,
+    budget: Option<&BudgetSummary>,
+) -> Vec<String> {
+    let amount = format_amount(transaction.amount.into(), budget)
+        .trim()
+        .to_string();
+    let fields = [
+        ("{date}", transaction.date.format("%Y-%m-%d").to_string()),
+        (
+            "{payee}",
+            transaction.payee_name.clone().unwrap_or_default(),
+        ),
+        ("{amount}", amount),
+        ("{memo}", transaction.memo.clone().unwrap_or_default()),
+        ("{account}", transaction.account_name.clone()),
+        (
+            "{category}",
+            transaction.category_name.clone().unwrap_or_default(),
+        ),
+        ("{id}", transaction.id.to_string()),
+    ];
+
+    template.iter().map(|arg| fill(arg, &fields)).collect()
+}
+
+fn fill(arg: &str, fields: &[(&str, String)]) -> String {
+    let mut filled = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match fields
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                filled.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures as fixtures;
+
+    #[test]
+    fn placeholders_are_filled_per_argument() {
+        let transaction = fixtures::transaction("2025-01-10", -42_500)
+            .id(uuid::Uuid::nil())
+            .payee("ACME {id}; rm -rf /")
+            .build();
+
+        let template = vec![
+            "task".to_string(),
+            "add".to_string(),
+            "Check {payee} {amount} on {date}{memo}".to_string(),
+            "project:finance".to_string(),
+            "{unknown} {id}".to_string(),
+        ];
+
+        assert_eq!(
+            render_args(&template, &transaction, None),
+            vec![
+                "task",
+                "add",
+                "Check ACME {id}; rm -rf / -$42.50 on 2025-01-10",
+                "project:finance",
+                "{unknown} 00000000-0000-0000-0000-000000000000",
+            ]
+        );
+    }
+}
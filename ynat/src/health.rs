@@ -0,0 +1,189 @@
+//! Budget health checklist: the few things worth tidying up each month,
+//! worked out from the accounts, transactions and plan already cached.
+
+use crate::reports::variance::INTERNAL_GROUP;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use ynab_api::endpoints::{accounts::Account, months::MonthDetail, transactions::Transaction};
+
+/// An account counts as reconciled if it was reconciled this recently
+pub const RECONCILE_DAYS: i64 = 30;
+
+/// An open account that hasn't been reconciled in [`RECONCILE_DAYS`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaleAccount {
+    pub account_id: String,
+    pub name: String,
+    /// Date of the newest reconciled transaction, if any is cached
+    pub last_reconciled: Option<NaiveDate>,
+}
+
+/// One line of the checklist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HealthItem {
+    /// Every transaction has been approved
+    Approval { unapproved: usize },
+    /// Every open account was reconciled recently; stalest first
+    Reconciliation { stale: Vec<StaleAccount> },
+    /// No category this month is overspent; ids and names in plan order
+    Overspending { categories: Vec<(String, String)> },
+    /// Ready to Assign is exactly zero
+    ReadyToAssign { amount: i64 },
+}
+
+impl HealthItem {
+    pub fn passed(&self) -> bool {
+        match self {
+            Self::Approval { unapproved } => *unapproved == 0,
+            Self::Reconciliation { stale } => stale.is_empty(),
+            Self::Overspending { categories } => categories.is_empty(),
+            Self::ReadyToAssign { amount } => *amount == 0,
+        }
+    }
+}
+
+/// The checklist for one budget.
+///
+/// `transactions` holds each account's cached history by account id;
+/// accounts without one are left out of the approval and reconciliation
+/// checks rather than guessed at.
+pub fn checklist(
+    accounts: &[Account],
+    transactions: &[(String, Vec<Transaction>)],
+    month: &MonthDetail,
+    today: NaiveDate,
+) -> Vec<HealthItem> {
+    let unapproved = transactions
+        .iter()
+        .flat_map(|(_, transactions)| transactions)
+        .filter(|t| !t.approved && !t.deleted)
+        .count();
+
+    let mut stale: Vec<StaleAccount> = accounts
+        .iter()
+        .filter(|a| !a.closed && !a.deleted)
+        .filter_map(|account| {
+            let account_id = account.id.to_string();
+            let (_, history) = transactions.iter().find(|(id, _)| *id == account_id)?;
+            let history: Vec<_> = history.iter().filter(|t| !t.deleted).collect();
+            if history.is_empty() {
+                return None;
+            }
+            let last_reconciled = history
+                .iter()
+                .filter(|t| t.is_reconciled())
+                .map(|t| t.date)
+                .max();
+            let recent =
+                last_reconciled.is_some_and(|date| (today - date).num_days() <= RECONCILE_DAYS);
+            (!recent).then(|| StaleAccount {
+                account_id,
+                name: account.name.clone(),
+                last_reconciled,
+            })
+        })
+        .collect();
+    // Never reconciled sorts before any date
+    stale.sort_by_key(|a| a.last_reconciled);
+
+    let overspent = month
+        .categories
+        .iter()
+        .filter(|c| {
+            !c.hidden
+                && !c.deleted
+                && i64::from(c.balance) < 0
+                && c.category_group_name.as_deref() != Some(INTERNAL_GROUP)
+        })
+        .map(|c| (c.id.to_string(), c.name.clone()))
+        .collect();
+
+    vec![
+        HealthItem::Approval { unapproved },
+        HealthItem::Reconciliation { stale },
+        HealthItem::Overspending {
+            categories: overspent,
+        },
+        HealthItem::ReadyToAssign {
+            amount: month.to_be_budgeted.into(),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{self as fixtures, account, transaction};
+    use ynab_api::endpoints::transactions::ReconciliationStatus::*;
+
+    fn month(to_be_budgeted: i64, balances: &[(&str, i64)]) -> MonthDetail {
+        let categories = balances
+            .iter()
+            .map(|(name, balance)| fixtures::category(name).amounts(0, *balance).build())
+            .collect();
+        MonthDetail {
+            to_be_budgeted: to_be_budgeted.into(),
+            ..fixtures::month(categories)
+        }
+    }
+
+    #[test]
+    fn a_tidy_budget_passes_every_check() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+        let accounts = vec![account("Checking").build()];
+        let transactions = vec![(
+            accounts[0].id.to_string(),
+            vec![transaction("2025-03-01", -10_000)
+                .cleared(Reconciled)
+                .build()],
+        )];
+
+        let items = checklist(&accounts, &transactions, &month(0, &[("Rent", 0)]), today);
+
+        assert!(items.iter().all(HealthItem::passed), "{items:?}");
+    }
+
+    #[test]
+    fn each_failing_check_says_what_needs_fixing() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+        let accounts = vec![
+            account("Checking").build(),
+            account("Savings").build(),
+            account("Not cached").build(),
+        ];
+        let transactions = vec![
+            (
+                accounts[0].id.to_string(),
+                vec![
+                    transaction("2025-01-31", -10_000)
+                        .cleared(Reconciled)
+                        .build(),
+                    transaction("2025-03-18", -10_000).approved(false).build(),
+                ],
+            ),
+            (
+                accounts[1].id.to_string(),
+                vec![transaction("2025-03-02", -10_000)
+                    .cleared(Uncleared)
+                    .approved(false)
+                    .build()],
+            ),
+        ];
+        let month = month(25_000, &[("Dining", -4_000), ("Rent", 0)]);
+
+        let items = checklist(&accounts, &transactions, &month, today);
+
+        assert_eq!(items[0], HealthItem::Approval { unapproved: 2 });
+        let HealthItem::Reconciliation { stale } = &items[1] else {
+            panic!("{:?}", items[1]);
+        };
+        let names: Vec<_> = stale.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Savings", "Checking"]);
+        let HealthItem::Overspending { categories } = &items[2] else {
+            panic!("{:?}", items[2]);
+        };
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].1, "Dining");
+        assert_eq!(items[3], HealthItem::ReadyToAssign { amount: 25_000 });
+    }
+}
@@ -0,0 +1,624 @@
+# English (built-in) string catalog.
+#
+# To translate, copy this file to <config dir>/ynat/locales/<locale>.ftl
+# (e.g. ~/.config/ynat/locales/de.ftl), translate the values, and set
+# `locale = "de"` under [display] in config.toml. Missing keys fall back
+# to English.
+#
+# Plural forms use `.zero`, `.one` and `.other` suffixes; `{count}` is
+# replaced with the number.
+
+# Help bar and popup
+help-bar-default = Press ? for help
+help-title = " Help (press ? or Esc to close) "
+help-signed-in-as = Signed in as YNAB user {id}
+help-global-header = --- Global ---
+help-move-up = Move selection up
+help-move-down = Move selection down
+help-select-budget = Select budget and view accounts
+help-refresh-budgets = Refresh budgets
+help-view-transactions = View transactions for selected account
+help-enter-filter = Enter filter mode
+help-filter-accounts = Filter accounts by name, type, or balance
+help-filter-transactions = Filter by payee, category, memo, or amount
+help-filter-tokens = Narrow with since:/before: dates, >50 or <-100, cleared:yes/no, flag:red/none
+help-filter-exit = Exit filter mode (keep filter active)
+help-filter-clear = Clear filter and exit filter mode
+help-filter-backspace = Delete last character
+help-toggle-closed = Toggle showing deleted/closed accounts
+help-refresh-accounts = Refresh accounts
+help-search-all = Search transactions in every account
+help-inbox = Review unapproved and uncategorized transactions
+help-new-transaction = Create a new transaction
+help-edit-transaction = Edit selected transaction
+help-categorize-transaction = Set the selected transaction's category
+help-accept-suggestion = Accept the suggested category
+help-approve-transaction = Approve transaction
+help-approve-listed = Approve every listed transaction
+help-import-linked = Import from linked accounts
+help-toggle-cleared = Toggle cleared status (uncleared ↔ cleared)
+help-delete-transaction = Delete selected transaction
+help-yank-transaction = Copy transaction for duplicating
+help-paste-transaction = Paste copied transaction dated today
+help-paste-transaction-edit = Paste copied transaction into the form
+help-cycle-flag = Cycle flag color
+help-flag-view = List one flag color at a time
+help-clear-flags = Clear the flags of the listed transactions
+help-jump-to-transfer = Jump to the other side of a transfer
+help-context-menu = Show actions for selected row
+help-toggle-reconciled = Toggle showing reconciled transactions
+help-toggle-presets = Toggle quick filters: unapproved, uncleared, this month, flagged, splits, imported
+help-cycle-sort = Sort by the next column: date, amount, payee, category, cleared
+help-reverse-sort = Reverse the sort order
+help-refresh-transactions = Refresh transactions
+help-reconcile = Reconcile transactions
+help-toggle-reimbursement = Toggle awaiting reimbursement
+help-follow-up = Create a follow-up to check this transaction
+help-payee-location = Show the transaction in full with where its payee is
+help-apply-payee-rules = Categorize transactions to review by payee rule
+help-scheduled = Show scheduled transactions for this account
+help-export-csv = Export the listed transactions to CSV
+help-edit-budgeted = Edit budgeted amount
+help-goal-calculator = What-if calculator for target balance goals
+help-move-money = Move money to another category
+help-edit-goal = Change the goal's target amount or date
+help-archive-review = Review unused categories to archive
+help-quick-budget-underfunded = Fund underfunded goals (selected / all listed)
+help-quick-budget-last-month = Budget last month's amount (selected / all listed)
+help-quick-budget-average = Budget the 3-month average spent (selected / all listed)
+help-health-checklist = Budget health checklist
+help-refresh-plan = Refresh plan
+help-refresh-all = Refresh all data for this budget
+help-import-export = Import a YNAB budget data export (CSV)
+help-toggle-focus-view = Toggle focus view
+help-toggle-plan-group = Fold or unfold a category group
+help-goal-details = Show or hide the category's goal progress
+help-toggle-category-details = Show or hide the category's note and goal
+help-compare-months = Compare with an earlier month
+help-shift-comparison = Compare with the month before or after
+help-move-category-up = Move the category to the end of the group above
+help-move-category-down = Move the category to the start of the group below
+help-logs-up = Scroll up (older logs)
+help-logs-down = Scroll down (newer logs)
+help-logs-page-up = Scroll up one page
+help-logs-page-down = Scroll down one page
+help-logs-top = Scroll to oldest logs
+help-logs-bottom = Scroll to newest logs
+help-inspect = Inspect raw API payload
+help-inspector-records = Select recorded response
+help-inspector-detail = Scroll payload
+help-inspector-newest = Jump to newest response
+help-inspector-oldest = Jump to oldest response
+help-accept-reimbursement = Accept suggested deposit
+help-mark-reimbursed = Mark reimbursed without a deposit
+help-refresh-reimbursements = Refresh reimbursements
+help-refresh-net-worth = Refresh the accounts from YNAB
+help-toggle-payee-group = Include or skip this group
+help-rename-payees = Rename selected payees in YNAB
+help-merge-payees = Merge selected groups into one payee each
+help-refresh-payee-groups = Refresh payee groups
+help-refresh-scheduled = Refresh scheduled transactions
+help-report-drill-down = Show transactions for category
+help-report-close-drill-down = Back to report
+help-report-sort = Toggle sort by variance
+help-report-period = Change period
+help-report-refresh = Refresh report
+help-report-horizon = Change projection horizon
+help-report-switch = Switch between budget vs actual and spending heatmap
+help-report-obligations = Goal contributions due next month
+help-report-back-to-variance = Back to budget vs actual
+help-report-spending = Spending by category or payee
+help-report-income-expense = Income vs expense by month
+help-report-previous-month = Newer month
+help-report-next-month = Older month
+help-report-spending-grouping = Switch between spending by category and by payee
+help-report-month = Change month
+help-report-day-transactions = Show transactions from this day
+help-report-next-day = Next day
+help-report-previous-day = Previous day
+help-cash-flow = Project account balance
+help-account-type-filter = Cycle account type: cash, credit, loans, tracking
+help-jump-account = Jump to account by name
+help-jump-done = Stop jumping
+help-reconcile-reminder = Change how often to be reminded to reconcile
+help-toggle-favorite = Star or unstar, listing it first
+help-navigate-back = Navigate back
+help-go-budgets = Go to budgets
+help-go-plan = Go to plan
+help-go-logs = Go to logs
+help-go-reports = Go to reports
+help-go-payee-cleanup = Go to payee cleanup
+help-go-reimbursements = Go to reimbursements
+help-go-net-worth = Go to net worth
+help-go-scheduled = Go to scheduled transactions
+help-go-inspector = Go to API inspector
+help-go-top = Navigate to top of list
+help-go-bottom = Navigate to bottom of list
+help-count = Move down or up that many rows
+help-half-page = Move half a screen down or up
+help-undo = Undo the last delete, budget edit or cleared toggle
+help-redo = Redo what was undone
+help-toggle-help = Toggle this help
+help-quit = Quit application
+
+loading-indicator = Loading…
+
+# Confirmation popups
+confirm-yes = "[Y]es "
+confirm-no = "[N]o "
+confirm-cancel = " Cancel"
+delete-title = " Confirm Delete "
+delete-question = Are you sure you want to delete this transaction?
+reconciled-edit-title = " Edit Reconciled Transaction "
+reconciled-edit-warning = This transaction is marked as RECONCILED.\nAre you sure you want to edit it?
+reconciled-edit-explanation = Editing reconciled transactions may cause discrepancies\nwith your bank records.
+reconciled-edit-yes = "[Y]es, Edit "
+reconciled-edit-no = "[N]o, Cancel "
+reconcile-title = " Reconcile Account "
+reconcile-question = Does your current account balance match the cleared balance?
+reconcile-cleared-balance = Cleared balance:
+reconcile-yes-hint = "- Mark cleared transactions as reconciled / "
+export-title = " Export Complete "
+export-written = Transactions exported to
+export-dismiss = Press any key to close
+
+# Goal what-if calculator
+goal-calculator-title = " What If: {category} "
+goal-calculator-target = "Target:"
+goal-calculator-available = "Available:"
+goal-calculator-remaining = "Still needed:"
+goal-calculator-monthly = "Budget per month:"
+goal-calculator-reached = "Target reached in {month}"
+goal-calculator-reached-now = Target already reached
+goal-calculator-never = Target is never reached at this amount
+goal-calculator-by-month = "Reach target by (YYYY-MM):"
+goal-calculator-needed = "Budget {amount} per month"
+goal-calculator-invalid-month = Enter a month from the current month onward
+goal-calculator-hint = "Tab: switch question | Esc: close"
+move-money-title = " Move Money From {category} "
+move-money-available = "Available:"
+move-money-amount = "Amount:"
+move-money-destination = "To category:"
+move-money-hint = "Tab: switch field | ↑/↓: pick category | Enter: move | Esc: cancel"
+move-money-invalid-amount = Enter an amount above zero
+move-money-no-destination = No category matches; type part of a category name
+goal-form-title = " Goal: {category} "
+goal-form-type = "Goal type:"
+goal-form-target = "Target:"
+goal-form-target-month = "By (YYYY-MM):"
+goal-form-hint = "Tab: switch field | Enter: save | Esc: cancel"
+goal-form-invalid-target = Enter a target above zero
+goal-form-invalid-month = Enter the month as YYYY-MM, or leave it empty for no date
+quick-budget-underfunded = Fund underfunded
+quick-budget-last-month = Budget last month's amounts
+quick-budget-average-spent = Budget average spent
+quick-budget-loading = Looking at previous months...
+quick-budget-empty = Nothing to change. Every category is already at that amount.
+quick-budget-summary.one = {count} category will change.
+quick-budget-summary.other = {count} categories will change.
+quick-budget-total = Total
+quick-budget-ready-to-assign = Ready to Assign
+quick-budget-hint = "y/Enter: assign | j/k: scroll | Esc: cancel"
+archive-review-title = " Archive Unused Categories "
+archive-review-loading = Checking recent months...
+archive-review-empty = No categories have been unused for six months. Nothing to tidy up.
+archive-review-summary.zero = Nothing selected. Space to tick a category.
+archive-review-summary.one = {count} category with nothing budgeted, spent or available for six months will be moved to your Archive group.
+archive-review-summary.other = {count} categories with nothing budgeted, spent or available for six months will be moved to your Archive group.
+archive-review-hint = "Space: toggle | Enter: archive selected | Esc: cancel"
+notice-categories-archived.one = Moved {count} category to {group}
+notice-categories-archived.other = Moved {count} categories to {group}
+notice-categories-not-archived.one = {count} couldn't be moved
+notice-categories-not-archived.other = {count} couldn't be moved
+notice-archive-group-missing = YNAB's API can't hide categories. Add a category group named Archive in YNAB, then try again.
+health-title = " Budget Health "
+health-loading = Checking accounts, transactions and this month's plan...
+health-hint = "↑/↓: select | Enter: go fix it | Esc: close"
+health-approved = All transactions approved
+health-unapproved.one = {count} transaction waiting for approval
+health-unapproved.other = {count} transactions waiting for approval
+health-reconciled = Every account reconciled in the last {days} days
+health-stale-account = {account} needs reconciling ({since})
+health-reconciled-on = last reconciled {date}
+health-never-reconciled = no reconciled transactions loaded
+health-more-accounts.one = and {count} more account
+health-more-accounts.other = and {count} more accounts
+health-not-overspent = No overspent categories
+health-overspent-one = {category} is overspent
+health-overspent.one = {category} and {count} other category are overspent
+health-overspent.other = {category} and {count} other categories are overspent
+health-all-assigned = Ready to Assign is zero
+health-ready-to-assign = {amount} still ready to assign
+health-overassigned = {amount} more assigned than you have
+
+conflict-title = " Changed on the Server "
+conflict-explanation = {payee} on {date} was changed in YNAB while you had unsaved changes to it.
+conflict-waiting = {count} more waiting.
+conflict-hint = "m: keep mine | s: take server's | d: show differences"
+conflict-mine = Mine
+conflict-server = Server
+conflict-field-date = Date
+conflict-field-amount = Amount
+conflict-field-payee = Payee
+conflict-field-category = Category
+conflict-field-memo = Memo
+conflict-field-cleared = Cleared
+conflict-field-approved = Approved
+conflict-field-flag = Flag
+
+# Transaction form
+form-outflow = Outflow
+form-inflow = Inflow
+form-rounding-unit = "(≈1)"
+form-rate-prompt = 1 {currency} =
+form-rate-invalid = Enter the exchange rate as a positive number, or Esc to edit the amount
+form-rounding-five-hundredths = "(≈0.05)"
+
+# Errors
+error-network = Can't reach YNAB
+error-network-suggestion = Check your internet connection, then retry.
+error-unauthorized = Not signed in
+error-unauthorized-suggestion = Your YNAB session has expired. Restart ynat to sign in again.
+error-not-found = Not found
+error-not-found-suggestion = It may have been deleted in YNAB. Go back and refresh.
+error-rate-limited = Too many requests
+error-rate-limited-suggestion = YNAB allows 200 requests an hour. Wait a few minutes, then retry.
+error-server = YNAB is having trouble
+error-server-suggestion = This is usually temporary. Retry in a moment.
+error-validation = Invalid input
+error-validation-suggestion = Correct the value and submit again.
+error-cache = Local data unreadable
+error-cache-suggestion = Retry to reload it from YNAB.
+error-other = Something went wrong
+error-other-suggestion = Retry, or check the logs (gl) for details.
+error-panel-retry = "r: retry | h: back"
+error-panel-back = "h: back"
+
+# Notices
+notice-read-only = Read-only: ynat can't change this budget
+notice-follow-up-created = Follow-up created
+notice-follow-up-failed = Follow-up command failed: {error}
+notice-payee-location-opened = Payee location {lat}, {lon} opened in maps
+notice-payee-location-failed = Couldn't open the payee location: {error}
+notice-export-failed = Export failed: {error}
+notice-undone = Undid {action}
+notice-redone = Redid {action}
+notice-nothing-to-undo = Nothing to undo
+notice-nothing-to-redo = Nothing to redo
+notice-undo-pending = Still waiting on YNAB for the last undo or redo
+lint-payee-spelling = Payee "{payee}" looks like "{existing}"
+lint-card-number = Memo looks like it contains a card number
+lint-account-number = Memo looks like it contains an account number
+lint-word = Memo mentions "{word}"
+lint-save-anyway = {warnings} · Enter again to save anyway
+notice-category-moved = Moved {category} to {group}
+notice-no-group-above = Already in the first group
+notice-no-group-below = Already in the last group
+undo-transaction-delete = transaction delete
+undo-budget-edit = budget edit
+undo-money-move = money move
+undo-quick-budget = quick budget
+undo-cleared-toggle = cleared toggle
+notice-payees-merged.one = Merged {groups} payee groups, moving {count} transaction
+notice-payees-merged.other = Merged {groups} payee groups, moving {count} transactions
+notice-payees-merge-failed = "; {failed} failed, try again"
+notice-transactions-approved.one = Approved {count} transaction
+notice-transactions-approved.other = Approved {count} transactions
+notice-approve-failed = Couldn't approve the transactions: {error}
+notice-approve-transaction-failed = Couldn't approve the transaction: {error}
+notice-update-failed = Couldn't update the transaction: {error}
+notice-delete-failed = Couldn't delete the transaction, press r to reload: {error}
+notice-reconcile-failed = Couldn't reconcile, press r to reload: {error}
+notice-budget-update-failed = Couldn't change the assigned amount: {error}
+notice-nothing-to-approve = No unapproved transactions listed
+notice-transactions-categorized.one = Categorized {count} transaction by payee rule
+notice-transactions-categorized.other = Categorized {count} transactions by payee rule
+notice-categorize-failed = Couldn't categorize the transactions: {error}
+notice-nothing-flagged = No flagged transactions listed
+notice-flags-cleared.one = Cleared the flag of {count} transaction
+notice-flags-cleared.other = Cleared the flags of {count} transactions
+notice-clear-flags-failed = Couldn't clear the flags: {error}
+notice-no-rule-matches = No transactions to review match a payee rule
+notice-importing = Importing from linked accounts…
+notice-transactions-imported.zero = No new transactions to import
+notice-transactions-imported.one = Imported {count} transaction
+notice-transactions-imported.other = Imported {count} transactions
+notice-import-failed = Couldn't import transactions: {error}
+notice-cache-recovered = Some cached data couldn't be read and is being reloaded from YNAB
+notice-write-queued.one = YNAB can't be reached; the change will be sent when it can
+notice-write-queued.other = YNAB can't be reached; {count} changes will be sent when it can
+notice-pending-writes-synced.one = Sent {count} change made offline to YNAB
+notice-pending-writes-synced.other = Sent {count} changes made offline to YNAB
+notice-pending-write-rejected = YNAB refused a change made offline ({change}): {error}
+notice-pending-write-unconfirmed = A change made offline may not have reached YNAB ({change}); check it there: {error}
+pending-write-create = new transaction on {date}
+pending-write-update = transaction edit
+pending-write-delete = transaction deletion
+pending-write-budget = assigned amount for {month}
+notice-goal-not-set = This category has no goal; YNAB only allows creating goals in its own apps
+notice-goal-update-failed = Couldn't change the goal: {error}
+rate-limit-warning = {remaining} of {limit} YNAB requests left this hour, refreshing less
+read-only-badge = READ-ONLY
+spending-counter = Today {today} · Week {week}
+sync-pending.one = {count} change not synced
+sync-pending.other = {count} changes not synced
+
+# Per-row context menu
+context-menu-copy-id = Copy id to clipboard
+context-menu-hint = "Enter: run | Esc: close"
+
+# Screen titles and empty states
+accounts-title = Accounts
+accounts-title-filtered.one = Accounts ({count} filtered)
+accounts-title-filtered.other = Accounts ({count} filtered)
+accounts-jump-title = Jump to (Enter/Esc: done)
+account-group-cash = Cash
+account-group-credit = Credit
+account-group-loans = Loans
+account-group-tracking = Tracking
+accounts-loading = Loading accounts...
+accounts-empty = No accounts found
+accounts-no-match = No matching accounts
+accounts-reconcile-due = due
+accounts-reconcile-overdue = overdue
+budgets-title = Budgets
+budgets-loading = Loading budgets...
+budgets-empty = No budgets found
+budgets-empty-hint = Create a budget at https://app.ynab.com
+logs-title.one = Logs ({count} entry)
+logs-title.other = Logs ({count} entries)
+logs-empty-title = Session Logs
+logs-empty = No logs yet
+filter-title = Filter (Enter: apply, Esc: clear)
+plan-favorites = ★ Favorites
+plan-age-of-money = Age of Money
+plan-age-of-money-days.one = {count} day
+plan-age-of-money-days.other = {count} days
+plan-age-of-money-unknown = —
+plan-income = Income
+plan-budgeted = Budgeted
+plan-activity = Activity
+plan-available = Available
+plan-month = Month
+plan-to-budget = To Budget
+plan-categories = Categories
+plan-categories-view = Categories - {view}
+plan-categories-editing = Categories - Editing: {category} (Enter=save, Esc=cancel)
+plan-categories-invalid = Categories - {error} [{category}]
+plan-view-all = All
+plan-view-snoozed = Snoozed
+plan-view-underfunded = Underfunded
+plan-view-overfunded = Overfunded
+plan-view-money-available = Money Available
+plan-empty = No categories to display
+plan-empty-snoozed = No snoozed categories
+plan-empty-underfunded = No underfunded categories
+plan-empty-overfunded = No overfunded categories
+plan-empty-money-available = No categories with money available
+goal-details-title = Goal
+goal-details-none = No goal
+goal-details-no-category = Select a category
+goal-details-type = Type
+goal-details-target = Target
+goal-details-by = By
+goal-details-funded = Funded
+goal-details-left = Left
+goal-details-this-month = This month
+goal-details-months-left = Months left
+goal-details-snoozed = Snoozed this month
+goal-details-percent-funded = {percent}% funded
+goal-type-target-balance = Target balance
+goal-type-monthly-savings = Monthly savings
+goal-type-needed-for-spending = Needed for spending
+goal-type-monthly-debt-payment = Monthly debt payment
+notice-reconcile-due = Time to reconcile: {accounts}
+reconcile-overdue = {account} (overdue)
+notice-reconcile-reminder-set = {account}: reconcile {cadence}
+notice-reconcile-reminder-off = {account}: no reconcile reminder
+notice-favorite-added = Starred {name}
+notice-favorite-removed = Unstarred {name}
+transactions-title = Transactions
+transactions-title-filtered.one = Transactions ({count} filtered)
+transactions-title-filtered.other = Transactions ({count} filtered)
+transactions-loading = Loading transactions...
+transactions-empty = No transactions found
+transactions-no-match = No matching transactions
+preset-unapproved = Unapproved
+preset-uncleared = Uncleared
+preset-this-month = This month
+preset-flagged = Flagged
+preset-splits = Splits
+preset-imported = Imported
+flag-red = Red
+flag-orange = Orange
+flag-yellow = Yellow
+flag-green = Green
+flag-blue = Blue
+flag-purple = Purple
+search-title = All Accounts
+inbox-title = Inbox: {reviewed} of {total} reviewed
+inbox-title-empty = Inbox: nothing to review
+search-title-filtered.one = All Accounts ({count} match)
+search-title-filtered.other = All Accounts ({count} matches)
+transactions-empty-since = No transactions since {date}. Press j to load older ones.
+transactions-older-hint = " Since {date} · j on the last row loads older "
+transactions-balance-column = Balance
+transactions-suggested-category = {category}? (Tab)
+transactions-sorted-by = " Sorted by {column} {arrow} "
+selection-category-available = {name}: {amount} available
+selection-account-working = {name}: {amount} working balance
+selection-possible-match = Possible match: {payee} on {date}
+transactions-reconciled.zero = No transactions reconciled
+transactions-reconciled.one = {count} transaction reconciled
+transactions-reconciled.other = {count} transactions reconciled
+
+# API inspector
+inspector-title = API Inspector
+inspector-title-focused = "API Inspector: {id}"
+inspector-records = Responses
+inspector-payload = Payload
+inspector-request = Request
+inspector-response = Response
+inspector-empty = No API responses recorded yet
+inspector-no-match = No recorded response contains this item
+
+# Transaction detail
+detail-title = " Transaction "
+detail-date = Date
+detail-payee = Payee
+detail-category = Category
+detail-account = Account
+detail-amount = Amount
+detail-memo = Memo
+detail-location = Location
+detail-coordinates = Coordinates
+detail-location-loading = Asking YNAB…
+detail-unknown-city = Town not looked up (see [maps] geocoder)
+detail-no-location = None recorded; locations come from YNAB's mobile apps
+detail-location-failed = "Couldn't load it: {error}"
+detail-open-in-maps = " open in maps  "
+detail-close = " close"
+
+# YNAB export import
+import-title = " Import YNAB Export "
+import-source = From {budget}, exported {date}
+import-other-budget = This export is from {export}, not {budget}. Its amounts will be assigned in this budget.
+import-question.one = Create up to {count} transaction and replace {allocations} assigned amounts in {months} months? {skipped} rows don't match this budget and will be skipped.
+import-question.other = Create up to {count} transactions and replace {allocations} assigned amounts in {months} months? {skipped} rows don't match this budget and will be skipped.
+import-register = Register: {file}
+import-plan = Plan: {file}
+import-no-file = none
+import-summary = "Imported {transactions} transactions and {allocations} plan amounts ({skipped} skipped)"
+
+# Payee cleanup
+settings-title = Settings
+settings-setting = Setting
+settings-value = Value
+settings-date-format = Date format
+settings-date-format-budget = Budget's own
+settings-rounding = Round new amounts
+settings-rounding-none = No rounding
+settings-rounding-unit = To whole units
+settings-rounding-five-hundredths = To 0.05
+settings-approve-on-save = Approve when saving
+settings-approve-on-clear = Approve when clearing
+settings-confirm-delete = Ask before deleting (this budget)
+settings-auto-refresh = Check for changes
+settings-on = On
+settings-off = Off
+settings-every-minutes.one = Every minute
+settings-every-minutes.other = Every {count} minutes
+settings-every-seconds.one = Every second
+settings-every-seconds.other = Every {count} seconds
+settings-preview = Preview
+settings-preview-date = Today is {date}
+settings-preview-amount = An outflow typed as {typed} is saved as {saved}
+settings-saved-to = Changes are saved to {path}
+help-change-setting = Next value
+help-change-setting-back = Previous value
+help-go-settings = Go to settings
+
+payee-cleanup-title = Similar Payees
+payee-cleanup-loading = Looking for similar payees...
+payee-cleanup-empty = No near-duplicate payees found
+payee-cleanup-summary.zero = nothing selected
+payee-cleanup-summary.one = {count} payee will be renamed
+payee-cleanup-summary.other = {count} payees will be renamed
+payee-merge-title = " Merge Payees "
+payee-merge-question.one = Move the transactions of {count} duplicate payee to the payee kept in its group, then hide it?
+payee-merge-question.other = Move the transactions of {count} duplicate payees to the payee kept in each of {groups} groups, then hide them?
+
+# Reimbursements
+reimbursements-title = Awaiting Reimbursement
+reimbursements-summary.one = "{count} outstanding, {total} expected"
+reimbursements-summary.other = "{count} outstanding, {total} expected"
+reimbursements-loading = Loading reimbursements...
+reimbursements-empty = Nothing awaiting reimbursement. Press $ on a transaction to track it.
+reimbursements-no-match = No matching deposit yet
+net-worth-title = Net Worth
+net-worth-loading = Adding up accounts...
+net-worth-empty = No open accounts
+net-worth-by-type = By account type
+net-worth-assets = Assets
+net-worth-liabilities = Liabilities
+net-worth-net = Net worth
+account-type-checking = Checking
+account-type-savings = Savings
+account-type-cash = Cash
+account-type-credit-card = Credit card
+account-type-line-of-credit = Line of credit
+account-type-other-asset = Tracking asset
+account-type-other-liability = Tracking liability
+account-type-mortgage = Mortgage
+account-type-auto-loan = Auto loan
+account-type-student-loan = Student loan
+account-type-personal-loan = Personal loan
+account-type-medical-debt = Medical debt
+account-type-other-debt = Other debt
+
+# Scheduled transactions
+scheduled-title = Scheduled Transactions
+scheduled-summary.one = {count} upcoming
+scheduled-summary.other = {count} upcoming
+scheduled-loading = Loading scheduled transactions...
+scheduled-empty = No scheduled transactions.
+frequency-never = Once
+frequency-daily = Daily
+frequency-weekly = Weekly
+frequency-every-other-week = Every other week
+frequency-twice-a-month = Twice a month
+frequency-every-4-weeks = Every 4 weeks
+frequency-monthly = Monthly
+frequency-every-other-month = Every other month
+frequency-every-3-months = Every 3 months
+frequency-every-4-months = Every 4 months
+frequency-twice-a-year = Twice a year
+frequency-yearly = Yearly
+frequency-every-other-year = Every other year
+
+# Reports
+reports-title = Reports
+reports-loading = Loading report...
+report-variance = Budget vs Actual
+report-variance-by-category = Categories
+report-variance-by-variance = Categories (most overspent first)
+report-variance-empty = Nothing budgeted or spent in this period
+report-drill-down-empty = No cached transactions for this category. Open the account to load them.
+report-period-this-month = This month
+report-period-last-month = Last month
+report-period-last-three-months = Last 3 months
+report-period-year-to-date = Year to date
+report-cash-flow = Cash Flow
+report-horizon-days = Next {days} days
+report-cash-flow-empty = No projection yet
+report-cash-flow-start = Today
+report-cash-flow-lowest = Lowest
+report-cash-flow-run-rate = Unscheduled per day
+report-cash-flow-negative-days = Days below zero
+report-cash-flow-never-negative = Balance stays above zero for the whole period
+report-obligations = Goals Due
+report-obligations-empty = No forecast yet
+report-obligations-due = Goal contributions
+report-obligations-income = Scheduled income
+report-obligations-left = Left over
+report-obligations-short = Goals need {amount} more than the income scheduled for the month. Schedule the paychecks you expect, or plan for the gap.
+report-obligations-covered = Scheduled income covers every goal
+report-obligations-goals = Underfunded goals
+report-obligations-scheduled-income = Scheduled income
+report-spending = Spending
+report-spending-by-category = By category
+report-spending-by-payee = By payee
+report-spending-total = Total spent
+report-spending-empty = Nothing spent in this period
+report-spending-uncategorized = Uncategorized
+report-spending-no-payee = No payee
+report-income-expense = Income vs Expense
+report-income-expense-months = last 12 months
+report-income-expense-total = Net over 12 months
+report-income-expense-empty = Nothing in or out this month
+report-heatmap = Spending Heatmap
+report-heatmap-empty = Nothing to show for this month
+report-heatmap-spent = Spent
+report-heatmap-total = Month total
+report-heatmap-less = Less
+report-heatmap-more = More
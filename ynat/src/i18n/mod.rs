@@ -0,0 +1,146 @@
+//! Localization of user-facing strings.
+//!
+//! Strings are looked up by key in a catalog written in a small fluent-style
+//! `key = value` format. The English catalog is compiled in; other locales are
+//! loaded from `<config dir>/ynat/locales/<locale>.ftl` and fall back to
+//! English for any missing key.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const EN_CATALOG: &str = include_str!("locales/en.ftl");
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+#[derive(Debug, Default)]
+struct Catalog {
+    strings: HashMap<String, &'static str>,
+}
+
+impl Catalog {
+    /// Build a catalog from the English defaults overlaid with an optional translation
+    fn new(translation: Option<&'static str>) -> Self {
+        let mut strings = parse(EN_CATALOG);
+        if let Some(source) = translation {
+            strings.extend(parse(source));
+        }
+        Self { strings }
+    }
+}
+
+/// Select the active locale. Must be called before the first lookup; later calls are ignored.
+///
+/// Unknown locales (no catalog file) log a warning and keep English.
+pub fn init(locale: Option<&str>) {
+    let translation = locale.filter(|l| *l != "en").and_then(load_locale_file);
+    let _ = CATALOG.set(Catalog::new(translation));
+}
+
+/// Look up a localized string by key. Returns the key itself if it is missing everywhere.
+pub fn t(key: &'static str) -> &'static str {
+    catalog().strings.get(key).copied().unwrap_or(key)
+}
+
+/// Look up a localized string with plural handling, replacing `{count}`.
+///
+/// Uses `<key>.zero` (if present) for 0, `<key>.one` for 1 and `<key>.other` otherwise.
+pub fn tn(key: &'static str, count: usize) -> String {
+    let strings = &catalog().strings;
+    let form = match count {
+        0 if strings.contains_key(&format!("{}.zero", key)) => "zero",
+        1 => "one",
+        _ => "other",
+    };
+    let template = strings
+        .get(&format!("{}.{}", key, form))
+        .or_else(|| strings.get(&format!("{}.other", key)))
+        .copied()
+        .unwrap_or(key);
+    template.replace("{count}", &count.to_string())
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| Catalog::new(None))
+}
+
+fn load_locale_file(locale: &str) -> Option<&'static str> {
+    let path = dirs::config_dir()?
+        .join("ynat")
+        .join("locales")
+        .join(format!("{}.ftl", locale));
+    match std::fs::read_to_string(&path) {
+        // Catalogs live for the whole program, so leaking gives us &'static lookups
+        Ok(source) => Some(Box::leak(source.into_boxed_str())),
+        Err(e) => {
+            tracing::warn!("No catalog for locale '{}' at {:?}: {}", locale, path, e);
+            None
+        }
+    }
+}
+
+/// Parse `key = value` lines. `#` starts a comment line, values may be wrapped in
+/// double quotes to keep surrounding spaces, and `\n` is an escaped newline.
+fn parse(source: &'static str) -> HashMap<String, &'static str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            let value: &'static str = if value.contains("\\n") {
+                Box::leak(value.replace("\\n", "\n").into_boxed_str())
+            } else {
+                value
+            };
+            Some((key.trim().to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_handles_quotes_comments_and_newlines() {
+        let strings = parse("# comment\nplain = Hello\nquoted = \" padded \"\nmulti = a\\nb\n");
+        assert_eq!(strings.get("plain"), Some(&"Hello"));
+        assert_eq!(strings.get("quoted"), Some(&" padded "));
+        assert_eq!(strings.get("multi"), Some(&"a\nb"));
+        assert!(!strings.contains_key("# comment"));
+    }
+
+    #[test]
+    fn translation_overrides_english_and_falls_back() {
+        let catalog = Catalog::new(Some("help-quit = Beenden\n"));
+        assert_eq!(catalog.strings.get("help-quit"), Some(&"Beenden"));
+        assert_eq!(
+            catalog.strings.get("help-go-plan"),
+            Some(&"Go to plan"),
+            "missing keys should fall back to English"
+        );
+    }
+
+    #[test]
+    fn tn_selects_plural_forms() {
+        assert_eq!(
+            tn("transactions-reconciled", 0),
+            "No transactions reconciled"
+        );
+        assert_eq!(tn("transactions-reconciled", 1), "1 transaction reconciled");
+        assert_eq!(
+            tn("transactions-reconciled", 3),
+            "3 transactions reconciled"
+        );
+    }
+
+    #[test]
+    fn t_returns_key_when_missing() {
+        assert_eq!(t("no-such-key"), "no-such-key");
+    }
+}
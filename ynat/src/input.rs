@@ -1,5 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent as CrosstermKeyEvent, KeyModifiers};
 
+use crate::utils::platform;
+
 /// Framework-agnostic key representation for testability
 ///
 /// This enum abstracts away the crossterm-specific KeyCode type,
@@ -79,13 +81,59 @@ impl From<KeyCode> for Key {
 
 impl From<CrosstermKeyEvent> for KeyEvent {
     fn from(event: CrosstermKeyEvent) -> Self {
-        Self {
-            key: Key::from(event.code),
-            modifiers: Modifiers {
+        Self::normalized(
+            Key::from(event.code),
+            Modifiers {
                 ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
                 alt: event.modifiers.contains(KeyModifiers::ALT),
                 shift: event.modifiers.contains(KeyModifiers::SHIFT),
             },
+            platform::ALTGR_IS_CTRL_ALT,
+        )
+    }
+}
+
+impl KeyEvent {
+    /// Smooth over terminal and platform differences in how keys are reported
+    fn normalized(mut key: Key, mut modifiers: Modifiers, altgr_is_ctrl_alt: bool) -> Self {
+        // Some terminals (e.g. Windows console, older xterms) send Shift+Tab as Tab + SHIFT
+        if key == Key::Tab && modifiers.shift {
+            key = Key::BackTab;
         }
+
+        // AltGr arrives as Ctrl+Alt on Windows; it is text input, not a shortcut
+        if altgr_is_ctrl_alt && modifiers.ctrl && modifiers.alt && matches!(key, Key::Char(_)) {
+            modifiers.ctrl = false;
+            modifiers.alt = false;
+        }
+
+        Self { key, modifiers }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifiers(ctrl: bool, alt: bool, shift: bool) -> Modifiers {
+        Modifiers { ctrl, alt, shift }
+    }
+
+    #[test]
+    fn shift_tab_is_normalized_to_backtab() {
+        let event = KeyEvent::normalized(Key::Tab, modifiers(false, false, true), false);
+        assert_eq!(event.key, Key::BackTab);
+    }
+
+    #[test]
+    fn altgr_char_is_plain_text_when_reported_as_ctrl_alt() {
+        let event = KeyEvent::normalized(Key::Char('@'), modifiers(true, true, false), true);
+        assert_eq!(event, KeyEvent::new(Key::Char('@')));
+    }
+
+    #[test]
+    fn ctrl_alt_is_kept_where_altgr_is_distinct() {
+        let event = KeyEvent::normalized(Key::Char('s'), modifiers(true, true, false), false);
+        assert!(event.modifiers.ctrl && event.modifiers.alt);
     }
 }
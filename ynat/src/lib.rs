@@ -1,13 +1,40 @@
+pub mod api_log;
 mod app;
 pub mod app_core;
+pub mod archive;
 mod background;
+pub mod budget_settings;
 mod cache;
+pub mod category_suggestions;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod config_bundle;
+pub mod config_edit;
+pub mod error;
 pub mod events;
+pub mod export;
+pub mod follow_up;
+pub mod health;
+pub mod i18n;
 pub mod input;
+pub mod lint;
 pub mod log_buffer;
 pub mod logging;
+pub mod maps;
+pub mod options;
+pub mod payee_cleanup;
+pub mod quick_budget;
+pub mod reconcile_reminders;
+pub mod reimbursements;
+pub mod replay;
+pub mod reports;
+pub mod rules;
+pub mod session_log;
+pub mod settings;
 pub mod state;
+#[cfg(test)]
+mod test_fixtures;
 pub mod ui;
 mod utils;
 
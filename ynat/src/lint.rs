@@ -0,0 +1,184 @@
+//! Second-look checks on the transaction form before it's saved.
+//!
+//! Nothing here stops a save for good: the first submit shows the warnings,
+//! and submitting again with the same warnings saves anyway. Each rule can be
+//! switched off under `[lint]` in `config.toml`.
+
+use crate::config::LintConfig;
+use crate::i18n::t;
+use crate::payee_cleanup;
+use crate::state::TransactionFormState;
+use ynab_api::endpoints::payees::Payee;
+
+/// Warnings for the form, in a stable order so a resubmit can recognise them
+pub fn check(form: &TransactionFormState, payees: &[Payee], config: &LintConfig) -> Vec<String> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let mut warnings = Vec::new();
+    if config.payee_spelling {
+        if let Some(existing) = misspelled_payee(&form.payee, payees) {
+            warnings.push(
+                t("lint-payee-spelling")
+                    .replace("{payee}", &form.payee)
+                    .replace("{existing}", existing),
+            );
+        }
+    }
+
+    let memos = std::iter::once(form.memo.as_str())
+        .chain(form.subtransactions.iter().map(|s| s.memo.as_str()))
+        .filter(|memo| !memo.is_empty());
+    for memo in memos {
+        if config.card_numbers && contains_card_number(memo) {
+            warnings.push(t("lint-card-number").to_string());
+        } else if config.account_numbers && contains_account_number(memo, config.account_digits) {
+            warnings.push(t("lint-account-number").to_string());
+        }
+        let lower = memo.to_lowercase();
+        for word in &config.words {
+            if !word.is_empty() && lower.contains(&word.to_lowercase()) {
+                warnings.push(t("lint-word").replace("{word}", word));
+            }
+        }
+    }
+    warnings.dedup();
+    warnings
+}
+
+/// An existing payee the typed name looks like a misspelling of. A name that
+/// matches a payee exactly (ignoring case) is never flagged.
+fn misspelled_payee<'a>(name: &str, payees: &'a [Payee]) -> Option<&'a str> {
+    let name = name.trim();
+    if name.is_empty()
+        || payees
+            .iter()
+            .any(|p| !p.deleted && p.name.eq_ignore_ascii_case(name))
+    {
+        return None;
+    }
+    let key = payee_cleanup::normalize(name);
+    payees
+        .iter()
+        .filter(|p| !p.deleted && p.transfer_account_id.is_none())
+        .find(|p| payee_cleanup::similar(&key, &payee_cleanup::normalize(&p.name)))
+        .map(|p| p.name.as_str())
+}
+
+/// 13 to 19 digits, optionally grouped by single spaces or dashes, that pass
+/// the Luhn check card numbers carry
+fn contains_card_number(text: &str) -> bool {
+    let mut digits: Vec<u32> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c.to_digit(10) {
+            Some(d) => digits.push(d),
+            // A separator only continues the number if a digit follows
+            None if (c == ' ' || c == '-')
+                && !digits.is_empty()
+                && chars.peek().is_some_and(|n| n.is_ascii_digit()) => {}
+            None => {
+                if is_card_number(&digits) {
+                    return true;
+                }
+                digits.clear();
+            }
+        }
+    }
+    is_card_number(&digits)
+}
+
+fn is_card_number(digits: &[u32]) -> bool {
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2 == 1, d * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => d,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// An unbroken run of at least `min_digits` digits
+fn contains_account_number(text: &str, min_digits: usize) -> bool {
+    text.split(|c: char| !c.is_ascii_digit())
+        .any(|run| min_digits > 0 && run.len() >= min_digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn payee(name: &str) -> Payee {
+        Payee {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            transfer_account_id: None,
+            deleted: false,
+        }
+    }
+
+    fn form(payee: &str, memo: &str) -> TransactionFormState {
+        TransactionFormState {
+            payee: payee.to_string(),
+            memo: memo.to_string(),
+            ..TransactionFormState::new("account".to_string(), "YYYY-MM-DD")
+        }
+    }
+
+    fn config() -> LintConfig {
+        LintConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn card_numbers_need_a_valid_check_digit() {
+        assert!(contains_card_number("paid with 4111 1111 1111 1111"));
+        assert!(contains_card_number("card 4111-1111-1111-1111."));
+        assert!(!contains_card_number("card 4111 1111 1111 1112"));
+        assert!(!contains_card_number("split 3 ways on 2025-01-15"));
+    }
+
+    #[test]
+    fn typos_of_known_payees_are_flagged_but_exact_names_are_not() {
+        let payees = vec![payee("Blue Bottle Coffee"), payee("Amazon")];
+        let config = config();
+
+        assert_eq!(
+            check(&form("amazon", ""), &payees, &config),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check(&form("Blue Botle Coffee", ""), &payees, &config),
+            vec!["Payee \"Blue Botle Coffee\" looks like \"Blue Bottle Coffee\"".to_string()]
+        );
+        assert!(check(&form("Corner Shop", ""), &payees, &config).is_empty());
+    }
+
+    #[test]
+    fn memos_are_checked_against_each_enabled_rule() {
+        let config = LintConfig {
+            words: vec!["PIN".to_string()],
+            ..config()
+        };
+
+        let warnings = check(&form("", "acct 123456789, pin 4321"), &[], &config);
+        assert_eq!(warnings.len(), 2);
+
+        let disabled = LintConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(check(&form("", "acct 123456789"), &[], &disabled).is_empty());
+    }
+}
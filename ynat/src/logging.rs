@@ -7,17 +7,13 @@ use tracing_subscriber::{
 };
 
 use crate::log_buffer::{LogBuffer, LogEntry};
+use crate::utils::platform;
 
 /// Initialize tracing with file-based logging
-/// Logs are written to ~/.config/ynat/logs/ynat-YYYY-MM-DD-HH-MM-SS.log
+/// Logs are written to ynat-YYYY-MM-DD-HH-MM-SS.log in [`platform::logs_dir`]
 pub fn init_logging() -> Result<PathBuf> {
-    // Get config directory
-    let config_dir = dirs::config_dir()
-        .ok_or(anyhow::anyhow!("Could not find config directory"))?
-        .join("ynat");
-
     // Create logs directory
-    let logs_dir = config_dir.join("logs");
+    let logs_dir = platform::logs_dir().ok_or(anyhow::anyhow!("Could not find logs directory"))?;
     std::fs::create_dir_all(&logs_dir)?;
 
     // Create timestamped log file name
@@ -55,13 +51,8 @@ pub fn init_logging() -> Result<PathBuf> {
 
 /// Initialize tracing with file-based logging and an in-memory buffer for UI display
 pub fn init_logging_with_buffer(buffer: LogBuffer) -> Result<PathBuf> {
-    // Get config directory
-    let config_dir = dirs::config_dir()
-        .ok_or(anyhow::anyhow!("Could not find config directory"))?
-        .join("ynat");
-
     // Create logs directory
-    let logs_dir = config_dir.join("logs");
+    let logs_dir = platform::logs_dir().ok_or(anyhow::anyhow!("Could not find logs directory"))?;
     std::fs::create_dir_all(&logs_dir)?;
 
     // Create timestamped log file name
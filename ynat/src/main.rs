@@ -1,13 +1,20 @@
 use anyhow::Result;
 
-use ynat::App;
+use ynat::{cli, App};
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Subcommands authenticate themselves if they need to
+    let resume = match cli::parse(std::env::args().skip(1))? {
+        Some(cli::Command::Resume) => true,
+        Some(command) => return cli::run(command).await,
+        None => false,
+    };
+
     let token = ynat_auth::authenticate().await?;
 
     // Logging is initialized in App::run() with buffer support
-    App::new(token).run().await?;
+    App::verified(token).await?.resume(resume).run().await?;
 
     Ok(())
 }
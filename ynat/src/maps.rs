@@ -0,0 +1,116 @@
+//! Showing where a payee is, from the locations YNAB's mobile apps record
+//! when a transaction is entered.
+//!
+//! The transaction detail popup (`L`) shows the coordinates, and the town or
+//! city when `[maps] geocoder` is set. `[maps] command` opens them on a map
+//! and, like follow-ups, is run directly rather than through a shell.
+
+use serde::{Deserialize, Serialize};
+use ynab_api::endpoints::payees::PayeeLocation;
+
+/// A payee's location as shown in the detail popup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Place {
+    pub latitude: String,
+    pub longitude: String,
+    /// Town or city and country, if a geocoder is configured and knew them
+    pub city: Option<String>,
+}
+
+impl Place {
+    pub fn new(location: &PayeeLocation) -> Self {
+        Self {
+            latitude: location.latitude.trim().to_string(),
+            longitude: location.longitude.trim().to_string(),
+            city: None,
+        }
+    }
+}
+
+/// The most recently recorded location that hasn't been deleted. YNAB lists
+/// them oldest first.
+pub fn latest(locations: &[PayeeLocation]) -> Option<&PayeeLocation> {
+    locations.iter().rev().find(|location| {
+        !location.deleted
+            && location.latitude.trim().parse::<f64>().is_ok()
+            && location.longitude.trim().parse::<f64>().is_ok()
+    })
+}
+
+/// Fill `{lat}` and `{lon}` in each argument, or in a geocoder URL
+pub fn render_args(template: &[String], place: &Place) -> Vec<String> {
+    template
+        .iter()
+        .map(|arg| {
+            arg.replace("{lat}", &place.latitude)
+                .replace("{lon}", &place.longitude)
+        })
+        .collect()
+}
+
+/// "Town, Country" from a reverse geocoding response in Nominatim's format
+pub fn city(response: &serde_json::Value) -> Option<String> {
+    let address = response.get("address")?;
+    let field = |key: &str| address.get(key).and_then(|value| value.as_str());
+    let town = ["city", "town", "village", "municipality", "county"]
+        .into_iter()
+        .find_map(field)?;
+    Some(match field("country") {
+        Some(country) => format!("{}, {}", town, country),
+        None => town.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn location(latitude: &str, longitude: &str, deleted: bool) -> PayeeLocation {
+        PayeeLocation {
+            id: Uuid::new_v4(),
+            payee_id: Uuid::nil(),
+            latitude: latitude.to_string(),
+            longitude: longitude.to_string(),
+            deleted,
+        }
+    }
+
+    #[test]
+    fn the_newest_usable_location_is_opened() {
+        let locations = vec![
+            location("59.3293", "18.0686", false),
+            location("40.7128", "-74.0060", false),
+            location("not a number", "0", false),
+            location("51.5072", "-0.1276", true),
+        ];
+        let newest = latest(&locations).unwrap();
+        assert_eq!(newest.latitude, "40.7128");
+
+        let template = vec![
+            "xdg-open".to_string(),
+            "https://maps.example/?q={lat},{lon}".to_string(),
+        ];
+        assert_eq!(
+            render_args(&template, &Place::new(newest)),
+            vec!["xdg-open", "https://maps.example/?q=40.7128,-74.0060"]
+        );
+        assert!(latest(&[]).is_none());
+    }
+
+    #[test]
+    fn the_city_comes_from_the_geocoder_address() {
+        let response = json!({
+            "display_name": "Drottninggatan, Norrmalm, Stockholm, Sweden",
+            "address": { "road": "Drottninggatan", "city": "Stockholm", "country": "Sweden" }
+        });
+        assert_eq!(city(&response).as_deref(), Some("Stockholm, Sweden"));
+
+        let village = json!({ "address": { "village": "Gnesta" } });
+        assert_eq!(city(&village).as_deref(), Some("Gnesta"));
+
+        // Nominatim answers an error object for coordinates in the sea
+        assert_eq!(city(&json!({ "error": "Unable to geocode" })), None);
+    }
+}
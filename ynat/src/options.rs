@@ -0,0 +1,189 @@
+//! What the settings screen (`g` then `c`) can change, and where each
+//! change is kept.
+//!
+//! Every option takes effect straight away, so the rest of the app doubles
+//! as the preview. Most are written back to `config.toml`; confirming
+//! deletes is a per-budget setting and stays with the budget.
+
+use crate::budget_settings::BudgetSettings;
+use crate::config::{AppConfig, Rounding};
+use crate::config_edit::ConfigEdit;
+use crate::i18n::{t, tn};
+use crate::state::AppState;
+
+/// Date formats offered besides the budget's own
+pub const DATE_FORMATS: [&str; 4] = ["YYYY-MM-DD", "MM/DD/YYYY", "DD/MM/YYYY", "DD.MM.YYYY"];
+
+/// Auto-refresh intervals offered, in seconds; 0 is off
+const REFRESH_INTERVALS: [u64; 6] = [0, 60, 300, 900, 1800, 3600];
+
+const ROUNDINGS: [Rounding; 3] = [Rounding::None, Rounding::Unit, Rounding::FiveHundredths];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingOption {
+    DateFormat,
+    Rounding,
+    ApproveOnSave,
+    ApproveOnClear,
+    ConfirmDelete,
+    AutoRefresh,
+}
+
+/// Where a changed option has to be saved
+#[derive(Debug, Clone, PartialEq)]
+pub enum Saved {
+    Config(ConfigEdit),
+    /// In the current budget's settings
+    Budget,
+}
+
+impl SettingOption {
+    /// In screen order
+    pub const ALL: [Self; 6] = [
+        Self::DateFormat,
+        Self::Rounding,
+        Self::ApproveOnSave,
+        Self::ApproveOnClear,
+        Self::ConfirmDelete,
+        Self::AutoRefresh,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DateFormat => t("settings-date-format"),
+            Self::Rounding => t("settings-rounding"),
+            Self::ApproveOnSave => t("settings-approve-on-save"),
+            Self::ApproveOnClear => t("settings-approve-on-clear"),
+            Self::ConfirmDelete => t("settings-confirm-delete"),
+            Self::AutoRefresh => t("settings-auto-refresh"),
+        }
+    }
+
+    /// The current value, as shown on the screen
+    pub fn value(self, config: &AppConfig, budget_settings: &BudgetSettings) -> String {
+        match self {
+            Self::DateFormat => match &config.display.date_format {
+                Some(format) => format.clone(),
+                None => t("settings-date-format-budget").to_string(),
+            },
+            Self::Rounding => t(match config.amounts.rounding {
+                Rounding::None => "settings-rounding-none",
+                Rounding::Unit => "settings-rounding-unit",
+                Rounding::FiveHundredths => "settings-rounding-five-hundredths",
+            })
+            .to_string(),
+            Self::ApproveOnSave => on_off(config.approval.on_save),
+            Self::ApproveOnClear => on_off(config.approval.on_clear),
+            Self::ConfirmDelete => on_off(budget_settings.confirm_delete),
+            Self::AutoRefresh => match config.auto_refresh.interval_secs {
+                0 => t("settings-off").to_string(),
+                secs if secs % 60 == 0 => tn("settings-every-minutes", (secs / 60) as usize),
+                secs => tn("settings-every-seconds", secs as usize),
+            },
+        }
+    }
+
+    /// Switch to the next value, or the previous one, and say where to save it
+    pub fn change(self, state: &mut AppState, forward: bool) -> Saved {
+        let config = &mut state.config;
+        match self {
+            Self::DateFormat => {
+                let choices: Vec<_> = std::iter::once(None)
+                    .chain(DATE_FORMATS.map(|f| Some(f.to_string())))
+                    .collect();
+                config.display.date_format = cycle(&choices, &config.display.date_format, forward);
+                // Empty means the budget's own, like leaving it out
+                let format = config.display.date_format.as_deref().unwrap_or_default();
+                Saved::Config(ConfigEdit::string("display", "date_format", format))
+            }
+            Self::Rounding => {
+                config.amounts.rounding = cycle(&ROUNDINGS, &config.amounts.rounding, forward);
+                Saved::Config(ConfigEdit::string(
+                    "amounts",
+                    "rounding",
+                    config.amounts.rounding.as_str(),
+                ))
+            }
+            Self::ApproveOnSave => {
+                config.approval.on_save = !config.approval.on_save;
+                Saved::Config(ConfigEdit::new(
+                    "approval",
+                    "on_save",
+                    config.approval.on_save,
+                ))
+            }
+            Self::ApproveOnClear => {
+                config.approval.on_clear = !config.approval.on_clear;
+                Saved::Config(ConfigEdit::new(
+                    "approval",
+                    "on_clear",
+                    config.approval.on_clear,
+                ))
+            }
+            Self::ConfirmDelete => {
+                state.budget_settings.confirm_delete = !state.budget_settings.confirm_delete;
+                Saved::Budget
+            }
+            Self::AutoRefresh => {
+                let interval = &mut config.auto_refresh.interval_secs;
+                *interval = cycle(&REFRESH_INTERVALS, interval, forward);
+                Saved::Config(ConfigEdit::new("auto_refresh", "interval_secs", *interval))
+            }
+        }
+    }
+}
+
+fn on_off(on: bool) -> String {
+    t(if on { "settings-on" } else { "settings-off" }).to_string()
+}
+
+/// The choice after `current` (or before it), wrapping around. A value
+/// that isn't one of the choices, e.g. typed into `config.toml`, moves to
+/// the first.
+fn cycle<T: Clone + PartialEq>(choices: &[T], current: &T, forward: bool) -> T {
+    let next = match choices.iter().position(|c| c == current) {
+        Some(i) if forward => (i + 1) % choices.len(),
+        Some(i) => (i + choices.len() - 1) % choices.len(),
+        None => 0,
+    };
+    choices[next].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_cycle_and_say_where_they_are_saved() {
+        let mut state = AppState::new();
+
+        let saved = SettingOption::DateFormat.change(&mut state, true);
+        assert_eq!(
+            state.config.display.date_format.as_deref(),
+            Some("YYYY-MM-DD")
+        );
+        assert_eq!(
+            saved,
+            Saved::Config(ConfigEdit::string("display", "date_format", "YYYY-MM-DD"))
+        );
+        SettingOption::DateFormat.change(&mut state, false);
+        assert_eq!(state.config.display.date_format, None);
+
+        SettingOption::AutoRefresh.change(&mut state, true);
+        assert_eq!(state.config.auto_refresh.interval_secs, 900);
+        assert_eq!(
+            SettingOption::AutoRefresh.value(&state.config, &state.budget_settings),
+            "Every 15 minutes"
+        );
+
+        assert_eq!(
+            SettingOption::Rounding.change(&mut state, false),
+            Saved::Config(ConfigEdit::string("amounts", "rounding", "0.05"))
+        );
+        assert_eq!(
+            SettingOption::ConfirmDelete.change(&mut state, true),
+            Saved::Budget
+        );
+        assert!(!state.budget_settings.confirm_delete);
+    }
+}
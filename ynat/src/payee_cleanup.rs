@@ -0,0 +1,298 @@
+//! Near-duplicate payee detection ("AMZN Mktp US*1234" vs "Amazon").
+//!
+//! Names are reduced to a comparison key (lowercase, no store numbers or
+//! processor noise), then grouped when keys match, one abbreviates the
+//! other, or they are only a typo apart.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ynab_api::endpoints::payees::Payee;
+
+/// Payees YNAB creates itself; renaming them does nothing useful
+const SYSTEM_PAYEES: [&str; 3] = [
+    "Starting Balance",
+    "Manual Balance Adjustment",
+    "Reconciliation Balance Adjustment",
+];
+
+/// Card processor prefixes that come before the real merchant ("SQ *Blue Bottle")
+const PROCESSOR_PREFIXES: [&str; 5] = ["sq", "tst", "pp", "paypal", "sp"];
+
+/// Words that don't help tell merchants apart
+const NOISE_WORDS: [&str; 16] = [
+    "inc",
+    "llc",
+    "ltd",
+    "co",
+    "corp",
+    "com",
+    "www",
+    "us",
+    "usa",
+    "mktp",
+    "marketplace",
+    "pos",
+    "debit",
+    "purchase",
+    "ach",
+    "the",
+];
+
+/// Minimum edit-distance similarity for two keys to count as the same merchant
+const MIN_SIMILARITY: f64 = 0.85;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayeeRef {
+    pub payee_id: String,
+    pub name: String,
+}
+
+/// Payees that look like the same merchant, with the name to give them all
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayeeCluster {
+    pub canonical_name: String,
+    pub members: Vec<PayeeRef>,
+}
+
+impl PayeeCluster {
+    /// Members that need renaming to the canonical name
+    pub fn renames(&self) -> impl Iterator<Item = &PayeeRef> {
+        self.members
+            .iter()
+            .filter(|m| m.name != self.canonical_name)
+    }
+
+    /// Keep the member that already has the canonical name (or the first
+    /// one) and fold the others into it
+    pub fn merge(&self) -> PayeeMerge {
+        let target = self
+            .members
+            .iter()
+            .position(|m| m.name == self.canonical_name)
+            .unwrap_or_default();
+        let mut duplicates = self.members.clone();
+        PayeeMerge {
+            target: duplicates.remove(target),
+            name: self.canonical_name.clone(),
+            duplicates,
+        }
+    }
+}
+
+/// Payees whose transactions all move to one kept payee
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayeeMerge {
+    /// Payee the transactions move to, renamed to `name` if it differs
+    pub target: PayeeRef,
+    pub name: String,
+    /// Payees left without transactions, hidden once merged
+    pub duplicates: Vec<PayeeRef>,
+}
+
+/// Comparison key for a payee name
+pub fn normalize(name: &str) -> String {
+    let lower = name.to_lowercase();
+    let merchant = match lower.split_once('*') {
+        Some((prefix, rest)) if PROCESSOR_PREFIXES.contains(&prefix.trim()) => rest,
+        Some((merchant, _)) => merchant,
+        None => lower.as_str(),
+    };
+    let merchant = merchant.split('#').next().unwrap_or_default();
+
+    merchant
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty() && !NOISE_WORDS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// First letter plus the consonants after it ("amazon" -> "amzn")
+fn skeleton(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .into_iter()
+        .chain(chars.filter(|c| !"aeiou".contains(*c)))
+        .collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Whether two comparison keys look like the same merchant
+pub fn similar(a: &str, b: &str) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    if a == b {
+        return true;
+    }
+
+    // "amzn" abbreviates "amazon"
+    let first_a = a.split(' ').next().unwrap_or_default();
+    let first_b = b.split(' ').next().unwrap_or_default();
+    let (short, long) = if first_a.len() <= first_b.len() {
+        (first_a, first_b)
+    } else {
+        (first_b, first_a)
+    };
+    if short.len() >= 3 && short != long && short == skeleton(long) {
+        return true;
+    }
+
+    let longest = a.chars().count().max(b.chars().count());
+    longest >= 5 && 1.0 - levenshtein(a, b) as f64 / longest as f64 >= MIN_SIMILARITY
+}
+
+/// Union-find root, halving the path on the way
+fn root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// How clean a name looks; the lowest score becomes the canonical name
+fn messiness(name: &str) -> (usize, usize) {
+    let noise = name
+        .chars()
+        .filter(|c| c.is_ascii_digit() || "*#".contains(*c))
+        .count();
+    let shouting = usize::from(name.len() > 3 && !name.chars().any(|c| c.is_lowercase()));
+    (noise + shouting, name.len())
+}
+
+/// Group near-duplicate payees. Only groups that would actually rename
+/// something are returned, largest first.
+pub fn clusters(payees: &[Payee]) -> Vec<PayeeCluster> {
+    let candidates: Vec<(&Payee, String)> = payees
+        .iter()
+        .filter(|p| !p.deleted && p.transfer_account_id.is_none())
+        .filter(|p| !SYSTEM_PAYEES.contains(&p.name.as_str()))
+        .map(|p| (p, normalize(&p.name)))
+        .collect();
+
+    // Union-find over every similar pair
+    let mut parent: Vec<usize> = (0..candidates.len()).collect();
+    for i in 0..candidates.len() {
+        for j in (i + 1)..candidates.len() {
+            if similar(&candidates[i].1, &candidates[j].1) {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                parent[rj] = ri;
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<&Payee>> = Vec::new();
+    let mut group_of_root = HashMap::new();
+    for (i, (payee, _)) in candidates.iter().enumerate() {
+        let r = root(&mut parent, i);
+        let index = *group_of_root.entry(r).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[index].push(payee);
+    }
+
+    let mut clusters: Vec<PayeeCluster> = groups
+        .into_iter()
+        .filter(|group| group.len() > 1)
+        .filter_map(|group| {
+            let canonical = group.iter().min_by_key(|p| messiness(&p.name))?;
+            Some(PayeeCluster {
+                canonical_name: canonical.name.clone(),
+                members: group
+                    .iter()
+                    .map(|p| PayeeRef {
+                        payee_id: p.id.to_string(),
+                        name: p.name.clone(),
+                    })
+                    .collect(),
+            })
+        })
+        .filter(|cluster| cluster.renames().next().is_some())
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        b.members
+            .len()
+            .cmp(&a.members.len())
+            .then_with(|| a.canonical_name.cmp(&b.canonical_name))
+    });
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn payee(id: u128, name: &str) -> Payee {
+        Payee {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            transfer_account_id: None,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn normalize_strips_store_numbers_and_processors() {
+        assert_eq!(normalize("AMZN Mktp US*1234"), "amzn");
+        assert_eq!(normalize("SQ *Blue Bottle #42"), "blue bottle");
+        assert_eq!(normalize("Starbucks Store 00123"), "starbucks store");
+    }
+
+    #[test]
+    fn similar_catches_abbreviations_and_typos() {
+        assert!(similar("amzn", "amazon"));
+        assert!(similar("starbucks", "starbuks"));
+        assert!(!similar("target", "walmart"));
+        assert!(!similar("", ""));
+    }
+
+    #[test]
+    fn clusters_pick_cleanest_name() {
+        let payees = vec![
+            payee(1, "AMZN Mktp US*1234"),
+            payee(2, "Amazon"),
+            payee(3, "AMZN Mktp US*9876"),
+            payee(4, "Whole Foods"),
+            payee(5, "Starting Balance"),
+        ];
+
+        let found = clusters(&payees);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].canonical_name, "Amazon");
+        assert_eq!(found[0].members.len(), 3);
+        assert_eq!(found[0].renames().count(), 2);
+    }
+
+    #[test]
+    fn merge_keeps_the_payee_already_named_canonically() {
+        let found = clusters(&[
+            payee(1, "AMZN Mktp US*1234"),
+            payee(2, "Amazon"),
+            payee(3, "AMZN Mktp US*9876"),
+        ]);
+
+        let merge = found[0].merge();
+        assert_eq!(merge.target.payee_id, Uuid::from_u128(2).to_string());
+        assert_eq!(merge.name, "Amazon");
+        assert_eq!(merge.duplicates.len(), 2);
+        assert!(merge.duplicates.iter().all(|d| d.name.starts_with("AMZN")));
+    }
+}
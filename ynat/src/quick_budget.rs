@@ -0,0 +1,165 @@
+//! Quick-budget amounts: fund goals, repeat last month, or budget what's usually spent.
+
+use crate::i18n::t;
+use serde::{Deserialize, Serialize};
+use ynab_api::endpoints::categories::Category;
+
+/// Months averaged for [`QuickBudgetAction::AverageSpent`]
+pub const AVERAGE_MONTHS: u32 = 3;
+
+/// How a quick-budget command works out each category's new assigned amount
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum QuickBudgetAction {
+    /// Add what the goal still needs this month
+    Underfunded,
+    /// Assign what was assigned the month before
+    LastMonth,
+    /// Assign the average spent over the last [`AVERAGE_MONTHS`] months
+    AverageSpent,
+}
+
+impl QuickBudgetAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Underfunded => t("quick-budget-underfunded"),
+            Self::LastMonth => t("quick-budget-last-month"),
+            Self::AverageSpent => t("quick-budget-average-spent"),
+        }
+    }
+
+    /// Months before the plan month the amounts are worked out from
+    pub fn history_months(&self) -> u32 {
+        match self {
+            Self::Underfunded => 0,
+            Self::LastMonth => 1,
+            Self::AverageSpent => AVERAGE_MONTHS,
+        }
+    }
+}
+
+/// A category's assigned amount before and after the quick-budget command
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickBudgetChange {
+    pub category_id: String,
+    pub category_name: String,
+    pub from: i64,
+    pub to: i64,
+}
+
+impl QuickBudgetChange {
+    /// Money taken from Ready to Assign (negative when it goes back)
+    pub fn delta(&self) -> i64 {
+        self.to - self.from
+    }
+}
+
+/// New assigned amounts for `categories`, in the order given.
+///
+/// `history` holds the categories in each of the months before the plan month,
+/// oldest first; a category missing from one of them counts as nothing
+/// assigned or spent. Categories
+/// already at their new amount are left out, as are snoozed goals.
+pub fn changes(
+    action: QuickBudgetAction,
+    categories: &[&Category],
+    history: &[Vec<Category>],
+) -> Vec<QuickBudgetChange> {
+    let in_month = |month: &[Category], category: &Category| {
+        month
+            .iter()
+            .find(|c| c.id == category.id)
+            .map(|c| (i64::from(c.budgeted), i64::from(c.activity)))
+            .unwrap_or_default()
+    };
+
+    categories
+        .iter()
+        .filter_map(|category| {
+            let from = i64::from(category.budgeted);
+            let to = match action {
+                QuickBudgetAction::Underfunded => {
+                    if category.goal_snoozed_at.is_some() {
+                        return None;
+                    }
+                    from + category.goal_under_funded.map(i64::from)?.max(0)
+                }
+                QuickBudgetAction::LastMonth => in_month(history.last()?, category).0,
+                QuickBudgetAction::AverageSpent => {
+                    if history.is_empty() {
+                        return None;
+                    }
+                    let spent: i64 = history
+                        .iter()
+                        .map(|month| (-in_month(month, category).1).max(0))
+                        .sum();
+                    // Round to the nearest cent
+                    (spent / history.len() as i64 + 5) / 10 * 10
+                }
+            };
+            (to != from).then(|| QuickBudgetChange {
+                category_id: category.id.to_string(),
+                category_name: category.name.clone(),
+                from,
+                to,
+            })
+        })
+        .collect()
+}
+
+/// Total taken from Ready to Assign by all the changes
+pub fn total(changes: &[QuickBudgetChange]) -> i64 {
+    changes.iter().map(QuickBudgetChange::delta).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::category;
+
+    #[test]
+    fn underfunded_tops_up_goals_only() {
+        let groceries = category("Groceries")
+            .amounts(100_000, 0)
+            .need(250_000)
+            .build();
+        let funded = category("Phone").amounts(50_000, 0).need(0).build();
+        let no_goal = category("Dining").build();
+
+        let changes = changes(
+            QuickBudgetAction::Underfunded,
+            &[&groceries, &funded, &no_goal],
+            &[],
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to, 350_000);
+        assert_eq!(total(&changes), 250_000);
+    }
+
+    #[test]
+    fn last_month_and_average_use_history() {
+        let dining = category("Dining").build();
+        let rent = category("Rent").amounts(1_000_000, 0).build();
+        let history = vec![
+            vec![category("Dining").amounts(100_000, -90_000).build()],
+            vec![category("Dining").amounts(100_000, -120_000).build()],
+            vec![
+                category("Dining").amounts(150_000, -100_000).build(),
+                category("Rent").amounts(900_000, -900_000).build(),
+            ],
+        ];
+
+        let last = changes(QuickBudgetAction::LastMonth, &[&dining, &rent], &history);
+        assert_eq!(
+            last.iter().map(|c| c.to).collect::<Vec<_>>(),
+            [150_000, 900_000]
+        );
+        assert_eq!(total(&last), 50_000);
+
+        // Rent was missing from two of the three months
+        let average = changes(QuickBudgetAction::AverageSpent, &[&dining, &rent], &history);
+        assert_eq!(
+            average.iter().map(|c| c.to).collect::<Vec<_>>(),
+            [103_330, 300_000]
+        );
+    }
+}
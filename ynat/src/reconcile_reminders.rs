@@ -0,0 +1,145 @@
+//! Reminders to reconcile accounts on a schedule.
+//!
+//! Each account can be given a cadence, kept in the budget's local settings.
+//! An account is due a few days before its cadence runs out since it was last
+//! reconciled (as YNAB records it), and overdue after. Accounts without a
+//! cadence are never reminded about.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use ynab_api::endpoints::accounts::Account;
+
+/// Days before the cadence runs out that an account counts as due
+pub const DUE_SOON_DAYS: i64 = 3;
+
+/// How often an account should be reconciled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileCadence {
+    Weekly,
+    Fortnightly,
+    Monthly,
+    Quarterly,
+}
+
+impl ReconcileCadence {
+    pub fn days(self) -> i64 {
+        match self {
+            Self::Weekly => 7,
+            Self::Fortnightly => 14,
+            Self::Monthly => 30,
+            Self::Quarterly => 91,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::Fortnightly => "fortnightly",
+            Self::Monthly => "monthly",
+            Self::Quarterly => "quarterly",
+        }
+    }
+
+    /// The cadence after `current`, going from none through each to none again
+    pub fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::Weekly),
+            Some(Self::Weekly) => Some(Self::Fortnightly),
+            Some(Self::Fortnightly) => Some(Self::Monthly),
+            Some(Self::Monthly) => Some(Self::Quarterly),
+            Some(Self::Quarterly) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderStatus {
+    Due,
+    Overdue,
+}
+
+/// Whether `account` should be reconciled soon on `cadence`. An account that
+/// was never reconciled is overdue.
+pub fn status(
+    account: &Account,
+    cadence: ReconcileCadence,
+    today: NaiveDate,
+) -> Option<ReminderStatus> {
+    let Some(last) = account.last_reconciled_at else {
+        return Some(ReminderStatus::Overdue);
+    };
+    let days = (today - last.date_naive()).num_days();
+    if days > cadence.days() {
+        Some(ReminderStatus::Overdue)
+    } else if days >= cadence.days() - DUE_SOON_DAYS {
+        Some(ReminderStatus::Due)
+    } else {
+        None
+    }
+}
+
+/// Open accounts with a cadence that are due or overdue, in the order given
+pub fn due_accounts<'a>(
+    accounts: &'a [Account],
+    cadences: &BTreeMap<String, ReconcileCadence>,
+    today: NaiveDate,
+) -> Vec<(&'a Account, ReminderStatus)> {
+    accounts
+        .iter()
+        .filter(|a| !a.closed && !a.deleted)
+        .filter_map(|a| {
+            let cadence = *cadences.get(&a.id.to_string())?;
+            Some((a, status(a, cadence, today)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::account;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn accounts_are_due_shortly_before_the_cadence_runs_out() {
+        let checking = account("Checking")
+            .reconciled_at("2025-03-01T09:30:00Z")
+            .build();
+        let weekly = ReconcileCadence::Weekly;
+        assert_eq!(status(&checking, weekly, date("2025-03-04")), None);
+        assert_eq!(
+            status(&checking, weekly, date("2025-03-05")),
+            Some(ReminderStatus::Due)
+        );
+        assert_eq!(
+            status(&checking, weekly, date("2025-03-08")),
+            Some(ReminderStatus::Due)
+        );
+        assert_eq!(
+            status(&checking, weekly, date("2025-03-09")),
+            Some(ReminderStatus::Overdue)
+        );
+        assert_eq!(
+            status(
+                &account("Savings").build(),
+                ReconcileCadence::Quarterly,
+                date("2025-03-09")
+            ),
+            Some(ReminderStatus::Overdue)
+        );
+    }
+
+    #[test]
+    fn only_accounts_with_a_cadence_are_reminded_about() {
+        let accounts = vec![account("Checking").build(), account("Savings").build()];
+        let cadences = BTreeMap::from([(accounts[1].id.to_string(), ReconcileCadence::Monthly)]);
+        let due = due_accounts(&accounts, &cadences, date("2025-03-09"));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0.id, accounts[1].id);
+    }
+}
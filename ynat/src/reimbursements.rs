@@ -0,0 +1,194 @@
+//! Transactions awaiting reimbursement.
+//!
+//! Tracked entries are the user's own records rather than anything YNAB can
+//! send again, so they're stored as `<state dir>/ynat/reimbursements/<budget
+//! id>.json` like the budget settings, not in the cache.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use ynab_api::endpoints::transactions::Transaction;
+
+/// How many days after the expense a deposit may arrive and still be suggested as its reimbursement
+pub const MATCH_WINDOW_DAYS: i64 = 90;
+
+/// A transaction flagged as awaiting reimbursement, kept in a local sidecar file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReimbursementEntry {
+    pub transaction_id: String,
+    pub account_id: String,
+    pub date: NaiveDate,
+    pub payee_name: Option<String>,
+    pub memo: Option<String>,
+    /// The original (negative) amount in milliunits
+    pub amount: i64,
+    /// The deposit that paid this back, or "manual" if marked without one
+    pub reimbursed_by: Option<String>,
+}
+
+impl ReimbursementEntry {
+    pub fn from_transaction(transaction: &Transaction) -> Self {
+        Self {
+            transaction_id: transaction.id.to_string(),
+            account_id: transaction.account_id.to_string(),
+            date: transaction.date,
+            payee_name: transaction.payee_name.clone(),
+            memo: transaction.memo.clone(),
+            amount: transaction.amount.into(),
+            reimbursed_by: None,
+        }
+    }
+
+    pub fn is_outstanding(&self) -> bool {
+        self.reimbursed_by.is_none()
+    }
+}
+
+/// A deposit that looks like the reimbursement for an outstanding entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReimbursementMatch {
+    pub transaction_id: String,
+    pub deposit_id: String,
+    pub deposit_date: NaiveDate,
+    pub deposit_payee: Option<String>,
+}
+
+fn entries_path(budget_id: &str) -> Option<PathBuf> {
+    let dir = dirs::state_dir().or_else(dirs::data_local_dir)?;
+    Some(
+        dir.join("ynat")
+            .join("reimbursements")
+            .join(format!("{}.json", budget_id)),
+    )
+}
+
+/// Entries tracked for `budget_id`, or `None` if none were ever saved.
+/// A file that can't be read or parsed is an error, not an empty list, so
+/// it's never overwritten by mistake.
+pub async fn load(budget_id: &str) -> std::io::Result<Option<Vec<ReimbursementEntry>>> {
+    let Some(path) = entries_path(budget_id) else {
+        return Ok(None);
+    };
+    match tokio::fs::read_to_string(&path).await {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(std::io::Error::other),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn save(budget_id: &str, entries: &[ReimbursementEntry]) -> std::io::Result<()> {
+    let Some(path) = entries_path(budget_id) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let json = serde_json::to_string_pretty(entries).map_err(std::io::Error::other)?;
+    // Replace the file in one step so an interrupted save leaves the old one intact
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, json).await?;
+    tokio::fs::rename(tmp_path, path).await
+}
+
+/// Total still owed across outstanding entries, as a positive amount in milliunits
+pub fn expected_total(entries: &[ReimbursementEntry]) -> i64 {
+    entries
+        .iter()
+        .filter(|e| e.is_outstanding())
+        .map(|e| -e.amount)
+        .sum()
+}
+
+/// Suggest a deposit for each outstanding entry.
+///
+/// A deposit matches when it is for exactly the amount owed and dated on or
+/// within [`MATCH_WINDOW_DAYS`] after the expense. Each deposit is used at most
+/// once, and the oldest expenses are matched first.
+pub fn suggest_matches(
+    entries: &[ReimbursementEntry],
+    transactions: &[Transaction],
+) -> Vec<ReimbursementMatch> {
+    let used: Vec<&str> = entries
+        .iter()
+        .filter_map(|e| e.reimbursed_by.as_deref())
+        .collect();
+    let mut deposits: Vec<&Transaction> = transactions
+        .iter()
+        .filter(|t| !t.deleted && t.amount.is_positive())
+        .filter(|t| !used.contains(&t.id.to_string().as_str()))
+        .collect();
+    deposits.sort_by_key(|t| t.date);
+
+    let mut outstanding: Vec<&ReimbursementEntry> =
+        entries.iter().filter(|e| e.is_outstanding()).collect();
+    outstanding.sort_by_key(|e| e.date);
+
+    let mut matches = Vec::new();
+    for entry in outstanding {
+        let position = deposits.iter().position(|d| {
+            i64::from(d.amount) == -entry.amount
+                && d.date >= entry.date
+                && (d.date - entry.date).num_days() <= MATCH_WINDOW_DAYS
+        });
+        if let Some(position) = position {
+            let deposit = deposits.remove(position);
+            matches.push(ReimbursementMatch {
+                transaction_id: entry.transaction_id.clone(),
+                deposit_id: deposit.id.to_string(),
+                deposit_date: deposit.date,
+                deposit_payee: deposit.payee_name.clone(),
+            });
+        }
+    }
+
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    #[test]
+    fn expected_total_counts_only_outstanding() {
+        let mut paid =
+            ReimbursementEntry::from_transaction(&transaction("2025-01-01", -5000).build());
+        paid.reimbursed_by = Some("manual".to_string());
+        let open = ReimbursementEntry::from_transaction(&transaction("2025-01-02", -2500).build());
+
+        assert_eq!(expected_total(&[paid, open]), 2500);
+    }
+
+    #[test]
+    fn suggests_exact_amount_deposits_after_the_expense() {
+        let entries = vec![
+            ReimbursementEntry::from_transaction(&transaction("2025-01-10", -4200).build()),
+            ReimbursementEntry::from_transaction(&transaction("2025-01-11", -999).build()),
+        ];
+        let transactions = vec![
+            transaction("2025-01-05", 4200).build(), // before the expense
+            transaction("2025-01-20", 4200).build(),
+            transaction("2025-01-21", 1000).build(), // wrong amount
+        ];
+
+        let matches = suggest_matches(&entries, &transactions);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].transaction_id, entries[0].transaction_id);
+        assert_eq!(matches[0].deposit_id, transactions[1].id.to_string());
+    }
+
+    #[test]
+    fn each_deposit_matches_once() {
+        let entries = vec![
+            ReimbursementEntry::from_transaction(&transaction("2025-01-10", -1000).build()),
+            ReimbursementEntry::from_transaction(&transaction("2025-01-12", -1000).build()),
+        ];
+        let transactions = vec![transaction("2025-01-15", 1000).build()];
+
+        let matches = suggest_matches(&entries, &transactions);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].transaction_id, entries[0].transaction_id);
+    }
+}
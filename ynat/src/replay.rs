@@ -0,0 +1,249 @@
+//! Scripted key presses replayed through the event loop, for end-to-end
+//! smoke tests of whole workflows.
+//!
+//! Replay uses the same executor, data loader and renderer as the TUI, but
+//! reads keys from a script, draws into an off-screen buffer and starts from
+//! an empty cache, so runs against a mock API server are reproducible. After
+//! each key it waits for the background loads that key started to finish.
+//!
+//! A script has one key per line:
+//!
+//! ```text
+//! # comments and blank lines are skipped
+//! j             a character
+//! Enter         a named key (Esc, Tab, BackTab, Backspace, Space, Up, Down,
+//!               Left, Right, PageUp, PageDown)
+//! Ctrl+r        with modifiers (Ctrl, Alt, Shift)
+//! type Rent     every character after "type "
+//! ```
+
+use anyhow::{anyhow, bail, Context, Result};
+use ratatui::{backend::TestBackend, Terminal};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use ynab_api::Client;
+
+use crate::api_log::ApiLog;
+use crate::app::load_initial_data;
+use crate::background::{data_loader::DataLoader, BackgroundTaskManager};
+use crate::cache::Cache;
+use crate::commands::{executor, handlers};
+use crate::events::DataEvent;
+use crate::input::{Key, KeyEvent, Modifiers};
+use crate::log_buffer::LogBuffer;
+use crate::state::AppState;
+use crate::ui::screens::Screen;
+
+/// Longest wait for the loads started by one key
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(30);
+const SETTLE_POLL: Duration = Duration::from_millis(20);
+
+/// Size of the off-screen terminal the screens are drawn into
+pub(crate) const TERMINAL_SIZE: (u16, u16) = (120, 40);
+
+/// Parse a key script into the key events it presses, in order
+pub fn parse_script(script: &str) -> Result<Vec<KeyEvent>> {
+    let mut keys = Vec::new();
+    for (index, line) in script.lines().enumerate() {
+        if let Some(text) = line.strip_prefix("type ") {
+            keys.extend(text.chars().map(|c| KeyEvent::new(Key::Char(c))));
+            continue;
+        }
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let key =
+            parse_key(line).ok_or_else(|| anyhow!("Line {}: unknown key {:?}", index + 1, line))?;
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+fn parse_key(spec: &str) -> Option<KeyEvent> {
+    let mut modifiers = Modifiers::default();
+    let mut name = spec;
+    // A lone "+" is the key itself, not a separator
+    while let Some((modifier, rest)) = name.split_once('+').filter(|(_, rest)| !rest.is_empty()) {
+        match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" => modifiers.ctrl = true,
+            "alt" => modifiers.alt = true,
+            "shift" => modifiers.shift = true,
+            _ => return None,
+        }
+        name = rest;
+    }
+
+    let mut chars = name.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(c), None) => Key::Char(c),
+        _ => match name.to_ascii_lowercase().as_str() {
+            "enter" => Key::Enter,
+            "esc" => Key::Esc,
+            "tab" => Key::Tab,
+            "backtab" => Key::BackTab,
+            "backspace" => Key::Backspace,
+            "space" => Key::Char(' '),
+            "up" => Key::Up,
+            "down" => Key::Down,
+            "left" => Key::Left,
+            "right" => Key::Right,
+            "pageup" => Key::PageUp,
+            "pagedown" => Key::PageDown,
+            _ => return None,
+        },
+    };
+    Some(KeyEvent { key, modifiers })
+}
+
+/// The name `--assert-screen` matches against
+pub fn screen_name(screen: &Screen) -> &'static str {
+    match screen {
+        Screen::Budgets(_) => "budgets",
+        Screen::Accounts(_) => "accounts",
+        Screen::Transactions(_) => "transactions",
+        Screen::Plan(_) => "plan",
+        Screen::Reimbursements(_) => "reimbursements",
+        Screen::NetWorth(_) => "net-worth",
+        Screen::ScheduledTransactions(_) => "scheduled",
+        Screen::PayeeCleanup(_) => "payee-cleanup",
+        Screen::Reports(_) => "reports",
+        Screen::Settings(_) => "settings",
+        Screen::Logs(_) => "logs",
+        Screen::Inspector(_) => "inspector",
+    }
+}
+
+/// Press `keys` against the API `client` talks to, and return the final state.
+///
+/// Stops early if a key quits the app. Every screen is drawn after each key,
+/// so a screen that fails to render fails the replay.
+pub async fn run(keys: &[KeyEvent], client: Client) -> Result<AppState> {
+    let cache_dir = scratch_cache_dir();
+    let result = replay(keys, client, cache_dir.clone()).await;
+    if let Err(e) = std::fs::remove_dir_all(&cache_dir) {
+        tracing::warn!("Failed to remove replay cache {:?}: {}", cache_dir, e);
+    }
+    result
+}
+
+async fn replay(keys: &[KeyEvent], client: Client, cache_dir: PathBuf) -> Result<AppState> {
+    let cache = Arc::new(Cache::in_dir(cache_dir).await?);
+    let (data_tx, mut data_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    // Default settings rather than the user's config, for reproducible runs
+    let mut ui_state = AppState::new();
+    crate::i18n::init(None);
+    crate::ui::components::loading_indicator::set_animated(false);
+    let mut task_manager = BackgroundTaskManager::new();
+    let data_loader = DataLoader::new(Arc::new(client), cache, data_tx);
+
+    let (width, height) = TERMINAL_SIZE;
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let log_buffer = LogBuffer::new(0);
+    let api_log = ApiLog::new(0);
+
+    load_initial_data(&mut ui_state, &mut task_manager, &data_loader);
+    settle(&mut ui_state, &task_manager, &mut data_rx).await?;
+
+    for (index, key) in keys.iter().enumerate() {
+        terminal.draw(|f| crate::ui::render_app(f, &ui_state, &log_buffer, &api_log))?;
+        if let Some(command) = handlers::handle_key_input(*key, &ui_state) {
+            tracing::info!("Replay key {}: {:?}", index + 1, command);
+            executor::execute_command(command, &mut ui_state, &mut task_manager, &data_loader);
+        }
+        settle(&mut ui_state, &task_manager, &mut data_rx)
+            .await
+            .with_context(|| format!("After key {} ({:?})", index + 1, key.key))?;
+        if ui_state.should_quit {
+            break;
+        }
+    }
+    terminal.draw(|f| crate::ui::render_app(f, &ui_state, &log_buffer, &api_log))?;
+
+    task_manager.cancel_all();
+    Ok(ui_state)
+}
+
+/// Apply data events until every background load has finished
+async fn settle(
+    ui_state: &mut AppState,
+    task_manager: &BackgroundTaskManager,
+    data_rx: &mut tokio::sync::mpsc::UnboundedReceiver<DataEvent>,
+) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + SETTLE_TIMEOUT;
+    loop {
+        while let Ok(event) = data_rx.try_recv() {
+            crate::app_core::reduce_data_event(ui_state, event);
+        }
+        if task_manager.is_idle() && data_rx.is_empty() {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            bail!("Background loads still running after {:?}", SETTLE_TIMEOUT);
+        }
+        // A task can finish without sending anything, so poll rather than wait
+        if let Ok(Some(event)) = tokio::time::timeout(SETTLE_POLL, data_rx.recv()).await {
+            crate::app_core::reduce_data_event(ui_state, event);
+        }
+    }
+}
+
+pub(crate) fn scratch_cache_dir() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("ynat-replay-{}-{}", std::process::id(), nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_name_keys_modifiers_and_typed_text() {
+        let keys = parse_script("# open the plan\n\np\nCtrl+r\nshift+Tab\n+\n\ntype a b\nEnter\n")
+            .unwrap();
+
+        assert_eq!(
+            keys,
+            vec![
+                KeyEvent::new(Key::Char('p')),
+                KeyEvent::with_ctrl(Key::Char('r')),
+                KeyEvent {
+                    key: Key::Tab,
+                    modifiers: Modifiers {
+                        shift: true,
+                        ..Default::default()
+                    },
+                },
+                KeyEvent::new(Key::Char('+')),
+                KeyEvent::new(Key::Char('a')),
+                KeyEvent::new(Key::Char(' ')),
+                KeyEvent::new(Key::Char('b')),
+                KeyEvent::new(Key::Enter),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_report_their_line() {
+        let error = parse_script("j\nHyper+j\n").unwrap_err();
+
+        assert!(error.to_string().starts_with("Line 2:"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn replay_drives_the_event_loop_until_loads_settle() {
+        // Nothing listens here, so every load fails fast
+        let client = Client::with_base_url("http://127.0.0.1:9/v1", "replay");
+        let keys = parse_script("?\n?\ng\nb\n").unwrap();
+
+        let state = run(&keys, client).await.unwrap();
+
+        assert!(!state.help_visible);
+        assert_eq!(screen_name(state.current_screen()), "budgets");
+    }
+}
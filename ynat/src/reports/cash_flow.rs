@@ -0,0 +1,311 @@
+//! Forward projection of an account balance from scheduled transactions
+//! plus the recent spending rate of unscheduled categories.
+
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use ynab_api::endpoints::{
+    scheduled_transactions::{Frequency, ScheduledTransaction},
+    transactions::Transaction,
+};
+
+use crate::i18n::t;
+
+/// Days of history used to estimate the spending rate
+pub const LOOKBACK_DAYS: i64 = 90;
+
+/// How far ahead to project
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProjectionHorizon {
+    #[default]
+    Days30,
+    Days60,
+    Days90,
+}
+
+impl ProjectionHorizon {
+    pub fn days(&self) -> u64 {
+        match self {
+            ProjectionHorizon::Days30 => 30,
+            ProjectionHorizon::Days60 => 60,
+            ProjectionHorizon::Days90 => 90,
+        }
+    }
+
+    pub fn cycle(&self, forward: bool) -> Self {
+        match (self, forward) {
+            (ProjectionHorizon::Days30, true) | (ProjectionHorizon::Days90, false) => {
+                ProjectionHorizon::Days60
+            }
+            (ProjectionHorizon::Days60, true) | (ProjectionHorizon::Days30, false) => {
+                ProjectionHorizon::Days90
+            }
+            (ProjectionHorizon::Days90, true) | (ProjectionHorizon::Days60, false) => {
+                ProjectionHorizon::Days30
+            }
+        }
+    }
+
+    pub fn label(&self) -> String {
+        t("report-horizon-days").replace("{days}", &self.days().to_string())
+    }
+}
+
+/// Projected end-of-day balance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectedDay {
+    pub date: NaiveDate,
+    pub balance: i64,
+    /// Net scheduled transactions landing on this day
+    pub scheduled: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CashFlowProjection {
+    pub account_id: String,
+    pub account_name: String,
+    pub horizon: ProjectionHorizon,
+    pub start_balance: i64,
+    /// Average net daily activity of unscheduled categories, in milliunits
+    pub daily_run_rate: i64,
+    pub days: Vec<ProjectedDay>,
+}
+
+impl CashFlowProjection {
+    /// Days on which the balance is projected to be below zero
+    pub fn negative_days(&self) -> Vec<&ProjectedDay> {
+        self.days.iter().filter(|d| d.balance < 0).collect()
+    }
+
+    pub fn lowest(&self) -> Option<&ProjectedDay> {
+        self.days.iter().min_by_key(|d| d.balance)
+    }
+}
+
+/// Dates from `next` onward (up to and including `until`) a schedule occurs on.
+/// Twice-a-month schedules are approximated as the scheduled day and 15 days later.
+pub fn occurrences(frequency: Frequency, next: NaiveDate, until: NaiveDate) -> Vec<NaiveDate> {
+    let by_days = |step: u64| -> Vec<NaiveDate> {
+        (0..)
+            .map_while(|i| next.checked_add_days(Days::new(step * i)))
+            .take_while(|d| *d <= until)
+            .collect()
+    };
+    // Step from the original date each time so a 31st doesn't drift to the 28th
+    let by_months = |step: u32| -> Vec<NaiveDate> {
+        (0..)
+            .map_while(|i| next.checked_add_months(Months::new(step * i)))
+            .take_while(|d| *d <= until)
+            .collect()
+    };
+
+    match frequency {
+        Frequency::Never => [next].into_iter().filter(|d| *d <= until).collect(),
+        Frequency::Daily => by_days(1),
+        Frequency::Weekly => by_days(7),
+        Frequency::EveryOtherWeek => by_days(14),
+        Frequency::Every4Weeks => by_days(28),
+        Frequency::TwiceAMonth => {
+            let mut dates: Vec<NaiveDate> = by_months(1)
+                .into_iter()
+                .flat_map(|d| [Some(d), d.checked_add_days(Days::new(15))])
+                .flatten()
+                .filter(|d| *d <= until)
+                .collect();
+            dates.sort();
+            dates
+        }
+        Frequency::Monthly => by_months(1),
+        Frequency::EveryOtherMonth => by_months(2),
+        Frequency::Every3Months => by_months(3),
+        Frequency::Every4Months => by_months(4),
+        Frequency::TwiceAYear => by_months(6),
+        Frequency::Yearly => by_months(12),
+        Frequency::EveryOtherYear => by_months(24),
+    }
+}
+
+/// Amount a scheduled transaction moves into `account_id`, if it touches it at all.
+/// Transfers scheduled from another account count with the opposite sign.
+pub fn scheduled_amount_for(scheduled: &ScheduledTransaction, account_id: Uuid) -> Option<i64> {
+    if scheduled.deleted {
+        return None;
+    }
+    if scheduled.account_id == account_id {
+        return Some(scheduled.amount.into());
+    }
+    if scheduled.transfer_account_id == Some(account_id) {
+        return Some(-i64::from(scheduled.amount));
+    }
+
+    let transfers: Vec<i64> = scheduled
+        .subtransactions
+        .iter()
+        .filter(|s| !s.deleted && s.transfer_account_id == Some(account_id))
+        .map(|s| -i64::from(s.amount))
+        .collect();
+    (!transfers.is_empty()).then(|| transfers.into_iter().sum())
+}
+
+/// Net activity over the lookback window for categorized, non-transfer
+/// transactions whose category isn't already covered by a schedule
+fn lookback_activity(
+    transactions: &[Transaction],
+    scheduled_categories: &HashSet<Uuid>,
+    today: NaiveDate,
+) -> i64 {
+    let start = today - Days::new(LOOKBACK_DAYS as u64);
+    let counts = |category_id: Option<Uuid>| {
+        category_id.is_some_and(|id| !scheduled_categories.contains(&id))
+    };
+
+    transactions
+        .iter()
+        .filter(|t| !t.deleted && t.date > start && t.date <= today)
+        .filter(|t| t.transfer_account_id.is_none())
+        .map(|t| {
+            if t.subtransactions.is_empty() {
+                if counts(t.category_id) {
+                    i64::from(t.amount)
+                } else {
+                    0
+                }
+            } else {
+                t.subtransactions
+                    .iter()
+                    .filter(|s| !s.deleted && s.transfer_account_id.is_none())
+                    .filter(|s| counts(s.category_id))
+                    .map(|s| i64::from(s.amount))
+                    .sum()
+            }
+        })
+        .sum()
+}
+
+/// Project the balance of `account_id` for each day after `today`
+pub fn project(
+    account_id: Uuid,
+    account_name: &str,
+    start_balance: i64,
+    today: NaiveDate,
+    horizon: ProjectionHorizon,
+    scheduled: &[ScheduledTransaction],
+    transactions: &[Transaction],
+) -> CashFlowProjection {
+    let until = today + Days::new(horizon.days());
+
+    // Scheduled amounts per day, and which categories they already account for
+    let mut events: HashMap<NaiveDate, i64> = HashMap::new();
+    let mut scheduled_categories = HashSet::new();
+    for st in scheduled {
+        let Some(amount) = scheduled_amount_for(st, account_id) else {
+            continue;
+        };
+        scheduled_categories.extend(st.category_id);
+        scheduled_categories.extend(st.subtransactions.iter().filter_map(|s| s.category_id));
+        for date in occurrences(st.frequency, st.date_next, until) {
+            if date > today {
+                *events.entry(date).or_default() += amount;
+            }
+        }
+    }
+
+    let activity = lookback_activity(transactions, &scheduled_categories, today);
+
+    let mut scheduled_total = 0;
+    let days = (1..=horizon.days())
+        .filter_map(|offset| today.checked_add_days(Days::new(offset)))
+        .enumerate()
+        .map(|(i, date)| {
+            let scheduled = events.get(&date).copied().unwrap_or_default();
+            scheduled_total += scheduled;
+            // Scale the whole window's activity so rounding doesn't accumulate
+            let run_rate_total = activity * (i as i64 + 1) / LOOKBACK_DAYS;
+            ProjectedDay {
+                date,
+                balance: start_balance + scheduled_total + run_rate_total,
+                scheduled,
+            }
+        })
+        .collect();
+
+    CashFlowProjection {
+        account_id: account_id.to_string(),
+        account_name: account_name.to_string(),
+        horizon,
+        start_balance,
+        daily_run_rate: activity / LOOKBACK_DAYS,
+        days,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::scheduled;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn monthly_occurrences_keep_day_of_month() {
+        assert_eq!(
+            occurrences(Frequency::Monthly, date(2025, 1, 31), date(2025, 3, 31)),
+            vec![date(2025, 1, 31), date(2025, 2, 28), date(2025, 3, 31)]
+        );
+        assert_eq!(
+            occurrences(
+                Frequency::EveryOtherWeek,
+                date(2025, 1, 1),
+                date(2025, 1, 29)
+            ),
+            vec![date(2025, 1, 1), date(2025, 1, 15), date(2025, 1, 29)]
+        );
+        assert_eq!(
+            occurrences(Frequency::Never, date(2025, 1, 1), date(2025, 6, 1)),
+            vec![date(2025, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn transfers_count_with_opposite_sign() {
+        let transfer = scheduled("2025-01-10", -500_000)
+            .account(Uuid::from_u128(2), "Savings")
+            .transfer_to(Uuid::from_u128(1))
+            .build();
+        assert_eq!(
+            scheduled_amount_for(&transfer, Uuid::from_u128(1)),
+            Some(500_000)
+        );
+        assert_eq!(
+            scheduled_amount_for(&transfer, Uuid::from_u128(2)),
+            Some(-500_000)
+        );
+        assert_eq!(scheduled_amount_for(&transfer, Uuid::from_u128(3)), None);
+    }
+
+    #[test]
+    fn projection_flags_days_below_zero() {
+        let rent = scheduled("2025-01-15", -1_500_000)
+            .account(Uuid::from_u128(1), "Checking")
+            .payee("Rent")
+            .build();
+        let projection = project(
+            Uuid::from_u128(1),
+            "Checking",
+            1_000_000,
+            date(2025, 1, 1),
+            ProjectionHorizon::Days30,
+            &[rent],
+            &[],
+        );
+
+        assert_eq!(projection.days.len(), 30);
+        assert_eq!(projection.days[12].balance, 1_000_000);
+        let negative = projection.negative_days();
+        assert_eq!(negative.first().map(|d| d.date), Some(date(2025, 1, 15)));
+        assert_eq!(projection.lowest().map(|d| d.balance), Some(-500_000));
+    }
+}
@@ -0,0 +1,102 @@
+//! Spending per day of a month, for the heatmap calendar.
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use ynab_api::endpoints::transactions::Transaction;
+
+use super::first_of_month;
+
+/// Shades a day can take, from nothing spent to the month's busiest day
+pub const LEVELS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpendingHeatmap {
+    /// First day of the month shown
+    pub month: NaiveDate,
+    /// Positive amount spent on each day, starting with the 1st
+    pub days: Vec<i64>,
+}
+
+impl SpendingHeatmap {
+    /// Date of the day at `index`
+    pub fn date(&self, index: usize) -> Option<NaiveDate> {
+        (index < self.days.len()).then(|| self.month + Days::new(index as u64))
+    }
+
+    pub fn total(&self) -> i64 {
+        self.days.iter().sum()
+    }
+
+    /// Shade for a day's spending, relative to the month's busiest day.
+    /// 0 means nothing spent; any spending gets at least 1.
+    pub fn level(&self, index: usize) -> usize {
+        let spent = self.days.get(index).copied().unwrap_or_default();
+        let max = self.days.iter().copied().max().unwrap_or_default();
+        if spent <= 0 || max <= 0 {
+            return 0;
+        }
+        let top = (LEVELS - 1) as i64;
+        ((spent * top + max - 1) / max).clamp(1, top) as usize
+    }
+}
+
+/// Total outflows per day in the month containing `month`
+///
+/// Transfers are left out since they don't leave the budget. Inflows don't
+/// offset spending on the same day.
+pub fn build(transactions: &[Transaction], month: NaiveDate) -> SpendingHeatmap {
+    let month = first_of_month(month);
+    let len = month
+        .checked_add_months(Months::new(1))
+        .map_or(31, |next| (next - month).num_days() as usize);
+
+    let mut days = vec![0; len];
+    for t in transactions
+        .iter()
+        .filter(|t| !t.deleted && t.transfer_account_id.is_none())
+        .filter(|t| first_of_month(t.date) == month)
+    {
+        let spent: i64 = if t.subtransactions.is_empty() {
+            (-i64::from(t.amount)).max(0)
+        } else {
+            t.subtransactions
+                .iter()
+                .filter(|s| !s.deleted && s.transfer_account_id.is_none())
+                .map(|s| (-i64::from(s.amount)).max(0))
+                .sum()
+        };
+        days[t.date.day0() as usize] += spent;
+    }
+
+    SpendingHeatmap { month, days }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    #[test]
+    fn days_sum_outflows_and_skip_transfers() {
+        let transactions = vec![
+            transaction("2025-02-03", -12_000).build(),
+            transaction("2025-02-03", -8_000).build(),
+            transaction("2025-02-03", 50_000).build(),
+            transaction("2025-02-10", -40_000).build(),
+            transaction("2025-02-10", -500_000).transfer().build(),
+            transaction("2025-03-01", -9_000).build(),
+        ];
+
+        let heatmap = build(&transactions, NaiveDate::from_ymd_opt(2025, 2, 14).unwrap());
+        assert_eq!(heatmap.days.len(), 28);
+        assert_eq!(heatmap.days[2], 20_000);
+        assert_eq!(heatmap.days[9], 40_000);
+        assert_eq!(heatmap.total(), 60_000);
+        assert_eq!(heatmap.date(9), NaiveDate::from_ymd_opt(2025, 2, 10));
+        assert_eq!(heatmap.date(28), None);
+
+        assert_eq!(heatmap.level(0), 0);
+        assert_eq!(heatmap.level(2), 2);
+        assert_eq!(heatmap.level(9), LEVELS - 1);
+    }
+}
@@ -0,0 +1,193 @@
+//! Money in and out of the budget per month, for the income vs expense report.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use chrono::{Months, NaiveDate};
+use ynab_api::endpoints::transactions::Transaction;
+
+use super::first_of_month;
+use crate::i18n::t;
+
+/// Months the report covers, the current one included
+pub const MONTHS: u32 = 12;
+
+/// One category's net amount in a month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryFlow {
+    pub name: String,
+    /// Inflows less outflows
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthFlow {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub inflow: i64,
+    /// Outflows, as a negative amount
+    pub outflow: i64,
+    /// Largest amounts first, in or out
+    pub categories: Vec<CategoryFlow>,
+}
+
+impl MonthFlow {
+    pub fn net(&self) -> i64 {
+        self.inflow + self.outflow
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomeExpense {
+    /// Newest month first
+    pub months: Vec<MonthFlow>,
+}
+
+impl IncomeExpense {
+    /// Net across every month shown
+    pub fn total_net(&self) -> i64 {
+        self.months.iter().map(MonthFlow::net).sum()
+    }
+}
+
+/// First day of the oldest month the report covers
+pub fn first_month(today: NaiveDate) -> NaiveDate {
+    let current = first_of_month(today);
+    current
+        .checked_sub_months(Months::new(MONTHS - 1))
+        .unwrap_or(current)
+}
+
+/// Inflows and outflows for each of the last [`MONTHS`] months up to `today`
+///
+/// Transfers between accounts are left out since the money stays in the
+/// budget. Split parts count under their own categories.
+pub fn build(transactions: &[Transaction], today: NaiveDate) -> IncomeExpense {
+    let current = first_of_month(today);
+    let mut months: Vec<(NaiveDate, HashMap<String, i64>, i64, i64)> = (0..MONTHS)
+        .filter_map(|back| current.checked_sub_months(Months::new(back)))
+        .map(|month| (month, HashMap::new(), 0, 0))
+        .collect();
+
+    for transaction in transactions
+        .iter()
+        .filter(|t| !t.deleted && t.transfer_account_id.is_none())
+    {
+        let Some((_, categories, inflow, outflow)) = months
+            .iter_mut()
+            .find(|(m, ..)| *m == first_of_month(transaction.date))
+        else {
+            continue;
+        };
+        let parts: Vec<(Option<&str>, i64)> = if transaction.subtransactions.is_empty() {
+            vec![(
+                transaction.category_name.as_deref(),
+                transaction.amount.into(),
+            )]
+        } else {
+            transaction
+                .subtransactions
+                .iter()
+                .filter(|s| !s.deleted && s.transfer_account_id.is_none())
+                .map(|s| (s.category_name.as_deref(), s.amount.into()))
+                .collect()
+        };
+        for (category, amount) in parts {
+            if amount > 0 {
+                *inflow += amount;
+            } else {
+                *outflow += amount;
+            }
+            let name = category.unwrap_or(t("report-spending-uncategorized"));
+            *categories.entry(name.to_string()).or_default() += amount;
+        }
+    }
+
+    IncomeExpense {
+        months: months
+            .into_iter()
+            .map(|(month, categories, inflow, outflow)| {
+                let mut categories: Vec<CategoryFlow> = categories
+                    .into_iter()
+                    .filter(|(_, amount)| *amount != 0)
+                    .map(|(name, amount)| CategoryFlow { name, amount })
+                    .collect();
+                categories.sort_by(|a, b| {
+                    b.amount
+                        .abs()
+                        .cmp(&a.amount.abs())
+                        .then_with(|| a.name.cmp(&b.name))
+                });
+                MonthFlow {
+                    month,
+                    inflow,
+                    outflow,
+                    categories,
+                }
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn months_split_inflow_and_outflow_and_skip_transfers() {
+        let transactions = vec![
+            transaction("2025-02-01", 3_000_000)
+                .category("Inflow: Ready to Assign")
+                .build(),
+            transaction("2025-02-03", -800_000).category("Rent").build(),
+            transaction("2025-02-04", -120_000)
+                .category("Groceries")
+                .build(),
+            transaction("2025-02-10", 20_000)
+                .category("Groceries")
+                .build(),
+            transaction("2025-02-11", -500_000).transfer().build(),
+            transaction("2025-01-05", 2_500_000)
+                .category("Inflow: Ready to Assign")
+                .build(),
+            transaction("2025-01-06", -2_700_000)
+                .category("Rent")
+                .build(),
+            transaction("2024-02-20", -50_000)
+                .category("Groceries")
+                .build(),
+        ];
+
+        let report = build(&transactions, date(2025, 2, 14));
+        assert_eq!(report.months.len(), MONTHS as usize);
+        assert_eq!(report.months[0].month, date(2025, 2, 1));
+        assert_eq!(report.months[11].month, date(2024, 3, 1));
+        assert_eq!(first_month(date(2025, 2, 14)), report.months[11].month);
+
+        let february = &report.months[0];
+        assert_eq!(february.inflow, 3_020_000);
+        assert_eq!(february.outflow, -920_000);
+        assert_eq!(february.net(), 2_100_000);
+        assert_eq!(
+            february
+                .categories
+                .iter()
+                .map(|c| (c.name.as_str(), c.amount))
+                .collect::<Vec<_>>(),
+            [
+                ("Inflow: Ready to Assign", 3_000_000),
+                ("Rent", -800_000),
+                ("Groceries", -100_000),
+            ]
+        );
+
+        assert_eq!(report.months[1].net(), -200_000);
+        // Last February is a month too far back
+        assert_eq!(report.total_net(), 1_900_000);
+    }
+}
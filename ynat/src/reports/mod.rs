@@ -0,0 +1,175 @@
+//! Reports built from plan months and cached transactions.
+//!
+//! Each report is a pure function over API models so it can be tested
+//! without a loader; the Reports screen only renders the results.
+
+use serde::{Deserialize, Serialize};
+pub mod cash_flow;
+pub mod heatmap;
+pub mod income_expense;
+pub mod net_worth;
+pub mod obligations;
+pub mod spending;
+pub mod variance;
+pub mod weekly;
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::i18n::t;
+
+/// Reports available on the Reports screen
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum ReportKind {
+    #[default]
+    Variance,
+    CashFlow,
+    /// Spending per day of a month, as a calendar
+    Heatmap,
+    /// Next month's goal contributions against its scheduled income
+    Obligations,
+    /// Spending over a period by category or payee
+    Spending,
+    /// Inflow, outflow and net for each of the last twelve months
+    IncomeExpense,
+}
+
+/// Time span a report covers, relative to today
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ReportPeriod {
+    #[default]
+    ThisMonth,
+    LastMonth,
+    LastThreeMonths,
+    YearToDate,
+}
+
+impl ReportPeriod {
+    const ALL: [ReportPeriod; 4] = [
+        ReportPeriod::ThisMonth,
+        ReportPeriod::LastMonth,
+        ReportPeriod::LastThreeMonths,
+        ReportPeriod::YearToDate,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportPeriod::ThisMonth => t("report-period-this-month"),
+            ReportPeriod::LastMonth => t("report-period-last-month"),
+            ReportPeriod::LastThreeMonths => t("report-period-last-three-months"),
+            ReportPeriod::YearToDate => t("report-period-year-to-date"),
+        }
+    }
+
+    /// The next (or previous) period, wrapping around
+    pub fn cycle(&self, forward: bool) -> Self {
+        let len = Self::ALL.len();
+        let idx = Self::ALL.iter().position(|p| p == self).unwrap_or_default();
+        let next = if forward {
+            (idx + 1) % len
+        } else {
+            (idx + len - 1) % len
+        };
+        Self::ALL[next]
+    }
+
+    /// First day of each month in the period, oldest first
+    pub fn months(&self, today: NaiveDate) -> Vec<NaiveDate> {
+        let current = first_of_month(today);
+        let (start, end) = match self {
+            ReportPeriod::ThisMonth => (current, current),
+            ReportPeriod::LastMonth => {
+                let last = current - Months::new(1);
+                (last, last)
+            }
+            ReportPeriod::LastThreeMonths => (current - Months::new(2), current),
+            ReportPeriod::YearToDate => (current.with_month(1).unwrap_or(current), current),
+        };
+
+        let mut months = vec![start];
+        while let Some(next) = months
+            .last()
+            .and_then(|m| m.checked_add_months(Months::new(1)))
+        {
+            if next > end {
+                break;
+            }
+            months.push(next);
+        }
+        months
+    }
+
+    /// First day of the period
+    pub fn start(&self, today: NaiveDate) -> NaiveDate {
+        self.months(today)
+            .first()
+            .copied()
+            .unwrap_or_else(|| first_of_month(today))
+    }
+
+    /// Whether `date` falls within the period
+    pub fn contains(&self, today: NaiveDate, date: NaiveDate) -> bool {
+        let months = self.months(today);
+        match (months.first(), months.last()) {
+            (Some(first), Some(last)) => date >= *first && first_of_month(date) <= *last,
+            _ => false,
+        }
+    }
+}
+
+pub fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn months_cover_period() {
+        let today = date(2025, 2, 14);
+        assert_eq!(
+            ReportPeriod::ThisMonth.months(today),
+            vec![date(2025, 2, 1)]
+        );
+        assert_eq!(
+            ReportPeriod::LastMonth.months(today),
+            vec![date(2025, 1, 1)]
+        );
+        assert_eq!(
+            ReportPeriod::LastThreeMonths.months(today),
+            vec![date(2024, 12, 1), date(2025, 1, 1), date(2025, 2, 1)]
+        );
+        assert_eq!(
+            ReportPeriod::YearToDate.months(today),
+            vec![date(2025, 1, 1), date(2025, 2, 1)]
+        );
+    }
+
+    #[test]
+    fn contains_checks_whole_months() {
+        let today = date(2025, 2, 14);
+        assert!(ReportPeriod::LastMonth.contains(today, date(2025, 1, 31)));
+        assert!(!ReportPeriod::LastMonth.contains(today, date(2025, 2, 1)));
+        assert!(ReportPeriod::ThisMonth.contains(today, date(2025, 2, 28)));
+        assert_eq!(
+            ReportPeriod::LastThreeMonths.start(today),
+            date(2024, 12, 1)
+        );
+    }
+
+    #[test]
+    fn cycle_wraps() {
+        assert_eq!(
+            ReportPeriod::YearToDate.cycle(true),
+            ReportPeriod::ThisMonth
+        );
+        assert_eq!(
+            ReportPeriod::ThisMonth.cycle(false),
+            ReportPeriod::YearToDate
+        );
+    }
+}
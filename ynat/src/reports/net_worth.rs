@@ -0,0 +1,150 @@
+//! Net worth across every open account, on budget and tracking.
+
+use crate::i18n::t;
+use serde::{Deserialize, Serialize};
+use ynab_api::endpoints::accounts::{Account, AccountType};
+
+/// Account types in the order they're listed: cash, credit, tracking, loans
+const ACCOUNT_TYPES: [AccountType; 13] = [
+    AccountType::Checking,
+    AccountType::Savings,
+    AccountType::Cash,
+    AccountType::CreditCard,
+    AccountType::LineOfCredit,
+    AccountType::OtherAsset,
+    AccountType::OtherLiability,
+    AccountType::Mortgage,
+    AccountType::AutoLoan,
+    AccountType::StudentLoan,
+    AccountType::PersonalLoan,
+    AccountType::MedicalDebt,
+    AccountType::OtherDebt,
+];
+
+pub fn account_type_label(account_type: AccountType) -> &'static str {
+    match account_type {
+        AccountType::Checking => t("account-type-checking"),
+        AccountType::Savings => t("account-type-savings"),
+        AccountType::Cash => t("account-type-cash"),
+        AccountType::CreditCard => t("account-type-credit-card"),
+        AccountType::LineOfCredit => t("account-type-line-of-credit"),
+        AccountType::OtherAsset => t("account-type-other-asset"),
+        AccountType::OtherLiability => t("account-type-other-liability"),
+        AccountType::Mortgage => t("account-type-mortgage"),
+        AccountType::AutoLoan => t("account-type-auto-loan"),
+        AccountType::StudentLoan => t("account-type-student-loan"),
+        AccountType::PersonalLoan => t("account-type-personal-loan"),
+        AccountType::MedicalDebt => t("account-type-medical-debt"),
+        AccountType::OtherDebt => t("account-type-other-debt"),
+    }
+}
+
+/// The open accounts of one type added together
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountTypeTotal {
+    pub account_type: AccountType,
+    pub accounts: usize,
+    pub balance: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetWorth {
+    /// Balances of the accounts in credit
+    pub assets: i64,
+    /// Balances of the accounts in debt, as a negative amount
+    pub liabilities: i64,
+    /// One entry per account type that has an open account
+    pub by_type: Vec<AccountTypeTotal>,
+}
+
+impl NetWorth {
+    pub fn net(&self) -> i64 {
+        self.assets + self.liabilities
+    }
+}
+
+/// Add up every open account, on budget or tracking
+///
+/// An account counts towards assets or liabilities by the sign of its
+/// balance rather than its type, so a checking account in overdraft is a
+/// liability and an overpaid credit card an asset.
+pub fn build(accounts: &[Account]) -> NetWorth {
+    let open: Vec<&Account> = accounts
+        .iter()
+        .filter(|a| !a.closed && !a.deleted)
+        .collect();
+    let balances = open.iter().map(|a| i64::from(a.balance));
+
+    let by_type = ACCOUNT_TYPES
+        .iter()
+        .filter_map(|&account_type| {
+            let of_type: Vec<i64> = open
+                .iter()
+                .filter(|a| a.account_type == account_type)
+                .map(|a| i64::from(a.balance))
+                .collect();
+            (!of_type.is_empty()).then(|| AccountTypeTotal {
+                account_type,
+                accounts: of_type.len(),
+                balance: of_type.iter().sum(),
+            })
+        })
+        .collect();
+
+    NetWorth {
+        assets: balances.clone().filter(|b| *b > 0).sum(),
+        liabilities: balances.filter(|b| *b < 0).sum(),
+        by_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::account;
+
+    #[test]
+    fn adds_up_open_accounts_by_sign_and_type() {
+        let accounts = vec![
+            account("Checking").balance(2_000_000).build(),
+            account("Joint").balance(-50_000).build(),
+            account("Visa")
+                .account_type(AccountType::CreditCard)
+                .balance(-400_000)
+                .build(),
+            account("House")
+                .account_type(AccountType::OtherAsset)
+                .balance(10_000_000)
+                .off_budget()
+                .build(),
+            account("Mortgage")
+                .account_type(AccountType::Mortgage)
+                .balance(-8_000_000)
+                .off_budget()
+                .build(),
+            account("Old savings")
+                .account_type(AccountType::Savings)
+                .balance(5_000_000)
+                .closed()
+                .build(),
+        ];
+
+        let net_worth = build(&accounts);
+        assert_eq!(net_worth.assets, 12_000_000);
+        assert_eq!(net_worth.liabilities, -8_450_000);
+        assert_eq!(net_worth.net(), 3_550_000);
+        assert_eq!(
+            net_worth
+                .by_type
+                .iter()
+                .map(|t| (t.account_type, t.accounts, t.balance))
+                .collect::<Vec<_>>(),
+            [
+                (AccountType::Checking, 2, 1_950_000),
+                (AccountType::CreditCard, 1, -400_000),
+                (AccountType::OtherAsset, 1, 10_000_000),
+                (AccountType::Mortgage, 1, -8_000_000),
+            ]
+        );
+    }
+}
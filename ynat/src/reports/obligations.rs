@@ -0,0 +1,162 @@
+//! Goal contributions due next month against the income scheduled for it.
+
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use ynab_api::endpoints::{months::MonthDetail, scheduled_transactions::ScheduledTransaction};
+
+use super::cash_flow::occurrences;
+use super::variance::INTERNAL_GROUP;
+
+/// What a category's goal still needs in the month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoalObligation {
+    pub category_id: String,
+    pub category_name: String,
+    pub group_name: Option<String>,
+    pub amount: i64,
+}
+
+/// One scheduled inflow landing in the month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedIncome {
+    pub date: NaiveDate,
+    pub payee_name: Option<String>,
+    pub account_name: String,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObligationsForecast {
+    /// First day of the month forecast
+    pub month: NaiveDate,
+    /// Largest first
+    pub obligations: Vec<GoalObligation>,
+    /// Earliest first
+    pub income: Vec<ExpectedIncome>,
+}
+
+impl ObligationsForecast {
+    pub fn total_obligations(&self) -> i64 {
+        self.obligations.iter().map(|o| o.amount).sum()
+    }
+
+    pub fn total_income(&self) -> i64 {
+        self.income.iter().map(|i| i.amount).sum()
+    }
+
+    /// How much more the goals need than is scheduled to come in; zero when
+    /// the income covers them
+    pub fn shortfall(&self) -> i64 {
+        (self.total_obligations() - self.total_income()).max(0)
+    }
+}
+
+/// Forecast `month` from its plan's goal underfunded amounts and the
+/// scheduled inflows dated in it
+///
+/// Hidden categories and snoozed goals don't count. Transfers between
+/// accounts aren't income.
+pub fn build(
+    month: NaiveDate,
+    plan: &MonthDetail,
+    scheduled: &[ScheduledTransaction],
+) -> ObligationsForecast {
+    let month = super::first_of_month(month);
+    let until = month
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.checked_sub_days(Days::new(1)))
+        .unwrap_or(month);
+
+    let mut obligations: Vec<GoalObligation> = plan
+        .categories
+        .iter()
+        .filter(|c| !c.hidden && !c.deleted && c.goal_snoozed_at.is_none())
+        .filter(|c| c.category_group_name.as_deref() != Some(INTERNAL_GROUP))
+        .filter_map(|c| {
+            let amount = c.goal_under_funded.map(i64::from).filter(|a| *a > 0)?;
+            Some(GoalObligation {
+                category_id: c.id.to_string(),
+                category_name: c.name.clone(),
+                group_name: c.category_group_name.clone(),
+                amount,
+            })
+        })
+        .collect();
+    obligations.sort_by_key(|o| std::cmp::Reverse(o.amount));
+
+    let mut income: Vec<ExpectedIncome> = scheduled
+        .iter()
+        .filter(|s| !s.deleted && s.transfer_account_id.is_none())
+        .filter(|s| i64::from(s.amount) > 0)
+        .flat_map(|s| {
+            occurrences(s.frequency, s.date_next, until)
+                .into_iter()
+                .filter(|date| *date >= month)
+                .map(|date| ExpectedIncome {
+                    date,
+                    payee_name: s.payee_name.clone(),
+                    account_name: s.account_name.clone(),
+                    amount: s.amount.into(),
+                })
+        })
+        .collect();
+    income.sort_by_key(|i| i.date);
+
+    ObligationsForecast {
+        month,
+        obligations,
+        income,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{category, month, scheduled};
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+    use ynab_api::endpoints::scheduled_transactions::Frequency;
+
+    #[test]
+    fn obligations_against_scheduled_income() {
+        let plan = month(vec![
+            category("Rent").group("Bills").need(1_200_000).build(),
+            category("Power").group("Bills").need(300_000).build(),
+            category("Insurance")
+                .group("Bills")
+                .need(500_000)
+                .snoozed(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap())
+                .build(),
+            category("Phone").group("Bills").need(0).build(),
+        ]);
+        let scheduled = vec![
+            // Paid every other week from February: lands on Mar 7 and Mar 21
+            scheduled("2025-02-21", 600_000)
+                .frequency(Frequency::EveryOtherWeek)
+                .payee("Employer")
+                .build(),
+            scheduled("2025-03-05", 400_000)
+                .transfer_to(Uuid::from_u128(2))
+                .build(),
+            scheduled("2025-03-10", -90_000).build(),
+        ];
+
+        let forecast = build(
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            &plan,
+            &scheduled,
+        );
+        assert_eq!(forecast.obligations.len(), 2);
+        assert_eq!(forecast.total_obligations(), 1_500_000);
+        assert_eq!(
+            forecast
+                .income
+                .iter()
+                .map(|i| i.date.to_string())
+                .collect::<Vec<_>>(),
+            ["2025-03-07", "2025-03-21"]
+        );
+        assert_eq!(forecast.total_income(), 1_200_000);
+        assert_eq!(forecast.shortfall(), 300_000);
+    }
+}
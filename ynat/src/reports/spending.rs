@@ -0,0 +1,225 @@
+//! Where the money went over a period, by category or by payee.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use ynab_api::endpoints::transactions::Transaction;
+
+use super::ReportPeriod;
+use crate::i18n::t;
+
+/// What the spending report adds up by
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpendingGrouping {
+    #[default]
+    Category,
+    Payee,
+}
+
+impl SpendingGrouping {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpendingGrouping::Category => t("report-spending-by-category"),
+            SpendingGrouping::Payee => t("report-spending-by-payee"),
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            SpendingGrouping::Category => SpendingGrouping::Payee,
+            SpendingGrouping::Payee => SpendingGrouping::Category,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpendingRow {
+    /// Category id when grouped by category; none for uncategorized spending
+    /// and payees
+    pub category_id: Option<String>,
+    pub name: String,
+    /// Outflows less refunds, as a positive amount
+    pub spent: i64,
+    pub transactions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpendingBreakdown {
+    pub period: ReportPeriod,
+    pub grouping: SpendingGrouping,
+    /// Biggest spending first
+    pub rows: Vec<SpendingRow>,
+}
+
+impl SpendingBreakdown {
+    pub fn total(&self) -> i64 {
+        self.rows.iter().map(|r| r.spent).sum()
+    }
+}
+
+/// Add up spending per category or payee for transactions with
+/// `include(date)` true
+///
+/// Splits count each part under its own category (and payee, falling back to
+/// the parent's). Transfers are left out, refunds reduce the spending they
+/// offset, and groups that took in more than they spent aren't listed.
+pub fn build(
+    transactions: &[Transaction],
+    period: ReportPeriod,
+    grouping: SpendingGrouping,
+    include: impl Fn(NaiveDate) -> bool,
+) -> SpendingBreakdown {
+    let mut totals: HashMap<String, SpendingRow> = HashMap::new();
+    let mut add =
+        |category_id: Option<String>, category: Option<&str>, payee: Option<&str>, amount: i64| {
+            let (key, category_id, name) = match grouping {
+                SpendingGrouping::Category => (
+                    category_id.clone().unwrap_or_default(),
+                    category_id,
+                    category.unwrap_or(t("report-spending-uncategorized")),
+                ),
+                SpendingGrouping::Payee => {
+                    let name = payee.unwrap_or(t("report-spending-no-payee"));
+                    (name.to_lowercase(), None, name)
+                }
+            };
+            let row = totals.entry(key).or_insert_with(|| SpendingRow {
+                category_id,
+                name: name.to_string(),
+                spent: 0,
+                transactions: 0,
+            });
+            row.spent -= amount;
+            row.transactions += 1;
+        };
+
+    for t in transactions
+        .iter()
+        .filter(|t| !t.deleted && t.transfer_account_id.is_none() && include(t.date))
+    {
+        if t.subtransactions.is_empty() {
+            add(
+                t.category_id.map(|id| id.to_string()),
+                t.category_name.as_deref(),
+                t.payee_name.as_deref(),
+                t.amount.into(),
+            );
+            continue;
+        }
+        for s in t
+            .subtransactions
+            .iter()
+            .filter(|s| !s.deleted && s.transfer_account_id.is_none())
+        {
+            add(
+                s.category_id.map(|id| id.to_string()),
+                s.category_name.as_deref(),
+                s.payee_name.as_deref().or(t.payee_name.as_deref()),
+                s.amount.into(),
+            );
+        }
+    }
+
+    let mut rows: Vec<SpendingRow> = totals.into_values().filter(|r| r.spent > 0).collect();
+    rows.sort_by(|a, b| b.spent.cmp(&a.spent).then_with(|| a.name.cmp(&b.name)));
+    SpendingBreakdown {
+        period,
+        grouping,
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    fn transactions() -> Vec<Transaction> {
+        let split = transaction("2025-02-08", -70_000)
+            .payee("Market")
+            .split(&[(-50_000, "Groceries"), (-20_000, "Household")])
+            .build();
+        let transfer = transaction("2025-02-09", -300_000)
+            .payee("Transfer : Savings")
+            .transfer()
+            .build();
+
+        vec![
+            transaction("2025-02-03", -30_000)
+                .payee("Corner Shop")
+                .category("Groceries")
+                .build(),
+            transaction("2025-02-05", 10_000)
+                .payee("Corner Shop")
+                .category("Groceries")
+                .build(),
+            transaction("2025-02-06", -45_000)
+                .payee("Cinema")
+                .category("Fun")
+                .build(),
+            transaction("2025-02-07", 2_000_000)
+                .payee("Employer")
+                .category("Inflow: Ready to Assign")
+                .build(),
+            split,
+            transfer,
+            transaction("2025-01-20", -99_000)
+                .payee("Cinema")
+                .category("Fun")
+                .build(),
+        ]
+    }
+
+    fn february(date: NaiveDate) -> bool {
+        date >= NaiveDate::from_ymd_opt(2025, 2, 1).unwrap()
+    }
+
+    #[test]
+    fn categories_net_refunds_and_count_split_parts() {
+        let breakdown = build(
+            &transactions(),
+            ReportPeriod::ThisMonth,
+            SpendingGrouping::Category,
+            february,
+        );
+        let rows: Vec<(&str, i64, usize)> = breakdown
+            .rows
+            .iter()
+            .map(|r| (r.name.as_str(), r.spent, r.transactions))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("Groceries", 70_000, 3),
+                ("Fun", 45_000, 1),
+                ("Household", 20_000, 1)
+            ]
+        );
+        assert!(breakdown.rows.iter().all(|r| r.category_id.is_some()));
+        assert_eq!(breakdown.total(), 135_000);
+    }
+
+    #[test]
+    fn payees_fall_back_to_the_parent_for_split_parts() {
+        let breakdown = build(
+            &transactions(),
+            ReportPeriod::ThisMonth,
+            SpendingGrouping::Payee,
+            february,
+        );
+        let rows: Vec<(&str, i64)> = breakdown
+            .rows
+            .iter()
+            .map(|r| (r.name.as_str(), r.spent))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("Market", 70_000),
+                ("Cinema", 45_000),
+                ("Corner Shop", 20_000)
+            ]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Budget vs actual variance by category.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ynab_api::endpoints::{months::MonthDetail, transactions::Transaction};
+
+/// YNAB's hidden group holding "Inflow: Ready to Assign"
+pub(crate) const INTERNAL_GROUP: &str = "Internal Master Category";
+
+/// Budgeted vs actual spending for one category over a period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VarianceRow {
+    pub category_id: String,
+    pub category_name: String,
+    pub group_name: Option<String>,
+    /// Total budgeted in milliunits
+    pub budgeted: i64,
+    /// Total spent in milliunits (positive means money went out)
+    pub actual: i64,
+}
+
+impl VarianceRow {
+    /// Budgeted minus actual; negative means overspent
+    pub fn variance(&self) -> i64 {
+        self.budgeted - self.actual
+    }
+
+    /// Variance as a percentage of the budgeted amount
+    pub fn variance_percent(&self) -> Option<f64> {
+        (self.budgeted != 0).then(|| self.variance() as f64 / self.budgeted as f64 * 100.0)
+    }
+}
+
+/// How the variance table is ordered
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum VarianceSort {
+    /// Plan order, as categories appear in YNAB
+    #[default]
+    Category,
+    /// Most overspent first
+    Variance,
+}
+
+/// A transaction (or the matching part of a split) behind a variance row
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VarianceTransaction {
+    pub date: NaiveDate,
+    pub account_name: String,
+    pub payee_name: Option<String>,
+    pub memo: Option<String>,
+    pub amount: i64,
+}
+
+/// Sum budgeted and activity per category across the given months, in plan order.
+/// Hidden, deleted and untouched categories are left out.
+pub fn build_rows(months: &[MonthDetail]) -> Vec<VarianceRow> {
+    let mut rows: Vec<VarianceRow> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for category in months.iter().flat_map(|m| &m.categories) {
+        if category.hidden
+            || category.deleted
+            || category.category_group_name.as_deref() == Some(INTERNAL_GROUP)
+        {
+            continue;
+        }
+
+        let id = category.id.to_string();
+        let i = *index.entry(id.clone()).or_insert_with(|| {
+            rows.push(VarianceRow {
+                category_id: id,
+                category_name: category.name.clone(),
+                group_name: category.category_group_name.clone(),
+                budgeted: 0,
+                actual: 0,
+            });
+            rows.len() - 1
+        });
+
+        let budgeted: i64 = category.budgeted.into();
+        let activity: i64 = category.activity.into();
+        rows[i].budgeted += budgeted;
+        rows[i].actual -= activity;
+    }
+
+    rows.retain(|r| r.budgeted != 0 || r.actual != 0);
+    rows
+}
+
+/// Rows in display order
+pub fn sorted(rows: &[VarianceRow], sort: VarianceSort) -> Vec<&VarianceRow> {
+    let mut sorted: Vec<&VarianceRow> = rows.iter().collect();
+    if sort == VarianceSort::Variance {
+        sorted.sort_by_key(|r| r.variance());
+    }
+    sorted
+}
+
+/// Transactions categorized to `category_id` with `include(date)` true, newest first.
+/// Splits contribute only their matching subtransactions.
+pub fn category_transactions(
+    transactions: &[Transaction],
+    category_id: &str,
+    include: impl Fn(NaiveDate) -> bool,
+) -> Vec<VarianceTransaction> {
+    let mut matched: Vec<VarianceTransaction> = transactions
+        .iter()
+        .filter(|t| !t.deleted && include(t.date))
+        .filter_map(|t| {
+            let amount: i64 = if t.subtransactions.is_empty() {
+                if t.category_id.map(|id| id.to_string()).as_deref() != Some(category_id) {
+                    return None;
+                }
+                t.amount.into()
+            } else {
+                let parts: Vec<i64> = t
+                    .subtransactions
+                    .iter()
+                    .filter(|s| {
+                        !s.deleted
+                            && s.category_id.map(|id| id.to_string()).as_deref()
+                                == Some(category_id)
+                    })
+                    .map(|s| s.amount.into())
+                    .collect();
+                if parts.is_empty() {
+                    return None;
+                }
+                parts.into_iter().sum()
+            };
+
+            Some(VarianceTransaction {
+                date: t.date,
+                account_name: t.account_name.clone(),
+                payee_name: t.payee_name.clone(),
+                memo: t.memo.clone(),
+                amount,
+            })
+        })
+        .collect();
+
+    matched.sort_by_key(|t| std::cmp::Reverse(t.date));
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{category, month};
+
+    #[test]
+    fn rows_sum_across_months() {
+        let months = vec![
+            month(vec![
+                category("Groceries").amounts(400_000, -450_000).build(),
+                category("Unused").build(),
+            ]),
+            month(vec![category("Groceries")
+                .amounts(400_000, -300_000)
+                .build()]),
+        ];
+
+        let rows = build_rows(&months);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].budgeted, 800_000);
+        assert_eq!(rows[0].actual, 750_000);
+        assert_eq!(rows[0].variance(), 50_000);
+        assert_eq!(rows[0].variance_percent(), Some(6.25));
+    }
+
+    #[test]
+    fn variance_sort_puts_overspent_first() {
+        let rows = build_rows(&[month(vec![
+            category("Rent").amounts(1_000_000, -1_000_000).build(),
+            category("Dining").amounts(100_000, -250_000).build(),
+        ])]);
+
+        let names: Vec<_> = sorted(&rows, VarianceSort::Variance)
+            .iter()
+            .map(|r| r.category_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Dining", "Rent"]);
+    }
+}
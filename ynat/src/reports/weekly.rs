@@ -0,0 +1,313 @@
+//! Weekly summary rendered to Markdown for `ynat report --weekly`.
+//!
+//! The summary covers the seven days ending today. Output goes through a
+//! plain `{placeholder}` template so it can be reshaped for whatever mails it.
+
+use chrono::{Days, NaiveDate};
+use std::collections::HashMap;
+use ynab_api::endpoints::{budgets::BudgetSummary, months::MonthDetail, transactions::Transaction};
+
+use crate::ui::utils::format_amount;
+
+/// Days covered by the summary, including today
+pub const WEEK_DAYS: u64 = 7;
+
+/// Template used when no `--template` is given
+pub const DEFAULT_TEMPLATE: &str = "# Weekly summary: {budget_name}
+
+{start} – {end}
+
+## Spending by category
+
+Total spent: {total_spent}
+
+{spending}
+
+## New transactions
+
+{transactions}
+
+## Budget status
+
+Ready to assign: {to_be_budgeted}
+
+{budget}
+";
+
+/// Outflows for one category over the week
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategorySpending {
+    pub category_name: String,
+    /// Positive amount spent
+    pub spent: i64,
+}
+
+/// A category's position in the current plan month
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryStatus {
+    pub category_name: String,
+    pub budgeted: i64,
+    pub activity: i64,
+    pub balance: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklySummary {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Largest spending first
+    pub spending: Vec<CategorySpending>,
+    /// Transactions dated within the week, newest first
+    pub transactions: Vec<Transaction>,
+    pub to_be_budgeted: Option<i64>,
+    /// Overspent categories first, then by name
+    pub budget: Vec<CategoryStatus>,
+}
+
+impl WeeklySummary {
+    pub fn total_spent(&self) -> i64 {
+        self.spending.iter().map(|s| s.spent).sum()
+    }
+}
+
+/// Build the summary for the week ending `today`
+///
+/// Transfers are left out of spending since they don't leave the budget.
+/// Splits contribute each subtransaction to its own category.
+pub fn build(
+    transactions: &[Transaction],
+    month: Option<&MonthDetail>,
+    today: NaiveDate,
+) -> WeeklySummary {
+    let start = today - Days::new(WEEK_DAYS - 1);
+
+    let mut week: Vec<Transaction> = transactions
+        .iter()
+        .filter(|t| !t.deleted && t.date >= start && t.date <= today)
+        .cloned()
+        .collect();
+    week.sort();
+
+    let mut by_category: HashMap<String, i64> = HashMap::new();
+    for t in week.iter().filter(|t| t.transfer_account_id.is_none()) {
+        if t.subtransactions.is_empty() {
+            let amount: i64 = t.amount.into();
+            if amount < 0 {
+                *by_category
+                    .entry(category_label(&t.category_name))
+                    .or_default() -= amount;
+            }
+        } else {
+            for sub in t.subtransactions.iter().filter(|s| !s.deleted) {
+                let amount: i64 = sub.amount.into();
+                if amount < 0 && sub.transfer_account_id.is_none() {
+                    *by_category
+                        .entry(category_label(&sub.category_name))
+                        .or_default() -= amount;
+                }
+            }
+        }
+    }
+
+    let mut spending: Vec<CategorySpending> = by_category
+        .into_iter()
+        .map(|(category_name, spent)| CategorySpending {
+            category_name,
+            spent,
+        })
+        .collect();
+    spending.sort_by(|a, b| {
+        b.spent
+            .cmp(&a.spent)
+            .then_with(|| a.category_name.cmp(&b.category_name))
+    });
+
+    let mut budget: Vec<CategoryStatus> = month
+        .map(|m| {
+            m.categories
+                .iter()
+                .filter(|c| !c.deleted && !c.hidden)
+                .map(|c| CategoryStatus {
+                    category_name: c.name.clone(),
+                    budgeted: c.budgeted.into(),
+                    activity: c.activity.into(),
+                    balance: c.balance.into(),
+                })
+                .filter(|c| c.budgeted != 0 || c.activity != 0 || c.balance != 0)
+                .collect()
+        })
+        .unwrap_or_default();
+    budget.sort_by(|a, b| {
+        (a.balance >= 0)
+            .cmp(&(b.balance >= 0))
+            .then_with(|| a.category_name.cmp(&b.category_name))
+    });
+
+    WeeklySummary {
+        start,
+        end: today,
+        spending,
+        transactions: week,
+        to_be_budgeted: month.map(|m| m.to_be_budgeted.into()),
+        budget,
+    }
+}
+
+fn category_label(name: &Option<String>) -> String {
+    name.clone()
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "Uncategorized".to_string())
+}
+
+/// Fill `template` from the summary
+///
+/// Supported placeholders: `{budget_name}`, `{start}`, `{end}`,
+/// `{total_spent}`, `{spending}`, `{transactions}`, `{to_be_budgeted}` and
+/// `{budget}`. The list placeholders expand to Markdown tables.
+pub fn render(summary: &WeeklySummary, budget: Option<&BudgetSummary>, template: &str) -> String {
+    // Right-aligned in the TUI, but the padding only gets in the way here
+    let fmt = |amount: i64| format_amount(amount, budget).trim().to_string();
+
+    let spending = if summary.spending.is_empty() {
+        "No spending this week.".to_string()
+    } else {
+        let mut out = "| Category | Spent |\n|---|---:|\n".to_string();
+        for row in &summary.spending {
+            out.push_str(&format!(
+                "| {} | {} |\n",
+                escape(&row.category_name),
+                fmt(row.spent)
+            ));
+        }
+        out.trim_end().to_string()
+    };
+
+    let transactions = if summary.transactions.is_empty() {
+        "No transactions this week.".to_string()
+    } else {
+        let mut out =
+            "| Date | Account | Payee | Category | Amount |\n|---|---|---|---|---:|\n".to_string();
+        for t in &summary.transactions {
+            let category = if t.subtransactions.is_empty() {
+                t.category_name.clone().unwrap_or_default()
+            } else {
+                "Split".to_string()
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                t.date.format("%Y-%m-%d"),
+                escape(&t.account_name),
+                escape(t.payee_name.as_deref().unwrap_or_default()),
+                escape(&category),
+                fmt(t.amount.into())
+            ));
+        }
+        out.trim_end().to_string()
+    };
+
+    let budget_status = if summary.budget.is_empty() {
+        "No plan cached for this month.".to_string()
+    } else {
+        let mut out =
+            "| Category | Assigned | Activity | Available |\n|---|---:|---:|---:|\n".to_string();
+        for row in &summary.budget {
+            let marker = if row.balance < 0 { " ⚠" } else { "" };
+            out.push_str(&format!(
+                "| {}{} | {} | {} | {} |\n",
+                escape(&row.category_name),
+                marker,
+                fmt(row.budgeted),
+                fmt(row.activity),
+                fmt(row.balance)
+            ));
+        }
+        out.trim_end().to_string()
+    };
+
+    template
+        .replace(
+            "{budget_name}",
+            budget.map(|b| b.name.as_str()).unwrap_or_default(),
+        )
+        .replace("{start}", &summary.start.format("%Y-%m-%d").to_string())
+        .replace("{end}", &summary.end.format("%Y-%m-%d").to_string())
+        .replace("{total_spent}", &fmt(summary.total_spent()))
+        .replace(
+            "{to_be_budgeted}",
+            &summary
+                .to_be_budgeted
+                .map(fmt)
+                .unwrap_or_else(|| "–".into()),
+        )
+        .replace("{spending}", &spending)
+        .replace("{transactions}", &transactions)
+        .replace("{budget}", &budget_status)
+}
+
+/// Keep user text from breaking a Markdown table row
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()
+    }
+
+    #[test]
+    fn spending_covers_only_the_last_seven_days() {
+        let transactions = vec![
+            transaction("2025-01-10", -5_000).category("Dining").build(),
+            transaction("2025-01-04", -20_000)
+                .category("Groceries")
+                .build(),
+            transaction("2025-01-03", -99_000)
+                .category("Groceries")
+                .build(),
+            transaction("2025-01-08", 100_000)
+                .category("Inflow: Ready to Assign")
+                .build(),
+        ];
+
+        let summary = build(&transactions, None, today());
+
+        assert_eq!(summary.start, NaiveDate::from_ymd_opt(2025, 1, 4).unwrap());
+        assert_eq!(summary.transactions.len(), 3);
+        assert_eq!(
+            summary.spending,
+            vec![
+                CategorySpending {
+                    category_name: "Groceries".into(),
+                    spent: 20_000
+                },
+                CategorySpending {
+                    category_name: "Dining".into(),
+                    spent: 5_000
+                },
+            ]
+        );
+        assert_eq!(summary.total_spent(), 25_000);
+    }
+
+    #[test]
+    fn render_fills_custom_template() {
+        let summary = build(
+            &[transaction("2025-01-09", -12_500)
+                .category("Dining")
+                .build()],
+            None,
+            today(),
+        );
+
+        let out = render(&summary, None, "{start}..{end}: {total_spent}");
+        assert_eq!(out, "2025-01-04..2025-01-10: $12.50");
+
+        let out = render(&summary, None, DEFAULT_TEMPLATE);
+        assert!(out.contains("| Dining | $12.50 |"));
+        assert!(out.contains("No plan cached for this month."));
+    }
+}
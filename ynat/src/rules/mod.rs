@@ -0,0 +1,140 @@
+//! Payee rules for categorizing transactions in bulk.
+//!
+//! Rules live under `[[rules]]` in `config.toml` and map a payee pattern to a
+//! category. Applying them only touches transactions that still need review
+//! (unapproved or uncategorized), so a category picked by hand on an
+//! approved transaction is never overwritten.
+
+use crate::config::PayeeRule;
+use crate::state;
+use uuid::Uuid;
+use ynab_api::endpoints::{categories::Category, transactions::Transaction};
+
+/// A transaction a rule puts in a category
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    pub transaction_id: String,
+    pub category_id: Uuid,
+    pub category_name: String,
+}
+
+/// The category each reviewable transaction gets from the first rule its
+/// payee matches. Splits, transfers and reconciled transactions are left
+/// alone, as are rules naming a category that doesn't exist (or is hidden).
+pub fn categorize<'a>(
+    rules: &[PayeeRule],
+    transactions: impl IntoIterator<Item = &'a Transaction>,
+    categories: &[Category],
+) -> Vec<RuleMatch> {
+    transactions
+        .into_iter()
+        .filter(|t| state::needs_review(t) && !t.deleted && !t.is_reconciled())
+        .filter(|t| t.transfer_account_id.is_none() && t.subtransactions.is_empty())
+        .filter_map(|t| {
+            let payee = t.payee_name.as_deref()?;
+            let rule = rules.iter().find(|rule| matches(&rule.payee, payee))?;
+            let category = categories.iter().find(|c| {
+                !c.hidden && !c.deleted && c.name.eq_ignore_ascii_case(rule.category.trim())
+            })?;
+            (t.category_id != Some(category.id)).then(|| RuleMatch {
+                transaction_id: t.id.to_string(),
+                category_id: category.id,
+                category_name: category.name.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Whether `payee` fits `pattern`, ignoring case, with `*` matching any run
+/// of characters
+fn matches(pattern: &str, payee: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let payee = payee.trim().to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = payee.strip_prefix(first) else {
+        return false;
+    };
+    let middle: Vec<&str> = parts.collect();
+    let Some((last, middle)) = middle.split_last() else {
+        // No `*` at all
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{category, id, transaction};
+
+    fn rule(payee: &str, category: &str) -> PayeeRule {
+        PayeeRule {
+            payee: payee.to_string(),
+            category: category.to_string(),
+        }
+    }
+
+    #[test]
+    fn patterns_ignore_case_and_star_matches_anything() {
+        assert!(matches("AMZN*", "amzn Mktp US*1234"));
+        assert!(matches("*coffee*", "Blue Bottle Coffee #42"));
+        assert!(matches("sq *bottle", "SQ *Blue Bottle"));
+        assert!(matches("Netflix", "NETFLIX"));
+        assert!(!matches("Netflix", "Netflix.com"));
+        assert!(!matches("*coffee*tea", "Coffee and cake"));
+        assert!(!matches("", "Anything"));
+    }
+
+    #[test]
+    fn only_transactions_needing_review_are_categorized() {
+        let rules = vec![
+            rule("amzn*", "Shopping"),
+            rule("*", "Groceries"),
+            rule("nowhere", "Missing"),
+        ];
+        let categories = vec![category("Groceries").build(), category("Shopping").build()];
+        let review = |payee: &str, approved: bool| {
+            transaction("2025-01-15", -10_000)
+                .payee(payee)
+                .approved(approved)
+        };
+        let transactions = vec![
+            // Unapproved, so YNAB's guess is replaced
+            review("AMZN Mktp", false).category("Groceries").build(),
+            // Approved and categorized by hand
+            review("AMZN Mktp", true).category("Groceries").build(),
+            // Uncategorized; the catch-all rule applies
+            review("Corner Shop", true).build(),
+            // Already in the category the rule picks
+            review("Corner Shop", false).category("Groceries").build(),
+        ];
+
+        let matched = categorize(&rules, &transactions, &categories);
+        assert_eq!(
+            matched,
+            vec![
+                RuleMatch {
+                    transaction_id: transactions[0].id.to_string(),
+                    category_id: id("Shopping"),
+                    category_name: "Shopping".to_string(),
+                },
+                RuleMatch {
+                    transaction_id: transactions[2].id.to_string(),
+                    category_id: id("Groceries"),
+                    category_name: "Groceries".to_string(),
+                },
+            ]
+        );
+    }
+}
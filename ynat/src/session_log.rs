@@ -0,0 +1,265 @@
+//! Session recording, for rebuilding the state behind a reported UI bug.
+//!
+//! With `[debug] record_session` set, every command a key press produces and
+//! every data event the app receives is appended to a file, one JSON object
+//! per line. `ynat replay FILE` starts from an empty state and applies them in
+//! order: commands through the executor, events through the pure reducer, so
+//! the state after each step is the one the user saw.
+//!
+//! Loads started by replayed commands go to an address nothing listens on and
+//! their results are dropped; only the recorded events reach the state.
+
+use anyhow::{anyhow, Context, Result};
+use ratatui::{backend::TestBackend, Terminal};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use ynab_api::Client;
+
+use crate::api_log::ApiLog;
+use crate::app::load_initial_data;
+use crate::background::{data_loader::DataLoader, BackgroundTaskManager};
+use crate::cache::Cache;
+use crate::commands::{executor, AppCommand};
+use crate::events::DataEvent;
+use crate::log_buffer::LogBuffer;
+use crate::replay;
+use crate::state::AppState;
+
+/// One line of a session log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SessionEntry {
+    Command(AppCommand),
+    Event(DataEvent),
+}
+
+/// Serialized like [`SessionEntry`], without cloning what's recorded
+#[derive(Serialize)]
+enum EntryRef<'a> {
+    Command(&'a AppCommand),
+    Event(&'a DataEvent),
+}
+
+impl SessionEntry {
+    /// Variant name, e.g. `SelectNext` or `Budget/AccountsLoaded`
+    pub fn name(&self) -> String {
+        // Events are wrapped in their domain, so take two levels of names
+        let (kind, value, depth) = match self {
+            SessionEntry::Command(command) => ("command", serde_json::to_value(command), 1),
+            SessionEntry::Event(event) => ("event", serde_json::to_value(event), 2),
+        };
+        let mut path = Vec::new();
+        let mut value = value.unwrap_or_default();
+        while path.len() < depth {
+            match value {
+                serde_json::Value::String(name) => {
+                    path.push(name);
+                    break;
+                }
+                serde_json::Value::Object(map) if map.len() == 1 => {
+                    let Some((name, inner)) = map.into_iter().next() else {
+                        break;
+                    };
+                    path.push(name);
+                    value = inner;
+                }
+                _ => break,
+            }
+        }
+        format!("{} {}", kind, path.join("/"))
+    }
+}
+
+/// Appends entries to a session log as they happen
+pub struct SessionRecorder {
+    path: PathBuf,
+    file: Option<BufWriter<File>>,
+}
+
+impl SessionRecorder {
+    /// Start a new log at `path`, replacing any earlier one
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create session log {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Some(BufWriter::new(file)),
+        })
+    }
+
+    pub fn command(&mut self, command: &AppCommand) {
+        self.write(&EntryRef::Command(command));
+    }
+
+    pub fn event(&mut self, event: &DataEvent) {
+        self.write(&EntryRef::Event(event));
+    }
+
+    /// Flushed after every entry so a crash keeps the steps leading up to it.
+    /// Recording stops at the first failure rather than failing the app.
+    fn write(&mut self, entry: &EntryRef) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        let result = serde_json::to_writer(&mut *file, entry)
+            .map_err(std::io::Error::from)
+            .and_then(|_| file.write_all(b"\n"))
+            .and_then(|_| file.flush());
+        if let Err(e) = result {
+            tracing::warn!(
+                "Stopped recording session to {}: {}",
+                self.path.display(),
+                e
+            );
+            self.file = None;
+        }
+    }
+}
+
+/// Parse a session log, one entry per non-blank line
+pub fn read(log: &str) -> Result<Vec<SessionEntry>> {
+    log.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| anyhow!("Line {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+/// What a replay ended with
+pub struct Replayed {
+    pub state: AppState,
+    /// Steps applied, up to `until` if given
+    pub steps: usize,
+    /// The screen after the last step, as drawn
+    pub screen: String,
+}
+
+/// Apply `entries` in order, stopping after step `until` if given. `on_step`
+/// is called after each one with its number (from 1), the entry and the
+/// resulting state.
+pub async fn replay(
+    entries: &[SessionEntry],
+    until: Option<usize>,
+    mut on_step: impl FnMut(usize, &SessionEntry, &AppState),
+) -> Result<Replayed> {
+    let cache_dir = replay::scratch_cache_dir();
+    let cache = Arc::new(Cache::in_dir(cache_dir.clone()).await?);
+    let (data_tx, mut data_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    // Nothing listens here, so loads fail fast; the recorded events stand in
+    // for their results
+    let client = Client::with_base_url("http://127.0.0.1:9/v1", "replay");
+    let data_loader = DataLoader::new(Arc::new(client), cache, data_tx);
+    let mut task_manager = BackgroundTaskManager::new();
+
+    let mut state = AppState::new();
+    crate::i18n::init(None);
+    crate::ui::components::loading_indicator::set_animated(false);
+    load_initial_data(&mut state, &mut task_manager, &data_loader);
+
+    let mut steps = 0;
+    for (index, entry) in entries.iter().take(until.unwrap_or(usize::MAX)).enumerate() {
+        while data_rx.try_recv().is_ok() {}
+        match entry.clone() {
+            SessionEntry::Command(command) => {
+                executor::execute_command(command, &mut state, &mut task_manager, &data_loader)
+            }
+            SessionEntry::Event(event) => crate::app_core::reduce_data_event(&mut state, event),
+        }
+        steps = index + 1;
+        on_step(steps, entry, &state);
+    }
+    task_manager.cancel_all();
+    if let Err(e) = std::fs::remove_dir_all(&cache_dir) {
+        tracing::warn!("Failed to remove replay cache {:?}: {}", cache_dir, e);
+    }
+
+    let screen = draw(&state)?;
+    Ok(Replayed {
+        state,
+        steps,
+        screen,
+    })
+}
+
+/// The screen for `state` as plain text, one line per terminal row
+fn draw(state: &AppState) -> Result<String> {
+    let (width, height) = replay::TERMINAL_SIZE;
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let log_buffer = LogBuffer::new(0);
+    let api_log = ApiLog::new(0);
+    terminal.draw(|f| crate::ui::render_app(f, state, &log_buffer, &api_log))?;
+
+    let buffer = terminal.backend().buffer();
+    let lines: Vec<String> = (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::BudgetEvent;
+
+    fn log(entries: &[EntryRef]) -> String {
+        entries
+            .iter()
+            .map(|entry| serde_json::to_string(entry).unwrap() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn recorded_entries_read_back_with_their_names() {
+        let log = log(&[
+            EntryRef::Command(&AppCommand::ToggleHelp),
+            EntryRef::Event(&DataEvent::Budget(BudgetEvent::AccountsCacheLoaded {
+                accounts: Vec::new(),
+            })),
+        ]);
+
+        let entries = read(&format!("{log}\n")).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(
+            entries[0],
+            SessionEntry::Command(AppCommand::ToggleHelp)
+        ));
+        assert_eq!(entries[0].name(), "command ToggleHelp");
+        assert_eq!(entries[1].name(), "event Budget/AccountsCacheLoaded");
+        assert!(read("{}\nnot json\n")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Line 1:"));
+    }
+
+    #[tokio::test]
+    async fn replay_stops_at_the_requested_step() {
+        let entries = vec![
+            SessionEntry::Command(AppCommand::ToggleHelp),
+            SessionEntry::Command(AppCommand::ToggleHelp),
+        ];
+        let mut seen = Vec::new();
+
+        let replayed = replay(&entries, Some(1), |step, _, state| {
+            seen.push((step, state.help_visible))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(seen, [(1, true)]);
+        assert_eq!(replayed.steps, 1);
+        assert!(replayed.state.help_visible);
+        assert!(!replayed.screen.is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+//! Local preferences that aren't tied to one budget.
+//!
+//! Stored as `<state dir>/ynat/settings.json`, next to the per-budget files
+//! in [`crate::budget_settings`], and kept up to date as you move around
+//! rather than edited by hand. View preferences (closed accounts, reconciled
+//! transactions, the sort, the Plan view and the last account opened) differ
+//! between budgets, so they're kept in the budget's file instead.
+
+use crate::commands::AppCommand;
+use crate::state::AppState;
+use crate::ui::screens::Screen;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Budget opened on startup (the last one picked), instead of YNAB's default
+    pub last_budget_id: Option<String>,
+    /// Screen open when ynat last quit, reopened with `--resume` or
+    /// `[startup] restore_screen`
+    pub last_screen: Option<LastScreen>,
+}
+
+/// A screen that can be reopened in a new session. The budget is
+/// [`Settings::last_budget_id`]; the accounts list is always underneath.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "screen", rename_all = "snake_case")]
+pub enum LastScreen {
+    Accounts,
+    Transactions { account_id: String },
+    TransactionSearch,
+    Inbox,
+    Plan,
+    NetWorth,
+    Reimbursements,
+    ScheduledTransactions { account_id: Option<String> },
+    PayeeCleanup,
+    Reports,
+}
+
+impl LastScreen {
+    /// The current screen, if it can be reopened. The budgets list,
+    /// settings, logs and inspector aren't worth coming back to.
+    pub fn of(state: &AppState) -> Option<Self> {
+        Some(match state.current_screen() {
+            Screen::Accounts(_) => LastScreen::Accounts,
+            Screen::Transactions(transactions_state) if transactions_state.inbox => {
+                LastScreen::Inbox
+            }
+            Screen::Transactions(transactions_state) if transactions_state.all_accounts => {
+                LastScreen::TransactionSearch
+            }
+            Screen::Transactions(_) => LastScreen::Transactions {
+                account_id: state.current_account_id.clone()?,
+            },
+            Screen::Plan(_) => LastScreen::Plan,
+            Screen::NetWorth(_) => LastScreen::NetWorth,
+            Screen::Reimbursements(_) => LastScreen::Reimbursements,
+            Screen::ScheduledTransactions(scheduled_state) => LastScreen::ScheduledTransactions {
+                account_id: scheduled_state.account_id.clone(),
+            },
+            Screen::PayeeCleanup(_) => LastScreen::PayeeCleanup,
+            Screen::Reports(_) => LastScreen::Reports,
+            Screen::Budgets(_) | Screen::Settings(_) | Screen::Logs(_) | Screen::Inspector(_) => {
+                return None
+            }
+        })
+    }
+
+    /// Command opening this screen over the accounts list; `None` for the
+    /// accounts list itself
+    pub fn command(&self, budget_id: String) -> Option<AppCommand> {
+        Some(match self.clone() {
+            LastScreen::Accounts => return None,
+            LastScreen::Transactions { account_id } => AppCommand::LoadTransactions {
+                budget_id,
+                account_id,
+                force_refresh: false,
+            },
+            LastScreen::TransactionSearch => AppCommand::NavigateToTransactionSearch { budget_id },
+            LastScreen::Inbox => AppCommand::NavigateToInbox { budget_id },
+            LastScreen::Plan => AppCommand::LoadPlan {
+                budget_id,
+                force_refresh: false,
+            },
+            LastScreen::NetWorth => AppCommand::NavigateToNetWorth { budget_id },
+            LastScreen::Reimbursements => AppCommand::NavigateToReimbursements { budget_id },
+            LastScreen::ScheduledTransactions { account_id } => {
+                AppCommand::NavigateToScheduledTransactions {
+                    budget_id,
+                    account_id,
+                }
+            }
+            LastScreen::PayeeCleanup => AppCommand::NavigateToPayeeCleanup { budget_id },
+            LastScreen::Reports => AppCommand::NavigateToReports { budget_id },
+        })
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    let dir = dirs::state_dir().or_else(dirs::data_local_dir)?;
+    Some(dir.join("ynat").join("settings.json"))
+}
+
+/// Saved settings, or the defaults if none were saved or the file is unreadable
+pub async fn load() -> Settings {
+    let Some(path) = settings_path() else {
+        return Settings::default();
+    };
+    match tokio::fs::read_to_string(&path).await {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable settings {:?}: {}", path, e);
+            Settings::default()
+        }),
+        Err(_) => Settings::default(),
+    }
+}
+
+pub async fn save(settings: &Settings) -> std::io::Result<()> {
+    let Some(path) = settings_path() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(std::io::Error::other)?;
+    // Replace the file in one step so an interrupted save leaves the old one intact
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, json).await?;
+    tokio::fs::rename(tmp_path, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_and_missing_fields_are_tolerated() {
+        let settings: Settings = serde_json::from_str(r#"{ "theme": "dark" }"#).unwrap();
+
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn last_screen_reopens_the_same_account() {
+        let mut state = AppState::new();
+        state.current_account_id = Some("a1".to_string());
+        state.navigate_to(Screen::Transactions(Box::default()));
+
+        let last_screen = LastScreen::of(&state).unwrap();
+        let json = serde_json::to_string(&last_screen).unwrap();
+
+        assert_eq!(json, r#"{"screen":"transactions","account_id":"a1"}"#);
+        assert_eq!(
+            serde_json::from_str::<LastScreen>(&json)
+                .unwrap()
+                .command("b1".to_string()),
+            Some(AppCommand::LoadTransactions {
+                budget_id: "b1".to_string(),
+                account_id: "a1".to_string(),
+                force_refresh: false,
+            })
+        );
+        assert_eq!(LastScreen::Accounts.command("b1".to_string()), None);
+    }
+}
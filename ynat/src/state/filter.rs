@@ -0,0 +1,232 @@
+//! Structured queries in the transactions filter.
+//!
+//! Besides plain words, the filter understands a few tokens: `since:` and
+//! `before:` take a date, `>50` or `<-100` bound the amount as shown (so
+//! outflows are negative), and `cleared:` and `flag:` pick a status. Any
+//! number of tokens can be mixed with words, and all of them have to match.
+//! A token that doesn't parse yet, like a date still being typed, is left
+//! out rather than hiding everything.
+
+use chrono::NaiveDate;
+use ynab_api::endpoints::transactions::{FlagColor, ReconciliationStatus, Transaction};
+use ynab_api::endpoints::CurrencyFormat;
+
+use crate::utils::money;
+
+/// A parsed filter string
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterExpr {
+    /// The words that aren't tokens, lowercased, matched against payee,
+    /// category, memo and amount
+    pub text: String,
+    /// On or after this date
+    pub since: Option<NaiveDate>,
+    /// Before this date
+    pub before: Option<NaiveDate>,
+    pub amounts: Vec<AmountBound>,
+    pub cleared: Option<ClearedFilter>,
+    pub flag: Option<FlagFilter>,
+}
+
+/// `>50`, `>=50`, `<-100` or `<=-100`, in milliunits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AmountBound {
+    Above(i64),
+    AtLeast(i64),
+    Below(i64),
+    AtMost(i64),
+}
+
+/// `cleared:yes` (cleared or reconciled), `cleared:no` or `cleared:reconciled`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClearedFilter {
+    Yes,
+    No,
+    Reconciled,
+}
+
+/// `flag:any`, `flag:none` or a color such as `flag:red`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlagFilter {
+    Any,
+    None,
+    Color(FlagColor),
+}
+
+impl FilterExpr {
+    /// Read `query`, with amounts in the budget's `currency` format
+    pub fn parse(query: &str, currency: Option<&CurrencyFormat>) -> Self {
+        let mut expr = Self::default();
+        let mut words = Vec::new();
+        for token in query.split_whitespace() {
+            let lower = token.to_lowercase();
+            if let Some((key, value)) = lower.split_once(':') {
+                match key {
+                    "since" => expr.since = parse_date(value).or(expr.since),
+                    "before" => expr.before = parse_date(value).or(expr.before),
+                    "cleared" => expr.cleared = parse_cleared(value).or(expr.cleared),
+                    "flag" => expr.flag = parse_flag(value).or(expr.flag),
+                    _ => {
+                        words.push(lower);
+                        continue;
+                    }
+                }
+                continue;
+            }
+            if lower.starts_with(['<', '>']) {
+                expr.amounts.extend(parse_amount(&lower, currency));
+                continue;
+            }
+            words.push(lower);
+        }
+        expr.text = words.join(" ");
+        expr
+    }
+
+    /// Whether `transaction` passes every token; the words are left to the
+    /// caller, which knows which fields to look in
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        let amount = i64::from(transaction.amount);
+        self.since.is_none_or(|since| transaction.date >= since)
+            && self.before.is_none_or(|before| transaction.date < before)
+            && self.amounts.iter().all(|bound| bound.matches(amount))
+            && self
+                .cleared
+                .is_none_or(|cleared| cleared.matches(transaction.cleared))
+            && self
+                .flag
+                .is_none_or(|flag| flag.matches(transaction.flag_color))
+    }
+}
+
+impl AmountBound {
+    fn matches(self, amount: i64) -> bool {
+        match self {
+            Self::Above(bound) => amount > bound,
+            Self::AtLeast(bound) => amount >= bound,
+            Self::Below(bound) => amount < bound,
+            Self::AtMost(bound) => amount <= bound,
+        }
+    }
+}
+
+impl ClearedFilter {
+    fn matches(self, status: ReconciliationStatus) -> bool {
+        match self {
+            Self::Yes => status != ReconciliationStatus::Uncleared,
+            Self::No => status == ReconciliationStatus::Uncleared,
+            Self::Reconciled => status == ReconciliationStatus::Reconciled,
+        }
+    }
+}
+
+impl FlagFilter {
+    fn matches(self, flag: Option<FlagColor>) -> bool {
+        match self {
+            Self::Any => flag.is_some(),
+            Self::None => flag.is_none(),
+            Self::Color(color) => flag == Some(color),
+        }
+    }
+}
+
+/// `2024-03-15`, `2024-03` or `2024`, a month or year meaning its first day,
+/// so `before:2024-03` ends with February
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date);
+    }
+    let mut parts = value.splitn(2, '-');
+    let year: i32 = parts.next()?.parse().ok()?;
+    if !(1000..=9999).contains(&year) {
+        return None;
+    }
+    match parts.next() {
+        Some(month) => NaiveDate::from_ymd_opt(year, month.parse().ok()?, 1),
+        None => NaiveDate::from_ymd_opt(year, 1, 1),
+    }
+}
+
+fn parse_amount(token: &str, currency: Option<&CurrencyFormat>) -> Option<AmountBound> {
+    let (bound, amount): (fn(i64) -> AmountBound, &str) =
+        if let Some(amount) = token.strip_prefix(">=") {
+            (AmountBound::AtLeast, amount)
+        } else if let Some(amount) = token.strip_prefix("<=") {
+            (AmountBound::AtMost, amount)
+        } else if let Some(amount) = token.strip_prefix('>') {
+            (AmountBound::Above, amount)
+        } else {
+            (AmountBound::Below, token.strip_prefix('<')?)
+        };
+    // Not the math `parse_input` also allows, so `>5-` doesn't read as 5
+    let amount = amount.trim_start_matches(currency.map_or("", |c| c.currency_symbol.as_str()));
+    if amount.is_empty() || !amount.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+        return None;
+    }
+    money::parse_input(amount, currency).map(bound)
+}
+
+fn parse_cleared(value: &str) -> Option<ClearedFilter> {
+    match value {
+        "yes" | "cleared" => Some(ClearedFilter::Yes),
+        "no" | "uncleared" => Some(ClearedFilter::No),
+        "reconciled" => Some(ClearedFilter::Reconciled),
+        _ => None,
+    }
+}
+
+fn parse_flag(value: &str) -> Option<FlagFilter> {
+    Some(match value {
+        "any" | "yes" => FlagFilter::Any,
+        "none" | "no" => FlagFilter::None,
+        "red" => FlagFilter::Color(FlagColor::Red),
+        "orange" => FlagFilter::Color(FlagColor::Orange),
+        "yellow" => FlagFilter::Color(FlagColor::Yellow),
+        "green" => FlagFilter::Color(FlagColor::Green),
+        "blue" => FlagFilter::Color(FlagColor::Blue),
+        "purple" => FlagFilter::Color(FlagColor::Purple),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn tokens_are_parsed_out_of_the_words() {
+        let expr = FilterExpr::parse(
+            "Whole Foods since:2024-03 before:2024-04-15 <-100 >=-250.5 cleared:no flag:red",
+            None,
+        );
+
+        assert_eq!(
+            expr,
+            FilterExpr {
+                text: "whole foods".to_string(),
+                since: Some(date("2024-03-01")),
+                before: Some(date("2024-04-15")),
+                amounts: vec![AmountBound::Below(-100_000), AmountBound::AtLeast(-250_500)],
+                cleared: Some(ClearedFilter::No),
+                flag: Some(FlagFilter::Color(FlagColor::Red)),
+            }
+        );
+        assert_eq!(parse_date("2024"), Some(date("2024-01-01")));
+    }
+
+    #[test]
+    fn unfinished_tokens_are_left_out() {
+        let expr = FilterExpr::parse("since:2024-1 > <- flag:re note:x", None);
+
+        assert_eq!(expr.since, Some(date("2024-01-01")));
+        assert!(expr.amounts.is_empty());
+        assert_eq!(expr.flag, None);
+        // Unknown keys are plain words
+        assert_eq!(expr.text, "note:x");
+        assert_eq!(FilterExpr::parse("since:20", None).since, None);
+    }
+}
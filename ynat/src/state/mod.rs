@@ -1,21 +1,62 @@
 pub mod autocomplete;
+pub mod filter;
 pub mod reducer;
+pub mod undo;
 pub mod validators;
 
+use crate::api_log::ApiRecord;
+use crate::archive::ArchiveSuggestion;
+use crate::budget_settings::BudgetSettings;
+use crate::category_suggestions::{self, CategorySuggestion};
+use crate::config::{AppConfig, Rounding};
+use crate::config_edit::ConfigEdit;
+use crate::error::AppError;
+use crate::events::AppCommand;
+use crate::health::HealthItem;
+use crate::i18n::t;
+use crate::maps::Place;
+use crate::options::SettingOption;
+use crate::payee_cleanup::{PayeeCluster, PayeeMerge};
+use crate::quick_budget::{self, QuickBudgetAction, QuickBudgetChange};
+use crate::reimbursements::{ReimbursementEntry, ReimbursementMatch};
+use crate::reports::cash_flow::{CashFlowProjection, ProjectionHorizon};
+use crate::reports::heatmap::SpendingHeatmap;
+use crate::reports::income_expense::{IncomeExpense, MonthFlow};
+use crate::reports::net_worth::NetWorth;
+use crate::reports::obligations::ObligationsForecast;
+use crate::reports::spending::{SpendingBreakdown, SpendingGrouping, SpendingRow};
+use crate::reports::variance::{self, VarianceRow, VarianceSort, VarianceTransaction};
+use crate::reports::{ReportKind, ReportPeriod};
+use crate::settings::Settings;
 use crate::ui::screens::Screen;
 use crate::ui::utils as ui_utils;
+use crate::utils;
+use crate::utils::balance_history::RecentSpending;
+use crate::utils::currency::ForeignAmount;
+use crate::utils::money;
+use crate::utils::ynab_export::ImportPreview;
+use chrono::{Datelike, NaiveDate};
 use itertools::Itertools;
 use ratatui::widgets::TableState;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use throbber_widgets_tui::ThrobberState;
+use undo::UndoHistory;
+use uuid::Uuid;
 use ynab_api::endpoints::{
-    accounts::Account,
+    accounts::{Account, AccountType},
     budgets::BudgetSummary,
     categories::Category,
     months::MonthDetail,
     payees::Payee,
+    scheduled_transactions::ScheduledTransaction,
     transactions::{FlagColor, ReconciliationStatus, SubTransaction, Transaction},
+    CurrencyFormat, DateFormat,
 };
+use ynab_api::RateLimit;
 
 /// Represents loading state separate from data state
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -24,7 +65,23 @@ pub enum LoadingState {
     NotStarted,
     Loading(ThrobberState),
     Loaded,
-    Error(String),
+    Error(AppError),
+}
+
+impl LoadingState {
+    /// Record `error` if a load is in progress
+    pub fn fail(&mut self, error: AppError) {
+        if matches!(self, LoadingState::Loading(..)) {
+            *self = LoadingState::Error(error);
+        }
+    }
+
+    /// Mark a load in progress as finished, leaving errors in place
+    pub fn settle(&mut self) {
+        if matches!(self, LoadingState::Loading(..)) {
+            *self = LoadingState::Loaded;
+        }
+    }
 }
 
 /// Represents input mode for screens that support editing
@@ -38,10 +95,63 @@ pub enum InputMode {
     ReconciledEditConfirmation,
     ReconcileConfirmation,
     BudgetEdit,
+    GoalCalculator,
+    ArchiveReview,
+    HealthChecklist,
+    MoveMoney,
+    GoalForm,
+    QuickBudget,
+    /// Typing the start of an account name to move the cursor to it
+    AccountJump,
+}
+
+/// Kinds of account, grouped the way YNAB's sidebar does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountGroup {
+    /// Checking, savings and cash: the budget's own money
+    Cash,
+    Credit,
+    Loans,
+    Tracking,
+}
+
+impl AccountGroup {
+    pub fn of(account_type: AccountType) -> Self {
+        use AccountType::*;
+        match account_type {
+            Checking | Savings | Cash => AccountGroup::Cash,
+            CreditCard | LineOfCredit => AccountGroup::Credit,
+            Mortgage | AutoLoan | StudentLoan | PersonalLoan | MedicalDebt | OtherDebt => {
+                AccountGroup::Loans
+            }
+            OtherAsset | OtherLiability => AccountGroup::Tracking,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccountGroup::Cash => t("account-group-cash"),
+            AccountGroup::Credit => t("account-group-credit"),
+            AccountGroup::Loans => t("account-group-loans"),
+            AccountGroup::Tracking => t("account-group-tracking"),
+        }
+    }
+
+    /// The next group to list, back to every account after the last
+    pub fn cycle(filter: Option<Self>) -> Option<Self> {
+        match filter {
+            None => Some(AccountGroup::Cash),
+            Some(AccountGroup::Cash) => Some(AccountGroup::Credit),
+            Some(AccountGroup::Credit) => Some(AccountGroup::Loans),
+            Some(AccountGroup::Loans) => Some(AccountGroup::Tracking),
+            Some(AccountGroup::Tracking) => None,
+        }
+    }
 }
 
 /// Focused view filter for Plan screen categories
-#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PlanFocusedView {
     #[default]
     All,
@@ -66,11 +176,168 @@ impl PlanFocusedView {
     /// Display name for the view
     pub fn display_name(&self) -> &'static str {
         match self {
-            Self::All => "All",
-            Self::Snoozed => "Snoozed",
-            Self::Underfunded => "Underfunded",
-            Self::Overfunded => "Overfunded",
-            Self::MoneyAvailable => "Money Available",
+            Self::All => t("plan-view-all"),
+            Self::Snoozed => t("plan-view-snoozed"),
+            Self::Underfunded => t("plan-view-underfunded"),
+            Self::Overfunded => t("plan-view-overfunded"),
+            Self::MoneyAvailable => t("plan-view-money-available"),
+        }
+    }
+}
+
+/// Quick filter toggled with the number keys on the transactions screen.
+///
+/// Active presets are ANDed with each other and with the text filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionPreset {
+    Unapproved,
+    Uncleared,
+    ThisMonth,
+    Flagged,
+    Splits,
+    /// Came in from the bank rather than being entered by hand
+    Imported,
+}
+
+impl TransactionPreset {
+    /// Every preset, in key order (`1` first)
+    pub const ALL: [Self; 6] = [
+        Self::Unapproved,
+        Self::Uncleared,
+        Self::ThisMonth,
+        Self::Flagged,
+        Self::Splits,
+        Self::Imported,
+    ];
+
+    /// Preset bound to a number key
+    pub fn from_key(c: char) -> Option<Self> {
+        let index = c.to_digit(10)?.checked_sub(1)?;
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// Number key that toggles the preset
+    pub fn key(&self) -> char {
+        let index = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+        char::from(b'1' + index as u8)
+    }
+
+    pub fn matches(&self, transaction: &Transaction, today: NaiveDate) -> bool {
+        match self {
+            Self::Unapproved => !transaction.approved,
+            Self::Uncleared => transaction.cleared == ReconciliationStatus::Uncleared,
+            Self::ThisMonth => {
+                transaction.date.year() == today.year() && transaction.date.month() == today.month()
+            }
+            Self::Flagged => transaction.flag_color.is_some(),
+            Self::Splits => !transaction.subtransactions.is_empty(),
+            Self::Imported => transaction.import_id.is_some(),
+        }
+    }
+}
+
+/// Whether a transaction still belongs in the review inbox: not approved, or
+/// with no category. Transfers and splits count as categorized.
+pub fn needs_review(transaction: &Transaction) -> bool {
+    let uncategorized = transaction.transfer_account_id.is_none()
+        && transaction.subtransactions.is_empty()
+        && (transaction.category_id.is_none()
+            || transaction.category_name.as_deref() == Some("Uncategorized"));
+    !transaction.approved || uncategorized
+}
+
+/// Column the transactions table is ordered by
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionSortColumn {
+    #[default]
+    Date,
+    Amount,
+    Payee,
+    Category,
+    Cleared,
+}
+
+impl TransactionSortColumn {
+    /// Cycle to the next column
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Date => Self::Amount,
+            Self::Amount => Self::Payee,
+            Self::Payee => Self::Category,
+            Self::Category => Self::Cleared,
+            Self::Cleared => Self::Date,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Amount => "amount",
+            Self::Payee => "payee",
+            Self::Category => "category",
+            Self::Cleared => "cleared",
+        }
+    }
+}
+
+/// Order of the transactions table. Each column has a natural direction
+/// (newest, largest outflow, A to Z, uncleared first) that `reversed` flips.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransactionSort {
+    pub column: TransactionSortColumn,
+    pub reversed: bool,
+}
+
+impl TransactionSort {
+    /// The next column, in its natural direction
+    pub fn next_column(self) -> Self {
+        Self {
+            column: self.column.next(),
+            reversed: false,
+        }
+    }
+
+    /// The same column the other way round
+    pub fn reversed(self) -> Self {
+        Self {
+            reversed: !self.reversed,
+            ..self
+        }
+    }
+
+    /// Compare two transactions; ties fall back to newest first
+    pub fn compare(&self, a: &Transaction, b: &Transaction) -> std::cmp::Ordering {
+        fn text(name: Option<&str>) -> (bool, String) {
+            // Missing names sort last
+            (name.is_none(), name.unwrap_or_default().to_lowercase())
+        }
+        fn cleared_rank(status: ReconciliationStatus) -> u8 {
+            match status {
+                ReconciliationStatus::Uncleared => 0,
+                ReconciliationStatus::Cleared => 1,
+                ReconciliationStatus::Reconciled => 2,
+            }
+        }
+
+        let ordering = match self.column {
+            TransactionSortColumn::Date => std::cmp::Ordering::Equal,
+            TransactionSortColumn::Amount => a.amount.cmp(&b.amount),
+            TransactionSortColumn::Payee => {
+                text(a.payee_name.as_deref()).cmp(&text(b.payee_name.as_deref()))
+            }
+            TransactionSortColumn::Category => {
+                text(a.category_name.as_deref()).cmp(&text(b.category_name.as_deref()))
+            }
+            TransactionSortColumn::Cleared => cleared_rank(a.cleared).cmp(&cleared_rank(b.cleared)),
+        }
+        .then_with(|| a.cmp(b));
+
+        if self.reversed {
+            ordering.reverse()
+        } else {
+            ordering
         }
     }
 }
@@ -117,9 +384,9 @@ impl SubTransactionFormState {
         }
     }
 
-    pub fn from_subtransaction(sub: &SubTransaction) -> Self {
+    pub fn from_subtransaction(sub: &SubTransaction, currency: Option<&CurrencyFormat>) -> Self {
         Self {
-            amount: format!("{:.2}", sub.amount.as_f64() / 1000.0),
+            amount: money::format_input(sub.amount.into(), currency),
             category: sub.category_name.clone().unwrap_or_default(),
             memo: sub.memo.clone().unwrap_or_default(),
             filtered_categories: Vec::new(),
@@ -157,13 +424,43 @@ pub struct TransactionFormState {
     pub editing_transaction_id: Option<String>,
 
     // Validation errors
-    pub validation_error: Option<String>,
+    pub validation_error: Option<AppError>,
 
     // Split mode state
     pub is_split_mode: bool,
     pub subtransactions: Vec<SubTransactionFormState>,
     pub active_subtransaction_index: Option<usize>,
     pub subtransaction_field: SubTransactionField,
+
+    // Rounding for entered amounts (new transactions only, so edits keep exact amounts)
+    pub rounding: Rounding,
+
+    // Budget's currency, for prefilled amounts and amounts quoted in errors
+    pub currency_format: Option<CurrencyFormat>,
+
+    // Values from the last transaction entered in this account, for Ctrl+P
+    pub last_used: Option<LastUsedEntry>,
+
+    // Lint warnings already shown; submitting with the same ones saves anyway
+    pub acknowledged_warnings: Vec<String>,
+
+    // Asking for the exchange rate of a foreign amount typed as "=45 EUR"
+    pub rate_prompt: Option<RatePrompt>,
+}
+
+/// Exchange rate being typed for a foreign amount with no configured rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatePrompt {
+    pub amount: ForeignAmount,
+    pub rate: String,
+}
+
+/// Payee, category and cleared status of the last transaction entered in an account
+#[derive(Debug, Clone, PartialEq)]
+pub struct LastUsedEntry {
+    pub payee: String,
+    pub category: String,
+    pub cleared: ReconciliationStatus,
 }
 
 impl TransactionFormState {
@@ -192,10 +489,19 @@ impl TransactionFormState {
             subtransactions: Vec::new(),
             active_subtransaction_index: None,
             subtransaction_field: SubTransactionField::default(),
+            rounding: Rounding::default(),
+            currency_format: None,
+            last_used: None,
+            acknowledged_warnings: Vec::new(),
+            rate_prompt: None,
         }
     }
 
-    pub fn from_transaction(transaction: &Transaction, date_format: &str) -> Self {
+    pub fn from_transaction(
+        transaction: &Transaction,
+        date_format: &str,
+        currency: Option<&CurrencyFormat>,
+    ) -> Self {
         // Convert the transaction's ISO date to user's preferred format
         let date_iso = transaction.date.format("%Y-%m-%d").to_string();
         let date = ui_utils::fmt_date_with_format(&date_iso, date_format);
@@ -205,14 +511,14 @@ impl TransactionFormState {
         let subtransactions: Vec<SubTransactionFormState> = transaction
             .subtransactions
             .iter()
-            .map(SubTransactionFormState::from_subtransaction)
+            .map(|sub| SubTransactionFormState::from_subtransaction(sub, currency))
             .collect();
 
         Self {
             account_id: transaction.account_id.to_string(),
             current_field: Some(FormField::Date),
             date,
-            amount: format!("{:.2}", transaction.amount.as_f64() / 1000.0),
+            amount: money::format_input(transaction.amount.into(), currency),
             payee: transaction.payee_name.clone().unwrap_or_default(),
             category: if is_split {
                 String::new() // Category is shown as "Split (N)" in UI
@@ -232,6 +538,28 @@ impl TransactionFormState {
             subtransactions,
             active_subtransaction_index: None,
             subtransaction_field: SubTransactionField::default(),
+            rounding: Rounding::default(),
+            currency_format: currency.cloned(),
+            last_used: None,
+            acknowledged_warnings: Vec::new(),
+            rate_prompt: None,
+        }
+    }
+
+    /// Create form pre-filled from `transaction`, dated today and uncleared
+    pub fn duplicate_of(
+        transaction: &Transaction,
+        account_id: String,
+        date_format: &str,
+        currency: Option<&CurrencyFormat>,
+    ) -> Self {
+        let today = Self::new(account_id.clone(), date_format).date;
+        Self {
+            account_id,
+            date: today,
+            cleared: ReconciliationStatus::Uncleared,
+            editing_transaction_id: None,
+            ..Self::from_transaction(transaction, date_format, currency)
         }
     }
 
@@ -239,6 +567,19 @@ impl TransactionFormState {
         self.editing_transaction_id.is_some()
     }
 
+    /// Amount of a split not yet given to a subtransaction, in milliunits;
+    /// amounts that can't be read count as zero
+    pub fn split_remaining(&self) -> i64 {
+        let currency = self.currency_format.as_ref();
+        let parse = |amount: &str| money::parse_input(amount, currency).unwrap_or(0);
+        parse(&self.amount)
+            - self
+                .subtransactions
+                .iter()
+                .map(|s| parse(&s.amount))
+                .sum::<i64>()
+    }
+
     pub fn is_last_field_focused(&self) -> bool {
         self.current_field == Some(FormField::Cleared)
     }
@@ -259,21 +600,371 @@ pub struct BudgetFormState {
     pub category_name: String,
     pub budgeted_input: String, // User input as string (supports math expressions)
     pub original_budgeted: i64, // For cancel/rollback
-    pub validation_error: Option<String>,
+    pub validation_error: Option<AppError>,
+    pub currency_format: Option<CurrencyFormat>,
 }
 
 impl BudgetFormState {
-    pub fn new(category_id: String, category_name: String, current_budgeted: i64) -> Self {
+    pub fn new(
+        category_id: String,
+        category_name: String,
+        current_budgeted: i64,
+        currency: Option<&CurrencyFormat>,
+    ) -> Self {
         Self {
             category_id,
             category_name,
-            budgeted_input: format!("{:.2}", current_budgeted as f64 / 1000.0),
+            budgeted_input: money::format_input(current_budgeted, currency),
             original_budgeted: current_budgeted,
             validation_error: None,
+            currency_format: currency.cloned(),
+        }
+    }
+}
+
+/// Which field of the move money form has focus
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum MoveMoneyField {
+    #[default]
+    Amount,
+    Destination,
+}
+
+/// State for the "move money" popup on the Plan screen
+#[derive(Debug, Clone)]
+pub struct MoveMoneyState {
+    pub source_id: String,
+    pub source_name: String,
+    /// Source category's available balance, shown as the most that can be moved
+    pub available: i64,
+    pub field: MoveMoneyField,
+    pub amount_input: String, // Supports math expressions like the budget form
+    pub destination_input: String,
+    /// Categories matching `destination_input`, never the source
+    pub destinations: Vec<Category>,
+    pub selected_destination: usize,
+    pub validation_error: Option<AppError>,
+    pub currency_format: Option<CurrencyFormat>,
+}
+
+impl MoveMoneyState {
+    /// Start with the whole available balance (if any) as the amount
+    pub fn new(
+        source: &Category,
+        categories: &[Category],
+        currency: Option<&CurrencyFormat>,
+    ) -> Self {
+        let available: i64 = source.balance.into();
+        let mut state = Self {
+            source_id: source.id.to_string(),
+            source_name: source.name.clone(),
+            available,
+            field: MoveMoneyField::Amount,
+            amount_input: if available > 0 {
+                money::format_input(available, currency)
+            } else {
+                String::new()
+            },
+            destination_input: String::new(),
+            destinations: Vec::new(),
+            selected_destination: 0,
+            validation_error: None,
+            currency_format: currency.cloned(),
+        };
+        state.update_destinations(categories);
+        state
+    }
+
+    /// Re-run the destination autocomplete after the query changed
+    pub fn update_destinations(&mut self, categories: &[Category]) {
+        let candidates: Vec<Category> = categories
+            .iter()
+            .filter(|c| !c.hidden && !c.deleted && c.id.to_string() != self.source_id)
+            .cloned()
+            .collect();
+        self.destinations = autocomplete::filter_categories(&candidates, &self.destination_input);
+        self.selected_destination = 0;
+    }
+
+    pub fn focused_input_mut(&mut self) -> &mut String {
+        match self.field {
+            MoveMoneyField::Amount => &mut self.amount_input,
+            MoveMoneyField::Destination => &mut self.destination_input,
+        }
+    }
+
+    /// The amount entered, in milliunits
+    pub fn amount(&self) -> Option<i64> {
+        money::parse_input(&self.amount_input, self.currency_format.as_ref())
+    }
+
+    pub fn destination(&self) -> Option<&Category> {
+        self.destinations.get(self.selected_destination)
+    }
+}
+
+/// Which question the goal calculator is answering
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum GoalCalculatorField {
+    /// "If I budget X per month, when do I hit the target?"
+    #[default]
+    Monthly,
+    /// "How much per month to hit the target by this month?"
+    TargetMonth,
+}
+
+/// State for the savings goal "what-if" popup on the Plan screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalCalculatorState {
+    pub category_name: String,
+    pub goal_target: i64,
+    pub balance: i64,
+    /// First day of the month being viewed; budgeting starts here
+    pub from_month: NaiveDate,
+    pub field: GoalCalculatorField,
+    pub monthly_input: String, // Supports math expressions like the budget form
+    pub target_month_input: String, // YYYY-MM
+    pub currency_format: Option<CurrencyFormat>,
+}
+
+impl GoalCalculatorState {
+    /// Start from the category's current budgeted amount and goal date, if any
+    pub fn new(
+        category: &Category,
+        from_month: NaiveDate,
+        currency: Option<&CurrencyFormat>,
+    ) -> Self {
+        let budgeted: i64 = category.budgeted.into();
+        Self {
+            category_name: category.name.clone(),
+            goal_target: category.goal_target.map(Into::into).unwrap_or_default(),
+            balance: category.balance.into(),
+            from_month,
+            field: GoalCalculatorField::Monthly,
+            monthly_input: money::format_input(budgeted, currency),
+            target_month_input: category
+                .goal_target_month
+                .as_deref()
+                .and_then(utils::goals::parse_month)
+                .map(|d| d.format("%Y-%m").to_string())
+                .unwrap_or_default(),
+            currency_format: currency.cloned(),
+        }
+    }
+
+    /// Amount still needed to reach the target
+    pub fn remaining(&self) -> i64 {
+        (self.goal_target - self.balance).max(0)
+    }
+
+    /// The monthly amount entered, in milliunits
+    pub fn monthly_amount(&self) -> Option<i64> {
+        money::parse_input(&self.monthly_input, self.currency_format.as_ref())
+    }
+
+    /// Month the target is reached at the entered monthly amount
+    pub fn projected_month(&self) -> Option<NaiveDate> {
+        utils::goals::target_reached_month(
+            self.from_month,
+            self.remaining(),
+            self.monthly_amount()?,
+        )
+    }
+
+    /// Monthly amount needed to hit the target by the entered month
+    pub fn required_monthly(&self) -> Option<i64> {
+        let target_month = utils::goals::parse_month(&self.target_month_input)?;
+        utils::goals::monthly_needed(self.remaining(), self.from_month, target_month)
+    }
+
+    /// The input belonging to the focused field
+    pub fn focused_input_mut(&mut self) -> &mut String {
+        match self.field {
+            GoalCalculatorField::Monthly => &mut self.monthly_input,
+            GoalCalculatorField::TargetMonth => &mut self.target_month_input,
+        }
+    }
+}
+
+/// Which field of the goal form has focus
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum GoalFormField {
+    #[default]
+    Target,
+    TargetMonth,
+}
+
+/// State for editing a category's goal target on the Plan screen. YNAB only
+/// lets the target amount and date change; the goal type stays as it is.
+#[derive(Debug, Clone)]
+pub struct GoalFormState {
+    pub category_id: String,
+    pub category_name: String,
+    pub goal_type: String,
+    pub field: GoalFormField,
+    pub target_input: String, // Supports math expressions like the budget form
+    pub target_month_input: String, // YYYY-MM, empty for no date
+    pub validation_error: Option<AppError>,
+    pub currency_format: Option<CurrencyFormat>,
+}
+
+impl GoalFormState {
+    /// Start from the category's current goal; `None` if it has no goal
+    pub fn new(category: &Category, currency: Option<&CurrencyFormat>) -> Option<Self> {
+        let goal_type = category.goal_type.clone()?;
+        Some(Self {
+            category_id: category.id.to_string(),
+            category_name: category.name.clone(),
+            goal_type,
+            field: GoalFormField::Target,
+            target_input: category
+                .goal_target
+                .map(|target| money::format_input(target.into(), currency))
+                .unwrap_or_default(),
+            target_month_input: category
+                .goal_target_month
+                .as_deref()
+                .and_then(utils::goals::parse_month)
+                .map(|d| d.format("%Y-%m").to_string())
+                .unwrap_or_default(),
+            validation_error: None,
+            currency_format: currency.cloned(),
+        })
+    }
+
+    pub fn focused_input_mut(&mut self) -> &mut String {
+        match self.field {
+            GoalFormField::Target => &mut self.target_input,
+            GoalFormField::TargetMonth => &mut self.target_month_input,
+        }
+    }
+
+    /// The target entered, in milliunits
+    pub fn target(&self) -> Option<i64> {
+        money::parse_input(&self.target_input, self.currency_format.as_ref())
+    }
+
+    /// The target month entered: `Some(None)` when left empty, `None` when
+    /// it isn't a month
+    pub fn target_month(&self) -> Option<Option<NaiveDate>> {
+        let input = self.target_month_input.trim();
+        if input.is_empty() {
+            return Some(None);
+        }
+        utils::goals::parse_month(input).map(Some)
+    }
+}
+
+/// Review list for hiding categories that have gone unused
+#[derive(Default, Debug, Clone)]
+pub struct ArchiveReviewState {
+    pub suggestions: Vec<ArchiveSuggestion>,
+    /// Parallel to `suggestions`; unticked categories are left alone
+    pub included: Vec<bool>,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+}
+
+impl ArchiveReviewState {
+    pub fn toggle_selected(&mut self) {
+        let selected = self.table_state.borrow().selected();
+        if let Some(included) = selected.and_then(|i| self.included.get_mut(i)) {
+            *included = !*included;
+        }
+    }
+
+    /// Ids of the categories that will be hidden
+    pub fn included_ids(&self) -> Vec<String> {
+        self.suggestions
+            .iter()
+            .zip(&self.included)
+            .filter(|(_, included)| **included)
+            .map(|(s, _)| s.category_id.clone())
+            .collect()
+    }
+}
+
+/// Confirmation of the amounts a quick-budget command would assign
+#[derive(Debug, Clone)]
+pub struct QuickBudgetState {
+    pub action: QuickBudgetAction,
+    /// Categories the command was run on, in plan order
+    pub category_ids: Vec<String>,
+    /// Plan month the amounts are assigned in
+    pub month: String,
+    pub changes: Vec<QuickBudgetChange>,
+    /// Ready to Assign before the changes
+    pub to_be_budgeted: i64,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+}
+
+/// Budget health checklist shown over the plan
+#[derive(Default, Debug, Clone)]
+pub struct HealthChecklistState {
+    pub items: Vec<HealthItem>,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+}
+
+impl HealthChecklistState {
+    pub fn selected_item(&self) -> Option<&HealthItem> {
+        let selected = self.table_state.borrow().selected()?;
+        self.items.get(selected)
+    }
+}
+
+/// An action offered in the per-row context menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextMenuItem {
+    pub label: &'static str,
+    /// Key that runs the same action outside the menu, if there is one
+    pub shortcut: Option<&'static str>,
+    pub command: AppCommand,
+}
+
+/// Actions available for the selected row
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ContextMenuState {
+    pub title: String,
+    pub items: Vec<ContextMenuItem>,
+    pub selected: usize,
+}
+
+impl ContextMenuState {
+    pub fn move_cursor(&mut self, down: bool) {
+        if self.items.is_empty() {
+            return;
         }
+        self.selected = if down {
+            (self.selected + 1) % self.items.len()
+        } else {
+            (self.selected + self.items.len() - 1) % self.items.len()
+        };
+    }
+
+    pub fn selected_command(&self) -> Option<&AppCommand> {
+        self.items.get(self.selected).map(|item| &item.command)
     }
 }
 
+/// A transaction in the detail popup
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionDetail {
+    pub transaction: Transaction,
+    pub location: PayeeLocationLookup,
+}
+
+/// Where the detail popup's payee is, as far as YNAB knows
+#[derive(Debug, Clone, PartialEq)]
+pub enum PayeeLocationLookup {
+    Loading,
+    Found(Place),
+    /// No location recorded, or the transaction has no payee
+    Missing,
+    Failed(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub history: Vec<Screen>,
@@ -282,10 +973,51 @@ pub struct AppState {
     pub current_budget_id: Option<String>,
     pub current_budget: Option<BudgetSummary>,
     pub current_account_id: Option<String>,
+    /// YNAB user the token belongs to, if it could be checked on startup
+    pub user_id: Option<String>,
+    /// Local preferences for the current budget
+    pub budget_settings: BudgetSettings,
+    /// Local preferences shared by all budgets
+    pub settings: Settings,
 
     // UI state
     pub help_visible: bool,
     pub pending_key: Option<char>,
+    /// Count typed before a movement key, as in `5j`
+    pub pending_count: Option<usize>,
+    /// Table rows that fit on screen, for Ctrl+D and Ctrl+U. Kept up to date
+    /// by the app from the terminal size.
+    pub table_rows: usize,
+    pub context_menu: Option<ContextMenuState>,
+    /// Transaction copied with `yy`, pasted as a new one with `p`
+    pub yanked_transaction: Option<Transaction>,
+    /// One-off message over the help bar, dismissed by the next key press
+    pub notice: Option<String>,
+    /// Where the last CSV export was written, shown until the next key press
+    pub export_path: Option<PathBuf>,
+    /// A YNAB export waiting for `y` before it's imported
+    pub import_preview: Option<ImportPreview>,
+    /// Transaction shown in full with its payee's location, opened with `L`
+    pub transaction_detail: Option<TransactionDetail>,
+    /// Deletes, budget edits and cleared toggles that `u` can take back
+    pub undo_history: UndoHistory,
+    /// Last values entered per account id, offered by Ctrl+P in the new-transaction form
+    pub last_used_entries: HashMap<String, LastUsedEntry>,
+    /// Exchange rates entered at the form's rate prompt, by ISO code
+    pub entered_rates: HashMap<String, f64>,
+    /// Changes made offline that haven't reached YNAB yet
+    pub pending_writes: usize,
+    /// YNAB's hourly quota, while it's nearly used up
+    pub rate_limit_warning: Option<RateLimit>,
+    /// The token can't change anything, or `[api] read_only` is set
+    pub read_only: bool,
+    /// Spent today and this week in the current budget, shown in the title row
+    pub recent_spending: Option<RecentSpending>,
+    /// Changed on the settings screen and not yet written to `config.toml`
+    pub config_edits: Vec<ConfigEdit>,
+
+    // User configuration
+    pub config: AppConfig,
 
     // System
     pub should_quit: bool,
@@ -299,9 +1031,31 @@ impl AppState {
             current_budget_id: None,
             current_budget: None,
             current_account_id: None,
+            user_id: None,
+            budget_settings: BudgetSettings::default(),
+            settings: Settings::default(),
 
             help_visible: false,
             pending_key: None,
+            pending_count: None,
+            // An 80x24 terminal until the first draw
+            table_rows: crate::ui::layouts::table_rows(24),
+            context_menu: None,
+            yanked_transaction: None,
+            notice: None,
+            export_path: None,
+            import_preview: None,
+            transaction_detail: None,
+            undo_history: UndoHistory::default(),
+            last_used_entries: HashMap::new(),
+            entered_rates: HashMap::new(),
+            pending_writes: 0,
+            rate_limit_warning: None,
+            read_only: false,
+            recent_spending: None,
+            config_edits: Vec::new(),
+
+            config: AppConfig::default(),
 
             should_quit: false,
         }
@@ -314,6 +1068,47 @@ impl AppState {
             .expect("Navigation stack should never be empty")
     }
 
+    /// The current budget's currency, if known
+    pub fn currency_format(&self) -> Option<CurrencyFormat> {
+        self.current_budget
+            .as_ref()
+            .and_then(|b| b.currency_format.clone())
+    }
+
+    /// `[display] date_format` if set, else the budget's
+    pub fn date_format(&self) -> Option<DateFormat> {
+        match &self.config.display.date_format {
+            Some(format) => Some(DateFormat {
+                format: format.clone(),
+            }),
+            None => self
+                .current_budget
+                .as_ref()
+                .and_then(|b| b.date_format.clone()),
+        }
+    }
+
+    /// The current budget as drawn, with `[display] date_format` in place of
+    /// its own
+    pub fn display_budget(&self) -> Option<Cow<'_, BudgetSummary>> {
+        let budget = self.current_budget.as_ref()?;
+        if self.config.display.date_format.is_none() {
+            return Some(Cow::Borrowed(budget));
+        }
+        Some(Cow::Owned(BudgetSummary {
+            date_format: self.date_format(),
+            ..budget.clone()
+        }))
+    }
+
+    /// Rate for a foreign currency: one entered this session, else the configured one
+    pub fn exchange_rate(&self, currency: &str) -> Option<f64> {
+        self.entered_rates
+            .get(currency)
+            .copied()
+            .or_else(|| self.config.amounts.rate(currency))
+    }
+
     /// Get mutable reference to current screen
     pub fn current_screen_mut(&mut self) -> &mut Screen {
         self.history
@@ -340,7 +1135,12 @@ impl AppState {
                 self.history.len(),
                 self.history.len() - 1
             );
-            self.history.pop();
+            if let Some(Screen::Transactions(transactions_state)) = self.history.pop() {
+                // Leaving a jumped-to transfer account returns to the account we came from
+                if let Some(account_id) = transactions_state.return_account_id {
+                    self.current_account_id = Some(account_id);
+                }
+            }
             true
         } else {
             tracing::debug!("Cannot navigate back, already at root screen");
@@ -349,32 +1149,22 @@ impl AppState {
     }
 
     pub fn loading_state(&mut self) -> Option<&mut ThrobberState> {
-        match self.current_screen_mut() {
-            Screen::Plan(state) => {
-                if let LoadingState::Loading(ref mut throbber_state) = state.plan_loading {
-                    return Some(throbber_state);
-                }
-            }
-            Screen::Accounts(state) => {
-                if let LoadingState::Loading(ref mut throbber_state) = state.accounts_loading {
-                    return Some(throbber_state);
-                }
-            }
-            Screen::Transactions(state) => {
-                if let LoadingState::Loading(ref mut throbber_state) = state.transactions_loading {
-                    return Some(throbber_state);
-                }
-            }
-            Screen::Budgets(state) => {
-                if let LoadingState::Loading(ref mut throbber_state) = state.budgets_loading {
-                    return Some(throbber_state);
-                }
-            }
-            Screen::Logs(_) => {
-                // Logs screen has no loading state
-            }
+        match self
+            .current_screen_mut()
+            .controller_mut()
+            .loading_state_mut()
+        {
+            Some(LoadingState::Loading(throbber_state)) => Some(throbber_state),
+            _ => None,
+        }
+    }
+
+    /// Why the current screen failed to load, if it did
+    pub fn current_error(&self) -> Option<&AppError> {
+        match self.current_screen().controller().loading_state() {
+            Some(LoadingState::Error(error)) => Some(error),
+            _ => None,
         }
-        None
     }
 }
 
@@ -399,6 +1189,20 @@ pub struct AccountsState {
     pub input_mode: InputMode,
     pub filter_query: String,
     pub show_closed_accounts: bool,
+    /// Daily balances (oldest first) keyed by account id, for the sparkline column
+    pub balance_history: HashMap<String, Vec<i64>>,
+    /// Net change so far this month keyed by account id, from cached transactions
+    pub month_changes: HashMap<String, i64>,
+    /// Budget's currency, so the filter matches balances as they're shown
+    pub currency_format: Option<CurrencyFormat>,
+    /// Accounts due for reconciling were listed when the budget opened
+    pub reconcile_reminded: bool,
+    /// Starred account ids, from the budget settings
+    pub favorite_ids: Vec<String>,
+    /// Only list this kind of account, cycled with `t`
+    pub type_filter: Option<AccountGroup>,
+    /// What's been typed since `f`, to jump to the account it names
+    pub jump_query: String,
 }
 
 #[derive(Debug, Clone)]
@@ -406,15 +1210,32 @@ pub struct TransactionsState {
     pub accounts: Vec<Account>,
     pub transactions: Vec<Transaction>,
     pub transactions_loading: LoadingState,
+    /// Earliest date loaded so far; `None` once the whole history is loaded
+    pub loaded_since: Option<NaiveDate>,
+    /// Search results from every account in the budget rather than one account
+    pub all_accounts: bool,
+    /// Only list transactions dated this day, as picked on the spending heatmap
+    pub on_date: Option<NaiveDate>,
+    /// Only list transactions with this flag, cycled with `f` for triage
+    pub flag_view: Option<FlagColor>,
+    /// The review inbox: every unapproved or uncategorized transaction in the
+    /// budget, kept listed after review so progress can be counted
+    pub inbox: bool,
     pub table_state: RefCell<TableState>,
     pub input_mode: InputMode,
     pub filter_query: String,
     pub show_reconciled_transactions: bool,
+    /// Quick filters toggled with `1`-`6`, in the order they were switched on
+    pub presets: Vec<TransactionPreset>,
+    pub sort: TransactionSort,
 
     // Transaction creation form
     pub form_state: Option<TransactionFormState>,
     pub payees: Vec<Payee>,
     pub categories: Vec<Category>,
+    /// This month's available balance by category id, from the plan, shown
+    /// above the list for the selected transaction
+    pub category_balances: HashMap<String, i64>,
 
     // Transaction deletion confirmation
     pub delete_confirmation_transaction_id: Option<String>,
@@ -424,6 +1245,82 @@ pub struct TransactionsState {
 
     // Reconciliation confirmation
     pub reconcile_cleared_balance: Option<i64>,
+
+    // Ids of transactions flagged as awaiting reimbursement
+    pub awaiting_reimbursement: HashSet<String>,
+    /// Each payee's most used category, by payee id, offered for new
+    /// uncategorized transactions
+    pub category_suggestions: HashMap<Uuid, CategorySuggestion>,
+
+    // Transaction to select once loaded (after jumping to a transfer)
+    pub focus_transaction_id: Option<String>,
+    // Account to make current again when navigating back from a jump
+    pub return_account_id: Option<String>,
+
+    /// The account's balance from the accounts list, when the screen was opened
+    pub account_balance: Option<i64>,
+    /// The account's cleared balance from the accounts list, for reconciling
+    /// against when only recent history is loaded
+    pub account_cleared_balance: Option<i64>,
+    /// Balance before the oldest loaded transaction; `None` if unknown, in
+    /// which case no running balances are shown
+    pub opening_balance: Option<i64>,
+
+    /// Transactions with a change sent to YNAB that hasn't been confirmed yet
+    pub unsaved: HashSet<String>,
+    /// Server changes held back because the row has a local change too,
+    /// oldest first; the first one is shown
+    pub conflicts: Vec<DeltaConflict>,
+
+    /// Budget's currency, so the filter matches amounts as they're shown
+    pub currency_format: Option<CurrencyFormat>,
+}
+
+/// A field of a transaction that can differ between two versions of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionField {
+    Date,
+    Amount,
+    Payee,
+    Category,
+    Memo,
+    Cleared,
+    Approved,
+    Flag,
+}
+
+/// A server change to a transaction that also has a local change
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaConflict {
+    /// The row as it is on screen
+    pub local: Transaction,
+    /// The version from the delta update
+    pub server: Transaction,
+    pub show_diff: bool,
+}
+
+impl DeltaConflict {
+    /// Fields the two versions disagree on
+    pub fn differing_fields(&self) -> Vec<TransactionField> {
+        let (a, b) = (&self.local, &self.server);
+        [
+            (TransactionField::Date, a.date != b.date),
+            (TransactionField::Amount, a.amount != b.amount),
+            (TransactionField::Payee, a.payee_name != b.payee_name),
+            (
+                TransactionField::Category,
+                a.category_name != b.category_name,
+            ),
+            (TransactionField::Memo, a.memo != b.memo),
+            (TransactionField::Cleared, a.cleared != b.cleared),
+            (TransactionField::Approved, a.approved != b.approved),
+            (TransactionField::Flag, a.flag_color != b.flag_color),
+        ]
+        .into_iter()
+        .filter(|(_, differs)| *differs)
+        .map(|(field, _)| field)
+        .collect()
+    }
 }
 
 impl Default for TransactionsState {
@@ -432,16 +1329,34 @@ impl Default for TransactionsState {
             accounts: Vec::default(),
             transactions: Vec::default(),
             transactions_loading: LoadingState::default(),
+            loaded_since: Option::default(),
+            all_accounts: false,
+            on_date: None,
+            flag_view: None,
+            inbox: false,
             table_state: RefCell::default(),
             input_mode: InputMode::default(),
             filter_query: String::default(),
             show_reconciled_transactions: true,
+            presets: Vec::default(),
+            sort: TransactionSort::default(),
             form_state: Option::default(),
             payees: Vec::default(),
             categories: Vec::default(),
+            category_balances: HashMap::default(),
             delete_confirmation_transaction_id: Option::default(),
             reconciled_edit_transaction_id: Option::default(),
             reconcile_cleared_balance: Option::default(),
+            awaiting_reimbursement: HashSet::default(),
+            category_suggestions: HashMap::default(),
+            focus_transaction_id: Option::default(),
+            return_account_id: Option::default(),
+            account_balance: Option::default(),
+            account_cleared_balance: Option::default(),
+            opening_balance: Option::default(),
+            unsaved: HashSet::default(),
+            conflicts: Vec::default(),
+            currency_format: None,
         }
     }
 }
@@ -454,13 +1369,298 @@ pub struct PlanState {
     pub table_state: RefCell<TableState>,
     pub input_mode: InputMode,
     pub budget_form: Option<BudgetFormState>,
+    pub goal_calculator: Option<GoalCalculatorState>,
+    pub archive_review: Option<ArchiveReviewState>,
+    pub health: Option<HealthChecklistState>,
+    pub move_money: Option<MoveMoneyState>,
+    pub goal_form: Option<GoalFormState>,
+    pub quick_budget: Option<QuickBudgetState>,
     pub focused_view: PlanFocusedView,
+    /// Category group ids whose categories are folded under their header
+    pub collapsed_groups: HashSet<String>,
+    /// An earlier month shown side by side with this one
+    pub comparison: Option<PlanComparison>,
+    /// Show the selected category's goal in a pane next to the table
+    pub goal_details: bool,
+    /// Starred category ids, from the budget settings
+    pub favorite_ids: Vec<String>,
 }
 
-impl PlanState {
-    /// Returns filtered categories based on the current focused view.
-    /// Always filters out hidden and deleted categories.
-    pub fn filtered_categories(&self) -> Vec<&Category> {
+/// A month the plan is compared against, column by column
+#[derive(Debug, Clone)]
+pub struct PlanComparison {
+    /// First day of the month, as YNAB writes it (`2024-01-01`)
+    pub month: String,
+    pub categories: Vec<Category>,
+    pub loading: LoadingState,
+}
+
+impl PlanComparison {
+    pub fn new(month: String) -> Self {
+        Self {
+            month,
+            categories: Vec::new(),
+            loading: LoadingState::Loading(ThrobberState::default()),
+        }
+    }
+
+    /// How a category changed since the compared month, as (budgeted,
+    /// activity, available). A category that didn't exist yet counts as
+    /// zero. `None` until the month has loaded.
+    pub fn deltas(&self, category: &Category) -> Option<(i64, i64, i64)> {
+        if self.loading != LoadingState::Loaded {
+            return None;
+        }
+        let (budgeted, activity, balance) = self
+            .categories
+            .iter()
+            .find(|c| c.id == category.id)
+            .map_or((0, 0, 0), |c| {
+                (c.budgeted.into(), c.activity.into(), c.balance.into())
+            });
+        Some((
+            i64::from(category.budgeted) - budgeted,
+            i64::from(category.activity) - activity,
+            i64::from(category.balance) - balance,
+        ))
+    }
+
+    /// [`Self::deltas`] summed over a group's categories
+    pub fn group_deltas<'a>(
+        &self,
+        categories: impl IntoIterator<Item = &'a Category>,
+    ) -> Option<(i64, i64, i64)> {
+        categories
+            .into_iter()
+            .map(|category| self.deltas(category))
+            .try_fold((0, 0, 0), |sum, deltas| {
+                let (budgeted, activity, balance) = deltas?;
+                Some((sum.0 + budgeted, sum.1 + activity, sum.2 + balance))
+            })
+    }
+}
+
+/// A row of the plan table
+#[derive(Debug, Clone)]
+pub enum PlanRow<'a> {
+    Group(PlanGroup<'a>),
+    Category(&'a Category),
+}
+
+/// Id of the header row grouping the starred categories at the top of the plan
+pub const FAVORITES_GROUP: &str = "favorites";
+
+/// A category group header with totals of its listed categories
+#[derive(Debug, Clone)]
+pub struct PlanGroup<'a> {
+    pub id: String,
+    pub name: &'a str,
+    pub budgeted: i64,
+    pub activity: i64,
+    pub balance: i64,
+    pub collapsed: bool,
+}
+
+impl PlanState {
+    pub fn is_favorite(&self, category_id: &str) -> bool {
+        self.favorite_ids.iter().any(|id| id == category_id)
+    }
+
+    /// Rows for the table: each group's header followed by its categories,
+    /// unless the group is collapsed. Groups keep the plan's order, after a
+    /// group of the starred categories, which are also listed in their own.
+    pub fn rows(&self) -> Vec<PlanRow<'_>> {
+        let categories = self.filtered_categories();
+        let groups = categories
+            .iter()
+            .copied()
+            .into_group_map_by(|c| c.category_group_id);
+        let mut rows = Vec::new();
+
+        let favorites: Vec<&Category> = categories
+            .iter()
+            .copied()
+            .filter(|c| self.is_favorite(&c.id.to_string()))
+            .collect();
+        if !favorites.is_empty() {
+            let collapsed = self.collapsed_groups.contains(FAVORITES_GROUP);
+            rows.push(PlanRow::Group(PlanGroup {
+                id: FAVORITES_GROUP.to_string(),
+                name: t("plan-favorites"),
+                budgeted: favorites.iter().map(|c| i64::from(c.budgeted)).sum(),
+                activity: favorites.iter().map(|c| i64::from(c.activity)).sum(),
+                balance: favorites.iter().map(|c| i64::from(c.balance)).sum(),
+                collapsed,
+            }));
+            if !collapsed {
+                rows.extend(favorites.into_iter().map(PlanRow::Category));
+            }
+        }
+        for group_id in categories.iter().map(|c| c.category_group_id).unique() {
+            let categories = &groups[&group_id];
+            let id = group_id.to_string();
+            let collapsed = self.collapsed_groups.contains(&id);
+            rows.push(PlanRow::Group(PlanGroup {
+                name: categories[0]
+                    .category_group_name
+                    .as_deref()
+                    .unwrap_or_default(),
+                budgeted: categories.iter().map(|c| i64::from(c.budgeted)).sum(),
+                activity: categories.iter().map(|c| i64::from(c.activity)).sum(),
+                balance: categories.iter().map(|c| i64::from(c.balance)).sum(),
+                collapsed,
+                id,
+            }));
+            if !collapsed {
+                rows.extend(categories.iter().map(|c| PlanRow::Category(c)));
+            }
+        }
+        rows
+    }
+
+    pub fn selected_row(&self) -> Option<PlanRow<'_>> {
+        let selected = self.table_state.borrow().selected()?;
+        self.rows().into_iter().nth(selected)
+    }
+
+    /// The selected category, or `None` when a group header is selected
+    pub fn selected_category(&self) -> Option<&Category> {
+        match self.selected_row()? {
+            PlanRow::Category(category) => Some(category),
+            PlanRow::Group(_) => None,
+        }
+    }
+
+    /// The group listed before (or after, if `forward`) a category's own, as
+    /// (id, name). YNAB's internal group can't take categories, so it's skipped.
+    pub fn adjacent_group(&self, category_id: &str, forward: bool) -> Option<(Uuid, String)> {
+        let groups: Vec<(Uuid, &str)> = self
+            .categories
+            .iter()
+            .filter(|c| !c.hidden && !c.deleted)
+            .filter(|c| c.category_group_name.as_deref() != Some(variance::INTERNAL_GROUP))
+            .map(|c| {
+                (
+                    c.category_group_id,
+                    c.category_group_name.as_deref().unwrap_or_default(),
+                )
+            })
+            .unique_by(|(id, _)| *id)
+            .collect();
+        let current = self
+            .categories
+            .iter()
+            .find(|c| c.id.to_string() == category_id)?
+            .category_group_id;
+        let index = groups.iter().position(|(id, _)| *id == current)?;
+        let neighbour = if forward {
+            groups.get(index + 1)
+        } else {
+            groups.get(index.checked_sub(1)?)
+        };
+        neighbour.map(|(id, name)| (*id, name.to_string()))
+    }
+
+    /// Put a category that moved group at the start of its new group when it
+    /// came from above, or the end when it came from below, and keep it selected
+    pub fn place_moved_category(&mut self, category: Category, forward: bool) {
+        self.categories.retain(|c| c.id != category.id);
+        let group_id = category.category_group_id;
+        let index = if forward {
+            self.categories
+                .iter()
+                .position(|c| c.category_group_id == group_id)
+        } else {
+            self.categories
+                .iter()
+                .rposition(|c| c.category_group_id == group_id)
+                .map(|i| i + 1)
+        }
+        .unwrap_or(self.categories.len());
+        let id = category.id;
+        self.categories.insert(index, category);
+
+        let row = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, PlanRow::Category(c) if c.id == id));
+        if let Some(row) = row {
+            self.table_state.borrow_mut().select(Some(row));
+        }
+    }
+
+    /// Select a category, switching to the All view and unfolding its group
+    /// if that's what it takes to show it
+    pub fn reveal_category(&mut self, category_id: &str) {
+        let Some(category) = self
+            .categories
+            .iter()
+            .find(|c| c.id.to_string() == category_id)
+        else {
+            return;
+        };
+        if !self
+            .filtered_categories()
+            .iter()
+            .any(|c| c.id == category.id)
+        {
+            self.focused_view = PlanFocusedView::All;
+        }
+        let group_id = category.category_group_id.to_string();
+        self.collapsed_groups.remove(&group_id);
+
+        let row = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, PlanRow::Category(c) if c.id.to_string() == category_id));
+        self.table_state.borrow_mut().select(row);
+    }
+
+    /// Fold or unfold a category group
+    pub fn toggle_group(&mut self, group_id: &str) {
+        if !self.collapsed_groups.remove(group_id) {
+            self.collapsed_groups.insert(group_id.to_string());
+        }
+    }
+
+    /// Categories listed in the table, skipping collapsed groups and YNAB's
+    /// internal one. A starred category counts once.
+    pub fn visible_categories(&self) -> Vec<&Category> {
+        self.rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                PlanRow::Category(category)
+                    if category.category_group_name.as_deref()
+                        != Some(variance::INTERNAL_GROUP) =>
+                {
+                    Some(category)
+                }
+                _ => None,
+            })
+            .unique_by(|c| c.id)
+            .collect()
+    }
+
+    /// Work out the pending quick-budget command's amounts from the months
+    /// before the plan month, oldest first
+    pub fn fill_quick_budget(&mut self, history: &[Vec<Category>]) {
+        let Some(ref mut quick) = self.quick_budget else {
+            return;
+        };
+        let categories: Vec<&Category> = quick
+            .category_ids
+            .iter()
+            .filter_map(|id| self.categories.iter().find(|c| c.id.to_string() == *id))
+            .collect();
+        quick.changes = quick_budget::changes(quick.action, &categories, history);
+        quick.loading = LoadingState::Loaded;
+        let selected = (!quick.changes.is_empty()).then_some(0);
+        quick.table_state.borrow_mut().select(selected);
+    }
+
+    /// Returns filtered categories based on the current focused view.
+    /// Always filters out hidden and deleted categories.
+    pub fn filtered_categories(&self) -> Vec<&Category> {
         self.categories
             .iter()
             .filter(|c| !c.hidden && !c.deleted)
@@ -491,15 +1691,280 @@ pub struct LogsState {
     pub total_entries: usize,
 }
 
+/// Review screen for renaming near-duplicate payees
+#[derive(Default, Debug, Clone)]
+pub struct PayeeCleanupState {
+    pub clusters: Vec<PayeeCluster>,
+    /// Parallel to `clusters`; unticked groups are left alone
+    pub included: Vec<bool>,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+    /// Asking whether to merge the ticked groups
+    pub confirm_merge: bool,
+}
+
+impl PayeeCleanupState {
+    pub fn toggle_selected(&mut self) {
+        let selected = self.table_state.borrow().selected();
+        if let Some(included) = selected.and_then(|i| self.included.get_mut(i)) {
+            *included = !*included;
+        }
+    }
+
+    /// (payee id, new name) for every payee in a ticked group
+    pub fn included_renames(&self) -> Vec<(String, String)> {
+        self.clusters
+            .iter()
+            .zip(&self.included)
+            .filter(|(_, included)| **included)
+            .flat_map(|(cluster, _)| {
+                cluster
+                    .renames()
+                    .map(|member| (member.payee_id.clone(), cluster.canonical_name.clone()))
+            })
+            .collect()
+    }
+
+    /// A merge for every ticked group
+    pub fn included_merges(&self) -> Vec<PayeeMerge> {
+        self.clusters
+            .iter()
+            .zip(&self.included)
+            .filter(|(_, included)| **included)
+            .map(|(cluster, _)| cluster.merge())
+            .collect()
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ScheduledTransactionsState {
+    /// Only show this account's schedules; `None` shows every account
+    pub account_id: Option<String>,
+    /// Every scheduled transaction in the budget
+    pub scheduled_transactions: Vec<ScheduledTransaction>,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+}
+
+impl ScheduledTransactionsState {
+    pub fn new(account_id: Option<String>) -> Self {
+        Self {
+            account_id,
+            ..Default::default()
+        }
+    }
+
+    /// Schedules for the selected account (or all), soonest first
+    pub fn visible(&self) -> Vec<&ScheduledTransaction> {
+        self.scheduled_transactions
+            .iter()
+            .filter(|s| !s.deleted)
+            .filter(|s| match self.account_id {
+                Some(ref account_id) => s.account_id.to_string() == *account_id,
+                None => true,
+            })
+            .sorted_by_key(|s| s.date_next)
+            .collect()
+    }
+}
+
+/// Assets, liabilities and each account type's total across the budget
+#[derive(Default, Debug, Clone)]
+pub struct NetWorthState {
+    pub net_worth: Option<NetWorth>,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+}
+
+/// The settings screen: one row per [`SettingOption`]
+#[derive(Debug, Clone)]
+pub struct SettingsState {
+    pub table_state: RefCell<TableState>,
+}
+
+impl Default for SettingsState {
+    fn default() -> Self {
+        Self {
+            table_state: RefCell::new(TableState::default().with_selected(0)),
+        }
+    }
+}
+
+impl SettingsState {
+    pub fn selected_option(&self) -> Option<SettingOption> {
+        let selected = self.table_state.borrow().selected()?;
+        SettingOption::ALL.get(selected).copied()
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ReimbursementsState {
+    /// Every tracked entry for the budget, including reimbursed ones
+    pub entries: Vec<ReimbursementEntry>,
+    /// Suggested deposits for outstanding entries
+    pub matches: Vec<ReimbursementMatch>,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+}
+
+impl ReimbursementsState {
+    /// Entries still awaiting reimbursement, oldest first
+    pub fn outstanding(&self) -> Vec<&ReimbursementEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.is_outstanding())
+            .sorted_by_key(|e| e.date)
+            .collect()
+    }
+
+    pub fn suggested_match(&self, transaction_id: &str) -> Option<&ReimbursementMatch> {
+        self.matches
+            .iter()
+            .find(|m| m.transaction_id == transaction_id)
+    }
+
+    pub fn selected_entry(&self) -> Option<&ReimbursementEntry> {
+        let selected = self.table_state.borrow().selected()?;
+        self.outstanding().get(selected).copied()
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ReportsState {
+    pub kind: ReportKind,
+    pub period: ReportPeriod,
+    pub variance_rows: Vec<VarianceRow>,
+    pub variance_sort: VarianceSort,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+    /// Transactions behind the selected row, shown in place of the report
+    pub drill_down: Option<ReportDrillDown>,
+    /// Account the cash-flow projection is for
+    pub account_id: Option<String>,
+    pub horizon: ProjectionHorizon,
+    pub cash_flow: Option<CashFlowProjection>,
+    /// Month the spending heatmap shows; the selected row is the day
+    pub heatmap_month: Option<NaiveDate>,
+    pub heatmap: Option<SpendingHeatmap>,
+    pub obligations: Option<ObligationsForecast>,
+    pub spending_grouping: SpendingGrouping,
+    pub spending: Option<SpendingBreakdown>,
+    pub income_expense: Option<IncomeExpense>,
+}
+
+impl ReportsState {
+    /// Variance rows in display order
+    pub fn sorted_variance_rows(&self) -> Vec<&VarianceRow> {
+        variance::sorted(&self.variance_rows, self.variance_sort)
+    }
+
+    pub fn selected_variance_row(&self) -> Option<&VarianceRow> {
+        let selected = self.table_state.borrow().selected()?;
+        self.sorted_variance_rows().get(selected).copied()
+    }
+
+    pub fn selected_spending_row(&self) -> Option<&SpendingRow> {
+        let selected = self.table_state.borrow().selected()?;
+        self.spending.as_ref()?.rows.get(selected)
+    }
+
+    pub fn selected_month_flow(&self) -> Option<&MonthFlow> {
+        let selected = self.table_state.borrow().selected()?;
+        self.income_expense.as_ref()?.months.get(selected)
+    }
+
+    /// Day selected on the heatmap
+    pub fn selected_heatmap_date(&self) -> Option<NaiveDate> {
+        let selected = self.table_state.borrow().selected()?;
+        self.heatmap.as_ref()?.date(selected)
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ReportDrillDown {
+    pub category_id: String,
+    pub category_name: String,
+    pub transactions: Vec<VarianceTransaction>,
+    pub loading: LoadingState,
+    pub table_state: RefCell<TableState>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct InspectorState {
+    /// Index into the (possibly focused) record list, newest first
+    pub selected: usize,
+    /// Scroll offset of the JSON detail pane
+    pub detail_scroll: usize,
+    pub total_records: usize,
+    /// Only show records containing the object with this id (a transaction or category)
+    pub focus_id: Option<String>,
+}
+
+impl InspectorState {
+    /// Lines the detail pane moves per page
+    const DETAIL_PAGE: usize = 10;
+
+    pub fn new(focus_id: Option<String>) -> Self {
+        Self {
+            focus_id,
+            ..Default::default()
+        }
+    }
+
+    /// Records to list, newest first, limited to those containing the focused object
+    pub fn visible_records<'a>(&self, records: &'a [ApiRecord]) -> Vec<&'a ApiRecord> {
+        records
+            .iter()
+            .rev()
+            .filter(|r| match &self.focus_id {
+                Some(id) => r.find_object(id).is_some(),
+                None => true,
+            })
+            .collect()
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.total_records {
+            self.selected += 1;
+            self.detail_scroll = 0;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+            self.detail_scroll = 0;
+        }
+    }
+
+    pub fn scroll_detail(&mut self, down: bool) {
+        self.detail_scroll = if down {
+            self.detail_scroll + Self::DETAIL_PAGE
+        } else {
+            self.detail_scroll.saturating_sub(Self::DETAIL_PAGE)
+        };
+    }
+}
+
 impl AccountsState {
-    /// Returns filtered accounts based on the current filter query.
+    pub fn is_favorite(&self, account_id: &str) -> bool {
+        self.favorite_ids.iter().any(|id| id == account_id)
+    }
+
+    /// Returns filtered accounts based on the current filter query, starred
+    /// accounts first.
     /// Optionally filters out deleted and closed accounts based on show_closed_accounts flag.
     pub fn filtered_accounts(&self) -> Vec<&Account> {
-        let accounts: Vec<_> = self
+        let mut accounts: Vec<_> = self
             .accounts
             .iter()
             .filter(|a| self.show_closed_accounts || !a.closed)
+            .filter(|a| {
+                self.type_filter
+                    .is_none_or(|group| AccountGroup::of(a.account_type) == group)
+            })
             .collect();
+        accounts.sort_by_key(|a| !self.is_favorite(&a.id.to_string()));
 
         if self.filter_query.is_empty() {
             return accounts;
@@ -511,29 +1976,259 @@ impl AccountsState {
             .into_iter()
             .filter(|a| {
                 let name_match = a.name.to_lowercase().contains(&query_lower);
-                let balance_str = format!("{:.2}", a.balance.as_f64() / 1000.0);
-                let balance_match = balance_str.contains(&query_lower);
+                let balance = i64::from(a.balance);
+                let currency = self.currency_format.as_ref();
+                let balance_match = money::format(balance, currency).contains(&query_lower)
+                    || money::format_input(balance, currency).contains(&query_lower);
                 name_match || balance_match
             })
             .collect()
     }
+
+    /// Position of the listed account best matching what was typed after
+    /// `f`: a name starting with it, then a word in the name starting with
+    /// it, then a name whose words start with its letters ("cs" for "Chase
+    /// Sapphire")
+    pub fn jump_target(&self) -> Option<usize> {
+        let query = self.jump_query.to_lowercase();
+        if query.is_empty() {
+            return None;
+        }
+        self.filtered_accounts()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, account)| {
+                let name = account.name.to_lowercase();
+                let initials: String = name
+                    .split_whitespace()
+                    .filter_map(|word| word.chars().next())
+                    .collect();
+                let rank = if name.starts_with(&query) {
+                    0
+                } else if name.split_whitespace().any(|word| word.starts_with(&query)) {
+                    1
+                } else if initials.starts_with(&query) {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, index))
+            })
+            .min()
+            .map(|(_, index)| index)
+    }
+
+    /// Move the cursor to the account named so far, if any
+    pub fn jump(&self) {
+        if let Some(index) = self.jump_target() {
+            self.table_state.borrow_mut().select(Some(index));
+        }
+    }
+
+    /// Move the cursor to an account if it's listed, otherwise to the top
+    pub fn select_account(&self, account_id: Option<&str>) {
+        let index = account_id
+            .and_then(|id| {
+                self.filtered_accounts()
+                    .iter()
+                    .position(|a| a.id.to_string() == id)
+            })
+            .unwrap_or(0);
+        self.table_state.borrow_mut().select(Some(index));
+    }
 }
 
 impl TransactionsState {
-    /// Returns filtered transactions based on the current filter query.
+    /// Whether moving down should page in older history: the last row is
+    /// selected (or nothing is listed) and there's more history to load
+    pub fn wants_older_history(&self) -> bool {
+        if self.loaded_since.is_none()
+            || matches!(self.transactions_loading, LoadingState::Loading(..))
+        {
+            return false;
+        }
+        let len = self.filtered_transactions().len();
+        match self.table_state.borrow().selected() {
+            Some(selected) => selected + 1 >= len,
+            None => true,
+        }
+    }
+
+    /// Work out the opening balance again after the whole list was replaced
+    pub fn reset_opening_balance(&mut self) {
+        self.opening_balance = if self.all_accounts {
+            None
+        } else if self.loaded_since.is_none() {
+            // The whole history is loaded, so it starts from nothing
+            Some(0)
+        } else {
+            let loaded: i64 = self.transactions.iter().map(|t| i64::from(t.amount)).sum();
+            self.account_balance.map(|balance| balance - loaded)
+        };
+    }
+
+    /// The account balance after each loaded transaction, by transaction id.
+    /// Worked out in date order whatever the table's sort, so filtering and
+    /// sorting don't change a row's balance.
+    pub fn running_balances(&self) -> HashMap<String, i64> {
+        let Some(mut balance) = self.opening_balance else {
+            return HashMap::new();
+        };
+        self.transactions
+            .iter()
+            .sorted()
+            .rev()
+            .map(|t| {
+                balance += i64::from(t.amount);
+                (t.id.to_string(), balance)
+            })
+            .collect()
+    }
+
+    /// Take out of `delta` the server changes to rows that have a local
+    /// change of their own (unsaved, or open in the edit form) and queue them
+    /// as conflicts. Returns the rest, which are safe to merge.
+    pub fn hold_back_conflicts(&mut self, delta: Vec<Transaction>) -> Vec<Transaction> {
+        let editing_id = self
+            .form_state
+            .as_ref()
+            .and_then(|form| form.editing_transaction_id.clone());
+        let mut clean = Vec::new();
+        for server in delta {
+            let id = server.id.to_string();
+            let has_local_change = self.unsaved.contains(&id) || editing_id.as_ref() == Some(&id);
+            let local = self.transactions.iter().find(|t| t.id == server.id);
+            match local {
+                Some(local) if has_local_change && *local != server => {
+                    let conflict = DeltaConflict {
+                        local: local.clone(),
+                        server,
+                        show_diff: false,
+                    };
+                    // A newer server change replaces one still waiting
+                    match self
+                        .conflicts
+                        .iter_mut()
+                        .find(|c| c.local.id == conflict.local.id)
+                    {
+                        Some(waiting) => waiting.server = conflict.server,
+                        None => self.conflicts.push(conflict),
+                    }
+                }
+                _ => clean.push(server),
+            }
+        }
+        clean
+    }
+
+    /// Settle the conflict on show, keeping the local row or taking the
+    /// server's version (which also closes an edit form open on it)
+    pub fn resolve_conflict(&mut self, keep_mine: bool) {
+        if self.conflicts.is_empty() {
+            return;
+        }
+        let conflict = self.conflicts.remove(0);
+        if keep_mine {
+            return;
+        }
+        let id = conflict.server.id.to_string();
+        if let Some(row) = self
+            .transactions
+            .iter_mut()
+            .find(|t| t.id == conflict.server.id)
+        {
+            *row = conflict.server;
+        }
+        self.unsaved.remove(&id);
+        let editing = self
+            .form_state
+            .as_ref()
+            .is_some_and(|form| form.editing_transaction_id.as_ref() == Some(&id));
+        if editing {
+            self.form_state = None;
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Select the transaction waiting for focus, once it has been loaded
+    pub fn apply_pending_focus(&mut self) {
+        let Some(ref focus_id) = self.focus_transaction_id else {
+            return;
+        };
+        let position = self
+            .filtered_transactions()
+            .iter()
+            .position(|t| t.id.to_string() == *focus_id);
+        if let Some(index) = position {
+            self.table_state.borrow_mut().select(Some(index));
+            self.focus_transaction_id = None;
+        }
+    }
+
+    /// Switch a quick filter on or off
+    pub fn toggle_preset(&mut self, preset: TransactionPreset) {
+        if let Some(index) = self.presets.iter().position(|p| *p == preset) {
+            self.presets.remove(index);
+        } else {
+            self.presets.push(preset);
+        }
+    }
+
+    /// Whether the text filter or any quick filter is narrowing the list
+    pub fn is_filtered(&self) -> bool {
+        !self.filter_query.is_empty()
+            || !self.presets.is_empty()
+            || self.on_date.is_some()
+            || self.flag_view.is_some()
+    }
+
+    /// Category to offer `transaction`, if it's new and has none. One
+    /// hidden or deleted since isn't offered.
+    pub fn suggested_category(&self, transaction: &Transaction) -> Option<&CategorySuggestion> {
+        if !category_suggestions::wants_suggestion(transaction) {
+            return None;
+        }
+        let suggestion = self.category_suggestions.get(&transaction.payee_id?)?;
+        let usable = self.categories.is_empty()
+            || self
+                .categories
+                .iter()
+                .any(|c| c.id == suggestion.category_id && !c.hidden && !c.deleted);
+        usable.then_some(suggestion)
+    }
+
+    /// Inbox transactions reviewed so far, and how many there are
+    pub fn inbox_progress(&self) -> (usize, usize) {
+        let reviewed = self
+            .transactions
+            .iter()
+            .filter(|t| !needs_review(t))
+            .count();
+        (reviewed, self.transactions.len())
+    }
+
+    /// Returns filtered transactions based on the current filter query and presets,
+    /// in the current sort order. A split matches when any of its parts does.
     pub fn filtered_transactions(&self) -> Vec<&Transaction> {
+        let today = chrono::Local::now().date_naive();
+        let currency = self.currency_format.as_ref();
+        let filter = filter::FilterExpr::parse(&self.filter_query, currency);
         let transactions: Vec<_> = self
             .transactions
             .iter()
             .filter(|t| self.show_reconciled_transactions || !t.is_reconciled())
-            .sorted()
+            .filter(|t| self.presets.iter().all(|p| p.matches(t, today)))
+            .filter(|t| self.on_date.is_none_or(|date| t.date == date))
+            .filter(|t| self.flag_view.is_none_or(|flag| t.flag_color == Some(flag)))
+            .filter(|t| filter.matches(t))
+            .sorted_by(|a, b| self.sort.compare(a, b))
             .collect();
 
-        if self.filter_query.is_empty() {
+        if filter.text.is_empty() {
             return transactions;
         }
 
-        let query_lower = self.filter_query.to_lowercase();
+        let query_lower = filter.text;
 
         fn optional_match(opt: Option<&str>, search: &str) -> bool {
             let Some(req) = opt else {
@@ -543,16 +2238,32 @@ impl TransactionsState {
             req.to_lowercase().contains(search)
         }
 
+        let amount_match = |amount: i64| {
+            money::format(amount, currency).contains(&query_lower)
+                || money::format_input(amount, currency).contains(&query_lower)
+        };
+
         transactions
             .into_iter()
             .filter(|t| {
                 let payee_match = optional_match(t.payee_name.as_deref(), &query_lower);
                 let category_match = optional_match(t.category_name.as_deref(), &query_lower);
                 let memo_match = optional_match(t.memo.as_deref(), &query_lower);
-                // TODO: this should match the budget format
-                let amount_str = format!("{:.2}", t.amount.as_f64() / 1000.0);
-                let amount_match = amount_str.contains(&query_lower);
-                payee_match || category_match || memo_match || amount_match
+                let account_match =
+                    self.all_accounts && optional_match(Some(&t.account_name), &query_lower);
+                // e.g. "Groceries" inside a warehouse club split
+                let split_match = t.subtransactions.iter().filter(|s| !s.deleted).any(|s| {
+                    optional_match(s.payee_name.as_deref(), &query_lower)
+                        || optional_match(s.category_name.as_deref(), &query_lower)
+                        || optional_match(s.memo.as_deref(), &query_lower)
+                        || amount_match(s.amount.into())
+                });
+                payee_match
+                    || category_match
+                    || memo_match
+                    || account_match
+                    || amount_match(t.amount.into())
+                    || split_match
             })
             .collect()
     }
@@ -584,6 +2295,31 @@ pub trait Scrollable {
             }
         }
     }
+
+    /// Move `rows` down, or up when negative, stopping at the first or last
+    /// row rather than wrapping around
+    fn select_by(&mut self, rows: isize) {
+        let num_items = self.num_items();
+        if num_items > 0 {
+            let mut table_state = self.table_state().borrow_mut();
+            let selected = table_state.selected().unwrap_or(0).min(num_items - 1);
+            let target = selected.saturating_add_signed(rows).min(num_items - 1);
+            table_state.select(Some(target));
+        }
+    }
+
+    fn select_first(&mut self) {
+        if self.num_items() > 0 {
+            self.table_state().borrow_mut().select(Some(0));
+        }
+    }
+
+    fn select_last(&mut self) {
+        let num_items = self.num_items();
+        if num_items > 0 {
+            self.table_state().borrow_mut().select(Some(num_items - 1));
+        }
+    }
 }
 
 impl Scrollable for AccountsState {
@@ -606,9 +2342,120 @@ impl Scrollable for TransactionsState {
     }
 }
 
+impl Scrollable for PayeeCleanupState {
+    fn num_items(&self) -> usize {
+        self.clusters.len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for NetWorthState {
+    fn num_items(&self) -> usize {
+        self.net_worth.as_ref().map_or(0, |n| n.by_type.len())
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for SettingsState {
+    fn num_items(&self) -> usize {
+        SettingOption::ALL.len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for ReimbursementsState {
+    fn num_items(&self) -> usize {
+        self.outstanding().len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for ScheduledTransactionsState {
+    fn num_items(&self) -> usize {
+        self.visible().len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for ReportsState {
+    fn num_items(&self) -> usize {
+        match self.kind {
+            ReportKind::Variance => self.variance_rows.len(),
+            // The cash-flow table lists the days the balance goes negative
+            ReportKind::CashFlow => self
+                .cash_flow
+                .as_ref()
+                .map_or(0, |p| p.negative_days().len()),
+            ReportKind::Heatmap => self.heatmap.as_ref().map_or(0, |h| h.days.len()),
+            ReportKind::Obligations => self.obligations.as_ref().map_or(0, |o| o.obligations.len()),
+            ReportKind::Spending => self.spending.as_ref().map_or(0, |s| s.rows.len()),
+            ReportKind::IncomeExpense => self.income_expense.as_ref().map_or(0, |r| r.months.len()),
+        }
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for ArchiveReviewState {
+    fn num_items(&self) -> usize {
+        self.suggestions.len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for QuickBudgetState {
+    fn num_items(&self) -> usize {
+        self.changes.len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for HealthChecklistState {
+    fn num_items(&self) -> usize {
+        self.items.len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
+impl Scrollable for ReportDrillDown {
+    fn num_items(&self) -> usize {
+        self.transactions.len()
+    }
+
+    fn table_state(&self) -> &RefCell<TableState> {
+        &self.table_state
+    }
+}
+
 impl Scrollable for PlanState {
     fn num_items(&self) -> usize {
-        self.filtered_categories().len()
+        self.rows().len()
     }
 
     fn table_state(&self) -> &RefCell<TableState> {
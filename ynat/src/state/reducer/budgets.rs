@@ -0,0 +1,266 @@
+use crate::events::BudgetEvent;
+use crate::i18n::t;
+use crate::reconcile_reminders::{self, ReminderStatus};
+use crate::state::{AppState, LoadingState};
+use crate::ui::screens::{Screen, ScreenEvent};
+use ratatui::widgets::TableState;
+use std::cell::RefCell;
+use ynab_api::endpoints::{
+    accounts::{Account, AccountType},
+    budgets::BudgetSummary,
+    BudgetId,
+};
+
+/// Summary of the budget opened by id, e.g. the last one on startup
+fn opened_budget(state: &AppState, budgets: &[BudgetSummary]) -> Option<BudgetSummary> {
+    let budget_id = state.current_budget_id.as_deref()?;
+    budgets
+        .iter()
+        .find(|budget| budget.id == budget_id)
+        .cloned()
+}
+
+/// Apply a budget and account event
+pub fn reduce_budget_event(state: &mut AppState, event: BudgetEvent) {
+    match event {
+        // Budgets cache loaded
+        BudgetEvent::BudgetsCacheLoaded {
+            budgets,
+            default_budget,
+        } => {
+            if state.current_budget.is_none() {
+                state.current_budget = opened_budget(state, &budgets).or(default_budget);
+            }
+            if let Screen::Budgets(budgets_state) = state.current_screen_mut() {
+                budgets_state.budgets = budgets;
+                budgets_state.budgets_loading = LoadingState::Loaded;
+                budgets_state.selected_budget_index = 0;
+            }
+        }
+
+        // Budgets loaded from API
+        BudgetEvent::BudgetsLoaded {
+            budgets,
+            default_budget,
+        } => {
+            if state.current_budget.is_none() {
+                state.current_budget = opened_budget(state, &budgets).or(default_budget);
+            }
+            if let Screen::Budgets(budgets_state) = state.current_screen_mut() {
+                budgets_state.budgets = budgets;
+                budgets_state.budgets_loading = LoadingState::Loaded;
+            }
+        }
+
+        // Accounts cache loaded
+        BudgetEvent::AccountsCacheLoaded { mut accounts } => {
+            let default_account_id = state.budget_settings.default_account_id.clone();
+            match state.current_screen_mut() {
+                Screen::Accounts(accounts_state) => {
+                    accounts.sort_by_key(|account| account_type_sort_order(account.account_type));
+                    accounts_state.accounts = accounts;
+                    accounts_state.accounts_loading = LoadingState::Loaded;
+                    accounts_state.table_state = RefCell::new(TableState::default());
+                    accounts_state.select_account(default_account_id.as_deref());
+                }
+                Screen::Transactions(transactions_state) => {
+                    transactions_state.accounts = accounts;
+                }
+                _ => {}
+            }
+            remind_to_reconcile(state);
+        }
+
+        // Accounts loaded from API
+        BudgetEvent::AccountsLoaded { mut accounts } => {
+            let default_account_id = state.budget_settings.default_account_id.clone();
+            match state.current_screen_mut() {
+                Screen::Accounts(accounts_state) => {
+                    accounts.sort_by_key(|account| account_type_sort_order(account.account_type));
+                    accounts_state.accounts = accounts;
+                    accounts_state.accounts_loading = LoadingState::Loaded;
+                    // First load without a cache: start on the default account
+                    if accounts_state.table_state.borrow().selected().is_none() {
+                        accounts_state.select_account(default_account_id.as_deref());
+                    }
+                }
+                Screen::Transactions(transactions_state) => {
+                    transactions_state.accounts = accounts;
+                }
+                _ => {}
+            }
+            remind_to_reconcile(state);
+        }
+
+        // Accounts delta loaded (merge into existing)
+        BudgetEvent::AccountsDeltaLoaded { delta } => match state.current_screen_mut() {
+            Screen::Accounts(accounts_state) => {
+                merge_accounts_delta(&mut accounts_state.accounts, delta);
+                accounts_state.accounts_loading = LoadingState::Loaded;
+            }
+            Screen::Transactions(transactions_state) => {
+                merge_accounts_delta(&mut transactions_state.accounts, delta);
+            }
+            _ => {}
+        },
+
+        // Balance history computed for a single account
+        BudgetEvent::AccountBalanceHistoryLoaded {
+            account_id,
+            balances,
+            month_change,
+        } => {
+            // The accounts screen may be below the current screen in the stack
+            for screen in state.history.iter_mut().rev() {
+                if let Screen::Accounts(accounts_state) = screen {
+                    accounts_state
+                        .month_changes
+                        .insert(account_id.clone(), month_change);
+                    accounts_state.balance_history.insert(account_id, balances);
+                    break;
+                }
+            }
+        }
+
+        BudgetEvent::RecentSpendingLoaded { spending } => {
+            state.recent_spending = Some(spending);
+        }
+
+        BudgetEvent::YnabExportPreviewed { preview } => {
+            state.import_preview = Some(preview);
+        }
+
+        BudgetEvent::YnabExportImported {
+            transactions,
+            allocations,
+            skipped,
+        } => {
+            let summary = t("import-summary")
+                .replace("{transactions}", &transactions.to_string())
+                .replace("{allocations}", &allocations.to_string())
+                .replace("{skipped}", &skipped.to_string());
+            tracing::info!("{}", summary);
+            state.notice = Some(summary);
+        }
+
+        BudgetEvent::BudgetSettingsLoaded {
+            budget_id,
+            settings,
+        } => {
+            // Ignore settings for a budget that was left before they arrived
+            if state.current_budget_id.as_deref() != Some(budget_id.as_str()) {
+                return;
+            }
+            match state.current_screen_mut() {
+                Screen::Accounts(accounts_state) => {
+                    accounts_state.show_closed_accounts = settings.show_closed_accounts;
+                    accounts_state.favorite_ids = settings.favorite_account_ids.clone();
+                }
+                Screen::Plan(plan_state) => {
+                    plan_state.favorite_ids = settings.favorite_category_ids.clone();
+                }
+                _ => {}
+            }
+            state.budget_settings = settings;
+        }
+
+        BudgetEvent::BudgetFormatsLoaded {
+            budget_id,
+            date_format,
+            currency_format,
+        } => {
+            if state.current_budget_id.as_deref() != Some(budget_id.as_str()) {
+                return;
+            }
+            let budget = state.current_budget.get_or_insert_with(|| BudgetSummary {
+                id: BudgetId::from(budget_id),
+                name: String::new(),
+                last_modified_on: None,
+                first_month: None,
+                last_month: None,
+                date_format: None,
+                currency_format: None,
+                accounts: None,
+            });
+            budget.date_format = budget.date_format.take().or(date_format);
+            budget.currency_format = budget.currency_format.take().or(currency_format);
+
+            // Screens opened before the format arrived filter with it from now on
+            let currency_format = budget.currency_format.clone();
+            for screen in &mut state.history {
+                match screen {
+                    Screen::Accounts(accounts_state) => {
+                        accounts_state.currency_format = currency_format.clone();
+                    }
+                    Screen::Transactions(transactions_state) => {
+                        transactions_state.currency_format = currency_format.clone();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Batch refresh finished - settle the indicator unless an error was reported
+        BudgetEvent::RefreshAllCompleted => state
+            .current_screen_mut()
+            .controller_mut()
+            .handle_event(ScreenEvent::RefreshCompleted),
+    }
+}
+
+/// Merge accounts delta into existing accounts list
+/// List the accounts due for reconciling, once each time the budget is opened
+fn remind_to_reconcile(state: &mut AppState) {
+    let cadences = &state.budget_settings.reconcile_reminders;
+    let Some(Screen::Accounts(accounts_state)) = state.history.last_mut() else {
+        return;
+    };
+    if accounts_state.reconcile_reminded || cadences.is_empty() {
+        return;
+    }
+    accounts_state.reconcile_reminded = true;
+
+    let today = chrono::Local::now().date_naive();
+    let due: Vec<String> =
+        reconcile_reminders::due_accounts(&accounts_state.accounts, cadences, today)
+            .into_iter()
+            .map(|(account, status)| match status {
+                ReminderStatus::Due => account.name.clone(),
+                ReminderStatus::Overdue => {
+                    t("reconcile-overdue").replace("{account}", &account.name)
+                }
+            })
+            .collect();
+    if !due.is_empty() {
+        state.notice = Some(t("notice-reconcile-due").replace("{accounts}", &due.join(", ")));
+    }
+}
+
+fn merge_accounts_delta(accounts: &mut Vec<Account>, delta: Vec<Account>) {
+    for delta_account in delta {
+        if delta_account.deleted {
+            // Remove deleted accounts
+            accounts.retain(|a| a.id != delta_account.id);
+        } else if let Some(existing) = accounts.iter_mut().find(|a| a.id == delta_account.id) {
+            // Update existing account
+            *existing = delta_account;
+        } else {
+            // Add new account
+            accounts.push(delta_account);
+        }
+    }
+
+    // Sort accounts by type after merge (to maintain consistent ordering)
+    accounts.sort_by_key(|account| account_type_sort_order(account.account_type));
+}
+
+/// Helper function to determine account type sort order
+fn account_type_sort_order(account_type: AccountType) -> usize {
+    use AccountType::*;
+    match account_type {
+        Checking | Savings | Cash => 0,
+        CreditCard | LineOfCredit => 1,
+        Mortgage | AutoLoan | StudentLoan | PersonalLoan | MedicalDebt | OtherDebt => 2,
+        OtherAsset | OtherLiability => 3,
+    }
+}
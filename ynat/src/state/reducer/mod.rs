@@ -0,0 +1,2226 @@
+//! Reducers for data events, one per domain.
+//!
+//! `app_core::reduce_data_event` routes each [`DataEvent`](crate::events::DataEvent)
+//! to the reducer for its domain; errors and notices that any domain can
+//! raise are handled here.
+
+mod budgets;
+mod payees;
+mod plan;
+mod reports;
+mod scheduled;
+mod transactions;
+
+pub use budgets::reduce_budget_event;
+pub use payees::reduce_payee_event;
+pub use plan::reduce_plan_event;
+pub use reports::reduce_report_event;
+pub use scheduled::reduce_scheduled_event;
+pub use transactions::reduce_transaction_event;
+
+use super::{AppState, InputMode, Scrollable};
+use crate::error::{AppError, ErrorKind};
+use crate::i18n::{t, tn};
+use crate::ui::screens::{Screen, ScreenEvent};
+
+/// Put the loading resource on the current screen into an error state
+pub fn reduce_load_error(state: &mut AppState, error: AppError) {
+    // Set error state for whichever resource was loading
+    state
+        .current_screen_mut()
+        .controller_mut()
+        .handle_event(ScreenEvent::LoadFailed(error));
+}
+
+/// A change YNAB refused, or a local action that failed. A rejected token
+/// can't be fixed by trying again, so it gets the sign-in advice instead.
+pub fn reduce_action_failed(state: &mut AppState, notice: &'static str, error: AppError) {
+    state.notice = Some(match error.kind {
+        ErrorKind::Unauthorized => format!("{}: {}", error.title(), error.suggestion()),
+        _ => t(notice).replace("{error}", &error.message),
+    });
+}
+
+/// A change was saved to send once YNAB is reachable. Deletes and assigned
+/// amounts are already on screen; a submitted transaction form closes as if
+/// it had been saved, since the change is no longer lost.
+pub fn reduce_write_queued(state: &mut AppState, pending: usize) {
+    state.pending_writes = pending;
+    state.undo_history.settle_queued();
+    state.notice = Some(tn("notice-write-queued", pending));
+    if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+        transactions_state.input_mode = InputMode::Normal;
+        transactions_state.form_state = None;
+    }
+}
+
+/// Queued changes were sent, or YNAB is still unreachable
+pub fn reduce_pending_writes_synced(state: &mut AppState, synced: usize, pending: usize) {
+    state.pending_writes = pending;
+    if synced > 0 {
+        state.notice = Some(tn("notice-pending-writes-synced", synced));
+    }
+}
+
+/// Keep a list's selection on a row after its contents change
+fn clamp_selection(scrollable: &impl Scrollable) {
+    let len = scrollable.num_items();
+    let mut table_state = scrollable.table_state().borrow_mut();
+    match table_state.selected() {
+        _ if len == 0 => table_state.select(None),
+        Some(i) if i >= len => table_state.select(Some(len - 1)),
+        None => table_state.select(Some(0)),
+        Some(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_core::reduce_data_event;
+    use crate::budget_settings::BudgetSettings;
+    use crate::error::{AppError, ErrorKind};
+    use crate::events::{
+        BudgetEvent, DataEvent, PayeeEvent, PlanEvent, ScheduledEvent, TransactionEvent,
+    };
+    use crate::health::HealthItem;
+    use crate::state::undo::UndoEntry;
+    use crate::state::InputMode;
+    use crate::state::{
+        AccountsState, BudgetsState, HealthChecklistState, LoadingState, MoveMoneyState,
+        PayeeLocationLookup, PlanComparison, PlanRow, PlanState, ReportDrillDown, ReportsState,
+        Scrollable, TransactionDetail, TransactionField, TransactionFormState, TransactionPreset,
+        TransactionSortColumn, TransactionsState,
+    };
+    use crate::ui::screens::Screen;
+    use chrono::NaiveDate;
+    use ratatui::widgets::TableState;
+    use std::cell::RefCell;
+    use uuid::Uuid;
+    use ynab_api::endpoints::{
+        accounts::{Account, AccountType},
+        budgets::BudgetSummary,
+        categories::Category,
+        months::MonthDetail,
+        payees::Payee,
+        transactions::{FlagColor, ReconciliationStatus, SubTransaction, Transaction},
+        BudgetId, CurrencyFormat, Milliunits, TransactionId,
+    };
+
+    // ============================================================================
+    // Helper Functions
+    // ============================================================================
+
+    /// Generate a deterministic UUID from a string ID for testing
+    /// Uses a simple hash-based approach to create reproducible UUIDs
+    fn test_uuid(id: &str) -> Uuid {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        id.hash(&mut hasher);
+        let hash = hasher.finish();
+        // Create a UUID from the hash bytes (padded/repeated as needed)
+        let bytes = [
+            (hash >> 56) as u8,
+            (hash >> 48) as u8,
+            (hash >> 40) as u8,
+            (hash >> 32) as u8,
+            (hash >> 24) as u8,
+            (hash >> 16) as u8,
+            (hash >> 8) as u8,
+            hash as u8,
+            (hash >> 56) as u8,
+            (hash >> 48) as u8,
+            (hash >> 40) as u8,
+            (hash >> 32) as u8,
+            (hash >> 24) as u8,
+            (hash >> 16) as u8,
+            (hash >> 8) as u8,
+            hash as u8,
+        ];
+        Uuid::from_bytes(bytes)
+    }
+
+    /// Generate a TransactionId string from a test id (for event matching)
+    fn test_transaction_id_str(id: &str) -> String {
+        test_uuid(id).to_string()
+    }
+
+    fn create_test_account(id: &str, name: &str, account_type: AccountType) -> Account {
+        Account {
+            id: test_uuid(id),
+            name: name.to_string(),
+            account_type,
+            on_budget: true,
+            closed: false,
+            note: None,
+            balance: Milliunits::new(100000),
+            cleared_balance: Milliunits::new(50000),
+            uncleared_balance: Milliunits::new(50000),
+            transfer_payee_id: None,
+            direct_import_linked: false,
+            direct_import_in_error: false,
+            last_reconciled_at: None,
+            deleted: false,
+        }
+    }
+
+    fn create_test_transaction(
+        id: &str,
+        date: &str,
+        amount: i64,
+        cleared: ReconciliationStatus,
+    ) -> Transaction {
+        Transaction {
+            id: TransactionId::new(test_uuid(id)),
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            amount: Milliunits::new(amount),
+            memo: None,
+            cleared,
+            approved: true,
+            flag_color: None,
+            account_id: test_uuid("test_account"),
+            account_name: "Checking".to_string(),
+            payee_id: None,
+            payee_name: None,
+            category_id: None,
+            category_name: None,
+            transfer_account_id: None,
+            transfer_transaction_id: None,
+            matched_transaction_id: None,
+            import_id: None,
+            deleted: false,
+            subtransactions: vec![],
+        }
+    }
+
+    fn create_test_category(id: &str, group: &str, budgeted: i64, balance: i64) -> Category {
+        Category {
+            id: test_uuid(id),
+            category_group_id: test_uuid(group),
+            category_group_name: Some(group.to_string()),
+            name: id.to_string(),
+            hidden: false,
+            original_category_group_id: None,
+            note: None,
+            budgeted: budgeted.into(),
+            activity: (balance - budgeted).into(),
+            balance: balance.into(),
+            goal_type: None,
+            goal_creation_month: None,
+            goal_target: None,
+            goal_target_month: None,
+            goal_percentage_complete: None,
+            goal_months_to_budget: None,
+            goal_under_funded: None,
+            goal_overall_funded: None,
+            goal_overall_left: None,
+            goal_snoozed_at: None,
+            deleted: false,
+        }
+    }
+
+    fn create_test_budget(id: &str, name: &str) -> BudgetSummary {
+        BudgetSummary {
+            id: test_uuid(id).into(),
+            name: name.to_string(),
+            last_modified_on: None,
+            first_month: None,
+            last_month: None,
+            date_format: None,
+            currency_format: None,
+            accounts: None,
+        }
+    }
+
+    // ============================================================================
+    // Budgets Tests
+    // ============================================================================
+
+    #[test]
+    fn test_budgets_cache_loaded() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Budgets(BudgetsState::default())];
+
+        let budgets = vec![
+            create_test_budget("b1", "Budget 1"),
+            create_test_budget("b2", "Budget 2"),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::BudgetsCacheLoaded {
+                budgets: budgets.clone(),
+                default_budget: Some(budgets[0].clone()),
+            }
+            .into(),
+        );
+
+        let Screen::Budgets(budgets_state) = state.current_screen() else {
+            panic!("Expected Budgets screen");
+        };
+        assert_eq!(budgets_state.budgets.len(), 2);
+        assert_eq!(budgets_state.budgets[0].id, BudgetId::from(test_uuid("b1")));
+        assert_eq!(budgets_state.budgets_loading, LoadingState::Loaded);
+        assert_eq!(budgets_state.selected_budget_index, 0);
+    }
+
+    #[test]
+    fn test_budgets_loaded() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Budgets(BudgetsState::default())];
+
+        let budgets = vec![create_test_budget("b1", "Budget 1")];
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::BudgetsLoaded {
+                budgets: budgets.clone(),
+                default_budget: Some(budgets[0].clone()),
+            }
+            .into(),
+        );
+
+        let Screen::Budgets(budgets_state) = state.current_screen() else {
+            panic!("Expected Budgets screen");
+        };
+        assert_eq!(budgets_state.budgets.len(), 1);
+        assert_eq!(budgets_state.budgets_loading, LoadingState::Loaded);
+    }
+
+    #[test]
+    fn test_budgets_loaded_describe_the_budget_opened_on_startup() {
+        let mut state = AppState::new();
+        state.current_budget_id = Some(test_uuid("b2").to_string());
+
+        let budgets = vec![
+            create_test_budget("b1", "Budget 1"),
+            create_test_budget("b2", "Budget 2"),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::BudgetsCacheLoaded {
+                budgets: budgets.clone(),
+                default_budget: Some(budgets[0].clone()),
+            }
+            .into(),
+        );
+
+        assert_eq!(state.current_budget.unwrap().name, "Budget 2");
+    }
+
+    // ============================================================================
+    // Accounts Tests
+    // ============================================================================
+
+    #[test]
+    fn test_accounts_cache_loaded() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Accounts(AccountsState::default())];
+
+        let accounts = vec![
+            create_test_account("a1", "Checking", AccountType::Checking),
+            create_test_account("a2", "Savings", AccountType::Savings),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountsCacheLoaded { accounts }.into(),
+        );
+
+        let Screen::Accounts(accounts_state) = state.current_screen() else {
+            panic!("Expected Accounts screen");
+        };
+        assert_eq!(accounts_state.accounts.len(), 2);
+        assert_eq!(accounts_state.accounts_loading, LoadingState::Loaded);
+        // Should have selection set to 0
+        assert_eq!(accounts_state.table_state.borrow().selected(), Some(0));
+    }
+
+    #[test]
+    fn test_accounts_due_for_reconciling_are_listed_once() {
+        use crate::reconcile_reminders::ReconcileCadence;
+
+        let mut state = AppState::new();
+        state.history = vec![Screen::Accounts(AccountsState::default())];
+        state
+            .budget_settings
+            .reconcile_reminders
+            .insert(test_uuid("a2").to_string(), ReconcileCadence::Monthly);
+        let accounts = vec![
+            create_test_account("a1", "Checking", AccountType::Checking),
+            create_test_account("a2", "Visa", AccountType::CreditCard),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountsCacheLoaded {
+                accounts: accounts.clone(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("Time to reconcile: Visa (overdue)")
+        );
+
+        // Fresh accounts after the cache don't repeat it
+        state.notice = None;
+        reduce_data_event(&mut state, BudgetEvent::AccountsLoaded { accounts }.into());
+        assert_eq!(state.notice, None);
+    }
+
+    #[test]
+    fn test_accounts_sorted_by_type() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Accounts(AccountsState::default())];
+
+        // Create accounts in wrong order
+        let accounts = vec![
+            create_test_account("a1", "Credit Card", AccountType::CreditCard),
+            create_test_account("a2", "Cash", AccountType::Cash),
+            create_test_account("a3", "Checking", AccountType::Checking),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountsCacheLoaded { accounts }.into(),
+        );
+
+        let Screen::Accounts(accounts_state) = state.current_screen() else {
+            panic!("Expected Accounts screen");
+        };
+        // Should be sorted: cash, checking, creditCard
+        assert_eq!(accounts_state.accounts[0].account_type, AccountType::Cash);
+        assert_eq!(
+            accounts_state.accounts[1].account_type,
+            AccountType::Checking
+        );
+        assert_eq!(
+            accounts_state.accounts[2].account_type,
+            AccountType::CreditCard
+        );
+    }
+
+    #[test]
+    fn test_budget_formats_fill_in_the_currency_for_filtering() {
+        let mut state = AppState::new();
+        state.current_budget_id = Some(test_uuid("budget1").to_string());
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![create_test_transaction(
+                "t1",
+                "2025-01-10",
+                -1_234_500,
+                ReconciliationStatus::Cleared,
+            )],
+            filter_query: "1.234,50".to_string(),
+            ..Default::default()
+        }))];
+        let euro = CurrencyFormat {
+            iso_code: "EUR".to_string(),
+            example_format: "123.456,78".to_string(),
+            decimal_digits: 2,
+            decimal_separator: ",".to_string(),
+            symbol_first: false,
+            group_separator: ".".to_string(),
+            currency_symbol: "€".to_string(),
+            display_symbol: true,
+        };
+        let filtered = |state: &AppState| match state.current_screen() {
+            Screen::Transactions(transactions_state) => {
+                transactions_state.filtered_transactions().len()
+            }
+            _ => panic!("Expected Transactions screen"),
+        };
+        assert_eq!(filtered(&state), 0);
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::BudgetFormatsLoaded {
+                budget_id: test_uuid("budget1").to_string(),
+                date_format: None,
+                currency_format: Some(euro.clone()),
+            }
+            .into(),
+        );
+
+        assert_eq!(state.currency_format(), Some(euro));
+        assert_eq!(filtered(&state), 1);
+    }
+
+    #[test]
+    fn test_budget_settings_apply_to_the_opened_budget_only() {
+        let mut state = AppState::new();
+        state.current_budget_id = Some("budget-1".to_string());
+        let settings = BudgetSettings {
+            default_account_id: Some(test_uuid("a2").to_string()),
+            show_closed_accounts: true,
+            ..Default::default()
+        };
+
+        // Settings for a budget that was left in the meantime are dropped
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::BudgetSettingsLoaded {
+                budget_id: "budget-0".to_string(),
+                settings: settings.clone(),
+            }
+            .into(),
+        );
+        assert_eq!(state.budget_settings, BudgetSettings::default());
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::BudgetSettingsLoaded {
+                budget_id: "budget-1".to_string(),
+                settings,
+            }
+            .into(),
+        );
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountsCacheLoaded {
+                accounts: vec![
+                    create_test_account("a1", "Checking", AccountType::Checking),
+                    create_test_account("a2", "Savings", AccountType::Savings),
+                ],
+            }
+            .into(),
+        );
+
+        let Screen::Accounts(accounts_state) = state.current_screen() else {
+            panic!("Expected Accounts screen");
+        };
+        assert!(accounts_state.show_closed_accounts);
+        assert_eq!(accounts_state.table_state.borrow().selected(), Some(1));
+    }
+
+    #[test]
+    fn test_accounts_delta_merge_update() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Accounts(AccountsState {
+            accounts: vec![create_test_account("a1", "Checking", AccountType::Checking)],
+            ..Default::default()
+        })];
+
+        // Delta with updated account
+        let mut updated_account =
+            create_test_account("a1", "Checking Updated", AccountType::Checking);
+        updated_account.balance = 200000.into();
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountsDeltaLoaded {
+                delta: vec![updated_account],
+            }
+            .into(),
+        );
+
+        let Screen::Accounts(accounts_state) = state.current_screen() else {
+            panic!("Expected Accounts screen");
+        };
+        assert_eq!(accounts_state.accounts.len(), 1);
+        assert_eq!(accounts_state.accounts[0].name, "Checking Updated");
+        assert_eq!(accounts_state.accounts[0].balance, Milliunits::new(200000));
+    }
+
+    #[test]
+    fn test_accounts_delta_merge_add() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Accounts(AccountsState {
+            accounts: vec![create_test_account("a1", "Checking", AccountType::Checking)],
+            ..Default::default()
+        })];
+
+        // Delta with new account
+        let new_account = create_test_account("a2", "Savings", AccountType::Savings);
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountsDeltaLoaded {
+                delta: vec![new_account],
+            }
+            .into(),
+        );
+
+        let Screen::Accounts(accounts_state) = state.current_screen() else {
+            panic!("Expected Accounts screen");
+        };
+        assert_eq!(accounts_state.accounts.len(), 2);
+    }
+
+    #[test]
+    fn test_accounts_delta_merge_delete() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Accounts(AccountsState {
+            accounts: vec![
+                create_test_account("a1", "Checking", AccountType::Checking),
+                create_test_account("a2", "Savings", AccountType::Savings),
+            ],
+            ..Default::default()
+        })];
+
+        // Delta with deleted account
+        let mut deleted_account = create_test_account("a1", "Checking", AccountType::Checking);
+        deleted_account.deleted = true;
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountsDeltaLoaded {
+                delta: vec![deleted_account],
+            }
+            .into(),
+        );
+
+        let Screen::Accounts(accounts_state) = state.current_screen() else {
+            panic!("Expected Accounts screen");
+        };
+        assert_eq!(accounts_state.accounts.len(), 1);
+        assert_eq!(accounts_state.accounts[0].name, "Savings"); // a2 was "Savings"
+    }
+
+    #[test]
+    fn test_balance_history_reaches_accounts_below_transactions() {
+        let mut state = AppState::new();
+        state.history = vec![
+            Screen::Accounts(AccountsState::default()),
+            Screen::Transactions(Box::default()),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            BudgetEvent::AccountBalanceHistoryLoaded {
+                account_id: "a1".to_string(),
+                balances: vec![1000, 2000],
+                month_change: -3500,
+            }
+            .into(),
+        );
+
+        let Screen::Accounts(accounts_state) = &state.history[0] else {
+            panic!("Expected Accounts screen");
+        };
+        assert_eq!(
+            accounts_state.balance_history.get("a1"),
+            Some(&vec![1000, 2000])
+        );
+        assert_eq!(accounts_state.month_changes.get("a1"), Some(&-3500));
+    }
+
+    // ============================================================================
+    // Transactions Tests
+    // ============================================================================
+
+    #[test]
+    fn test_transactions_cache_loaded() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::default())];
+
+        let transactions = vec![
+            create_test_transaction("t1", "2024-01-15", -5000, ReconciliationStatus::Cleared),
+            create_test_transaction("t2", "2024-01-10", -3000, ReconciliationStatus::Uncleared),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsCacheLoaded {
+                transactions,
+                loaded_since: None,
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(trans_state.transactions.len(), 2);
+        assert_eq!(trans_state.transactions_loading, LoadingState::Loaded);
+        // Should be sorted by date descending (most recent first)
+        assert_eq!(
+            trans_state.transactions[0].date,
+            NaiveDate::parse_from_str("2024-01-15", "%Y-%m-%d").unwrap()
+        );
+        assert_eq!(
+            trans_state.transactions[1].date,
+            NaiveDate::parse_from_str("2024-01-10", "%Y-%m-%d").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_transactions_sorted_by_date_descending() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::default())];
+
+        // Create transactions in wrong order
+        let transactions = vec![
+            create_test_transaction("t1", "2024-01-10", -5000, ReconciliationStatus::Cleared),
+            create_test_transaction("t2", "2024-01-20", -3000, ReconciliationStatus::Uncleared),
+            create_test_transaction("t3", "2024-01-15", -2000, ReconciliationStatus::Cleared),
+        ];
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsCacheLoaded {
+                transactions,
+                loaded_since: None,
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        // Should be sorted newest first
+        assert_eq!(
+            trans_state.transactions[0].date,
+            NaiveDate::parse_from_str("2024-01-20", "%Y-%m-%d").unwrap()
+        );
+        assert_eq!(
+            trans_state.transactions[1].date,
+            NaiveDate::parse_from_str("2024-01-15", "%Y-%m-%d").unwrap()
+        );
+        assert_eq!(
+            trans_state.transactions[2].date,
+            NaiveDate::parse_from_str("2024-01-10", "%Y-%m-%d").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_jumped_to_transfer_is_selected_once_loaded() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            focus_transaction_id: Some(test_transaction_id_str("t1")),
+            ..Default::default()
+        }))];
+
+        let transactions = vec![
+            create_test_transaction("t1", "2024-01-10", -5000, ReconciliationStatus::Cleared),
+            create_test_transaction("t2", "2024-01-20", -3000, ReconciliationStatus::Cleared),
+        ];
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsCacheLoaded {
+                transactions,
+                loaded_since: None,
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(trans_state.table_state.borrow().selected(), Some(1));
+        assert_eq!(trans_state.focus_transaction_id, None);
+    }
+
+    #[test]
+    fn test_transaction_update_failed_rollback() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![create_test_transaction(
+                "t1",
+                "2024-01-15",
+                -5000,
+                ReconciliationStatus::Cleared,
+            )],
+            ..Default::default()
+        }))];
+
+        // Simulate failed update - should rollback to original status
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionUpdateFailed {
+                transaction_id: test_transaction_id_str("t1"),
+                original_status: ReconciliationStatus::Uncleared,
+                original_approved: false,
+                error: AppError::new(ErrorKind::Server, "API error"),
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(
+            trans_state.transactions[0].cleared,
+            ReconciliationStatus::Uncleared
+        );
+        assert!(!trans_state.transactions[0].approved);
+    }
+
+    #[test]
+    fn test_transaction_created() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![create_test_transaction(
+                "t1",
+                "2024-01-10",
+                -5000,
+                ReconciliationStatus::Cleared,
+            )],
+            input_mode: InputMode::TransactionForm,
+            form_state: Some(TransactionFormState::new("acc1".to_string(), "YYYY-MM-DD")),
+            ..Default::default()
+        }))];
+
+        let new_transaction =
+            create_test_transaction("t2", "2024-01-15", -3000, ReconciliationStatus::Uncleared);
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionCreated {
+                transaction: new_transaction,
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        // Should have 2 transactions
+        assert_eq!(trans_state.transactions.len(), 2);
+        // Should be sorted with new one first (newer date)
+        assert_eq!(
+            trans_state.transactions[0].date,
+            NaiveDate::parse_from_str("2024-01-15", "%Y-%m-%d").unwrap()
+        );
+        // Form should be closed
+        assert_eq!(trans_state.input_mode, InputMode::Normal);
+        assert!(trans_state.form_state.is_none());
+        // Selection should be reset to first item
+        assert_eq!(trans_state.table_state.borrow().selected(), Some(0));
+    }
+
+    #[test]
+    fn test_transaction_created_is_remembered_for_its_account() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            input_mode: InputMode::TransactionForm,
+            form_state: Some(TransactionFormState::new("acc1".to_string(), "YYYY-MM-DD")),
+            ..Default::default()
+        }))];
+        let mut transaction =
+            create_test_transaction("t1", "2024-01-15", -3000, ReconciliationStatus::Cleared);
+        transaction.payee_name = Some("Corner Shop".to_string());
+        transaction.category_name = Some("Groceries".to_string());
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionCreated { transaction }.into(),
+        );
+
+        assert_eq!(
+            state
+                .last_used_entries
+                .get(&test_uuid("test_account").to_string()),
+            Some(&crate::state::LastUsedEntry {
+                payee: "Corner Shop".to_string(),
+                category: "Groceries".to_string(),
+                cleared: ReconciliationStatus::Cleared,
+            })
+        );
+    }
+
+    #[test]
+    fn test_failed_bulk_approval_rolls_back() {
+        let mut state = AppState::new();
+        let mut unapproved =
+            create_test_transaction("t1", "2025-01-10", -1_000, ReconciliationStatus::Cleared);
+        unapproved.approved = false;
+        let already =
+            create_test_transaction("t2", "2025-01-11", -2_000, ReconciliationStatus::Cleared);
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![unapproved, already],
+            ..Default::default()
+        }))];
+        // Approved optimistically before the request failed
+        if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+            transactions_state.transactions[0].approved = true;
+        }
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsApproveFailed {
+                transaction_ids: vec![test_transaction_id_str("t1")],
+                error: AppError::new(ErrorKind::Network, "Network error"),
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(transactions_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert!(!transactions_state.transactions[0].approved);
+        assert!(transactions_state.transactions[1].approved);
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("Couldn't approve the transactions: Network error")
+        );
+    }
+
+    #[test]
+    fn test_rejected_token_on_a_write_asks_to_sign_in() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![create_test_transaction(
+                "t1",
+                "2025-01-10",
+                -1_000,
+                ReconciliationStatus::Cleared,
+            )],
+            transactions_loading: LoadingState::Loaded,
+            ..Default::default()
+        }))];
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsApproveFailed {
+                transaction_ids: vec![test_transaction_id_str("t1")],
+                error: AppError::new(ErrorKind::Unauthorized, "Unauthorized"),
+            }
+            .into(),
+        );
+
+        // Signing in again is the fix, not retrying
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("Not signed in: Your YNAB session has expired. Restart ynat to sign in again.")
+        );
+    }
+
+    #[test]
+    fn test_transactions_imported_reports_the_count() {
+        let mut state = AppState::new();
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsImported { count: 0 }.into(),
+        );
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("No new transactions to import")
+        );
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsImported { count: 3 }.into(),
+        );
+        assert_eq!(state.notice.as_deref(), Some("Imported 3 transactions"));
+    }
+
+    #[test]
+    fn test_payee_location_fills_in_the_open_detail_popup() {
+        let mut state = AppState::new();
+        let mut transaction =
+            create_test_transaction("t1", "2025-01-10", -4_500, ReconciliationStatus::Cleared);
+        transaction.payee_id = Some(test_uuid("cafe"));
+        state.transaction_detail = Some(TransactionDetail {
+            transaction,
+            location: PayeeLocationLookup::Loading,
+        });
+        let place = crate::maps::Place {
+            latitude: "59.3293".to_string(),
+            longitude: "18.0686".to_string(),
+            city: Some("Stockholm, Sweden".to_string()),
+        };
+
+        // An answer for a payee shown earlier is ignored
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::PayeeLocationLoaded {
+                payee_id: test_uuid("bakery").to_string(),
+                place: None,
+            }
+            .into(),
+        );
+        let location =
+            |state: &AppState| state.transaction_detail.as_ref().unwrap().location.clone();
+        assert_eq!(location(&state), PayeeLocationLookup::Loading);
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::PayeeLocationLoaded {
+                payee_id: test_uuid("cafe").to_string(),
+                place: Some(place.clone()),
+            }
+            .into(),
+        );
+        assert_eq!(location(&state), PayeeLocationLookup::Found(place));
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::PayeeLocationLoadFailed {
+                payee_id: test_uuid("cafe").to_string(),
+                error: AppError::new(ErrorKind::Network, "Network error"),
+            }
+            .into(),
+        );
+        assert_eq!(
+            location(&state),
+            PayeeLocationLookup::Failed("Network error".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transaction_create_failed() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            input_mode: InputMode::TransactionForm,
+            form_state: Some(TransactionFormState::new("acc1".to_string(), "YYYY-MM-DD")),
+            ..Default::default()
+        }))];
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionCreateFailed {
+                error: AppError::validation("Validation error: amount required"),
+            }
+            .into(),
+        );
+
+        if let Screen::Transactions(trans_state) = state.current_screen() {
+            // Form should still be open
+            assert_eq!(trans_state.input_mode, InputMode::TransactionForm);
+            // Error should be set
+            let Some(ref form) = trans_state.form_state else {
+                panic!("Expected form_state to be Some");
+            };
+            assert_eq!(
+                form.validation_error,
+                Some(AppError::validation("Validation error: amount required"))
+            );
+        } else {
+            panic!("Expected Transactions screen");
+        }
+    }
+
+    #[test]
+    fn test_transaction_updated_full() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![create_test_transaction(
+                "t1",
+                "2024-01-10",
+                -5000,
+                ReconciliationStatus::Cleared,
+            )],
+            input_mode: InputMode::TransactionForm,
+            form_state: Some(TransactionFormState::new("acc1".to_string(), "YYYY-MM-DD")),
+            ..Default::default()
+        }))];
+
+        let updated_transaction =
+            create_test_transaction("t1", "2024-01-15", -7000, ReconciliationStatus::Uncleared);
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionUpdatedFull {
+                transaction: updated_transaction,
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(trans_state.transactions.len(), 1);
+        assert_eq!(trans_state.transactions[0].amount, Milliunits::new(-7000));
+        assert_eq!(
+            trans_state.transactions[0].date,
+            NaiveDate::parse_from_str("2024-01-15", "%Y-%m-%d").unwrap()
+        );
+        // Form should be closed
+        assert_eq!(trans_state.input_mode, InputMode::Normal);
+        assert!(trans_state.form_state.is_none());
+    }
+
+    #[test]
+    fn test_transactions_delta_merge() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![
+                create_test_transaction("t1", "2024-01-15", -5000, ReconciliationStatus::Cleared),
+                create_test_transaction("t2", "2024-01-10", -3000, ReconciliationStatus::Uncleared),
+            ],
+            ..Default::default()
+        }))];
+
+        // Delta: update t1, delete t2, add t3
+        let updated_t1 =
+            create_test_transaction("t1", "2024-01-15", -6000, ReconciliationStatus::Cleared);
+        let mut deleted_t2 =
+            create_test_transaction("t2", "2024-01-10", -3000, ReconciliationStatus::Uncleared);
+        deleted_t2.deleted = true;
+        let new_t3 =
+            create_test_transaction("t3", "2024-01-20", -2000, ReconciliationStatus::Cleared);
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsDeltaLoaded {
+                delta: vec![updated_t1, deleted_t2, new_t3],
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            // Should have t3 (newest) and t1, t2 should be deleted
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(trans_state.transactions.len(), 2);
+        // Verify order: newest first (2024-01-20), then 2024-01-15
+        assert_eq!(
+            trans_state.transactions[0].date,
+            NaiveDate::parse_from_str("2024-01-20", "%Y-%m-%d").unwrap()
+        );
+        assert_eq!(
+            trans_state.transactions[1].date,
+            NaiveDate::parse_from_str("2024-01-15", "%Y-%m-%d").unwrap()
+        );
+        assert_eq!(trans_state.transactions[1].amount, Milliunits::new(-6000)); // updated amount
+    }
+
+    #[test]
+    fn test_delta_holds_back_changes_to_rows_with_local_edits() {
+        let mut state = AppState::new();
+        let mut flagged =
+            create_test_transaction("t1", "2024-01-15", -5000, ReconciliationStatus::Cleared);
+        flagged.flag_color = Some(FlagColor::Red);
+        let editing =
+            create_test_transaction("t2", "2024-01-10", -3000, ReconciliationStatus::Cleared);
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![flagged, editing.clone()],
+            unsaved: [test_transaction_id_str("t1")].into(),
+            input_mode: InputMode::TransactionForm,
+            form_state: Some(TransactionFormState::from_transaction(
+                &editing,
+                "YYYY-MM-DD",
+                None,
+            )),
+            ..Default::default()
+        }))];
+
+        let server_t1 =
+            create_test_transaction("t1", "2024-01-15", -5500, ReconciliationStatus::Cleared);
+        let server_t2 =
+            create_test_transaction("t2", "2024-01-10", -3000, ReconciliationStatus::Reconciled);
+        let new_t3 =
+            create_test_transaction("t3", "2024-01-20", -2000, ReconciliationStatus::Cleared);
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsDeltaLoaded {
+                delta: vec![server_t1, server_t2, new_t3],
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen_mut() else {
+            panic!("Expected Transactions screen");
+        };
+        // New rows merge; both changed rows wait for a decision
+        assert_eq!(trans_state.transactions.len(), 3);
+        assert_eq!(trans_state.conflicts.len(), 2);
+        assert_eq!(
+            trans_state.conflicts[0].differing_fields(),
+            [TransactionField::Amount, TransactionField::Flag]
+        );
+
+        trans_state.resolve_conflict(true);
+        let t1 = trans_state
+            .transactions
+            .iter()
+            .find(|t| t.id.to_string() == test_transaction_id_str("t1"))
+            .unwrap();
+        assert_eq!(t1.flag_color, Some(FlagColor::Red));
+
+        // Taking the server's version of the row being edited closes the form
+        trans_state.resolve_conflict(false);
+        assert!(trans_state.conflicts.is_empty());
+        assert!(trans_state.form_state.is_none());
+        assert_eq!(trans_state.input_mode, InputMode::Normal);
+        let t2 = trans_state
+            .transactions
+            .iter()
+            .find(|t| t.id.to_string() == test_transaction_id_str("t2"))
+            .unwrap();
+        assert_eq!(t2.cleared, ReconciliationStatus::Reconciled);
+    }
+
+    #[test]
+    fn test_older_transactions_appended_below() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            transactions: vec![create_test_transaction(
+                "t1",
+                "2024-03-15",
+                -5000,
+                ReconciliationStatus::Cleared,
+            )],
+            loaded_since: NaiveDate::from_ymd_opt(2024, 3, 1),
+            table_state: RefCell::new(TableState::default().with_selected(0)),
+            ..Default::default()
+        }))];
+
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::OlderTransactionsLoaded {
+                transactions: vec![
+                    create_test_transaction(
+                        "t2",
+                        "2024-01-10",
+                        -3000,
+                        ReconciliationStatus::Cleared,
+                    ),
+                    create_test_transaction(
+                        "t3",
+                        "2024-02-20",
+                        -2000,
+                        ReconciliationStatus::Cleared,
+                    ),
+                ],
+                loaded_since: NaiveDate::from_ymd_opt(2024, 1, 1),
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        let ids: Vec<_> = trans_state
+            .transactions
+            .iter()
+            .map(|t| t.id.clone())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                TransactionId::new(test_uuid("t1")),
+                TransactionId::new(test_uuid("t3")),
+                TransactionId::new(test_uuid("t2")),
+            ]
+        );
+        assert_eq!(
+            trans_state.loaded_since,
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+        );
+        // The selected row doesn't move
+        assert_eq!(trans_state.table_state.borrow().selected(), Some(0));
+    }
+
+    #[test]
+    fn test_scheduled_transactions_delta_merge() {
+        use crate::state::ScheduledTransactionsState;
+        use ynab_api::endpoints::scheduled_transactions::{Frequency, ScheduledTransaction};
+
+        let scheduled = |id: &str, account: &str, date_next: &str| ScheduledTransaction {
+            id: test_uuid(id),
+            date_first: NaiveDate::parse_from_str("2024-01-01", "%Y-%m-%d").unwrap(),
+            date_next: NaiveDate::parse_from_str(date_next, "%Y-%m-%d").unwrap(),
+            frequency: Frequency::Monthly,
+            amount: Milliunits::new(-10000),
+            memo: None,
+            flag_color: None,
+            account_id: test_uuid(account),
+            payee_id: None,
+            category_id: None,
+            transfer_account_id: None,
+            deleted: false,
+            account_name: account.to_string(),
+            payee_name: None,
+            category_name: None,
+            subtransactions: vec![],
+        };
+
+        let mut state = AppState::new();
+        let mut scheduled_state =
+            ScheduledTransactionsState::new(Some(test_uuid("checking").to_string()));
+        scheduled_state.scheduled_transactions = vec![
+            scheduled("rent", "checking", "2024-03-01"),
+            scheduled("gym", "checking", "2024-02-15"),
+        ];
+        state.history = vec![Screen::ScheduledTransactions(scheduled_state)];
+
+        // Delta: delete rent, add a schedule on another account and one on this account
+        let mut deleted_rent = scheduled("rent", "checking", "2024-03-01");
+        deleted_rent.deleted = true;
+        reduce_data_event(
+            &mut state,
+            ScheduledEvent::ScheduledTransactionsDeltaLoaded {
+                delta: vec![
+                    deleted_rent,
+                    scheduled("card", "credit", "2024-02-01"),
+                    scheduled("phone", "checking", "2024-02-10"),
+                ],
+            }
+            .into(),
+        );
+
+        let Screen::ScheduledTransactions(scheduled_state) = state.current_screen() else {
+            panic!("Expected ScheduledTransactions screen");
+        };
+        assert_eq!(scheduled_state.loading, LoadingState::Loaded);
+        let visible: Vec<Uuid> = scheduled_state.visible().iter().map(|s| s.id).collect();
+        assert_eq!(visible, vec![test_uuid("phone"), test_uuid("gym")]);
+    }
+
+    // ============================================================================
+    // Payees and Categories Tests
+    // ============================================================================
+
+    #[test]
+    fn test_payees_loaded() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::default())];
+
+        let payees = vec![Payee {
+            id: Uuid::new_v4(),
+            name: "Grocery Store".to_string(),
+            transfer_account_id: None,
+            deleted: false,
+        }];
+
+        reduce_data_event(
+            &mut state,
+            PayeeEvent::PayeesLoaded {
+                payees: payees.clone(),
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(trans_state.payees.len(), 1);
+        assert_eq!(trans_state.payees[0].name, "Grocery Store");
+    }
+
+    #[test]
+    fn test_merged_payees_are_hidden_from_suggestions() {
+        use crate::payee_cleanup::{PayeeCluster, PayeeMerge, PayeeRef};
+
+        let payee = |id: &str, name: &str| Payee {
+            id: test_uuid(id),
+            name: name.to_string(),
+            transfer_account_id: None,
+            deleted: false,
+        };
+        let member = |p: &Payee| PayeeRef {
+            payee_id: p.id.to_string(),
+            name: p.name.clone(),
+        };
+        let (amazon, amzn) = (payee("amazon", "Amazon"), payee("amzn", "AMZN Mktp"));
+        let mut state = AppState::new();
+        state
+            .history
+            .push(Screen::PayeeCleanup(crate::state::PayeeCleanupState {
+                clusters: vec![PayeeCluster {
+                    canonical_name: "Amazon".to_string(),
+                    members: vec![member(&amazon), member(&amzn)],
+                }],
+                included: vec![true],
+                ..Default::default()
+            }));
+
+        reduce_data_event(
+            &mut state,
+            PayeeEvent::PayeesMerged {
+                merged: vec![PayeeMerge {
+                    target: member(&amazon),
+                    name: "Amazon".to_string(),
+                    duplicates: vec![member(&amzn)],
+                }],
+                transactions_moved: 4,
+                failed: 0,
+            }
+            .into(),
+        );
+
+        let Screen::PayeeCleanup(cleanup_state) = state.current_screen() else {
+            panic!("Expected PayeeCleanup screen");
+        };
+        assert!(cleanup_state.clusters.is_empty());
+        assert_eq!(
+            state.budget_settings.hidden_payee_ids,
+            vec![amzn.id.to_string()]
+        );
+
+        state.history.push(Screen::Transactions(Box::default()));
+        reduce_data_event(
+            &mut state,
+            PayeeEvent::PayeesLoaded {
+                payees: vec![amazon, amzn],
+            }
+            .into(),
+        );
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        let names: Vec<&str> = trans_state.payees.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Amazon"]);
+    }
+
+    #[test]
+    fn test_categories_loaded() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::default())];
+
+        let categories = vec![Category {
+            id: Uuid::new_v4(),
+            category_group_id: Uuid::new_v4(),
+            category_group_name: Some("Monthly".to_string()),
+            name: "Groceries".to_string(),
+            hidden: false,
+            original_category_group_id: None,
+            note: None,
+            budgeted: 0.into(),
+            activity: 0.into(),
+            balance: 0.into(),
+            goal_type: None,
+            goal_creation_month: None,
+            goal_target: None,
+            goal_target_month: None,
+            goal_percentage_complete: None,
+            goal_months_to_budget: None,
+            goal_under_funded: None,
+            goal_overall_funded: None,
+            goal_overall_left: None,
+            goal_snoozed_at: None,
+            deleted: false,
+        }];
+
+        reduce_data_event(
+            &mut state,
+            PlanEvent::CategoriesLoaded {
+                categories: categories.clone(),
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(trans_state.categories.len(), 1);
+        assert_eq!(trans_state.categories[0].name, "Groceries");
+    }
+
+    #[test]
+    fn test_plan_gives_transactions_screen_category_balances() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::default())];
+
+        let groceries = create_test_category("groceries", "Monthly", 300_000, 120_000);
+        let month = MonthDetail {
+            month: "2025-01-01".to_string(),
+            note: None,
+            income: 0.into(),
+            budgeted: 300_000.into(),
+            activity: (-180_000).into(),
+            to_be_budgeted: 0.into(),
+            age_of_money: None,
+            deleted: false,
+            categories: vec![groceries.clone()],
+        };
+
+        reduce_data_event(
+            &mut state,
+            PlanEvent::PlanCacheLoaded {
+                month,
+                categories: vec![groceries],
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(
+            trans_state
+                .category_balances
+                .get(&test_uuid("groceries").to_string()),
+            Some(&120_000)
+        );
+    }
+
+    #[test]
+    fn test_plan_rows_group_categories_with_totals() {
+        let mut plan_state = PlanState {
+            categories: vec![
+                create_test_category("rent", "Bills", 1_000_000, 0),
+                create_test_category("games", "Fun", 50_000, 20_000),
+                create_test_category("power", "Bills", 100_000, 40_000),
+            ],
+            ..Default::default()
+        };
+
+        let summary = |plan_state: &PlanState| {
+            plan_state
+                .rows()
+                .iter()
+                .map(|row| match row {
+                    PlanRow::Group(group) => format!("{} {}", group.name, group.balance),
+                    PlanRow::Category(category) => category.name.clone(),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            summary(&plan_state),
+            ["Bills 40000", "rent", "power", "Fun 20000", "games"]
+        );
+
+        plan_state.toggle_group(&test_uuid("Bills").to_string());
+        assert_eq!(summary(&plan_state), ["Bills 40000", "Fun 20000", "games"]);
+        assert_eq!(plan_state.num_items(), 3);
+
+        // Starred categories get a group of their own at the top too
+        plan_state.favorite_ids = vec![test_uuid("games").to_string()];
+        assert_eq!(
+            summary(&plan_state),
+            [
+                "★ Favorites 20000",
+                "games",
+                "Bills 40000",
+                "Fun 20000",
+                "games"
+            ]
+        );
+        assert_eq!(plan_state.visible_categories().len(), 1);
+    }
+
+    #[test]
+    fn test_comparison_month_shows_what_changed_since() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Plan(Box::new(PlanState {
+            categories: vec![
+                create_test_category("rent", "Bills", 1_000_000, 0),
+                create_test_category("power", "Bills", 120_000, 40_000),
+            ],
+            comparison: Some(PlanComparison::new("2024-12-01".to_string())),
+            ..Default::default()
+        }))];
+        let month = |month: &str, categories: Vec<Category>| PlanEvent::ComparisonMonthLoaded {
+            month: MonthDetail {
+                month: month.to_string(),
+                note: None,
+                income: 0.into(),
+                budgeted: 0.into(),
+                activity: 0.into(),
+                to_be_budgeted: 0.into(),
+                age_of_money: None,
+                deleted: false,
+                categories: categories.clone(),
+            },
+            categories,
+        };
+
+        // A month that's no longer compared against is dropped
+        reduce_data_event(&mut state, month("2024-11-01", vec![]).into());
+        let Screen::Plan(plan_state) = state.current_screen() else {
+            panic!("Expected Plan screen");
+        };
+        let comparison = plan_state.comparison.as_ref().unwrap();
+        assert_eq!(comparison.deltas(&plan_state.categories[0]), None);
+
+        // Power didn't exist yet, so all of it is new
+        reduce_data_event(
+            &mut state,
+            month(
+                "2024-12-01",
+                vec![create_test_category("rent", "Bills", 900_000, 0)],
+            )
+            .into(),
+        );
+        let Screen::Plan(plan_state) = state.current_screen() else {
+            panic!("Expected Plan screen");
+        };
+        let comparison = plan_state.comparison.as_ref().unwrap();
+        assert_eq!(
+            comparison.deltas(&plan_state.categories[0]),
+            Some((100_000, -100_000, 0))
+        );
+        assert_eq!(
+            comparison.group_deltas(&plan_state.categories),
+            Some((220_000, -180_000, 40_000))
+        );
+    }
+
+    #[test]
+    fn test_moved_category_lands_next_to_the_group_it_left() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Plan(Box::new(PlanState {
+            categories: vec![
+                create_test_category("rent", "Bills", 0, 0),
+                create_test_category("power", "Bills", 0, 0),
+                create_test_category("games", "Fun", 0, 0),
+                create_test_category("books", "Fun", 0, 0),
+            ],
+            ..Default::default()
+        }))];
+        let Screen::Plan(plan_state) = state.current_screen() else {
+            panic!("Expected Plan screen");
+        };
+        let power = test_uuid("power").to_string();
+        assert_eq!(
+            plan_state.adjacent_group(&power, true),
+            Some((test_uuid("Fun"), "Fun".to_string()))
+        );
+        assert_eq!(plan_state.adjacent_group(&power, false), None);
+
+        let mut moved = create_test_category("power", "Fun", 0, 0);
+        moved.category_group_id = test_uuid("Fun");
+        reduce_data_event(
+            &mut state,
+            PlanEvent::CategoryMoved {
+                category: moved,
+                forward: true,
+            }
+            .into(),
+        );
+
+        let Screen::Plan(plan_state) = state.current_screen() else {
+            panic!("Expected Plan screen");
+        };
+        let names: Vec<_> = plan_state
+            .rows()
+            .iter()
+            .map(|row| match row {
+                PlanRow::Group(group) => group.name.to_string(),
+                PlanRow::Category(category) => category.name.clone(),
+            })
+            .collect();
+        assert_eq!(names, ["Bills", "rent", "Fun", "power", "games", "books"]);
+        assert_eq!(plan_state.table_state.borrow().selected(), Some(3));
+        assert_eq!(state.notice.as_deref(), Some("Moved power to Fun"));
+    }
+
+    #[test]
+    fn test_health_checklist_starts_on_the_first_problem() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Plan(Box::new(PlanState {
+            categories: vec![
+                create_test_category("rent", "Bills", 0, 0),
+                create_test_category("games", "Fun", 0, -5_000),
+            ],
+            collapsed_groups: [test_uuid("Fun").to_string()].into(),
+            health: Some(HealthChecklistState::default()),
+            ..Default::default()
+        }))];
+        let games = test_uuid("games").to_string();
+
+        reduce_data_event(
+            &mut state,
+            PlanEvent::HealthChecklistLoaded {
+                items: vec![
+                    HealthItem::Approval { unapproved: 0 },
+                    HealthItem::Overspending {
+                        categories: vec![(games.clone(), "games".to_string())],
+                    },
+                ],
+            }
+            .into(),
+        );
+
+        let Screen::Plan(plan_state) = state.current_screen_mut() else {
+            panic!("Expected Plan screen");
+        };
+        let health = plan_state.health.as_ref().unwrap();
+        assert_eq!(health.loading, LoadingState::Loaded);
+        assert!(matches!(
+            health.selected_item(),
+            Some(HealthItem::Overspending { .. })
+        ));
+
+        // Fixing it unfolds the group to select the category
+        plan_state.reveal_category(&games);
+        assert!(matches!(
+            plan_state.selected_row(),
+            Some(PlanRow::Category(c)) if c.id.to_string() == games
+        ));
+    }
+
+    #[test]
+    fn test_move_money_offers_every_other_category() {
+        let categories = vec![
+            create_test_category("rent", "Bills", 1_000_000, 0),
+            create_test_category("power", "Bills", 100_000, 40_000),
+            create_test_category("games", "Fun", 50_000, 20_000),
+        ];
+
+        let mut form = MoveMoneyState::new(&categories[1], &categories, None);
+        assert_eq!(form.amount(), Some(40_000));
+        let names = |form: &MoveMoneyState| {
+            form.destinations
+                .iter()
+                .map(|c| c.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&form), ["rent", "games"]);
+
+        form.destination_input = "fun".to_string();
+        form.update_destinations(&categories);
+        assert_eq!(names(&form), ["games"]);
+    }
+
+    #[test]
+    fn test_archived_categories_move_to_the_archive_group() {
+        let mut state = AppState::new();
+        let hobby = create_test_category("hobby", "Fun", 0, 0);
+        let games = create_test_category("games", "Fun", 0, 0);
+        state.history = vec![Screen::Plan(Box::new(PlanState {
+            categories: vec![hobby.clone(), games],
+            ..Default::default()
+        }))];
+
+        let mut archived = hobby;
+        archived.category_group_id = test_uuid("archive");
+        archived.category_group_name = Some("Archive".to_string());
+        reduce_data_event(
+            &mut state,
+            PlanEvent::CategoriesArchived {
+                categories: vec![archived],
+                group: "Archive".to_string(),
+                failed: 1,
+            }
+            .into(),
+        );
+
+        let Screen::Plan(plan_state) = state.current_screen() else {
+            panic!("Expected Plan screen");
+        };
+        assert_eq!(
+            plan_state.categories[0].category_group_name.as_deref(),
+            Some("Archive")
+        );
+        assert_eq!(
+            plan_state.categories[0].category_group_id,
+            test_uuid("archive")
+        );
+        assert!(!plan_state.categories[0].hidden);
+        assert_eq!(
+            plan_state.categories[1].category_group_name.as_deref(),
+            Some("Fun")
+        );
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("Moved 1 category to Archive. 1 couldn't be moved")
+        );
+
+        reduce_data_event(&mut state, PlanEvent::ArchiveGroupMissing.into());
+        assert!(state.notice.unwrap().contains("Archive"));
+    }
+
+    #[test]
+    fn test_failed_budget_update_rolls_back_available() {
+        let mut state = AppState::new();
+        let mut power = create_test_category("power", "Bills", 100_000, 40_000);
+        // Optimistically moved 30.00 out of the category
+        power.budgeted = 70_000.into();
+        power.balance = 10_000.into();
+        state.history = vec![Screen::Plan(Box::new(PlanState {
+            categories: vec![power],
+            ..Default::default()
+        }))];
+
+        reduce_data_event(
+            &mut state,
+            PlanEvent::CategoryBudgetUpdateFailed {
+                category_id: test_uuid("power").to_string(),
+                original_budgeted: 100_000,
+                new_budgeted: 70_000,
+                error: AppError::new(ErrorKind::Network, "Network error"),
+            }
+            .into(),
+        );
+
+        let Screen::Plan(plan_state) = state.current_screen() else {
+            panic!("Expected Plan screen");
+        };
+        assert_eq!(i64::from(plan_state.categories[0].budgeted), 100_000);
+        assert_eq!(i64::from(plan_state.categories[0].balance), 40_000);
+        assert_eq!(
+            state.notice.as_deref(),
+            Some("Couldn't change the assigned amount: Network error")
+        );
+    }
+
+    #[test]
+    fn test_failed_goal_update_puts_the_old_goal_back() {
+        let mut state = AppState::new();
+        let mut trip = create_test_category("trip", "Savings", 50_000, 200_000);
+        trip.goal_type = Some("TBD".to_string());
+        trip.goal_target = Some(1_000_000.into());
+        trip.goal_target_month = Some("2025-12-01".to_string());
+        let original = trip.clone();
+        // Optimistically pushed out and raised
+        trip.goal_target = Some(1_500_000.into());
+        trip.goal_target_month = Some("2026-06-01".to_string());
+        state.history = vec![Screen::Plan(Box::new(PlanState {
+            categories: vec![trip],
+            ..Default::default()
+        }))];
+
+        reduce_data_event(
+            &mut state,
+            PlanEvent::CategoryGoalUpdateFailed {
+                original,
+                error: AppError::new(ErrorKind::Network, "Network error"),
+            }
+            .into(),
+        );
+
+        let Screen::Plan(plan_state) = state.current_screen() else {
+            panic!("Expected Plan screen");
+        };
+        let trip = &plan_state.categories[0];
+        assert_eq!(trip.goal_target.map(i64::from), Some(1_000_000));
+        assert_eq!(trip.goal_target_month.as_deref(), Some("2025-12-01"));
+        assert_eq!(i64::from(trip.balance), 200_000);
+        assert!(state.notice.is_some_and(|n| n.contains("Network error")));
+    }
+
+    // ============================================================================
+    // Error Handling Tests
+    // ============================================================================
+
+    #[test]
+    fn test_load_error_on_accounts_screen() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Accounts(AccountsState {
+            accounts_loading: LoadingState::Loading(throbber_widgets_tui::ThrobberState::default()),
+            ..Default::default()
+        })];
+
+        reduce_data_event(
+            &mut state,
+            DataEvent::LoadError {
+                error: AppError::new(ErrorKind::Network, "Network error"),
+            },
+        );
+
+        let Screen::Accounts(accounts_state) = state.current_screen() else {
+            panic!("Expected Accounts screen");
+        };
+        match &accounts_state.accounts_loading {
+            LoadingState::Error(error) => {
+                assert_eq!(error.kind, ErrorKind::Network);
+                assert_eq!(error.message, "Network error");
+            }
+            _ => panic!("Expected Error loading state"),
+        }
+    }
+
+    #[test]
+    fn test_load_error_on_budgets_screen() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Budgets(BudgetsState {
+            budgets_loading: LoadingState::Loading(throbber_widgets_tui::ThrobberState::default()),
+            ..Default::default()
+        })];
+
+        reduce_data_event(
+            &mut state,
+            DataEvent::LoadError {
+                error: AppError::new(ErrorKind::Server, "API timeout"),
+            },
+        );
+
+        let Screen::Budgets(budgets_state) = state.current_screen() else {
+            panic!("Expected Budgets screen");
+        };
+        match &budgets_state.budgets_loading {
+            LoadingState::Error(error) => assert_eq!(error.message, "API timeout"),
+            _ => panic!("Expected Error loading state"),
+        }
+    }
+
+    #[test]
+    fn test_load_error_lands_on_open_drill_down() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Reports(ReportsState {
+            loading: LoadingState::Loaded,
+            drill_down: Some(ReportDrillDown {
+                loading: LoadingState::Loading(throbber_widgets_tui::ThrobberState::default()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })];
+
+        reduce_data_event(
+            &mut state,
+            DataEvent::LoadError {
+                error: AppError::new(ErrorKind::Network, "Network error"),
+            },
+        );
+
+        let Screen::Reports(reports_state) = state.current_screen() else {
+            panic!("Expected Reports screen");
+        };
+        assert_eq!(reports_state.loading, LoadingState::Loaded);
+        assert!(matches!(
+            reports_state.drill_down.as_ref().unwrap().loading,
+            LoadingState::Error(..)
+        ));
+        assert_eq!(
+            state.current_error().map(|error| error.message.as_str()),
+            Some("Network error")
+        );
+    }
+
+    #[test]
+    fn test_cache_recovered_shows_notice() {
+        let mut state = AppState::new();
+
+        reduce_data_event(&mut state, DataEvent::CacheRecovered);
+
+        assert!(state.notice.is_some());
+    }
+
+    #[test]
+    fn test_rate_limit_warning_is_shown_until_quota_frees_up() {
+        let mut state = AppState::new();
+        let rate_limit = ynab_api::RateLimit::parse("190/200").unwrap();
+
+        reduce_data_event(
+            &mut state,
+            DataEvent::RateLimitWarning {
+                rate_limit: Some(rate_limit),
+            },
+        );
+        assert_eq!(state.rate_limit_warning.map(|r| r.remaining()), Some(10));
+
+        reduce_data_event(&mut state, DataEvent::RateLimitWarning { rate_limit: None });
+        assert!(state.rate_limit_warning.is_none());
+    }
+
+    #[test]
+    fn test_queued_write_closes_the_form_until_it_syncs() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            input_mode: InputMode::TransactionForm,
+            form_state: Some(TransactionFormState::new("acc1".to_string(), "YYYY-MM-DD")),
+            ..Default::default()
+        }))];
+
+        reduce_data_event(&mut state, DataEvent::WriteQueued { pending: 2 });
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert!(trans_state.form_state.is_none());
+        assert_eq!(trans_state.input_mode, InputMode::Normal);
+        assert_eq!(state.pending_writes, 2);
+
+        // Still offline: the count stays and nothing new is announced
+        state.notice = None;
+        reduce_data_event(
+            &mut state,
+            DataEvent::PendingWritesSynced {
+                synced: 0,
+                pending: 2,
+            },
+        );
+        assert!(state.notice.is_none());
+
+        reduce_data_event(
+            &mut state,
+            DataEvent::PendingWritesSynced {
+                synced: 2,
+                pending: 0,
+            },
+        );
+        assert_eq!(state.pending_writes, 0);
+        assert!(state.notice.is_some());
+    }
+
+    #[test]
+    fn test_restored_transaction_is_listed_and_history_follows_its_new_id() {
+        let mut state = AppState::new();
+        state.current_account_id = Some(test_uuid("test_account").to_string());
+        state.history = vec![Screen::Transactions(Box::default())];
+        let deleted = create_test_transaction(
+            "deleted",
+            "2025-01-10",
+            -5000,
+            ReconciliationStatus::Cleared,
+        );
+        state.undo_history.record(UndoEntry::TransactionDeleted {
+            budget_id: "budget".to_string(),
+            transaction: Box::new(deleted.clone()),
+        });
+        state.undo_history.undo();
+
+        let mut recreated = deleted.clone();
+        recreated.id = TransactionId::new(test_uuid("recreated"));
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionRestored {
+                original_id: deleted.id.to_string(),
+                transaction: recreated.clone(),
+            }
+            .into(),
+        );
+
+        let Screen::Transactions(trans_state) = state.current_screen() else {
+            panic!("Expected Transactions screen");
+        };
+        assert_eq!(trans_state.transactions, vec![recreated.clone()]);
+        // Redoing deletes the recreated transaction, not the original id
+        assert_eq!(
+            state.undo_history.redo(),
+            Some(UndoEntry::TransactionDeleted {
+                budget_id: "budget".to_string(),
+                transaction: Box::new(recreated),
+            })
+        );
+    }
+
+    #[test]
+    fn test_transaction_presets_combine_with_text_filter() {
+        let mut unapproved = create_test_transaction(
+            "unapproved",
+            "2025-01-10",
+            -5000,
+            ReconciliationStatus::Uncleared,
+        );
+        unapproved.approved = false;
+        unapproved.payee_name = Some("Corner Shop".to_string());
+        let mut cleared_unapproved = unapproved.clone();
+        cleared_unapproved.id = TransactionId::new(test_uuid("cleared_unapproved"));
+        cleared_unapproved.cleared = ReconciliationStatus::Cleared;
+        let mut other_payee = unapproved.clone();
+        other_payee.id = TransactionId::new(test_uuid("other_payee"));
+        other_payee.payee_name = Some("Gas Station".to_string());
+        let approved = create_test_transaction(
+            "approved",
+            "2025-01-11",
+            -5000,
+            ReconciliationStatus::Uncleared,
+        );
+
+        let mut trans_state = TransactionsState {
+            transactions: vec![unapproved, cleared_unapproved, other_payee, approved],
+            ..Default::default()
+        };
+        trans_state.toggle_preset(TransactionPreset::Unapproved);
+        assert_eq!(trans_state.filtered_transactions().len(), 3);
+
+        trans_state.toggle_preset(TransactionPreset::Uncleared);
+        assert_eq!(trans_state.filtered_transactions().len(), 2);
+
+        trans_state.filter_query = "corner".to_string();
+        let filtered = trans_state.filtered_transactions();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(
+            filtered[0].id.to_string(),
+            test_transaction_id_str("unapproved")
+        );
+
+        // Toggling again switches a preset back off
+        trans_state.toggle_preset(TransactionPreset::Unapproved);
+        trans_state.toggle_preset(TransactionPreset::Uncleared);
+        assert!(trans_state.presets.is_empty());
+        assert_eq!(trans_state.filtered_transactions().len(), 2);
+    }
+
+    #[test]
+    fn test_filter_finds_the_parts_of_a_split() {
+        let mut split =
+            create_test_transaction("split", "2025-01-10", -80000, ReconciliationStatus::Cleared);
+        split.payee_name = Some("Costco".to_string());
+        let part = |index: usize, category: &str, amount: i64, deleted: bool| SubTransaction {
+            id: format!("{}_{}", split.id, index),
+            transaction_id: split.id.clone(),
+            amount: Milliunits::from(amount),
+            memo: Some("bulk".to_string()),
+            payee_id: None,
+            payee_name: None,
+            category_id: None,
+            category_name: Some(category.to_string()),
+            transfer_account_id: None,
+            deleted,
+        };
+        split.subtransactions = vec![
+            part(0, "Groceries", -50000, false),
+            part(1, "Household", -30000, false),
+            part(2, "Clothing", -12345, true),
+        ];
+        let other =
+            create_test_transaction("other", "2025-01-11", -5000, ReconciliationStatus::Cleared);
+
+        let mut trans_state = TransactionsState {
+            transactions: vec![split, other],
+            ..Default::default()
+        };
+        let matches = |trans_state: &mut TransactionsState, query: &str| {
+            trans_state.filter_query = query.to_string();
+            trans_state.filtered_transactions().len()
+        };
+
+        assert_eq!(matches(&mut trans_state, "groceries"), 1);
+        assert_eq!(matches(&mut trans_state, "bulk"), 1);
+        assert_eq!(matches(&mut trans_state, "30.00"), 1);
+        // Deleted parts are gone from the split
+        assert_eq!(matches(&mut trans_state, "clothing"), 0);
+        assert_eq!(matches(&mut trans_state, "123.45"), 0);
+    }
+
+    #[test]
+    fn test_filter_tokens_narrow_by_date_amount_and_status() {
+        let mut rent = create_test_transaction(
+            "rent",
+            "2025-01-01",
+            -1_200_000,
+            ReconciliationStatus::Cleared,
+        );
+        rent.payee_name = Some("Landlord".to_string());
+        let mut coffee = create_test_transaction(
+            "coffee",
+            "2025-01-20",
+            -4500,
+            ReconciliationStatus::Uncleared,
+        );
+        coffee.payee_name = Some("Cafe".to_string());
+        coffee.flag_color = Some(FlagColor::Red);
+        let mut paycheck = create_test_transaction(
+            "pay",
+            "2025-02-01",
+            3_000_000,
+            ReconciliationStatus::Reconciled,
+        );
+        paycheck.payee_name = Some("Employer".to_string());
+
+        let mut trans_state = TransactionsState {
+            transactions: vec![rent, coffee, paycheck],
+            show_reconciled_transactions: true,
+            ..Default::default()
+        };
+        let matches = |trans_state: &mut TransactionsState, query: &str| {
+            trans_state.filter_query = query.to_string();
+            let mut payees: Vec<_> = trans_state
+                .filtered_transactions()
+                .iter()
+                .filter_map(|t| t.payee_name.clone())
+                .collect();
+            payees.sort();
+            payees.join(" ")
+        };
+
+        assert_eq!(
+            matches(&mut trans_state, "since:2025-01-02"),
+            "Cafe Employer"
+        );
+        assert_eq!(matches(&mut trans_state, "before:2025-01"), "");
+        assert_eq!(
+            matches(&mut trans_state, "before:2025-02-01 <-100"),
+            "Landlord"
+        );
+        assert_eq!(matches(&mut trans_state, ">0"), "Employer");
+        assert_eq!(
+            matches(&mut trans_state, "cleared:yes"),
+            "Employer Landlord"
+        );
+        assert_eq!(matches(&mut trans_state, "cleared:reconciled"), "Employer");
+        assert_eq!(matches(&mut trans_state, "flag:red"), "Cafe");
+        assert_eq!(matches(&mut trans_state, "flag:none landlord"), "Landlord");
+        // Still typing the date
+        assert_eq!(
+            matches(&mut trans_state, "since:2025-0"),
+            "Cafe Employer Landlord"
+        );
+    }
+
+    #[test]
+    fn test_running_balances_start_from_the_account_balance() {
+        let mut state = AppState::new();
+        state.history = vec![Screen::Transactions(Box::new(TransactionsState {
+            account_balance: Some(100_000),
+            ..Default::default()
+        }))];
+        let since = NaiveDate::from_ymd_opt(2025, 1, 1);
+        let recent = vec![
+            create_test_transaction("pay", "2025-01-05", 50_000, ReconciliationStatus::Cleared),
+            create_test_transaction("rent", "2025-01-10", -30_000, ReconciliationStatus::Cleared),
+        ];
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::TransactionsLoaded {
+                transactions: recent,
+                loaded_since: since,
+            }
+            .into(),
+        );
+        let balance = |state: &AppState, id: &str| {
+            let Screen::Transactions(trans_state) = state.current_screen() else {
+                panic!("Expected Transactions screen");
+            };
+            trans_state
+                .running_balances()
+                .get(&test_transaction_id_str(id))
+                .copied()
+        };
+        assert_eq!(balance(&state, "rent"), Some(100_000));
+        assert_eq!(balance(&state, "pay"), Some(130_000));
+
+        // Loading older history doesn't move the newer rows' balances
+        reduce_data_event(
+            &mut state,
+            TransactionEvent::OlderTransactionsLoaded {
+                transactions: vec![create_test_transaction(
+                    "coffee",
+                    "2024-12-20",
+                    -5_000,
+                    ReconciliationStatus::Cleared,
+                )],
+                loaded_since: NaiveDate::from_ymd_opt(2024, 12, 1),
+            }
+            .into(),
+        );
+        assert_eq!(balance(&state, "coffee"), Some(80_000));
+        assert_eq!(balance(&state, "rent"), Some(100_000));
+    }
+
+    #[test]
+    fn test_transaction_sort_orders_by_column_and_direction() {
+        let mut rent =
+            create_test_transaction("rent", "2025-01-01", -150000, ReconciliationStatus::Cleared);
+        rent.payee_name = Some("landlord".to_string());
+        let mut coffee = create_test_transaction(
+            "coffee",
+            "2025-01-10",
+            -4000,
+            ReconciliationStatus::Uncleared,
+        );
+        coffee.payee_name = Some("Cafe".to_string());
+        let salary = create_test_transaction(
+            "salary",
+            "2025-01-05",
+            300000,
+            ReconciliationStatus::Reconciled,
+        );
+        let mut trans_state = TransactionsState {
+            transactions: vec![rent, coffee, salary],
+            ..Default::default()
+        };
+        let order = |trans_state: &TransactionsState| -> Vec<String> {
+            trans_state
+                .filtered_transactions()
+                .iter()
+                .map(|t| t.id.to_string())
+                .collect()
+        };
+        let ids = |names: [&str; 3]| -> Vec<String> {
+            names.iter().map(|n| test_transaction_id_str(n)).collect()
+        };
+
+        assert_eq!(order(&trans_state), ids(["coffee", "salary", "rent"]));
+
+        trans_state.sort = trans_state.sort.next_column();
+        assert_eq!(trans_state.sort.column, TransactionSortColumn::Amount);
+        assert_eq!(order(&trans_state), ids(["rent", "coffee", "salary"]));
+
+        // Payee names compare without case; missing names go last
+        trans_state.sort = trans_state.sort.next_column();
+        assert_eq!(order(&trans_state), ids(["coffee", "rent", "salary"]));
+
+        trans_state.sort = trans_state.sort.reversed();
+        assert_eq!(order(&trans_state), ids(["salary", "rent", "coffee"]));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::events::PayeeEvent;
+use crate::i18n::{t, tn};
+use crate::state::{autocomplete, AppState, LoadingState, PayeeCleanupState, Scrollable};
+use crate::ui::screens::Screen;
+
+/// Apply a payee event
+pub fn reduce_payee_event(state: &mut AppState, event: PayeeEvent) {
+    match event {
+        // Payees loaded (for transaction creation)
+        PayeeEvent::PayeesLoaded { mut payees } => {
+            let hidden = &state.budget_settings.hidden_payee_ids;
+            payees.retain(|p| !hidden.contains(&p.id.to_string()));
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.payees = payees;
+
+                // Update filtered payees in form if form is open
+                if let Some(ref mut form) = transactions_state.form_state {
+                    form.filtered_payees =
+                        autocomplete::filter_payees(&transactions_state.payees, &form.payee);
+                }
+            }
+        }
+
+        PayeeEvent::PayeeClustersLoaded { clusters } => {
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                // Everything starts ticked; the review is for opting out
+                cleanup_state.included = vec![true; clusters.len()];
+                let selected = (!clusters.is_empty()).then_some(0);
+                cleanup_state.clusters = clusters;
+                cleanup_state.loading = LoadingState::Loaded;
+                cleanup_state.table_state.borrow_mut().select(selected);
+            }
+        }
+
+        PayeeEvent::PayeesRenamed { payees, failed } => {
+            tracing::info!("Renamed {} payees ({} failed)", payees.len(), failed);
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                // Drop groups that are fully renamed; anything that failed stays for a retry
+                for member in cleanup_state
+                    .clusters
+                    .iter_mut()
+                    .flat_map(|c| c.members.iter_mut())
+                {
+                    if let Some(payee) = payees.iter().find(|p| p.id.to_string() == member.payee_id)
+                    {
+                        member.name = payee.name.clone();
+                    }
+                }
+                let (clusters, included): (Vec<_>, Vec<_>) = cleanup_state
+                    .clusters
+                    .drain(..)
+                    .zip(cleanup_state.included.drain(..))
+                    .filter(|(cluster, _)| cluster.renames().next().is_some())
+                    .unzip();
+                cleanup_state.clusters = clusters;
+                cleanup_state.included = included;
+                cleanup_state.loading = LoadingState::Loaded;
+                clamp_selection(cleanup_state);
+            }
+        }
+
+        PayeeEvent::PayeesMerged {
+            merged,
+            transactions_moved,
+            failed,
+        } => {
+            tracing::info!(
+                "Merged {} payee groups, moving {} transactions ({} failed)",
+                merged.len(),
+                transactions_moved,
+                failed
+            );
+            // The loader saved these; keep the in-memory settings in step
+            let hidden = &mut state.budget_settings.hidden_payee_ids;
+            for duplicate in merged.iter().flat_map(|m| &m.duplicates) {
+                if !hidden.contains(&duplicate.payee_id) {
+                    hidden.push(duplicate.payee_id.clone());
+                }
+            }
+            let mut notice = tn("notice-payees-merged", transactions_moved)
+                .replace("{groups}", &merged.len().to_string());
+            if failed > 0 {
+                notice.push_str(
+                    &t("notice-payees-merge-failed").replace("{failed}", &failed.to_string()),
+                );
+            }
+            state.notice = Some(notice);
+
+            if let Screen::PayeeCleanup(cleanup_state) = state.current_screen_mut() {
+                // Merged groups are done; failed ones stay for a retry
+                let (clusters, included): (Vec<_>, Vec<_>) = cleanup_state
+                    .clusters
+                    .drain(..)
+                    .zip(cleanup_state.included.drain(..))
+                    .filter(|(cluster, _)| {
+                        !merged.iter().any(|m| cluster.members.contains(&m.target))
+                    })
+                    .unzip();
+                cleanup_state.clusters = clusters;
+                cleanup_state.included = included;
+                cleanup_state.loading = LoadingState::Loaded;
+                clamp_selection(cleanup_state);
+            }
+        }
+    }
+}
+
+/// Keep the selection on a row after groups were removed
+fn clamp_selection(cleanup_state: &mut PayeeCleanupState) {
+    let len = cleanup_state.num_items();
+    let mut table_state = cleanup_state.table_state.borrow_mut();
+    match table_state.selected() {
+        _ if len == 0 => table_state.select(None),
+        Some(i) if i >= len => table_state.select(Some(len - 1)),
+        _ => {}
+    }
+}
@@ -0,0 +1,269 @@
+use crate::events::PlanEvent;
+use crate::i18n::{t, tn};
+use crate::state::{autocomplete, AppState, LoadingState, Scrollable};
+use crate::ui::screens::Screen;
+use ratatui::widgets::TableState;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use ynab_api::endpoints::categories::Category;
+
+/// Apply a plan and category event
+pub fn reduce_plan_event(state: &mut AppState, event: PlanEvent) {
+    match event {
+        // Plan cache loaded
+        PlanEvent::PlanCacheLoaded { month, categories } => match state.current_screen_mut() {
+            Screen::Plan(plan_state) => {
+                plan_state.month = Some(month);
+                plan_state.categories = categories;
+                plan_state.plan_loading = LoadingState::Loaded;
+                plan_state.table_state = RefCell::new(TableState::default().with_selected(0));
+            }
+            Screen::Transactions(transactions_state) => {
+                transactions_state.category_balances = category_balances(&categories);
+            }
+            _ => {}
+        },
+
+        // Plan data loaded
+        PlanEvent::PlanLoaded { month, categories } => match state.current_screen_mut() {
+            Screen::Plan(plan_state) => {
+                plan_state.month = Some(month);
+                plan_state.categories = categories;
+                plan_state.plan_loading = LoadingState::Loaded;
+            }
+            Screen::Transactions(transactions_state) => {
+                transactions_state.category_balances = category_balances(&categories);
+            }
+            _ => {}
+        },
+
+        // Categories loaded (for transaction creation)
+        PlanEvent::CategoriesLoaded { categories } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions_state.categories = categories;
+
+                // Update filtered categories in form if form is open
+                if let Some(ref mut form) = transactions_state.form_state {
+                    form.filtered_categories = autocomplete::filter_categories(
+                        &transactions_state.categories,
+                        &form.category,
+                    );
+                }
+            }
+        }
+
+        PlanEvent::ComparisonMonthLoaded { month, categories } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                // The compared month may have moved on while this loaded
+                if let Some(ref mut comparison) = plan_state.comparison {
+                    if comparison.month == month.month {
+                        comparison.categories = categories;
+                        comparison.loading = LoadingState::Loaded;
+                    }
+                }
+            }
+        }
+
+        // Budget category updates
+        PlanEvent::CategoryBudgetUpdated { category } => {
+            state.undo_history.settle(&category.id.to_string(), true);
+            tracing::info!(
+                "Category {} budget updated to {}",
+                category.id,
+                category.budgeted
+            );
+            // Update the category in the plan state if we're on the plan screen
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(existing) = plan_state
+                    .categories
+                    .iter_mut()
+                    .find(|c| c.id == category.id)
+                {
+                    *existing = category;
+                }
+            }
+        }
+
+        PlanEvent::CategoryGoalUpdated { category } => {
+            tracing::info!("Category {} goal updated", category.id);
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(existing) = plan_state
+                    .categories
+                    .iter_mut()
+                    .find(|c| c.id == category.id)
+                {
+                    copy_goal(&category, existing);
+                }
+            }
+        }
+
+        PlanEvent::CategoryGoalUpdateFailed { original, error } => {
+            tracing::error!(
+                "Failed to update category {} goal: {}. Rolling back",
+                original.id,
+                error
+            );
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(existing) = plan_state
+                    .categories
+                    .iter_mut()
+                    .find(|c| c.id == original.id)
+                {
+                    copy_goal(&original, existing);
+                }
+            }
+            super::reduce_action_failed(state, "notice-goal-update-failed", error);
+        }
+
+        PlanEvent::QuickBudgetHistoryLoaded { months } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.fill_quick_budget(&months);
+            }
+        }
+
+        PlanEvent::ArchiveSuggestionsLoaded { suggestions } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut review) = plan_state.archive_review {
+                    // Everything starts ticked; the review is for opting out
+                    review.included = vec![true; suggestions.len()];
+                    let selected = (!suggestions.is_empty()).then_some(0);
+                    review.suggestions = suggestions;
+                    review.loading = LoadingState::Loaded;
+                    review.table_state.borrow_mut().select(selected);
+                }
+            }
+        }
+
+        PlanEvent::HealthChecklistLoaded { items } => {
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                if let Some(ref mut health) = plan_state.health {
+                    // Start on the first thing that needs fixing
+                    let selected = items
+                        .iter()
+                        .position(|item| !item.passed())
+                        .or((!items.is_empty()).then_some(0));
+                    health.items = items;
+                    health.loading = LoadingState::Loaded;
+                    health.table_state.borrow_mut().select(selected);
+                }
+            }
+        }
+
+        PlanEvent::CategoriesArchived {
+            categories,
+            group,
+            failed,
+        } => {
+            tracing::info!(
+                "Moved {} categories to {} ({} failed)",
+                categories.len(),
+                group,
+                failed
+            );
+            let mut notice =
+                tn("notice-categories-archived", categories.len()).replace("{group}", &group);
+            if failed > 0 {
+                notice = format!(
+                    "{}. {}",
+                    notice,
+                    tn("notice-categories-not-archived", failed)
+                );
+            }
+            state.notice = Some(notice);
+
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                for category in categories {
+                    if let Some(existing) = plan_state
+                        .categories
+                        .iter_mut()
+                        .find(|c| c.id == category.id)
+                    {
+                        existing.category_group_id = category.category_group_id;
+                        existing.category_group_name = category.category_group_name;
+                    }
+                }
+                // Rows change groups, so keep the selection in range
+                let len = plan_state.num_items();
+                let mut table_state = plan_state.table_state.borrow_mut();
+                if let Some(i) = table_state.selected() {
+                    table_state.select((len > 0).then(|| i.min(len - 1)));
+                }
+            }
+        }
+
+        PlanEvent::ArchiveGroupMissing => {
+            state.notice = Some(t("notice-archive-group-missing").to_string());
+        }
+
+        PlanEvent::CategoryMoved { category, forward } => {
+            state.notice = Some(
+                t("notice-category-moved")
+                    .replace("{category}", &category.name)
+                    .replace(
+                        "{group}",
+                        category.category_group_name.as_deref().unwrap_or_default(),
+                    ),
+            );
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                plan_state.place_moved_category(category, forward);
+            }
+        }
+
+        PlanEvent::CategoryBudgetUpdateFailed {
+            category_id,
+            original_budgeted,
+            new_budgeted,
+            error,
+        } => {
+            state.undo_history.settle(&category_id, false);
+            tracing::error!(
+                "Failed to update category {} budget: {}. Rolling back to {}",
+                category_id,
+                error,
+                original_budgeted
+            );
+            // Rollback the optimistic update
+            if let Screen::Plan(plan_state) = state.current_screen_mut() {
+                use ynab_api::endpoints::Milliunits;
+                let delta: Milliunits = (new_budgeted - original_budgeted).into();
+
+                // Rollback category budgeted and available amounts
+                if let Some(category) = plan_state
+                    .categories
+                    .iter_mut()
+                    .find(|c| c.id.to_string() == category_id)
+                {
+                    category.budgeted = original_budgeted.into();
+                    category.balance -= delta;
+                }
+
+                // Rollback month summary (reverse the delta that was applied)
+                if let Some(ref mut month_detail) = plan_state.month {
+                    month_detail.budgeted -= delta;
+                    month_detail.to_be_budgeted += delta;
+                }
+            }
+            super::reduce_action_failed(state, "notice-budget-update-failed", error);
+        }
+    }
+}
+
+/// Take `from`'s goal, leaving the amounts for the month shown alone (the
+/// category endpoint reports the current month's)
+fn copy_goal(from: &Category, to: &mut Category) {
+    to.goal_type = from.goal_type.clone();
+    to.goal_target = from.goal_target;
+    to.goal_target_month = from.goal_target_month.clone();
+    to.goal_percentage_complete = from.goal_percentage_complete;
+    to.goal_months_to_budget = from.goal_months_to_budget;
+    to.goal_under_funded = from.goal_under_funded;
+    to.goal_overall_funded = from.goal_overall_funded;
+    to.goal_overall_left = from.goal_overall_left;
+}
+
+fn category_balances(categories: &[Category]) -> HashMap<String, i64> {
+    categories
+        .iter()
+        .map(|category| (category.id.to_string(), category.balance.into()))
+        .collect()
+}
@@ -0,0 +1,149 @@
+use crate::events::ReportEvent;
+use crate::reports::{first_of_month, ReportKind};
+use crate::state::{AppState, LoadingState, Scrollable};
+use crate::ui::screens::Screen;
+use chrono::Datelike;
+use ratatui::widgets::TableState;
+use std::cell::RefCell;
+
+/// Apply a report event
+pub fn reduce_report_event(state: &mut AppState, event: ReportEvent) {
+    match event {
+        ReportEvent::NetWorthLoaded { net_worth } => {
+            if let Screen::NetWorth(net_worth_state) = state.current_screen_mut() {
+                let selected = (!net_worth.by_type.is_empty()).then_some(0);
+                net_worth_state.net_worth = Some(net_worth);
+                net_worth_state.loading = LoadingState::Loaded;
+                let mut table_state = net_worth_state.table_state.borrow_mut();
+                if table_state.selected().is_none() {
+                    table_state.select(selected);
+                }
+            }
+        }
+
+        ReportEvent::VarianceReportLoaded { period, rows } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                // Ignore results for a period the user has already moved away from
+                if reports_state.kind == ReportKind::Variance && reports_state.period == period {
+                    reports_state.variance_rows = rows;
+                    reports_state.loading = LoadingState::Loaded;
+                    let len = reports_state.num_items();
+                    let mut table_state = reports_state.table_state.borrow_mut();
+                    match table_state.selected() {
+                        _ if len == 0 => table_state.select(None),
+                        Some(i) if i >= len => table_state.select(Some(len - 1)),
+                        None => table_state.select(Some(0)),
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        ReportEvent::VarianceTransactionsLoaded {
+            category_id,
+            transactions,
+        } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                if let Some(ref mut drill_down) = reports_state.drill_down {
+                    if drill_down.category_id == category_id {
+                        let selected = (!transactions.is_empty()).then_some(0);
+                        drill_down.transactions = transactions;
+                        drill_down.loading = LoadingState::Loaded;
+                        drill_down.table_state =
+                            RefCell::new(TableState::default().with_selected(selected));
+                    }
+                }
+            }
+        }
+
+        ReportEvent::CashFlowProjected { projection } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                // Drop projections for an account or horizon the user has moved away from
+                if reports_state.kind == ReportKind::CashFlow
+                    && reports_state.account_id.as_deref() == Some(projection.account_id.as_str())
+                    && reports_state.horizon == projection.horizon
+                {
+                    let selected = (!projection.negative_days().is_empty()).then_some(0);
+                    reports_state.cash_flow = Some(projection);
+                    reports_state.loading = LoadingState::Loaded;
+                    reports_state.table_state.borrow_mut().select(selected);
+                }
+            }
+        }
+
+        ReportEvent::SpendingBreakdownLoaded { breakdown } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                // Ignore results for a period or grouping the user has moved away from
+                if reports_state.kind == ReportKind::Spending
+                    && reports_state.period == breakdown.period
+                    && reports_state.spending_grouping == breakdown.grouping
+                {
+                    let selected = (!breakdown.rows.is_empty()).then_some(0);
+                    reports_state.spending = Some(breakdown);
+                    reports_state.loading = LoadingState::Loaded;
+                    reports_state.table_state.borrow_mut().select(selected);
+                }
+            }
+        }
+
+        ReportEvent::IncomeExpenseLoaded { report } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                if reports_state.kind == ReportKind::IncomeExpense {
+                    // Keep the selected month across refreshes
+                    let len = report.months.len();
+                    let refreshed = reports_state.income_expense.is_some();
+                    let selected = reports_state
+                        .table_state
+                        .borrow()
+                        .selected()
+                        .filter(|i| refreshed && *i < len)
+                        .or((len > 0).then_some(0));
+                    reports_state.table_state.borrow_mut().select(selected);
+                    reports_state.income_expense = Some(report);
+                    reports_state.loading = LoadingState::Loaded;
+                }
+            }
+        }
+
+        ReportEvent::GoalObligationsForecast { forecast } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                if reports_state.kind == ReportKind::Obligations {
+                    let selected = (!forecast.obligations.is_empty()).then_some(0);
+                    reports_state.obligations = Some(forecast);
+                    reports_state.loading = LoadingState::Loaded;
+                    reports_state.table_state.borrow_mut().select(selected);
+                }
+            }
+        }
+
+        ReportEvent::SpendingHeatmapLoaded { heatmap } => {
+            if let Screen::Reports(reports_state) = state.current_screen_mut() {
+                // Drop results for a month the user has already moved away from
+                if reports_state.kind == ReportKind::Heatmap
+                    && reports_state.heatmap_month == Some(heatmap.month)
+                {
+                    // A reload keeps the selected day. A new month starts on today
+                    // when it's in the month, otherwise on the same day number.
+                    let today = chrono::Local::now().date_naive();
+                    let len = heatmap.days.len();
+                    let reloaded = reports_state
+                        .heatmap
+                        .as_ref()
+                        .is_some_and(|h| h.month == heatmap.month);
+                    let selected = match reports_state.table_state.borrow().selected() {
+                        Some(i) if reloaded => i,
+                        _ if first_of_month(today) == heatmap.month => today.day0() as usize,
+                        Some(i) => i.min(len - 1),
+                        None => 0,
+                    };
+                    reports_state.heatmap = Some(heatmap);
+                    reports_state.loading = LoadingState::Loaded;
+                    reports_state
+                        .table_state
+                        .borrow_mut()
+                        .select(Some(selected));
+                }
+            }
+        }
+    }
+}
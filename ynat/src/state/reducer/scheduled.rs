@@ -0,0 +1,50 @@
+use super::clamp_selection;
+use crate::events::ScheduledEvent;
+use crate::state::{AppState, LoadingState};
+use crate::ui::screens::Screen;
+use ynab_api::endpoints::scheduled_transactions::ScheduledTransaction;
+
+/// Apply a scheduled transaction event
+pub fn reduce_scheduled_event(state: &mut AppState, event: ScheduledEvent) {
+    match event {
+        // Scheduled transactions loaded (from cache or API)
+        ScheduledEvent::ScheduledTransactionsLoaded {
+            scheduled_transactions,
+        } => {
+            if let Screen::ScheduledTransactions(scheduled_state) = state.current_screen_mut() {
+                scheduled_state.scheduled_transactions = scheduled_transactions;
+                scheduled_state.loading = LoadingState::Loaded;
+                clamp_selection(scheduled_state);
+            }
+        }
+
+        ScheduledEvent::ScheduledTransactionsDeltaLoaded { delta } => {
+            if let Screen::ScheduledTransactions(scheduled_state) = state.current_screen_mut() {
+                merge_scheduled_transactions_delta(
+                    &mut scheduled_state.scheduled_transactions,
+                    delta,
+                );
+                scheduled_state.loading = LoadingState::Loaded;
+                clamp_selection(scheduled_state);
+            }
+        }
+    }
+}
+
+fn merge_scheduled_transactions_delta(
+    scheduled_transactions: &mut Vec<ScheduledTransaction>,
+    delta: Vec<ScheduledTransaction>,
+) {
+    for delta_scheduled in delta {
+        if delta_scheduled.deleted {
+            scheduled_transactions.retain(|s| s.id != delta_scheduled.id);
+        } else if let Some(existing) = scheduled_transactions
+            .iter_mut()
+            .find(|s| s.id == delta_scheduled.id)
+        {
+            *existing = delta_scheduled;
+        } else {
+            scheduled_transactions.push(delta_scheduled);
+        }
+    }
+}
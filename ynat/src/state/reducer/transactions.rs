@@ -0,0 +1,524 @@
+use super::clamp_selection;
+use crate::events::TransactionEvent;
+use crate::i18n::tn;
+use crate::state::{AppState, InputMode, LastUsedEntry, LoadingState, PayeeLocationLookup};
+use crate::ui::screens::Screen;
+use ratatui::widgets::TableState;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use ynab_api::endpoints::transactions::Transaction;
+
+/// Apply a transaction event
+pub fn reduce_transaction_event(state: &mut AppState, event: TransactionEvent) {
+    match event {
+        // Transactions cache loaded
+        TransactionEvent::TransactionsCacheLoaded {
+            mut transactions,
+            loaded_since,
+        } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                // Sort in descending date order (most recent first)
+                transactions.sort_by(|a, b| b.date.cmp(&a.date));
+                transactions_state.transactions = transactions;
+                transactions_state.loaded_since = loaded_since;
+                transactions_state.reset_opening_balance();
+                transactions_state.transactions_loading = LoadingState::Loaded;
+                transactions_state.table_state =
+                    RefCell::new(TableState::default().with_selected(0));
+                transactions_state.apply_pending_focus();
+            }
+        }
+
+        // Transactions loaded from API
+        TransactionEvent::TransactionsLoaded {
+            mut transactions,
+            loaded_since,
+        } => {
+            // Sort in descending date order (most recent first)
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                transactions.sort_by(|a, b| b.date.cmp(&a.date));
+                transactions_state.transactions = transactions;
+                transactions_state.loaded_since = loaded_since;
+                transactions_state.reset_opening_balance();
+                transactions_state.transactions_loading = LoadingState::Loaded;
+                clamp_selection(transactions_state.as_ref());
+                transactions_state.apply_pending_focus();
+            }
+        }
+
+        // Older history appended below what's already listed
+        TransactionEvent::OlderTransactionsLoaded {
+            transactions,
+            loaded_since,
+        } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                let mut added = 0;
+                for transaction in transactions {
+                    if !transactions_state
+                        .transactions
+                        .iter()
+                        .any(|t| t.id == transaction.id)
+                    {
+                        added += i64::from(transaction.amount);
+                        transactions_state.transactions.push(transaction);
+                    }
+                }
+                // Older transactions come out of the balance before them
+                transactions_state.opening_balance = match loaded_since {
+                    None if !transactions_state.all_accounts => Some(0),
+                    _ => transactions_state.opening_balance.map(|b| b - added),
+                };
+                transactions_state
+                    .transactions
+                    .sort_by_key(|t| std::cmp::Reverse(t.date));
+                transactions_state.loaded_since = loaded_since;
+                transactions_state.transactions_loading = LoadingState::Loaded;
+                transactions_state.apply_pending_focus();
+            }
+        }
+
+        // Transactions delta loaded (merge into existing)
+        TransactionEvent::TransactionsDeltaLoaded { delta } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                let delta = transactions_state.hold_back_conflicts(delta);
+                merge_transactions_delta(&mut transactions_state.transactions, delta);
+                transactions_state.transactions_loading = LoadingState::Loaded;
+            }
+        }
+
+        // Transaction updated successfully
+        TransactionEvent::TransactionUpdated { transaction_id } => {
+            // Optimistic update already applied
+            tracing::debug!("Transaction {transaction_id} update confirmed by server");
+            state.undo_history.settle(&transaction_id, true);
+            forget_unsaved(state, &[transaction_id]);
+        }
+
+        // Transaction update failed - rollback optimistic update
+        TransactionEvent::TransactionUpdateFailed {
+            transaction_id,
+            original_status,
+            original_approved,
+            error,
+        } => {
+            tracing::warn!("Rolling back transaction update: {}", error);
+            state.undo_history.settle(&transaction_id, false);
+            forget_unsaved(state, std::slice::from_ref(&transaction_id));
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                if let Some(transaction) = transactions_state
+                    .transactions
+                    .iter_mut()
+                    .find(|t| t.id.to_string() == transaction_id)
+                {
+                    transaction.cleared = original_status;
+                    transaction.approved = original_approved;
+                    tracing::info!(
+                        "Rolled back transaction {} to status: {}",
+                        transaction_id,
+                        transaction.cleared
+                    );
+                }
+            }
+            super::reduce_action_failed(state, "notice-update-failed", error);
+        }
+
+        TransactionEvent::TransactionFlagUpdateFailed {
+            transaction_id,
+            original_flag,
+            error,
+        } => {
+            tracing::warn!("Rolling back transaction flag: {}", error);
+            forget_unsaved(state, std::slice::from_ref(&transaction_id));
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                if let Some(transaction) = transactions_state
+                    .transactions
+                    .iter_mut()
+                    .find(|t| t.id.to_string() == transaction_id)
+                {
+                    transaction.flag_color = original_flag;
+                }
+            }
+        }
+
+        TransactionEvent::TransactionApproved { .. } => {
+            // Already locally approved, nothing to do
+        }
+
+        TransactionEvent::TransactionApproveFailed {
+            transaction_id,
+            error,
+        } => {
+            tracing::warn!("Rolling back transaction approval: {}", error);
+            forget_unsaved(state, std::slice::from_ref(&transaction_id));
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                if let Some(transaction) = transactions_state
+                    .transactions
+                    .iter_mut()
+                    .find(|t| t.id.to_string() == transaction_id)
+                {
+                    transaction.approved = false;
+                    tracing::info!("Rolled back transaction {} to unapproved", transaction_id,);
+                }
+            }
+            super::reduce_action_failed(state, "notice-approve-transaction-failed", error);
+        }
+
+        TransactionEvent::TransactionsApproved { transaction_ids } => {
+            state.notice = Some(tn("notice-transactions-approved", transaction_ids.len()));
+            forget_unsaved(state, &transaction_ids);
+        }
+
+        TransactionEvent::TransactionsApproveFailed {
+            transaction_ids,
+            error,
+        } => {
+            tracing::warn!("Rolling back bulk approval: {}", error);
+            forget_unsaved(state, &transaction_ids);
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                for transaction in transactions_state
+                    .transactions
+                    .iter_mut()
+                    .filter(|t| transaction_ids.contains(&t.id.to_string()))
+                {
+                    transaction.approved = false;
+                }
+            }
+            super::reduce_action_failed(state, "notice-approve-failed", error);
+        }
+
+        TransactionEvent::TransactionsCategorized { transaction_ids } => {
+            state.notice = Some(tn("notice-transactions-categorized", transaction_ids.len()));
+            forget_unsaved(state, &transaction_ids);
+        }
+
+        TransactionEvent::CategorySuggestionAccepted { transaction_id } => {
+            // No notice, so the next suggestion can follow straight away
+            forget_unsaved(state, &[transaction_id]);
+        }
+
+        TransactionEvent::CategorySuggestionsLoaded { suggestions } => {
+            // Update every screen in history so suggestions survive navigating back
+            for screen in state.history.iter_mut() {
+                if let Screen::Transactions(transactions_state) = screen {
+                    transactions_state.category_suggestions = suggestions.clone();
+                }
+            }
+        }
+
+        TransactionEvent::TransactionsCategorizeFailed { originals, error } => {
+            tracing::warn!("Rolling back categories: {}", error);
+            let transaction_ids: Vec<String> = originals.iter().map(|t| t.id.to_string()).collect();
+            forget_unsaved(state, &transaction_ids);
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                for original in originals {
+                    if let Some(transaction) = transactions_state
+                        .transactions
+                        .iter_mut()
+                        .find(|t| t.id == original.id)
+                    {
+                        *transaction = original;
+                    }
+                }
+            }
+            super::reduce_action_failed(state, "notice-categorize-failed", error);
+        }
+
+        TransactionEvent::TransactionFlagsCleared { transaction_ids } => {
+            state.notice = Some(tn("notice-flags-cleared", transaction_ids.len()));
+            forget_unsaved(state, &transaction_ids);
+        }
+
+        TransactionEvent::TransactionFlagsClearFailed { originals, error } => {
+            tracing::warn!("Rolling back cleared flags: {}", error);
+            let transaction_ids: Vec<String> = originals.iter().map(|t| t.id.to_string()).collect();
+            forget_unsaved(state, &transaction_ids);
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                for original in originals {
+                    if let Some(transaction) = transactions_state
+                        .transactions
+                        .iter_mut()
+                        .find(|t| t.id == original.id)
+                    {
+                        transaction.flag_color = original.flag_color;
+                    }
+                }
+            }
+            super::reduce_action_failed(state, "notice-clear-flags-failed", error);
+        }
+
+        TransactionEvent::TransactionsImported { count } => {
+            state.notice = Some(tn("notice-transactions-imported", count));
+        }
+
+        TransactionEvent::TransactionsImportFailed { error } => {
+            super::reduce_action_failed(state, "notice-import-failed", error);
+        }
+
+        // Transaction created successfully
+        TransactionEvent::TransactionCreated { transaction } => {
+            // A split's category name isn't one that can be typed back in
+            let category = if transaction.subtransactions.is_empty() {
+                transaction.category_name.clone().unwrap_or_default()
+            } else {
+                String::new()
+            };
+            state.last_used_entries.insert(
+                transaction.account_id.to_string(),
+                LastUsedEntry {
+                    payee: transaction.payee_name.clone().unwrap_or_default(),
+                    category,
+                    cleared: transaction.cleared,
+                },
+            );
+
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                // Add new transaction to list (at the beginning after sorting)
+                transactions_state.transactions.push(transaction);
+                transactions_state
+                    .transactions
+                    .sort_by(|a, b| b.date.cmp(&a.date));
+
+                // Close form
+                transactions_state.input_mode = InputMode::Normal;
+                transactions_state.form_state = None;
+
+                // Reset table selection to first item
+                transactions_state.table_state =
+                    RefCell::new(TableState::default().with_selected(0));
+
+                tracing::info!("Transaction created successfully and added to list");
+            }
+        }
+
+        TransactionEvent::TransactionRestored {
+            original_id,
+            transaction,
+        } => {
+            state.undo_history.settle(&original_id, true);
+            state
+                .undo_history
+                .replace_transaction_id(&original_id, &transaction);
+            let current_account_id = state.current_account_id.clone();
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                let in_view = transactions_state.all_accounts
+                    || current_account_id == Some(transaction.account_id.to_string());
+                if in_view {
+                    transactions_state.transactions.push(transaction);
+                    transactions_state
+                        .transactions
+                        .sort_by_key(|t| std::cmp::Reverse(t.date));
+                }
+            }
+        }
+
+        // Transaction creation failed
+        TransactionEvent::TransactionCreateFailed { error } => {
+            tracing::error!("Transaction creation failed: {}", error);
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                // Keep form open, show error
+                if let Some(ref mut form) = transactions_state.form_state {
+                    form.validation_error = Some(error);
+                }
+            }
+        }
+
+        TransactionEvent::TransactionRestoreFailed { original_id, error } => {
+            tracing::error!("Failed to restore transaction {}: {}", original_id, error);
+            state.undo_history.settle(&original_id, false);
+            super::reduce_load_error(state, error);
+        }
+
+        // Transaction deletion confirmed by API
+        TransactionEvent::TransactionDeleted { transaction_id } => {
+            state.undo_history.settle(&transaction_id, true);
+            // Optimistic removal already done, just log confirmation
+            tracing::debug!(
+                "Transaction {} deletion confirmed by server",
+                transaction_id
+            );
+        }
+
+        // Transaction deletion failed
+        TransactionEvent::TransactionDeleteFailed {
+            transaction_id,
+            error,
+        } => {
+            tracing::error!("Failed to delete transaction {}: {}", transaction_id, error);
+            state.undo_history.settle(&transaction_id, false);
+            // Transaction was already removed optimistically; the notice asks
+            // for a refresh to bring it back
+            super::reduce_action_failed(state, "notice-delete-failed", error);
+        }
+
+        // Transaction edited (full update) confirmed by API
+        TransactionEvent::TransactionUpdatedFull { transaction } => {
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                // Find and replace the transaction in the list
+                if let Some(idx) = transactions_state
+                    .transactions
+                    .iter()
+                    .position(|t| t.id == transaction.id)
+                {
+                    transactions_state.transactions[idx] = transaction;
+
+                    // Re-sort by date (transactions may have moved if date changed)
+                    transactions_state
+                        .transactions
+                        .sort_by(|a, b| b.date.cmp(&a.date));
+                }
+
+                // Close form
+                transactions_state.input_mode = InputMode::Normal;
+                transactions_state.form_state = None;
+
+                tracing::info!("Transaction updated and form closed");
+            }
+        }
+
+        // Transaction update failed
+        TransactionEvent::TransactionUpdateFullFailed {
+            transaction_id,
+            error,
+        } => {
+            tracing::error!("Failed to update transaction {}: {}", transaction_id, error);
+            if let Screen::Transactions(transactions_state) = state.current_screen_mut() {
+                // Keep form open, show error
+                if let Some(ref mut form) = transactions_state.form_state {
+                    form.validation_error = Some(error);
+                }
+            }
+        }
+
+        // Transactions reconciled successfully
+        TransactionEvent::TransactionsReconciled { transaction_ids } => {
+            // Optimistic update already applied, just log confirmation
+            tracing::info!(
+                "{}",
+                crate::i18n::tn("transactions-reconciled", transaction_ids.len())
+            );
+        }
+
+        // Transactions reconciliation failed - no rollback since optimistic update already applied
+        TransactionEvent::TransactionsReconcileFailed { error } => {
+            // The notice asks for a refresh to reload the real statuses
+            tracing::error!("Reconciliation failed: {}", error);
+            super::reduce_action_failed(state, "notice-reconcile-failed", error);
+        }
+
+        TransactionEvent::ReimbursementsLoaded { entries, matches } => {
+            let awaiting: HashSet<String> = entries
+                .iter()
+                .filter(|e| e.is_outstanding())
+                .map(|e| e.transaction_id.clone())
+                .collect();
+
+            // Update every screen in history so markers survive navigating back
+            for screen in state.history.iter_mut() {
+                match screen {
+                    Screen::Transactions(transactions_state) => {
+                        transactions_state.awaiting_reimbursement = awaiting.clone();
+                    }
+                    Screen::Reimbursements(reimbursements_state) => {
+                        reimbursements_state.entries = entries.clone();
+                        reimbursements_state.matches = matches.clone();
+                        reimbursements_state.loading = LoadingState::Loaded;
+                        clamp_selection(reimbursements_state);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        TransactionEvent::FollowUpCreated => {
+            state.notice = Some(crate::i18n::t("notice-follow-up-created").to_string());
+        }
+
+        TransactionEvent::FollowUpFailed { error } => {
+            super::reduce_action_failed(state, "notice-follow-up-failed", error);
+        }
+
+        TransactionEvent::PayeeLocationOpened {
+            latitude,
+            longitude,
+        } => {
+            state.notice = Some(
+                crate::i18n::t("notice-payee-location-opened")
+                    .replace("{lat}", &latitude)
+                    .replace("{lon}", &longitude),
+            );
+        }
+
+        TransactionEvent::PayeeLocationLoaded { payee_id, place } => {
+            set_payee_location(
+                state,
+                &payee_id,
+                place.map_or(PayeeLocationLookup::Missing, PayeeLocationLookup::Found),
+            );
+        }
+
+        TransactionEvent::PayeeLocationLoadFailed { payee_id, error } => {
+            set_payee_location(state, &payee_id, PayeeLocationLookup::Failed(error.message));
+        }
+
+        TransactionEvent::PayeeLocationFailed { error } => {
+            super::reduce_action_failed(state, "notice-payee-location-failed", error);
+        }
+
+        TransactionEvent::TransactionsExported { path } => {
+            state.export_path = Some(path);
+        }
+
+        TransactionEvent::TransactionsExportFailed { error } => {
+            super::reduce_action_failed(state, "notice-export-failed", error);
+        }
+    }
+}
+
+/// Fill in the detail popup's location, unless it was closed or moved on to
+/// another payee while YNAB was asked
+fn set_payee_location(state: &mut AppState, payee_id: &str, location: PayeeLocationLookup) {
+    if let Some(detail) = state.transaction_detail.as_mut().filter(|detail| {
+        detail
+            .transaction
+            .payee_id
+            .map(|id| id.to_string())
+            .as_deref()
+            == Some(payee_id)
+    }) {
+        detail.location = location;
+    }
+}
+
+/// Merge transactions delta into existing transactions list
+/// Stop treating these transactions as having unsaved changes, on every
+/// transactions screen they might be listed on
+fn forget_unsaved(state: &mut AppState, transaction_ids: &[String]) {
+    for screen in state.history.iter_mut() {
+        if let Screen::Transactions(transactions_state) = screen {
+            for id in transaction_ids {
+                transactions_state.unsaved.remove(id);
+            }
+        }
+    }
+}
+
+fn merge_transactions_delta(transactions: &mut Vec<Transaction>, delta: Vec<Transaction>) {
+    for delta_transaction in delta {
+        if delta_transaction.deleted {
+            // Remove deleted transactions
+            transactions.retain(|t| t.id != delta_transaction.id);
+        } else if let Some(existing) = transactions
+            .iter_mut()
+            .find(|t| t.id == delta_transaction.id)
+        {
+            // Update existing transaction
+            *existing = delta_transaction;
+        } else {
+            // Add new transaction
+            transactions.push(delta_transaction);
+        }
+    }
+
+    // Sort in descending date order (most recent first)
+    transactions.sort_by(|a, b| b.date.cmp(&a.date));
+}
@@ -0,0 +1,377 @@
+//! Undo/redo history for changes that are otherwise hard to take back.
+//!
+//! Each entry records both sides of a change, so undoing applies the old
+//! value and redoing applies the new one again through the same API calls.
+//! An entry only moves between the stacks once YNAB confirms those calls, so
+//! a failed undo can be tried again rather than leaving a redo behind. When
+//! only some calls of an entry succeed, the entry is split so each stack
+//! holds just the part that still applies.
+
+use ynab_api::endpoints::transactions::{ReconciliationStatus, Transaction};
+
+/// Entries kept on each stack before the oldest are dropped
+const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UndoEntry {
+    /// A transaction was deleted; undo recreates it from these contents
+    TransactionDeleted {
+        budget_id: String,
+        transaction: Box<Transaction>,
+    },
+    /// A category's assigned amount was edited
+    BudgetEdited {
+        budget_id: String,
+        month: String,
+        category_id: String,
+        from: i64,
+        to: i64,
+    },
+    /// Several categories' assigned amounts were set at once by a quick-budget
+    /// command, as (category id, from, to)
+    BudgetsEdited {
+        budget_id: String,
+        month: String,
+        edits: Vec<(String, i64, i64)>,
+    },
+    /// `amount` was moved from one category's assigned amount to another's;
+    /// the `*_budgeted` amounts are from before the move
+    MoneyMoved {
+        budget_id: String,
+        month: String,
+        source_id: String,
+        source_budgeted: i64,
+        destination_id: String,
+        destination_budgeted: i64,
+        amount: i64,
+    },
+    /// A transaction's cleared status was toggled
+    ClearedToggled {
+        budget_id: String,
+        transaction_id: String,
+        from: ReconciliationStatus,
+        to: ReconciliationStatus,
+    },
+}
+
+impl UndoEntry {
+    /// Ids of the transactions or categories the entry's API calls report on
+    fn targets(&self) -> Vec<String> {
+        match self {
+            Self::TransactionDeleted { transaction, .. } => vec![transaction.id.to_string()],
+            Self::BudgetEdited { category_id, .. } => vec![category_id.clone()],
+            Self::BudgetsEdited { edits, .. } => {
+                edits.iter().map(|(id, _, _)| id.clone()).collect()
+            }
+            Self::MoneyMoved {
+                source_id,
+                destination_id,
+                ..
+            } => vec![source_id.clone(), destination_id.clone()],
+            Self::ClearedToggled { transaction_id, .. } => vec![transaction_id.clone()],
+        }
+    }
+
+    /// Split an entry with several targets into the edits for `targets` and
+    /// the rest. `None` for entries with a single target.
+    fn split(&self, targets: &[String]) -> Option<(UndoEntry, UndoEntry)> {
+        let (budget_id, month, edits) = match self {
+            Self::BudgetsEdited {
+                budget_id,
+                month,
+                edits,
+            } => (budget_id, month, edits.clone()),
+            Self::MoneyMoved {
+                budget_id,
+                month,
+                source_id,
+                source_budgeted,
+                destination_id,
+                destination_budgeted,
+                amount,
+            } => (
+                budget_id,
+                month,
+                vec![
+                    (
+                        source_id.clone(),
+                        *source_budgeted,
+                        source_budgeted - amount,
+                    ),
+                    (
+                        destination_id.clone(),
+                        *destination_budgeted,
+                        destination_budgeted + amount,
+                    ),
+                ],
+            ),
+            _ => return None,
+        };
+        let (covered, rest) = edits
+            .into_iter()
+            .partition(|(category_id, _, _)| targets.contains(category_id));
+        let entry = |edits| Self::BudgetsEdited {
+            budget_id: budget_id.clone(),
+            month: month.clone(),
+            edits,
+        };
+        Some((entry(covered), entry(rest)))
+    }
+}
+
+/// An undo or redo sent to YNAB and waiting on its results
+#[derive(Debug, Clone)]
+struct Pending {
+    entry: UndoEntry,
+    undo: bool,
+    /// Targets whose calls haven't answered yet
+    waiting: Vec<String>,
+    /// Targets whose calls failed
+    failed: Vec<String>,
+    /// A new change was recorded meanwhile, so an undo can't be redone
+    superseded: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UndoHistory {
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    pending: Option<Pending>,
+}
+
+impl UndoHistory {
+    /// Record a new change. Anything that was undone can no longer be redone.
+    pub fn record(&mut self, entry: UndoEntry) {
+        self.redo.clear();
+        if let Some(pending) = &mut self.pending {
+            pending.superseded = true;
+        }
+        push_capped(&mut self.undo, entry);
+    }
+
+    /// Whether an undo or redo is still waiting on YNAB
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Take the latest change to undo. It moves to the redo stack once its
+    /// calls succeed (see [`Self::settle`]).
+    pub fn undo(&mut self) -> Option<UndoEntry> {
+        self.take(true)
+    }
+
+    /// Take the latest undone change to apply again. It moves back to the
+    /// undo stack once its calls succeed.
+    pub fn redo(&mut self) -> Option<UndoEntry> {
+        self.take(false)
+    }
+
+    fn take(&mut self, undo: bool) -> Option<UndoEntry> {
+        if self.pending.is_some() {
+            return None;
+        }
+        let entry = if undo {
+            self.undo.pop()
+        } else {
+            self.redo.pop()
+        }?;
+        self.pending = Some(Pending {
+            entry: entry.clone(),
+            undo,
+            waiting: entry.targets(),
+            failed: Vec::new(),
+            superseded: false,
+        });
+        Some(entry)
+    }
+
+    /// Note how a call for `target`, a transaction or category id, went. Once
+    /// every call of the pending undo or redo has answered, the entry moves to
+    /// the other stack if they all succeeded, or goes back to where it was
+    /// taken from if they all failed. Otherwise the succeeded edits move and
+    /// the failed ones go back.
+    pub fn settle(&mut self, target: &str, ok: bool) {
+        let Some(pending) = &mut self.pending else {
+            return;
+        };
+        let Some(index) = pending.waiting.iter().position(|t| t == target) else {
+            return;
+        };
+        let target = pending.waiting.remove(index);
+        if !ok {
+            pending.failed.push(target);
+        }
+        if !pending.waiting.is_empty() {
+            return;
+        }
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        if pending.failed.is_empty() {
+            self.finish(pending, true);
+        } else if let Some((failed, succeeded)) = pending.entry.split(&pending.failed) {
+            self.finish(
+                Pending {
+                    entry: succeeded,
+                    ..pending.clone()
+                },
+                true,
+            );
+            self.finish(
+                Pending {
+                    entry: failed,
+                    ..pending
+                },
+                false,
+            );
+        } else {
+            self.finish(pending, false);
+        }
+    }
+
+    /// Offline, a pending undo or redo is done once its calls are queued to
+    /// send later, like any other change
+    pub fn settle_queued(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            self.finish(pending, true);
+        }
+    }
+
+    fn finish(&mut self, pending: Pending, ok: bool) {
+        match (ok, pending.undo) {
+            (true, true) if pending.superseded => {}
+            (true, true) | (false, false) => push_capped(&mut self.redo, pending.entry),
+            (true, false) | (false, true) => push_capped(&mut self.undo, pending.entry),
+        }
+    }
+
+    /// Point entries at a transaction's new id after undo recreated it
+    pub fn replace_transaction_id(&mut self, old_id: &str, new: &Transaction) {
+        let pending = self.pending.as_mut().map(|pending| &mut pending.entry);
+        for entry in self
+            .undo
+            .iter_mut()
+            .chain(self.redo.iter_mut())
+            .chain(pending)
+        {
+            match entry {
+                UndoEntry::TransactionDeleted { transaction, .. }
+                    if transaction.id.to_string() == old_id =>
+                {
+                    **transaction = new.clone();
+                }
+                UndoEntry::ClearedToggled { transaction_id, .. } if transaction_id == old_id => {
+                    *transaction_id = new.id.to_string();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn push_capped(stack: &mut Vec<UndoEntry>, entry: UndoEntry) {
+    if stack.len() == MAX_ENTRIES {
+        stack.remove(0);
+    }
+    stack.push(entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleared_toggle(id: &str) -> UndoEntry {
+        UndoEntry::ClearedToggled {
+            budget_id: "budget".to_string(),
+            transaction_id: id.to_string(),
+            from: ReconciliationStatus::Uncleared,
+            to: ReconciliationStatus::Cleared,
+        }
+    }
+
+    #[test]
+    fn undone_changes_can_be_redone_until_something_new_happens() {
+        let mut history = UndoHistory::default();
+        history.record(cleared_toggle("a"));
+        history.record(cleared_toggle("b"));
+
+        assert_eq!(history.undo(), Some(cleared_toggle("b")));
+        history.settle("b", true);
+        assert_eq!(history.undo(), Some(cleared_toggle("a")));
+        history.settle("a", true);
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(cleared_toggle("a")));
+        history.settle("a", true);
+
+        history.record(cleared_toggle("c"));
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.undo(), Some(cleared_toggle("c")));
+    }
+
+    #[test]
+    fn failed_undos_stay_on_the_undo_stack() {
+        let mut history = UndoHistory::default();
+        history.record(cleared_toggle("a"));
+
+        assert_eq!(history.undo(), Some(cleared_toggle("a")));
+        // One at a time, until YNAB answers
+        assert!(history.is_pending());
+        assert_eq!(history.undo(), None);
+        // Results for other rows don't count
+        history.settle("b", false);
+        assert!(history.is_pending());
+
+        history.settle("a", false);
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.undo(), Some(cleared_toggle("a")));
+    }
+
+    fn budgets_edited(edits: &[(&str, i64, i64)]) -> UndoEntry {
+        UndoEntry::BudgetsEdited {
+            budget_id: "budget".to_string(),
+            month: "2026-03-01".to_string(),
+            edits: edits
+                .iter()
+                .map(|(id, from, to)| (id.to_string(), *from, *to))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn a_partly_failed_undo_only_keeps_what_is_left_to_undo() {
+        let mut history = UndoHistory::default();
+        history.record(UndoEntry::MoneyMoved {
+            budget_id: "budget".to_string(),
+            month: "2026-03-01".to_string(),
+            source_id: "groceries".to_string(),
+            source_budgeted: 500_000,
+            destination_id: "dining".to_string(),
+            destination_budgeted: 100_000,
+            amount: 50_000,
+        });
+
+        assert!(history.undo().is_some());
+        history.settle("groceries", true);
+        // Still waiting on the other category
+        assert!(history.is_pending());
+        history.settle("dining", false);
+        assert!(!history.is_pending());
+
+        // Undoing again only retries the category that failed
+        assert_eq!(
+            history.undo(),
+            Some(budgets_edited(&[("dining", 100_000, 150_000)]))
+        );
+        history.settle("dining", true);
+        // And redo applies both again
+        assert_eq!(
+            history.redo(),
+            Some(budgets_edited(&[("dining", 100_000, 150_000)]))
+        );
+        history.settle("dining", true);
+        assert_eq!(
+            history.redo(),
+            Some(budgets_edited(&[("groceries", 500_000, 450_000)]))
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use crate::state::TransactionFormState;
 use crate::ui::utils as ui_utils;
+use crate::utils::money;
 use chrono::NaiveDate;
 use uuid::Uuid;
 use ynab_api::endpoints::{
     categories::Category,
     payees::Payee,
-    transactions::{NewSubTransaction, NewTransaction, TransactionUpdate},
+    transactions::{
+        NewSubTransaction, NewTransaction, ReconciliationStatus, Transaction, TransactionUpdate,
+    },
 };
 
 /// Validate and build a NewTransaction from form state
@@ -24,7 +27,7 @@ pub fn validate_and_build_transaction(
     let date = validate_date(&form.date, date_format)?;
 
     // Validate amount
-    let amount_milliunits = validate_amount(&form.amount)?;
+    let amount_milliunits = validate_amount(&form.amount, form)?;
 
     // Resolve payee (ID or name)
     let (payee_id, payee_name) = resolve_payee(&form.payee, payees);
@@ -53,9 +56,50 @@ pub fn validate_and_build_transaction(
         approved: Some(true),
         flag_color: form.flag_color,
         subtransactions: None,
+        import_id: None,
     })
 }
 
+/// Build an uncleared copy of an existing transaction in `account_id` on `date`
+///
+/// Ids are copied as-is, so unlike going through the form this doesn't need
+/// payees and categories to be loaded.
+pub fn build_duplicate_transaction(
+    transaction: &Transaction,
+    account_id: Uuid,
+    date: NaiveDate,
+) -> NewTransaction {
+    let subtransactions: Vec<NewSubTransaction> = transaction
+        .subtransactions
+        .iter()
+        .filter(|s| !s.deleted)
+        .map(|s| NewSubTransaction {
+            amount: s.amount,
+            category_id: s.category_id,
+            memo: s.memo.clone(),
+        })
+        .collect();
+
+    NewTransaction {
+        account_id,
+        date: date.format("%Y-%m-%d").to_string(),
+        amount: transaction.amount,
+        payee_id: transaction.payee_id,
+        payee_name: transaction
+            .payee_id
+            .is_none()
+            .then(|| transaction.payee_name.clone())
+            .flatten(),
+        category_id: transaction.category_id,
+        memo: transaction.memo.clone(),
+        cleared: Some(ReconciliationStatus::Uncleared),
+        approved: Some(true),
+        flag_color: transaction.flag_color,
+        subtransactions: (!subtransactions.is_empty()).then_some(subtransactions),
+        import_id: None,
+    }
+}
+
 /// Validate and build a split transaction (transaction with subtransactions)
 fn validate_and_build_split_transaction(
     form: &TransactionFormState,
@@ -67,7 +111,7 @@ fn validate_and_build_split_transaction(
     let date = validate_date(&form.date, date_format)?;
 
     // Validate parent amount
-    let parent_amount = validate_amount(&form.amount)?;
+    let parent_amount = validate_amount(&form.amount, form)?;
 
     // Resolve payee (ID or name)
     let (payee_id, payee_name) = resolve_payee(&form.payee, payees);
@@ -82,7 +126,7 @@ fn validate_and_build_split_transaction(
 
     for (i, sub) in form.subtransactions.iter().enumerate() {
         let sub_amount =
-            validate_amount(&sub.amount).map_err(|e| format!("Split {}: {}", i + 1, e))?;
+            validate_amount(&sub.amount, form).map_err(|e| format!("Split {}: {}", i + 1, e))?;
         sum_of_subtransactions += sub_amount;
 
         let category_id = resolve_category(&sub.category, categories);
@@ -101,15 +145,15 @@ fn validate_and_build_split_transaction(
     // Verify subtransaction amounts sum equals parent amount
     if sum_of_subtransactions != parent_amount {
         let diff = parent_amount - sum_of_subtransactions;
-        let diff_formatted = format!("{:.2}", diff.abs() as f64 / 1000.0);
+        let diff_formatted = money::format(diff.abs(), form.currency_format.as_ref());
         if diff > 0 {
             return Err(format!(
-                "Split amounts are ${} under the total",
+                "Split amounts are {} under the total",
                 diff_formatted
             ));
         } else {
             return Err(format!(
-                "Split amounts are ${} over the total",
+                "Split amounts are {} over the total",
                 diff_formatted
             ));
         }
@@ -132,6 +176,7 @@ fn validate_and_build_split_transaction(
         approved: Some(true),
         flag_color: form.flag_color,
         subtransactions: Some(subtransactions),
+        import_id: None,
     })
 }
 
@@ -154,17 +199,19 @@ fn validate_date_as_naive(date_str: &str, date_format: &str) -> Result<NaiveDate
         .map_err(|_| format!("Invalid date. Use format: {}", date_format))
 }
 
-fn validate_amount(amount_str: &str) -> Result<i64, String> {
-    if amount_str.is_empty() {
+/// Read an amount with the form's currency and rounding
+fn validate_amount(amount_str: &str, form: &TransactionFormState) -> Result<i64, String> {
+    let amount_str = amount_str.trim();
+    if amount_str.is_empty() || amount_str == "-" {
         return Err("Amount cannot be empty".to_string());
     }
 
-    let amount: f64 = amount_str.parse().map_err(|_| {
-        "Invalid amount. Enter a number (e.g., -50.00 for outflow, 50.00 for inflow)".to_string()
+    // Expressions also normalize whatever the sign toggle produced ("+5", "--5", "-(5+3)")
+    let amount = money::parse_input(amount_str, form.currency_format.as_ref()).ok_or_else(|| {
+        "Invalid amount. Enter a number (e.g., -50.00 for outflow, 50.00 for inflow; Ctrl+- flips the sign)"
+            .to_string()
     })?;
-
-    // Convert to milliunits (YNAB uses milliunits: 1000 = $1.00)
-    Ok((amount * 1000.0) as i64)
+    Ok(form.rounding.apply(amount))
 }
 
 fn resolve_payee(input: &str, payees: &[Payee]) -> (Option<Uuid>, Option<String>) {
@@ -211,7 +258,7 @@ pub fn build_transaction_update(
     let date = validate_date_as_naive(&form.date, date_format)?;
 
     // Validate amount
-    let amount_milliunits = validate_amount(&form.amount)?;
+    let amount_milliunits = validate_amount(&form.amount, form)?;
 
     // Resolve payee
     let (payee_id, payee_name) = resolve_payee(&form.payee, payees);
@@ -223,8 +270,8 @@ pub fn build_transaction_update(
         let mut sum_of_subtransactions: i64 = 0;
 
         for (i, sub) in form.subtransactions.iter().enumerate() {
-            let sub_amount =
-                validate_amount(&sub.amount).map_err(|e| format!("Split {}: {}", i + 1, e))?;
+            let sub_amount = validate_amount(&sub.amount, form)
+                .map_err(|e| format!("Split {}: {}", i + 1, e))?;
             sum_of_subtransactions += sub_amount;
 
             let cat_id = resolve_category(&sub.category, categories);
@@ -243,15 +290,15 @@ pub fn build_transaction_update(
         // Verify subtransaction amounts sum equals parent amount
         if sum_of_subtransactions != amount_milliunits {
             let diff = amount_milliunits - sum_of_subtransactions;
-            let diff_formatted = format!("{:.2}", diff.abs() as f64 / 1000.0);
+            let diff_formatted = money::format(diff.abs(), form.currency_format.as_ref());
             if diff > 0 {
                 return Err(format!(
-                    "Split amounts are ${} under the total",
+                    "Split amounts are {} under the total",
                     diff_formatted
                 ));
             } else {
                 return Err(format!(
-                    "Split amounts are ${} over the total",
+                    "Split amounts are {} over the total",
                     diff_formatted
                 ));
             }
@@ -0,0 +1,333 @@
+//! Builders for the YNAB types unit tests are written against.
+//!
+//! Each starts from a plain, valid value (a cleared, approved transaction in
+//! "Checking"; an empty category in "Everyday"; an open checking account with
+//! nothing in it) so a test only spells out the fields it's about. Names given to the builders also set matching ids,
+//! so the same name always means the same payee or category.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+use ynab_api::endpoints::{
+    accounts::{Account, AccountType},
+    budgets::BudgetSummary,
+    categories::Category,
+    months::MonthDetail,
+    scheduled_transactions::{Frequency, ScheduledTransaction},
+    transactions::{ReconciliationStatus, SubTransaction, Transaction},
+    TransactionId,
+};
+
+/// A stable id for a payee, category or account name
+pub fn id(name: &str) -> Uuid {
+    Uuid::from_u128(
+        name.bytes()
+            .fold(0u128, |id, b| id.wrapping_mul(31).wrapping_add(b.into())),
+    )
+}
+
+fn date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").expect("dates are written YYYY-MM-DD")
+}
+
+/// A transaction of `amount` milliunits on `date` (`YYYY-MM-DD`)
+pub fn transaction(date: &str, amount: i64) -> TransactionBuilder {
+    TransactionBuilder(Transaction {
+        id: TransactionId::new(Uuid::new_v4()),
+        date: self::date(date),
+        amount: amount.into(),
+        memo: None,
+        cleared: ReconciliationStatus::Cleared,
+        approved: true,
+        flag_color: None,
+        account_id: Uuid::nil(),
+        payee_id: None,
+        category_id: None,
+        transfer_account_id: None,
+        transfer_transaction_id: None,
+        matched_transaction_id: None,
+        import_id: None,
+        deleted: false,
+        account_name: "Checking".to_string(),
+        payee_name: None,
+        category_name: None,
+        subtransactions: Vec::new(),
+    })
+}
+
+pub struct TransactionBuilder(Transaction);
+
+impl TransactionBuilder {
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.0.id = TransactionId::new(id);
+        self
+    }
+
+    pub fn payee(mut self, name: &str) -> Self {
+        self.0.payee_id = Some(id(name));
+        self.0.payee_name = Some(name.to_string());
+        self
+    }
+
+    pub fn category(mut self, name: &str) -> Self {
+        self.0.category_id = Some(id(name));
+        self.0.category_name = Some(name.to_string());
+        self
+    }
+
+    pub fn memo(mut self, memo: &str) -> Self {
+        self.0.memo = Some(memo.to_string());
+        self
+    }
+
+    pub fn cleared(mut self, cleared: ReconciliationStatus) -> Self {
+        self.0.cleared = cleared;
+        self
+    }
+
+    pub fn approved(mut self, approved: bool) -> Self {
+        self.0.approved = approved;
+        self
+    }
+
+    pub fn import_id(mut self, import_id: &str) -> Self {
+        self.0.import_id = Some(import_id.to_string());
+        self
+    }
+
+    /// Make it a transfer to or from another account
+    pub fn transfer(mut self) -> Self {
+        self.0.transfer_account_id = Some(Uuid::nil());
+        self
+    }
+
+    /// Split it into parts of `(amount, category)`
+    pub fn split(mut self, parts: &[(i64, &str)]) -> Self {
+        let transaction_id = self.0.id.clone();
+        self.0.subtransactions = parts
+            .iter()
+            .enumerate()
+            .map(|(i, (amount, category))| SubTransaction {
+                id: format!("{}_{}", transaction_id, i),
+                transaction_id: transaction_id.clone(),
+                amount: (*amount).into(),
+                memo: None,
+                payee_id: None,
+                payee_name: None,
+                category_id: Some(id(category)),
+                category_name: Some(category.to_string()),
+                transfer_account_id: None,
+                deleted: false,
+            })
+            .collect();
+        self
+    }
+
+    pub fn build(self) -> Transaction {
+        self.0
+    }
+}
+
+/// A category named `name` in the "Everyday" group, with nothing assigned
+pub fn category(name: &str) -> CategoryBuilder {
+    CategoryBuilder(Category {
+        id: id(name),
+        category_group_id: id("Everyday"),
+        category_group_name: Some("Everyday".to_string()),
+        name: name.to_string(),
+        hidden: false,
+        original_category_group_id: None,
+        note: None,
+        budgeted: 0.into(),
+        activity: 0.into(),
+        balance: 0.into(),
+        goal_type: None,
+        goal_creation_month: None,
+        goal_target: None,
+        goal_target_month: None,
+        goal_percentage_complete: None,
+        goal_months_to_budget: None,
+        goal_under_funded: None,
+        goal_overall_funded: None,
+        goal_overall_left: None,
+        goal_snoozed_at: None,
+        deleted: false,
+    })
+}
+
+pub struct CategoryBuilder(Category);
+
+impl CategoryBuilder {
+    pub fn group(mut self, name: &str) -> Self {
+        self.0.category_group_id = id(name);
+        self.0.category_group_name = Some(name.to_string());
+        self
+    }
+
+    /// Assigned and spent this month; the balance is what's left
+    pub fn amounts(mut self, budgeted: i64, activity: i64) -> Self {
+        self.0.budgeted = budgeted.into();
+        self.0.activity = activity.into();
+        self.0.balance = (budgeted + activity).into();
+        self
+    }
+
+    /// A monthly "needed for spending" goal still short by `under_funded`
+    pub fn need(mut self, under_funded: i64) -> Self {
+        self.0.goal_type = Some("NEED".to_string());
+        self.0.goal_under_funded = Some(under_funded.into());
+        self
+    }
+
+    pub fn snoozed(mut self, at: DateTime<Utc>) -> Self {
+        self.0.goal_snoozed_at = Some(at);
+        self
+    }
+
+    pub fn build(self) -> Category {
+        self.0
+    }
+}
+
+/// An open, on-budget checking account named `name` with a zero balance
+pub fn account(name: &str) -> AccountBuilder {
+    AccountBuilder(Account {
+        id: id(name),
+        name: name.to_string(),
+        account_type: AccountType::Checking,
+        on_budget: true,
+        closed: false,
+        note: None,
+        balance: 0.into(),
+        cleared_balance: 0.into(),
+        uncleared_balance: 0.into(),
+        transfer_payee_id: None,
+        direct_import_linked: false,
+        direct_import_in_error: false,
+        last_reconciled_at: None,
+        deleted: false,
+    })
+}
+
+pub struct AccountBuilder(Account);
+
+impl AccountBuilder {
+    pub fn account_type(mut self, account_type: AccountType) -> Self {
+        self.0.account_type = account_type;
+        self
+    }
+
+    /// A balance of `balance` milliunits, all of it cleared
+    pub fn balance(mut self, balance: i64) -> Self {
+        self.0.balance = balance.into();
+        self.0.cleared_balance = balance.into();
+        self
+    }
+
+    pub fn off_budget(mut self) -> Self {
+        self.0.on_budget = false;
+        self
+    }
+
+    pub fn closed(mut self) -> Self {
+        self.0.closed = true;
+        self
+    }
+
+    pub fn transfer_payee(mut self, payee_id: Uuid) -> Self {
+        self.0.transfer_payee_id = Some(payee_id);
+        self
+    }
+
+    /// Last reconciled at `at` (RFC 3339)
+    pub fn reconciled_at(mut self, at: &str) -> Self {
+        self.0.last_reconciled_at = Some(at.parse().expect("reconcile times are written RFC 3339"));
+        self
+    }
+
+    pub fn build(self) -> Account {
+        self.0
+    }
+}
+
+/// A monthly scheduled transaction of `amount` milliunits from "Checking",
+/// next due on `date_next` (`YYYY-MM-DD`)
+pub fn scheduled(date_next: &str, amount: i64) -> ScheduledBuilder {
+    ScheduledBuilder(ScheduledTransaction {
+        id: Uuid::new_v4(),
+        date_first: date(date_next),
+        date_next: date(date_next),
+        frequency: Frequency::Monthly,
+        amount: amount.into(),
+        memo: None,
+        flag_color: None,
+        account_id: Uuid::nil(),
+        payee_id: None,
+        category_id: None,
+        transfer_account_id: None,
+        deleted: false,
+        account_name: "Checking".to_string(),
+        payee_name: None,
+        category_name: None,
+        subtransactions: Vec::new(),
+    })
+}
+
+pub struct ScheduledBuilder(ScheduledTransaction);
+
+impl ScheduledBuilder {
+    pub fn frequency(mut self, frequency: Frequency) -> Self {
+        self.0.frequency = frequency;
+        self
+    }
+
+    pub fn account(mut self, account_id: Uuid, name: &str) -> Self {
+        self.0.account_id = account_id;
+        self.0.account_name = name.to_string();
+        self
+    }
+
+    pub fn payee(mut self, name: &str) -> Self {
+        self.0.payee_id = Some(id(name));
+        self.0.payee_name = Some(name.to_string());
+        self
+    }
+
+    /// Make it a transfer to `account_id`
+    pub fn transfer_to(mut self, account_id: Uuid) -> Self {
+        self.0.transfer_account_id = Some(account_id);
+        self
+    }
+
+    pub fn build(self) -> ScheduledTransaction {
+        self.0
+    }
+}
+
+/// A budget named `name` with none of its optional settings
+pub fn budget(name: &str) -> BudgetSummary {
+    BudgetSummary {
+        id: id(name).into(),
+        name: name.to_string(),
+        last_modified_on: None,
+        first_month: None,
+        last_month: None,
+        date_format: None,
+        currency_format: None,
+        accounts: None,
+    }
+}
+
+/// January 2025's plan with `categories` and nothing else set
+pub fn month(categories: Vec<Category>) -> MonthDetail {
+    MonthDetail {
+        month: "2025-01-01".to_string(),
+        note: None,
+        income: 0.into(),
+        budgeted: 0.into(),
+        activity: 0.into(),
+        to_be_budgeted: 0.into(),
+        age_of_money: None,
+        deleted: false,
+        categories,
+    }
+}
@@ -1,10 +1,19 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
 use crate::app_core::{AppCore, DataEventHandler};
+use crate::background::{data_loader::DataLoader, BackgroundTaskManager};
+use crate::cache::Cache;
 use crate::commands::executor;
 use crate::events::{AppCommand, DataEvent};
 use crate::input::{Key, KeyEvent};
 use crate::state::AppState;
 use crate::ui::screens::Screen;
 
+pub use crate::background::faults::Faults;
+
 /// Mock data event handler for tests (no real async tasks)
 ///
 /// This handler executes commands synchronously using execute_command_sync,
@@ -30,8 +39,77 @@ impl DataEventHandler for MockDataHandler {
     }
 }
 
-pub struct TestApp {
-    core: AppCore<MockDataHandler>,
+/// Real command execution against a scratch cache, with faults injected
+/// into every API request
+///
+/// Commands run through the same executor and [`DataLoader`] as the app, so
+/// loading, error, retry and rollback paths behave as they do for users. The
+/// results arrive as data events; apply them with [`TestApp::settle`].
+pub struct FaultyDataHandler {
+    task_manager: BackgroundTaskManager,
+    data_loader: DataLoader,
+    data_rx: mpsc::UnboundedReceiver<DataEvent>,
+    cache_dir: PathBuf,
+}
+
+impl FaultyDataHandler {
+    /// Requests that aren't failed by `faults` are sent with `client`; point
+    /// it at a mock server, or somewhere unreachable to fail them too
+    pub async fn new(client: ynab_api::Client, faults: Faults) -> anyhow::Result<Self> {
+        let cache_dir = crate::replay::scratch_cache_dir();
+        let cache = Arc::new(Cache::in_dir(cache_dir.clone()).await?);
+        let (data_tx, data_rx) = mpsc::unbounded_channel();
+        Ok(Self {
+            task_manager: BackgroundTaskManager::new(),
+            data_loader: DataLoader::new(Arc::new(client), cache, data_tx).with_faults(faults),
+            data_rx,
+            cache_dir,
+        })
+    }
+
+    /// Give up on API requests after `timeout` rather than the configured time
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.data_loader = self.data_loader.clone().with_timeouts(timeout, timeout);
+        self
+    }
+
+    /// The next data event, or `None` once every background task has finished
+    pub async fn next_event(&mut self) -> Option<DataEvent> {
+        loop {
+            if let Ok(event) = self.data_rx.try_recv() {
+                return Some(event);
+            }
+            if self.task_manager.is_idle() {
+                return self.data_rx.try_recv().ok();
+            }
+            // A task can finish without sending anything, so poll rather than wait
+            if let Ok(event) =
+                tokio::time::timeout(Duration::from_millis(10), self.data_rx.recv()).await
+            {
+                return event;
+            }
+        }
+    }
+}
+
+impl DataEventHandler for FaultyDataHandler {
+    fn execute_with_context(&mut self, command: AppCommand, state: &mut AppState) {
+        executor::execute_command(command, state, &mut self.task_manager, &self.data_loader);
+    }
+
+    fn check_for_changes(&mut self, state: &AppState) {
+        executor::check_for_changes(state, &mut self.task_manager, &self.data_loader);
+    }
+}
+
+impl Drop for FaultyDataHandler {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.cache_dir);
+    }
+}
+
+pub struct TestApp<H: DataEventHandler = MockDataHandler> {
+    core: AppCore<H>,
 }
 
 impl TestApp {
@@ -41,7 +119,25 @@ impl TestApp {
             core: AppCore::new(MockDataHandler::new()),
         }
     }
+}
+
+impl TestApp<FaultyDataHandler> {
+    /// Create a test app that runs commands for real, with injected faults
+    pub fn with_faults(handler: FaultyDataHandler) -> Self {
+        Self {
+            core: AppCore::new(handler),
+        }
+    }
+
+    /// Apply data events until every background task has finished
+    pub async fn settle(&mut self) {
+        while let Some(event) = self.core.handler_mut().next_event().await {
+            self.core.handle_data_event(event);
+        }
+    }
+}
 
+impl<H: DataEventHandler> TestApp<H> {
     /// Send a single key event
     pub fn send_key(&mut self, key: Key) {
         self.core.handle_key(KeyEvent::new(key));
@@ -59,6 +155,11 @@ impl TestApp {
         }
     }
 
+    /// Run an auto-refresh check, as the app does every few minutes
+    pub fn check_for_changes(&mut self) {
+        self.core.check_for_changes();
+    }
+
     /// Inject a data event (simulate API response or cache load)
     pub fn send_data_event(&mut self, event: DataEvent) {
         self.core.handle_data_event(event);
@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::Style,
+    text::Line,
+    widgets::{Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::i18n::{t, tn};
+use crate::state::{ArchiveReviewState, LoadingState};
+use crate::ui::{components::empty_state, layouts, theme};
+
+/// Render the review list of unused categories to archive
+pub fn render_archive_review(f: &mut Frame, review: &ArchiveReviewState) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::LARGE,
+        t("archive-review-title"),
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Explanation
+            Constraint::Min(3),    // Suggestions
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    match &review.loading {
+        LoadingState::Loading(..) | LoadingState::NotStarted => {
+            empty_state::render_loading_state(f, chunks[1], "Status", t("archive-review-loading"));
+        }
+        LoadingState::Error(error) => {
+            f.render_widget(
+                Paragraph::new(super::error_panel::error_line(error))
+                    .style(Style::default().fg(theme::COLOR_NEGATIVE)),
+                chunks[1],
+            );
+        }
+        LoadingState::Loaded if review.suggestions.is_empty() => {
+            f.render_widget(
+                Paragraph::new(t("archive-review-empty")).alignment(Alignment::Center),
+                chunks[1],
+            );
+        }
+        LoadingState::Loaded => {
+            let count = review.included_ids().len();
+            let summary = tn("archive-review-summary", count);
+            f.render_widget(Paragraph::new(Line::from(summary)), chunks[0]);
+
+            let rows: Vec<Row> = review
+                .suggestions
+                .iter()
+                .zip(&review.included)
+                .map(|(suggestion, included)| {
+                    Row::new(vec![
+                        Cell::from(if *included { "[x]" } else { "[ ]" }),
+                        Cell::from(suggestion.category_name.clone()),
+                        Cell::from(suggestion.group_name.clone().unwrap_or_default())
+                            .style(theme::help_text_style()),
+                    ])
+                })
+                .collect();
+
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(4),
+                    Constraint::Percentage(50),
+                    Constraint::Percentage(40),
+                ],
+            )
+            .row_highlight_style(theme::selection_style());
+            f.render_stateful_widget(table, chunks[1], &mut review.table_state.borrow_mut());
+        }
+    }
+
+    let instructions = Paragraph::new(t("archive-review-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[2]);
+}
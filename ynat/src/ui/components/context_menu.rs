@@ -0,0 +1,54 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::i18n::t;
+use crate::state::ContextMenuState;
+use crate::ui::{layouts, theme};
+
+/// Render the action menu for the selected row
+pub fn render_context_menu(f: &mut Frame, menu: &ContextMenuState) {
+    let title = format!(" {} ", menu.title);
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        &title,
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(1),    // Actions
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let items: Vec<ListItem> = menu
+        .items
+        .iter()
+        .map(|item| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:8}", item.shortcut.unwrap_or_default()),
+                    theme::header_style(),
+                ),
+                Span::raw(item.label),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(theme::selection_style());
+    let mut list_state = ListState::default().with_selected(Some(menu.selected));
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+
+    let instructions = Paragraph::new(t("context-menu-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[1]);
+}
@@ -6,6 +6,7 @@ use ratatui::{
     Frame,
 };
 
+use crate::i18n::t;
 use crate::ui::{layouts, theme};
 
 /// Render a confirmation popup for transaction deletion
@@ -15,7 +16,7 @@ pub fn render_delete_confirmation(f: &mut Frame) {
         f,
         f.area(),
         layouts::popup_sizes::SMALL,
-        " Confirm Delete ",
+        t("delete-title"),
         theme::danger_border_style(),
     );
 
@@ -31,7 +32,7 @@ pub fn render_delete_confirmation(f: &mut Frame) {
         .split(inner);
 
     // Warning message
-    let warning = Paragraph::new("Are you sure you want to delete this transaction?")
+    let warning = Paragraph::new(t("delete-question"))
         .style(theme::loading_style().add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
     f.render_widget(warning, chunks[0]);
@@ -39,21 +40,21 @@ pub fn render_delete_confirmation(f: &mut Frame) {
     // Instructions
     let instructions = Line::from(vec![
         Span::styled(
-            "[Y]es ",
+            t("confirm-yes"),
             Style::default()
                 .fg(theme::COLOR_POSITIVE)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("/ "),
         Span::styled(
-            "[N]o ",
+            t("confirm-no"),
             Style::default()
                 .fg(theme::COLOR_NEGATIVE)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("/ "),
         Span::styled("[Esc]", Style::default().fg(theme::COLOR_HELP_TEXT)),
-        Span::raw(" Cancel"),
+        Span::raw(t("confirm-cancel")),
     ]);
     let instructions_para = Paragraph::new(instructions).alignment(Alignment::Center);
     f.render_widget(instructions_para, chunks[2]);
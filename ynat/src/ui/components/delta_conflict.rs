@@ -0,0 +1,126 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    widgets::{Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+use ynab_api::endpoints::{budgets::BudgetSummary, transactions::Transaction};
+
+use crate::i18n::t;
+use crate::state::{DeltaConflict, TransactionField};
+use crate::ui::{layouts, theme, utils};
+
+/// Render the choice between a local change and the server's version of the
+/// same transaction
+pub fn render_delta_conflict(
+    f: &mut Frame,
+    conflict: &DeltaConflict,
+    waiting: usize,
+    budget: Option<&BudgetSummary>,
+) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        t("conflict-title"),
+        theme::loading_style().add_modifier(Modifier::BOLD),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Explanation
+            Constraint::Min(0),    // Diff
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let mut explanation = t("conflict-explanation")
+        .replace(
+            "{payee}",
+            conflict.local.payee_name.as_deref().unwrap_or_default(),
+        )
+        .replace("{date}", &conflict.local.date.to_string());
+    if waiting > 0 {
+        explanation = format!(
+            "{} {}",
+            explanation,
+            t("conflict-waiting").replace("{count}", &waiting.to_string())
+        );
+    }
+    f.render_widget(
+        Paragraph::new(explanation)
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center),
+        chunks[0],
+    );
+
+    if conflict.show_diff {
+        let rows: Vec<Row> = conflict
+            .differing_fields()
+            .into_iter()
+            .map(|field| {
+                Row::new(vec![
+                    Cell::from(field_label(field)).style(theme::help_text_style()),
+                    Cell::from(field_value(&conflict.local, field, budget)),
+                    Cell::from(field_value(&conflict.server, field, budget)),
+                ])
+            })
+            .collect();
+        let header = Row::new(vec![
+            Cell::from(""),
+            Cell::from(t("conflict-mine")),
+            Cell::from(t("conflict-server")),
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Percentage(45),
+                Constraint::Percentage(45),
+            ],
+        )
+        .header(header);
+        f.render_widget(table, chunks[1]);
+    }
+
+    let instructions = Paragraph::new(t("conflict-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[2]);
+}
+
+fn field_label(field: TransactionField) -> &'static str {
+    match field {
+        TransactionField::Date => t("conflict-field-date"),
+        TransactionField::Amount => t("conflict-field-amount"),
+        TransactionField::Payee => t("conflict-field-payee"),
+        TransactionField::Category => t("conflict-field-category"),
+        TransactionField::Memo => t("conflict-field-memo"),
+        TransactionField::Cleared => t("conflict-field-cleared"),
+        TransactionField::Approved => t("conflict-field-approved"),
+        TransactionField::Flag => t("conflict-field-flag"),
+    }
+}
+
+fn field_value(
+    transaction: &Transaction,
+    field: TransactionField,
+    budget: Option<&BudgetSummary>,
+) -> String {
+    match field {
+        TransactionField::Date => transaction.date.to_string(),
+        TransactionField::Amount => utils::format_amount(transaction.amount.into(), budget),
+        TransactionField::Payee => transaction.payee_name.clone().unwrap_or_default(),
+        TransactionField::Category => transaction.category_name.clone().unwrap_or_default(),
+        TransactionField::Memo => transaction.memo.clone().unwrap_or_default(),
+        TransactionField::Cleared => transaction.cleared.to_string(),
+        TransactionField::Approved => if transaction.approved { "✓" } else { "" }.to_string(),
+        TransactionField::Flag => transaction
+            .flag_color
+            .map(|flag| format!("{:?}", flag))
+            .unwrap_or_default(),
+    }
+}
@@ -0,0 +1,68 @@
+//! Shared presentation for [`AppError`]s.
+//!
+//! Screens whose load failed get the full panel; forms and other cramped
+//! spots use [`error_line`] so the wording stays the same everywhere.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::error::{AppError, ErrorKind};
+use crate::i18n::t;
+use crate::ui::{layouts, theme};
+
+/// One-line version for forms and titles, e.g. "Can't reach YNAB: connection refused"
+pub fn error_line(error: &AppError) -> String {
+    match error.kind {
+        // The message already says what to fix
+        ErrorKind::Validation => error.message.clone(),
+        _ => format!("{}: {}", error.title(), error.message),
+    }
+}
+
+/// Render a screen's load error as a popup with the suggested action and retry hint
+pub fn render_error_panel(f: &mut Frame, error: &AppError) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::MEDIUM,
+        error.title(),
+        theme::danger_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(2),    // Message
+            Constraint::Length(2), // Suggestion
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let message = Paragraph::new(error.message.as_str())
+        .style(Style::default().add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(message, chunks[0]);
+
+    let suggestion = Paragraph::new(error.suggestion())
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(suggestion, chunks[1]);
+
+    let hint = if error.is_retryable() {
+        t("error-panel-retry")
+    } else {
+        t("error-panel-back")
+    };
+    let instructions = Paragraph::new(Line::from(hint))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[2]);
+}
@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::i18n::t;
+use crate::ui::{layouts, theme};
+
+/// Render the popup confirming where a CSV export was written
+pub fn render_export_confirmation(f: &mut Frame, path: &Path) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::MEDIUM,
+        t("export-title"),
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Message
+            Constraint::Length(1), // Empty line
+            Constraint::Min(2),    // Path
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let message = Paragraph::new(t("export-written"))
+        .style(Style::default().add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center);
+    f.render_widget(message, chunks[0]);
+
+    let path = Paragraph::new(path.display().to_string())
+        .style(theme::title_style())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false });
+    f.render_widget(path, chunks[2]);
+
+    let instructions = Paragraph::new(t("export-dismiss"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[3]);
+}
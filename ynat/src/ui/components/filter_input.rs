@@ -6,6 +6,7 @@ use ratatui::{
     Frame,
 };
 
+use crate::i18n::t;
 use crate::ui::theme;
 
 /// Render a filter input field with the current query.
@@ -17,8 +18,19 @@ pub fn render_filter_input(f: &mut Frame, area: Rect, query: &str) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Filter (Enter: apply, Esc: clear)"),
+                .title(t("filter-title")),
         );
 
     f.render_widget(input, area);
 }
+
+/// Render what's been typed to jump to an account, in the filter's place
+pub fn render_jump_input(f: &mut Frame, area: Rect, query: &str) {
+    let input = Paragraph::new(query).style(theme::loading_style()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(t("accounts-jump-title")),
+    );
+
+    f.render_widget(input, area);
+}
@@ -0,0 +1,139 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::i18n::t;
+use crate::state::{GoalCalculatorField, GoalCalculatorState};
+use crate::ui::{layouts, theme, utils};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+/// Render the savings goal "what-if" calculator popup
+pub fn render_goal_calculator(
+    f: &mut Frame,
+    calculator: &GoalCalculatorState,
+    budget: Option<&BudgetSummary>,
+) {
+    let title = t("goal-calculator-title").replace("{category}", &calculator.category_name);
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        &title,
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Target / available / remaining
+            Constraint::Length(1), // Empty line
+            Constraint::Length(2), // Monthly amount question
+            Constraint::Length(1), // Empty line
+            Constraint::Length(2), // Target month question
+            Constraint::Min(0),
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let summary = vec![
+        summary_line(t("goal-calculator-target"), calculator.goal_target, budget),
+        summary_line(t("goal-calculator-available"), calculator.balance, budget),
+        summary_line(
+            t("goal-calculator-remaining"),
+            calculator.remaining(),
+            budget,
+        ),
+    ];
+    f.render_widget(Paragraph::new(summary), chunks[0]);
+
+    // "If I budget X per month, when do I hit the target?"
+    let projection = match calculator.monthly_amount() {
+        _ if calculator.remaining() == 0 => t("goal-calculator-reached-now").to_string(),
+        Some(_) => match calculator.projected_month() {
+            Some(month) => {
+                t("goal-calculator-reached").replace("{month}", &month.format("%B %Y").to_string())
+            }
+            None => t("goal-calculator-never").to_string(),
+        },
+        None => String::new(),
+    };
+    render_question(
+        f,
+        chunks[2],
+        t("goal-calculator-monthly"),
+        &calculator.monthly_input,
+        calculator.field == GoalCalculatorField::Monthly,
+        projection,
+    );
+
+    // "How much per month to hit the target by this month?"
+    let required = if calculator.target_month_input.is_empty() {
+        String::new()
+    } else {
+        match calculator.required_monthly() {
+            Some(amount) => t("goal-calculator-needed")
+                .replace("{amount}", &utils::format_amount(amount, budget)),
+            None => t("goal-calculator-invalid-month").to_string(),
+        }
+    };
+    render_question(
+        f,
+        chunks[4],
+        t("goal-calculator-by-month"),
+        &calculator.target_month_input,
+        calculator.field == GoalCalculatorField::TargetMonth,
+        required,
+    );
+
+    let instructions = Paragraph::new(t("goal-calculator-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[6]);
+}
+
+fn summary_line(label: &str, amount: i64, budget: Option<&BudgetSummary>) -> Line<'static> {
+    Line::from(vec![
+        Span::raw(format!("{:15}", label)),
+        Span::styled(
+            utils::format_amount(amount, budget),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+    ])
+}
+
+fn render_question(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    label: &str,
+    input: &str,
+    focused: bool,
+    answer: String,
+) {
+    let input_style = if focused {
+        theme::form_field_focused_style()
+    } else {
+        theme::form_field_style()
+    };
+    let input_text = if focused {
+        format!("{}_", input)
+    } else {
+        input.to_string()
+    };
+
+    let lines = vec![
+        Line::from(vec![
+            Span::raw(format!("{} ", label)),
+            Span::styled(input_text, input_style),
+        ]),
+        Line::from(Span::styled(
+            answer,
+            Style::default().fg(theme::COLOR_TITLE),
+        )),
+    ];
+    f.render_widget(Paragraph::new(lines), area);
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::i18n::t;
+use crate::state::{GoalFormField, GoalFormState};
+use crate::ui::{components::error_panel, layouts, theme};
+use crate::utils::goals;
+
+/// Render the popup for changing a category's goal target
+pub fn render_goal_form(f: &mut Frame, form: &GoalFormState) {
+    let title = t("goal-form-title").replace("{category}", &form.category_name);
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        &title,
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Goal type
+            Constraint::Length(1), // Empty line
+            Constraint::Length(1), // Target
+            Constraint::Length(1), // Target month
+            Constraint::Min(0),
+            Constraint::Length(1), // Validation error
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    // The type can only be changed in YNAB itself
+    let goal_type = goals::goal_type_name(&form.goal_type).unwrap_or(&form.goal_type);
+    let goal_type = Line::from(vec![
+        Span::raw(format!("{:15}", t("goal-form-type"))),
+        Span::styled(goal_type.to_string(), theme::help_text_style()),
+    ]);
+    f.render_widget(Paragraph::new(goal_type), chunks[0]);
+
+    f.render_widget(
+        input_line(
+            t("goal-form-target"),
+            &form.target_input,
+            form.field == GoalFormField::Target,
+        ),
+        chunks[2],
+    );
+    f.render_widget(
+        input_line(
+            t("goal-form-target-month"),
+            &form.target_month_input,
+            form.field == GoalFormField::TargetMonth,
+        ),
+        chunks[3],
+    );
+
+    if let Some(ref error) = form.validation_error {
+        f.render_widget(
+            Paragraph::new(error_panel::error_line(error))
+                .style(Style::default().fg(theme::COLOR_NEGATIVE)),
+            chunks[5],
+        );
+    }
+
+    let instructions = Paragraph::new(t("goal-form-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[6]);
+}
+
+fn input_line<'a>(label: &str, input: &str, focused: bool) -> Paragraph<'a> {
+    let (style, text) = if focused {
+        (theme::form_field_focused_style(), format!("{}_", input))
+    } else {
+        (theme::form_field_style(), input.to_string())
+    };
+    Paragraph::new(Line::from(vec![
+        Span::raw(format!("{:15}", label)),
+        Span::styled(text, style),
+    ]))
+}
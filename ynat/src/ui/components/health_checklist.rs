@@ -0,0 +1,112 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::Style,
+    widgets::{Cell, Paragraph, Row, Table},
+    Frame,
+};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+use crate::health::{HealthItem, RECONCILE_DAYS};
+use crate::i18n::{t, tn};
+use crate::state::{HealthChecklistState, LoadingState};
+use crate::ui::{components::empty_state, layouts, theme, utils};
+
+/// Render the budget health checklist over the plan
+pub fn render_health_checklist(
+    f: &mut Frame,
+    health: &HealthChecklistState,
+    budget: Option<&BudgetSummary>,
+) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        t("health-title"),
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(3),    // Checklist
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    match &health.loading {
+        LoadingState::Loading(..) | LoadingState::NotStarted => {
+            empty_state::render_loading_state(f, chunks[0], "Status", t("health-loading"));
+        }
+        LoadingState::Error(error) => {
+            f.render_widget(
+                Paragraph::new(super::error_panel::error_line(error))
+                    .style(Style::default().fg(theme::COLOR_NEGATIVE)),
+                chunks[0],
+            );
+        }
+        LoadingState::Loaded => {
+            let rows: Vec<Row> = health
+                .items
+                .iter()
+                .map(|item| {
+                    let (mark, style) = if item.passed() {
+                        ("✓", Style::default().fg(theme::COLOR_POSITIVE))
+                    } else {
+                        ("✗", Style::default().fg(theme::COLOR_NEGATIVE))
+                    };
+                    Row::new(vec![
+                        Cell::from(mark).style(style),
+                        Cell::from(item_label(item, budget)),
+                    ])
+                })
+                .collect();
+
+            let table = Table::new(rows, [Constraint::Length(2), Constraint::Min(10)])
+                .row_highlight_style(theme::selection_style());
+            f.render_stateful_widget(table, chunks[0], &mut health.table_state.borrow_mut());
+        }
+    }
+
+    let instructions = Paragraph::new(t("health-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[1]);
+}
+
+/// What the item checks, or what's wrong if it failed
+fn item_label(item: &HealthItem, budget: Option<&BudgetSummary>) -> String {
+    match item {
+        HealthItem::Approval { unapproved: 0 } => t("health-approved").to_string(),
+        HealthItem::Approval { unapproved } => tn("health-unapproved", *unapproved),
+        HealthItem::Reconciliation { stale } => match stale.first() {
+            None => t("health-reconciled").replace("{days}", &RECONCILE_DAYS.to_string()),
+            Some(account) => {
+                let since = match account.last_reconciled {
+                    Some(date) => t("health-reconciled-on").replace("{date}", &date.to_string()),
+                    None => t("health-never-reconciled").to_string(),
+                };
+                let label = t("health-stale-account")
+                    .replace("{account}", &account.name)
+                    .replace("{since}", &since);
+                match stale.len() - 1 {
+                    0 => label,
+                    others => format!("{} {}", label, tn("health-more-accounts", others)),
+                }
+            }
+        },
+        HealthItem::Overspending { categories } => match categories.as_slice() {
+            [] => t("health-not-overspent").to_string(),
+            [(_, name)] => t("health-overspent-one").replace("{category}", name),
+            [(_, name), ..] => {
+                tn("health-overspent", categories.len() - 1).replace("{category}", name)
+            }
+        },
+        HealthItem::ReadyToAssign { amount: 0 } => t("health-all-assigned").to_string(),
+        HealthItem::ReadyToAssign { amount } if *amount > 0 => {
+            t("health-ready-to-assign").replace("{amount}", &utils::format_amount(*amount, budget))
+        }
+        HealthItem::ReadyToAssign { amount } => t("health-overassigned")
+            .replace("{amount}", &utils::format_amount(amount.abs(), budget)),
+    }
+}
@@ -7,6 +7,7 @@ use ratatui::{
     Frame,
 };
 
+use crate::i18n;
 use crate::ui::theme;
 
 /// Render a standard help bar with the given text.
@@ -23,4 +24,6 @@ pub fn render_help_bar(f: &mut Frame, area: Rect, text: &str) {
 }
 
 /// Standard help bar text used across most screens
-pub const HELP_TEXT_DEFAULT: &str = "Press ? for help";
+pub fn help_text_default() -> &'static str {
+    i18n::t("help-bar-default")
+}
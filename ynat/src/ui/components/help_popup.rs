@@ -4,22 +4,29 @@ use ratatui::{
     Frame,
 };
 
+use crate::config::AppConfig;
+use crate::i18n::t;
 use crate::ui::{layouts, screens::Screen, theme};
 
-pub fn render_help_popup(f: &mut Frame, screen: &Screen) {
-    let help_items = get_help_items(screen);
+pub fn render_help_popup(
+    f: &mut Frame,
+    screen: &Screen,
+    config: &AppConfig,
+    user_id: Option<&str>,
+) {
+    let help_items = get_help_items(screen, config);
 
     // Use shared popup frame
     let inner = super::popup::render_popup_frame(
         f,
         f.area(),
         layouts::popup_sizes::LARGE,
-        " Help (press ? or Esc to close) ",
+        t("help-title"),
         theme::accent_border_style(),
     );
 
     // Create the help list
-    let items: Vec<ListItem> = help_items
+    let mut items: Vec<ListItem> = help_items
         .iter()
         .map(|(key, description)| {
             ListItem::new(Line::from(vec![
@@ -28,84 +35,49 @@ pub fn render_help_popup(f: &mut Frame, screen: &Screen) {
             ]))
         })
         .collect();
+    if let Some(user_id) = user_id {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Span::styled(
+            t("help-signed-in-as").replace("{id}", user_id),
+            theme::help_text_style(),
+        )));
+    }
 
     let list = List::new(items).style(Style::default().fg(Color::White));
 
     f.render_widget(list, inner);
 }
 
-fn get_help_items(screen: &Screen) -> Vec<(&'static str, &'static str)> {
-    let mut items = vec![];
+fn get_help_items(screen: &Screen, config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+    let inspector_enabled = config.debug.inspector;
 
     // Screen-specific help
-    match screen {
-        Screen::Budgets(..) => {
-            items.push(("↑/k", "Move selection up"));
-            items.push(("↓/j", "Move selection down"));
-            items.push(("Enter/→/l", "Select budget and view accounts"));
-            items.push(("r", "Refresh budgets"));
-        }
-        Screen::Accounts(state) => {
-            items.push(("↑/k", "Move selection up"));
-            items.push(("↓/j", "Move selection down"));
-            items.push(("Enter/→/l", "View transactions for selected account"));
-            items.push(("/", "Enter filter mode"));
-            if state.input_mode == crate::state::InputMode::Filter {
-                items.push(("Type", "Filter accounts by name, type, or balance"));
-                items.push(("Enter", "Exit filter mode (keep filter active)"));
-                items.push(("Esc", "Clear filter and exit filter mode"));
-                items.push(("Backspace", "Delete last character"));
-            }
-            items.push((".", "Toggle showing deleted/closed accounts"));
-            items.push(("r", "Refresh accounts"));
-        }
-        Screen::Transactions(state) => {
-            items.push(("↑/k", "Move selection up"));
-            items.push(("↓/j", "Move selection down"));
-            items.push(("n", "Create a new transaction"));
-            items.push(("e", "Edit selected transaction"));
-            items.push(("a", "Approve transaction"));
-            items.push(("c", "Toggle cleared status (uncleared ↔ cleared)"));
-            items.push(("d/Backspace", "Delete selected transaction"));
-            items.push(("/", "Enter filter mode"));
-            if state.input_mode == crate::state::InputMode::Filter {
-                items.push(("Type", "Filter by payee, category, memo, or amount"));
-                items.push(("Enter", "Exit filter mode (keep filter active)"));
-                items.push(("Esc", "Clear filter and exit filter mode"));
-                items.push(("Backspace", "Delete last character"));
-            }
-            items.push((".", "Toggle showing reconciled transactions"));
-            items.push(("r", "Refresh transactions"));
-            items.push(("R", "Reconcile transactions"));
-        }
-        Screen::Plan(..) => {
-            items.push(("↑/k", "Move selection up"));
-            items.push(("↓/j", "Move selection down"));
-            items.push(("e", "Edit budgeted amount"));
-            items.push(("r", "Refresh plan"));
-            items.push((",", "Toggle focus view"));
-        }
-        Screen::Logs(..) => {
-            items.push(("↑/k", "Scroll up (older logs)"));
-            items.push(("↓/j", "Scroll down (newer logs)"));
-            items.push(("Page Up", "Scroll up one page"));
-            items.push(("Page Down", "Scroll down one page"));
-            items.push(("g then g", "Scroll to oldest logs"));
-            items.push(("G", "Scroll to newest logs"));
-        }
-    }
+    let mut items = screen.controller().help_items(config);
 
     // Global help
     items.push(("", ""));
-    items.push(("--- Global ---", ""));
-    items.push(("h/←", "Navigate back"));
-    items.push(("g then b", "Go to budgets"));
-    items.push(("g then p", "Go to plan"));
-    items.push(("g then l", "Go to logs"));
-    items.push(("g then g", "Navigate to top of list"));
-    items.push(("G", "Navigate to bottom of list"));
-    items.push(("?", "Toggle this help"));
-    items.push(("q", "Quit application"));
+    items.push((t("help-global-header"), ""));
+    items.push(("h/←", t("help-navigate-back")));
+    items.push(("g then b", t("help-go-budgets")));
+    items.push(("g then p", t("help-go-plan")));
+    items.push(("g then c", t("help-go-settings")));
+    items.push(("g then l", t("help-go-logs")));
+    items.push(("g then r", t("help-go-reports")));
+    items.push(("g then n", t("help-go-payee-cleanup")));
+    items.push(("g then $", t("help-go-reimbursements")));
+    items.push(("g then w", t("help-go-net-worth")));
+    items.push(("g then s", t("help-go-scheduled")));
+    if inspector_enabled {
+        items.push(("g then i", t("help-go-inspector")));
+    }
+    items.push(("g then g", t("help-go-top")));
+    items.push(("G", t("help-go-bottom")));
+    items.push(("5j / 5k", t("help-count")));
+    items.push(("Ctrl+d/u", t("help-half-page")));
+    items.push(("u", t("help-undo")));
+    items.push(("Ctrl+r", t("help-redo")));
+    items.push(("?", t("help-toggle-help")));
+    items.push(("q", t("help-quit")));
 
     items
 }
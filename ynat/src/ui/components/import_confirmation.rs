@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::i18n::{t, tn};
+use crate::ui::{layouts, theme};
+use crate::utils::ynab_export::ImportPreview;
+
+/// Render a confirmation popup listing what importing a YNAB export will change
+pub fn render_import_confirmation(
+    f: &mut Frame,
+    preview: &ImportPreview,
+    budget_name: Option<&str>,
+) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        t("import-title"),
+        theme::danger_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Source
+            Constraint::Length(2), // Other budget warning
+            Constraint::Length(3), // Question
+            Constraint::Min(2),    // Files
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let export = &preview.export;
+    let source = t("import-source")
+        .replace("{budget}", &export.budget_name)
+        .replace(
+            "{date}",
+            &export.exported_at.format("%Y-%m-%d %H:%M").to_string(),
+        );
+    f.render_widget(
+        Paragraph::new(source).alignment(Alignment::Center),
+        chunks[0],
+    );
+
+    if !preview.same_budget {
+        let warning = t("import-other-budget")
+            .replace("{export}", &export.budget_name)
+            .replace("{budget}", budget_name.unwrap_or_default());
+        let warning = Paragraph::new(warning)
+            .style(
+                Style::default()
+                    .fg(theme::COLOR_NEGATIVE)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(warning, chunks[1]);
+    }
+
+    let question = tn("import-question", preview.transactions)
+        .replace("{allocations}", &preview.allocations.to_string())
+        .replace("{months}", &preview.months.to_string())
+        .replace("{skipped}", &preview.skipped.to_string());
+    let question = Paragraph::new(question)
+        .style(theme::loading_style().add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(question, chunks[2]);
+
+    let file_name = |path: &Option<std::path::PathBuf>| {
+        path.as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| t("import-no-file").to_string())
+    };
+    let files = vec![
+        Line::from(t("import-register").replace("{file}", &file_name(&export.register))),
+        Line::from(t("import-plan").replace("{file}", &file_name(&export.plan))),
+    ];
+    f.render_widget(
+        Paragraph::new(files)
+            .style(theme::help_text_style())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        chunks[3],
+    );
+
+    let instructions = Line::from(vec![
+        Span::styled(
+            t("confirm-yes"),
+            Style::default()
+                .fg(theme::COLOR_POSITIVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("/ "),
+        Span::styled(
+            t("confirm-no"),
+            Style::default()
+                .fg(theme::COLOR_NEGATIVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    f.render_widget(
+        Paragraph::new(instructions).alignment(Alignment::Center),
+        chunks[4],
+    );
+}
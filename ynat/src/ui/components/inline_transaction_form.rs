@@ -6,8 +6,12 @@ use ratatui::{
     Frame,
 };
 
+use crate::error::AppError;
+use crate::i18n::t;
 use crate::state::{FormField, SubTransactionField, TransactionFormState};
 use crate::ui::{components::autocomplete_input::AutocompleteInput, theme, utils};
+use crate::utils::currency::ForeignAmount;
+use crate::utils::money;
 use ynab_api::endpoints::budgets::BudgetSummary;
 
 /// Which field the autocomplete dropdown is anchored to
@@ -40,7 +44,7 @@ pub fn get_autocomplete_overlay(form_state: &TransactionFormState) -> Option<Aut
                     .map(|p| p.name.clone())
                     .collect(),
                 selected_index: form_state.payee_selection_index,
-                hint: None,
+                hint: same_as_last_hint(form_state),
             })
         }
         Some(FormField::Category) if form_state.is_split_mode => {
@@ -94,7 +98,7 @@ pub fn get_autocomplete_overlay(form_state: &TransactionFormState) -> Option<Aut
 }
 
 /// Column constraints matching the transaction table layout
-pub const FORM_COLUMN_CONSTRAINTS: [Constraint; 8] = [
+pub const FORM_COLUMN_CONSTRAINTS: [Constraint; 9] = [
     Constraint::Length(1),      // Flag
     Constraint::Length(10),     // Date
     Constraint::Percentage(30), // Payee
@@ -103,6 +107,7 @@ pub const FORM_COLUMN_CONSTRAINTS: [Constraint; 8] = [
     Constraint::Percentage(15), // Amount
     Constraint::Length(1),      // Approved
     Constraint::Length(1),      // Cleared
+    Constraint::Length(12),     // Running balance
 ];
 
 /// Check if there's a validation error to display
@@ -113,7 +118,7 @@ pub fn has_validation_error(form_state: &TransactionFormState) -> bool {
 /// Render the validation error message above the table
 pub fn render_validation_error(f: &mut Frame, area: Rect, form_state: &TransactionFormState) {
     if let Some(ref error) = form_state.validation_error {
-        let error_text = format!(" Error: {}", error);
+        let error_text = format!(" Error: {}", super::error_panel::error_line(error));
         let paragraph = ratatui::widgets::Paragraph::new(
             Span::from(error_text).style(
                 Style::default()
@@ -216,22 +221,16 @@ pub fn render_subtransaction_rows_direct(
         f.render_widget(hint_span, columns[2]);
 
         // Calculate and render remaining amount in amount column
-        let parent_amount: f64 = form_state.amount.parse().unwrap_or(0.0);
-        let sum_of_splits: f64 = form_state
-            .subtransactions
-            .iter()
-            .filter_map(|s| s.amount.parse::<f64>().ok())
-            .sum();
-        let remaining = parent_amount - sum_of_splits;
+        let remaining = form_state.split_remaining();
 
-        let (remaining_text, remaining_style) = if remaining.abs() < 0.001 {
+        let (remaining_text, remaining_style) = if remaining == 0 {
             (
                 "✓ Balanced".to_string(),
                 Style::default().fg(theme::COLOR_POSITIVE),
             )
         } else {
             (
-                format!("{:+.2} remaining", remaining),
+                remaining_label(form_state, remaining),
                 Style::default().fg(theme::COLOR_NEGATIVE),
             )
         };
@@ -333,10 +332,16 @@ fn render_payee_dropdown(f: &mut Frame, area: Rect, form_state: &TransactionForm
             .focused(true)
             .items(&items)
             .selected_index(form_state.payee_selection_index)
+            .hint(same_as_last_hint(form_state))
             .render(f, area);
     }
 }
 
+/// Point out Ctrl+P while the payee is still empty and there's something to fill in
+fn same_as_last_hint(form_state: &TransactionFormState) -> Option<&'static str> {
+    (form_state.payee.is_empty() && form_state.last_used.is_some()).then_some("Ctrl+P same as last")
+}
+
 /// Render category field without the autocomplete dropdown (just the input text)
 fn render_category_field_no_dropdown(f: &mut Frame, area: Rect, form_state: &TransactionFormState) {
     let is_focused = form_state.current_field == Some(FormField::Category);
@@ -422,6 +427,20 @@ fn render_amount_field(f: &mut Frame, area: Rect, form_state: &TransactionFormSt
         theme::form_field_style()
     };
 
+    if let Some(ref prompt) = form_state.rate_prompt {
+        let text = Text::from(Line::from(vec![
+            Span::from(format!(
+                "{} ",
+                t("form-rate-prompt").replace("{currency}", &prompt.amount.currency)
+            ))
+            .style(theme::help_text_style()),
+            Span::from(format!("{}_", prompt.rate)).style(style),
+        ]))
+        .right_aligned();
+        f.render_widget(text, area);
+        return;
+    }
+
     let value = if form_state.amount.is_empty() {
         "_______".to_string()
     } else {
@@ -429,10 +448,46 @@ fn render_amount_field(f: &mut Frame, area: Rect, form_state: &TransactionFormSt
     };
 
     // Right-align the amount
-    let text = Text::from(Line::from(Span::from(value).style(style))).right_aligned();
+    let mut spans = flow_indicator(&form_state.amount);
+    spans.push(Span::from(value).style(style));
+    if let Some(rule) = form_state.rounding.label().filter(|_| is_focused) {
+        spans.push(Span::from(format!(" {}", rule)).style(theme::help_text_style()));
+    }
+    let text = Text::from(Line::from(spans)).right_aligned();
     f.render_widget(text, area);
 }
 
+/// What's left to split, signed like "+12.50 remaining"
+fn remaining_label(form_state: &TransactionFormState, remaining: i64) -> String {
+    let sign = if remaining > 0 { "+" } else { "" };
+    format!(
+        "{}{} remaining",
+        sign,
+        money::format(remaining, form_state.currency_format.as_ref())
+    )
+}
+
+/// "Outflow"/"Inflow" label ahead of an amount, so the sign is hard to miss
+fn flow_indicator(amount: &str) -> Vec<Span<'static>> {
+    let value = ForeignAmount::parse(amount)
+        .map(|foreign| foreign.value)
+        .or_else(|| {
+            crate::utils::math::evaluate_expression(amount).and_then(|v| v.parse::<f64>().ok())
+        })
+        .unwrap_or_default();
+    let (label, color) = if value < 0.0 || amount.trim() == "-" {
+        (t("form-outflow"), theme::COLOR_NEGATIVE)
+    } else if value > 0.0 {
+        (t("form-inflow"), theme::COLOR_POSITIVE)
+    } else {
+        return vec![];
+    };
+    vec![
+        Span::from(label).style(Style::default().fg(color)),
+        Span::raw(" "),
+    ]
+}
+
 fn render_cleared_field(f: &mut Frame, area: Rect, form_state: &TransactionFormState) {
     let is_focused = form_state.current_field == Some(FormField::Cleared);
     let style = if is_focused {
@@ -630,7 +685,9 @@ fn build_amount_text(form_state: &TransactionFormState) -> Text<'static> {
         form_state.amount.clone()
     };
 
-    Text::from(Line::from(Span::from(value).style(style)))
+    let mut spans = flow_indicator(&form_state.amount);
+    spans.push(Span::from(value).style(style));
+    Text::from(Line::from(spans))
 }
 
 fn build_flag_cell(form_state: &TransactionFormState) -> Cell<'static> {
@@ -733,8 +790,8 @@ fn build_cleared_cell(form_state: &TransactionFormState) -> Cell<'static> {
 }
 
 /// Build error row to display validation errors
-fn build_error_row(error: &str) -> Row<'static> {
-    let error_text = format!(" Error: {}", error);
+fn build_error_row(error: &AppError) -> Row<'static> {
+    let error_text = format!(" Error: {}", super::error_panel::error_line(error));
     let cell = Cell::from(Text::from(
         Span::from(error_text).style(
             Style::default()
@@ -862,21 +919,15 @@ fn build_subtransaction_row(
 /// Build hint row showing split mode keyboard shortcuts
 fn build_split_mode_hint_row(form_state: &TransactionFormState) -> Row<'static> {
     // Calculate remaining amount
-    let parent_amount: f64 = form_state.amount.parse().unwrap_or(0.0);
-    let sum_of_splits: f64 = form_state
-        .subtransactions
-        .iter()
-        .filter_map(|s| s.amount.parse::<f64>().ok())
-        .sum();
-    let remaining = parent_amount - sum_of_splits;
-
-    let remaining_text = if remaining.abs() < 0.001 {
+    let remaining = form_state.split_remaining();
+
+    let remaining_text = if remaining == 0 {
         "✓ Balanced".to_string()
     } else {
-        format!("{:+.2} remaining", remaining)
+        remaining_label(form_state, remaining)
     };
 
-    let remaining_style = if remaining.abs() < 0.001 {
+    let remaining_style = if remaining == 0 {
         Style::default().fg(theme::COLOR_POSITIVE)
     } else {
         Style::default().fg(theme::COLOR_NEGATIVE)
@@ -6,16 +6,37 @@ use ratatui::{
     Frame,
 };
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::i18n::t;
 use crate::state::LoadingState;
+use crate::ui::theme;
+use crate::utils::platform;
+
+/// Whether the spinner animates; set once at startup from config
+static ANIMATED: AtomicBool = AtomicBool::new(true);
+
+/// Switch between the animated spinner and a static "Loading…" label
+pub fn set_animated(animated: bool) {
+    ANIMATED.store(animated, Ordering::Relaxed);
+}
 
 /// Render a loading indicator in the top-right corner
 /// Shows current loading state with color coding
 pub fn render_loading_indicator(f: &mut Frame, area: Rect, loading_state: &LoadingState) {
     let (text, color) = match &loading_state {
         LoadingState::NotStarted => return, // Don't show anything
+        LoadingState::Loading(_) if !ANIMATED.load(Ordering::Relaxed) => {
+            (t("loading-indicator"), theme::COLOR_LOADING)
+        }
         LoadingState::Loading(throbber_state) => {
-            let simple = throbber_widgets_tui::Throbber::default()
-                .throbber_set(throbber_widgets_tui::BRAILLE_EIGHT);
+            // Legacy Windows consoles can't draw braille, fall back to ASCII
+            let throbber_set = if platform::supports_unicode_symbols() {
+                throbber_widgets_tui::BRAILLE_EIGHT
+            } else {
+                throbber_widgets_tui::ASCII
+            };
+            let simple = throbber_widgets_tui::Throbber::default().throbber_set(throbber_set);
             f.render_stateful_widget(simple, area, &mut throbber_state.clone());
             return;
         }
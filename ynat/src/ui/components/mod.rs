@@ -1,12 +1,31 @@
+pub mod archive_review;
 pub mod autocomplete_input;
+pub mod context_menu;
 pub mod delete_confirmation;
+pub mod delta_conflict;
 pub mod empty_state;
+pub mod error_panel;
+pub mod export_confirmation;
 pub mod filter_input;
+pub mod goal_calculator;
+pub mod goal_form;
+pub mod health_checklist;
 pub mod help_bar;
 pub mod help_popup;
+pub mod import_confirmation;
 pub mod inline_transaction_form;
 pub mod loading_indicator;
+pub mod move_money;
+pub mod notice;
+pub mod payee_merge_confirmation;
 pub mod popup;
+pub mod quick_budget;
+pub mod rate_limit_banner;
+pub mod read_only_badge;
 pub mod reconcile_confirmation;
 pub mod reconciled_edit_confirmation;
 pub mod screen_title;
+pub mod spending_counter;
+pub mod sync_status;
+pub mod table_skeleton;
+pub mod transaction_detail;
@@ -0,0 +1,113 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::i18n::t;
+use crate::state::{MoveMoneyField, MoveMoneyState};
+use crate::ui::{components::error_panel, layouts, theme, utils};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+/// Render the popup for moving money out of a category
+pub fn render_move_money(f: &mut Frame, form: &MoveMoneyState, budget: Option<&BudgetSummary>) {
+    let title = t("move-money-title").replace("{category}", &form.source_name);
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        &title,
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Available
+            Constraint::Length(1), // Empty line
+            Constraint::Length(1), // Amount
+            Constraint::Length(1), // Destination
+            Constraint::Min(0),    // Destination matches
+            Constraint::Length(1), // Validation error
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let available = Line::from(vec![
+        Span::raw(format!("{:15}", t("move-money-available"))),
+        Span::styled(
+            utils::format_amount(form.available, budget),
+            Style::default()
+                .fg(utils::get_amount_color(form.available))
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(available), chunks[0]);
+
+    f.render_widget(
+        input_line(
+            t("move-money-amount"),
+            &form.amount_input,
+            form.field == MoveMoneyField::Amount,
+        ),
+        chunks[2],
+    );
+    f.render_widget(
+        input_line(
+            t("move-money-destination"),
+            &form.destination_input,
+            form.field == MoveMoneyField::Destination,
+        ),
+        chunks[3],
+    );
+
+    let matches: Vec<Line> = form
+        .destinations
+        .iter()
+        .enumerate()
+        .map(|(i, category)| {
+            let name = match category.category_group_name {
+                Some(ref group) => format!("{}: {}", group, category.name),
+                None => category.name.clone(),
+            };
+            let style = if i == form.selected_destination {
+                theme::selection_style()
+            } else {
+                theme::help_text_style()
+            };
+            Line::from(vec![
+                Span::styled(format!("{:16}", ""), Style::default()),
+                Span::styled(name, style),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(matches), chunks[4]);
+
+    if let Some(ref error) = form.validation_error {
+        f.render_widget(
+            Paragraph::new(error_panel::error_line(error))
+                .style(Style::default().fg(theme::COLOR_NEGATIVE)),
+            chunks[5],
+        );
+    }
+
+    let instructions = Paragraph::new(t("move-money-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[6]);
+}
+
+fn input_line<'a>(label: &str, input: &str, focused: bool) -> Paragraph<'a> {
+    let (style, text) = if focused {
+        (theme::form_field_focused_style(), format!("{}_", input))
+    } else {
+        (theme::form_field_style(), input.to_string())
+    };
+    Paragraph::new(Line::from(vec![
+        Span::raw(format!("{:15}", label)),
+        Span::styled(text, style),
+    ]))
+}
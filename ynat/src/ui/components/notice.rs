@@ -0,0 +1,26 @@
+//! One-off notice drawn over the help bar until the next key press.
+
+use ratatui::{
+    layout::Alignment,
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::ui::{layouts, theme};
+
+pub fn render_notice(f: &mut Frame, text: &str) {
+    let (_, _, help_area) = layouts::screen_layout(f.area());
+
+    let notice = Paragraph::new(text)
+        .style(Style::default().fg(theme::COLOR_LOADING))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme::COLOR_LOADING)),
+        );
+
+    f.render_widget(Clear, help_area);
+    f.render_widget(notice, help_area);
+}
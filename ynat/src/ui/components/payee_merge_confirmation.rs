@@ -0,0 +1,58 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::i18n::{t, tn};
+use crate::ui::{layouts, theme};
+
+/// Render a confirmation popup for merging payee groups
+pub fn render_payee_merge_confirmation(f: &mut Frame, groups: usize, duplicates: usize) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::MEDIUM,
+        t("payee-merge-title"),
+        theme::danger_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Question
+            Constraint::Length(1), // Empty line
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let question = tn("payee-merge-question", duplicates).replace("{groups}", &groups.to_string());
+    let question = Paragraph::new(question)
+        .style(theme::loading_style().add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(question, chunks[0]);
+
+    let instructions = Line::from(vec![
+        Span::styled(
+            t("confirm-yes"),
+            Style::default()
+                .fg(theme::COLOR_POSITIVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("/ "),
+        Span::styled(
+            t("confirm-no"),
+            Style::default()
+                .fg(theme::COLOR_NEGATIVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    f.render_widget(
+        Paragraph::new(instructions).alignment(Alignment::Center),
+        chunks[2],
+    );
+}
@@ -0,0 +1,125 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Cell, Paragraph, Row, Table},
+    Frame,
+};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+use crate::i18n::{t, tn};
+use crate::quick_budget;
+use crate::state::{LoadingState, QuickBudgetState};
+use crate::ui::{components::empty_state, layouts, theme, utils};
+
+/// Render the amounts a quick-budget command would assign, with what it
+/// leaves in Ready to Assign
+pub fn render_quick_budget(
+    f: &mut Frame,
+    quick: &QuickBudgetState,
+    budget: Option<&BudgetSummary>,
+) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::LARGE,
+        &format!(" {} ", quick.action.label()),
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Summary
+            Constraint::Min(3),    // Changes
+            Constraint::Length(2), // Ready to Assign
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    match &quick.loading {
+        LoadingState::Loading(..) | LoadingState::NotStarted => {
+            empty_state::render_loading_state(f, chunks[1], "Status", t("quick-budget-loading"));
+        }
+        LoadingState::Error(error) => {
+            f.render_widget(
+                Paragraph::new(super::error_panel::error_line(error))
+                    .style(Style::default().fg(theme::COLOR_NEGATIVE)),
+                chunks[1],
+            );
+        }
+        LoadingState::Loaded if quick.changes.is_empty() => {
+            f.render_widget(
+                Paragraph::new(t("quick-budget-empty")).alignment(Alignment::Center),
+                chunks[1],
+            );
+        }
+        LoadingState::Loaded => {
+            let summary = tn("quick-budget-summary", quick.changes.len());
+            f.render_widget(Paragraph::new(Line::from(summary)), chunks[0]);
+
+            let rows: Vec<Row> = quick
+                .changes
+                .iter()
+                .map(|change| {
+                    let delta = change.delta();
+                    Row::new(vec![
+                        Cell::from(change.category_name.clone()),
+                        Cell::from(
+                            Text::from(utils::format_amount(change.from, budget)).right_aligned(),
+                        )
+                        .style(theme::help_text_style()),
+                        Cell::from(
+                            Text::from(utils::format_amount(change.to, budget)).right_aligned(),
+                        ),
+                        Cell::from(
+                            Text::from(format!(
+                                "{}{}",
+                                if delta > 0 { "+" } else { "" },
+                                utils::format_amount(delta, budget)
+                            ))
+                            .right_aligned(),
+                        )
+                        .style(Style::default().fg(utils::get_amount_color(delta))),
+                    ])
+                })
+                .collect();
+
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(20),
+                ],
+            )
+            .row_highlight_style(theme::selection_style());
+            f.render_stateful_widget(table, chunks[1], &mut quick.table_state.borrow_mut());
+
+            let total = quick_budget::total(&quick.changes);
+            let after = quick.to_be_budgeted - total;
+            let impact = Line::from(vec![
+                Span::raw(format!("{}: ", t("quick-budget-total"))),
+                Span::styled(
+                    utils::format_amount(total, budget),
+                    Style::default().fg(utils::get_amount_color(total)),
+                ),
+                Span::raw(format!("   {}: ", t("quick-budget-ready-to-assign"))),
+                Span::raw(utils::format_amount(quick.to_be_budgeted, budget)),
+                Span::raw(" → "),
+                Span::styled(
+                    utils::format_amount(after, budget),
+                    Style::default().fg(utils::get_amount_color(after)),
+                ),
+            ]);
+            f.render_widget(Paragraph::new(impact), chunks[2]);
+        }
+    }
+
+    let instructions = Paragraph::new(t("quick-budget-hint"))
+        .style(theme::help_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(instructions, chunks[3]);
+}
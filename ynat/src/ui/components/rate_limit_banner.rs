@@ -0,0 +1,36 @@
+//! Warning drawn in the middle of the title row while YNAB's hourly request
+//! quota is nearly used up.
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Paragraph,
+    Frame,
+};
+use ynab_api::RateLimit;
+
+use crate::i18n::t;
+use crate::ui::{layouts, theme};
+
+pub fn render_rate_limit_banner(f: &mut Frame, rate_limit: RateLimit) {
+    let (title_area, _, _) = layouts::screen_layout(f.area());
+    let text = t("rate-limit-warning")
+        .replace("{remaining}", &rate_limit.remaining().to_string())
+        .replace("{limit}", &rate_limit.limit.to_string());
+
+    // Only cover the text itself so the screen title keeps its style
+    let width = (text.chars().count() as u16).min(title_area.width);
+    let area = Rect {
+        x: title_area.x + (title_area.width - width) / 2,
+        width,
+        height: 1,
+        ..title_area
+    };
+
+    let banner = Paragraph::new(text).style(
+        Style::default()
+            .fg(theme::COLOR_LOADING)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(banner, area);
+}
@@ -0,0 +1,34 @@
+//! Reminder that nothing can be changed, drawn at the right of the title row
+//! next to the loading indicator.
+
+use ratatui::{
+    layout::Alignment,
+    style::{Modifier, Style},
+    widgets::Paragraph,
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::i18n::t;
+use crate::ui::{layouts, theme};
+
+/// Returns the width taken, so other status text can sit beside it
+pub fn render_read_only_badge(f: &mut Frame) -> u16 {
+    let (title_area, _, _) = layouts::screen_layout(f.area());
+    let (text_area, _) = layouts::title_with_loading(title_area);
+    // Leave a gap before the loading indicator
+    let mut area = text_area;
+    area.width = area.width.saturating_sub(1);
+
+    let text = t("read-only-badge");
+    let width = text.width() as u16;
+    let badge = Paragraph::new(text)
+        .style(
+            Style::default()
+                .fg(theme::COLOR_LOADING)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Right);
+    f.render_widget(badge, area);
+    width
+}
@@ -6,7 +6,9 @@ use ratatui::{
     Frame,
 };
 
-use crate::ui::{layouts, theme, utils};
+use crate::i18n::t;
+use crate::ui::{layouts, theme};
+use crate::utils::money;
 use ynab_api::endpoints::CurrencyFormat;
 
 /// Render a confirmation popup for account reconciliation
@@ -20,7 +22,7 @@ pub fn render_reconcile_confirmation(
         f,
         f.area(),
         layouts::popup_sizes::MEDIUM,
-        " Reconcile Account ",
+        t("reconcile-title"),
         theme::info_border_style(),
     );
 
@@ -38,7 +40,7 @@ pub fn render_reconcile_confirmation(
         .split(inner);
 
     // Question
-    let question = Paragraph::new("Does your current account balance match the cleared balance?")
+    let question = Paragraph::new(t("reconcile-question"))
         .style(
             Style::default()
                 .fg(ratatui::style::Color::White)
@@ -47,45 +49,40 @@ pub fn render_reconcile_confirmation(
         .alignment(Alignment::Center);
     f.render_widget(question, chunks[0]);
 
-    // Format the cleared balance
-    let balance_value = cleared_balance as f64 / 1000.0;
-    let formatted_balance = if let Some(fmt) = currency_format {
-        utils::fmt_currency(cleared_balance, fmt)
-            .content
-            .to_string()
-    } else {
-        format!("${:.2}", balance_value)
-    };
+    let formatted_balance = money::format(cleared_balance, currency_format);
+    let balance_color = theme::amount_color(cleared_balance);
 
-    let balance_color = theme::amount_color_f64(balance_value);
-
-    let balance_text = Paragraph::new(format!("Cleared balance: {}", formatted_balance))
-        .style(
-            Style::default()
-                .fg(balance_color)
-                .add_modifier(Modifier::BOLD),
-        )
-        .alignment(Alignment::Center);
+    let balance_text = Paragraph::new(format!(
+        "{} {}",
+        t("reconcile-cleared-balance"),
+        formatted_balance
+    ))
+    .style(
+        Style::default()
+            .fg(balance_color)
+            .add_modifier(Modifier::BOLD),
+    )
+    .alignment(Alignment::Center);
     f.render_widget(balance_text, chunks[2]);
 
     // Instructions
     let instructions = Line::from(vec![
         Span::styled(
-            "[Y]es ",
+            t("confirm-yes"),
             Style::default()
                 .fg(theme::COLOR_POSITIVE)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw("- Mark cleared transactions as reconciled / "),
+        Span::raw(t("reconcile-yes-hint")),
         Span::styled(
-            "[N]o ",
+            t("confirm-no"),
             Style::default()
                 .fg(theme::COLOR_NEGATIVE)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("/ "),
         Span::styled("[Esc]", Style::default().fg(theme::COLOR_HELP_TEXT)),
-        Span::raw(" Cancel"),
+        Span::raw(t("confirm-cancel")),
     ]);
     let instructions_para = Paragraph::new(instructions).alignment(Alignment::Center);
     f.render_widget(instructions_para, chunks[4]);
@@ -6,6 +6,7 @@ use ratatui::{
     Frame,
 };
 
+use crate::i18n::t;
 use crate::ui::{layouts, theme};
 
 /// Render a confirmation popup for editing reconciled transactions
@@ -15,7 +16,7 @@ pub fn render_reconciled_edit_confirmation(f: &mut Frame) {
         f,
         f.area(),
         layouts::popup_sizes::MEDIUM,
-        " Edit Reconciled Transaction ",
+        t("reconciled-edit-title"),
         theme::loading_style().add_modifier(Modifier::BOLD), // Yellow for warning
     );
 
@@ -32,36 +33,32 @@ pub fn render_reconciled_edit_confirmation(f: &mut Frame) {
         .split(inner);
 
     // Warning message
-    let warning = Paragraph::new(
-        "This transaction is marked as RECONCILED.\nAre you sure you want to edit it?",
-    )
-    .style(theme::loading_style().add_modifier(Modifier::BOLD))
-    .alignment(Alignment::Center);
+    let warning = Paragraph::new(t("reconciled-edit-warning"))
+        .style(theme::loading_style().add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center);
     f.render_widget(warning, chunks[0]);
 
     // Explanation
-    let explanation = Paragraph::new(
-        "Editing reconciled transactions may cause discrepancies\nwith your bank records.",
-    )
-    .style(
-        Style::default()
-            .fg(theme::COLOR_HELP_TEXT)
-            .add_modifier(Modifier::ITALIC),
-    )
-    .alignment(Alignment::Center);
+    let explanation = Paragraph::new(t("reconciled-edit-explanation"))
+        .style(
+            Style::default()
+                .fg(theme::COLOR_HELP_TEXT)
+                .add_modifier(Modifier::ITALIC),
+        )
+        .alignment(Alignment::Center);
     f.render_widget(explanation, chunks[2]);
 
     // Instructions
     let instructions = Line::from(vec![
         Span::styled(
-            "[Y]es, Edit ",
+            t("reconciled-edit-yes"),
             Style::default()
                 .fg(theme::COLOR_POSITIVE)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("/ "),
         Span::styled(
-            "[N]o, Cancel ",
+            t("reconciled-edit-no"),
             Style::default()
                 .fg(theme::COLOR_NEGATIVE)
                 .add_modifier(Modifier::BOLD),
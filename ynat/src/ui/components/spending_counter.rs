@@ -0,0 +1,34 @@
+//! What the budget spent today and this week, drawn at the right of the title
+//! row, left of any sync or read-only badge.
+
+use ratatui::{layout::Alignment, style::Style, widgets::Paragraph, Frame};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+use crate::i18n::t;
+use crate::ui::{layouts, theme, utils};
+use crate::utils::balance_history::RecentSpending;
+
+pub fn render_spending_counter(
+    f: &mut Frame,
+    spending: RecentSpending,
+    budget: Option<&BudgetSummary>,
+    badge_width: u16,
+) {
+    let (title_area, _, _) = layouts::screen_layout(f.area());
+    let (text_area, _) = layouts::title_with_loading(title_area);
+    // Leave a gap before the loading indicator, and two more before a badge
+    let mut area = text_area;
+    let gap = if badge_width > 0 { badge_width + 2 } else { 0 };
+    area.width = area.width.saturating_sub(1 + gap);
+
+    let text = t("spending-counter")
+        .replace(
+            "{today}",
+            utils::format_amount(spending.today, budget).trim(),
+        )
+        .replace("{week}", utils::format_amount(spending.week, budget).trim());
+    let counter = Paragraph::new(text)
+        .style(Style::default().fg(theme::COLOR_HELP_TEXT))
+        .alignment(Alignment::Right);
+    f.render_widget(counter, area);
+}
@@ -0,0 +1,31 @@
+//! Count of offline changes still waiting to reach YNAB, drawn at the right
+//! of the title row next to the loading indicator.
+
+use ratatui::{
+    layout::Alignment,
+    style::Style,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::i18n::tn;
+use crate::ui::{layouts, theme};
+
+/// Returns the width taken, so other status text can sit beside it
+pub fn render_sync_status(f: &mut Frame, pending: usize) -> u16 {
+    let (title_area, _, _) = layouts::screen_layout(f.area());
+    let (text_area, _) = layouts::title_with_loading(title_area);
+    // Leave a gap before the loading indicator
+    let mut area = text_area;
+    area.width = area.width.saturating_sub(1);
+
+    let text = tn("sync-pending", pending);
+    let width = text.width() as u16;
+    let status = Paragraph::new(text)
+        .style(Style::default().fg(theme::COLOR_LOADING))
+        .alignment(Alignment::Right)
+        .wrap(Wrap { trim: true });
+    f.render_widget(status, area);
+    width
+}
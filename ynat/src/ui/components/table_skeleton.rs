@@ -0,0 +1,110 @@
+//! Placeholder rows drawn in place of a table while its first load is in
+//! flight, so the screen has its final shape straight away.
+//!
+//! Each screen names its table; the number of rows it last showed is kept for
+//! the rest of the session so the skeleton matches it the next time the
+//! screen loads. Until then the skeleton fills the space.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+
+use crate::ui::theme;
+use crate::utils::platform;
+
+/// Rows each table showed last, by table name
+static ROW_COUNTS: Mutex<Option<HashMap<&'static str, usize>>> = Mutex::new(None);
+
+/// Share of a column each placeholder fills, varied so rows don't look identical
+const FILLS: [u16; 5] = [80, 55, 70, 40, 65];
+
+/// Record how many rows `table` has on screen, to size its next skeleton
+pub fn remember_rows(table: &'static str, rows: usize) {
+    if let Ok(mut counts) = ROW_COUNTS.lock() {
+        counts.get_or_insert_with(HashMap::new).insert(table, rows);
+    }
+}
+
+fn remembered_rows(table: &'static str) -> Option<usize> {
+    ROW_COUNTS.lock().ok()?.as_ref()?.get(table).copied()
+}
+
+/// Render a bordered table of dimmed placeholders with the table's own
+/// column widths, titled with a loading message
+pub fn render_table_skeleton(
+    f: &mut Frame,
+    area: Rect,
+    table: &'static str,
+    title: &str,
+    widths: &[Constraint],
+) {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let inner = block.inner(area);
+    let columns = Layout::horizontal(widths.iter().copied())
+        .spacing(theme::TABLE_COLUMN_SPACING)
+        .split(inner);
+
+    let fits = inner.height as usize;
+    let count = remembered_rows(table).map_or(fits, |rows| rows.clamp(1, fits.max(1)));
+    let symbol = if platform::supports_unicode_symbols() {
+        "░"
+    } else {
+        "-"
+    };
+
+    let rows = (0..count).map(|row| {
+        Row::new(columns.iter().enumerate().map(|(column, rect)| {
+            let fill = FILLS[(row + column) % FILLS.len()];
+            symbol.repeat((rect.width * fill / 100).max(1) as usize)
+        }))
+    });
+    let skeleton = Table::new(rows, widths.iter().copied())
+        .block(block)
+        .column_spacing(theme::TABLE_COLUMN_SPACING)
+        .style(
+            Style::default()
+                .fg(theme::COLOR_ZERO)
+                .add_modifier(Modifier::DIM),
+        );
+
+    f.render_widget(skeleton, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    /// Rows of placeholders drawn for `table` in a 40x12 frame
+    fn skeleton_rows(table: &'static str) -> usize {
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal
+            .draw(|f| {
+                let widths = [Constraint::Percentage(50), Constraint::Percentage(50)];
+                render_table_skeleton(f, f.area(), table, "Loading...", &widths);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (1..buffer.area.height - 1)
+            .filter(|&y| {
+                (1..buffer.area.width - 1).any(|x| matches!(buffer[(x, y)].symbol(), "░" | "-"))
+            })
+            .count()
+    }
+
+    #[test]
+    fn skeletons_fill_the_table_until_its_size_is_known() {
+        assert_eq!(skeleton_rows("skeleton_test_unseen"), 10);
+
+        remember_rows("skeleton_test_seen", 3);
+        assert_eq!(skeleton_rows("skeleton_test_seen"), 3);
+        remember_rows("skeleton_test_seen", 50);
+        assert_eq!(skeleton_rows("skeleton_test_seen"), 10);
+    }
+}
@@ -0,0 +1,112 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::i18n::t;
+use crate::state::{PayeeLocationLookup, TransactionDetail};
+use crate::ui::{layouts, theme, utils};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+/// Render a transaction in full with where its payee is
+pub fn render_transaction_detail(
+    f: &mut Frame,
+    detail: &TransactionDetail,
+    budget: Option<&BudgetSummary>,
+    can_open_maps: bool,
+) {
+    let inner = super::popup::render_popup_frame(
+        f,
+        f.area(),
+        layouts::popup_sizes::FORM,
+        t("detail-title"),
+        theme::info_border_style(),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(6),    // Fields
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner);
+
+    let transaction = &detail.transaction;
+    let amount: i64 = transaction.amount.into();
+    let field = |label: &'static str, value: Span<'static>| {
+        Line::from(vec![
+            Span::styled(format!("{:<13}", t(label)), theme::help_text_style()),
+            value,
+        ])
+    };
+    let text = |value: Option<&str>| Span::raw(value.unwrap_or("—").to_string());
+
+    let mut lines = vec![
+        field("detail-date", Span::raw(transaction.date.to_string())),
+        field("detail-payee", text(transaction.payee_name.as_deref())),
+        field(
+            "detail-category",
+            text(transaction.category_name.as_deref()),
+        ),
+        field(
+            "detail-account",
+            Span::raw(transaction.account_name.clone()),
+        ),
+        field(
+            "detail-amount",
+            Span::styled(
+                utils::format_amount(amount, budget),
+                Style::default()
+                    .fg(theme::amount_color(amount))
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ),
+        field("detail-memo", text(transaction.memo.as_deref())),
+        Line::default(),
+    ];
+    match &detail.location {
+        PayeeLocationLookup::Loading => lines.push(field(
+            "detail-location",
+            Span::styled(t("detail-location-loading"), theme::loading_style()),
+        )),
+        PayeeLocationLookup::Found(place) => {
+            let city = place.city.as_deref().unwrap_or(t("detail-unknown-city"));
+            lines.push(field(
+                "detail-location",
+                Span::styled(city.to_string(), theme::title_style()),
+            ));
+            lines.push(field(
+                "detail-coordinates",
+                Span::raw(format!("{}, {}", place.latitude, place.longitude)),
+            ));
+        }
+        PayeeLocationLookup::Missing => lines.push(field(
+            "detail-location",
+            Span::styled(t("detail-no-location"), theme::help_text_style()),
+        )),
+        PayeeLocationLookup::Failed(error) => lines.push(field(
+            "detail-location",
+            Span::styled(
+                t("detail-location-failed").replace("{error}", error),
+                Style::default().fg(theme::COLOR_NEGATIVE),
+            ),
+        )),
+    }
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    let mut instructions = Vec::new();
+    if can_open_maps && matches!(detail.location, PayeeLocationLookup::Found(_)) {
+        instructions.push(Span::styled("[o]", theme::title_style()));
+        instructions.push(Span::raw(t("detail-open-in-maps")));
+    }
+    instructions.push(Span::styled("[Esc]", theme::title_style()));
+    instructions.push(Span::raw(t("detail-close")));
+    f.render_widget(
+        Paragraph::new(Line::from(instructions)).style(theme::help_text_style()),
+        chunks[1],
+    );
+}
@@ -24,6 +24,14 @@ pub fn screen_layout(area: Rect) -> (Rect, Rect, Rect) {
     (chunks[0], chunks[1], chunks[2])
 }
 
+/// Rows of a bordered table with a header that fit on a terminal `height`
+/// rows tall, in the standard [`screen_layout`]
+pub fn table_rows(height: u16) -> usize {
+    // Two borders and the header
+    let chrome = 2 * SCREEN_MARGIN + TITLE_HEIGHT + HELP_BAR_HEIGHT + 3;
+    usize::from(height.saturating_sub(chrome).max(1))
+}
+
 /// Screen layout with filter input visible.
 ///
 /// Returns a tuple of (title_area, filter_area, content_area, help_area)
@@ -114,6 +122,9 @@ pub mod popup_sizes {
     /// Medium popup (60% x 30%) - for confirmations with more info
     pub const MEDIUM: (u16, u16) = (60, 30);
 
+    /// Form popup (60% x 50%) - for small input dialogs
+    pub const FORM: (u16, u16) = (60, 50);
+
     /// Large popup (80% x 80%) - for help screens and complex dialogs
     pub const LARGE: (u16, u16) = (80, 80);
 }
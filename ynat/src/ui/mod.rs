@@ -4,85 +4,146 @@ pub mod screens;
 pub mod theme;
 pub mod utils;
 
+use crate::api_log::ApiLog;
 use crate::log_buffer::LogBuffer;
-use crate::state::{AppState, InputMode};
+use crate::state::AppState;
+use ratatui::buffer::Buffer;
 use ratatui::Frame;
-use screens::*;
+use screens::RenderContext;
 
 /// Pure render dispatcher - routes to appropriate screen renderer
 /// This function is read-only and never mutates state
-pub fn render_app(f: &mut Frame, state: &AppState, log_buffer: &LogBuffer) {
+pub fn render_app(f: &mut Frame, state: &AppState, log_buffer: &LogBuffer, api_log: &ApiLog) {
     // Render the current screen
-    match state.current_screen() {
-        Screen::Budgets(budgets_state) => {
-            budgets_screen::render(f, budgets_state);
-        }
-        Screen::Accounts(accounts_state) => {
-            accounts_screen::render(f, accounts_state, state.current_budget.as_ref());
-        }
-        Screen::Transactions(transactions_state) => {
-            transactions_screen::render(f, transactions_state, state.current_budget.as_ref());
-
-            // Render delete confirmation popup if active
-            if transactions_state.input_mode == InputMode::DeleteConfirmation {
-                if let Some(ref transaction_id) =
-                    transactions_state.delete_confirmation_transaction_id
-                {
-                    // Find the transaction to show details
-                    if transactions_state
-                        .transactions
-                        .iter()
-                        .find(|t| t.id.to_string() == *transaction_id)
-                        .is_some()
-                    {
-                        components::delete_confirmation::render_delete_confirmation(f);
-                    }
-                }
-            }
+    // With the settings' date format applied
+    let budget = state.display_budget();
+    let ctx = RenderContext {
+        budget: budget.as_deref(),
+        config: &state.config,
+        budget_settings: &state.budget_settings,
+        log_buffer,
+        api_log,
+    };
+    state.current_screen().controller().render(f, &ctx);
 
-            // Render reconciled edit confirmation popup if active
-            if transactions_state.input_mode == InputMode::ReconciledEditConfirmation {
-                if let Some(ref transaction_id) = transactions_state.reconciled_edit_transaction_id
-                {
-                    // Find the transaction to show details
-                    if transactions_state
-                        .transactions
-                        .iter()
-                        .find(|t| t.id.to_string() == *transaction_id)
-                        .is_some()
-                    {
-                        components::reconciled_edit_confirmation::render_reconciled_edit_confirmation(
-                            f,
-                        );
-                    }
-                }
-            }
+    if let Some(rate_limit) = state.rate_limit_warning {
+        components::rate_limit_banner::render_rate_limit_banner(f, rate_limit);
+    }
 
-            // Render reconcile confirmation popup if active
-            if transactions_state.input_mode == InputMode::ReconcileConfirmation {
-                if let Some(cleared_balance) = transactions_state.reconcile_cleared_balance {
-                    let currency_format = state
-                        .current_budget
-                        .as_ref()
-                        .and_then(|b| b.currency_format.as_ref());
-                    components::reconcile_confirmation::render_reconcile_confirmation(
-                        f,
-                        cleared_balance,
-                        currency_format,
-                    );
-                }
-            }
-        }
-        Screen::Plan(plan_state) => {
-            screens::plan_screen::render(f, plan_state, state.current_budget.as_ref());
-        }
-        Screen::Logs(logs_state) => {
-            screens::logs_screen::render(f, logs_state, log_buffer);
-        }
+    // Offline changes can't be sent while read-only, so the badge takes
+    // their place
+    let badge_width = if state.read_only {
+        components::read_only_badge::render_read_only_badge(f)
+    } else if state.pending_writes > 0 {
+        components::sync_status::render_sync_status(f, state.pending_writes)
+    } else {
+        0
+    };
+
+    if let Some(spending) = state.recent_spending {
+        components::spending_counter::render_spending_counter(
+            f,
+            spending,
+            state.current_budget.as_ref(),
+            badge_width,
+        );
+    }
+
+    if let Some(error) = state.current_error() {
+        components::error_panel::render_error_panel(f, error);
+    }
+
+    if let Some(ref notice) = state.notice {
+        components::notice::render_notice(f, notice);
+    }
+
+    if let Some(ref menu) = state.context_menu {
+        components::context_menu::render_context_menu(f, menu);
+    }
+
+    if let Some(ref path) = state.export_path {
+        components::export_confirmation::render_export_confirmation(f, path);
+    }
+
+    if let Some(ref preview) = state.import_preview {
+        let budget_name = state.current_budget.as_ref().map(|b| b.name.as_str());
+        components::import_confirmation::render_import_confirmation(f, preview, budget_name);
+    }
+
+    if let Some(ref detail) = state.transaction_detail {
+        components::transaction_detail::render_transaction_detail(
+            f,
+            detail,
+            state.current_budget.as_ref(),
+            !state.config.maps.command.is_empty(),
+        );
     }
 
     // Render help popup on top if visible
     if state.help_visible {
-        components::help_popup::render_help_popup(f, state.current_screen());
+        components::help_popup::render_help_popup(
+            f,
+            state.current_screen(),
+            &state.config,
+            state.user_id.as_deref(),
+        );
+    }
+
+    // Box-drawing characters take three bytes each, so over a slow link the
+    // borders are drawn in ASCII
+    if state.config.display.low_bandwidth_enabled() {
+        ascii_borders(f.buffer_mut());
+    }
+}
+
+/// Replace the line-drawing characters of borders with `+`, `-` and `|`
+fn ascii_borders(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        let ascii = match cell.symbol() {
+            "─" | "━" | "═" | "╌" | "╍" | "┄" | "┅" | "┈" | "┉" => "-",
+            "│" | "┃" | "║" | "╎" | "╏" | "┆" | "┇" | "┊" | "┋" => "|",
+            symbol
+                if symbol
+                    .chars()
+                    .next()
+                    .is_some_and(|c| ('\u{250C}'..='\u{257F}').contains(&c)) =>
+            {
+                "+"
+            }
+            _ => continue,
+        };
+        cell.set_symbol(ascii);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render(low_bandwidth: bool) -> String {
+        let mut state = AppState::new();
+        state.config.display.low_bandwidth = Some(low_bandwidth);
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal
+            .draw(|f| render_app(f, &state, &LogBuffer::new(0), &ApiLog::new(0)))
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn low_bandwidth_mode_draws_borders_in_ascii() {
+        let screen = render(true);
+        assert!(screen.contains("+-"), "{screen}");
+        assert!(screen.contains('|'));
+        assert!(!screen.chars().any(|c| ('\u{2500}'..='\u{257F}').contains(&c)));
+
+        assert!(render(false).contains("┌─"));
     }
 }
@@ -3,29 +3,63 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Row, Table},
 };
 
-use crate::state::{AccountsState, InputMode, LoadingState};
+use super::{RenderContext, ScreenController, ScreenEvent};
+use crate::budget_settings::BudgetSettings;
+use crate::config::{AppConfig, ColorsConfig};
+use crate::events::AppCommand;
+use crate::i18n::{t, tn};
+use crate::input::{Key, KeyEvent};
+use crate::reconcile_reminders::{self, ReminderStatus};
+use crate::reports::cash_flow::ProjectionHorizon;
+use crate::state::{AccountGroup, AccountsState, AppState, InputMode, LoadingState, Scrollable};
 use crate::ui::{
-    components::{empty_state, filter_input, help_bar, screen_title},
+    components::{empty_state, filter_input, help_bar, screen_title, table_skeleton},
     layouts, theme, utils,
 };
-use ynab_api::endpoints::{accounts::AccountType, budgets::BudgetSummary};
+use chrono::NaiveDate;
+use ynab_api::endpoints::{accounts::Account, budgets::BudgetSummary};
 
-pub fn render(f: &mut Frame, state: &AccountsState, budget: Option<&BudgetSummary>) {
-    if state.input_mode == InputMode::Filter {
+/// Width of the balance history sparkline column (one bar per 5 days of a 90-day history)
+const SPARKLINE_WIDTH: usize = 18;
+
+pub fn render(
+    f: &mut Frame,
+    state: &AccountsState,
+    budget: Option<&BudgetSummary>,
+    colors: &ColorsConfig,
+    settings: &BudgetSettings,
+) {
+    if matches!(state.input_mode, InputMode::Filter | InputMode::AccountJump) {
         let (title_area, filter_area, content_area, help_area) =
             layouts::screen_layout_with_filter(f.area());
 
         screen_title::render_screen_title(f, title_area, &state.accounts_loading);
-        filter_input::render_filter_input(f, filter_area, &state.filter_query);
-        render_content(f, content_area, state, budget);
-        help_bar::render_help_bar(f, help_area, help_bar::HELP_TEXT_DEFAULT);
+        if state.input_mode == InputMode::AccountJump {
+            filter_input::render_jump_input(f, filter_area, &state.jump_query);
+        } else {
+            filter_input::render_filter_input(f, filter_area, &state.filter_query);
+        }
+        render_content(f, content_area, state, budget, colors, settings);
+        help_bar::render_help_bar(f, help_area, help_bar::help_text_default());
     } else {
         let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
 
         screen_title::render_screen_title(f, title_area, &state.accounts_loading);
-        render_content(f, content_area, state, budget);
-        help_bar::render_help_bar(f, help_area, help_bar::HELP_TEXT_DEFAULT);
+        render_content(f, content_area, state, budget, colors, settings);
+        help_bar::render_help_bar(f, help_area, help_bar::help_text_default());
+    }
+}
+
+fn column_widths(settings: &BudgetSettings) -> Vec<Constraint> {
+    let mut widths = vec![Constraint::Percentage(40), Constraint::Percentage(20)];
+    if settings.balance_history_column {
+        widths.push(Constraint::Length(SPARKLINE_WIDTH as u16));
     }
+    if settings.month_change_column {
+        widths.push(Constraint::Percentage(15));
+    }
+    widths.push(Constraint::Percentage(20));
+    widths
 }
 
 fn render_content(
@@ -33,10 +67,18 @@ fn render_content(
     area: Rect,
     state: &AccountsState,
     budget: Option<&BudgetSummary>,
+    colors: &ColorsConfig,
+    settings: &BudgetSettings,
 ) {
     // Show loading message if currently loading and no cached data
     if matches!(state.accounts_loading, LoadingState::Loading(..)) && state.accounts.is_empty() {
-        empty_state::render_loading_state(f, area, "Status", "Loading accounts...");
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "accounts",
+            t("accounts-loading"),
+            &column_widths(settings),
+        );
         return;
     }
 
@@ -46,68 +88,297 @@ fn render_content(
     // Show accounts table if we have data
     if !filtered.is_empty() {
         // Create table header
-        let header = Row::new(vec![
-            Cell::from("Account Name"),
-            Cell::from("Type"),
-            Cell::from(Text::from("Balance").right_aligned()),
-        ])
-        .style(theme::header_style())
-        .underlined();
+        let mut header = vec![Cell::from("Account Name"), Cell::from("Type")];
+        if settings.balance_history_column {
+            header.push(Cell::from("90 Days"));
+        }
+        if settings.month_change_column {
+            header.push(Cell::from(Text::from("This Month").right_aligned()));
+        }
+        header.push(Cell::from(Text::from("Balance").right_aligned()));
+        let header = Row::new(header).style(theme::header_style()).underlined();
+
+        let today = chrono::Local::now().date_naive();
 
         // Create table rows from filtered accounts
         let rows: Vec<Row> = filtered
             .iter()
             .map(|account| {
-                let balance_color = utils::get_amount_color(account.balance.into());
+                let balance_color =
+                    theme::threshold_color(account.balance.into(), &colors.balances);
                 let balance_str = utils::format_amount(account.balance.into(), budget);
 
-                Row::new(vec![
-                    Cell::from(account.name.clone()),
-                    Cell::from(format_account_type(account.account_type)),
+                let mut cells = vec![
+                    Cell::from(account_name(account, settings, today)),
+                    Cell::from(AccountGroup::of(account.account_type).label()),
+                ];
+                if settings.balance_history_column {
+                    // Balance trend sparkline (empty until transactions have been cached)
+                    cells.push(match state.balance_history.get(&account.id.to_string()) {
+                        Some(balances) => {
+                            Cell::from(utils::sparkline_text(balances, SPARKLINE_WIDTH))
+                                .style(Style::default().fg(utils::trend_color(balances)))
+                        }
+                        None => Cell::from(""),
+                    });
+                }
+                if settings.month_change_column {
+                    // Net change so far this month (empty until transactions have been cached)
+                    cells.push(match state.month_changes.get(&account.id.to_string()) {
+                        Some(&change) => Cell::from(
+                            Text::from(utils::format_amount(change, budget)).right_aligned(),
+                        )
+                        .style(Style::default().fg(theme::amount_color(change))),
+                        None => Cell::from(""),
+                    });
+                }
+                cells.push(
                     Cell::from(Text::from(balance_str).right_aligned())
                         .style(Style::default().fg(balance_color)),
-                ])
+                );
+                Row::new(cells)
             })
             .collect();
 
         // Update table title to show filter status
-        let title = if !state.filter_query.is_empty() {
-            format!("Accounts ({} filtered)", filtered.len())
+        let mut title = if !state.filter_query.is_empty() {
+            tn("accounts-title-filtered", filtered.len())
         } else {
-            "Accounts".to_string()
+            t("accounts-title").to_string()
         };
+        if let Some(group) = state.type_filter {
+            title = format!("{} · {}", title, group.label());
+        }
 
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Percentage(40),
-                Constraint::Percentage(40),
-                Constraint::Percentage(20),
-            ],
-        )
-        .header(header)
-        .block(Block::default().borders(Borders::ALL).title(title))
-        .row_highlight_style(theme::selection_style());
+        table_skeleton::remember_rows("accounts", rows.len());
+        let table = Table::new(rows, column_widths(settings))
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .row_highlight_style(theme::selection_style());
 
         f.render_stateful_widget(table, area, &mut state.table_state.borrow_mut());
     } else {
         // No matching accounts - show message based on filter state
-        let message = if !state.filter_query.is_empty() {
-            "No matching accounts"
+        let message = if !state.filter_query.is_empty() || state.type_filter.is_some() {
+            t("accounts-no-match")
         } else {
-            "No accounts found"
+            t("accounts-empty")
         };
 
-        empty_state::render_empty_state(f, area, "Accounts", message, None);
+        empty_state::render_empty_state(f, area, t("accounts-title"), message, None);
     }
 }
 
-fn format_account_type(account_type: AccountType) -> &'static str {
-    use AccountType::*;
-    match account_type {
-        Checking | Savings | Cash => "Cash",
-        CreditCard | LineOfCredit => "Credit",
-        Mortgage | AutoLoan | StudentLoan | PersonalLoan | MedicalDebt | OtherDebt => "Debt",
-        OtherAsset | OtherLiability => "Tracking",
+/// The account's name, starred if it's a favorite, with a badge when it's
+/// due for reconciling
+fn account_name(account: &Account, settings: &BudgetSettings, today: NaiveDate) -> Line<'static> {
+    let id = account.id.to_string();
+    let status = settings
+        .reconcile_reminders
+        .get(&id)
+        .and_then(|&cadence| reconcile_reminders::status(account, cadence, today))
+        .filter(|_| !account.closed);
+    let mut spans = Vec::new();
+    if settings.favorite_account_ids.contains(&id) {
+        spans.push(Span::styled(
+            format!("{} ", theme::FAVORITE_GLYPH),
+            Style::default().fg(theme::COLOR_FAVORITE),
+        ));
+    }
+    spans.push(Span::from(account.name.clone()));
+    match status {
+        Some(ReminderStatus::Due) => spans.push(Span::styled(
+            format!(" ● {}", t("accounts-reconcile-due")),
+            Style::default().fg(theme::COLOR_LOADING),
+        )),
+        Some(ReminderStatus::Overdue) => spans.push(Span::styled(
+            format!(" ● {}", t("accounts-reconcile-overdue")),
+            Style::default().fg(theme::COLOR_NEGATIVE),
+        )),
+        None => {}
+    }
+    Line::from(spans)
+}
+
+impl ScreenController for AccountsState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.budget, &ctx.config.colors, ctx.budget_settings);
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            // Per-row action menu
+            Key::Char(' ' | 'o') => Some(AppCommand::OpenContextMenu),
+            Key::Char('/') => Some(AppCommand::EnterFilterMode),
+            Key::Char('.') => Some(AppCommand::ToggleShowClosedAccounts),
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Enter | Key::Right | Key::Char('l') => {
+                // Load transactions for selected account
+                if let Some(budget_id) = &state.current_budget_id {
+                    let filtered_accounts = self.filtered_accounts();
+
+                    if self.table_state.borrow().selected().unwrap() < filtered_accounts.len() {
+                        let account_id = filtered_accounts
+                            [self.table_state.borrow().selected().unwrap()]
+                        .id
+                        .to_string();
+                        Some(AppCommand::LoadTransactions {
+                            budget_id: budget_id.clone(),
+                            account_id,
+                            force_refresh: false,
+                        })
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+            Key::Char('R') => {
+                // Refresh everything for the current budget in one batch
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::RefreshAll {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            Key::Char('s') => {
+                // Search transactions in every account
+                state.current_budget_id.as_ref().map(|budget_id| {
+                    AppCommand::NavigateToTransactionSearch {
+                        budget_id: budget_id.clone(),
+                    }
+                })
+            }
+            Key::Char('i') => {
+                // Review what's unapproved or uncategorized in every account
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::NavigateToInbox {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            Key::Char('I') => {
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::PreviewYnabExport {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            Key::Char('f') => Some(AppCommand::EnterAccountJump),
+            Key::Char('t') => Some(AppCommand::CycleAccountTypeFilter),
+            Key::Char('F') => {
+                // Project the selected account's balance forward
+                let budget_id = state.current_budget_id.as_ref()?;
+                let selected = self.table_state.borrow().selected()?;
+                self.filtered_accounts().get(selected).map(|account| {
+                    AppCommand::LoadCashFlowProjection {
+                        budget_id: budget_id.clone(),
+                        account_id: account.id.to_string(),
+                        horizon: ProjectionHorizon::default(),
+                    }
+                })
+            }
+            Key::Char('c') => {
+                let selected = self.table_state.borrow().selected()?;
+                self.filtered_accounts().get(selected).map(|account| {
+                    AppCommand::CycleReconcileReminder {
+                        account_id: account.id.to_string(),
+                    }
+                })
+            }
+            Key::Char('*') => {
+                let selected = self.table_state.borrow().selected()?;
+                self.filtered_accounts().get(selected).map(|account| {
+                    AppCommand::ToggleFavoriteAccount {
+                        account_id: account.id.to_string(),
+                    }
+                })
+            }
+            Key::Char('r') => {
+                // Force refresh accounts
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::LoadAccounts {
+                        budget_id: budget_id.clone(),
+                        budget: Box::new(state.current_budget.clone()),
+                        force_refresh: true,
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_event(&mut self, event: ScreenEvent) {
+        match event {
+            ScreenEvent::LoadFailed(error) => self.accounts_loading.fail(error),
+            ScreenEvent::RefreshCompleted => self.accounts_loading.settle(),
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.accounts_loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.accounts_loading)
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        let mut items = vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("Enter/→/l", t("help-view-transactions")),
+            ("/", t("help-enter-filter")),
+        ];
+        if self.input_mode == InputMode::Filter {
+            items.push(("Type", t("help-filter-accounts")));
+            items.push(("Enter", t("help-filter-exit")));
+            items.push(("Esc", t("help-filter-clear")));
+            items.push(("Backspace", t("help-filter-backspace")));
+        }
+        if self.input_mode == InputMode::AccountJump {
+            items.push(("Type", t("help-jump-account")));
+            items.push(("Enter/Esc", t("help-jump-done")));
+        }
+        items.push((".", t("help-toggle-closed")));
+        items.push(("t", t("help-account-type-filter")));
+        items.push(("f", t("help-jump-account")));
+        items.push(("F", t("help-cash-flow")));
+        items.push(("c", t("help-reconcile-reminder")));
+        items.push(("*", t("help-toggle-favorite")));
+        items.push(("s", t("help-search-all")));
+        items.push(("i", t("help-inbox")));
+        items.push(("Space/o", t("help-context-menu")));
+        items.push(("r", t("help-refresh-accounts")));
+        items.push(("R", t("help-refresh-all")));
+        items.push(("I", t("help-import-export")));
+        items
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
     }
 }
@@ -3,9 +3,14 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem},
 };
 
-use crate::state::{BudgetsState, LoadingState};
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::t;
+use crate::input::{Key, KeyEvent};
+use crate::state::{AppState, BudgetsState, LoadingState};
 use crate::ui::{
-    components::{empty_state, help_bar, screen_title},
+    components::{empty_state, help_bar, screen_title, table_skeleton},
     layouts, theme,
 };
 
@@ -14,13 +19,19 @@ pub fn render(f: &mut Frame, state: &BudgetsState) {
 
     screen_title::render_screen_title(f, title_area, &state.budgets_loading);
     render_content(f, content_area, state);
-    help_bar::render_help_bar(f, help_area, help_bar::HELP_TEXT_DEFAULT);
+    help_bar::render_help_bar(f, help_area, help_bar::help_text_default());
 }
 
 fn render_content(f: &mut Frame, area: Rect, state: &BudgetsState) {
     // Show loading message if currently loading and no cached data
     if matches!(state.budgets_loading, LoadingState::Loading(..)) && state.budgets.is_empty() {
-        empty_state::render_loading_state(f, area, "Status", "Loading budgets...");
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "budgets",
+            t("budgets-loading"),
+            &[Constraint::Percentage(100)],
+        );
         return;
     }
 
@@ -41,7 +52,12 @@ fn render_content(f: &mut Frame, area: Rect, state: &BudgetsState) {
             })
             .collect();
 
-        let list = List::new(items).block(Block::default().borders(Borders::ALL).title("Budgets"));
+        table_skeleton::remember_rows("budgets", items.len());
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t("budgets-title")),
+        );
 
         f.render_widget(list, area);
     } else {
@@ -49,9 +65,93 @@ fn render_content(f: &mut Frame, area: Rect, state: &BudgetsState) {
         empty_state::render_empty_state(
             f,
             area,
-            "Budgets",
-            "No budgets found",
-            Some("Create a budget at https://app.ynab.com"),
+            t("budgets-title"),
+            t("budgets-empty"),
+            Some(t("budgets-empty-hint")),
         );
     }
 }
+
+impl ScreenController for BudgetsState {
+    fn render(&self, f: &mut Frame, _ctx: &RenderContext) {
+        render(f, self);
+    }
+
+    fn handle_key(&self, event: KeyEvent, _state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Enter | Key::Right | Key::Char('l') => {
+                // Load selected budget's accounts
+                if !self.budgets.is_empty() {
+                    let budget = self.budgets[self.selected_budget_index].clone();
+                    Some(AppCommand::LoadAccounts {
+                        budget_id: budget.id.to_string(),
+                        budget: Box::new(Some(budget)),
+                        force_refresh: false,
+                    })
+                } else {
+                    None
+                }
+            }
+            Key::Char('r') => Some(AppCommand::LoadBudgets {
+                force_refresh: true,
+                load_accounts: false,
+            }),
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.budgets_loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.budgets_loading)
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("Enter/→/l", t("help-select-budget")),
+            ("r", t("help-refresh-budgets")),
+        ]
+    }
+
+    fn select_next(&mut self) {
+        if !self.budgets.is_empty() {
+            self.selected_budget_index = (self.selected_budget_index + 1) % self.budgets.len();
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if !self.budgets.is_empty() {
+            if self.selected_budget_index == 0 {
+                self.selected_budget_index = self.budgets.len() - 1;
+            } else {
+                self.selected_budget_index -= 1;
+            }
+        }
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        if !self.budgets.is_empty() {
+            self.selected_budget_index = self
+                .selected_budget_index
+                .saturating_add_signed(rows)
+                .min(self.budgets.len() - 1);
+        }
+    }
+
+    fn select_first(&mut self) {
+        self.selected_budget_index = 0;
+    }
+
+    fn select_last(&mut self) {
+        if !self.budgets.is_empty() {
+            self.selected_budget_index = self.budgets.len() - 1;
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+use super::{RenderContext, ScreenController};
+use crate::api_log::{ApiLog, ApiRecord};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::t;
+use crate::input::{Key, KeyEvent};
+use crate::state::{AppState, InspectorState, LoadingState};
+use crate::ui::{
+    components::{empty_state, help_bar},
+    layouts, theme,
+};
+
+pub fn render(f: &mut Frame, state: &InspectorState, api_log: &ApiLog) {
+    let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
+
+    render_title(f, title_area, state);
+
+    let records = api_log.get_records();
+    let visible = state.visible_records(&records);
+    if visible.is_empty() {
+        let message = if state.focus_id.is_some() {
+            t("inspector-no-match")
+        } else {
+            t("inspector-empty")
+        };
+        empty_state::render_empty_state(f, content_area, t("inspector-title"), message, None);
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(content_area);
+
+        let selected = state.selected.min(visible.len() - 1);
+        render_records(f, chunks[0], &visible, selected);
+        render_payload(f, chunks[1], state, visible[selected]);
+    }
+
+    help_bar::render_help_bar(
+        f,
+        help_area,
+        "j/k: select response | PgUp/PgDn: scroll payload | h: back | ?: help",
+    );
+}
+
+fn render_title(f: &mut Frame, area: Rect, state: &InspectorState) {
+    let title = match &state.focus_id {
+        Some(id) => t("inspector-title-focused").replace("{id}", id),
+        None => t("inspector-title").to_string(),
+    };
+    let paragraph = Paragraph::new(title).style(theme::title_style());
+    f.render_widget(paragraph, area);
+}
+
+fn render_records(f: &mut Frame, area: Rect, records: &[&ApiRecord], selected: usize) {
+    let items: Vec<ListItem> = records
+        .iter()
+        .enumerate()
+        .map(|(i, record)| {
+            let line = format!(
+                "{} {}",
+                record.timestamp.format("%H:%M:%S"),
+                record.endpoint
+            );
+            let style = if i == selected {
+                theme::selection_style()
+            } else {
+                Style::default()
+            };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(format!(
+        " {} ({}) ",
+        t("inspector-records"),
+        records.len()
+    )));
+    f.render_widget(list, area);
+}
+
+fn render_payload(f: &mut Frame, area: Rect, state: &InspectorState, record: &ApiRecord) {
+    let pretty = |value| serde_json::to_string_pretty(value).unwrap_or_default();
+    // When focused on an item, show just that object rather than the whole exchange
+    let text = match state
+        .focus_id
+        .as_deref()
+        .and_then(|id| record.find_object(id))
+    {
+        Some(object) => pretty(object),
+        None => {
+            let response = format!(
+                "{} {}\n{}",
+                t("inspector-response"),
+                record.status,
+                pretty(&record.payload)
+            );
+            match &record.request {
+                Some(request) => {
+                    format!(
+                        "{}\n{}\n\n{}",
+                        t("inspector-request"),
+                        pretty(request),
+                        response
+                    )
+                }
+                None => response,
+            }
+        }
+    };
+
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " {}: {} ",
+            t("inspector-payload"),
+            record.endpoint
+        )))
+        .scroll((state.detail_scroll.min(u16::MAX as usize) as u16, 0));
+    f.render_widget(paragraph, area);
+}
+
+impl ScreenController for InspectorState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.api_log);
+    }
+
+    fn handle_key(&self, event: KeyEvent, _state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::PageUp => Some(AppCommand::ScrollInspectorDetail { down: false }),
+            Key::PageDown => Some(AppCommand::ScrollInspectorDetail { down: true }),
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        None
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        None
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-inspector-records")),
+            ("↓/j", t("help-inspector-records")),
+            ("PgUp/PgDn", t("help-inspector-detail")),
+            ("g then g", t("help-inspector-newest")),
+            ("G", t("help-inspector-oldest")),
+        ]
+    }
+
+    fn select_next(&mut self) {
+        InspectorState::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        self.select_prev();
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(rows)
+            .min(self.total_records.saturating_sub(1));
+        self.detail_scroll = 0;
+    }
+
+    fn select_first(&mut self) {
+        self.selected = 0;
+        self.detail_scroll = 0;
+    }
+
+    fn select_last(&mut self) {
+        self.selected = self.total_records.saturating_sub(1);
+        self.detail_scroll = 0;
+    }
+}
@@ -4,8 +4,13 @@ use ratatui::{
 };
 use tracing::Level;
 
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::{t, tn};
+use crate::input::{Key, KeyEvent};
 use crate::log_buffer::LogBuffer;
-use crate::state::LogsState;
+use crate::state::{AppState, LoadingState, LogsState};
 use crate::ui::{
     components::{empty_state, help_bar},
     layouts, theme,
@@ -20,7 +25,7 @@ pub fn render(f: &mut Frame, state: &LogsState, log_buffer: &LogBuffer) {
 }
 
 fn render_title(f: &mut Frame, area: Rect, state: &LogsState) {
-    let title = format!("Logs ({} entries)", state.total_entries);
+    let title = tn("logs-title", state.total_entries);
     let paragraph = ratatui::widgets::Paragraph::new(title).style(theme::title_style());
     f.render_widget(paragraph, area);
 }
@@ -30,7 +35,7 @@ fn render_logs(f: &mut Frame, area: Rect, state: &LogsState, log_buffer: &LogBuf
     let total = entries.len();
 
     if total == 0 {
-        empty_state::render_empty_state(f, area, "Session Logs", "No logs yet", None);
+        empty_state::render_empty_state(f, area, t("logs-empty-title"), t("logs-empty"), None);
         return;
     }
 
@@ -115,3 +120,50 @@ fn truncate_target(target: &str, max_len: usize) -> String {
         format!("...{}", &target[target.len() - max_len + 3..])
     }
 }
+
+impl ScreenController for LogsState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.log_buffer);
+    }
+
+    fn handle_key(&self, event: KeyEvent, _state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            Key::Up | Key::Char('k') => Some(AppCommand::ScrollLogsUp),
+            Key::Down | Key::Char('j') => Some(AppCommand::ScrollLogsDown),
+            Key::PageUp => Some(AppCommand::ScrollLogsPageUp),
+            Key::PageDown => Some(AppCommand::ScrollLogsPageDown),
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        None
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        None
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-logs-up")),
+            ("↓/j", t("help-logs-down")),
+            ("Page Up", t("help-logs-page-up")),
+            ("Page Down", t("help-logs-page-down")),
+            ("g then g", t("help-logs-top")),
+            ("G", t("help-logs-bottom")),
+        ]
+    }
+
+    // j/k scroll through ScrollLogs* commands instead of moving a selection
+
+    fn select_first(&mut self) {
+        // Oldest logs are at the top
+        self.scroll_offset = self.total_entries.saturating_sub(1);
+    }
+
+    fn select_last(&mut self) {
+        self.scroll_offset = 0;
+    }
+}
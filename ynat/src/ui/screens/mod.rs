@@ -1,16 +1,140 @@
 pub mod accounts_screen;
 pub mod budgets_screen;
+pub mod inspector_screen;
 pub mod logs_screen;
+pub mod net_worth_screen;
+pub mod payee_cleanup_screen;
 pub mod plan_screen;
+pub mod reimbursements_screen;
+pub mod reports_screen;
+pub mod scheduled_transactions_screen;
+pub mod settings_screen;
 pub mod transactions_screen;
 
-use crate::state::{AccountsState, BudgetsState, LogsState, PlanState, TransactionsState};
+use ratatui::Frame;
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+use crate::api_log::ApiLog;
+use crate::budget_settings::BudgetSettings;
+use crate::config::AppConfig;
+use crate::error::AppError;
+use crate::events::AppCommand;
+use crate::input::KeyEvent;
+use crate::log_buffer::LogBuffer;
+use crate::state::{
+    AccountsState, AppState, BudgetsState, InspectorState, LoadingState, LogsState, NetWorthState,
+    PayeeCleanupState, PlanState, ReimbursementsState, ReportsState, ScheduledTransactionsState,
+    SettingsState, TransactionsState,
+};
 
 #[derive(Debug, Clone)]
 pub enum Screen {
     Budgets(BudgetsState),
     Accounts(AccountsState),
     Transactions(Box<TransactionsState>),
-    Plan(PlanState),
+    Plan(Box<PlanState>),
+    Reimbursements(ReimbursementsState),
+    NetWorth(NetWorthState),
+    ScheduledTransactions(ScheduledTransactionsState),
+    PayeeCleanup(PayeeCleanupState),
+    Reports(ReportsState),
+    Settings(SettingsState),
     Logs(LogsState),
+    Inspector(InspectorState),
+}
+
+impl Screen {
+    /// The screen's state as a controller.
+    ///
+    /// This is the only place a new screen has to be listed; rendering, key
+    /// handling, loading and selection all go through [`ScreenController`].
+    pub fn controller(&self) -> &dyn ScreenController {
+        match self {
+            Screen::Budgets(state) => state,
+            Screen::Accounts(state) => state,
+            Screen::Transactions(state) => state.as_ref(),
+            Screen::Plan(state) => state.as_ref(),
+            Screen::Reimbursements(state) => state,
+            Screen::NetWorth(state) => state,
+            Screen::ScheduledTransactions(state) => state,
+            Screen::PayeeCleanup(state) => state,
+            Screen::Reports(state) => state,
+            Screen::Settings(state) => state,
+            Screen::Logs(state) => state,
+            Screen::Inspector(state) => state,
+        }
+    }
+
+    pub fn controller_mut(&mut self) -> &mut dyn ScreenController {
+        match self {
+            Screen::Budgets(state) => state,
+            Screen::Accounts(state) => state,
+            Screen::Transactions(state) => state.as_mut(),
+            Screen::Plan(state) => state.as_mut(),
+            Screen::Reimbursements(state) => state,
+            Screen::NetWorth(state) => state,
+            Screen::ScheduledTransactions(state) => state,
+            Screen::PayeeCleanup(state) => state,
+            Screen::Reports(state) => state,
+            Screen::Settings(state) => state,
+            Screen::Logs(state) => state,
+            Screen::Inspector(state) => state,
+        }
+    }
+}
+
+/// Everything outside a screen's own state that it may draw from
+pub struct RenderContext<'a> {
+    pub budget: Option<&'a BudgetSummary>,
+    pub config: &'a AppConfig,
+    pub budget_settings: &'a BudgetSettings,
+    pub log_buffer: &'a LogBuffer,
+    pub api_log: &'a ApiLog,
+}
+
+/// App-wide events delivered to whichever screen is current
+#[derive(Debug)]
+pub enum ScreenEvent {
+    /// A request the screen may be waiting on failed
+    LoadFailed(AppError),
+    /// A "refresh everything" batch finished
+    RefreshCompleted,
+}
+
+/// Per-screen behaviour, implemented by each screen's state
+pub trait ScreenController {
+    /// Draw the screen, including any popups it owns
+    fn render(&self, f: &mut Frame, ctx: &RenderContext);
+
+    /// Map a key to a command. Called after the global keys (`?`, `q`, `g`,
+    /// `G`); unhandled `h`/`←` falls back to navigating back.
+    fn handle_key(&self, event: KeyEvent, app: &AppState) -> Option<AppCommand>;
+
+    /// React to an app-wide event. By default a failure is recorded on
+    /// [`Self::loading_state_mut`] if a load is in progress.
+    fn handle_event(&mut self, event: ScreenEvent) {
+        if let (ScreenEvent::LoadFailed(error), Some(loading)) = (event, self.loading_state_mut()) {
+            loading.fail(error);
+        }
+    }
+
+    /// The load whose progress the title bar shows; `None` for screens that
+    /// don't load anything
+    fn loading_state(&self) -> Option<&LoadingState>;
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState>;
+
+    /// Screen-specific entries for the help popup
+    fn help_items(&self, config: &AppConfig) -> Vec<(&'static str, &'static str)>;
+
+    fn select_next(&mut self) {}
+
+    fn select_previous(&mut self) {}
+
+    /// Move `rows` down, or up when negative, without wrapping around
+    fn select_by(&mut self, _rows: isize) {}
+
+    fn select_first(&mut self) {}
+
+    fn select_last(&mut self) {}
 }
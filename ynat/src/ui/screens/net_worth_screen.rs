@@ -0,0 +1,180 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::t;
+use crate::input::{Key, KeyEvent};
+use crate::reports::net_worth::{self, NetWorth};
+use crate::state::{AppState, LoadingState, NetWorthState, Scrollable};
+use crate::ui::{
+    components::{empty_state, help_bar, screen_title, table_skeleton},
+    layouts, theme, utils,
+};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+pub fn render(f: &mut Frame, state: &NetWorthState, budget: Option<&BudgetSummary>) {
+    let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
+
+    screen_title::render_screen_title(f, title_area, &state.loading);
+    render_content(f, content_area, state, budget);
+    help_bar::render_help_bar(f, help_area, "r: refresh from YNAB | h: back | ?: help");
+}
+
+const COLUMN_WIDTHS: [Constraint; 3] = [
+    Constraint::Percentage(50),
+    Constraint::Percentage(20),
+    Constraint::Percentage(30),
+];
+
+fn render_content(
+    f: &mut Frame,
+    area: Rect,
+    state: &NetWorthState,
+    budget: Option<&BudgetSummary>,
+) {
+    let Some(ref net_worth) = state.net_worth else {
+        if matches!(state.loading, LoadingState::Loading(..)) {
+            table_skeleton::render_table_skeleton(
+                f,
+                area,
+                "net-worth",
+                t("net-worth-loading"),
+                &COLUMN_WIDTHS,
+            );
+        }
+        return;
+    };
+    if net_worth.by_type.is_empty() {
+        empty_state::render_empty_state(f, area, t("net-worth-title"), t("net-worth-empty"), None);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(theme::SUMMARY_CARD_HEIGHT),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    render_summary(f, chunks[0], net_worth, budget);
+
+    let header = Row::new(vec![
+        Cell::from("Account Type"),
+        Cell::from(Text::from("Accounts").right_aligned()),
+        Cell::from(Text::from("Balance").right_aligned()),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let rows: Vec<Row> = net_worth
+        .by_type
+        .iter()
+        .map(|total| {
+            Row::new(vec![
+                Cell::from(net_worth::account_type_label(total.account_type)),
+                Cell::from(Text::from(total.accounts.to_string()).right_aligned()),
+                Cell::from(Text::from(utils::format_amount(total.balance, budget)).right_aligned())
+                    .style(Style::default().fg(utils::get_amount_color(total.balance))),
+            ])
+        })
+        .collect();
+
+    table_skeleton::remember_rows("net-worth", rows.len());
+    let table = Table::new(rows, COLUMN_WIDTHS)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t("net-worth-by-type")),
+        )
+        .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, chunks[1], &mut state.table_state.borrow_mut());
+}
+
+/// Assets, liabilities and net worth side by side
+fn render_summary(f: &mut Frame, area: Rect, net_worth: &NetWorth, budget: Option<&BudgetSummary>) {
+    let cards = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3); 3])
+        .split(area);
+    let totals = [
+        (t("net-worth-assets"), net_worth.assets),
+        (t("net-worth-liabilities"), net_worth.liabilities),
+        (t("net-worth-net"), net_worth.net()),
+    ];
+    for ((label, amount), card) in totals.into_iter().zip(cards.iter()) {
+        let paragraph = Paragraph::new(Span::styled(
+            utils::format_amount(amount, budget),
+            Style::default()
+                .fg(utils::get_amount_color(amount))
+                .add_modifier(Modifier::BOLD),
+        ))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title(label));
+        f.render_widget(paragraph, *card);
+    }
+}
+
+impl ScreenController for NetWorthState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.budget);
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        match event.key {
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Char('r') => {
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::LoadNetWorth {
+                        budget_id: budget_id.clone(),
+                        force_refresh: true,
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.loading)
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("r", t("help-refresh-net-worth")),
+        ]
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
+    }
+}
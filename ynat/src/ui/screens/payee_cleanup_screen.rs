@@ -0,0 +1,185 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Row, Table},
+};
+
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::{t, tn};
+use crate::input::{Key, KeyEvent};
+use crate::state::{AppState, LoadingState, PayeeCleanupState, Scrollable};
+use crate::ui::{
+    components::{empty_state, help_bar, payee_merge_confirmation, screen_title, table_skeleton},
+    layouts, theme,
+};
+
+pub fn render(f: &mut Frame, state: &PayeeCleanupState) {
+    let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
+
+    screen_title::render_screen_title(f, title_area, &state.loading);
+    render_content(f, content_area, state);
+    help_bar::render_help_bar(
+        f,
+        help_area,
+        "Space: toggle | Enter: rename selected | m: merge selected | h: back | ?: help",
+    );
+
+    if state.confirm_merge {
+        let merges = state.included_merges();
+        let duplicates = merges.iter().map(|m| m.duplicates.len()).sum();
+        payee_merge_confirmation::render_payee_merge_confirmation(f, merges.len(), duplicates);
+    }
+}
+
+const COLUMN_WIDTHS: [Constraint; 3] = [
+    Constraint::Length(4),
+    Constraint::Percentage(30),
+    Constraint::Percentage(65),
+];
+
+fn render_content(f: &mut Frame, area: Rect, state: &PayeeCleanupState) {
+    if matches!(state.loading, LoadingState::Loading(..)) && state.clusters.is_empty() {
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "payee_cleanup",
+            t("payee-cleanup-loading"),
+            &COLUMN_WIDTHS,
+        );
+        return;
+    }
+    if state.clusters.is_empty() {
+        empty_state::render_empty_state(
+            f,
+            area,
+            t("payee-cleanup-title"),
+            t("payee-cleanup-empty"),
+            None,
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from("Rename to"),
+        Cell::from("Payees"),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let rows: Vec<Row> = state
+        .clusters
+        .iter()
+        .zip(&state.included)
+        .map(|(cluster, included)| {
+            let names = cluster
+                .renames()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Row::new(vec![
+                Cell::from(if *included { "[x]" } else { "[ ]" }),
+                Cell::from(cluster.canonical_name.clone())
+                    .style(Style::default().fg(theme::COLOR_POSITIVE)),
+                Cell::from(names),
+            ])
+        })
+        .collect();
+
+    let renames = state.included_renames().len();
+    let title = format!(
+        "{} - {}",
+        t("payee-cleanup-title"),
+        tn("payee-cleanup-summary", renames)
+    );
+
+    table_skeleton::remember_rows("payee_cleanup", rows.len());
+    let table = Table::new(rows, COLUMN_WIDTHS)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, area, &mut state.table_state.borrow_mut());
+}
+
+impl ScreenController for PayeeCleanupState {
+    fn render(&self, f: &mut Frame, _ctx: &RenderContext) {
+        render(f, self);
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Char(' ') => Some(AppCommand::TogglePayeeCluster),
+            Key::Enter => {
+                if matches!(self.loading, LoadingState::Loading(..)) {
+                    return None;
+                }
+                let budget_id = state.current_budget_id.as_ref()?;
+                let renames = self.included_renames();
+                (!renames.is_empty()).then(|| AppCommand::ApplyPayeeRenames {
+                    budget_id: budget_id.clone(),
+                    renames,
+                })
+            }
+
+            Key::Char('m') => {
+                let idle = !matches!(self.loading, LoadingState::Loading(..));
+                (idle && self.included.contains(&true))
+                    .then_some(AppCommand::OpenPayeeMergeConfirmation)
+            }
+
+            Key::Char('r') => {
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::LoadPayeeClusters {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.loading)
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("Space", t("help-toggle-payee-group")),
+            ("Enter", t("help-rename-payees")),
+            ("m", t("help-merge-payees")),
+            ("r", t("help-refresh-payee-groups")),
+        ]
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
+    }
+}
@@ -1,15 +1,42 @@
-use crate::state::{InputMode, LoadingState, PlanFocusedView, PlanState};
+use super::{RenderContext, ScreenController, ScreenEvent};
+use crate::budget_settings::BudgetSettings;
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::{t, tn};
+use crate::input::{Key, KeyEvent};
+use crate::state::{
+    AppState, InputMode, LoadingState, PlanComparison, PlanFocusedView, PlanGroup, PlanRow,
+    PlanState, Scrollable, FAVORITES_GROUP,
+};
 use crate::ui::{
-    components::{empty_state, help_bar, loading_indicator},
+    components::{
+        archive_review, empty_state, error_panel, goal_calculator, goal_form, health_checklist,
+        help_bar, loading_indicator, move_money, quick_budget, table_skeleton,
+    },
     layouts, theme, utils,
 };
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Bar, BarChart, Block, Borders, Gauge, Paragraph, Row, Table},
+};
+use unicode_width::UnicodeWidthStr;
+use ynab_api::endpoints::{
+    budgets::BudgetSummary, categories::Category, months::MonthDetail, Milliunits,
 };
-use ynab_api::endpoints::{budgets::BudgetSummary, months::MonthDetail};
 
-pub fn render(f: &mut Frame, state: &PlanState, budget: Option<&BudgetSummary>) {
+/// Lines under the table for the selected category's note and goal
+const CATEGORY_DETAILS_HEIGHT: u16 = 2;
+
+/// Width of the goal pane next to the table
+const GOAL_DETAILS_WIDTH: u16 = 36;
+
+pub fn render(
+    f: &mut Frame,
+    state: &PlanState,
+    budget: Option<&BudgetSummary>,
+    config: &AppConfig,
+    settings: &BudgetSettings,
+) {
     let area = f.area();
 
     // Use sidebar layout
@@ -18,11 +45,11 @@ pub fn render(f: &mut Frame, state: &PlanState, budget: Option<&BudgetSummary>)
 
     render_header(f, header_area, state);
     render_sidebar(f, sidebar_area, state);
-    render_main_content(f, main_area, state, budget);
+    render_main_content(f, main_area, state, budget, config, settings);
     help_bar::render_help_bar(
         f,
         help_area,
-        "j/k: navigate  e: edit  ,: view  Tab: month  ?: help",
+        "j/k: navigate  e: edit  w: what-if  n: notes  ,: view  Tab: month  ?: help",
     );
 }
 
@@ -103,15 +130,35 @@ fn render_sidebar(f: &mut Frame, area: Rect, state: &PlanState) {
     f.render_widget(paragraph, inner);
 }
 
+const CATEGORY_WIDTHS: [Constraint; 4] = [
+    Constraint::Percentage(40),
+    Constraint::Percentage(20),
+    Constraint::Percentage(20),
+    Constraint::Percentage(20),
+];
+
+/// Category, then budgeted, activity and available with the change since the
+/// compared month
+const COMPARISON_WIDTHS: [Constraint; 6] = [
+    Constraint::Percentage(28),
+    Constraint::Percentage(15),
+    Constraint::Percentage(14),
+    Constraint::Percentage(15),
+    Constraint::Percentage(14),
+    Constraint::Percentage(14),
+];
+
 fn render_main_content(
     f: &mut Frame,
     area: Rect,
     state: &PlanState,
     budget: Option<&BudgetSummary>,
+    config: &AppConfig,
+    settings: &BudgetSettings,
 ) {
     // Show loading message if currently loading and no cached data
     if matches!(state.plan_loading, LoadingState::Loading(..)) && state.categories.is_empty() {
-        empty_state::render_loading_state(f, area, "Status", "Loading plan...");
+        table_skeleton::render_table_skeleton(f, area, "plan", "Loading plan...", &CATEGORY_WIDTHS);
         return;
     }
 
@@ -120,13 +167,37 @@ fn render_main_content(
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(theme::SUMMARY_CARD_HEIGHT),
+                Constraint::Length(1),
+                Constraint::Length(theme::MONTH_SUMMARY_HEIGHT),
                 Constraint::Min(0),
             ])
             .split(area);
 
-        render_summary_cards(f, chunks[0], month, budget);
-        render_categories_table(f, chunks[1], state);
+        render_month_metrics(f, chunks[0], month, budget);
+        render_month_summary(f, chunks[1], state, month, budget);
+        let table_area = if state.goal_details {
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(GOAL_DETAILS_WIDTH)])
+                .split(chunks[2]);
+            render_goal_details(f, panes[1], state.selected_category(), budget);
+            panes[0]
+        } else {
+            chunks[2]
+        };
+        if settings.plan_category_details {
+            let table_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(CATEGORY_DETAILS_HEIGHT),
+                ])
+                .split(table_area);
+            render_categories_table(f, table_chunks[0], state, budget, config);
+            render_category_details(f, table_chunks[1], state.selected_category(), budget);
+        } else {
+            render_categories_table(f, table_area, state, budget, config);
+        }
     } else {
         // No data loaded yet
         empty_state::render_empty_state(
@@ -139,83 +210,149 @@ fn render_main_content(
     }
 }
 
-fn render_summary_cards(
+/// Age of Money and the month's income, budgeted and activity
+fn month_metrics(
+    month: &MonthDetail,
+    budget: Option<&BudgetSummary>,
+) -> [(&'static str, String); 4] {
+    let age_of_money = match month.age_of_money {
+        Some(days) => tn("plan-age-of-money-days", days.max(0) as usize),
+        None => t("plan-age-of-money-unknown").to_string(),
+    };
+    let amount = |amount: Milliunits| {
+        utils::format_amount(amount.into(), budget)
+            .trim()
+            .to_string()
+    };
+    [
+        (t("plan-age-of-money"), age_of_money),
+        (t("plan-income"), amount(month.income)),
+        (t("plan-budgeted"), amount(month.budgeted)),
+        (t("plan-activity"), amount(month.activity)),
+    ]
+}
+
+fn render_month_metrics(
+    f: &mut Frame,
+    area: Rect,
+    month: &MonthDetail,
+    budget: Option<&BudgetSummary>,
+) {
+    let mut spans = Vec::new();
+    for (index, (label, value)) in month_metrics(month, budget).into_iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled("  │  ", theme::help_text_style()));
+        }
+        spans.push(Span::styled(format!("{label} "), theme::help_text_style()));
+        spans.push(Span::styled(
+            value,
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// The month at a glance: budgeted, spent and available as bars, and how
+/// much of the month's money has been given a job as a gauge
+fn render_month_summary(
     f: &mut Frame,
     area: Rect,
+    state: &PlanState,
     month: &MonthDetail,
     budget: Option<&BudgetSummary>,
 ) {
-    // Split into 3 equal cards with gaps
-    let card_chunks = Layout::default()
+    let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-            Constraint::Percentage(33),
-        ])
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
         .split(area);
 
-    // Card 1: To Be Budgeted
-    let tbb_str = utils::format_amount(month.to_be_budgeted.into(), budget);
-    render_card(
-        f,
-        card_chunks[0],
-        &tbb_str,
-        "To Budget",
-        utils::get_amount_color(month.to_be_budgeted.into()),
-    );
+    let budgeted: i64 = month.budgeted.into();
+    let activity: i64 = month.activity.into();
+    let available: i64 = state
+        .categories
+        .iter()
+        .filter(|c| !c.hidden && !c.deleted)
+        .map(|c| i64::from(c.balance))
+        .sum();
 
-    // Card 2: Income
-    let income_str = utils::format_amount(month.income.into(), budget);
-    render_card(
-        f,
-        card_chunks[1],
-        &income_str,
-        "Income",
-        utils::get_amount_color(month.income.into()),
+    let bar = |label: &'static str, amount: i64, color: Color| {
+        Bar::with_label(label, amount.unsigned_abs())
+            .text_value(utils::format_amount(amount, budget).trim().to_string())
+            .style(Style::default().fg(color))
+            .value_style(Style::default().fg(Color::Black).bg(color))
+    };
+    let chart = BarChart::horizontal(vec![
+        bar(t("plan-budgeted"), budgeted, theme::COLOR_HEADER),
+        bar(
+            t("plan-activity"),
+            activity,
+            utils::get_amount_color(activity),
+        ),
+        bar(
+            t("plan-available"),
+            available,
+            utils::get_amount_color(available),
+        ),
+    ])
+    .bar_width(1)
+    .bar_gap(0)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(t("plan-month")),
     );
+    f.render_widget(chart, chunks[0]);
 
-    // Card 3: Budgeted
-    let budgeted_str = utils::format_amount(month.budgeted.into(), budget);
-    render_card(
-        f,
-        card_chunks[2],
-        &budgeted_str,
-        "Budgeted",
-        utils::get_amount_color(month.budgeted.into()),
-    );
+    let to_be_budgeted: i64 = month.to_be_budgeted.into();
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t("plan-to-budget")),
+        )
+        .ratio(assigned_ratio(budgeted, to_be_budgeted))
+        .label(
+            utils::format_amount(to_be_budgeted, budget)
+                .trim()
+                .to_string(),
+        )
+        .gauge_style(Style::default().fg(utils::get_amount_color(to_be_budgeted)));
+    f.render_widget(gauge, chunks[1]);
 }
 
-fn render_card(f: &mut Frame, area: Rect, amount: &str, label: &str, color: Color) {
-    let block = Block::default().borders(Borders::ALL).title(label);
-    let inner = block.inner(area);
-    f.render_widget(block, area);
-
-    let paragraph = Paragraph::new(Span::styled(
-        amount,
-        Style::default().fg(color).add_modifier(Modifier::BOLD),
-    ))
-    .alignment(Alignment::Center);
-    f.render_widget(paragraph, inner);
+/// Share of the month's money that has been budgeted; full once nothing is
+/// left to budget, including when too much was budgeted
+fn assigned_ratio(budgeted: i64, to_be_budgeted: i64) -> f64 {
+    if to_be_budgeted <= 0 {
+        return 1.0;
+    }
+    let budgeted = budgeted.max(0) as f64;
+    budgeted / (budgeted + to_be_budgeted as f64)
 }
 
-fn render_categories_table(f: &mut Frame, area: Rect, state: &PlanState) {
-    // Use filtered categories based on focused view
-    let visible_categories = state.filtered_categories();
+fn render_categories_table(
+    f: &mut Frame,
+    area: Rect,
+    state: &PlanState,
+    budget: Option<&BudgetSummary>,
+    config: &AppConfig,
+) {
+    // Group headers and the categories of the focused view
+    let plan_rows = state.rows();
 
     // Generate title based on focused view (used for both empty state and table)
     let title = match state.focused_view {
-        PlanFocusedView::All => "Categories".to_string(),
-        _ => format!("Categories - {}", state.focused_view.display_name()),
+        PlanFocusedView::All => t("plan-categories").to_string(),
+        _ => t("plan-categories-view").replace("{view}", state.focused_view.display_name()),
     };
 
-    if visible_categories.is_empty() {
+    if plan_rows.is_empty() {
         let message = match state.focused_view {
-            PlanFocusedView::All => "No categories to display",
-            PlanFocusedView::Snoozed => "No snoozed categories",
-            PlanFocusedView::Underfunded => "No underfunded categories",
-            PlanFocusedView::Overfunded => "No overfunded categories",
-            PlanFocusedView::MoneyAvailable => "No categories with money available",
+            PlanFocusedView::All => t("plan-empty"),
+            PlanFocusedView::Snoozed => t("plan-empty-snoozed"),
+            PlanFocusedView::Underfunded => t("plan-empty-underfunded"),
+            PlanFocusedView::Overfunded => t("plan-empty-overfunded"),
+            PlanFocusedView::MoneyAvailable => t("plan-empty-money-available"),
         };
         empty_state::render_empty_state(f, area, &title, message, None);
         return;
@@ -228,14 +365,38 @@ fn render_categories_table(f: &mut Frame, area: Rect, state: &PlanState) {
         None
     };
 
+    let comparison = state.comparison.as_ref();
+    let (widths, name_percent) = match comparison {
+        Some(_) => (&COMPARISON_WIDTHS[..], 28),
+        None => (&CATEGORY_WIDTHS[..], 40),
+    };
+    let name_width = area.width.saturating_sub(2) as usize * name_percent / 100;
+    let listed = state.filtered_categories();
+
     // Create table rows
-    let rows: Vec<Row> = visible_categories
+    let rows: Vec<Row> = plan_rows
         .iter()
-        .map(|category| {
-            // Convert milliunits to dollars
-            let budgeted = category.budgeted.as_f64() / 1000.0;
-            let activity = category.activity.as_f64() / 1000.0;
-            let balance = category.balance.as_f64() / 1000.0;
+        .map(|row| {
+            let category = match row {
+                PlanRow::Group(group) => {
+                    let cells = build_group_cells(group, name_width, budget, config);
+                    let cells = with_deltas(cells, comparison, budget, |comparison| {
+                        comparison.group_deltas(listed.iter().copied().filter(|c| {
+                            if group.id == FAVORITES_GROUP {
+                                state.is_favorite(&c.id.to_string())
+                            } else {
+                                c.category_group_id.to_string() == group.id
+                            }
+                        }))
+                    });
+                    return Row::new(cells).style(Style::default().add_modifier(Modifier::BOLD));
+                }
+                PlanRow::Category(category) => category,
+            };
+
+            let budgeted: i64 = category.budgeted.into();
+            let activity: i64 = category.activity.into();
+            let balance: i64 = category.balance.into();
 
             // Check if this category is being edited
             let budgeted_cell =
@@ -251,49 +412,89 @@ fn render_categories_table(f: &mut Frame, area: Rect, state: &PlanState) {
                             .style(theme::form_field_focused_style().fg(Color::White))
                             .right_aligned()
                     } else {
-                        Text::from(utils::fmt_dollars(budgeted))
-                            .style(Style::default().fg(utils::get_amount_color_f64(budgeted)))
+                        Text::from(utils::format_amount(budgeted, budget))
+                            .style(Style::default().fg(utils::get_amount_color(budgeted)))
                             .right_aligned()
                     }
                 } else {
-                    Text::from(utils::fmt_dollars(budgeted))
-                        .style(Style::default().fg(utils::get_amount_color_f64(budgeted)))
+                    Text::from(utils::format_amount(budgeted, budget))
+                        .style(Style::default().fg(utils::get_amount_color(budgeted)))
                         .right_aligned()
                 };
 
-            Row::new(vec![
-                Text::from(category.name.clone()),
+            let mut name = vec![Span::raw("  ")];
+            if state.is_favorite(&category.id.to_string()) {
+                name.push(Span::styled(
+                    format!("{} ", theme::FAVORITE_GLYPH),
+                    Style::default().fg(theme::COLOR_FAVORITE),
+                ));
+            }
+            let indent = name.iter().map(|span| span.width()).sum::<usize>();
+            name.push(Span::raw(utils::truncate_to_width(
+                config.display.category_name(&category.name),
+                name_width.saturating_sub(indent),
+            )));
+
+            let cells = vec![
+                Text::from(Line::from(name)),
                 budgeted_cell,
-                Text::from(utils::fmt_dollars(activity))
-                    .style(Style::default().fg(utils::get_amount_color_f64(activity)))
+                Text::from(utils::format_amount(activity, budget))
+                    .style(Style::default().fg(utils::get_amount_color(activity)))
                     .right_aligned(),
-                Text::from(utils::fmt_dollars(balance))
-                    .style(Style::default().fg(utils::get_amount_color_f64(balance)))
+                Text::from(utils::format_amount(balance, budget))
+                    .style(Style::default().fg(theme::threshold_color(
+                        category.balance.into(),
+                        &config.colors.available,
+                    )))
                     .right_aligned(),
-            ])
+            ];
+            Row::new(with_deltas(cells, comparison, budget, |comparison| {
+                comparison.deltas(category)
+            }))
         })
         .collect();
 
     // Create header
-    let header = Row::new(vec![
-        Text::from("Category"),
-        Text::from("Budgeted").right_aligned(),
-        Text::from("Activity").right_aligned(),
-        Text::from("Available").right_aligned(),
-    ])
-    .style(theme::header_style())
-    .underlined();
+    let header = match comparison {
+        Some(_) => vec![
+            Text::from("Category"),
+            Text::from("Budgeted").right_aligned(),
+            Text::from("Δ Budgeted").right_aligned(),
+            Text::from("Activity").right_aligned(),
+            Text::from("Δ Activity").right_aligned(),
+            Text::from("Δ Available").right_aligned(),
+        ],
+        None => vec![
+            Text::from("Category"),
+            Text::from("Budgeted").right_aligned(),
+            Text::from("Activity").right_aligned(),
+            Text::from("Available").right_aligned(),
+        ],
+    };
+    let header = Row::new(header).style(theme::header_style()).underlined();
+
+    let title = match comparison {
+        Some(comparison) => match &comparison.loading {
+            LoadingState::Error(error) => format!(
+                "{} vs {} [{}]",
+                title,
+                format_month_display(&comparison.month),
+                error_panel::error_line(error)
+            ),
+            _ => format!("{} vs {}", title, format_month_display(&comparison.month)),
+        },
+        None => title,
+    };
 
     // Override title if in edit mode
     let title = if state.input_mode == InputMode::BudgetEdit {
         if let Some(ref form) = state.budget_form {
             if let Some(ref error) = form.validation_error {
-                format!("Categories - {} [{}]", error, form.category_name)
+                t("plan-categories-invalid")
+                    .replace("{error}", &error_panel::error_line(error))
+                    .replace("{category}", &form.category_name)
             } else {
-                format!(
-                    "Categories - Editing: {} (Enter=save, Esc=cancel)",
-                    form.category_name
-                )
+                t("plan-categories-editing").replace("{category}", &form.category_name)
             }
         } else {
             title
@@ -303,17 +504,10 @@ fn render_categories_table(f: &mut Frame, area: Rect, state: &PlanState) {
     };
 
     // Create table
-    let mut table = Table::new(
-        rows,
-        [
-            Constraint::Percentage(40),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-        ],
-    )
-    .header(header)
-    .block(Block::default().borders(Borders::ALL).title(title));
+    table_skeleton::remember_rows("plan", rows.len());
+    let mut table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title));
 
     // Only highlight rows when not in edit mode
     if state.input_mode != InputMode::BudgetEdit {
@@ -323,6 +517,246 @@ fn render_categories_table(f: &mut Frame, area: Rect, state: &PlanState) {
     f.render_stateful_widget(table, area, &mut state.table_state.borrow_mut());
 }
 
+/// The selected category's note and goal, one line each
+fn render_category_details(
+    f: &mut Frame,
+    area: Rect,
+    category: Option<&Category>,
+    budget: Option<&BudgetSummary>,
+) {
+    let dim = Style::default().fg(theme::COLOR_HELP_TEXT);
+    let width = area.width as usize;
+    let line = |label: &str, text: Option<String>| match text {
+        Some(text) => Line::from(vec![
+            Span::styled(format!(" {}: ", label), dim),
+            Span::from(utils::truncate_to_width(
+                &text,
+                width.saturating_sub(label.len() + 3),
+            )),
+        ]),
+        None => Line::from(Span::styled(format!(" No {}", label.to_lowercase()), dim)),
+    };
+
+    let lines = match category {
+        Some(category) => vec![
+            line("Note", note_preview(category)),
+            line("Goal", goal_summary(category, budget)),
+        ],
+        None => vec![],
+    };
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+/// The selected category's goal field by field, with a gauge of how much of
+/// it is funded
+fn render_goal_details(
+    f: &mut Frame,
+    area: Rect,
+    category: Option<&Category>,
+    budget: Option<&BudgetSummary>,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(t("goal-details-title"));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let dim = Style::default().fg(theme::COLOR_HELP_TEXT);
+    let Some((category, goal_type)) =
+        category.and_then(|c| c.goal_type.as_deref().map(|goal_type| (c, goal_type)))
+    else {
+        let message = match category {
+            Some(_) => t("goal-details-none"),
+            None => t("goal-details-no-category"),
+        };
+        f.render_widget(Paragraph::new(Span::styled(message, dim)), inner);
+        return;
+    };
+
+    let width = inner.width as usize;
+    let amount = |milliunits: Option<Milliunits>| {
+        milliunits.map_or("—".to_string(), |m| {
+            utils::format_amount(m.into(), budget).trim().to_string()
+        })
+    };
+    // Labels line up on the longest one in the current language
+    const LABELS: [&str; 7] = [
+        "goal-details-type",
+        "goal-details-target",
+        "goal-details-by",
+        "goal-details-funded",
+        "goal-details-left",
+        "goal-details-this-month",
+        "goal-details-months-left",
+    ];
+    let label_width = LABELS.iter().map(|key| t(key).width()).max().unwrap_or(0) + 1;
+    let field = |key: &'static str, value: String| {
+        let label = t(key);
+        Line::from(vec![
+            Span::styled(
+                format!("{}{}", label, " ".repeat(label_width - label.width())),
+                dim,
+            ),
+            Span::from(utils::truncate_to_width(
+                &value,
+                width.saturating_sub(label_width),
+            )),
+        ])
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(
+            utils::truncate_to_width(&category.name, width),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        field(
+            "goal-details-type",
+            crate::utils::goals::goal_type_name(goal_type)
+                .unwrap_or(goal_type)
+                .to_string(),
+        ),
+        field("goal-details-target", amount(category.goal_target)),
+        field(
+            "goal-details-by",
+            category
+                .goal_target_month
+                .as_deref()
+                .map_or("—".to_string(), format_month_display),
+        ),
+        field("goal-details-funded", amount(category.goal_overall_funded)),
+        field("goal-details-left", amount(category.goal_overall_left)),
+        field(
+            "goal-details-this-month",
+            amount(category.goal_under_funded),
+        ),
+    ];
+    if let Some(months) = category.goal_months_to_budget {
+        lines.push(field("goal-details-months-left", months.to_string()));
+    }
+    if category.goal_snoozed_at.is_some() {
+        lines.push(Line::from(Span::styled(t("goal-details-snoozed"), dim)));
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(lines.len() as u16 + 1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let percent = category.goal_percentage_complete.unwrap_or(0).clamp(0, 100);
+    let color = if percent == 100 {
+        theme::COLOR_POSITIVE
+    } else {
+        theme::COLOR_HEADER
+    };
+    let gauge = Gauge::default()
+        .percent(percent as u16)
+        .label(t("goal-details-percent-funded").replace("{percent}", &percent.to_string()))
+        .gauge_style(Style::default().fg(color));
+    f.render_widget(gauge, chunks[1]);
+}
+
+/// The note on one line, or `None` if there's no note
+fn note_preview(category: &Category) -> Option<String> {
+    let note = category
+        .note
+        .as_deref()?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!note.is_empty()).then_some(note)
+}
+
+/// The goal as the web app describes it, e.g.
+/// "Target balance $500.00 by March 2026 · 40% funded · $300.00 left"
+fn goal_summary(category: &Category, budget: Option<&BudgetSummary>) -> Option<String> {
+    let goal_type = category.goal_type.as_deref()?;
+    let amount = |milliunits: i64| utils::format_amount(milliunits, budget).trim().to_string();
+
+    let mut summary = crate::utils::goals::goal_type_name(goal_type)
+        .unwrap_or(goal_type)
+        .to_string();
+    if let Some(target) = category.goal_target {
+        summary.push_str(&format!(" {}", amount(target.into())));
+    }
+    if let Some(month) = &category.goal_target_month {
+        summary.push_str(&format!(" by {}", format_month_display(month)));
+    }
+    if let Some(percent) = category.goal_percentage_complete {
+        summary.push_str(&format!(" · {}% funded", percent));
+    }
+    if let Some(left) = category.goal_overall_left.map(i64::from).filter(|&l| l > 0) {
+        summary.push_str(&format!(" · {} left", amount(left)));
+    }
+    if category.goal_snoozed_at.is_some() {
+        summary.push_str(" · snoozed");
+    }
+    Some(summary)
+}
+
+/// Swap a row's available column for the change since the compared month,
+/// and put the changes in budgeted and activity next to them. Rows are left
+/// as they are when nothing is compared.
+fn with_deltas<'a>(
+    mut cells: Vec<Text<'a>>,
+    comparison: Option<&PlanComparison>,
+    budget: Option<&BudgetSummary>,
+    deltas: impl FnOnce(&PlanComparison) -> Option<(i64, i64, i64)>,
+) -> Vec<Text<'a>> {
+    let Some(comparison) = comparison else {
+        return cells;
+    };
+    let deltas = deltas(comparison);
+    let delta = |pick: fn((i64, i64, i64)) -> i64| {
+        let text = match deltas.map(pick) {
+            Some(0) => Text::from("–").style(Style::default().fg(theme::COLOR_HELP_TEXT)),
+            Some(delta) => {
+                let amount = utils::format_amount(delta, budget);
+                let sign = if delta > 0 { "+" } else { "" };
+                Text::from(format!("{}{}", sign, amount.trim()))
+                    .style(Style::default().fg(utils::get_amount_color(delta)))
+            }
+            None => Text::from("…").style(Style::default().fg(theme::COLOR_HELP_TEXT)),
+        };
+        text.right_aligned()
+    };
+    cells.truncate(3);
+    cells.insert(2, delta(|d| d.0));
+    cells.push(delta(|d| d.1));
+    cells.push(delta(|d| d.2));
+    cells
+}
+
+/// A category group header with the totals of its listed categories
+fn build_group_cells<'a>(
+    group: &PlanGroup,
+    name_width: usize,
+    budget: Option<&BudgetSummary>,
+    config: &AppConfig,
+) -> Vec<Text<'a>> {
+    let marker = if group.collapsed { "▸" } else { "▾" };
+    let amount = |milliunits: i64, color: Color| {
+        Text::from(utils::format_amount(milliunits, budget))
+            .style(Style::default().fg(color))
+            .right_aligned()
+    };
+    vec![
+        Text::from(format!(
+            "{} {}",
+            marker,
+            utils::truncate_to_width(group.name, name_width.saturating_sub(2))
+        )),
+        amount(group.budgeted, utils::get_amount_color(group.budgeted)),
+        amount(group.activity, utils::get_amount_color(group.activity)),
+        amount(
+            group.balance,
+            theme::threshold_color(group.balance, &config.colors.available),
+        ),
+    ]
+}
+
 /// Format a month string (YYYY-MM-DD) to a human-readable format (e.g., "January 2025")
 fn format_month_display(month: &str) -> String {
     // Parse YYYY-MM-DD format
@@ -350,3 +784,340 @@ fn format_month_display(month: &str) -> String {
         month.to_string()
     }
 }
+
+impl ScreenController for PlanState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.budget, ctx.config, ctx.budget_settings);
+
+        // Render goal calculator popup if active
+        if self.input_mode == InputMode::GoalCalculator {
+            if let Some(ref calculator) = self.goal_calculator {
+                goal_calculator::render_goal_calculator(f, calculator, ctx.budget);
+            }
+        }
+
+        // Render unused category review if active
+        if self.input_mode == InputMode::ArchiveReview {
+            if let Some(ref review) = self.archive_review {
+                archive_review::render_archive_review(f, review);
+            }
+        }
+
+        if self.input_mode == InputMode::HealthChecklist {
+            if let Some(ref health) = self.health {
+                health_checklist::render_health_checklist(f, health, ctx.budget);
+            }
+        }
+
+        if self.input_mode == InputMode::MoveMoney {
+            if let Some(ref form) = self.move_money {
+                move_money::render_move_money(f, form, ctx.budget);
+            }
+        }
+
+        if self.input_mode == InputMode::GoalForm {
+            if let Some(ref form) = self.goal_form {
+                goal_form::render_goal_form(f, form);
+            }
+        }
+
+        if self.input_mode == InputMode::QuickBudget {
+            if let Some(ref quick) = self.quick_budget {
+                quick_budget::render_quick_budget(f, quick, ctx.budget);
+            }
+        }
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            // Fold or unfold a category group, or show a category's goal
+            Key::Enter | Key::Char(' ') if self.input_mode == InputMode::Normal => {
+                match self.selected_row()? {
+                    PlanRow::Group(group) => {
+                        Some(AppCommand::TogglePlanGroup { group_id: group.id })
+                    }
+                    PlanRow::Category(_) if key == Key::Char(' ') => {
+                        Some(AppCommand::OpenContextMenu)
+                    }
+                    PlanRow::Category(_) => Some(AppCommand::TogglePlanGoalDetails),
+                }
+            }
+            // Per-row action menu
+            Key::Char(' ' | 'o') => Some(AppCommand::OpenContextMenu),
+            Key::Char('R') => {
+                // Refresh everything for the current budget in one batch
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::RefreshAll {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Char(',') => Some(AppCommand::TogglePlanFocusedView),
+            Key::Char('n') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::TogglePlanCategoryDetails)
+            }
+            Key::Char('c') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::TogglePlanComparison)
+            }
+            Key::Char('*') if self.input_mode == InputMode::Normal => {
+                self.selected_category()
+                    .map(|category| AppCommand::ToggleFavoriteCategory {
+                        category_id: category.id.to_string(),
+                    })
+            }
+            Key::Char(c @ ('[' | ']'))
+                if self.input_mode == InputMode::Normal && self.comparison.is_some() =>
+            {
+                Some(AppCommand::ShiftPlanComparison { forward: c == ']' })
+            }
+            Key::Tab => Some(AppCommand::NavigatePlanMonth { forward: true }),
+            Key::BackTab => Some(AppCommand::NavigatePlanMonth { forward: false }),
+            Key::Char('e') => {
+                // Edit budgeted amount - only in Normal mode with valid selection
+                if self.input_mode == InputMode::Normal {
+                    self.selected_category()
+                        .map(|category| AppCommand::InitiateBudgetEdit {
+                            category_id: category.id.to_string(),
+                        })
+                } else {
+                    None
+                }
+            }
+            Key::Char('w') => {
+                // What-if calculator - only for target balance goals
+                if self.input_mode != InputMode::Normal {
+                    return None;
+                }
+                self.selected_category()
+                    .filter(|category| {
+                        crate::utils::goals::is_target_balance_goal(category.goal_type.as_deref())
+                    })
+                    .map(|category| AppCommand::OpenGoalCalculator {
+                        category_id: category.id.to_string(),
+                    })
+            }
+            Key::Char('t') if self.input_mode == InputMode::Normal => {
+                // Change the goal's target amount or date
+                self.selected_category()
+                    .map(|category| AppCommand::OpenGoalForm {
+                        category_id: category.id.to_string(),
+                    })
+            }
+            Key::Char('m') if self.input_mode == InputMode::Normal => {
+                self.selected_category()
+                    .map(|category| AppCommand::OpenMoveMoney {
+                        category_id: category.id.to_string(),
+                    })
+            }
+            Key::Char(c @ ('J' | 'K')) if self.input_mode == InputMode::Normal => {
+                // Move the selected category into the group below or above
+                let budget_id = state.current_budget_id.clone()?;
+                self.selected_category()
+                    .map(|category| AppCommand::MoveCategoryToGroup {
+                        budget_id,
+                        category_id: category.id.to_string(),
+                        forward: c == 'J',
+                    })
+            }
+            // Quick-budget commands: `b` then u, l or a (capitalised for every listed category)
+            Key::Char('b') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::SetPendingKey('b'))
+            }
+            Key::Char('A') if self.input_mode == InputMode::Normal => {
+                // Review categories that have gone unused, to hide them
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::OpenArchiveReview {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            Key::Char('H') if self.input_mode == InputMode::Normal => {
+                // What's left to tidy up: approvals, reconciling, overspending
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::OpenHealthChecklist {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            Key::Char('i') if state.config.debug.inspector => {
+                // Inspect the raw API payload for the selected category
+                self.selected_category()
+                    .map(|category| AppCommand::NavigateToInspector {
+                        focus_id: Some(category.id.to_string()),
+                    })
+            }
+            Key::Char('r') => {
+                // Force refresh plan
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::LoadPlan {
+                        budget_id: budget_id.clone(),
+                        force_refresh: true,
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_event(&mut self, event: ScreenEvent) {
+        match event {
+            ScreenEvent::LoadFailed(error) => {
+                // A failed popup or comparison load is shown there, not over
+                // the plan
+                let review_loading = [
+                    self.archive_review
+                        .as_mut()
+                        .map(|review| &mut review.loading),
+                    self.health.as_mut().map(|health| &mut health.loading),
+                    self.quick_budget.as_mut().map(|quick| &mut quick.loading),
+                    self.comparison
+                        .as_mut()
+                        .map(|comparison| &mut comparison.loading),
+                ]
+                .into_iter()
+                .flatten()
+                .find(|loading| matches!(loading, LoadingState::Loading(..)));
+                match review_loading {
+                    Some(loading) => loading.fail(error),
+                    None => self.plan_loading.fail(error),
+                }
+            }
+            ScreenEvent::RefreshCompleted => self.plan_loading.settle(),
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.plan_loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.plan_loading)
+    }
+
+    fn help_items(&self, config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        let mut items = vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("Enter", t("help-toggle-plan-group")),
+            ("Enter", t("help-goal-details")),
+            ("e", t("help-edit-budgeted")),
+            ("w", t("help-goal-calculator")),
+            ("t", t("help-edit-goal")),
+            ("m", t("help-move-money")),
+            ("bu/bU", t("help-quick-budget-underfunded")),
+            ("bl/bL", t("help-quick-budget-last-month")),
+            ("ba/bA", t("help-quick-budget-average")),
+            ("K", t("help-move-category-up")),
+            ("J", t("help-move-category-down")),
+            ("*", t("help-toggle-favorite")),
+            ("A", t("help-archive-review")),
+            ("H", t("help-health-checklist")),
+            ("Space/o", t("help-context-menu")),
+            ("r", t("help-refresh-plan")),
+            ("R", t("help-refresh-all")),
+            (",", t("help-toggle-focus-view")),
+            ("n", t("help-toggle-category-details")),
+            ("c", t("help-compare-months")),
+        ];
+        if self.comparison.is_some() {
+            items.push(("[/]", t("help-shift-comparison")));
+        }
+        if config.debug.inspector {
+            items.push(("i", t("help-inspect")));
+        }
+        items
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures as fixtures;
+
+    #[test]
+    fn assigned_ratio_is_full_once_nothing_is_left_to_budget() {
+        assert_eq!(assigned_ratio(300_000, 100_000), 0.75);
+        assert_eq!(assigned_ratio(0, 100_000), 0.0);
+        assert_eq!(assigned_ratio(300_000, 0), 1.0);
+        assert_eq!(assigned_ratio(300_000, -50_000), 1.0);
+    }
+
+    #[test]
+    fn month_metrics_show_age_of_money_in_days() {
+        let mut month = MonthDetail {
+            income: 3_000_000.into(),
+            budgeted: 2_750_500.into(),
+            activity: (-1_204_250).into(),
+            to_be_budgeted: 249_500.into(),
+            age_of_money: Some(42),
+            ..fixtures::month(Vec::new())
+        };
+
+        let metrics = month_metrics(&month, None);
+        assert_eq!(
+            metrics.map(|(label, value)| format!("{label} {value}")),
+            [
+                "Age of Money 42 days",
+                "Income $3,000.00",
+                "Budgeted $2,750.50",
+                "Activity -$1,204.25",
+            ]
+        );
+
+        month.age_of_money = Some(1);
+        assert_eq!(month_metrics(&month, None)[0].1, "1 day");
+        month.age_of_money = None;
+        assert_eq!(month_metrics(&month, None)[0].1, "—");
+    }
+
+    #[test]
+    fn category_details_read_like_the_web_app() {
+        let category = Category {
+            note: Some("Flights in March\nHotel deposit".to_string()),
+            balance: 200_000.into(),
+            goal_type: Some("TBD".to_string()),
+            goal_target: Some(500_000.into()),
+            goal_target_month: Some("2026-03-01".to_string()),
+            goal_percentage_complete: Some(40),
+            goal_overall_funded: Some(200_000.into()),
+            goal_overall_left: Some(300_000.into()),
+            ..fixtures::category("Vacation").group("Savings").build()
+        };
+
+        assert_eq!(
+            note_preview(&category).as_deref(),
+            Some("Flights in March Hotel deposit")
+        );
+        assert_eq!(
+            goal_summary(&category, None).as_deref(),
+            Some("Target balance $500.00 by March 2026 · 40% funded · $300.00 left")
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Row, Table},
+};
+
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::{t, tn};
+use crate::input::{Key, KeyEvent};
+use crate::reimbursements;
+use crate::state::{AppState, LoadingState, ReimbursementsState, Scrollable};
+use crate::ui::{
+    components::{empty_state, help_bar, screen_title, table_skeleton},
+    layouts, theme, utils,
+};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+pub fn render(f: &mut Frame, state: &ReimbursementsState, budget: Option<&BudgetSummary>) {
+    let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
+
+    screen_title::render_screen_title(f, title_area, &state.loading);
+    render_content(f, content_area, state, budget);
+    help_bar::render_help_bar(
+        f,
+        help_area,
+        "Enter: accept match | x: mark reimbursed | h: back | ?: help",
+    );
+}
+
+const COLUMN_WIDTHS: [Constraint; 5] = [
+    Constraint::Length(12),
+    Constraint::Percentage(25),
+    Constraint::Percentage(25),
+    Constraint::Length(14),
+    Constraint::Percentage(30),
+];
+
+fn render_content(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReimbursementsState,
+    budget: Option<&BudgetSummary>,
+) {
+    if matches!(state.loading, LoadingState::Loading(..)) && state.entries.is_empty() {
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "reimbursements",
+            t("reimbursements-loading"),
+            &COLUMN_WIDTHS,
+        );
+        return;
+    }
+
+    let outstanding = state.outstanding();
+    if outstanding.is_empty() {
+        empty_state::render_empty_state(
+            f,
+            area,
+            t("reimbursements-title"),
+            t("reimbursements-empty"),
+            None,
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Date"),
+        Cell::from("Payee"),
+        Cell::from("Memo"),
+        Cell::from(Text::from("Amount").right_aligned()),
+        Cell::from("Suggested Match"),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let rows: Vec<Row> = outstanding
+        .iter()
+        .map(|entry| {
+            let suggestion = match state.suggested_match(&entry.transaction_id) {
+                Some(m) => Cell::from(format!(
+                    "{} {}",
+                    m.deposit_date.format("%Y-%m-%d"),
+                    m.deposit_payee.as_deref().unwrap_or("")
+                ))
+                .style(Style::default().fg(theme::COLOR_POSITIVE)),
+                None => Cell::from(t("reimbursements-no-match"))
+                    .style(Style::default().fg(theme::COLOR_ZERO)),
+            };
+
+            Row::new(vec![
+                Cell::from(entry.date.format("%Y-%m-%d").to_string()),
+                Cell::from(entry.payee_name.clone().unwrap_or_default()),
+                Cell::from(entry.memo.clone().unwrap_or_default()),
+                Cell::from(Text::from(utils::format_amount(entry.amount, budget)).right_aligned())
+                    .style(Style::default().fg(utils::get_amount_color(entry.amount))),
+                suggestion,
+            ])
+        })
+        .collect();
+
+    let title = format!(
+        "{} - {}",
+        t("reimbursements-title"),
+        tn("reimbursements-summary", outstanding.len()).replace(
+            "{total}",
+            &utils::format_amount(reimbursements::expected_total(&state.entries), budget)
+        )
+    );
+
+    table_skeleton::remember_rows("reimbursements", rows.len());
+    let table = Table::new(rows, COLUMN_WIDTHS)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, area, &mut state.table_state.borrow_mut());
+}
+
+impl ScreenController for ReimbursementsState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.budget);
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Enter => {
+                // Accept the suggested deposit, if there is one
+                let budget_id = state.current_budget_id.as_ref()?;
+                let entry = self.selected_entry()?;
+                let suggestion = self.suggested_match(&entry.transaction_id)?;
+                Some(AppCommand::MarkReimbursed {
+                    budget_id: budget_id.clone(),
+                    transaction_id: entry.transaction_id.clone(),
+                    deposit_id: Some(suggestion.deposit_id.clone()),
+                })
+            }
+            Key::Char('x') => {
+                let budget_id = state.current_budget_id.as_ref()?;
+                let entry = self.selected_entry()?;
+                Some(AppCommand::MarkReimbursed {
+                    budget_id: budget_id.clone(),
+                    transaction_id: entry.transaction_id.clone(),
+                    deposit_id: None,
+                })
+            }
+            Key::Char('r') => {
+                state
+                    .current_budget_id
+                    .as_ref()
+                    .map(|budget_id| AppCommand::LoadReimbursements {
+                        budget_id: budget_id.clone(),
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.loading)
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("Enter", t("help-accept-reimbursement")),
+            ("x", t("help-mark-reimbursed")),
+            ("r", t("help-refresh-reimbursements")),
+        ]
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
+    }
+}
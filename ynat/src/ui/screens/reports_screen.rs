@@ -0,0 +1,1189 @@
+use ratatui::{
+    prelude::*,
+    symbols::Marker,
+    widgets::{
+        Axis, Bar, BarChart, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table,
+    },
+};
+
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::t;
+use crate::input::{Key, KeyEvent};
+use crate::reports::cash_flow::CashFlowProjection;
+use crate::reports::variance::VarianceSort;
+use crate::reports::{first_of_month, heatmap, ReportKind};
+use crate::state::{AppState, LoadingState, ReportDrillDown, ReportsState, Scrollable};
+use crate::ui::{
+    components::{empty_state, help_bar, loading_indicator, table_skeleton},
+    layouts, theme, utils,
+};
+use chrono::{Datelike, Months};
+use ynab_api::endpoints::budgets::BudgetSummary;
+
+pub fn render(f: &mut Frame, state: &ReportsState, budget: Option<&BudgetSummary>) {
+    let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
+
+    match &state.drill_down {
+        Some(drill_down) => {
+            render_title(f, title_area, state, &drill_down.loading);
+            render_drill_down(f, content_area, state, drill_down, budget);
+            help_bar::render_help_bar(f, help_area, "j/k: navigate | h/Esc: back to report");
+        }
+        None if state.kind == ReportKind::Heatmap => {
+            render_title(f, title_area, state, &state.loading);
+            render_heatmap(f, content_area, state, budget);
+            help_bar::render_help_bar(
+                f,
+                help_area,
+                "Enter: transactions | j/k: day | Tab: month | m: budget vs actual | o: goals due | ?: help",
+            );
+        }
+        None if state.kind == ReportKind::Spending => {
+            render_title(f, title_area, state, &state.loading);
+            render_spending(f, content_area, state, budget);
+            help_bar::render_help_bar(
+                f,
+                help_area,
+                "Enter: transactions | b: category/payee | Tab: period | m: budget vs actual | ?: help",
+            );
+        }
+        None if state.kind == ReportKind::IncomeExpense => {
+            render_title(f, title_area, state, &state.loading);
+            render_income_expense(f, content_area, state, budget);
+            help_bar::render_help_bar(
+                f,
+                help_area,
+                "j/k: month | r: refresh | m: budget vs actual | h: back | ?: help",
+            );
+        }
+        None if state.kind == ReportKind::Obligations => {
+            render_title(f, title_area, state, &state.loading);
+            render_obligations(f, content_area, state, budget);
+            help_bar::render_help_bar(
+                f,
+                help_area,
+                "r: refresh | m: budget vs actual | h: back | ?: help",
+            );
+        }
+        None if state.kind == ReportKind::CashFlow => {
+            render_title(f, title_area, state, &state.loading);
+            render_cash_flow(f, content_area, state, budget);
+            help_bar::render_help_bar(
+                f,
+                help_area,
+                "Tab: horizon | r: refresh | h: back | ?: help",
+            );
+        }
+        None => {
+            render_title(f, title_area, state, &state.loading);
+            render_variance(f, content_area, state, budget);
+            help_bar::render_help_bar(
+                f,
+                help_area,
+                "Enter: transactions | s: sort | Tab: period | m: heatmap | b: spending | i: income | o: goals due | ?: help",
+            );
+        }
+    }
+}
+
+fn render_title(f: &mut Frame, area: Rect, state: &ReportsState, loading: &LoadingState) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(20), Constraint::Length(28)])
+        .split(area);
+
+    let title = match state.kind {
+        ReportKind::Variance => format!(
+            "{} - {} ({})",
+            t("reports-title"),
+            t("report-variance"),
+            state.period.label()
+        ),
+        ReportKind::CashFlow => format!(
+            "{} - {}: {} ({})",
+            t("reports-title"),
+            t("report-cash-flow"),
+            state
+                .cash_flow
+                .as_ref()
+                .map(|p| p.account_name.as_str())
+                .unwrap_or_default(),
+            state.horizon.label()
+        ),
+        ReportKind::Obligations => format!(
+            "{} - {} ({})",
+            t("reports-title"),
+            t("report-obligations"),
+            state
+                .obligations
+                .as_ref()
+                .map(|o| o.month.format("%B %Y").to_string())
+                .unwrap_or_default()
+        ),
+        ReportKind::Spending => format!(
+            "{} - {}, {} ({})",
+            t("reports-title"),
+            t("report-spending"),
+            state.spending_grouping.label(),
+            state.period.label()
+        ),
+        ReportKind::IncomeExpense => format!(
+            "{} - {} ({})",
+            t("reports-title"),
+            t("report-income-expense"),
+            t("report-income-expense-months")
+        ),
+        ReportKind::Heatmap => format!(
+            "{} - {} ({})",
+            t("reports-title"),
+            t("report-heatmap"),
+            state
+                .heatmap_month
+                .map(|m| m.format("%B %Y").to_string())
+                .unwrap_or_default()
+        ),
+    };
+    f.render_widget(Paragraph::new(title).style(theme::title_style()), chunks[0]);
+    loading_indicator::render_loading_indicator(f, chunks[1], loading);
+}
+
+const VARIANCE_WIDTHS: [Constraint; 5] = [
+    Constraint::Percentage(36),
+    Constraint::Percentage(18),
+    Constraint::Percentage(18),
+    Constraint::Percentage(18),
+    Constraint::Percentage(10),
+];
+
+const DRILL_DOWN_WIDTHS: [Constraint; 5] = [
+    Constraint::Length(12),
+    Constraint::Percentage(20),
+    Constraint::Percentage(25),
+    Constraint::Percentage(30),
+    Constraint::Length(14),
+];
+
+fn render_variance(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReportsState,
+    budget: Option<&BudgetSummary>,
+) {
+    if matches!(state.loading, LoadingState::Loading(..)) && state.variance_rows.is_empty() {
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "variance",
+            t("reports-loading"),
+            &VARIANCE_WIDTHS,
+        );
+        return;
+    }
+
+    let rows = state.sorted_variance_rows();
+    if rows.is_empty() {
+        empty_state::render_empty_state(
+            f,
+            area,
+            t("report-variance"),
+            t("report-variance-empty"),
+            None,
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Category"),
+        Cell::from(Text::from("Budgeted").right_aligned()),
+        Cell::from(Text::from("Actual").right_aligned()),
+        Cell::from(Text::from("Variance").right_aligned()),
+        Cell::from(Text::from("%").right_aligned()),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let table_rows: Vec<Row> = rows
+        .iter()
+        .map(|row| {
+            let variance = row.variance();
+            let percent = row
+                .variance_percent()
+                .map(|p| format!("{:+.0}%", p))
+                .unwrap_or_else(|| "-".to_string());
+            let variance_style = Style::default().fg(utils::get_amount_color(variance));
+
+            Row::new(vec![
+                Cell::from(row.category_name.clone()),
+                Cell::from(Text::from(utils::format_amount(row.budgeted, budget)).right_aligned()),
+                Cell::from(Text::from(utils::format_amount(row.actual, budget)).right_aligned()),
+                Cell::from(Text::from(utils::format_amount(variance, budget)).right_aligned())
+                    .style(variance_style),
+                Cell::from(Text::from(percent).right_aligned()).style(variance_style),
+            ])
+        })
+        .collect();
+
+    let title = match state.variance_sort {
+        VarianceSort::Category => t("report-variance-by-category"),
+        VarianceSort::Variance => t("report-variance-by-variance"),
+    };
+
+    table_skeleton::remember_rows("variance", table_rows.len());
+    let table = Table::new(table_rows, VARIANCE_WIDTHS)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, area, &mut state.table_state.borrow_mut());
+}
+
+const SPENDING_WIDTHS: [Constraint; 4] = [
+    Constraint::Percentage(50),
+    Constraint::Percentage(15),
+    Constraint::Percentage(20),
+    Constraint::Percentage(15),
+];
+
+/// Bars shown above the spending table, biggest first
+const SPENDING_BARS: usize = 8;
+
+fn render_spending(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReportsState,
+    budget: Option<&BudgetSummary>,
+) {
+    let Some(breakdown) = &state.spending else {
+        if matches!(state.loading, LoadingState::Loading(..)) {
+            table_skeleton::render_table_skeleton(
+                f,
+                area,
+                "spending",
+                t("reports-loading"),
+                &SPENDING_WIDTHS,
+            );
+        }
+        return;
+    };
+    if breakdown.rows.is_empty() {
+        empty_state::render_empty_state(
+            f,
+            area,
+            t("report-spending"),
+            t("report-spending-empty"),
+            None,
+        );
+        return;
+    }
+
+    let bars = breakdown.rows.len().min(SPENDING_BARS);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(bars as u16 + 2), Constraint::Min(0)])
+        .split(area);
+
+    let chart = BarChart::horizontal(
+        breakdown
+            .rows
+            .iter()
+            .take(bars)
+            .map(|row| {
+                Bar::with_label(row.name.clone(), row.spent.unsigned_abs())
+                    .text_value(utils::format_amount(row.spent, budget).trim().to_string())
+                    .style(Style::default().fg(theme::COLOR_NEGATIVE))
+                    .value_style(Style::default().fg(Color::Black).bg(theme::COLOR_NEGATIVE))
+            })
+            .collect::<Vec<_>>(),
+    )
+    .bar_width(1)
+    .bar_gap(0)
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "{}: {}",
+        t("report-spending-total"),
+        utils::format_amount(breakdown.total(), budget).trim()
+    )));
+    f.render_widget(chart, chunks[0]);
+
+    let header = Row::new(vec![
+        Cell::from(state.spending_grouping.label()),
+        Cell::from(Text::from("Transactions").right_aligned()),
+        Cell::from(Text::from("Spent").right_aligned()),
+        Cell::from(Text::from("Share").right_aligned()),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let total = breakdown.total().max(1) as f64;
+    let rows: Vec<Row> = breakdown
+        .rows
+        .iter()
+        .map(|row| {
+            Row::new(vec![
+                Cell::from(row.name.clone()),
+                Cell::from(Text::from(row.transactions.to_string()).right_aligned()),
+                Cell::from(Text::from(utils::format_amount(row.spent, budget)).right_aligned()),
+                Cell::from(
+                    Text::from(format!("{:.0}%", row.spent as f64 * 100.0 / total)).right_aligned(),
+                ),
+            ])
+        })
+        .collect();
+
+    table_skeleton::remember_rows("spending", rows.len());
+    let table = Table::new(rows, SPENDING_WIDTHS)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL))
+        .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, chunks[1], &mut state.table_state.borrow_mut());
+}
+
+const INCOME_EXPENSE_WIDTHS: [Constraint; 4] = [
+    Constraint::Percentage(25),
+    Constraint::Percentage(25),
+    Constraint::Percentage(25),
+    Constraint::Percentage(25),
+];
+
+fn render_income_expense(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReportsState,
+    budget: Option<&BudgetSummary>,
+) {
+    let Some(report) = &state.income_expense else {
+        if matches!(state.loading, LoadingState::Loading(..)) {
+            table_skeleton::render_table_skeleton(
+                f,
+                area,
+                "income-expense",
+                t("reports-loading"),
+                &INCOME_EXPENSE_WIDTHS,
+            );
+        }
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+
+    let amount_cell = |amount: i64| {
+        Cell::from(Text::from(utils::format_amount(amount, budget)).right_aligned())
+            .style(Style::default().fg(utils::get_amount_color(amount)))
+    };
+
+    let header = Row::new(vec![
+        Cell::from("Month"),
+        Cell::from(Text::from("Inflow").right_aligned()),
+        Cell::from(Text::from("Outflow").right_aligned()),
+        Cell::from(Text::from("Net").right_aligned()),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let rows: Vec<Row> = report
+        .months
+        .iter()
+        .map(|month| {
+            Row::new(vec![
+                Cell::from(month.month.format("%b %Y").to_string()),
+                amount_cell(month.inflow),
+                amount_cell(month.outflow),
+                amount_cell(month.net()),
+            ])
+        })
+        .collect();
+
+    let title = format!(
+        "{}: {}",
+        t("report-income-expense-total"),
+        utils::format_amount(report.total_net(), budget).trim()
+    );
+    table_skeleton::remember_rows("income-expense", rows.len());
+    let table = Table::new(rows, INCOME_EXPENSE_WIDTHS)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(theme::selection_style());
+    f.render_stateful_widget(table, chunks[0], &mut state.table_state.borrow_mut());
+
+    // Categories behind the selected month
+    let Some(month) = state.selected_month_flow() else {
+        return;
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(month.month.format("%B %Y").to_string());
+    if month.categories.is_empty() {
+        f.render_widget(
+            Paragraph::new(t("report-income-expense-empty"))
+                .style(theme::help_text_style())
+                .block(block),
+            chunks[1],
+        );
+        return;
+    }
+    let rows: Vec<Row> = month
+        .categories
+        .iter()
+        .map(|category| {
+            Row::new(vec![
+                Cell::from(category.name.clone()),
+                amount_cell(category.amount),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [Constraint::Percentage(60), Constraint::Percentage(40)],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Category"),
+            Cell::from(Text::from("Amount").right_aligned()),
+        ])
+        .style(theme::header_style())
+        .underlined(),
+    )
+    .block(block);
+    f.render_widget(table, chunks[1]);
+}
+
+fn render_cash_flow(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReportsState,
+    budget: Option<&BudgetSummary>,
+) {
+    if matches!(state.loading, LoadingState::Loading(..)) {
+        empty_state::render_loading_state(f, area, "Status", t("reports-loading"));
+        return;
+    }
+    let Some(projection) = &state.cash_flow else {
+        empty_state::render_empty_state(
+            f,
+            area,
+            t("report-cash-flow"),
+            t("report-cash-flow-empty"),
+            None,
+        );
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(10),
+            Constraint::Length(3),
+            Constraint::Length(8),
+        ])
+        .split(area);
+
+    render_cash_flow_chart(f, chunks[0], projection, budget);
+
+    let lowest = projection
+        .lowest()
+        .map_or(projection.start_balance, |d| d.balance);
+    let summary = Line::from(vec![
+        Span::raw(format!("{}: ", t("report-cash-flow-start"))),
+        Span::styled(
+            utils::format_amount(projection.start_balance, budget),
+            Style::default().fg(utils::get_amount_color(projection.start_balance)),
+        ),
+        Span::raw(format!("   {}: ", t("report-cash-flow-lowest"))),
+        Span::styled(
+            utils::format_amount(lowest, budget),
+            Style::default().fg(utils::get_amount_color(lowest)),
+        ),
+        Span::raw(format!("   {}: ", t("report-cash-flow-run-rate"))),
+        Span::styled(
+            utils::format_amount(projection.daily_run_rate, budget),
+            Style::default().fg(utils::get_amount_color(projection.daily_run_rate)),
+        ),
+    ]);
+    f.render_widget(
+        Paragraph::new(summary).block(Block::default().borders(Borders::ALL)),
+        chunks[1],
+    );
+
+    let negative_days = projection.negative_days();
+    if negative_days.is_empty() {
+        f.render_widget(
+            Paragraph::new(t("report-cash-flow-never-negative"))
+                .style(Style::default().fg(theme::COLOR_POSITIVE))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(t("report-cash-flow-negative-days")),
+                ),
+            chunks[2],
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Date"),
+        Cell::from(Text::from("Scheduled").right_aligned()),
+        Cell::from(Text::from("Balance").right_aligned()),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let rows: Vec<Row> = negative_days
+        .iter()
+        .map(|day| {
+            Row::new(vec![
+                Cell::from(day.date.format("%Y-%m-%d").to_string()),
+                Cell::from(Text::from(utils::format_amount(day.scheduled, budget)).right_aligned()),
+                Cell::from(Text::from(utils::format_amount(day.balance, budget)).right_aligned())
+                    .style(Style::default().fg(utils::get_amount_color(day.balance))),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Percentage(40),
+            Constraint::Percentage(40),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(t("report-cash-flow-negative-days")),
+    )
+    .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, chunks[2], &mut state.table_state.borrow_mut());
+}
+
+fn render_cash_flow_chart(
+    f: &mut Frame,
+    area: Rect,
+    projection: &CashFlowProjection,
+    budget: Option<&BudgetSummary>,
+) {
+    // Plot in currency units; day 0 is today's balance
+    let balance: Vec<(f64, f64)> = std::iter::once((0.0, projection.start_balance as f64))
+        .chain(
+            projection
+                .days
+                .iter()
+                .enumerate()
+                .map(|(i, d)| ((i + 1) as f64, d.balance as f64)),
+        )
+        .map(|(x, y)| (x, y / 1000.0))
+        .collect();
+    let days = projection.days.len() as f64;
+    let zero = [(0.0, 0.0), (days, 0.0)];
+
+    let (min, max) = balance.iter().fold((0.0_f64, 0.0_f64), |(lo, hi), (_, y)| {
+        (lo.min(*y), hi.max(*y))
+    });
+    // Leave some headroom so the line doesn't sit on the border
+    let padding = ((max - min) * 0.1).max(1.0);
+    let (min, max) = (min - padding, max + padding);
+
+    let line_color = if projection.negative_days().is_empty() {
+        theme::COLOR_POSITIVE
+    } else {
+        theme::COLOR_NEGATIVE
+    };
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme::COLOR_ZERO))
+            .data(&zero),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(line_color))
+            .data(&balance),
+    ];
+
+    let first = projection
+        .days
+        .first()
+        .map(|d| d.date.format("%b %d").to_string());
+    let last = projection
+        .days
+        .last()
+        .map(|d| d.date.format("%b %d").to_string());
+    let amount = |v: f64| utils::format_amount((v * 1000.0) as i64, budget);
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t("report-cash-flow")),
+        )
+        .x_axis(Axis::default().bounds([0.0, days]).labels(vec![
+            t("report-cash-flow-start").to_string(),
+            first.unwrap_or_default(),
+            last.unwrap_or_default(),
+        ]))
+        .y_axis(Axis::default().bounds([min, max]).labels(vec![
+            amount(min),
+            amount(0.0),
+            amount(max),
+        ]));
+
+    f.render_widget(chart, area);
+}
+
+fn render_obligations(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReportsState,
+    budget: Option<&BudgetSummary>,
+) {
+    if matches!(state.loading, LoadingState::Loading(..)) {
+        empty_state::render_loading_state(f, area, "Status", t("reports-loading"));
+        return;
+    }
+    let Some(forecast) = &state.obligations else {
+        empty_state::render_empty_state(
+            f,
+            area,
+            t("report-obligations"),
+            t("report-obligations-empty"),
+            None,
+        );
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(5)])
+        .split(area);
+
+    let obligations = forecast.total_obligations();
+    let income = forecast.total_income();
+    let left = income - obligations;
+    let mut lines = vec![Line::from(vec![
+        Span::raw(format!("{}: ", t("report-obligations-due"))),
+        Span::raw(utils::format_amount(obligations, budget)),
+        Span::raw(format!("   {}: ", t("report-obligations-income"))),
+        Span::raw(utils::format_amount(income, budget)),
+        Span::raw(format!("   {}: ", t("report-obligations-left"))),
+        Span::styled(
+            utils::format_amount(left, budget),
+            Style::default().fg(utils::get_amount_color(left)),
+        ),
+    ])];
+    lines.push(if forecast.shortfall() > 0 {
+        Line::styled(
+            t("report-obligations-short").replace(
+                "{amount}",
+                &utils::format_amount(forecast.shortfall(), budget),
+            ),
+            Style::default().fg(theme::COLOR_NEGATIVE),
+        )
+    } else {
+        Line::styled(
+            t("report-obligations-covered"),
+            Style::default().fg(theme::COLOR_POSITIVE),
+        )
+    });
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL)),
+        chunks[0],
+    );
+
+    let tables = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[1]);
+
+    let rows: Vec<Row> = forecast
+        .obligations
+        .iter()
+        .map(|obligation| {
+            Row::new(vec![
+                Cell::from(obligation.category_name.clone()),
+                Cell::from(obligation.group_name.clone().unwrap_or_default())
+                    .style(theme::help_text_style()),
+                Cell::from(
+                    Text::from(utils::format_amount(obligation.amount, budget)).right_aligned(),
+                ),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(45),
+            Constraint::Percentage(30),
+            Constraint::Percentage(25),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Category"),
+            Cell::from("Group"),
+            Cell::from(Text::from("Needed").right_aligned()),
+        ])
+        .style(theme::header_style())
+        .underlined(),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(t("report-obligations-goals")),
+    )
+    .row_highlight_style(theme::selection_style());
+    f.render_stateful_widget(table, tables[0], &mut state.table_state.borrow_mut());
+
+    let rows: Vec<Row> = forecast
+        .income
+        .iter()
+        .map(|income| {
+            Row::new(vec![
+                Cell::from(income.date.format("%b %d").to_string()),
+                Cell::from(income.payee_name.clone().unwrap_or_default()),
+                Cell::from(Text::from(utils::format_amount(income.amount, budget)).right_aligned())
+                    .style(Style::default().fg(theme::COLOR_POSITIVE)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(7),
+            Constraint::Percentage(50),
+            Constraint::Percentage(35),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Date"),
+            Cell::from("Payee"),
+            Cell::from(Text::from("Amount").right_aligned()),
+        ])
+        .style(theme::header_style())
+        .underlined(),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(t("report-obligations-scheduled-income")),
+    );
+    f.render_widget(table, tables[1]);
+}
+
+/// Shade per heatmap level, from nothing spent to the month's busiest day
+const HEATMAP_SHADES: [&str; heatmap::LEVELS] = ["·", "░", "▒", "▓", "█"];
+
+const HEATMAP_CELL_WIDTH: u16 = 9;
+
+fn render_heatmap(f: &mut Frame, area: Rect, state: &ReportsState, budget: Option<&BudgetSummary>) {
+    let Some(heatmap) = &state.heatmap else {
+        if matches!(state.loading, LoadingState::Loading(..)) {
+            empty_state::render_loading_state(f, area, "Status", t("reports-loading"));
+        } else {
+            empty_state::render_empty_state(
+                f,
+                area,
+                t("report-heatmap"),
+                t("report-heatmap-empty"),
+                None,
+            );
+        }
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(4)])
+        .split(area);
+
+    let selected = state.table_state.borrow().selected();
+    let header = Row::new(
+        ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+            .into_iter()
+            .map(|day| Cell::from(Text::from(day).centered())),
+    )
+    .style(theme::header_style())
+    .underlined();
+
+    // Pad the first week so each day sits under its weekday
+    let offset = heatmap.month.weekday().num_days_from_monday() as usize;
+    let cells: Vec<Cell> = std::iter::repeat_with(|| Cell::from(""))
+        .take(offset)
+        .chain(heatmap.days.iter().enumerate().map(|(i, spent)| {
+            let level = heatmap.level(i);
+            let style = if selected == Some(i) {
+                theme::selection_style()
+            } else if *spent > 0 {
+                Style::default().fg(theme::COLOR_NEGATIVE)
+            } else {
+                Style::default().fg(theme::COLOR_ZERO)
+            };
+            Cell::from(format!("{:>2} {}", i + 1, HEATMAP_SHADES[level].repeat(4))).style(style)
+        }))
+        .collect();
+    let mut rows = Vec::new();
+    let mut cells = cells.into_iter().peekable();
+    while cells.peek().is_some() {
+        rows.push(Row::new(cells.by_ref().take(7).collect::<Vec<_>>()).height(2));
+    }
+
+    let table = Table::new(rows, [Constraint::Length(HEATMAP_CELL_WIDTH); 7])
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t("report-heatmap")),
+        );
+    f.render_widget(table, chunks[0]);
+
+    let mut lines = Vec::new();
+    if let Some(date) = state.selected_heatmap_date() {
+        let spent = selected
+            .and_then(|i| heatmap.days.get(i))
+            .copied()
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "{} {}: ",
+                date.format("%a %b %-d"),
+                t("report-heatmap-spent")
+            )),
+            Span::styled(
+                utils::format_amount(spent, budget),
+                Style::default().fg(utils::get_amount_color(-spent)),
+            ),
+            Span::raw(format!("   {}: ", t("report-heatmap-total"))),
+            Span::styled(
+                utils::format_amount(heatmap.total(), budget),
+                Style::default().fg(utils::get_amount_color(-heatmap.total())),
+            ),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::raw(format!("{} ", t("report-heatmap-less"))),
+        Span::styled(
+            HEATMAP_SHADES.concat(),
+            Style::default().fg(theme::COLOR_NEGATIVE),
+        ),
+        Span::raw(format!(" {}", t("report-heatmap-more"))),
+    ]));
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL)),
+        chunks[1],
+    );
+}
+
+fn render_drill_down(
+    f: &mut Frame,
+    area: Rect,
+    state: &ReportsState,
+    drill_down: &ReportDrillDown,
+    budget: Option<&BudgetSummary>,
+) {
+    let title = format!("{} - {}", drill_down.category_name, state.period.label());
+
+    if matches!(drill_down.loading, LoadingState::Loading(..)) {
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "variance_drill_down",
+            &title,
+            &DRILL_DOWN_WIDTHS,
+        );
+        return;
+    }
+    if drill_down.transactions.is_empty() {
+        empty_state::render_empty_state(f, area, &title, t("report-drill-down-empty"), None);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Date"),
+        Cell::from("Account"),
+        Cell::from("Payee"),
+        Cell::from("Memo"),
+        Cell::from(Text::from("Amount").right_aligned()),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let rows: Vec<Row> = drill_down
+        .transactions
+        .iter()
+        .map(|transaction| {
+            Row::new(vec![
+                Cell::from(transaction.date.format("%Y-%m-%d").to_string()),
+                Cell::from(transaction.account_name.clone()),
+                Cell::from(transaction.payee_name.clone().unwrap_or_default()),
+                Cell::from(transaction.memo.clone().unwrap_or_default()),
+                Cell::from(
+                    Text::from(utils::format_amount(transaction.amount, budget)).right_aligned(),
+                )
+                .style(Style::default().fg(utils::get_amount_color(transaction.amount))),
+            ])
+        })
+        .collect();
+
+    table_skeleton::remember_rows("variance_drill_down", rows.len());
+    let table = Table::new(rows, DRILL_DOWN_WIDTHS)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, area, &mut drill_down.table_state.borrow_mut());
+}
+
+impl ScreenController for ReportsState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.budget);
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            // Back out of a drill-down before leaving the screen
+            Key::Left | Key::Char('h') | Key::Esc if self.drill_down.is_some() => {
+                Some(AppCommand::CloseReportDrillDown)
+            }
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Char('o')
+                if self.kind != ReportKind::CashFlow
+                    && self.kind != ReportKind::Obligations
+                    && self.drill_down.is_none() =>
+            {
+                let budget_id = state.current_budget_id.clone()?;
+                Some(AppCommand::LoadGoalObligations {
+                    budget_id,
+                    force_refresh: false,
+                })
+            }
+            Key::Char('r') if self.kind == ReportKind::Obligations => {
+                let budget_id = state.current_budget_id.clone()?;
+                Some(AppCommand::LoadGoalObligations {
+                    budget_id,
+                    force_refresh: true,
+                })
+            }
+            Key::Tab | Key::BackTab if self.kind == ReportKind::Obligations => None,
+            Key::Char('i') if self.kind != ReportKind::CashFlow && self.drill_down.is_none() => {
+                let budget_id = state.current_budget_id.clone()?;
+                Some(AppCommand::LoadIncomeExpense { budget_id })
+            }
+            Key::Char('r') if self.kind == ReportKind::IncomeExpense => {
+                let budget_id = state.current_budget_id.clone()?;
+                Some(AppCommand::LoadIncomeExpense { budget_id })
+            }
+            // The report always covers the last twelve months
+            Key::Tab | Key::BackTab if self.kind == ReportKind::IncomeExpense => None,
+            Key::Char('b') if self.kind != ReportKind::CashFlow && self.drill_down.is_none() => {
+                let budget_id = state.current_budget_id.clone()?;
+                let grouping = match self.kind {
+                    ReportKind::Spending => self.spending_grouping.toggle(),
+                    _ => self.spending_grouping,
+                };
+                Some(AppCommand::LoadSpendingBreakdown {
+                    budget_id,
+                    period: self.period,
+                    grouping,
+                })
+            }
+            Key::Tab | Key::BackTab | Key::Char('r')
+                if self.kind == ReportKind::Spending && self.drill_down.is_none() =>
+            {
+                let budget_id = state.current_budget_id.clone()?;
+                let period = match key {
+                    Key::Tab => self.period.cycle(true),
+                    Key::BackTab => self.period.cycle(false),
+                    _ => self.period,
+                };
+                Some(AppCommand::LoadSpendingBreakdown {
+                    budget_id,
+                    period,
+                    grouping: self.spending_grouping,
+                })
+            }
+            Key::Enter if self.kind == ReportKind::Spending && self.drill_down.is_none() => {
+                let budget_id = state.current_budget_id.clone()?;
+                let category_id = self.selected_spending_row()?.category_id.clone()?;
+                Some(AppCommand::DrillIntoVarianceCategory {
+                    budget_id,
+                    category_id,
+                })
+            }
+            Key::Char('m') if self.kind != ReportKind::CashFlow && self.drill_down.is_none() => {
+                let budget_id = state.current_budget_id.clone()?;
+                if self.kind != ReportKind::Variance {
+                    return Some(AppCommand::LoadVarianceReport {
+                        budget_id,
+                        period: self.period,
+                        force_refresh: false,
+                    });
+                }
+                let month = self
+                    .heatmap_month
+                    .unwrap_or_else(|| first_of_month(chrono::Local::now().date_naive()));
+                Some(AppCommand::LoadSpendingHeatmap { budget_id, month })
+            }
+            Key::Tab | Key::BackTab | Key::Char('r') if self.kind == ReportKind::Heatmap => {
+                let budget_id = state.current_budget_id.clone()?;
+                let month = self.heatmap_month?;
+                let month = match key {
+                    Key::Tab => month.checked_add_months(Months::new(1))?,
+                    Key::BackTab => month.checked_sub_months(Months::new(1))?,
+                    _ => month,
+                };
+                Some(AppCommand::LoadSpendingHeatmap { budget_id, month })
+            }
+            Key::Enter if self.kind == ReportKind::Heatmap => {
+                let budget_id = state.current_budget_id.clone()?;
+                self.selected_heatmap_date()
+                    .map(|date| AppCommand::NavigateToTransactionsOnDate { budget_id, date })
+            }
+            Key::Tab | Key::BackTab | Key::Char('r') if self.kind == ReportKind::CashFlow => {
+                let budget_id = state.current_budget_id.as_ref()?;
+                let horizon = match key {
+                    Key::Tab => self.horizon.cycle(true),
+                    Key::BackTab => self.horizon.cycle(false),
+                    _ => self.horizon,
+                };
+                Some(AppCommand::LoadCashFlowProjection {
+                    budget_id: budget_id.clone(),
+                    account_id: self.account_id.clone()?,
+                    horizon,
+                })
+            }
+            Key::Tab | Key::BackTab | Key::Char('r') => {
+                if self.drill_down.is_some() {
+                    return None;
+                }
+                let budget_id = state.current_budget_id.as_ref()?;
+                let (period, force_refresh) = match key {
+                    Key::Tab => (self.period.cycle(true), false),
+                    Key::BackTab => (self.period.cycle(false), false),
+                    _ => (self.period, true),
+                };
+                Some(AppCommand::LoadVarianceReport {
+                    budget_id: budget_id.clone(),
+                    period,
+                    force_refresh,
+                })
+            }
+            Key::Char('s') if self.kind == ReportKind::Variance && self.drill_down.is_none() => {
+                Some(AppCommand::ToggleVarianceSort)
+            }
+            Key::Enter if self.kind == ReportKind::Variance && self.drill_down.is_none() => {
+                let budget_id = state.current_budget_id.as_ref()?;
+                self.selected_variance_row()
+                    .map(|row| AppCommand::DrillIntoVarianceCategory {
+                        budget_id: budget_id.clone(),
+                        category_id: row.category_id.clone(),
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(match self.drill_down {
+            Some(ref drill_down) => &drill_down.loading,
+            None => &self.loading,
+        })
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(match self.drill_down {
+            Some(ref mut drill_down) => &mut drill_down.loading,
+            None => &mut self.loading,
+        })
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        let mut items = vec![("↑/k", t("help-move-up")), ("↓/j", t("help-move-down"))];
+        if self.drill_down.is_some() {
+            items.push(("h/Esc", t("help-report-close-drill-down")));
+        } else if self.kind == ReportKind::Obligations {
+            items.push(("m", t("help-report-back-to-variance")));
+            items.push(("r", t("help-report-refresh")));
+        } else if self.kind == ReportKind::Heatmap {
+            items = vec![
+                ("↑/k", t("help-report-previous-day")),
+                ("↓/j", t("help-report-next-day")),
+                ("Enter", t("help-report-day-transactions")),
+                ("Tab/S-Tab", t("help-report-month")),
+                ("m", t("help-report-switch")),
+                ("o", t("help-report-obligations")),
+                ("r", t("help-report-refresh")),
+            ];
+        } else if self.kind == ReportKind::IncomeExpense {
+            items = vec![
+                ("↑/k", t("help-report-previous-month")),
+                ("↓/j", t("help-report-next-month")),
+                ("m", t("help-report-back-to-variance")),
+                ("r", t("help-report-refresh")),
+            ];
+        } else if self.kind == ReportKind::Spending {
+            items.push(("Enter", t("help-report-drill-down")));
+            items.push(("b", t("help-report-spending-grouping")));
+            items.push(("m", t("help-report-back-to-variance")));
+            items.push(("Tab/S-Tab", t("help-report-period")));
+            items.push(("r", t("help-report-refresh")));
+        } else if self.kind == ReportKind::CashFlow {
+            items.push(("Tab/S-Tab", t("help-report-horizon")));
+            items.push(("r", t("help-report-refresh")));
+        } else {
+            items.push(("Enter", t("help-report-drill-down")));
+            items.push(("s", t("help-report-sort")));
+            items.push(("m", t("help-report-switch")));
+            items.push(("o", t("help-report-obligations")));
+            items.push(("b", t("help-report-spending")));
+            items.push(("i", t("help-report-income-expense")));
+            items.push(("Tab/S-Tab", t("help-report-period")));
+            items.push(("r", t("help-report-refresh")));
+        }
+        items
+    }
+
+    fn select_next(&mut self) {
+        match self.drill_down {
+            Some(ref mut drill_down) => drill_down.select_next(),
+            None => Scrollable::select_next(self),
+        }
+    }
+
+    fn select_previous(&mut self) {
+        match self.drill_down {
+            Some(ref mut drill_down) => drill_down.select_prev(),
+            None => Scrollable::select_prev(self),
+        }
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        match self.drill_down {
+            Some(ref mut drill_down) => drill_down.select_by(rows),
+            None => Scrollable::select_by(self, rows),
+        }
+    }
+
+    fn select_first(&mut self) {
+        match self.drill_down {
+            Some(ref mut drill_down) => drill_down.select_first(),
+            None => Scrollable::select_first(self),
+        }
+    }
+
+    fn select_last(&mut self) {
+        match self.drill_down {
+            Some(ref mut drill_down) => drill_down.select_last(),
+            None => Scrollable::select_last(self),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Row, Table},
+};
+
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::{t, tn};
+use crate::input::{Key, KeyEvent};
+use crate::state::{AppState, LoadingState, ScheduledTransactionsState, Scrollable};
+use crate::ui::{
+    components::{empty_state, help_bar, screen_title, table_skeleton},
+    layouts, theme, utils,
+};
+use ynab_api::endpoints::{budgets::BudgetSummary, scheduled_transactions::Frequency};
+
+pub fn render(f: &mut Frame, state: &ScheduledTransactionsState, budget: Option<&BudgetSummary>) {
+    let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
+
+    screen_title::render_screen_title(f, title_area, &state.loading);
+    render_content(f, content_area, state, budget);
+    help_bar::render_help_bar(f, help_area, "r: refresh | h: back | ?: help");
+}
+
+fn column_widths(show_account: bool) -> Vec<Constraint> {
+    let mut widths = vec![Constraint::Length(12), Constraint::Length(18)];
+    if show_account {
+        widths.push(Constraint::Percentage(20));
+    }
+    widths.extend([
+        Constraint::Percentage(30),
+        Constraint::Percentage(25),
+        Constraint::Length(14),
+    ]);
+    widths
+}
+
+fn render_content(
+    f: &mut Frame,
+    area: Rect,
+    state: &ScheduledTransactionsState,
+    budget: Option<&BudgetSummary>,
+) {
+    // The account column is only useful when every account is listed
+    let show_account = state.account_id.is_none();
+
+    if matches!(state.loading, LoadingState::Loading(..)) && state.scheduled_transactions.is_empty()
+    {
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "scheduled",
+            t("scheduled-loading"),
+            &column_widths(show_account),
+        );
+        return;
+    }
+
+    let visible = state.visible();
+    if visible.is_empty() {
+        empty_state::render_empty_state(f, area, t("scheduled-title"), t("scheduled-empty"), None);
+        return;
+    }
+
+    let mut header = vec![Cell::from("Next Date"), Cell::from("Frequency")];
+    if show_account {
+        header.push(Cell::from("Account"));
+    }
+    header.extend([
+        Cell::from("Payee"),
+        Cell::from("Category"),
+        Cell::from(Text::from("Amount").right_aligned()),
+    ]);
+    let header = Row::new(header).style(theme::header_style()).underlined();
+
+    let rows: Vec<Row> = visible
+        .iter()
+        .map(|scheduled| {
+            let amount: i64 = scheduled.amount.into();
+            let mut cells = vec![
+                Cell::from(scheduled.date_next.format("%Y-%m-%d").to_string()),
+                Cell::from(frequency_label(scheduled.frequency)),
+            ];
+            if show_account {
+                cells.push(Cell::from(scheduled.account_name.clone()));
+            }
+            cells.extend([
+                Cell::from(scheduled.payee_name.clone().unwrap_or_default()),
+                Cell::from(scheduled.category_name.clone().unwrap_or_default()),
+                Cell::from(Text::from(utils::format_amount(amount, budget)).right_aligned())
+                    .style(Style::default().fg(utils::get_amount_color(amount))),
+            ]);
+            Row::new(cells)
+        })
+        .collect();
+
+    let title = format!(
+        "{} - {}",
+        t("scheduled-title"),
+        tn("scheduled-summary", visible.len())
+    );
+
+    table_skeleton::remember_rows("scheduled", rows.len());
+    let table = Table::new(rows, column_widths(show_account))
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, area, &mut state.table_state.borrow_mut());
+}
+
+fn frequency_label(frequency: Frequency) -> &'static str {
+    match frequency {
+        Frequency::Never => t("frequency-never"),
+        Frequency::Daily => t("frequency-daily"),
+        Frequency::Weekly => t("frequency-weekly"),
+        Frequency::EveryOtherWeek => t("frequency-every-other-week"),
+        Frequency::TwiceAMonth => t("frequency-twice-a-month"),
+        Frequency::Every4Weeks => t("frequency-every-4-weeks"),
+        Frequency::Monthly => t("frequency-monthly"),
+        Frequency::EveryOtherMonth => t("frequency-every-other-month"),
+        Frequency::Every3Months => t("frequency-every-3-months"),
+        Frequency::Every4Months => t("frequency-every-4-months"),
+        Frequency::TwiceAYear => t("frequency-twice-a-year"),
+        Frequency::Yearly => t("frequency-yearly"),
+        Frequency::EveryOtherYear => t("frequency-every-other-year"),
+    }
+}
+
+impl ScreenController for ScheduledTransactionsState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.budget);
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Char('r') => state.current_budget_id.as_ref().map(|budget_id| {
+                AppCommand::LoadScheduledTransactions {
+                    budget_id: budget_id.clone(),
+                    force_refresh: true,
+                }
+            }),
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.loading)
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("r", t("help-refresh-scheduled")),
+        ]
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
+    }
+}
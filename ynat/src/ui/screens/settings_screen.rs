@@ -0,0 +1,156 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::events::AppCommand;
+use crate::i18n::t;
+use crate::input::{Key, KeyEvent};
+use crate::options::SettingOption;
+use crate::state::{AppState, LoadingState, Scrollable, SettingsState};
+use crate::ui::{components::help_bar, layouts, theme, utils};
+
+/// Typed into the preview's made-up transaction, in milliunits
+const PREVIEW_AMOUNT: i64 = -12_340;
+
+pub fn render(f: &mut Frame, state: &SettingsState, ctx: &RenderContext) {
+    let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
+
+    let title = Paragraph::new(t("settings-title")).style(theme::title_style());
+    f.render_widget(title, title_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(SettingOption::ALL.len() as u16 + 3),
+            Constraint::Min(0),
+        ])
+        .split(content_area);
+    render_options(f, chunks[0], state, ctx);
+    render_preview(f, chunks[1], ctx);
+    help_bar::render_help_bar(
+        f,
+        help_area,
+        "Enter/→: next value | ←: previous value | h: back | ?: help",
+    );
+}
+
+fn render_options(f: &mut Frame, area: Rect, state: &SettingsState, ctx: &RenderContext) {
+    let header = Row::new(vec![
+        Cell::from(t("settings-setting")),
+        Cell::from(t("settings-value")),
+    ])
+    .style(theme::header_style())
+    .underlined();
+
+    let rows: Vec<Row> = SettingOption::ALL
+        .iter()
+        .map(|option| {
+            Row::new(vec![
+                Cell::from(option.label()),
+                Cell::from(option.value(ctx.config, ctx.budget_settings))
+                    .style(Style::default().fg(theme::COLOR_TITLE)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [Constraint::Percentage(50), Constraint::Percentage(50)],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL))
+    .row_highlight_style(theme::selection_style());
+
+    f.render_stateful_widget(table, area, &mut state.table_state.borrow_mut());
+}
+
+/// A date and an amount as the rest of the app will now show them
+fn render_preview(f: &mut Frame, area: Rect, ctx: &RenderContext) {
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let date = match ctx.budget.and_then(|b| b.date_format.as_ref()) {
+        Some(format) => utils::fmt_date(&today, format),
+        None => today,
+    };
+    let saved = ctx.config.amounts.rounding.apply(PREVIEW_AMOUNT);
+    let amount = |milliunits| {
+        utils::format_amount(milliunits, ctx.budget)
+            .trim()
+            .to_string()
+    };
+
+    let lines = vec![
+        Line::from(t("settings-preview-date").replace("{date}", &date)),
+        Line::from(
+            t("settings-preview-amount")
+                .replace("{typed}", &amount(PREVIEW_AMOUNT))
+                .replace("{saved}", &amount(saved)),
+        ),
+        Line::from(""),
+        Line::from(t("settings-saved-to").replace("{path}", &AppConfig::path()))
+            .style(theme::help_text_style()),
+    ];
+    let preview = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(t("settings-preview")),
+    );
+    f.render_widget(preview, area);
+}
+
+impl ScreenController for SettingsState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx);
+    }
+
+    fn handle_key(&self, event: KeyEvent, _state: &AppState) -> Option<AppCommand> {
+        match event.key {
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => Some(AppCommand::SelectNext),
+            Key::Enter | Key::Char(' ') | Key::Right | Key::Char('l') => {
+                Some(AppCommand::ChangeSetting { forward: true })
+            }
+            Key::Left => Some(AppCommand::ChangeSetting { forward: false }),
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        None
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        None
+    }
+
+    fn help_items(&self, _config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("Enter/→", t("help-change-setting")),
+            ("←", t("help-change-setting-back")),
+        ]
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
+    }
+}
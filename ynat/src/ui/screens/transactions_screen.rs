@@ -4,32 +4,52 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
 };
 
-use crate::state::{InputMode, LoadingState, TransactionsState};
+use super::{RenderContext, ScreenController};
+use crate::config::AppConfig;
+use crate::config::DisplayConfig;
+use crate::events::AppCommand;
+use crate::i18n::{t, tn};
+use crate::input::{Key, KeyEvent};
+use crate::reimbursements::ReimbursementEntry;
+use crate::state::{
+    AppState, InputMode, LoadingState, Scrollable, TransactionPreset, TransactionSort,
+    TransactionsState,
+};
 use crate::ui::{
-    components::{empty_state, filter_input, help_bar, inline_transaction_form, screen_title},
+    components::{
+        delete_confirmation, delta_conflict, empty_state, filter_input, help_bar,
+        inline_transaction_form, reconcile_confirmation, reconciled_edit_confirmation,
+        screen_title, table_skeleton,
+    },
     layouts, theme, utils,
 };
 use itertools::Itertools;
+use std::collections::HashMap;
 use ynab_api::endpoints::{
     budgets::BudgetSummary,
-    transactions::{ReconciliationStatus, SubTransaction, Transaction},
+    transactions::{FlagColor, ReconciliationStatus, SubTransaction, Transaction},
 };
 
-pub fn render(f: &mut Frame, state: &TransactionsState, budget: Option<&BudgetSummary>) {
+pub fn render(
+    f: &mut Frame,
+    state: &TransactionsState,
+    budget: Option<&BudgetSummary>,
+    display: &DisplayConfig,
+) {
     if state.input_mode == InputMode::Filter {
         let (title_area, filter_area, content_area, help_area) =
             layouts::screen_layout_with_filter(f.area());
 
         screen_title::render_screen_title(f, title_area, &state.transactions_loading);
         filter_input::render_filter_input(f, filter_area, &state.filter_query);
-        render_content(f, content_area, state, budget);
-        help_bar::render_help_bar(f, help_area, help_bar::HELP_TEXT_DEFAULT);
+        render_content(f, content_area, state, budget, display);
+        help_bar::render_help_bar(f, help_area, help_bar::help_text_default());
     } else {
         let (title_area, content_area, help_area) = layouts::screen_layout(f.area());
 
         screen_title::render_screen_title(f, title_area, &state.transactions_loading);
-        render_content(f, content_area, state, budget);
-        help_bar::render_help_bar(f, help_area, help_bar::HELP_TEXT_DEFAULT);
+        render_content(f, content_area, state, budget, display);
+        help_bar::render_help_bar(f, help_area, help_bar::help_text_default());
     }
 }
 
@@ -38,12 +58,19 @@ fn render_content(
     area: Rect,
     state: &TransactionsState,
     budget: Option<&BudgetSummary>,
+    display: &DisplayConfig,
 ) {
     // Show loading message if currently loading and no cached data
     if matches!(state.transactions_loading, LoadingState::Loading(..))
         && state.transactions.is_empty()
     {
-        empty_state::render_loading_state(f, area, "Status", "Loading transactions...");
+        table_skeleton::render_table_skeleton(
+            f,
+            area,
+            "transactions",
+            t("transactions-loading"),
+            &inline_transaction_form::FORM_COLUMN_CONSTRAINTS,
+        );
         return;
     }
 
@@ -54,41 +81,58 @@ fn render_content(
         .map(inline_transaction_form::has_validation_error)
         .unwrap_or(false);
 
-    // Build layout with optional error row
-    let (summary_area, error_area, table_area) = if has_error {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(theme::SUMMARY_CARD_HEIGHT),
-                Constraint::Length(1), // Error row
-                Constraint::Min(0),
-            ])
-            .split(area);
-        (chunks[0], Some(chunks[1]), chunks[2])
-    } else {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(theme::SUMMARY_CARD_HEIGHT),
-                Constraint::Min(0),
-            ])
-            .split(area);
-        (chunks[0], None, chunks[1])
+    // Build layout with optional selection context, error and preset chip rows
+    let filtered = state.filtered_transactions();
+    let selected = match state.input_mode {
+        InputMode::TransactionForm => None,
+        _ => state
+            .table_state
+            .borrow()
+            .selected()
+            .and_then(|idx| filtered.get(idx).copied()),
     };
+    let has_presets =
+        !state.presets.is_empty() || state.on_date.is_some() || state.flag_view.is_some();
+    let mut constraints = vec![Constraint::Length(theme::SUMMARY_CARD_HEIGHT)];
+    if selected.is_some() {
+        constraints.push(Constraint::Length(1));
+    }
+    if has_error {
+        constraints.push(Constraint::Length(1));
+    }
+    if has_presets {
+        constraints.push(Constraint::Length(1));
+    }
+    constraints.push(Constraint::Min(0));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+    let summary_area = chunks[0];
+    let selection_area = selected.is_some().then(|| chunks[1]);
+    let error_area = has_error.then(|| chunks[1 + usize::from(selected.is_some())]);
+    let presets_area = has_presets.then(|| chunks[chunks.len() - 2]);
+    let table_area = chunks[chunks.len() - 1];
 
     // Render balance summary in the top area
     render_balance_summary(f, summary_area, state, budget);
 
+    if let (Some(selection_area), Some(transaction)) = (selection_area, selected) {
+        render_selection_context(f, selection_area, state, transaction, budget);
+    }
+
     // Render validation error if present
     if let (Some(error_rect), Some(ref form_state)) = (error_area, &state.form_state) {
         inline_transaction_form::render_validation_error(f, error_rect, form_state);
     }
 
-    // Apply filter to transactions
-    let filtered = state.filtered_transactions();
+    if let Some(presets_area) = presets_area {
+        render_preset_chips(f, presets_area, state, display);
+    }
 
     // Show transactions table if we have data
     if !filtered.is_empty() {
+        let balances = state.running_balances();
         // Create table header
         let header = Row::new(vec![
             Cell::from("▱"),
@@ -99,6 +143,7 @@ fn render_content(
             Cell::from(Text::from("Amount").right_aligned()),
             Cell::from("ⓘ"),
             Cell::from("C"),
+            Cell::from(Text::from(t("transactions-balance-column")).right_aligned()),
         ])
         .style(theme::header_style())
         .underlined();
@@ -127,42 +172,54 @@ fn render_content(
                             if transaction.id.to_string() == *edit_id {
                                 form_visual_offset = Some(visual_offset);
                                 // Add placeholder row (will be rendered directly)
-                                rows.push(Row::new(vec![Cell::from(""); 8]));
+                                rows.push(Row::new(vec![Cell::from(""); 9]));
                                 // Add placeholder rows for subtransactions
                                 for _ in 0..subtransaction_count {
-                                    rows.push(Row::new(vec![Cell::from(""); 8]));
+                                    rows.push(Row::new(vec![Cell::from(""); 9]));
                                 }
                                 visual_offset += 1 + subtransaction_count as u16;
                             } else {
                                 let row_height = calculate_row_height(transaction);
-                                rows.push(build_transaction_row(transaction, budget));
+                                rows.push(build_transaction_row(
+                                    transaction,
+                                    state,
+                                    &balances,
+                                    budget,
+                                    display,
+                                ));
                                 visual_offset += row_height;
                             }
                         }
                     } else {
                         // CREATE MODE: Insert placeholder at top
                         form_visual_offset = Some(0);
-                        rows.push(Row::new(vec![Cell::from(""); 8]));
+                        rows.push(Row::new(vec![Cell::from(""); 9]));
                         // Add placeholder rows for subtransactions
                         for _ in 0..subtransaction_count {
-                            rows.push(Row::new(vec![Cell::from(""); 8]));
+                            rows.push(Row::new(vec![Cell::from(""); 9]));
                         }
                         // Then add all existing transactions
                         for transaction in filtered.iter() {
-                            rows.push(build_transaction_row(transaction, budget));
+                            rows.push(build_transaction_row(
+                                transaction,
+                                state,
+                                &balances,
+                                budget,
+                                display,
+                            ));
                         }
                     }
                 } else {
                     rows = filtered
                         .iter()
-                        .map(|t| build_transaction_row(t, budget))
+                        .map(|t| build_transaction_row(t, state, &balances, budget, display))
                         .collect();
                 }
             } else {
                 // Normal rendering without form
                 rows = filtered
                     .iter()
-                    .map(|t| build_transaction_row(t, budget))
+                    .map(|t| build_transaction_row(t, state, &balances, budget, display))
                     .collect();
             }
 
@@ -170,28 +227,45 @@ fn render_content(
         };
 
         // Update table title to show filter status
-        let title = if !state.filter_query.is_empty() {
-            format!("Transactions ({} filtered)", filtered.len())
-        } else {
-            "Transactions".to_string()
+        let (reviewed, total) = state.inbox_progress();
+        let title = match (state.all_accounts, !state.is_filtered()) {
+            _ if state.inbox && total == 0 => t("inbox-title-empty").to_string(),
+            _ if state.inbox => t("inbox-title")
+                .replace("{reviewed}", &reviewed.to_string())
+                .replace("{total}", &total.to_string()),
+            (true, true) => t("search-title").to_string(),
+            (true, false) => tn("search-title-filtered", filtered.len()),
+            (false, true) => t("transactions-title").to_string(),
+            (false, false) => tn("transactions-title-filtered", filtered.len()),
         };
 
-        let mut table = Table::new(
-            rows,
-            [
-                Constraint::Length(1),
-                Constraint::Length(10),
-                Constraint::Percentage(30),
-                Constraint::Percentage(25),
-                Constraint::Percentage(30),
-                Constraint::Percentage(15),
-                Constraint::Length(1),
-                Constraint::Length(1),
-            ],
-        )
-        .header(header)
-        .block(Block::default().borders(Borders::ALL).title(title))
-        .column_spacing(theme::TABLE_COLUMN_SPACING);
+        let mut block = Block::default().borders(Borders::ALL).title(title);
+        if state.sort != TransactionSort::default() {
+            let arrow = if state.sort.reversed { "▲" } else { "▼" };
+            block = block.title(
+                Line::from(
+                    t("transactions-sorted-by")
+                        .replace("{column}", state.sort.column.display_name())
+                        .replace("{arrow}", arrow),
+                )
+                .right_aligned(),
+            );
+        }
+        if let Some(since) = state.loaded_since {
+            block = block.title_bottom(
+                Line::from(
+                    t("transactions-older-hint")
+                        .replace("{date}", &since.format("%Y-%m-%d").to_string()),
+                )
+                .right_aligned(),
+            );
+        }
+
+        table_skeleton::remember_rows("transactions", rows.len());
+        let mut table = Table::new(rows, inline_transaction_form::FORM_COLUMN_CONSTRAINTS)
+            .header(header)
+            .block(block)
+            .column_spacing(theme::TABLE_COLUMN_SPACING);
 
         if !matches!(state.input_mode, InputMode::TransactionForm) {
             table = table.row_highlight_style(theme::selection_style());
@@ -238,13 +312,15 @@ fn render_content(
         }
     } else {
         // No matching transactions - show message based on filter state
-        let message = if !state.filter_query.is_empty() {
-            "No matching transactions"
+        let message = if state.is_filtered() {
+            t("transactions-no-match").to_string()
+        } else if let Some(since) = state.loaded_since {
+            t("transactions-empty-since").replace("{date}", &since.format("%Y-%m-%d").to_string())
         } else {
-            "No transactions found"
+            t("transactions-empty").to_string()
         };
 
-        empty_state::render_empty_state(f, table_area, "Transactions", message, None);
+        empty_state::render_empty_state(f, table_area, t("transactions-title"), &message, None);
     }
 }
 
@@ -260,6 +336,7 @@ fn build_parent_line(
     transaction: &Transaction,
     column: &str,
     budget: Option<&BudgetSummary>,
+    display: &DisplayConfig,
 ) -> Line<'static> {
     match column {
         "date" => {
@@ -289,6 +366,7 @@ fn build_parent_line(
                     transaction
                         .category_name
                         .as_deref()
+                        .map(|name| display.category_name(name))
                         .unwrap_or("-")
                         .to_string(),
                 )
@@ -334,6 +412,7 @@ fn build_subtransaction_line(
     subtransaction: &SubTransaction,
     column: &str,
     budget: Option<&BudgetSummary>,
+    display: &DisplayConfig,
 ) -> Line<'static> {
     match column {
         "date" => Line::from(""),
@@ -354,7 +433,8 @@ fn build_subtransaction_line(
         "category" => {
             let category = subtransaction
                 .category_name
-                .clone()
+                .as_deref()
+                .map(|name| display.category_name(name).to_string())
                 .unwrap_or_else(|| String::from("-"));
             Line::from(vec![Span::from("  └─ "), Span::from(category)])
         }
@@ -383,8 +463,9 @@ fn build_multiline_cell(
     transaction: &Transaction,
     column: &str,
     budget: Option<&BudgetSummary>,
+    display: &DisplayConfig,
 ) -> Text<'static> {
-    let mut lines = vec![build_parent_line(transaction, column, budget)];
+    let mut lines = vec![build_parent_line(transaction, column, budget, display)];
 
     for subtransaction in transaction
         .subtransactions
@@ -397,6 +478,7 @@ fn build_multiline_cell(
             subtransaction,
             column,
             budget,
+            display,
         ));
     }
 
@@ -405,19 +487,62 @@ fn build_multiline_cell(
 
 fn build_transaction_row(
     transaction: &Transaction,
+    state: &TransactionsState,
+    balances: &HashMap<String, i64>,
     budget: Option<&BudgetSummary>,
+    display: &DisplayConfig,
 ) -> Row<'static> {
     let row_height = calculate_row_height(transaction);
 
     // Build multi-line content for each column
-    let flag_cell = build_multiline_cell(transaction, "flag", budget);
-    let date_cell = build_multiline_cell(transaction, "date", budget);
-    let payee_cell = build_multiline_cell(transaction, "payee", budget);
-    let category_cell = build_multiline_cell(transaction, "category", budget);
-    let memo_cell = build_multiline_cell(transaction, "memo", budget);
-    let amount_cell = build_multiline_cell(transaction, "amount", budget);
-    let approved_cell = build_multiline_cell(transaction, "approved", budget);
-    let cleared_cell = build_multiline_cell(transaction, "cleared", budget);
+    let flag_cell = build_multiline_cell(transaction, "flag", budget, display);
+    let date_cell = build_multiline_cell(transaction, "date", budget, display);
+    let mut payee_cell = build_multiline_cell(transaction, "payee", budget, display);
+    if state.all_accounts {
+        // Search results mix accounts, so say which one each row is in
+        if let Some(line) = payee_cell.lines.first_mut() {
+            line.spans.insert(
+                0,
+                Span::styled(
+                    format!("{} · ", transaction.account_name),
+                    Style::default().fg(theme::COLOR_ZERO),
+                ),
+            );
+        }
+    }
+    let mut category_cell = build_multiline_cell(transaction, "category", budget, display);
+    if let Some(suggestion) = state.suggested_category(transaction) {
+        // Offered in place of the "-" until accepted with Tab
+        category_cell = Text::from(Span::styled(
+            t("transactions-suggested-category").replace(
+                "{category}",
+                display.category_name(&suggestion.category_name),
+            ),
+            theme::help_text_style().italic(),
+        ));
+    }
+    let mut memo_cell = build_multiline_cell(transaction, "memo", budget, display);
+    if state
+        .awaiting_reimbursement
+        .contains(&transaction.id.to_string())
+    {
+        if let Some(line) = memo_cell.lines.first_mut() {
+            line.spans.insert(
+                0,
+                Span::styled("↺ ", Style::default().fg(theme::COLOR_LOADING)),
+            );
+        }
+    }
+    let amount_cell = build_multiline_cell(transaction, "amount", budget, display);
+    let approved_cell = build_multiline_cell(transaction, "approved", budget, display);
+    let cleared_cell = build_multiline_cell(transaction, "cleared", budget, display);
+    let balance_cell = match balances.get(&transaction.id.to_string()) {
+        Some(&balance) => Text::from(
+            Span::from(utils::format_amount(balance, budget))
+                .style(Style::default().fg(utils::get_amount_color(balance))),
+        ),
+        None => Text::default(),
+    };
 
     // Row styling (bold if unapproved)
     let row_style = if transaction.approved {
@@ -435,6 +560,7 @@ fn build_transaction_row(
         Cell::from(amount_cell.right_aligned()),
         Cell::from(approved_cell),
         Cell::from(cleared_cell),
+        Cell::from(balance_cell.right_aligned()),
     ])
     .style(row_style)
     .height(row_height)
@@ -530,6 +656,145 @@ fn render_balance_summary(
     );
 }
 
+/// The selected transaction's category available and account working balance,
+/// for context while categorizing
+fn render_selection_context(
+    f: &mut Frame,
+    area: Rect,
+    state: &TransactionsState,
+    transaction: &Transaction,
+    budget: Option<&BudgetSummary>,
+) {
+    let mut spans = Vec::new();
+
+    // Splits and transfers have no single category to show
+    let category_balance = transaction.category_id.and_then(|id| {
+        state.category_balances.get(&id.to_string()).map(|balance| {
+            (
+                transaction.category_name.as_deref().unwrap_or_default(),
+                *balance,
+            )
+        })
+    });
+    if let Some((name, balance)) = category_balance {
+        spans.extend(context_spans(
+            t("selection-category-available"),
+            name,
+            balance,
+            budget,
+        ));
+        spans.push(Span::styled(" · ", theme::help_text_style()));
+    }
+
+    let working_balance: i64 = state
+        .transactions
+        .iter()
+        .filter(|t| t.account_id == transaction.account_id)
+        .map(|t| i64::from(t.amount))
+        .sum();
+    spans.extend(context_spans(
+        t("selection-account-working"),
+        &transaction.account_name,
+        working_balance,
+        budget,
+    ));
+
+    // An import that looks like something already entered by hand
+    let loaded: Vec<&Transaction> = state.transactions.iter().collect();
+    if let Some(candidate) = crate::utils::imports::suggested_match(transaction, &loaded) {
+        spans.push(Span::styled(" · ", theme::help_text_style()));
+        spans.push(Span::styled(
+            t("selection-possible-match")
+                .replace(
+                    "{payee}",
+                    candidate.payee_name.as_deref().unwrap_or_default(),
+                )
+                .replace("{date}", &candidate.date.to_string()),
+            Style::default().fg(theme::COLOR_HEADER),
+        ));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// `template` with `{name}` filled in and `{amount}` as a colored span
+fn context_spans(
+    template: &str,
+    name: &str,
+    amount: i64,
+    budget: Option<&BudgetSummary>,
+) -> Vec<Span<'static>> {
+    let template = template.replace("{name}", name);
+    let (before, after) = template.split_once("{amount}").unwrap_or((&template, ""));
+    vec![
+        Span::raw(before.to_string()),
+        Span::styled(
+            utils::format_amount(amount, budget).trim().to_string(),
+            Style::default()
+                .fg(utils::get_amount_color(amount))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(after.to_string()),
+    ]
+}
+
+/// Active quick filters as `[1 Unapproved]` chips, after the day picked on the heatmap
+fn render_preset_chips(
+    f: &mut Frame,
+    area: Rect,
+    state: &TransactionsState,
+    display: &DisplayConfig,
+) {
+    // The flag view's chip takes the flag's color
+    let flag_chip = state.flag_view.map(|flag| {
+        let name = display
+            .flag_names
+            .get(flag)
+            .unwrap_or_else(|| flag_label(flag));
+        Span::styled(
+            format!(" f ⚑ {} ", name),
+            theme::chip_style().fg(utils::flag_color_to_ratatui_color(&flag)),
+        )
+    });
+    let date_chip = state
+        .on_date
+        .map(|date| format!(" {} ", date.format("%Y-%m-%d")));
+    let labels = date_chip.into_iter().chain(
+        state
+            .presets
+            .iter()
+            .map(|preset| format!(" {} {} ", preset.key(), preset_label(*preset))),
+    );
+    let chips: Vec<Span> = flag_chip
+        .into_iter()
+        .chain(labels.map(|label| Span::styled(label, theme::chip_style())))
+        .flat_map(|chip| [chip, Span::raw(" ")])
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(chips)), area);
+}
+
+fn flag_label(flag: FlagColor) -> &'static str {
+    match flag {
+        FlagColor::Red => t("flag-red"),
+        FlagColor::Orange => t("flag-orange"),
+        FlagColor::Yellow => t("flag-yellow"),
+        FlagColor::Green => t("flag-green"),
+        FlagColor::Blue => t("flag-blue"),
+        FlagColor::Purple => t("flag-purple"),
+    }
+}
+
+fn preset_label(preset: TransactionPreset) -> &'static str {
+    match preset {
+        TransactionPreset::Unapproved => t("preset-unapproved"),
+        TransactionPreset::Uncleared => t("preset-uncleared"),
+        TransactionPreset::ThisMonth => t("preset-this-month"),
+        TransactionPreset::Flagged => t("preset-flagged"),
+        TransactionPreset::Splits => t("preset-splits"),
+        TransactionPreset::Imported => t("preset-imported"),
+    }
+}
+
 fn render_balance_card(f: &mut Frame, area: Rect, amount: &str, label: &str, color: Color) {
     let block = Block::default().borders(Borders::ALL).title(label);
     let inner = block.inner(area);
@@ -542,3 +807,488 @@ fn render_balance_card(f: &mut Frame, area: Rect, amount: &str, label: &str, col
     .alignment(Alignment::Center);
     f.render_widget(paragraph, inner);
 }
+
+impl ScreenController for TransactionsState {
+    fn render(&self, f: &mut Frame, ctx: &RenderContext) {
+        render(f, self, ctx.budget, &ctx.config.display);
+
+        // Render delete confirmation popup if active
+        if self.input_mode == InputMode::DeleteConfirmation {
+            if let Some(ref transaction_id) = self.delete_confirmation_transaction_id {
+                // Find the transaction to show details
+                if self
+                    .transactions
+                    .iter()
+                    .any(|t| t.id.to_string() == *transaction_id)
+                {
+                    delete_confirmation::render_delete_confirmation(f);
+                }
+            }
+        }
+
+        // Render reconciled edit confirmation popup if active
+        if self.input_mode == InputMode::ReconciledEditConfirmation {
+            if let Some(ref transaction_id) = self.reconciled_edit_transaction_id {
+                // Find the transaction to show details
+                if self
+                    .transactions
+                    .iter()
+                    .any(|t| t.id.to_string() == *transaction_id)
+                {
+                    reconciled_edit_confirmation::render_reconciled_edit_confirmation(f);
+                }
+            }
+        }
+
+        // Render reconcile confirmation popup if active
+        if self.input_mode == InputMode::ReconcileConfirmation {
+            if let Some(cleared_balance) = self.reconcile_cleared_balance {
+                let currency_format = ctx.budget.and_then(|b| b.currency_format.as_ref());
+                reconcile_confirmation::render_reconcile_confirmation(
+                    f,
+                    cleared_balance,
+                    currency_format,
+                );
+            }
+        }
+
+        // A held-back server change goes over everything, even the edit form
+        if let Some(conflict) = self.conflicts.first() {
+            delta_conflict::render_delta_conflict(
+                f,
+                conflict,
+                self.conflicts.len() - 1,
+                ctx.budget,
+            );
+        }
+    }
+
+    fn handle_key(&self, event: KeyEvent, state: &AppState) -> Option<AppCommand> {
+        let key = event.key;
+        match key {
+            // Per-row action menu
+            Key::Char(' ' | 'o') => Some(AppCommand::OpenContextMenu),
+            Key::Char('n') => Some(AppCommand::EnterTransactionCreateMode),
+            Key::Backspace | Key::Char('d') => {
+                // Delete transaction - only in Normal mode with a valid selection
+                if self.input_mode == InputMode::Normal {
+                    let selected_idx = self.table_state.borrow().selected()?;
+                    let filtered_transactions = self.filtered_transactions();
+
+                    if selected_idx < filtered_transactions.len() {
+                        let transaction = filtered_transactions[selected_idx];
+                        if !state.budget_settings.confirm_delete {
+                            return Some(AppCommand::ConfirmTransactionDelete {
+                                transaction_id: transaction.id.to_string(),
+                                budget_id: state.current_budget_id.clone()?,
+                            });
+                        }
+                        Some(AppCommand::InitiateTransactionDelete {
+                            transaction_id: transaction.id.to_string(),
+                        })
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+            Key::Char('a') => {
+                let Some(budget_id) = &state.current_budget_id else {
+                    return None;
+                };
+                if self.input_mode == InputMode::Normal {
+                    let selected_idx = self.table_state.borrow().selected()?;
+                    let filtered_transactions = self.filtered_transactions();
+
+                    if selected_idx < filtered_transactions.len() {
+                        let transaction = filtered_transactions[selected_idx];
+                        if transaction.approved {
+                            return None;
+                        }
+
+                        Some(AppCommand::ApproveTransaction {
+                            budget_id: budget_id.clone(),
+                            transaction_id: transaction.id.to_string(),
+                        })
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+            Key::Char('A') if self.input_mode == InputMode::Normal => {
+                // Approve everything listed that isn't approved yet
+                let transaction_ids = self
+                    .filtered_transactions()
+                    .iter()
+                    .filter(|t| !t.approved)
+                    .map(|t| t.id.to_string())
+                    .collect();
+                Some(AppCommand::ApproveTransactions {
+                    budget_id: state.current_budget_id.clone()?,
+                    transaction_ids,
+                })
+            }
+            Key::Char('B') if self.input_mode == InputMode::Normal => {
+                // Categorize by the payee rules in the config
+                Some(AppCommand::ApplyPayeeRules {
+                    budget_id: state.current_budget_id.clone()?,
+                })
+            }
+            Key::Char('I') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::ImportLinkedTransactions {
+                    budget_id: state.current_budget_id.clone()?,
+                    account_id: if self.all_accounts {
+                        None
+                    } else {
+                        state.current_account_id.clone()
+                    },
+                })
+            }
+            Key::Char('e') => {
+                // Edit transaction - only in Normal mode with a valid selection
+                if self.input_mode == InputMode::Normal {
+                    let selected_idx = self.table_state.borrow().selected()?;
+                    let filtered_transactions = self.filtered_transactions();
+
+                    if selected_idx < filtered_transactions.len() {
+                        let transaction = filtered_transactions[selected_idx];
+                        Some(AppCommand::InitiateTransactionEdit {
+                            transaction_id: transaction.id.to_string(),
+                        })
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+            Key::Char('C') if self.input_mode == InputMode::Normal => {
+                // Edit straight into the category; splits and transfers have none to set
+                let selected_idx = self.table_state.borrow().selected()?;
+                self.filtered_transactions()
+                    .get(selected_idx)
+                    .filter(|t| t.subtransactions.is_empty() && t.transfer_account_id.is_none())
+                    .filter(|t| t.cleared != ReconciliationStatus::Reconciled)
+                    .map(|transaction| AppCommand::CategorizeTransaction {
+                        transaction_id: transaction.id.to_string(),
+                    })
+            }
+            Key::Tab if self.input_mode == InputMode::Normal => {
+                // Take the category suggested from the payee's history
+                let selected_idx = self.table_state.borrow().selected()?;
+                let transaction = *self.filtered_transactions().get(selected_idx)?;
+                self.suggested_category(transaction)?;
+                Some(AppCommand::AcceptCategorySuggestion {
+                    budget_id: state.current_budget_id.clone()?,
+                    transaction_id: transaction.id.to_string(),
+                })
+            }
+            Key::Char('/') => Some(AppCommand::EnterFilterMode),
+            Key::Up | Key::Char('k') => Some(AppCommand::SelectPrevious),
+            Key::Down | Key::Char('j') => {
+                // Moving past the last row pages in older history
+                if self.wants_older_history() {
+                    if let (Some(budget_id), Some(account_id)) =
+                        (&state.current_budget_id, &state.current_account_id)
+                    {
+                        return Some(AppCommand::LoadOlderTransactions {
+                            budget_id: budget_id.clone(),
+                            account_id: account_id.clone(),
+                        });
+                    }
+                }
+                Some(AppCommand::SelectNext)
+            }
+            Key::Char('c') => {
+                // Toggle cleared status of selected transaction
+                if let Some(budget_id) = &state.current_budget_id {
+                    let selected_idx = self.table_state.borrow().selected()?;
+                    let filtered_transactions = self.filtered_transactions();
+
+                    if selected_idx < filtered_transactions.len() {
+                        let transaction = filtered_transactions[selected_idx];
+                        if transaction.cleared != ReconciliationStatus::Reconciled {
+                            Some(AppCommand::ToggleTransactionCleared {
+                                transaction_id: transaction.id.to_string(),
+                                budget_id: budget_id.clone(),
+                            })
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+            Key::Char('r') => {
+                // Force refresh transactions
+                if let Some(budget_id) = &state.current_budget_id {
+                    if self.inbox {
+                        return Some(AppCommand::LoadInbox {
+                            budget_id: budget_id.clone(),
+                        });
+                    }
+                    if self.all_accounts {
+                        return Some(AppCommand::LoadBudgetTransactions {
+                            budget_id: budget_id.clone(),
+                        });
+                    }
+                    state.current_account_id.as_ref().map(|account_id| {
+                        AppCommand::LoadTransactions {
+                            budget_id: budget_id.clone(),
+                            account_id: account_id.clone(),
+                            force_refresh: true,
+                        }
+                    })
+                } else {
+                    None
+                }
+            }
+            Key::Char('.') => Some(AppCommand::ToggleShowReconciledTransactions),
+            Key::Char(c @ '1'..='6') if self.input_mode == InputMode::Normal => {
+                TransactionPreset::from_key(c).map(AppCommand::ToggleTransactionPreset)
+            }
+            Key::Char('s') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::CycleTransactionSort)
+            }
+            Key::Char('f') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::CycleFlagView)
+            }
+            Key::Char('X') if self.input_mode == InputMode::Normal => {
+                // Done with the flagged transactions listed: clear their flags
+                let transaction_ids = self
+                    .filtered_transactions()
+                    .iter()
+                    .filter(|t| t.flag_color.is_some())
+                    .map(|t| t.id.to_string())
+                    .collect();
+                Some(AppCommand::ClearTransactionFlags {
+                    budget_id: state.current_budget_id.clone()?,
+                    transaction_ids,
+                })
+            }
+            Key::Char('v') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::ReverseTransactionSort)
+            }
+            Key::Char('i') if state.config.debug.inspector => {
+                // Inspect the raw API payload for the selected transaction
+                let selected_idx = self.table_state.borrow().selected()?;
+                self.filtered_transactions()
+                    .get(selected_idx)
+                    .map(|transaction| AppCommand::NavigateToInspector {
+                        focus_id: Some(transaction.id.to_string()),
+                    })
+            }
+            Key::Char('y') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::SetPendingKey('y'))
+            }
+            Key::Char('p' | 'P') if self.input_mode == InputMode::Normal => {
+                // Paste a copy of the yanked transaction into this account
+                state.yanked_transaction.as_ref()?;
+                Some(AppCommand::PasteTransaction {
+                    budget_id: state.current_budget_id.clone()?,
+                    account_id: state.current_account_id.clone()?,
+                    open_form: key == Key::Char('P'),
+                })
+            }
+            Key::Char('F') => {
+                if self.input_mode != InputMode::Normal {
+                    return None;
+                }
+                let budget_id = state.current_budget_id.as_ref()?;
+                let selected_idx = self.table_state.borrow().selected()?;
+                self.filtered_transactions()
+                    .get(selected_idx)
+                    .map(|transaction| AppCommand::CycleTransactionFlag {
+                        budget_id: budget_id.clone(),
+                        transaction_id: transaction.id.to_string(),
+                    })
+            }
+            Key::Char('t') => {
+                // Jump to the other side of a transfer
+                if self.input_mode != InputMode::Normal {
+                    return None;
+                }
+                let budget_id = state.current_budget_id.as_ref()?;
+                let selected_idx = self.table_state.borrow().selected()?;
+                let transaction = *self.filtered_transactions().get(selected_idx)?;
+                transaction
+                    .transfer_account_id
+                    .map(|account_id| AppCommand::JumpToTransfer {
+                        budget_id: budget_id.clone(),
+                        account_id: account_id.to_string(),
+                        transaction_id: transaction
+                            .transfer_transaction_id
+                            .as_ref()
+                            .map(|id| id.to_string()),
+                    })
+            }
+            Key::Char('E') if self.input_mode == InputMode::Normal => {
+                Some(AppCommand::ExportTransactions)
+            }
+            Key::Char('T') => {
+                // Hand the selected transaction to the configured follow-up command
+                if self.input_mode != InputMode::Normal || state.config.follow_up.command.is_empty()
+                {
+                    return None;
+                }
+                let selected_idx = self.table_state.borrow().selected()?;
+                self.filtered_transactions()
+                    .get(selected_idx)
+                    .map(|transaction| AppCommand::CreateFollowUp {
+                        transaction_id: transaction.id.to_string(),
+                    })
+            }
+            Key::Char('L') => {
+                // The selected transaction in full, with where its payee is
+                if self.input_mode != InputMode::Normal {
+                    return None;
+                }
+                let budget_id = state.current_budget_id.clone()?;
+                let selected_idx = self.table_state.borrow().selected()?;
+                let transaction = self.filtered_transactions().get(selected_idx).copied()?;
+                Some(AppCommand::ShowTransactionDetail {
+                    budget_id,
+                    transaction_id: transaction.id.to_string(),
+                })
+            }
+            Key::Char('S') => {
+                // Upcoming scheduled transactions for this account
+                if self.input_mode != InputMode::Normal {
+                    return None;
+                }
+                state.current_budget_id.as_ref().map(|budget_id| {
+                    AppCommand::NavigateToScheduledTransactions {
+                        budget_id: budget_id.clone(),
+                        account_id: state.current_account_id.clone(),
+                    }
+                })
+            }
+            Key::Char('$') => {
+                // Track an outflow as awaiting reimbursement (or stop tracking it)
+                if self.input_mode != InputMode::Normal {
+                    return None;
+                }
+                let budget_id = state.current_budget_id.as_ref()?;
+                let selected_idx = self.table_state.borrow().selected()?;
+                self.filtered_transactions()
+                    .get(selected_idx)
+                    .filter(|transaction| transaction.amount.is_negative())
+                    .map(|transaction| AppCommand::ToggleAwaitingReimbursement {
+                        budget_id: budget_id.clone(),
+                        entry: ReimbursementEntry::from_transaction(transaction),
+                    })
+            }
+            Key::Char('R') => {
+                // Initiate reconciliation against the account's cleared balance,
+                // adding up the loaded transactions only when it isn't known
+                if self.input_mode == InputMode::Normal && !self.all_accounts {
+                    use ynab_api::endpoints::Milliunits;
+                    let cleared_balance = self.account_cleared_balance.unwrap_or_else(|| {
+                        self.transactions
+                            .iter()
+                            .filter(|t| {
+                                matches!(
+                                    t.cleared,
+                                    ReconciliationStatus::Cleared
+                                        | ReconciliationStatus::Reconciled
+                                )
+                            })
+                            .map(|t| t.amount)
+                            .sum::<Milliunits>()
+                            .into()
+                    });
+                    Some(AppCommand::InitiateReconcile { cleared_balance })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn loading_state(&self) -> Option<&LoadingState> {
+        Some(&self.transactions_loading)
+    }
+
+    fn loading_state_mut(&mut self) -> Option<&mut LoadingState> {
+        Some(&mut self.transactions_loading)
+    }
+
+    fn help_items(&self, config: &AppConfig) -> Vec<(&'static str, &'static str)> {
+        let mut items = vec![
+            ("↑/k", t("help-move-up")),
+            ("↓/j", t("help-move-down")),
+            ("n", t("help-new-transaction")),
+            ("e", t("help-edit-transaction")),
+            ("C", t("help-categorize-transaction")),
+            ("Tab", t("help-accept-suggestion")),
+            ("a", t("help-approve-transaction")),
+            ("A", t("help-approve-listed")),
+            ("I", t("help-import-linked")),
+            ("c", t("help-toggle-cleared")),
+            ("d/Backspace", t("help-delete-transaction")),
+            ("y then y", t("help-yank-transaction")),
+            ("p", t("help-paste-transaction")),
+            ("P", t("help-paste-transaction-edit")),
+            ("F", t("help-cycle-flag")),
+            ("f", t("help-flag-view")),
+            ("X", t("help-clear-flags")),
+            ("t", t("help-jump-to-transfer")),
+            ("Space/o", t("help-context-menu")),
+            ("/", t("help-enter-filter")),
+        ];
+        if self.input_mode == InputMode::Filter {
+            items.push(("Type", t("help-filter-transactions")));
+            items.push(("since:, >50", t("help-filter-tokens")));
+            items.push(("Enter", t("help-filter-exit")));
+            items.push(("Esc", t("help-filter-clear")));
+            items.push(("Backspace", t("help-filter-backspace")));
+        }
+        items.push((".", t("help-toggle-reconciled")));
+        items.push(("1-6", t("help-toggle-presets")));
+        items.push(("s", t("help-cycle-sort")));
+        items.push(("v", t("help-reverse-sort")));
+        items.push(("r", t("help-refresh-transactions")));
+        items.push(("R", t("help-reconcile")));
+        items.push(("$", t("help-toggle-reimbursement")));
+        items.push(("S", t("help-scheduled")));
+        items.push(("E", t("help-export-csv")));
+        if !config.follow_up.command.is_empty() {
+            items.push(("T", t("help-follow-up")));
+        }
+        items.push(("L", t("help-payee-location")));
+        if !config.rules.is_empty() {
+            items.push(("B", t("help-apply-payee-rules")));
+        }
+        if config.debug.inspector {
+            items.push(("i", t("help-inspect")));
+        }
+        items
+    }
+
+    fn select_next(&mut self) {
+        Scrollable::select_next(self);
+    }
+
+    fn select_previous(&mut self) {
+        Scrollable::select_prev(self);
+    }
+
+    fn select_by(&mut self, rows: isize) {
+        Scrollable::select_by(self, rows);
+    }
+
+    fn select_first(&mut self) {
+        Scrollable::select_first(self);
+    }
+
+    fn select_last(&mut self) {
+        Scrollable::select_last(self);
+    }
+}
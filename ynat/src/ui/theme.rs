@@ -5,6 +5,8 @@
 
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::config::ColorThreshold;
+
 // =============================================================================
 // Colors
 // =============================================================================
@@ -33,6 +35,12 @@ pub const COLOR_TITLE: Color = Color::Cyan;
 /// Color for loading/status messages
 pub const COLOR_LOADING: Color = Color::Yellow;
 
+/// Color for the star on favorite accounts and categories
+pub const COLOR_FAVORITE: Color = Color::Yellow;
+
+/// Marks a favorite account or category
+pub const FAVORITE_GLYPH: &str = "★";
+
 /// Border color for danger/warning popups (delete confirmations)
 pub const COLOR_BORDER_DANGER: Color = Color::Red;
 
@@ -73,6 +81,9 @@ pub const SIDEBAR_WIDTH: u16 = 16;
 /// Height of summary cards
 pub const SUMMARY_CARD_HEIGHT: u16 = 3;
 
+/// Height of the plan's month summary (three bars inside a border)
+pub const MONTH_SUMMARY_HEIGHT: u16 = 5;
+
 // =============================================================================
 // Style Functions
 // =============================================================================
@@ -139,6 +150,11 @@ pub fn accent_border_style() -> Style {
     Style::default().fg(COLOR_BORDER_ACCENT)
 }
 
+/// Style for toggled filter chips
+pub fn chip_style() -> Style {
+    Style::default().fg(Color::Black).bg(COLOR_TITLE)
+}
+
 // =============================================================================
 // Amount Color Helper
 // =============================================================================
@@ -155,14 +171,38 @@ pub fn amount_color(amount: i64) -> Color {
     }
 }
 
-/// Get the appropriate color for a float amount value.
-/// Positive = green, negative = red, zero = gray
-pub fn amount_color_f64(amount: f64) -> Color {
-    if amount > 0.0 {
-        COLOR_POSITIVE
-    } else if amount < 0.0 {
-        COLOR_NEGATIVE
-    } else {
-        COLOR_ZERO
+/// Color for an amount (milliunits) using configured thresholds, falling back
+/// to [`amount_color`] when none applies
+pub fn threshold_color(amount: i64, thresholds: &[ColorThreshold]) -> Color {
+    thresholds
+        .iter()
+        .filter(|threshold| (amount as f64) < threshold.below * 1000.0)
+        .min_by(|a, b| a.below.total_cmp(&b.below))
+        .map(|threshold| threshold.color)
+        .unwrap_or_else(|| amount_color(amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowest_matching_threshold_wins() {
+        let thresholds = [
+            ColorThreshold {
+                below: 20.0,
+                color: Color::Yellow,
+            },
+            ColorThreshold {
+                below: 0.0,
+                color: Color::Magenta,
+            },
+        ];
+
+        assert_eq!(threshold_color(-5_000, &thresholds), Color::Magenta);
+        assert_eq!(threshold_color(0, &thresholds), Color::Yellow);
+        assert_eq!(threshold_color(19_990, &thresholds), Color::Yellow);
+        assert_eq!(threshold_color(20_000, &thresholds), COLOR_POSITIVE);
+        assert_eq!(threshold_color(0, &[]), COLOR_ZERO);
     }
 }
@@ -4,14 +4,9 @@ use ynab_api::endpoints::{
 };
 
 use super::theme;
-
-pub fn fmt_dollars(amount: f64) -> Span<'static> {
-    if amount >= 0.0 {
-        Span::from(format!(" ${:.2}", amount))
-    } else {
-        Span::from(format!("-${:.2}", amount.abs()))
-    }
-}
+use crate::utils::money;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 pub fn flag_color_to_ratatui_color(color: &FlagColor) -> Color {
     match color {
@@ -24,71 +19,17 @@ pub fn flag_color_to_ratatui_color(color: &FlagColor) -> Color {
     }
 }
 
-/// Format currency using the budget's currency format
+/// Format currency using the budget's currency format, with a leading
+/// space in place of the minus sign so columns of amounts line up
 pub fn fmt_currency(amount: i64, currency_format: &CurrencyFormat) -> Span<'static> {
-    // YNAB amounts are in milliunits (1000 = 1.00)
-    let amount_float = amount as f64 / 1000.0;
-    let is_negative = amount_float < 0.0;
-    let abs_amount = amount_float.abs();
-
-    // Format the number with the appropriate decimal digits
-    let formatted_number = format_number_with_separators(
-        abs_amount,
-        currency_format.decimal_digits as usize,
-        &currency_format.decimal_separator,
-        &currency_format.group_separator,
-    );
-
-    // Build the final string based on currency format preferences
-    let result = if currency_format.display_symbol {
-        if currency_format.symbol_first {
-            if is_negative {
-                format!("-{}{}", currency_format.currency_symbol, formatted_number)
-            } else {
-                format!(" {}{}", currency_format.currency_symbol, formatted_number)
-            }
-        } else if is_negative {
-            format!("-{}{}", formatted_number, currency_format.currency_symbol)
-        } else {
-            format!(" {}{}", formatted_number, currency_format.currency_symbol)
-        }
-    } else if is_negative {
-        format!("-{}", formatted_number)
-    } else {
-        format!(" {}", formatted_number)
-    };
-
-    Span::from(result)
+    Span::from(pad_sign(money::format(amount, Some(currency_format))))
 }
 
-/// Format a number with thousands separators and decimal separator
-fn format_number_with_separators(
-    amount: f64,
-    decimal_digits: usize,
-    decimal_separator: &str,
-    group_separator: &str,
-) -> String {
-    // Split into integer and decimal parts
-    let integer_part = amount.floor() as i64;
-    let decimal_part =
-        ((amount - amount.floor()) * 10_f64.powi(decimal_digits as i32)).round() as i64;
-
-    // Format integer part with group separators
-    let integer_str = integer_part.to_string();
-    let mut formatted_integer = String::new();
-    for (i, c) in integer_str.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            formatted_integer.insert(0, group_separator.chars().next().unwrap_or(','));
-        }
-        formatted_integer.insert(0, c);
-    }
-
-    // Format decimal part
-    if decimal_digits > 0 {
-        let decimal_str = format!("{:0width$}", decimal_part, width = decimal_digits);
-        format!("{}{}{}", formatted_integer, decimal_separator, decimal_str)
+fn pad_sign(formatted: String) -> String {
+    if formatted.starts_with('-') {
+        formatted
     } else {
-        formatted_integer
+        format!(" {}", formatted)
     }
 }
 
@@ -172,13 +113,85 @@ pub fn get_date_separator(format: &str) -> char {
 /// Format an amount using the budget's currency format, or fallback to dollars.
 /// This consolidates the duplicate format_amount functions from screens.
 pub fn format_amount(amount: i64, budget: Option<&BudgetSummary>) -> String {
-    if let Some(budget) = budget {
-        if let Some(ref currency_format) = budget.currency_format {
-            return fmt_currency(amount, currency_format).content.into();
+    pad_sign(money::format(
+        amount,
+        budget.and_then(|b| b.currency_format.as_ref()),
+    ))
+}
+
+// =============================================================================
+// Text Width
+// =============================================================================
+
+/// Truncate text to fit `max_width` terminal columns, ending with '…' when cut.
+/// Works on grapheme clusters so emoji (including ZWJ sequences and
+/// variation selectors) are never split and their double width is respected.
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut result = String::new();
+    let mut width = 0;
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        // Leave one column for the ellipsis
+        if width + grapheme_width > max_width - 1 {
+            break;
         }
+        result.push_str(grapheme);
+        width += grapheme_width;
+    }
+    result.push('…');
+    result
+}
+
+// =============================================================================
+// Sparklines
+// =============================================================================
+
+const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Render a series of values as a single-line sparkline of at most `width` characters.
+/// Values are bucketed (last value of each bucket) so long series fit narrow columns.
+pub fn sparkline_text(values: &[i64], width: usize) -> String {
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+
+    let bucket_size = values.len().div_ceil(width);
+    let points: Vec<i64> = values
+        .chunks(bucket_size)
+        .filter_map(|chunk| chunk.last().copied())
+        .collect();
+
+    let min = *points.iter().min().unwrap_or(&0);
+    let max = *points.iter().max().unwrap_or(&0);
+    let range = (max - min) as f64;
+
+    points
+        .iter()
+        .map(|&value| {
+            if range == 0.0 {
+                SPARKLINE_BARS[SPARKLINE_BARS.len() / 2]
+            } else {
+                let level = ((value - min) as f64 / range * (SPARKLINE_BARS.len() - 1) as f64)
+                    .round() as usize;
+                SPARKLINE_BARS[level]
+            }
+        })
+        .collect()
+}
+
+/// Get the trend color for a series: green if it ended higher than it started, red if lower
+pub fn trend_color(values: &[i64]) -> Color {
+    match (values.first(), values.last()) {
+        (Some(first), Some(last)) => theme::amount_color(last - first),
+        _ => theme::COLOR_ZERO,
     }
-    let amount_f64 = amount as f64 / 1000.0;
-    fmt_dollars(amount_f64).content.into()
 }
 
 /// Get the appropriate color for an amount value.
@@ -187,8 +200,30 @@ pub fn get_amount_color(amount: i64) -> Color {
     theme::amount_color(amount)
 }
 
-/// Get the appropriate color for a float amount value.
-/// Re-exports from theme for convenience.
-pub fn get_amount_color_f64(amount: f64) -> Color {
-    theme::amount_color_f64(amount)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_to_width_keeps_short_text() {
+        assert_eq!(truncate_to_width("Rent", 10), "Rent");
+    }
+
+    #[test]
+    fn truncate_to_width_counts_emoji_as_double_width() {
+        // "🛒" is two columns wide, so only one more column fits before the ellipsis
+        assert_eq!(truncate_to_width("🛒 Groceries", 4), "🛒 …");
+    }
+
+    #[test]
+    fn truncate_to_width_never_splits_graphemes() {
+        // Family emoji is a single grapheme made of several code points
+        assert_eq!(truncate_to_width("👨‍👩‍👧 Family", 2), "…");
+    }
+
+    #[test]
+    fn sparkline_text_buckets_to_width() {
+        assert_eq!(sparkline_text(&[0, 0, 10, 10], 2), "▁█");
+        assert_eq!(sparkline_text(&[5, 5, 5], 3), "▅▅▅");
+    }
 }
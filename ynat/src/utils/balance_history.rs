@@ -0,0 +1,166 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use ynab_api::endpoints::transactions::Transaction;
+
+/// Number of days covered by the account balance sparkline
+pub const BALANCE_HISTORY_DAYS: usize = 90;
+
+/// Reconstruct end-of-day balances for the last `days` days, oldest first.
+///
+/// Works backwards from the account's current balance by undoing each
+/// transaction dated after the given day, so only the transactions inside the
+/// window (and any future-dated ones) need to be present.
+pub fn daily_balances(
+    current_balance: i64,
+    transactions: &[Transaction],
+    today: NaiveDate,
+    days: usize,
+) -> Vec<i64> {
+    if days == 0 {
+        return Vec::new();
+    }
+
+    let mut balances = vec![0i64; days];
+    let mut balance = current_balance;
+
+    // Undo anything dated after today first (scheduled/future transactions)
+    balance -= transactions
+        .iter()
+        .filter(|t| !t.deleted && t.date > today)
+        .map(|t| i64::from(t.amount))
+        .sum::<i64>();
+
+    for offset in 0..days {
+        let day = today - Duration::days(offset as i64);
+        balances[days - 1 - offset] = balance;
+
+        // Step to the end of the previous day
+        balance -= transactions
+            .iter()
+            .filter(|t| !t.deleted && t.date == day)
+            .map(|t| i64::from(t.amount))
+            .sum::<i64>();
+    }
+
+    balances
+}
+
+/// Sum of the transactions dated this month up to `today`: how much the
+/// account has gained or lost so far this month
+pub fn month_net_change(transactions: &[Transaction], today: NaiveDate) -> i64 {
+    let month_start = crate::reports::first_of_month(today);
+    transactions
+        .iter()
+        .filter(|t| !t.deleted && t.date >= month_start && t.date <= today)
+        .map(|t| i64::from(t.amount))
+        .sum()
+}
+
+/// Money spent today and so far this week (from Monday), as positive amounts
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RecentSpending {
+    pub today: i64,
+    pub week: i64,
+}
+
+impl RecentSpending {
+    /// Add an account's outflows dated this week up to `today`. Transfers
+    /// only move money between accounts and inflows aren't spending, so
+    /// neither counts.
+    pub fn add(&mut self, transactions: &[Transaction], today: NaiveDate) {
+        let week_start = today - Duration::days(today.weekday().num_days_from_monday().into());
+        for t in transactions.iter().filter(|t| {
+            !t.deleted && t.transfer_account_id.is_none() && t.date >= week_start && t.date <= today
+        }) {
+            let outflow = (-i64::from(t.amount)).max(0);
+            self.week += outflow;
+            if t.date == today {
+                self.today += outflow;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn daily_balances_without_transactions_is_flat() {
+        assert_eq!(
+            daily_balances(5000, &[], date("2025-01-10"), 3),
+            vec![5000, 5000, 5000]
+        );
+    }
+
+    #[test]
+    fn daily_balances_walks_back_from_current_balance() {
+        let transactions = vec![
+            transaction("2025-01-10", -2000).build(),
+            transaction("2025-01-09", 10000).build(),
+        ];
+        assert_eq!(
+            daily_balances(18000, &transactions, date("2025-01-10"), 3),
+            vec![10000, 20000, 18000]
+        );
+    }
+
+    #[test]
+    fn daily_balances_ignores_future_and_deleted_transactions() {
+        let mut deleted = transaction("2025-01-10", 99000).build();
+        deleted.deleted = true;
+        let transactions = vec![transaction("2025-01-12", 1000).build(), deleted];
+        assert_eq!(
+            daily_balances(6000, &transactions, date("2025-01-10"), 2),
+            vec![5000, 5000]
+        );
+    }
+
+    #[test]
+    fn month_net_change_counts_this_month_up_to_today() {
+        let mut deleted = transaction("2025-01-05", -7000).build();
+        deleted.deleted = true;
+        let transactions = vec![
+            transaction("2024-12-31", -50000).build(),
+            transaction("2025-01-01", 10000).build(),
+            transaction("2025-01-09", -2500).build(),
+            transaction("2025-01-20", -1000).build(),
+            deleted,
+        ];
+        assert_eq!(month_net_change(&transactions, date("2025-01-10")), 7500);
+    }
+
+    #[test]
+    fn recent_spending_counts_outflows_since_monday() {
+        let transfer = transaction("2025-01-09", -30000).transfer().build();
+        let transactions = vec![
+            // Sunday, last week
+            transaction("2025-01-05", -4000).build(),
+            transaction("2025-01-06", -1500).build(),
+            transaction("2025-01-09", -2500).build(),
+            transaction("2025-01-09", 80000).build(),
+            transaction("2025-01-10", -1000).build(),
+            transfer,
+        ];
+        let mut spending = RecentSpending::default();
+        // A Thursday
+        spending.add(&transactions, date("2025-01-09"));
+        assert_eq!(
+            spending,
+            RecentSpending {
+                today: 2500,
+                week: 4000
+            }
+        );
+    }
+
+    #[test]
+    fn daily_balances_zero_days_is_empty() {
+        assert!(daily_balances(1000, &[], date("2025-01-10"), 0).is_empty());
+    }
+}
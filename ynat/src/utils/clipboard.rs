@@ -0,0 +1,67 @@
+//! Copying text to the system clipboard.
+//!
+//! Uses the OSC 52 escape sequence, which most modern terminals (and tmux
+//! with `set-clipboard on`) forward to the clipboard, including over SSH.
+//! Locally on Windows the clipboard is set directly, since the console host
+//! ignores OSC 52.
+
+use std::io::{self, Write};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Place `text` on the clipboard
+#[cfg(windows)]
+pub fn copy(text: &str) -> io::Result<()> {
+    // Over SSH the clipboard to fill is the one on the client's side
+    if std::env::var_os("SSH_CONNECTION").is_some() {
+        return copy_through_terminal(text);
+    }
+    clipboard_win::set_clipboard_string(text).map_err(|e| io::Error::other(e.to_string()))
+}
+
+/// Ask the terminal to place `text` on the clipboard
+#[cfg(not(windows))]
+pub fn copy(text: &str) -> io::Result<()> {
+    copy_through_terminal(text)
+}
+
+fn copy_through_terminal(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
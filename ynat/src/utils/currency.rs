@@ -0,0 +1,106 @@
+//! Foreign amounts typed into the amount field as `=45 EUR`.
+//!
+//! The amount is converted to the budget's currency with a rate from the
+//! `[amounts.rates]` table or one entered when asked, and the original
+//! amount is kept in the memo.
+
+use crate::config::Rounding;
+use crate::utils;
+
+/// An amount in another currency, still to be converted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignAmount {
+    /// The amount in the foreign currency, signed like the transaction
+    pub value: f64,
+    /// ISO code, upper case
+    pub currency: String,
+}
+
+impl ForeignAmount {
+    /// Read `=45 EUR`, `=-12.50 gbp` or `=3*4.5 CHF`. The expression can use
+    /// the same math as a plain amount.
+    pub fn parse(input: &str) -> Option<Self> {
+        let rest = input.trim().strip_prefix('=')?;
+        let (expr, code) = rest.trim().rsplit_once(' ')?;
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let value = utils::math::evaluate_expression(expr)?.parse().ok()?;
+        Some(Self {
+            value,
+            currency: code.to_ascii_uppercase(),
+        })
+    }
+
+    /// Milliunits of the budget's currency, `rate` being what one unit of
+    /// the foreign currency is worth
+    pub fn convert(&self, rate: f64, rounding: Rounding) -> i64 {
+        rounding.apply((self.value * rate * 1000.0).round() as i64)
+    }
+
+    /// What's added to the memo: `45.00 EUR @ 1.08`
+    pub fn memo_note(&self, rate: f64) -> String {
+        format!("{:.2} {} @ {}", self.value.abs(), self.currency, rate)
+    }
+}
+
+/// A rate as typed at the prompt, if it's a positive number
+pub fn parse_rate(input: &str) -> Option<f64> {
+    input
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+}
+
+/// Add `note` to the end of a memo, in parentheses if there's already one
+pub fn append_to_memo(memo: &str, note: &str) -> String {
+    match memo.trim() {
+        "" => note.to_string(),
+        memo => format!("{} ({})", memo, note),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn foreign_amounts_need_an_equals_sign_and_a_currency_code() {
+        assert_eq!(
+            ForeignAmount::parse("=45 EUR"),
+            Some(ForeignAmount {
+                value: 45.0,
+                currency: "EUR".to_string()
+            })
+        );
+        assert_eq!(
+            ForeignAmount::parse(" =-3*4.5 chf "),
+            Some(ForeignAmount {
+                value: -13.5,
+                currency: "CHF".to_string()
+            })
+        );
+        assert_eq!(ForeignAmount::parse("45 EUR"), None);
+        assert_eq!(ForeignAmount::parse("=45"), None);
+        assert_eq!(ForeignAmount::parse("=45 EURO"), None);
+        assert_eq!(ForeignAmount::parse("= EUR"), None);
+    }
+
+    #[test]
+    fn conversion_rounds_like_a_typed_amount_and_notes_the_original() {
+        let amount = ForeignAmount::parse("=-45 EUR").unwrap();
+        assert_eq!(amount.convert(1.08, Rounding::None), -48_600);
+        assert_eq!(amount.convert(1.0837, Rounding::FiveHundredths), -48_750);
+        assert_eq!(amount.memo_note(1.08), "45.00 EUR @ 1.08");
+
+        assert_eq!(append_to_memo("", "45.00 EUR @ 1.08"), "45.00 EUR @ 1.08");
+        assert_eq!(
+            append_to_memo("Dinner", "45.00 EUR @ 1.08"),
+            "Dinner (45.00 EUR @ 1.08)"
+        );
+        assert_eq!(parse_rate("0.0091"), Some(0.0091));
+        assert_eq!(parse_rate("0"), None);
+        assert_eq!(parse_rate("abc"), None);
+    }
+}
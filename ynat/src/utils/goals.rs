@@ -0,0 +1,124 @@
+//! Savings goal projections for the "what-if" calculator.
+//!
+//! All amounts are milliunits and all months are the first day of the month.
+
+use crate::i18n::t;
+use chrono::{Datelike, Months, NaiveDate};
+
+/// YNAB goal types that save toward a fixed balance
+const TARGET_BALANCE_GOALS: &[&str] = &["TB", "TBD"];
+
+/// Whether a category's goal is a target balance (with or without a date)
+pub fn is_target_balance_goal(goal_type: Option<&str>) -> bool {
+    goal_type.is_some_and(|g| TARGET_BALANCE_GOALS.contains(&g))
+}
+
+/// A readable name for a goal type, or `None` for an unknown one
+pub fn goal_type_name(goal_type: &str) -> Option<&'static str> {
+    match goal_type {
+        "TB" => Some(t("goal-type-target-balance")),
+        "TBD" => Some(t("goal-type-target-balance")), // "by <month>" follows
+        "MF" => Some(t("goal-type-monthly-savings")),
+        "NEED" => Some(t("goal-type-needed-for-spending")),
+        "DEBT" => Some(t("goal-type-monthly-debt-payment")),
+        _ => None,
+    }
+}
+
+/// Number of months, counting `from` itself, until and including `to`
+fn months_inclusive(from: NaiveDate, to: NaiveDate) -> i64 {
+    let from = from.year() as i64 * 12 + from.month0() as i64;
+    let to = to.year() as i64 * 12 + to.month0() as i64;
+    to - from + 1
+}
+
+/// Months of budgeting `monthly` needed to cover `remaining`.
+/// Returns `None` if the target can never be reached.
+pub fn months_to_target(remaining: i64, monthly: i64) -> Option<u32> {
+    if remaining <= 0 {
+        return Some(0);
+    }
+    if monthly <= 0 {
+        return None;
+    }
+    u32::try_from((remaining + monthly - 1) / monthly).ok()
+}
+
+/// The month in which the target is reached when budgeting from `from`
+pub fn target_reached_month(from: NaiveDate, remaining: i64, monthly: i64) -> Option<NaiveDate> {
+    let months = months_to_target(remaining, monthly)?;
+    // Budgeting in `from` counts as the first month
+    from.checked_add_months(Months::new(months.saturating_sub(1)))
+}
+
+/// Amount to budget each month from `from` through `target_month` to cover `remaining`.
+/// Returns `None` if the target month is before `from`.
+pub fn monthly_needed(remaining: i64, from: NaiveDate, target_month: NaiveDate) -> Option<i64> {
+    let months = months_inclusive(from, target_month);
+    if months <= 0 {
+        return None;
+    }
+    if remaining <= 0 {
+        return Some(0);
+    }
+    Some((remaining + months - 1) / months)
+}
+
+/// Parse a `YYYY-MM` or `YYYY-MM-DD` month as the first of that month
+pub fn parse_month(input: &str) -> Option<NaiveDate> {
+    let mut parts = input.trim().split('-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    NaiveDate::from_ymd_opt(year, month, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month(y: i32, m: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, 1).unwrap()
+    }
+
+    #[test]
+    fn months_to_target_rounds_up() {
+        assert_eq!(months_to_target(1_000_000, 300_000), Some(4));
+        assert_eq!(months_to_target(900_000, 300_000), Some(3));
+        assert_eq!(months_to_target(0, 300_000), Some(0));
+        assert_eq!(months_to_target(1_000, 0), None);
+    }
+
+    #[test]
+    fn target_reached_month_counts_current_month() {
+        assert_eq!(
+            target_reached_month(month(2025, 11), 900_000, 300_000),
+            Some(month(2026, 1))
+        );
+        assert_eq!(
+            target_reached_month(month(2025, 11), 0, 0),
+            Some(month(2025, 11))
+        );
+    }
+
+    #[test]
+    fn monthly_needed_spreads_over_inclusive_months() {
+        // Nov, Dec, Jan, Feb = 4 months
+        assert_eq!(
+            monthly_needed(1_000_000, month(2025, 11), month(2026, 2)),
+            Some(250_000)
+        );
+        assert_eq!(
+            monthly_needed(1_001, month(2025, 1), month(2025, 2)),
+            Some(501)
+        );
+        assert_eq!(monthly_needed(1_000, month(2025, 3), month(2025, 2)), None);
+    }
+
+    #[test]
+    fn parse_month_accepts_short_and_full_dates() {
+        assert_eq!(parse_month("2026-03"), Some(month(2026, 3)));
+        assert_eq!(parse_month("2026-03-01"), Some(month(2026, 3)));
+        assert_eq!(parse_month("2026-13"), None);
+        assert_eq!(parse_month("2026"), None);
+    }
+}
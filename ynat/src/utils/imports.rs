@@ -0,0 +1,82 @@
+//! Hints for transactions that came in from the bank.
+//!
+//! YNAB gives imported transactions an `import_id` of the form
+//! `YNAB:<milliunits>:<date>:<occurrence>`, recording the amount and date the
+//! bank reported even if the transaction was edited since.
+
+use chrono::NaiveDate;
+use ynab_api::endpoints::transactions::Transaction;
+
+/// How far apart an import and a hand-entered transaction can be dated and
+/// still be suggested as the same purchase
+pub const MATCH_WINDOW_DAYS: i64 = 10;
+
+/// The amount and date the bank reported, from a YNAB import id
+pub fn parse_import_id(import_id: &str) -> Option<(i64, NaiveDate)> {
+    let mut parts = import_id.split(':');
+    if parts.next()? != "YNAB" {
+        return None;
+    }
+    let amount = parts.next()?.parse().ok()?;
+    let date = NaiveDate::parse_from_str(parts.next()?, "%Y-%m-%d").ok()?;
+    Some((amount, date))
+}
+
+/// A hand-entered transaction in the same account that an unapproved,
+/// unmatched import probably duplicates: same amount as the bank reported,
+/// dated within [`MATCH_WINDOW_DAYS`], the closest date winning.
+pub fn suggested_match<'a>(
+    imported: &Transaction,
+    transactions: &[&'a Transaction],
+) -> Option<&'a Transaction> {
+    if imported.approved || imported.matched_transaction_id.is_some() {
+        return None;
+    }
+    let (amount, date) = parse_import_id(imported.import_id.as_deref()?)?;
+    transactions
+        .iter()
+        .copied()
+        .filter(|t| {
+            t.id != imported.id
+                && t.account_id == imported.account_id
+                && t.import_id.is_none()
+                && t.matched_transaction_id.is_none()
+                && !t.deleted
+                && i64::from(t.amount) == amount
+                && (t.date - date).num_days().abs() <= MATCH_WINDOW_DAYS
+        })
+        .min_by_key(|t| (t.date - date).num_days().abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::transaction;
+
+    #[test]
+    fn import_ids_carry_the_bank_amount_and_date() {
+        assert_eq!(
+            parse_import_id("YNAB:-12340:2025-03-01:2"),
+            Some((-12_340, NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()))
+        );
+        assert_eq!(parse_import_id("bank-export-17"), None);
+    }
+
+    #[test]
+    fn imports_are_matched_to_the_closest_hand_entered_transaction() {
+        let imported = transaction("2025-03-05", -12_340)
+            .import_id("YNAB:-12340:2025-03-04:1")
+            .approved(false)
+            .build();
+        let far = transaction("2025-03-20", -12_340).build();
+        let close = transaction("2025-03-02", -12_340).build();
+        let other_amount = transaction("2025-03-04", -9_990).build();
+        let listed = [&imported, &far, &close, &other_amount];
+
+        assert_eq!(
+            suggested_match(&imported, &listed).map(|t| &t.id),
+            Some(&close.id)
+        );
+        assert_eq!(suggested_match(&close, &listed), None);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::config::Rounding;
+use ynab_api::endpoints::Milliunits;
+
 /// Evaluate a simple math expression containing +, -, *, /, and parentheses.
 /// Returns the result as a formatted string with 2 decimal places, or None if invalid.
 ///
@@ -31,6 +34,63 @@ pub fn evaluate_expression(expr: &str) -> Option<String> {
         .map(|result| format!("{:.2}", result))
 }
 
+/// Like [`evaluate_expression`], with the result rounded by `rounding`
+pub fn evaluate_expression_rounded(expr: &str, rounding: Rounding) -> Option<String> {
+    let result: Milliunits = evaluate_expression(expr)?.parse().ok()?;
+    Some(Milliunits::from(rounding.apply(result.into())).format_plain(2))
+}
+
+/// Flip the sign of an amount or expression, keeping it readable.
+///
+/// Examples:
+/// - "12.50" -> "-12.50"
+/// - "-12.50" -> "12.50"
+/// - "5+3" -> "-(5+3)"
+/// - "-(5+3)" -> "5+3"
+/// - "" -> "-" (so the amount typed next is an outflow)
+pub fn negate_expression(expr: &str) -> String {
+    let expr = expr.trim();
+    match expr {
+        "" => return "-".to_string(),
+        "-" => return String::new(),
+        _ => {}
+    }
+
+    if let Some(rest) = expr.strip_prefix('-') {
+        if rest.parse::<f64>().is_ok() {
+            return rest.to_string();
+        }
+        if let Some(inner) = unwrap_parens(rest) {
+            return inner.to_string();
+        }
+    }
+
+    let positive = expr.strip_prefix('+').unwrap_or(expr);
+    if positive.parse::<f64>().is_ok() {
+        format!("-{}", positive)
+    } else {
+        format!("-({})", expr)
+    }
+}
+
+/// The inside of `s` if a single pair of parentheses wraps all of it
+fn unwrap_parens(s: &str) -> Option<&str> {
+    let inner = s.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0i32;
+    for c in inner.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        // The opening paren closed before the end, e.g. "(1)+(2)"
+        if depth < 0 {
+            return None;
+        }
+    }
+    (depth == 0).then_some(inner)
+}
+
 /// Simple recursive descent parser for math expressions
 struct ExprParser<'a> {
     input: &'a str,
@@ -266,6 +326,39 @@ mod tests {
         assert_eq!(evaluate_expression("(+10)*2"), Some("20.00".to_string()));
     }
 
+    #[test]
+    fn evaluate_expression_rounded_to_step() {
+        let nickel = Rounding::FiveHundredths;
+        assert_eq!(
+            evaluate_expression_rounded("12.37", nickel),
+            Some("12.35".to_string())
+        );
+        assert_eq!(
+            evaluate_expression_rounded("-12.375", nickel),
+            Some("-12.40".to_string())
+        );
+        assert_eq!(
+            evaluate_expression_rounded("10/3", Rounding::Unit),
+            Some("3.00".to_string())
+        );
+        assert_eq!(
+            evaluate_expression_rounded("10/3", Rounding::None),
+            Some("3.33".to_string())
+        );
+    }
+
+    #[test]
+    fn negate_expression_flips_sign() {
+        assert_eq!(negate_expression("12.50"), "-12.50");
+        assert_eq!(negate_expression("-12.50"), "12.50");
+        assert_eq!(negate_expression("+5"), "-5");
+        assert_eq!(negate_expression("5+3"), "-(5+3)");
+        assert_eq!(negate_expression("-(5+3)"), "5+3");
+        assert_eq!(negate_expression("-(1)+(2)"), "-(-(1)+(2))");
+        assert_eq!(negate_expression(""), "-");
+        assert_eq!(negate_expression("-"), "");
+    }
+
     #[test]
     fn evaluate_expression_invalid() {
         assert_eq!(evaluate_expression(""), None);
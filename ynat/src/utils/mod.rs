@@ -1,2 +1,10 @@
+pub mod balance_history;
+pub mod clipboard;
+pub mod currency;
 pub mod dates;
+pub mod goals;
+pub mod imports;
 pub mod math;
+pub mod money;
+pub mod platform;
+pub mod ynab_export;
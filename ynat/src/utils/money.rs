@@ -0,0 +1,112 @@
+//! Amounts written the way the budget writes them.
+//!
+//! YNAB stores amounts as milliunits and gives each budget a
+//! [`CurrencyFormat`] (symbol, separators, decimal digits). Everything that
+//! shows an amount, fills a form with one or reads one back goes through here
+//! so a euro or yen budget doesn't get dollars. Budgets whose format isn't
+//! known yet are shown as US dollars.
+
+use crate::utils;
+use ynab_api::endpoints::{CurrencyFormat, Milliunits};
+
+/// Format used until the budget's own is known
+pub fn fallback_format() -> CurrencyFormat {
+    CurrencyFormat {
+        iso_code: "USD".to_string(),
+        example_format: "123,456.78".to_string(),
+        decimal_digits: 2,
+        decimal_separator: ".".to_string(),
+        symbol_first: true,
+        group_separator: ",".to_string(),
+        currency_symbol: "$".to_string(),
+        display_symbol: true,
+    }
+}
+
+/// An amount for display: `-$1,234.50`, `1.234,50€`, `¥1,500`
+pub fn format(amount: i64, currency: Option<&CurrencyFormat>) -> String {
+    match currency {
+        Some(currency) => Milliunits::from(amount).format(currency),
+        None => Milliunits::from(amount).format(&fallback_format()),
+    }
+}
+
+/// An amount to prefill an input with: the budget's decimal digits but no
+/// symbol or grouping, and always a `.` so it can be edited and typed back
+pub fn format_input(amount: i64, currency: Option<&CurrencyFormat>) -> String {
+    Milliunits::from(amount).format_plain(currency.map_or(2, |c| c.decimal_digits))
+}
+
+/// An amount typed into a form, in milliunits: a number with a `.`, one
+/// written in the budget's format (`1.234,50€`), or a math expression
+pub fn parse_input(input: &str, currency: Option<&CurrencyFormat>) -> Option<i64> {
+    let input = input.trim();
+    input
+        .parse::<Milliunits>()
+        .ok()
+        .or_else(|| Milliunits::from_str_with_format(input, currency?).ok())
+        .or_else(|| {
+            utils::math::evaluate_expression(input)?
+                .parse::<Milliunits>()
+                .ok()
+        })
+        .map(i64::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn euro() -> CurrencyFormat {
+        CurrencyFormat {
+            iso_code: "EUR".to_string(),
+            example_format: "123.456,78".to_string(),
+            decimal_digits: 2,
+            decimal_separator: ",".to_string(),
+            symbol_first: false,
+            group_separator: ".".to_string(),
+            currency_symbol: "€".to_string(),
+            display_symbol: true,
+        }
+    }
+
+    fn yen() -> CurrencyFormat {
+        CurrencyFormat {
+            iso_code: "JPY".to_string(),
+            example_format: "123,457".to_string(),
+            decimal_digits: 0,
+            decimal_separator: ".".to_string(),
+            symbol_first: true,
+            group_separator: ",".to_string(),
+            currency_symbol: "¥".to_string(),
+            display_symbol: true,
+        }
+    }
+
+    #[test]
+    fn amounts_follow_the_budget_currency() {
+        assert_eq!(format(-1_234_500, None), "-$1,234.50");
+        assert_eq!(format(1_234_500, Some(&euro())), "1.234,50€");
+        assert_eq!(format(1_500_499, Some(&yen())), "¥1,500");
+        assert_eq!(format(999_995, None), "$1,000.00");
+        assert_eq!(format(-4, None), "$0.00");
+    }
+
+    #[test]
+    fn inputs_are_read_with_a_point_or_in_the_budget_format() {
+        assert_eq!(parse_input("-12.5", Some(&euro())), Some(-12_500));
+        assert_eq!(parse_input("-1.234,50 €", Some(&euro())), Some(-1_234_500));
+        assert_eq!(parse_input("¥1,500", Some(&yen())), Some(1_500_000));
+        assert_eq!(parse_input("0.29", None), Some(290));
+        assert_eq!(parse_input("1.0005", None), Some(1_001));
+        assert_eq!(parse_input("10+5.25", None), Some(15_250));
+        assert_eq!(parse_input("1,50", None), None);
+    }
+
+    #[test]
+    fn inputs_keep_the_decimal_digits_without_grouping() {
+        assert_eq!(format_input(-1_234_500, Some(&euro())), "-1234.50");
+        assert_eq!(format_input(1_500_000, Some(&yen())), "1500");
+        assert_eq!(format_input(12_345, None), "12.35");
+    }
+}
@@ -0,0 +1,43 @@
+//! Platform-specific terminal capabilities.
+//!
+//! Keeps `cfg(windows)` checks in one place so rendering and input code can
+//! ask about capabilities instead of operating systems.
+
+use std::path::PathBuf;
+
+/// Whether the terminal can be expected to render non-ASCII symbols such as
+/// braille spinners and block characters.
+///
+/// The legacy Windows console host (conhost) uses raster fonts without these
+/// glyphs; Windows Terminal sets `WT_SESSION`, and most other modern terminals
+/// (VS Code, WezTerm, Alacritty) set `TERM_PROGRAM`.
+#[cfg(windows)]
+pub fn supports_unicode_symbols() -> bool {
+    std::env::var_os("WT_SESSION").is_some() || std::env::var_os("TERM_PROGRAM").is_some()
+}
+
+/// Whether the terminal can be expected to render non-ASCII symbols such as
+/// braille spinners and block characters.
+#[cfg(not(windows))]
+pub fn supports_unicode_symbols() -> bool {
+    true
+}
+
+/// Where log files go: `%LOCALAPPDATA%\ynat\logs`, so they stay out of the
+/// roaming profile.
+#[cfg(windows)]
+pub fn logs_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("ynat").join("logs"))
+}
+
+/// Where log files go: `ynat/logs` in the user's config directory.
+#[cfg(not(windows))]
+pub fn logs_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ynat").join("logs"))
+}
+
+/// Whether Ctrl+Alt on a character key should be treated as AltGr text input.
+///
+/// Windows reports AltGr as Ctrl+Alt, so characters like '@', '{' or '€' on
+/// many European keyboard layouts would otherwise be mistaken for shortcuts.
+pub const ALTGR_IS_CTRL_ALT: bool = cfg!(windows);
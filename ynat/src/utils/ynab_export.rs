@@ -0,0 +1,794 @@
+//! Parser for YNAB's "Export budget data" CSV files.
+//!
+//! Handles both the nYNAB export (`... - Register.csv` / `... - Plan.csv`) and
+//! the older YNAB4 export (`... - Register.csv` / `... - Budget.csv`). Columns
+//! are located by header name, so either layout works.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use ynab_api::endpoints::{
+    accounts::Account,
+    categories::Category,
+    transactions::{FlagColor, NewTransaction, ReconciliationStatus},
+};
+
+/// A register row from the export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedTransaction {
+    pub account: String,
+    pub date: NaiveDate,
+    pub payee: Option<String>,
+    pub category_group: Option<String>,
+    pub category: Option<String>,
+    pub memo: Option<String>,
+    /// Inflow minus outflow, in milliunits
+    pub amount: i64,
+    pub cleared: ReconciliationStatus,
+    pub flag: Option<FlagColor>,
+}
+
+/// A plan (budget) row from the export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedAllocation {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub category_group: String,
+    pub category: String,
+    /// Assigned amount in milliunits
+    pub budgeted: i64,
+}
+
+/// The register and plan files of one export, matched by the name YNAB gives
+/// both files of a download
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportFiles {
+    /// Name of the budget the export was made from
+    pub budget_name: String,
+    /// When the export was made, from the file name or else the files' mtime
+    pub exported_at: NaiveDateTime,
+    pub register: Option<PathBuf>,
+    pub plan: Option<PathBuf>,
+}
+
+/// What an import would do, shown for confirmation before anything is written
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportPreview {
+    pub export: ExportFiles,
+    /// The export was made from the open budget rather than another one
+    pub same_budget: bool,
+    /// Transactions to create, before YNAB drops rows imported already
+    pub transactions: usize,
+    /// Assigned amounts that will be replaced
+    pub allocations: usize,
+    pub months: usize,
+    /// Rows that can't be parsed or matched to the open budget
+    pub skipped: usize,
+}
+
+/// Find every export in `dir`, newest first
+pub fn find_exports(dir: &Path) -> std::io::Result<Vec<ExportFiles>> {
+    let files = std::fs::read_dir(dir)?.filter_map(|entry| {
+        let entry = entry.ok()?;
+        let modified = entry.metadata().ok()?.modified().ok()?;
+        let modified = chrono::DateTime::<chrono::Local>::from(modified).naive_local();
+        Some((entry.path(), modified))
+    });
+    Ok(group_exports(files))
+}
+
+/// Pair up register and plan files by their shared prefix, newest first
+fn group_exports(files: impl IntoIterator<Item = (PathBuf, NaiveDateTime)>) -> Vec<ExportFiles> {
+    let mut exports: HashMap<String, ExportFiles> = HashMap::new();
+    for (path, modified) in files {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let (prefix, is_register) = if let Some(prefix) = name.strip_suffix(" - Register.csv") {
+            (prefix, true)
+        } else if let Some(prefix) = name
+            .strip_suffix(" - Plan.csv")
+            .or_else(|| name.strip_suffix(" - Budget.csv"))
+        {
+            (prefix, false)
+        } else {
+            continue;
+        };
+
+        let (budget_name, stamped) = parse_export_prefix(prefix);
+        let export = exports
+            .entry(prefix.to_string())
+            .or_insert_with(|| ExportFiles {
+                budget_name,
+                exported_at: stamped.unwrap_or(modified),
+                register: None,
+                plan: None,
+            });
+        if stamped.is_none() {
+            export.exported_at = export.exported_at.max(modified);
+        }
+        if is_register {
+            export.register = Some(path);
+        } else {
+            export.plan = Some(path);
+        }
+    }
+
+    let mut exports: Vec<ExportFiles> = exports.into_values().collect();
+    exports.sort_by(|a, b| {
+        b.exported_at
+            .cmp(&a.exported_at)
+            .then_with(|| a.budget_name.cmp(&b.budget_name))
+    });
+    exports
+}
+
+/// Split "My Budget as of 2024-01-20 1021 AM" into the budget name and time
+fn parse_export_prefix(prefix: &str) -> (String, Option<NaiveDateTime>) {
+    let Some((name, stamp)) = prefix.rsplit_once(" as of ") else {
+        return (prefix.trim().to_string(), None);
+    };
+    let stamp = stamp.trim();
+    let parsed = ["%Y-%m-%d %I%M %p", "%Y-%m-%d %H%M", "%Y-%m-%d %I-%M %p"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(stamp, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(stamp, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        });
+    (name.trim().to_string(), parsed)
+}
+
+/// The newest export of the open budget, or the newest of any budget when
+/// none was made from it
+pub fn pick_export(exports: Vec<ExportFiles>, budget_name: Option<&str>) -> Option<ExportFiles> {
+    let matches = |export: &ExportFiles| {
+        budget_name.is_some_and(|name| export.budget_name.eq_ignore_ascii_case(name.trim()))
+    };
+    match exports.iter().position(matches) {
+        Some(index) => exports.into_iter().nth(index),
+        None => exports.into_iter().next(),
+    }
+}
+
+/// Parse a register export. Rows that cannot be parsed are skipped and counted.
+pub fn parse_register(source: &str) -> (Vec<ExportedTransaction>, usize) {
+    let mut rows = parse_csv(source).into_iter();
+    let Some(header) = rows.next() else {
+        return (Vec::new(), 0);
+    };
+    let columns = Columns::new(&header);
+
+    let mut skipped = 0;
+    let transactions = rows
+        .filter_map(|row| {
+            let parsed = columns.transaction(&row);
+            if parsed.is_none() {
+                skipped += 1;
+            }
+            parsed
+        })
+        .collect();
+
+    (transactions, skipped)
+}
+
+/// Parse a plan/budget export, skipping rows with nothing assigned
+pub fn parse_plan(source: &str) -> (Vec<ExportedAllocation>, usize) {
+    let mut rows = parse_csv(source).into_iter();
+    let Some(header) = rows.next() else {
+        return (Vec::new(), 0);
+    };
+    let columns = Columns::new(&header);
+
+    let mut skipped = 0;
+    let allocations = rows
+        .filter_map(|row| match columns.allocation(&row) {
+            Some(allocation) if allocation.budgeted != 0 => Some(allocation),
+            Some(_) => None,
+            None => {
+                skipped += 1;
+                None
+            }
+        })
+        .collect();
+
+    (allocations, skipped)
+}
+
+/// Matches exported names against the target budget's accounts and categories
+pub struct BudgetMatcher<'a> {
+    accounts: HashMap<String, &'a Account>,
+    categories: HashMap<(String, String), Uuid>,
+    /// Category name -> id, `None` when the name exists in several groups
+    categories_by_name: HashMap<String, Option<Uuid>>,
+    /// "Inflow: Ready to Assign", the target for income rows
+    inflow_category: Option<Uuid>,
+}
+
+impl<'a> BudgetMatcher<'a> {
+    /// `categories` must have `category_group_name` set
+    pub fn new(accounts: &'a [Account], categories: &[Category]) -> Self {
+        let mut categories_by_name: HashMap<String, Option<Uuid>> = HashMap::new();
+        for category in categories {
+            categories_by_name
+                .entry(category.name.to_lowercase())
+                .and_modify(|id| *id = None)
+                .or_insert(Some(category.id));
+        }
+
+        Self {
+            accounts: accounts
+                .iter()
+                .filter(|a| !a.deleted)
+                .map(|a| (a.name.to_lowercase(), a))
+                .collect(),
+            categories: categories
+                .iter()
+                .map(|c| {
+                    let group = c.category_group_name.clone().unwrap_or_default();
+                    ((group.to_lowercase(), c.name.to_lowercase()), c.id)
+                })
+                .collect(),
+            categories_by_name,
+            inflow_category: categories
+                .iter()
+                .find(|c| c.name.starts_with("Inflow:"))
+                .map(|c| c.id),
+        }
+    }
+
+    fn category_id(&self, group: Option<&str>, name: Option<&str>) -> Option<Uuid> {
+        let name = name?.to_lowercase();
+        let group = group.map(str::to_lowercase);
+        if matches!(group.as_deref(), Some("inflow" | "income")) {
+            return self.inflow_category;
+        }
+        group
+            .and_then(|g| self.categories.get(&(g, name.clone())).copied())
+            .or_else(|| self.categories_by_name.get(&name).copied().flatten())
+    }
+
+    /// Build create requests for the exported register.
+    ///
+    /// Rows for unknown accounts are skipped, and transfers between two
+    /// imported accounts are only created from the outflow side since YNAB
+    /// creates the matching inflow. Returns the transactions and the number skipped.
+    pub fn transactions(&self, exported: &[ExportedTransaction]) -> (Vec<NewTransaction>, usize) {
+        let mut skipped = 0;
+        let mut occurrences: HashMap<(Uuid, i64, NaiveDate), usize> = HashMap::new();
+        let mut transactions = Vec::new();
+
+        for row in exported {
+            let Some(account) = self.accounts.get(&row.account.to_lowercase()) else {
+                skipped += 1;
+                continue;
+            };
+
+            let mut transaction = NewTransaction::new(
+                account.id,
+                row.date.format("%Y-%m-%d").to_string(),
+                row.amount,
+            );
+
+            match row
+                .payee
+                .as_deref()
+                .and_then(|p| p.strip_prefix("Transfer : "))
+            {
+                Some(target) => match self.accounts.get(&target.to_lowercase()) {
+                    Some(other) if row.amount < 0 => transaction.payee_id = other.transfer_payee_id,
+                    Some(_) => {
+                        skipped += 1;
+                        continue;
+                    }
+                    None => transaction.payee_name = Some(target.to_string()),
+                },
+                None => transaction.payee_name = row.payee.clone(),
+            }
+
+            if transaction.payee_id.is_none() {
+                transaction.category_id =
+                    self.category_id(row.category_group.as_deref(), row.category.as_deref());
+            }
+            transaction.memo = row.memo.clone();
+            transaction.cleared = Some(row.cleared);
+            transaction.approved = Some(true);
+            transaction.flag_color = row.flag;
+
+            // Stable id so re-running the import skips rows that already exist
+            let occurrence = occurrences
+                .entry((account.id, row.amount, row.date))
+                .or_default();
+            *occurrence += 1;
+            transaction.import_id = Some(format!(
+                "YNAT:{}:{}:{}",
+                row.amount,
+                row.date.format("%Y-%m-%d"),
+                occurrence
+            ));
+
+            transactions.push(transaction);
+        }
+
+        (transactions, skipped)
+    }
+
+    /// Resolve exported allocations to (month, category id, budgeted), counting unmatched rows
+    pub fn allocations(
+        &self,
+        exported: &[ExportedAllocation],
+    ) -> (Vec<(String, Uuid, i64)>, usize) {
+        let mut skipped = 0;
+        let allocations = exported
+            .iter()
+            .filter_map(|row| {
+                let id = self.category_id(Some(&row.category_group), Some(&row.category));
+                if id.is_none() {
+                    skipped += 1;
+                }
+                Some((row.month.format("%Y-%m-%d").to_string(), id?, row.budgeted))
+            })
+            .collect();
+        (allocations, skipped)
+    }
+}
+
+/// Header name to column index lookup
+struct Columns(HashMap<String, usize>);
+
+impl Columns {
+    fn new(header: &[String]) -> Self {
+        Self(
+            header
+                .iter()
+                .enumerate()
+                // Exports start with a UTF-8 BOM
+                .map(|(i, name)| (name.trim_start_matches('\u{feff}').trim().to_string(), i))
+                .collect(),
+        )
+    }
+
+    /// First non-empty value among the given column names
+    fn get<'a>(&self, row: &'a [String], names: &[&str]) -> Option<&'a str> {
+        names
+            .iter()
+            .filter_map(|name| self.0.get(*name))
+            .filter_map(|&i| row.get(i))
+            .map(|v| v.trim())
+            .find(|v| !v.is_empty())
+    }
+
+    fn transaction(&self, row: &[String]) -> Option<ExportedTransaction> {
+        let amount = match self.get(row, &["Amount"]) {
+            Some(amount) => parse_amount(amount)?,
+            None => {
+                let inflow = self.get(row, &["Inflow"]).map_or(Some(0), parse_amount)?;
+                let outflow = self.get(row, &["Outflow"]).map_or(Some(0), parse_amount)?;
+                inflow - outflow
+            }
+        };
+
+        let (category_group, category) = self.category(row);
+        let cleared = match self.get(row, &["Cleared"]) {
+            Some("Reconciled" | "R") => ReconciliationStatus::Reconciled,
+            Some("Cleared" | "C") => ReconciliationStatus::Cleared,
+            _ => ReconciliationStatus::Uncleared,
+        };
+
+        Some(ExportedTransaction {
+            account: self.get(row, &["Account"])?.to_string(),
+            date: parse_date(self.get(row, &["Date"])?)?,
+            payee: self.get(row, &["Payee"]).map(str::to_string),
+            category_group,
+            category,
+            memo: self.get(row, &["Memo"]).map(str::to_string),
+            amount,
+            cleared,
+            flag: self.get(row, &["Flag"]).and_then(parse_flag),
+        })
+    }
+
+    fn allocation(&self, row: &[String]) -> Option<ExportedAllocation> {
+        let (category_group, category) = self.category(row);
+        Some(ExportedAllocation {
+            month: parse_month(self.get(row, &["Month"])?)?,
+            category_group: category_group?,
+            category: category?,
+            budgeted: parse_amount(self.get(row, &["Assigned", "Budgeted"])?)?,
+        })
+    }
+
+    /// (group, category) from either the split columns or a combined "Group: Category"
+    fn category(&self, row: &[String]) -> (Option<String>, Option<String>) {
+        let group = self.get(row, &["Category Group", "Master Category"]);
+        let name = self.get(row, &["Sub Category", "Category"]);
+        match (group, name) {
+            (Some(group), Some(name)) => {
+                // YNAB4's "Category" column is "Master: Sub"; prefer the split columns
+                let name = name
+                    .strip_prefix(group)
+                    .and_then(|n| n.strip_prefix(':'))
+                    .map(str::trim)
+                    .unwrap_or(name);
+                (Some(group.to_string()), Some(name.to_string()))
+            }
+            (None, Some(combined)) => match combined.split_once(':') {
+                Some((group, name)) => (
+                    Some(group.trim().to_string()),
+                    Some(name.trim().to_string()),
+                ),
+                None => (None, Some(combined.to_string())),
+            },
+            _ => (None, None),
+        }
+    }
+}
+
+/// Split CSV text into rows, handling quoted fields with embedded commas, quotes and newlines
+fn parse_csv(source: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    rows
+}
+
+/// Parse a formatted amount like `$1,234.56`, `-12.00€` or `1.234,56 kr` into milliunits
+fn parse_amount(text: &str) -> Option<i64> {
+    let negative = text.contains('-') || (text.starts_with('(') && text.ends_with(')'));
+    let digits: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+    if digits.is_empty() {
+        return None;
+    }
+
+    // Whichever separator comes last is the decimal one if 1-2 digits follow it
+    let (whole, fraction) = match digits.rfind(['.', ',']) {
+        Some(i) if digits.len() - i - 1 <= 2 => (&digits[..i], &digits[i + 1..]),
+        _ => (digits.as_str(), ""),
+    };
+    let whole: String = whole.chars().filter(char::is_ascii_digit).collect();
+    let whole: i64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: i64 = format!("{:0<3}", fraction).parse().ok()?;
+
+    let milliunits = whole * 1000 + fraction;
+    Some(if negative { -milliunits } else { milliunits })
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    ["%m/%d/%Y", "%Y-%m-%d", "%d.%m.%Y", "%d/%m/%Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+}
+
+/// Parse a plan month like "Jan 2024", "January 2024" or "2024-01"
+fn parse_month(text: &str) -> Option<NaiveDate> {
+    let day_first = format!("1 {}", text);
+    ["%d %b %Y", "%d %B %Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(&day_first, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(&format!("{}-01", text), "%Y-%m-%d").ok())
+}
+
+fn parse_flag(text: &str) -> Option<FlagColor> {
+    match text.to_ascii_lowercase().as_str() {
+        "red" => Some(FlagColor::Red),
+        "orange" => Some(FlagColor::Orange),
+        "yellow" => Some(FlagColor::Yellow),
+        "green" => Some(FlagColor::Green),
+        "blue" => Some(FlagColor::Blue),
+        "purple" => Some(FlagColor::Purple),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{account, category, id};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn parses_nynab_register() {
+        let csv = "\u{feff}\"Account\",\"Flag\",\"Date\",\"Payee\",\"Category Group/Category\",\"Category Group\",\"Category\",\"Memo\",\"Outflow\",\"Inflow\",\"Cleared\"\n\
+            \"Checking\",\"Red\",\"01/15/2024\",\"Grocer, Inc.\",\"Everyday: Groceries\",\"Everyday\",\"Groceries\",\"\",$45.20,$0.00,\"Cleared\"\n\
+            \"Checking\",\"\",\"01/16/2024\",\"Employer\",\"Inflow: Ready to Assign\",\"Inflow\",\"Ready to Assign\",\"Jan \"\"pay\"\"\",$0.00,\"$1,500.00\",\"Reconciled\"\n";
+
+        let (transactions, skipped) = parse_register(csv);
+        assert_eq!(skipped, 0);
+        assert_eq!(transactions.len(), 2);
+
+        assert_eq!(
+            transactions[0],
+            ExportedTransaction {
+                account: "Checking".to_string(),
+                date: date("2024-01-15"),
+                payee: Some("Grocer, Inc.".to_string()),
+                category_group: Some("Everyday".to_string()),
+                category: Some("Groceries".to_string()),
+                memo: None,
+                amount: -45200,
+                cleared: ReconciliationStatus::Cleared,
+                flag: Some(FlagColor::Red),
+            }
+        );
+        assert_eq!(transactions[1].amount, 1_500_000);
+        assert_eq!(transactions[1].memo.as_deref(), Some("Jan \"pay\""));
+        assert_eq!(transactions[1].cleared, ReconciliationStatus::Reconciled);
+    }
+
+    #[test]
+    fn parses_ynab4_register_categories() {
+        let csv = "Account,Flag,Check Number,Date,Payee,Category,Master Category,Sub Category,Memo,Outflow,Inflow,Cleared,Running Balance\n\
+            Savings,,,2015-03-02,Bank,Bills: Rent,Bills,Rent,,\"1.200,00€\",\"0,00€\",U,0\n";
+
+        let (transactions, _) = parse_register(csv);
+        assert_eq!(transactions[0].category_group.as_deref(), Some("Bills"));
+        assert_eq!(transactions[0].category.as_deref(), Some("Rent"));
+        assert_eq!(transactions[0].amount, -1_200_000);
+        assert_eq!(transactions[0].cleared, ReconciliationStatus::Uncleared);
+    }
+
+    #[test]
+    fn skips_unparseable_register_rows() {
+        let csv =
+            "Account,Date,Outflow,Inflow\nChecking,not a date,1.00,0\nChecking,2024-02-01,1.00,0\n";
+        let (transactions, skipped) = parse_register(csv);
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn parses_plan_and_drops_empty_allocations() {
+        let csv = "\"Month\",\"Category Group/Category\",\"Category Group\",\"Category\",\"Assigned\",\"Activity\",\"Available\"\n\
+            \"Jan 2024\",\"Everyday: Groceries\",\"Everyday\",\"Groceries\",$400.00,-$45.20,$354.80\n\
+            \"Jan 2024\",\"Everyday: Dining\",\"Everyday\",\"Dining\",$0.00,$0.00,$0.00\n";
+
+        let (allocations, skipped) = parse_plan(csv);
+        assert_eq!(skipped, 0);
+        assert_eq!(
+            allocations,
+            vec![ExportedAllocation {
+                month: date("2024-01-01"),
+                category_group: "Everyday".to_string(),
+                category: "Groceries".to_string(),
+                budgeted: 400_000,
+            }]
+        );
+    }
+
+    fn exported(
+        account: &str,
+        payee: &str,
+        category: Option<(&str, &str)>,
+        amount: i64,
+    ) -> ExportedTransaction {
+        ExportedTransaction {
+            account: account.to_string(),
+            date: date("2024-01-15"),
+            payee: Some(payee.to_string()),
+            category_group: category.map(|(g, _)| g.to_string()),
+            category: category.map(|(_, c)| c.to_string()),
+            memo: None,
+            amount,
+            cleared: ReconciliationStatus::Cleared,
+            flag: None,
+        }
+    }
+
+    #[test]
+    fn matcher_maps_accounts_categories_and_transfers() {
+        let accounts = vec![
+            account("Checking").build(),
+            account("Savings")
+                .transfer_payee(id("Transfer : Savings"))
+                .build(),
+        ];
+        let categories = vec![
+            category("Groceries").build(),
+            category("Inflow: Ready to Assign")
+                .group("Internal Master Category")
+                .build(),
+        ];
+        let matcher = BudgetMatcher::new(&accounts, &categories);
+
+        let rows = vec![
+            exported("Checking", "Grocer", Some(("Everyday", "Groceries")), -5000),
+            exported("Checking", "Grocer", Some(("Everyday", "Groceries")), -5000),
+            exported(
+                "Checking",
+                "Employer",
+                Some(("Inflow", "Ready to Assign")),
+                90000,
+            ),
+            exported("Checking", "Transfer : Savings", None, -1000),
+            exported("Savings", "Transfer : Checking", None, 1000),
+            exported("Brokerage", "Broker", None, 1),
+        ];
+        let (transactions, skipped) = matcher.transactions(&rows);
+
+        // Inflow side of the transfer and the unknown account are skipped
+        assert_eq!(skipped, 2);
+        assert_eq!(transactions.len(), 4);
+        assert_eq!(transactions[0].category_id, Some(id("Groceries")));
+        assert_eq!(
+            transactions[0].import_id.as_deref(),
+            Some("YNAT:-5000:2024-01-15:1")
+        );
+        assert_eq!(
+            transactions[1].import_id.as_deref(),
+            Some("YNAT:-5000:2024-01-15:2")
+        );
+        assert_eq!(
+            transactions[2].category_id,
+            Some(id("Inflow: Ready to Assign"))
+        );
+        assert_eq!(transactions[3].payee_id, Some(id("Transfer : Savings")));
+        assert_eq!(transactions[3].category_id, None);
+    }
+
+    #[test]
+    fn matcher_skips_unknown_allocation_categories() {
+        let categories = vec![category("Groceries").build()];
+        let matcher = BudgetMatcher::new(&[], &categories);
+        let rows = vec![
+            ExportedAllocation {
+                month: date("2024-01-01"),
+                category_group: "Everyday".to_string(),
+                category: "Groceries".to_string(),
+                budgeted: 400_000,
+            },
+            ExportedAllocation {
+                month: date("2024-01-01"),
+                category_group: "Fun".to_string(),
+                category: "Games".to_string(),
+                budgeted: 1_000,
+            },
+        ];
+
+        let (allocations, skipped) = matcher.allocations(&rows);
+        assert_eq!(skipped, 1);
+        assert_eq!(
+            allocations,
+            vec![("2024-01-01".to_string(), id("Groceries"), 400_000)]
+        );
+    }
+
+    #[test]
+    fn parse_amount_handles_formats() {
+        assert_eq!(parse_amount("$1,234.56"), Some(1_234_560));
+        assert_eq!(parse_amount("-12.5"), Some(-12_500));
+        assert_eq!(parse_amount("1.234,56 kr"), Some(1_234_560));
+        assert_eq!(parse_amount("(3.00)"), Some(-3_000));
+        assert_eq!(parse_amount("1,000"), Some(1_000_000));
+        assert_eq!(parse_amount(""), None);
+    }
+
+    #[test]
+    fn parse_month_handles_formats() {
+        assert_eq!(parse_month("Feb 2024"), Some(date("2024-02-01")));
+        assert_eq!(parse_month("February 2024"), Some(date("2024-02-01")));
+        assert_eq!(parse_month("2024-02"), Some(date("2024-02-01")));
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn groups_exports_by_download_and_orders_by_timestamp() {
+        let modified = at("2024-03-01 09:00");
+        let files = [
+            "Zoo as of 2024-01-20 1021 AM - Register.csv",
+            "Zoo as of 2024-01-20 1021 AM - Plan.csv",
+            // Sorts before "Zoo" by name but is the newer export
+            "Apartment as of 2024-02-02 0315 PM - Register.csv",
+            "Apartment as of 2024-02-02 0315 PM - Plan.csv",
+            "Apartment as of 2023-12-31 1159 PM - Register.csv",
+            "notes.txt",
+        ]
+        .into_iter()
+        .map(|name| (PathBuf::from("/exports").join(name), modified));
+
+        let exports = group_exports(files);
+        assert_eq!(exports.len(), 3);
+
+        assert_eq!(exports[0].budget_name, "Apartment");
+        assert_eq!(exports[0].exported_at, at("2024-02-02 15:15"));
+        assert_eq!(
+            exports[0].register.as_deref(),
+            Some(Path::new(
+                "/exports/Apartment as of 2024-02-02 0315 PM - Register.csv"
+            ))
+        );
+        assert_eq!(
+            exports[0].plan.as_deref(),
+            Some(Path::new(
+                "/exports/Apartment as of 2024-02-02 0315 PM - Plan.csv"
+            ))
+        );
+
+        assert_eq!(exports[1].budget_name, "Zoo");
+        assert_eq!(exports[1].exported_at, at("2024-01-20 10:21"));
+
+        // The older export's register is never paired with the newer plan
+        assert_eq!(exports[2].exported_at, at("2023-12-31 23:59"));
+        assert_eq!(exports[2].plan, None);
+    }
+
+    #[test]
+    fn unstamped_exports_fall_back_to_mtime() {
+        let files = [
+            (PathBuf::from("Old - Register.csv"), at("2024-01-01 08:00")),
+            (PathBuf::from("Old - Budget.csv"), at("2024-01-01 08:05")),
+            (PathBuf::from("New - Register.csv"), at("2024-02-01 08:00")),
+        ];
+
+        let exports = group_exports(files);
+        assert_eq!(exports[0].budget_name, "New");
+        assert_eq!(exports[1].budget_name, "Old");
+        assert_eq!(exports[1].exported_at, at("2024-01-01 08:05"));
+        assert!(exports[1].plan.is_some());
+    }
+
+    #[test]
+    fn picks_the_open_budgets_export_over_newer_ones() {
+        let export = |name: &str, when: &str| ExportFiles {
+            budget_name: name.to_string(),
+            exported_at: at(when),
+            register: None,
+            plan: None,
+        };
+        let exports = vec![
+            export("Other", "2024-03-01 10:00"),
+            export("Household", "2024-02-01 10:00"),
+            export("Household", "2024-01-01 10:00"),
+        ];
+
+        let picked = pick_export(exports.clone(), Some("household")).unwrap();
+        assert_eq!(picked.exported_at, at("2024-02-01 10:00"));
+
+        // Nothing from the open budget: offer the newest, flagged by the caller
+        let picked = pick_export(exports, Some("Vacation")).unwrap();
+        assert_eq!(picked.budget_name, "Other");
+
+        assert_eq!(pick_export(Vec::new(), Some("Household")), None);
+    }
+}
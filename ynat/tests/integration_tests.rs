@@ -1,4 +1,4 @@
-use ynat::events::DataEvent;
+use ynat::events::BudgetEvent;
 use ynat::input::Key;
 use ynat::state::InputMode;
 use ynat::testing::TestApp;
@@ -115,41 +115,44 @@ fn test_navigation_with_j_k() {
     let mut app = TestApp::new();
 
     // Inject some budget data
-    app.send_data_event(DataEvent::BudgetsCacheLoaded {
-        budgets: vec![
-            ynab_api::endpoints::budgets::BudgetSummary {
-                id: test_uuid("budget1").into(),
-                name: "Budget 1".to_string(),
-                last_modified_on: None,
-                first_month: None,
-                last_month: None,
-                date_format: None,
-                currency_format: None,
-                accounts: None,
-            },
-            ynab_api::endpoints::budgets::BudgetSummary {
-                id: test_uuid("budget2").into(),
-                name: "Budget 2".to_string(),
-                last_modified_on: None,
-                first_month: None,
-                last_month: None,
-                date_format: None,
-                currency_format: None,
-                accounts: None,
-            },
-            ynab_api::endpoints::budgets::BudgetSummary {
-                id: test_uuid("budget3").into(),
-                name: "Budget 3".to_string(),
-                last_modified_on: None,
-                first_month: None,
-                last_month: None,
-                date_format: None,
-                currency_format: None,
-                accounts: None,
-            },
-        ],
-        default_budget: None,
-    });
+    app.send_data_event(
+        BudgetEvent::BudgetsCacheLoaded {
+            budgets: vec![
+                ynab_api::endpoints::budgets::BudgetSummary {
+                    id: test_uuid("budget1").into(),
+                    name: "Budget 1".to_string(),
+                    last_modified_on: None,
+                    first_month: None,
+                    last_month: None,
+                    date_format: None,
+                    currency_format: None,
+                    accounts: None,
+                },
+                ynab_api::endpoints::budgets::BudgetSummary {
+                    id: test_uuid("budget2").into(),
+                    name: "Budget 2".to_string(),
+                    last_modified_on: None,
+                    first_month: None,
+                    last_month: None,
+                    date_format: None,
+                    currency_format: None,
+                    accounts: None,
+                },
+                ynab_api::endpoints::budgets::BudgetSummary {
+                    id: test_uuid("budget3").into(),
+                    name: "Budget 3".to_string(),
+                    last_modified_on: None,
+                    first_month: None,
+                    last_month: None,
+                    date_format: None,
+                    currency_format: None,
+                    accounts: None,
+                },
+            ],
+            default_budget: None,
+        }
+        .into(),
+    );
 
     if let Screen::Budgets(budgets_state) = app.state().current_screen() {
         assert_eq!(budgets_state.selected_budget_index, 0);
@@ -181,39 +184,46 @@ fn test_filter_mode_entry_and_typing() {
     let mut app = TestApp::new();
 
     // Inject budget first
-    app.send_data_event(DataEvent::BudgetsCacheLoaded {
-        budgets: vec![ynab_api::endpoints::budgets::BudgetSummary {
-            id: test_uuid("budget1").into(),
-            name: "Test Budget".to_string(),
-            last_modified_on: None,
-            first_month: None,
-            last_month: None,
-            date_format: None,
-            currency_format: None,
-            accounts: None,
-        }],
-        default_budget: None,
-    });
+    app.send_data_event(
+        BudgetEvent::BudgetsCacheLoaded {
+            budgets: vec![ynab_api::endpoints::budgets::BudgetSummary {
+                id: test_uuid("budget1").into(),
+                name: "Test Budget".to_string(),
+                last_modified_on: None,
+                first_month: None,
+                last_month: None,
+                date_format: None,
+                currency_format: None,
+                accounts: None,
+            }],
+            default_budget: None,
+        }
+        .into(),
+    );
 
     // Navigate to accounts (need to trigger LoadAccounts via 'gb' won't work in sync mode)
     // Instead, manually inject AccountsCacheLoaded event to simulate being on accounts screen
-    app.send_data_event(DataEvent::AccountsCacheLoaded {
-        accounts: vec![Account {
-            id: uuid::Uuid::new_v4(),
-            name: "Checking Account".to_string(),
-            account_type: AccountType::Checking,
-            on_budget: true,
-            closed: false,
-            note: None,
-            balance: 100000.into(),
-            cleared_balance: 50000.into(),
-            uncleared_balance: 50000.into(),
-            transfer_payee_id: None,
-            direct_import_linked: false,
-            direct_import_in_error: false,
-            deleted: false,
-        }],
-    });
+    app.send_data_event(
+        BudgetEvent::AccountsCacheLoaded {
+            accounts: vec![Account {
+                id: uuid::Uuid::new_v4(),
+                name: "Checking Account".to_string(),
+                account_type: AccountType::Checking,
+                on_budget: true,
+                closed: false,
+                note: None,
+                balance: 100000.into(),
+                cleared_balance: 50000.into(),
+                uncleared_balance: 50000.into(),
+                transfer_payee_id: None,
+                direct_import_linked: false,
+                direct_import_in_error: false,
+                last_reconciled_at: None,
+                deleted: false,
+            }],
+        }
+        .into(),
+    );
 
     // Verify we're on accounts screen
     if let Screen::Accounts(accounts_state) = app.state().current_screen() {
@@ -269,41 +279,44 @@ fn test_capital_g_navigates_to_bottom() {
     let mut app = TestApp::new();
 
     // Inject budget data
-    app.send_data_event(DataEvent::BudgetsCacheLoaded {
-        budgets: vec![
-            ynab_api::endpoints::budgets::BudgetSummary {
-                id: test_uuid("budget1").into(),
-                name: "Budget 1".to_string(),
-                last_modified_on: None,
-                first_month: None,
-                last_month: None,
-                date_format: None,
-                currency_format: None,
-                accounts: None,
-            },
-            ynab_api::endpoints::budgets::BudgetSummary {
-                id: test_uuid("budget2").into(),
-                name: "Budget 2".to_string(),
-                last_modified_on: None,
-                first_month: None,
-                last_month: None,
-                date_format: None,
-                currency_format: None,
-                accounts: None,
-            },
-            ynab_api::endpoints::budgets::BudgetSummary {
-                id: test_uuid("budget3").into(),
-                name: "Budget 3".to_string(),
-                last_modified_on: None,
-                first_month: None,
-                last_month: None,
-                date_format: None,
-                currency_format: None,
-                accounts: None,
-            },
-        ],
-        default_budget: None,
-    });
+    app.send_data_event(
+        BudgetEvent::BudgetsCacheLoaded {
+            budgets: vec![
+                ynab_api::endpoints::budgets::BudgetSummary {
+                    id: test_uuid("budget1").into(),
+                    name: "Budget 1".to_string(),
+                    last_modified_on: None,
+                    first_month: None,
+                    last_month: None,
+                    date_format: None,
+                    currency_format: None,
+                    accounts: None,
+                },
+                ynab_api::endpoints::budgets::BudgetSummary {
+                    id: test_uuid("budget2").into(),
+                    name: "Budget 2".to_string(),
+                    last_modified_on: None,
+                    first_month: None,
+                    last_month: None,
+                    date_format: None,
+                    currency_format: None,
+                    accounts: None,
+                },
+                ynab_api::endpoints::budgets::BudgetSummary {
+                    id: test_uuid("budget3").into(),
+                    name: "Budget 3".to_string(),
+                    last_modified_on: None,
+                    first_month: None,
+                    last_month: None,
+                    date_format: None,
+                    currency_format: None,
+                    accounts: None,
+                },
+            ],
+            default_budget: None,
+        }
+        .into(),
+    );
 
     // Press 'G' (capital G / Shift+g) to navigate to bottom
     app.send_key(Key::Char('G'));
@@ -344,3 +357,237 @@ fn test_pending_key_cleared_after_invalid_sequence() {
     // Pending key should be cleared
     assert_eq!(app.state().pending_key, None);
 }
+
+/// Client for a server that isn't there, so no request can succeed
+fn unreachable_client() -> ynab_api::Client {
+    ynab_api::Client::with_base_url("http://127.0.0.1:9/v1", "test")
+}
+
+fn test_budget() -> ynab_api::endpoints::budgets::BudgetSummary {
+    ynab_api::endpoints::budgets::BudgetSummary {
+        id: test_uuid("budget1").into(),
+        name: "Test Budget".to_string(),
+        last_modified_on: None,
+        first_month: None,
+        last_month: None,
+        date_format: None,
+        currency_format: None,
+        accounts: None,
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limited_load_shows_loading_then_error() {
+    use std::time::Duration;
+    use ynat::error::ErrorKind;
+    use ynat::state::LoadingState;
+    use ynat::testing::{Faults, FaultyDataHandler};
+
+    let faults = Faults {
+        delay: Duration::from_millis(50),
+        ..Faults::rate_limited()
+    };
+    let handler = FaultyDataHandler::new(unreachable_client(), faults)
+        .await
+        .unwrap();
+    let mut app = TestApp::with_faults(handler);
+
+    app.send_keys(&[Key::Char('g'), Key::Char('b')]);
+    let Screen::Budgets(budgets) = app.state().current_screen() else {
+        panic!("Expected the budgets screen");
+    };
+    assert!(matches!(budgets.budgets_loading, LoadingState::Loading(..)));
+
+    app.settle().await;
+    let Screen::Budgets(budgets) = app.state().current_screen() else {
+        panic!("Expected the budgets screen");
+    };
+    assert!(
+        matches!(&budgets.budgets_loading, LoadingState::Error(e) if e.kind == ErrorKind::RateLimited),
+        "{:?}",
+        budgets.budgets_loading
+    );
+}
+
+/// An app that can't reach YNAB, on the transactions screen of a budget
+/// filled in from the "cache" with one uncleared transaction
+async fn offline_app_with_transaction() -> TestApp<ynat::testing::FaultyDataHandler> {
+    use ynab_api::endpoints::transactions::Transaction;
+    use ynat::events::TransactionEvent;
+    use ynat::testing::{Faults, FaultyDataHandler};
+
+    let handler = FaultyDataHandler::new(unreachable_client(), Faults::offline())
+        .await
+        .unwrap();
+    let mut app = TestApp::with_faults(handler);
+
+    // Open the budget's only account; the loads fail, so fill in from the "cache"
+    app.send_keys(&[Key::Char('g'), Key::Char('b')]);
+    app.settle().await;
+    app.send_data_event(
+        BudgetEvent::BudgetsCacheLoaded {
+            budgets: vec![test_budget()],
+            default_budget: None,
+        }
+        .into(),
+    );
+    app.send_key(Key::Enter);
+    app.settle().await;
+    let account: ynab_api::endpoints::accounts::Account =
+        serde_json::from_value(serde_json::json!({
+            "id": test_uuid("checking"),
+            "name": "Checking",
+            "type": "checking",
+            "on_budget": true,
+            "closed": false,
+            "note": null,
+            "balance": 0,
+            "cleared_balance": 0,
+            "uncleared_balance": 0,
+            "transfer_payee_id": null,
+            "direct_import_linked": false,
+            "direct_import_in_error": false,
+            "deleted": false
+        }))
+        .unwrap();
+    app.send_data_event(
+        BudgetEvent::AccountsCacheLoaded {
+            accounts: vec![account],
+        }
+        .into(),
+    );
+    app.send_key(Key::Enter);
+    app.settle().await;
+    let transaction: Transaction = serde_json::from_value(serde_json::json!({
+        "id": test_uuid("coffee"),
+        "date": "2025-03-07",
+        "amount": -4500,
+        "memo": null,
+        "cleared": "uncleared",
+        "approved": true,
+        "flag_color": null,
+        "account_id": test_uuid("checking"),
+        "payee_id": null,
+        "category_id": null,
+        "transfer_account_id": null,
+        "transfer_transaction_id": null,
+        "matched_transaction_id": null,
+        "import_id": null,
+        "deleted": false,
+        "account_name": "Checking",
+        "payee_name": "Cafe",
+        "category_name": null,
+        "subtransactions": []
+    }))
+    .unwrap();
+    app.send_data_event(
+        TransactionEvent::TransactionsCacheLoaded {
+            transactions: vec![transaction],
+            loaded_since: None,
+        }
+        .into(),
+    );
+    app
+}
+
+#[tokio::test]
+async fn test_failed_cleared_toggle_is_rolled_back() {
+    use ynab_api::endpoints::transactions::ReconciliationStatus;
+    use ynat::testing::FaultyDataHandler;
+
+    let mut app = offline_app_with_transaction().await;
+
+    let cleared = |app: &TestApp<FaultyDataHandler>| match app.state().current_screen() {
+        Screen::Transactions(state) => state.transactions[0].cleared,
+        other => panic!("Expected the transactions screen, got {:?}", other),
+    };
+
+    // Cleared straight away, then put back once the update fails
+    app.send_key(Key::Char('c'));
+    assert_eq!(cleared(&app), ReconciliationStatus::Cleared);
+    app.settle().await;
+    assert_eq!(cleared(&app), ReconciliationStatus::Uncleared);
+}
+
+#[tokio::test]
+async fn test_stuck_load_times_out_to_an_error() {
+    use std::time::Duration;
+    use ynat::error::ErrorKind;
+    use ynat::state::LoadingState;
+    use ynat::testing::{Faults, FaultyDataHandler};
+
+    let faults = Faults {
+        delay: Duration::from_secs(60),
+        ..Default::default()
+    };
+    let handler = FaultyDataHandler::new(unreachable_client(), faults)
+        .await
+        .unwrap()
+        .with_timeout(Duration::from_millis(50));
+    let mut app = TestApp::with_faults(handler);
+
+    app.send_keys(&[Key::Char('g'), Key::Char('b')]);
+    app.settle().await;
+    let Screen::Budgets(budgets) = app.state().current_screen() else {
+        panic!("Expected the budgets screen");
+    };
+    assert!(
+        matches!(&budgets.budgets_loading, LoadingState::Error(e) if e.kind == ErrorKind::Network),
+        "{:?}",
+        budgets.budgets_loading
+    );
+}
+
+#[tokio::test]
+async fn test_offline_delete_is_kept_to_send_later() {
+    let mut app = offline_app_with_transaction().await;
+
+    app.send_keys(&[Key::Char('d'), Key::Char('y')]);
+    app.settle().await;
+
+    let Screen::Transactions(transactions) = app.state().current_screen() else {
+        panic!("Expected the transactions screen");
+    };
+    assert!(transactions.transactions.is_empty());
+    assert_eq!(app.state().pending_writes, 1);
+}
+
+#[tokio::test]
+async fn test_failed_auto_refresh_keeps_the_screen_as_is() {
+    use ynab_api::endpoints::months::MonthDetail;
+    use ynat::events::PlanEvent;
+    use ynat::state::LoadingState;
+
+    let mut app = offline_app_with_transaction().await;
+    app.send_keys(&[Key::Char('g'), Key::Char('p')]);
+    app.settle().await;
+    let month: MonthDetail = serde_json::from_value(serde_json::json!({
+        "month": "2025-03-01",
+        "note": null,
+        "income": 0,
+        "budgeted": 0,
+        "activity": 0,
+        "to_be_budgeted": 0,
+        "age_of_money": null,
+        "deleted": false,
+        "categories": []
+    }))
+    .unwrap();
+    app.send_data_event(
+        PlanEvent::PlanCacheLoaded {
+            month,
+            categories: vec![],
+        }
+        .into(),
+    );
+
+    app.check_for_changes();
+    app.settle().await;
+
+    let Screen::Plan(plan) = app.state().current_screen() else {
+        panic!("Expected the plan screen");
+    };
+    assert!(matches!(plan.plan_loading, LoadingState::Loaded));
+    assert_eq!(plan.month.as_ref().unwrap().month, "2025-03-01");
+    assert!(app.state().current_error().is_none());
+}